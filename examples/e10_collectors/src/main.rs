@@ -134,33 +134,19 @@ async fn challenge(ctx: &Context, msg: &Message, _: Args) -> CommandResult {
     let _ = msg.reply(ctx, "Write 5 messages in 10 seconds").await;
 
     // We can create a collector from scratch too using this builder future.
-    let collector = MessageCollectorBuilder::new(&ctx)
+    // Awaiting `collect_n` builds the collector and waits until 5 messages
+    // were collected or the timeout ran out, whichever comes first.
+    let collected = MessageCollectorBuilder::new(&ctx)
     // Only collect messages by this user.
         .author_id(msg.author.id)
         .channel_id(msg.channel_id)
-        .collect_limit(5u32)
         .timeout(Duration::from_secs(10))
-    // Build the collector.
+        .collect_n(5)
         .await;
 
-    // Let's acquire borrow HTTP to send a message inside the `async move`.
-    let http = &ctx.http;
-
-    // We want to process each message and get the length.
-    // There are a couple of ways to do this. Folding the stream with `fold`
-    // is one way.
-    // Using `then` to first reply and then create a new stream with all
-    // messages is another way to do it, which can be nice if you want
-    // to further process the messages.
-    // If you don't want to collect the stream, `for_each` may be sufficient.
-    let collected: Vec<_> = collector
-        .then(|msg| async move {
-            let _ = msg.reply(http, format!("I repeat: {}", msg.content)).await;
-
-            msg
-        })
-        .collect()
-        .await;
+    for collected_msg in &collected {
+        let _ = collected_msg.reply(ctx, format!("I repeat: {}", collected_msg.content)).await;
+    }
 
     if collected.len() >= 5 {
         score += 1;
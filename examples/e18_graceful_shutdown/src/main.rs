@@ -0,0 +1,80 @@
+//! This is an example showing how to run the client as part of a larger
+//! service, which shuts down all of its components together once Ctrl+C is
+//! pressed.
+//!
+//! The client is given a shutdown signal when it's built. Once the signal
+//! resolves, the client shuts its shards down and waits for the event handlers
+//! which are still running, such as the one for `!slow` below, before `start`
+//! returns.
+use std::{env, time::Duration};
+
+use serenity::{
+    async_trait,
+    model::{channel::Message, gateway::Ready},
+    prelude::*,
+};
+use tokio::{signal, sync::watch, time::sleep};
+
+struct Handler;
+
+#[async_trait]
+impl EventHandler for Handler {
+    async fn message(&self, ctx: Context, msg: Message) {
+        if msg.content == "!slow" {
+            // Pressing Ctrl+C while this handler sleeps still sends the reply,
+            // as handlers are awaited for up to the shutdown timeout.
+            sleep(Duration::from_secs(5)).await;
+
+            if let Err(why) = msg.channel_id.say(&ctx.http, "Done!").await {
+                println!("Error sending message: {:?}", why);
+            }
+        }
+    }
+
+    async fn ready(&self, _: Context, ready: Ready) {
+        println!("{} is connected!", ready.user.name);
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let token = env::var("DISCORD_TOKEN").expect("Expected a token in the environment");
+
+    // The rest of the service is told to shut down through this channel.
+    let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+
+    // Another component of the service, which runs until the shutdown.
+    let worker = tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.changed() => break,
+                _ = sleep(Duration::from_secs(30)) => println!("The worker is still running"),
+            }
+        }
+
+        println!("The worker has stopped");
+    });
+
+    let mut client = Client::builder(&token)
+        .event_handler(Handler)
+        // Resolves once Ctrl+C is pressed, which would otherwise terminate the
+        // process right away.
+        .shutdown_signal(async move {
+            signal::ctrl_c().await.expect("Could not register the Ctrl+C handler");
+            println!("Shutting down");
+
+            let _ = shutdown_tx.send(true);
+        })
+        .shutdown_timeout(Duration::from_secs(10))
+        .await
+        .expect("Err creating client");
+
+    // Returns `Ok` once the client was shut down by the signal.
+    if let Err(why) = client.start().await {
+        println!("Client error: {:?}", why);
+    }
+
+    worker.await.expect("The worker panicked");
+
+    println!("The client has stopped");
+}
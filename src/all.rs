@@ -0,0 +1,111 @@
+//! A curated set of re-exports covering most of what a bot needs in scope.
+//!
+//! Where the [`prelude`] only contains the most common client and error types,
+//! this module additionally re-exports all builders, collectors, model types
+//! and Ids, the [`EventHandler`], [`GatewayIntents`] and the error types of
+//! every module. Items belonging to a disabled feature are left out, so the
+//! module can be glob-imported under any combination of features.
+//!
+//! Types which would otherwise share a name are re-exported under a
+//! distinct one: the builders' [`Timestamp`] is available as
+//! [`EmbedTimestamp`], and all error enums are exported as `<Module>Error`,
+//! e.g. [`HttpError`] and [`CollectorError`].
+//!
+//! # Examples
+//!
+//! Import everything:
+//!
+//! ```rust
+//! use serenity::all::*;
+//! ```
+//!
+//! [`prelude`]: crate::prelude
+//! [`EventHandler`]: crate::client::EventHandler
+//! [`GatewayIntents`]: crate::client::bridge::gateway::GatewayIntents
+//! [`Timestamp`]: crate::builder::Timestamp
+//! [`EmbedTimestamp`]: crate::builder::Timestamp
+//! [`HttpError`]: crate::http::HttpError
+//! [`CollectorError`]: crate::collector::CollectorError
+
+#[cfg(feature = "builder")]
+pub use crate::builder::Timestamp as EmbedTimestamp;
+#[cfg(feature = "builder")]
+pub use crate::builder::{
+    AddMember,
+    CreateAllowedMentions,
+    CreateBotAuthParameters,
+    CreateChannel,
+    CreateEmbed,
+    CreateEmbedAuthor,
+    CreateEmbedFooter,
+    CreateInvite,
+    CreateMessage,
+    CreateStageInstance,
+    CreateThread,
+    EditChannel,
+    EditGuild,
+    EditGuildWelcomeScreen,
+    EditGuildWidget,
+    EditMember,
+    EditMessage,
+    EditProfile,
+    EditRole,
+    EditStageInstance,
+    EditThread,
+    EditVoiceState,
+    EditWebhookMessage,
+    ExecuteWebhook,
+    GetMessages,
+    ParseValue,
+    SplitStrategy,
+};
+#[cfg(all(feature = "builder", feature = "unstable_discord_api"))]
+pub use crate::builder::{
+    CreateActionRow,
+    CreateApplicationCommand,
+    CreateApplicationCommandOption,
+    CreateApplicationCommandPermissionData,
+    CreateApplicationCommandPermissions,
+    CreateApplicationCommandPermissionsData,
+    CreateApplicationCommands,
+    CreateApplicationCommandsPermissions,
+    CreateAutocompleteResponse,
+    CreateButton,
+    CreateComponents,
+    CreateInputText,
+    CreateInteractionResponse,
+    CreateInteractionResponseData,
+    CreateInteractionResponseFollowup,
+    CreateSelectMenu,
+    CreateSelectMenuOption,
+    CreateSelectMenuOptions,
+    EditInteractionResponse,
+};
+#[cfg(feature = "cache")]
+pub use crate::cache::{Cache, Settings as CacheSettings};
+#[cfg(feature = "gateway")]
+pub use crate::client::bridge::gateway::{
+    ChunkGuildFilter,
+    EventTypeFilter,
+    GatewayIntents,
+    ParseIntentsError,
+    ShardManager,
+    ShardMessenger,
+};
+#[cfg(feature = "client")]
+pub use crate::client::Context;
+#[cfg(feature = "gateway")]
+pub use crate::client::{Client, ClientBuilder, EventHandler, RawEventHandler};
+#[cfg(feature = "collector")]
+pub use crate::collector::*;
+#[cfg(feature = "framework")]
+pub use crate::framework::Framework;
+#[cfg(feature = "standard_framework")]
+pub use crate::framework::StandardFramework;
+#[cfg(feature = "gateway")]
+pub use crate::gateway::Shard;
+#[cfg(feature = "http")]
+pub use crate::http::{CacheHttp, Http};
+pub use crate::model::prelude::*;
+pub use crate::prelude::*;
+pub use crate::Result;
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use serde_json::{json, Value};
 
@@ -7,7 +7,10 @@ use crate::model::channel::ChannelType;
 use crate::model::interactions::application_command::{
     ApplicationCommandOptionType,
     ApplicationCommandType,
+    ApplicationCommandViolation,
 };
+use crate::model::interactions::{InstallationContext, InteractionContext};
+use crate::model::misc::Locale;
 use crate::utils;
 
 /// A builder for creating a new [`ApplicationCommandOption`].
@@ -188,6 +191,21 @@ impl CreateApplicationCommandOption {
     }
 }
 
+/// Inserts a localized value into the localization map under the given key,
+/// creating the map if needed.
+fn insert_localized(
+    map: &mut HashMap<&'static str, Value>,
+    key: &'static str,
+    locale: Locale,
+    value: String,
+) {
+    let localizations = map.entry(key).or_insert_with(|| Value::Object(JsonMap::new()));
+
+    if let Value::Object(localizations) = localizations {
+        localizations.insert(locale.as_str().to_string(), Value::String(value));
+    }
+}
+
 /// A builder for creating a new [`ApplicationCommand`].
 ///
 /// [`Self::name`] and [`Self::description`] are required fields.
@@ -205,6 +223,22 @@ impl CreateApplicationCommand {
         self
     }
 
+    /// Specifies a localized name of the application command, shown to users
+    /// of the given locale.
+    ///
+    /// The locale can be given as a [`Locale`] or as its code, such as
+    /// `"de"`.
+    ///
+    /// **Note**: The same requirements as for [`Self::name`] apply.
+    pub fn name_localized<L: Into<Locale>, D: ToString>(
+        &mut self,
+        locale: L,
+        name: D,
+    ) -> &mut Self {
+        insert_localized(&mut self.0, "name_localizations", locale.into(), name.to_string());
+        self
+    }
+
     /// Specifies the type of the application command.
     pub fn kind(&mut self, kind: ApplicationCommandType) -> &mut Self {
         self.0.insert("type", Value::Number(Number::from(kind as u8)));
@@ -221,6 +255,48 @@ impl CreateApplicationCommand {
         self
     }
 
+    /// Specifies the installation contexts the command is available in, e.g.
+    /// to make it usable by users who installed the application to their
+    /// account.
+    ///
+    /// Defaults to the installation contexts of the application.
+    ///
+    /// **Note**: Only applies to global commands.
+    pub fn integration_types(&mut self, integration_types: Vec<InstallationContext>) -> &mut Self {
+        self.0.insert(
+            "integration_types",
+            Value::Array(
+                integration_types
+                    .into_iter()
+                    .map(|i| Value::Number(Number::from(i as u8)))
+                    .collect(),
+            ),
+        );
+
+        self
+    }
+
+    /// Specifies the interaction contexts the command can be invoked from.
+    ///
+    /// Every context must be supported by one of the
+    /// [`Self::integration_types`], which is checked when creating the
+    /// command. Refer to [`InteractionContext::required_installation`] for
+    /// which combinations are valid.
+    ///
+    /// Defaults to all contexts.
+    ///
+    /// **Note**: Only applies to global commands.
+    pub fn contexts(&mut self, contexts: Vec<InteractionContext>) -> &mut Self {
+        self.0.insert(
+            "contexts",
+            Value::Array(
+                contexts.into_iter().map(|i| Value::Number(Number::from(i as u8))).collect(),
+            ),
+        );
+
+        self
+    }
+
     /// Specifies the description of the application command.
     ///
     /// **Note**: Must be between 1 and 100 characters long.
@@ -229,6 +305,20 @@ impl CreateApplicationCommand {
         self
     }
 
+    /// Specifies a localized description of the application command, shown to
+    /// users of the given locale.
+    ///
+    /// **Note**: The same requirements as for [`Self::description`] apply.
+    pub fn description_localized<L: Into<Locale>, D: ToString>(
+        &mut self,
+        locale: L,
+        description: D,
+    ) -> &mut Self {
+        let description = description.to_string();
+        insert_localized(&mut self.0, "description_localizations", locale.into(), description);
+        self
+    }
+
     /// Creates an application command option for the application command.
     ///
     /// **Note**: Application commands can have up to 25 options.
@@ -264,6 +354,140 @@ impl CreateApplicationCommand {
         self.0.insert("options", Value::Array(new_options));
         self
     }
+
+    /// Checks the application command for mistakes which Discord would reject
+    /// when registering it, returning every violation which was found.
+    ///
+    /// The following is checked:
+    /// - no option has both choices and autocomplete set;
+    /// - required options come before optional ones;
+    /// - names are made of 1 to 32 lowercase letters, numbers, `-` or `_`,
+    ///   except for the names of user and message commands;
+    /// - option names are unique among their siblings;
+    /// - sub-command groups only contain sub-commands, and sub-commands only
+    ///   contain options which are neither;
+    /// - no command or option has more than 25 options.
+    ///
+    /// This is done automatically when registering the command.
+    ///
+    /// # Errors
+    ///
+    /// Returns every [`ApplicationCommandViolation`] which was found, if any.
+    pub fn build_check(&self) -> StdResult<(), Vec<ApplicationCommandViolation>> {
+        let violations = command_violations(|key| self.0.get(key), false);
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+/// The maximum number of options of a command or option.
+const MAX_OPTIONS: usize = 25;
+
+/// Finds every violation of a serialized application command, with `get`
+/// returning the value of a field.
+///
+/// An `edit` only holds the fields to change, so a missing name is not a
+/// violation.
+pub(crate) fn command_violations<'a>(
+    get: impl Fn(&str) -> Option<&'a Value>,
+    edit: bool,
+) -> Vec<ApplicationCommandViolation> {
+    let mut violations = Vec::new();
+
+    let name = get("name").and_then(Value::as_str);
+    let is_chat_input = get("type")
+        .and_then(Value::as_u64)
+        .map_or(true, |kind| kind == ApplicationCommandType::ChatInput as u64);
+    let name_missing = edit && name.is_none();
+    let name = name.unwrap_or_default();
+
+    if is_chat_input && !name_missing && !is_valid_name(name) {
+        violations.push(ApplicationCommandViolation::InvalidName(name.to_string()));
+    }
+
+    if let Some(options) = get("options").and_then(Value::as_array) {
+        check_options(name, None, options, &mut violations);
+    }
+
+    violations
+}
+
+fn check_options(
+    path: &str,
+    parent_kind: Option<u64>,
+    options: &[Value],
+    violations: &mut Vec<ApplicationCommandViolation>,
+) {
+    const SUB_COMMAND: u64 = ApplicationCommandOptionType::SubCommand as u64;
+    const SUB_COMMAND_GROUP: u64 = ApplicationCommandOptionType::SubCommandGroup as u64;
+
+    if options.len() > MAX_OPTIONS {
+        violations.push(ApplicationCommandViolation::TooManyOptions(path.to_string()));
+    }
+
+    let mut names = HashSet::new();
+    let mut optional_seen = false;
+
+    for option in options.iter().filter_map(Value::as_object) {
+        let name = option.get("name").and_then(Value::as_str).unwrap_or_default();
+        let option_path = format!("{} {}", path, name);
+        let kind = option.get("type").and_then(Value::as_u64);
+        let is_sub_command = matches!(kind, Some(SUB_COMMAND | SUB_COMMAND_GROUP));
+
+        if !is_valid_name(name) {
+            violations.push(ApplicationCommandViolation::InvalidName(option_path.clone()));
+        }
+
+        if !names.insert(name) {
+            violations.push(ApplicationCommandViolation::DuplicateOptionName(option_path.clone()));
+        }
+
+        let valid_nesting = match parent_kind {
+            None => true,
+            Some(SUB_COMMAND_GROUP) => kind == Some(SUB_COMMAND),
+            Some(SUB_COMMAND) => !is_sub_command,
+            Some(_) => false,
+        };
+
+        if !valid_nesting {
+            violations.push(ApplicationCommandViolation::InvalidNesting(option_path.clone()));
+        }
+
+        if !is_sub_command {
+            let required = option.get("required").and_then(Value::as_bool).unwrap_or(false);
+
+            if required && optional_seen {
+                violations
+                    .push(ApplicationCommandViolation::RequiredAfterOptional(option_path.clone()));
+            }
+
+            optional_seen |= !required;
+        }
+
+        let has_choices =
+            option.get("choices").and_then(Value::as_array).map_or(false, |c| !c.is_empty());
+        let autocomplete = option.get("autocomplete").and_then(Value::as_bool).unwrap_or(false);
+
+        if has_choices && autocomplete {
+            violations
+                .push(ApplicationCommandViolation::ChoicesWithAutocomplete(option_path.clone()));
+        }
+
+        if let Some(sub_options) = option.get("options").and_then(Value::as_array) {
+            check_options(&option_path, kind, sub_options, violations);
+        }
+    }
+}
+
+/// Whether the name is made of 1 to 32 lowercase letters, numbers, `-` or `_`,
+/// as required for chat input commands and options.
+fn is_valid_name(name: &str) -> bool {
+    (1..=32).contains(&name.chars().count())
+        && name.chars().all(|c| (c == '-' || c == '_' || c.is_alphanumeric()) && !c.is_uppercase())
 }
 
 #[derive(Clone, Debug, Default)]
@@ -309,3 +533,121 @@ impl CreateApplicationCommands {
         self
     }
 }
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{CreateApplicationCommand, CreateApplicationCommandOption};
+    use crate::model::interactions::application_command::{
+        ApplicationCommandOptionType,
+        ApplicationCommandType,
+        ApplicationCommandViolation,
+    };
+    use crate::model::interactions::{InstallationContext, InteractionContext};
+    use crate::model::misc::Locale;
+
+    #[test]
+    fn test_localizations() {
+        let mut command = CreateApplicationCommand::default();
+        command
+            .name("ping")
+            .name_localized(Locale::German, "pingen")
+            .name_localized("pt-BR", "pingar")
+            .description("Pings the bot")
+            .description_localized(Locale::German, "Pingt den Bot");
+
+        assert_eq!(command.0["name_localizations"], json!({"de": "pingen", "pt-BR": "pingar"}));
+        assert_eq!(command.0["description_localizations"], json!({"de": "Pingt den Bot"}));
+    }
+
+    #[test]
+    fn test_installation_contexts() {
+        let mut command = CreateApplicationCommand::default();
+        command
+            .integration_types(vec![InstallationContext::Guild, InstallationContext::User])
+            .contexts(vec![InteractionContext::BotDm, InteractionContext::PrivateChannel]);
+
+        assert_eq!(command.0["integration_types"], json!([0, 1]));
+        assert_eq!(command.0["contexts"], json!([1, 2]));
+    }
+
+    #[test]
+    fn test_build_check() {
+        let mut command = CreateApplicationCommand::default();
+        command
+            .name("config")
+            .create_option(|o| {
+                o.kind(ApplicationCommandOptionType::SubCommand)
+                    .name("set")
+                    .create_sub_option(|o| {
+                        o.kind(ApplicationCommandOptionType::String).name("key").required(true)
+                    })
+                    .create_sub_option(|o| {
+                        o.kind(ApplicationCommandOptionType::String)
+                            .name("value")
+                            .set_autocomplete(true)
+                    })
+            })
+            .create_option(|o| o.kind(ApplicationCommandOptionType::SubCommand).name("reset"));
+        assert_eq!(command.build_check(), Ok(()));
+
+        let mut command = CreateApplicationCommand::default();
+        command.kind(ApplicationCommandType::User).name("Show Profile");
+        assert_eq!(command.build_check(), Ok(()));
+    }
+
+    #[test]
+    fn test_build_check_violations() {
+        use ApplicationCommandViolation::*;
+
+        let mut command = CreateApplicationCommand::default();
+        command
+            .name("Config")
+            .create_option(|o| {
+                o.kind(ApplicationCommandOptionType::String)
+                    .name("key")
+                    .add_string_choice("Prefix", "prefix")
+                    .set_autocomplete(true)
+            })
+            .create_option(|o| {
+                o.kind(ApplicationCommandOptionType::String).name("value").required(true)
+            })
+            .create_option(|o| o.kind(ApplicationCommandOptionType::Boolean).name("key"))
+            .create_option(|o| {
+                o.kind(ApplicationCommandOptionType::SubCommandGroup)
+                    .name("group")
+                    .create_sub_option(|o| o.kind(ApplicationCommandOptionType::Integer).name("n"))
+                    .create_sub_option(|o| {
+                        o.kind(ApplicationCommandOptionType::SubCommand)
+                            .name("sub")
+                            .create_sub_option(|o| {
+                                o.kind(ApplicationCommandOptionType::SubCommand).name("deeper")
+                            })
+                    })
+            })
+            .create_option(|o| o.kind(ApplicationCommandOptionType::String).name("has space"));
+
+        assert_eq!(command.build_check(), Err(vec![
+            InvalidName("Config".to_string()),
+            ChoicesWithAutocomplete("Config key".to_string()),
+            RequiredAfterOptional("Config value".to_string()),
+            DuplicateOptionName("Config key".to_string()),
+            InvalidNesting("Config group n".to_string()),
+            InvalidNesting("Config group sub deeper".to_string()),
+            InvalidName("Config has space".to_string()),
+        ]));
+
+        let options = (0..26)
+            .map(|i| {
+                let mut option = CreateApplicationCommandOption::default();
+                option.kind(ApplicationCommandOptionType::String).name(format!("option-{}", i));
+                option
+            })
+            .collect();
+        let mut command = CreateApplicationCommand::default();
+        command.name("many").set_options(options);
+
+        assert_eq!(command.build_check(), Err(vec![TooManyOptions("many".to_string())]));
+    }
+}
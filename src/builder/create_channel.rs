@@ -24,6 +24,9 @@ impl CreateChannel {
         self
     }
     /// Specify what type the channel is, whether it's a text, voice, category or news channel.
+    ///
+    /// With the `unstable_discord_api` feature, this can also be a forum or media channel,
+    /// which only contain threads.
     pub fn kind(&mut self, kind: ChannelType) -> &mut Self {
         self.0.insert("type", Value::Number(Number::from(kind as u8)));
 
@@ -104,6 +107,38 @@ impl CreateChannel {
         self
     }
 
+    /// Sets the extra features of the channel.
+    ///
+    /// **Note**: [`ChannelFlags::REQUIRE_TAG`] can only be set on forum and media channels,
+    /// [`ChannelFlags::HIDE_MEDIA_DOWNLOAD_OPTIONS`] only on media channels, and
+    /// [`ChannelFlags::PINNED`] never on new channels. Creating the channel otherwise returns
+    /// [`ModelError::InvalidChannelFlags`].
+    ///
+    /// [`ModelError::InvalidChannelFlags`]: crate::model::error::Error::InvalidChannelFlags
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn flags(&mut self, flags: ChannelFlags) -> &mut Self {
+        self.0.insert("flags", Value::Number(Number::from(flags.bits())));
+
+        self
+    }
+
+    /// [Forum-only] Sets the default layout of the posts in the channel.
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn default_forum_layout(&mut self, layout: ForumLayout) -> &mut Self {
+        self.0.insert("default_forum_layout", Value::Number(Number::from(layout as u8)));
+
+        self
+    }
+
+    /// [Forum and media-only] Sets the default order in which the posts in the channel are
+    /// sorted.
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn default_sort_order(&mut self, order: SortOrder) -> &mut Self {
+        self.0.insert("default_sort_order", Value::Number(Number::from(order as u8)));
+
+        self
+    }
+
     /// A set of overwrites defining what a user or a user carrying a certain role can
     /// and cannot do.
     ///
@@ -1,8 +1,20 @@
 use std::collections::HashMap;
 
+use serde::de::{Deserialize, Deserializer, Error as DeError};
+use serde::ser::{Serialize, Serializer};
+use serde_json::json;
+
+use crate::constants::limits;
 use crate::internal::prelude::*;
-use crate::model::channel::ReactionType;
-use crate::model::interactions::message_component::{ButtonStyle, InputTextStyle};
+use crate::model::channel::{ChannelType, ReactionType};
+use crate::model::interactions::message_component::{
+    ButtonStyle,
+    ComponentType,
+    ComponentViolation,
+    InputTextStyle,
+    SelectMenuDefaultValue,
+    SelectMenuType,
+};
 use crate::utils;
 
 /// A builder for creating several [`ActionRow`]s.
@@ -11,6 +23,126 @@ use crate::utils;
 #[derive(Clone, Debug, Default)]
 pub struct CreateComponents(pub Vec<Value>);
 
+/// Whether the value is an action row, i.e. an object of type `1` with an
+/// array of components which all have a type.
+fn is_valid_action_row(row: &Value) -> bool {
+    row.get("type").and_then(Value::as_u64) == Some(1)
+        && row.get("components").and_then(Value::as_array).map_or(false, |components| {
+            components.iter().all(|component| {
+                component.get("type").and_then(Value::as_u64).map_or(false, |kind| kind != 1)
+            })
+        })
+}
+
+/// Finds every violation of serialized action rows.
+pub(crate) fn component_violations(rows: &[Value]) -> Vec<ComponentViolation> {
+    let mut violations = Vec::new();
+
+    if rows.len() > limits::ACTION_ROW_COUNT {
+        violations.push(ComponentViolation::TooManyRows(rows.len()));
+    }
+
+    for (row, value) in rows.iter().enumerate() {
+        let components =
+            value.get("components").and_then(Value::as_array).map_or(&[][..], Vec::as_slice);
+
+        if components.is_empty() {
+            violations.push(ComponentViolation::EmptyRow {
+                row,
+            });
+        }
+
+        let mut buttons = 0;
+
+        for (component, value) in components.iter().enumerate() {
+            let kind = value.get("type").and_then(Value::as_u64);
+
+            // Buttons are the only components which can share a row.
+            if kind == Some(ComponentType::Button as u64) {
+                buttons += 1;
+            } else if components.len() > 1 {
+                violations.push(ComponentViolation::SharedRow {
+                    row,
+                    component,
+                });
+            }
+
+            let options = value.get("options").and_then(Value::as_array);
+            if let Some(options) = options.filter(|o| o.len() > limits::SELECT_MENU_OPTION_COUNT) {
+                violations.push(ComponentViolation::TooManyOptions {
+                    row,
+                    component,
+                    count: options.len(),
+                });
+            }
+
+            if kind == Some(ComponentType::InputText as u64) {
+                violations.extend(input_text_violations(value, row, component));
+            }
+        }
+
+        if buttons > limits::ACTION_ROW_BUTTON_COUNT {
+            violations.push(ComponentViolation::TooManyButtons {
+                row,
+                count: buttons,
+            });
+        }
+    }
+
+    violations
+}
+
+/// Finds the violations of a serialized input text, the `component` of the
+/// action row `row`.
+fn input_text_violations(value: &Value, row: usize, component: usize) -> Vec<ComponentViolation> {
+    let mut violations = Vec::new();
+
+    let label = value.get("label").and_then(Value::as_str).unwrap_or_default();
+    let length = label.chars().count();
+    if length > limits::INPUT_TEXT_LABEL_LENGTH {
+        violations.push(ComponentViolation::InputTextLabelTooLong {
+            row,
+            component,
+            length,
+        });
+    }
+
+    let min = value.get("min_length").and_then(Value::as_u64);
+    let max = value.get("max_length").and_then(Value::as_u64);
+    let limit = limits::INPUT_TEXT_LENGTH as u64;
+    let exceeds_limit = min.into_iter().chain(max).any(|length| length > limit);
+    if exceeds_limit || min.zip(max).map_or(false, |(min, max)| min > max) {
+        violations.push(ComponentViolation::InvalidInputTextLength {
+            row,
+            component,
+            min,
+            max,
+        });
+    }
+
+    violations
+}
+
+impl Serialize for CreateComponents {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CreateComponents {
+    /// Deserializes the components from an array of action rows, e.g. a
+    /// layout stored in a config file.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        let rows = Vec::<Value>::deserialize(deserializer)?;
+
+        if let Some(row) = rows.iter().find(|row| !is_valid_action_row(row)) {
+            return Err(DeError::custom(format_args!("invalid action row: {}", row)));
+        }
+
+        Ok(Self(rows))
+    }
+}
+
 impl CreateComponents {
     /// Creates an action row.
     pub fn create_action_row<F>(&mut self, f: F) -> &mut Self
@@ -50,6 +182,33 @@ impl CreateComponents {
 
         self
     }
+
+    /// Checks the action rows against the limits of Discord, such as to find
+    /// out why they would be rejected before sending them.
+    ///
+    /// There can be up to 5 action rows, each holding either up to 5 buttons
+    /// or a single other component, such as a select menu with up to 25
+    /// options.
+    ///
+    /// The label of an input text can be up to 45 characters long, and its
+    /// minimum length can be no greater than its maximum length, which can be
+    /// no greater than 4000.
+    ///
+    /// This is done automatically when sending a message or interaction
+    /// response with these components.
+    ///
+    /// # Errors
+    ///
+    /// Returns every [`ComponentViolation`] which was found, if any.
+    pub fn build_check(&self) -> StdResult<(), Vec<ComponentViolation>> {
+        let violations = component_violations(&self.0);
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
 }
 
 /// A builder for creating an [`ActionRow`].
@@ -212,6 +371,16 @@ impl CreateButton {
 pub struct CreateSelectMenu(pub HashMap<&'static str, Value>);
 
 impl CreateSelectMenu {
+    /// Sets the type of the select menu, such as [`SelectMenuType::Role`] for
+    /// a select menu of the roles populated by Discord.
+    ///
+    /// Defaults to [`SelectMenuType::String`], a select menu of the options
+    /// set with [`Self::options`].
+    pub fn kind(&mut self, kind: SelectMenuType) -> &mut Self {
+        self.0.insert("type", Value::Number(serde_json::Number::from(kind as u8)));
+        self
+    }
+
     /// The placeholder of the select menu.
     pub fn placeholder<D: ToString>(&mut self, label: D) -> &mut Self {
         self.0.insert("placeholder", Value::String(label.to_string()));
@@ -254,8 +423,46 @@ impl CreateSelectMenu {
         self
     }
 
+    /// Sets the types of channels which can be selected in a select menu of
+    /// the channels. Channels of any type can be selected if none are set.
+    ///
+    /// **Note**: This is only valid for [`SelectMenuType::Channel`].
+    pub fn channel_types(&mut self, channel_types: Vec<ChannelType>) -> &mut Self {
+        let channel_types =
+            channel_types.into_iter().map(|kind| Value::Number(Number::from(kind as u8))).collect();
+
+        self.0.insert("channel_types", Value::Array(channel_types));
+        self
+    }
+
+    /// Sets the users, roles or channels which are selected by default.
+    ///
+    /// **Note**: This is only valid for select menus populated by Discord, and
+    /// the defaults must be of the type of the select menu, such as roles for
+    /// [`SelectMenuType::Role`].
+    pub fn default_values<It>(&mut self, values: It) -> &mut Self
+    where
+        It: IntoIterator<Item = SelectMenuDefaultValue>,
+    {
+        let values = values
+            .into_iter()
+            .map(|value| {
+                let (kind, id) = match value {
+                    SelectMenuDefaultValue::User(id) => ("user", id.0),
+                    SelectMenuDefaultValue::Role(id) => ("role", id.0),
+                    SelectMenuDefaultValue::Channel(id) => ("channel", id.0),
+                };
+
+                json!({"id": id.to_string(), "type": kind})
+            })
+            .collect();
+
+        self.0.insert("default_values", Value::Array(values));
+        self
+    }
+
     pub fn build(mut self) -> Value {
-        self.0.insert("type", Value::Number(serde_json::Number::from(3_u8)));
+        self.0.entry("type").or_insert_with(|| Value::Number(serde_json::Number::from(3_u8)));
 
         utils::hashmap_to_json_map(self.0.clone()).into()
     }
@@ -382,7 +589,7 @@ impl CreateInputText {
         self
     }
 
-    /// Sets the label of this input text.
+    /// Sets the label of this input text, of at most 45 characters.
     pub fn label<D: ToString>(&mut self, label: D) -> &mut Self {
         self.0.insert("label", Value::String(label.to_string()));
         self
@@ -394,19 +601,23 @@ impl CreateInputText {
         self
     }
 
-    /// Sets the minimum length required for the input text
+    /// Sets the minimum length required for the input text, at most 4000 and
+    /// no greater than the [maximum length].
+    ///
+    /// [maximum length]: Self::max_length
     pub fn min_length(&mut self, min: u64) -> &mut Self {
         self.0.insert("min_length", Value::Number(Number::from(min)));
         self
     }
 
-    /// Sets the maximum length required for the input text
+    /// Sets the maximum length required for the input text, at most 4000.
     pub fn max_length(&mut self, max: u64) -> &mut Self {
         self.0.insert("max_length", Value::Number(Number::from(max)));
         self
     }
 
-    /// Sets the value of this input text.
+    /// Sets the value the input text is pre-filled with, such as the current
+    /// value of what the modal edits.
     pub fn value<D: ToString>(&mut self, value: D) -> &mut Self {
         self.0.insert("value", Value::String(value.to_string()));
         self
@@ -424,3 +635,247 @@ impl CreateInputText {
         utils::hashmap_to_json_map(self.0.clone()).into()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{CreateActionRow, CreateComponents, CreateInputText, CreateSelectMenu};
+    use crate::model::channel::ChannelType;
+    use crate::model::id::{ChannelId, RoleId};
+    use crate::model::interactions::message_component::{
+        ButtonStyle,
+        ComponentViolation,
+        InputTextStyle,
+        SelectMenuDefaultValue,
+        SelectMenuType,
+    };
+
+    fn buttons(count: usize) -> CreateActionRow {
+        let mut row = CreateActionRow::default();
+        for i in 0..count {
+            row.create_button(|b| b.style(ButtonStyle::Primary).label(i).custom_id(i));
+        }
+
+        row
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_components_round_trip() {
+        let mut components = CreateComponents::default();
+        components.create_action_row(|row| {
+            row.create_button(|button| {
+                button.style(ButtonStyle::Primary).label("Accept").custom_id("accept")
+            })
+        });
+
+        let value = serde_json::to_value(&components).unwrap();
+        assert_eq!(value[0]["type"], 1);
+        assert_eq!(value[0]["components"][0]["custom_id"], "accept");
+
+        let parsed: CreateComponents = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), value);
+    }
+
+    #[test]
+    fn test_invalid_components() {
+        let invalid = [
+            json!({"type": 1, "components": []}),
+            json!([{"type": 2, "components": []}]),
+            json!([{"type": 1}]),
+            json!([{"type": 1, "components": [{"label": "missing a type"}]}]),
+            json!([{"type": 1, "components": [{"type": 1, "components": []}]}]),
+        ];
+
+        for value in &invalid {
+            assert!(
+                serde_json::from_value::<CreateComponents>(value.clone()).is_err(),
+                "{} was accepted",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_check_within_limits() {
+        let mut components = CreateComponents::default();
+        for _ in 0..4 {
+            components.add_action_row(buttons(5));
+        }
+        components.create_action_row(|row| {
+            row.create_select_menu(|menu| {
+                menu.custom_id("menu").options(|o| {
+                    for i in 0..25 {
+                        o.create_option(|option| option.label(i).value(i));
+                    }
+                    o
+                })
+            })
+        });
+
+        assert_eq!(components.build_check(), Ok(()));
+    }
+
+    #[test]
+    fn test_build_check_rows_and_buttons() {
+        let mut components = CreateComponents::default();
+        components.add_action_row(buttons(6));
+        components.add_action_row(buttons(0));
+        for _ in 0..4 {
+            components.add_action_row(buttons(1));
+        }
+
+        assert_eq!(components.build_check(), Err(vec![
+            ComponentViolation::TooManyRows(6),
+            ComponentViolation::TooManyButtons {
+                row: 0,
+                count: 6,
+            },
+            ComponentViolation::EmptyRow {
+                row: 1,
+            },
+        ]));
+    }
+
+    #[test]
+    fn test_build_check_mixed_row() {
+        let mut components = CreateComponents::default();
+        components.add_action_row(buttons(2));
+        components.create_action_row(|row| {
+            row.create_button(|b| b.style(ButtonStyle::Primary).label("a").custom_id("a"))
+                .create_select_menu(|menu| menu.custom_id("menu"))
+                .create_button(|b| b.style(ButtonStyle::Primary).label("b").custom_id("b"))
+        });
+
+        assert_eq!(components.build_check(), Err(vec![ComponentViolation::SharedRow {
+            row: 1,
+            component: 1,
+        }]));
+    }
+
+    #[test]
+    fn test_build_check_select_menu_options() {
+        let mut components = CreateComponents::default();
+        components.add_action_row(buttons(1));
+        components.create_action_row(|row| {
+            row.create_select_menu(|menu| {
+                menu.custom_id("menu").options(|o| {
+                    for i in 0..26 {
+                        o.create_option(|option| option.label(i).value(i));
+                    }
+                    o
+                })
+            })
+        });
+
+        let violations = components.build_check().unwrap_err();
+        assert_eq!(violations, vec![ComponentViolation::TooManyOptions {
+            row: 1,
+            component: 0,
+            count: 26,
+        }]);
+        assert_eq!(
+            violations[0].to_string(),
+            "select menu 0 of action row 1 has 26 options, over 25"
+        );
+    }
+
+    #[test]
+    fn test_select_menu_kinds() {
+        let mut menu = CreateSelectMenu::default();
+        menu.custom_id("roles")
+            .kind(SelectMenuType::Role)
+            .default_values(vec![SelectMenuDefaultValue::Role(RoleId(1))]);
+
+        assert_eq!(
+            menu.build(),
+            json!({
+                "type": 6,
+                "custom_id": "roles",
+                "default_values": [{"id": "1", "type": "role"}],
+            })
+        );
+
+        let mut menu = CreateSelectMenu::default();
+        menu.custom_id("channels")
+            .kind(SelectMenuType::Channel)
+            .channel_types(vec![ChannelType::Text, ChannelType::News])
+            .default_values(vec![SelectMenuDefaultValue::Channel(ChannelId(2))]);
+
+        assert_eq!(
+            menu.build(),
+            json!({
+                "type": 8,
+                "custom_id": "channels",
+                "channel_types": [0, 5],
+                "default_values": [{"id": "2", "type": "channel"}],
+            })
+        );
+
+        let mut menu = CreateSelectMenu::default();
+        menu.custom_id("options");
+        assert_eq!(menu.build()["type"], 3);
+    }
+
+    #[test]
+    fn test_input_text() {
+        let mut input_text = CreateInputText::default();
+        input_text
+            .custom_id("note")
+            .style(InputTextStyle::Paragraph)
+            .label("Note")
+            .placeholder("Write a note")
+            .min_length(1)
+            .max_length(500)
+            .value("The current note")
+            .required(false);
+
+        assert_eq!(
+            input_text.build(),
+            json!({
+                "type": 4,
+                "custom_id": "note",
+                "style": 2,
+                "label": "Note",
+                "placeholder": "Write a note",
+                "min_length": 1,
+                "max_length": 500,
+                "value": "The current note",
+                "required": false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_check_input_texts() {
+        let mut components = CreateComponents::default();
+        components
+            .create_action_row(|row| {
+                row.create_input_text(|i| i.label("a".repeat(45)).min_length(0).max_length(4000))
+            })
+            .create_action_row(|row| row.create_input_text(|i| i.label("a".repeat(46))))
+            .create_action_row(|row| row.create_input_text(|i| i.min_length(10).max_length(5)))
+            .create_action_row(|row| row.create_input_text(|i| i.max_length(4001)));
+
+        assert_eq!(components.build_check(), Err(vec![
+            ComponentViolation::InputTextLabelTooLong {
+                row: 1,
+                component: 0,
+                length: 46,
+            },
+            ComponentViolation::InvalidInputTextLength {
+                row: 2,
+                component: 0,
+                min: Some(10),
+                max: Some(5),
+            },
+            ComponentViolation::InvalidInputTextLength {
+                row: 3,
+                component: 0,
+                min: None,
+                max: Some(4001),
+            },
+        ]));
+    }
+}
@@ -14,14 +14,21 @@
 //! [`ExecuteWebhook::embeds`]: crate::builder::ExecuteWebhook::embeds
 //! [here]: https://discord.com/developers/docs/resources/channel#embed-object
 
-use std::collections::HashMap;
-use std::fmt::Display;
-
-use chrono::{DateTime, TimeZone};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error as StdError;
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use chrono::{DateTime, ParseError, SecondsFormat, TimeZone, Utc};
+use serde::de::{Deserialize, Deserializer, Error as DeError};
+use serde::ser::{Serialize, Serializer};
 use serde_json::{json, Value};
 
+use crate::constants::limits;
 use crate::internal::prelude::*;
 use crate::model::channel::Embed;
+use crate::model::ModelError;
 use crate::utils;
 #[cfg(feature = "utils")]
 use crate::utils::Colour;
@@ -41,6 +48,59 @@ use crate::utils::Colour;
 pub struct CreateEmbed(pub HashMap<&'static str, Value>);
 
 impl CreateEmbed {
+    /// Creates an embed from its JSON representation, such as a template
+    /// stored in a config file.
+    ///
+    /// Refer to [`Self::try_from_json`] for what is validated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Json`] if the value is not a valid embed.
+    ///
+    /// [`Error::Json`]: crate::error::Error::Json
+    pub fn from_value(value: Value) -> Result<Self> {
+        serde_json::from_value(value).map_err(From::from)
+    }
+
+    /// Creates an embed from a JSON string, such as a template stored in a
+    /// config file.
+    ///
+    /// Only the fields a [`CreateEmbed`] can set are accepted, and each of
+    /// them must have the shape Discord expects, e.g. a `color` must be a
+    /// number of at most `0xFFFFFF`, and every one of the `fields` must have a
+    /// `name` and a `value`. Missing fields are left unset, except for the
+    /// `type`, which defaults to `rich`.
+    ///
+    /// # Examples
+    ///
+    /// Load an embed template, and fill in the parts that change:
+    ///
+    /// ```rust
+    /// use serenity::builder::CreateEmbed;
+    ///
+    /// let template = r#"{
+    ///     "color": 3447003,
+    ///     "title": "Server rules",
+    ///     "footer": {"text": "Contact a moderator with any questions"}
+    /// }"#;
+    ///
+    /// let mut embed = CreateEmbed::try_from_json(template)?;
+    /// embed.description("1. Be nice");
+    ///
+    /// assert_eq!(embed.0["title"], "Server rules");
+    /// assert_eq!(embed.0["description"], "1. Be nice");
+    /// # Ok::<(), serenity::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Json`] if the string is not a valid embed.
+    ///
+    /// [`Error::Json`]: crate::error::Error::Json
+    pub fn try_from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(From::from)
+    }
+
     /// Build the author of the embed.
     ///
     /// Refer to the documentation for [`CreateEmbedAuthor`] for more
@@ -156,6 +216,158 @@ impl CreateEmbed {
         self
     }
 
+    /// Adds fields from an iterator until the embed holds `max_fields` fields,
+    /// returning the fields that did not fit so that they can be added to
+    /// another embed.
+    ///
+    /// A field also does not fit if the embed already holds 25 fields, or if
+    /// it would make the textual content of the embed exceed 6000 characters.
+    ///
+    /// Names longer than 256 characters and values longer than 1024
+    /// characters are truncated, ending with an ellipsis, so that every field
+    /// fits into an empty embed.
+    ///
+    /// # Examples
+    ///
+    /// Split the fields of a summary over as many embeds as needed:
+    ///
+    /// ```rust
+    /// use serenity::builder::CreateEmbed;
+    ///
+    /// let mut fields: Vec<_> =
+    ///     (1..=30).map(|i| (format!("Item {}", i), i.to_string(), true)).collect();
+    /// let mut embeds = Vec::new();
+    ///
+    /// while !fields.is_empty() {
+    ///     let mut embed = CreateEmbed::default();
+    ///     fields = embed.fields_chunked(fields, 25);
+    ///     embeds.push(embed);
+    /// }
+    ///
+    /// assert_eq!(embeds.len(), 2);
+    /// ```
+    pub fn fields_chunked<T, U, It>(
+        &mut self,
+        fields: It,
+        max_fields: usize,
+    ) -> Vec<(String, String, bool)>
+    where
+        It: IntoIterator<Item = (T, U, bool)>,
+        T: ToString,
+        U: ToString,
+    {
+        let max_fields = max_fields.min(limits::EMBED_FIELD_COUNT);
+        let mut count = self.field_count();
+        let mut length = embed_length(|key| self.0.get(key));
+        let mut overflow = Vec::new();
+
+        for (name, value, inline) in fields {
+            let name = truncate(name.to_string(), limits::EMBED_FIELD_NAME_LENGTH);
+            let value = truncate(value.to_string(), limits::EMBED_FIELD_VALUE_LENGTH);
+            let field_length = name.chars().count() + value.chars().count();
+
+            if overflow.is_empty()
+                && count < max_fields
+                && length + field_length <= limits::EMBED_TOTAL_LENGTH
+            {
+                count += 1;
+                length += field_length;
+                self._field(name, value, inline);
+            } else {
+                overflow.push((name, value, inline));
+            }
+        }
+
+        overflow
+    }
+
+    fn field_count(&self) -> usize {
+        match self.0.get("fields") {
+            Some(Value::Array(fields)) => fields.len(),
+            _ => 0,
+        }
+    }
+
+    /// Sets the description of the embed to one `**key**: value` line per
+    /// pair.
+    ///
+    /// The values are aligned by padding the shorter keys with spaces after
+    /// the colon, so that every value starts after as many characters.
+    ///
+    /// If the lines exceed the 4096 characters a description can hold, the
+    /// last lines are replaced by a line stating how many were left out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::builder::CreateEmbed;
+    ///
+    /// let mut embed = CreateEmbed::default();
+    /// embed.description_from_pairs(vec![("Members", 42), ("Channels", 7)]);
+    ///
+    /// assert_eq!(embed.0["description"], "**Members**:  42\n**Channels**: 7");
+    /// ```
+    pub fn description_from_pairs<K, V, It>(&mut self, pairs: It) -> &mut Self
+    where
+        It: IntoIterator<Item = (K, V)>,
+        K: fmt::Display,
+        V: fmt::Display,
+    {
+        let pairs: Vec<(String, V)> =
+            pairs.into_iter().map(|(key, value)| (key.to_string(), value)).collect();
+        let width = pairs.iter().map(|(key, _)| key.chars().count()).max().unwrap_or(0);
+        let lines: Vec<String> = pairs
+            .iter()
+            .map(|(key, value)| {
+                let padding = width - key.chars().count();
+
+                format!("**{}**: {:padding$}{}", key, "", value, padding = padding)
+            })
+            .collect();
+        let mut lengths = Vec::new();
+        let mut length = 0;
+
+        for line in &lines {
+            let line_length = line.chars().count() + usize::from(!lengths.is_empty());
+
+            if length + line_length > limits::EMBED_DESCRIPTION_LENGTH {
+                break;
+            }
+
+            length += line_length;
+            lengths.push(line_length);
+        }
+
+        let mut shown = lengths.len();
+        let mut indicator = String::new();
+
+        while shown < lines.len() {
+            indicator = format!("… and {} more", lines.len() - shown);
+
+            let indicator_length = indicator.chars().count() + usize::from(shown > 0);
+
+            if length + indicator_length <= limits::EMBED_DESCRIPTION_LENGTH {
+                break;
+            }
+
+            shown -= 1;
+            length -= lengths[shown];
+        }
+
+        let mut description = lines[..shown].join("\n");
+
+        if shown < lines.len() {
+            if shown > 0 {
+                description.push('\n');
+            }
+
+            description.push_str(&indicator);
+        }
+
+        self.0.insert("description", Value::String(description));
+        self
+    }
+
     /// Build the footer of the embed.
     ///
     /// Refer to the documentation for [`CreateEmbedFooter`] for more
@@ -204,16 +416,10 @@ impl CreateEmbed {
 
     /// Set the timestamp.
     ///
-    /// You may pass a direct string:
-    ///
-    /// - `2017-01-03T23:00:00`
-    /// - `2004-06-08T16:04:23`
-    /// - `2004-06-08T16:04:23`
-    ///
-    /// This timestamp must be in ISO-8601 format. It must also be in UTC format.
-    ///
-    /// You can also pass an instance of `chrono::DateTime<Utc>`,
-    /// which will construct the timestamp string out of it.
+    /// You may pass a [`Timestamp`], a `chrono::DateTime` in any timezone, or a
+    /// [`SystemTime`]. Strings must be parsed with [`Timestamp::parse`] first,
+    /// so that a malformed timestamp is caught here rather than rejected by
+    /// Discord.
     ///
     /// # Examples
     ///
@@ -222,6 +428,7 @@ impl CreateEmbed {
     /// ```rust,no_run
     /// # #[cfg(feature = "client")]
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// use serenity::builder::Timestamp;
     /// use serenity::model::channel::Message;
     /// use serenity::prelude::*;
     ///
@@ -234,7 +441,8 @@ impl CreateEmbed {
     ///             let _ = msg
     ///                 .channel_id
     ///                 .send_message(&context.http, |m| {
-    ///                     m.embed(|e| e.title("hello").timestamp("2004-06-08T16:04:23"));
+    ///                     let timestamp = Timestamp::parse("2004-06-08T16:04:23Z").unwrap();
+    ///                     m.embed(|e| e.title("hello").timestamp(timestamp));
     ///
     ///                     m
     ///                 })
@@ -309,6 +517,13 @@ impl CreateEmbed {
         self.0.insert("timestamp", Value::String(timestamp.ts));
     }
 
+    /// Set the timestamp to the current time.
+    #[inline]
+    pub fn timestamp_now(&mut self) -> &mut Self {
+        self._timestamp(Timestamp::now());
+        self
+    }
+
     /// Set the title of the embed.
     #[inline]
     pub fn title<D: ToString>(&mut self, title: D) -> &mut Self {
@@ -337,6 +552,286 @@ impl CreateEmbed {
 
         self
     }
+
+    /// Checks the embed against all of Discord's limits, returning its JSON
+    /// representation if it is within them.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::EmbedPartTooLong`] if the title, description,
+    /// author name, footer text, or the name or value of a field is too long,
+    /// a [`ModelError::EmbedFieldAmount`] if there are over 25 fields, and a
+    /// [`ModelError::EmbedTooLarge`] if the textual content of the embed
+    /// exceeds 6000 characters altogether.
+    ///
+    /// [`ModelError::EmbedPartTooLong`]: crate::model::error::Error::EmbedPartTooLong
+    /// [`ModelError::EmbedFieldAmount`]: crate::model::error::Error::EmbedFieldAmount
+    /// [`ModelError::EmbedTooLarge`]: crate::model::error::Error::EmbedTooLarge
+    pub fn try_build(&self) -> Result<Value> {
+        check_embed(|key| self.0.get(key))?;
+
+        Ok(Value::Object(utils::hashmap_to_json_map(self.0.clone())))
+    }
+
+    /// Checks the lengths of the parts of the embed against Discord's limits.
+    ///
+    /// Unlike [`Self::try_build`], the error reports the length of every part
+    /// along with its limit, such as to log which parts are too long.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EmbedError`] if the title, description, author name,
+    /// footer text, or the name or value of a field is too long, if there are
+    /// over 25 fields, or if the textual content of the embed exceeds 6000
+    /// characters altogether.
+    pub fn check_length(&self) -> StdResult<(), EmbedError> {
+        let checked = embed_limits(|key| self.0.get(key));
+
+        if checked.iter().any(EmbedLimit::is_exceeded) {
+            return Err(EmbedError {
+                limits: checked,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// A part of an embed which Discord limits, as reported by
+/// [`CreateEmbed::check_length`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum EmbedPart {
+    Title,
+    Description,
+    AuthorName,
+    FooterText,
+    /// The number of fields.
+    FieldCount,
+    /// The name of the field at the given index.
+    FieldName(usize),
+    /// The value of the field at the given index.
+    FieldValue(usize),
+    /// The textual content of the embed altogether.
+    Total,
+}
+
+impl EmbedPart {
+    /// The name of the part as reported by [`ModelError::EmbedPartTooLong`].
+    fn name(self) -> &'static str {
+        match self {
+            EmbedPart::Title => "title",
+            EmbedPart::Description => "description",
+            EmbedPart::AuthorName => "author name",
+            EmbedPart::FooterText => "footer text",
+            EmbedPart::FieldCount => "fields",
+            EmbedPart::FieldName(_) => "field name",
+            EmbedPart::FieldValue(_) => "field value",
+            EmbedPart::Total => "total",
+        }
+    }
+}
+
+impl fmt::Display for EmbedPart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmbedPart::FieldName(index) => write!(f, "field {} name", index),
+            EmbedPart::FieldValue(index) => write!(f, "field {} value", index),
+            _ => f.write_str(self.name()),
+        }
+    }
+}
+
+/// The length of a part of an embed along with its limit, as reported by
+/// [`CreateEmbed::check_length`].
+///
+/// Lengths are counted in unicode code points.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct EmbedLimit {
+    pub part: EmbedPart,
+    /// The length of the part, or the number of fields for
+    /// [`EmbedPart::FieldCount`].
+    pub length: usize,
+    pub limit: usize,
+}
+
+impl EmbedLimit {
+    /// Whether the part exceeds its limit.
+    pub fn is_exceeded(&self) -> bool {
+        self.length > self.limit
+    }
+
+    /// By how much the part exceeds its limit, or `0` if it does not.
+    pub fn overflow(&self) -> usize {
+        self.length.saturating_sub(self.limit)
+    }
+}
+
+impl fmt::Display for EmbedLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.is_exceeded() { "over" } else { "ok" };
+
+        write!(f, "{} {}/{} {}", self.part, self.length, self.limit, status)
+    }
+}
+
+/// An embed which exceeds Discord's limits, as returned by
+/// [`CreateEmbed::check_length`].
+///
+/// It displays every limit of the embed, such as
+/// `description 4096/4096 ok, ..., total 6240/6000 over`, leaving out the
+/// fields whose name and value are within their limits.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct EmbedError {
+    /// Every limit of the embed, including the ones it is within.
+    pub limits: Vec<EmbedLimit>,
+}
+
+impl EmbedError {
+    /// The limits which the embed exceeds.
+    pub fn exceeded(&self) -> impl Iterator<Item = &EmbedLimit> {
+        self.limits.iter().filter(|limit| limit.is_exceeded())
+    }
+}
+
+impl fmt::Display for EmbedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Embed exceeds its limits:")?;
+
+        let shown = self.limits.iter().filter(|limit| {
+            let field_part =
+                matches!(limit.part, EmbedPart::FieldName(_) | EmbedPart::FieldValue(_));
+
+            !field_part || limit.is_exceeded()
+        });
+
+        for (index, limit) in shown.enumerate() {
+            let separator = if index == 0 { " " } else { ", " };
+            write!(f, "{}{}", separator, limit)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl StdError for EmbedError {}
+
+impl From<EmbedError> for ModelError {
+    /// Converts the first limit the embed exceeds, in the order of
+    /// [`EmbedError::limits`].
+    fn from(error: EmbedError) -> ModelError {
+        let limit = match error.exceeded().next() {
+            Some(limit) => *limit,
+            None => return ModelError::EmbedTooLarge(0),
+        };
+
+        match limit.part {
+            EmbedPart::FieldCount => ModelError::EmbedFieldAmount,
+            EmbedPart::Total => ModelError::EmbedTooLarge(limit.overflow()),
+            part => ModelError::EmbedPartTooLong {
+                part: part.name(),
+                overflow: limit.overflow(),
+            },
+        }
+    }
+}
+
+/// Checks the parts of an embed, given a way to look up its keys, against
+/// their limits, returning the length of its textual content.
+pub(crate) fn check_embed<'a>(get: impl Fn(&str) -> Option<&'a Value>) -> Result<usize> {
+    let checked = embed_limits(&get);
+
+    if checked.iter().any(EmbedLimit::is_exceeded) {
+        let error = EmbedError {
+            limits: checked,
+        };
+
+        return Err(Error::Model(error.into()));
+    }
+
+    Ok(embed_length(get))
+}
+
+/// Measures every part of an embed which Discord limits, given a way to look
+/// up its keys.
+fn embed_limits<'a>(get: impl Fn(&str) -> Option<&'a Value>) -> Vec<EmbedLimit> {
+    let count = |text: Option<&str>| text.map_or(0, |text| text.chars().count());
+    let limit = |part, length, limit| EmbedLimit {
+        part,
+        length,
+        limit,
+    };
+
+    let mut checked = vec![
+        limit(EmbedPart::Title, count(string_at(&get, &["title"])), limits::EMBED_TITLE_LENGTH),
+        limit(
+            EmbedPart::Description,
+            count(string_at(&get, &["description"])),
+            limits::EMBED_DESCRIPTION_LENGTH,
+        ),
+        limit(
+            EmbedPart::AuthorName,
+            count(string_at(&get, &["author", "name"])),
+            limits::EMBED_AUTHOR_NAME_LENGTH,
+        ),
+        limit(
+            EmbedPart::FooterText,
+            count(string_at(&get, &["footer", "text"])),
+            limits::EMBED_FOOTER_TEXT_LENGTH,
+        ),
+    ];
+
+    let fields = get("fields").and_then(Value::as_array).map_or(&[][..], Vec::as_slice);
+    checked.push(limit(EmbedPart::FieldCount, fields.len(), limits::EMBED_FIELD_COUNT));
+
+    for (index, field) in fields.iter().enumerate() {
+        let name = count(field.get("name").and_then(Value::as_str));
+        let value = count(field.get("value").and_then(Value::as_str));
+
+        checked.push(limit(EmbedPart::FieldName(index), name, limits::EMBED_FIELD_NAME_LENGTH));
+        checked.push(limit(EmbedPart::FieldValue(index), value, limits::EMBED_FIELD_VALUE_LENGTH));
+    }
+
+    checked.push(limit(EmbedPart::Total, embed_length(&get), limits::EMBED_TOTAL_LENGTH));
+
+    checked
+}
+
+/// Returns the string at the given path of nested objects, if there is one.
+fn string_at<'a>(
+    get: impl Fn(&str) -> Option<&'a Value>,
+    path: &[&'static str],
+) -> Option<&'a str> {
+    let (first, rest) = path.split_first()?;
+    let mut value = get(first)?;
+
+    for key in rest {
+        value = value.get(key)?;
+    }
+
+    value.as_str()
+}
+
+/// Counts the characters of the textual content of an embed, given a way to
+/// look up its keys, which Discord limits to 6000 altogether.
+pub(crate) fn embed_length<'a>(get: impl Fn(&str) -> Option<&'a Value>) -> usize {
+    let count = |text: Option<&str>| text.map_or(0, |text| text.chars().count());
+
+    let mut length = count(string_at(&get, &["title"]))
+        + count(string_at(&get, &["description"]))
+        + count(string_at(&get, &["author", "name"]))
+        + count(string_at(&get, &["footer", "text"]));
+
+    if let Some(Value::Array(fields)) = get("fields") {
+        for field in fields {
+            length += count(field.get("name").and_then(Value::as_str));
+            length += count(field.get("value").and_then(Value::as_str));
+        }
+    }
+
+    length
 }
 
 impl Default for CreateEmbed {
@@ -349,6 +844,83 @@ impl Default for CreateEmbed {
     }
 }
 
+/// The fields a [`CreateEmbed`] can set.
+const EMBED_KEYS: &[&str] = &[
+    "author",
+    "color",
+    "description",
+    "fields",
+    "footer",
+    "image",
+    "thumbnail",
+    "timestamp",
+    "title",
+    "type",
+    "url",
+];
+
+/// Takes the entries of a builder's JSON representation, failing if a key is
+/// not one of the `keys` the builder can set.
+pub(crate) fn known_keys<E: DeError>(
+    map: JsonMap,
+    keys: &'static [&'static str],
+) -> StdResult<HashMap<&'static str, Value>, E> {
+    map.into_iter()
+        .map(|(key, value)| match keys.iter().find(|known| **known == key) {
+            Some(known) => Ok((*known, value)),
+            None => Err(E::unknown_field(&key, keys)),
+        })
+        .collect()
+}
+
+/// Whether the value is an object with a string under the given key.
+fn has_string(value: &Value, key: &str) -> bool {
+    value.get(key).map_or(false, Value::is_string)
+}
+
+fn is_valid_embed_value(key: &str, value: &Value) -> bool {
+    match key {
+        "author" => has_string(value, "name"),
+        "color" => value.as_u64().map_or(false, |colour| colour <= 0xFF_FFFF),
+        "fields" => value.as_array().map_or(false, |fields| {
+            fields.iter().all(|field| {
+                has_string(field, "name")
+                    && has_string(field, "value")
+                    && field.get("inline").map_or(true, Value::is_boolean)
+            })
+        }),
+        "footer" => has_string(value, "text"),
+        "image" | "thumbnail" => has_string(value, "url"),
+        "timestamp" => {
+            value.as_str().map_or(false, |timestamp| Timestamp::parse(timestamp).is_ok())
+        },
+        _ => value.is_string(),
+    }
+}
+
+impl Serialize for CreateEmbed {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().collect::<BTreeMap<_, _>>())
+    }
+}
+
+impl<'de> Deserialize<'de> for CreateEmbed {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        let map = known_keys(JsonMap::deserialize(deserializer)?, EMBED_KEYS)?;
+
+        if let Some((key, value)) =
+            map.iter().find(|(key, value)| !is_valid_embed_value(key, value))
+        {
+            return Err(DeError::custom(format_args!("invalid embed {}: {}", key, value)));
+        }
+
+        let mut embed = CreateEmbed::default();
+        embed.0.extend(map);
+
+        Ok(embed)
+    }
+}
+
 impl From<Embed> for CreateEmbed {
     /// Converts the fields of an embed into the values for a new embed builder.
     ///
@@ -385,7 +957,7 @@ impl From<Embed> for CreateEmbed {
             b.image(&image.url);
         }
 
-        if let Some(timestamp) = embed.timestamp {
+        if let Some(timestamp) = embed.timestamp.and_then(|ts| Timestamp::parse(&ts).ok()) {
             b.timestamp(timestamp);
         }
 
@@ -469,54 +1041,107 @@ impl CreateEmbedFooter {
     }
 }
 
-#[derive(Clone, Debug)]
+/// A timestamp in the format Discord expects, such as
+/// `2004-06-08T16:04:23.000Z`.
+///
+/// Timestamps are created from a `chrono::DateTime` in any timezone or a
+/// [`SystemTime`], or parsed from a string with [`Timestamp::parse`]. They are
+/// always sent in UTC, with millisecond precision.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Timestamp {
+    /// The timestamp as sent to Discord.
+    ///
+    /// **Note**: Setting this directly bypasses the validation of
+    /// [`Timestamp::parse`].
     pub ts: String,
 }
 
-impl From<String> for Timestamp {
-    fn from(ts: String) -> Self {
-        Self {
-            ts,
-        }
+impl Timestamp {
+    /// Parses an RFC 3339 timestamp, such as `2004-06-08T16:04:23Z` or
+    /// `2004-06-08T18:04:23+02:00`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the string is not a valid RFC 3339
+    /// timestamp, e.g. because it is missing the timezone offset.
+    ///
+    /// [`ParseError`]: chrono::ParseError
+    pub fn parse(s: &str) -> StdResult<Self, ParseError> {
+        DateTime::parse_from_rfc3339(s).map(Self::from)
+    }
+
+    /// Returns a timestamp of the current time.
+    #[must_use]
+    pub fn now() -> Self {
+        Self::from(Utc::now())
+    }
+
+    /// Returns the timestamp as it is sent to Discord.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.ts
     }
 }
 
-impl<'a> From<&'a str> for Timestamp {
-    fn from(ts: &'a str) -> Self {
-        Self {
-            ts: ts.to_string(),
-        }
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.ts)
     }
 }
 
-impl<Tz: TimeZone> From<DateTime<Tz>> for Timestamp
-where
-    Tz::Offset: Display,
-{
+impl FromStr for Timestamp {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl<Tz: TimeZone> From<DateTime<Tz>> for Timestamp {
     fn from(dt: DateTime<Tz>) -> Self {
-        Self {
-            ts: dt.to_rfc3339(),
-        }
+        Self::from(&dt)
     }
 }
 
-impl<'a, Tz: TimeZone> From<&'a DateTime<Tz>> for Timestamp
-where
-    Tz::Offset: Display,
-{
+impl<'a, Tz: TimeZone> From<&'a DateTime<Tz>> for Timestamp {
     fn from(dt: &'a DateTime<Tz>) -> Self {
         Self {
-            ts: dt.to_rfc3339(),
+            ts: dt.with_timezone(&Utc).to_rfc3339_opts(SecondsFormat::Millis, true),
         }
     }
 }
 
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        Self::from(DateTime::<Utc>::from(time))
+    }
+}
+
+/// Truncates `text` to at most `limit` characters, marking the cut with an
+/// ellipsis.
+fn truncate(mut text: String, limit: usize) -> String {
+    if let Some((end, _)) = text.char_indices().nth(limit) {
+        // The last character kept makes room for the ellipsis.
+        let end = text[..end].char_indices().last().map_or(0, |(index, _)| index);
+
+        text.truncate(end);
+        text.push('\u{2026}');
+    }
+
+    text
+}
+
 #[cfg(test)]
 mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use chrono::{FixedOffset, TimeZone, Utc};
     use serde_json::{json, Value};
 
-    use super::CreateEmbed;
+    use super::{CreateEmbed, EmbedLimit, EmbedPart, Timestamp};
+    use crate::builder::CreateEmbedAuthor;
+    use crate::error::Error;
+    use crate::model::ModelError;
     use crate::{
         model::channel::{Embed, EmbedField, EmbedFooter, EmbedImage, EmbedVideo},
         utils::{self, Colour},
@@ -602,4 +1227,339 @@ mod test {
 
         assert_eq!(built, obj);
     }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_timestamp_conversions() {
+        let expected = "2004-06-08T16:04:23.000Z";
+
+        let utc = Utc.with_ymd_and_hms(2004, 6, 8, 16, 4, 23).unwrap();
+        assert_eq!(Timestamp::from(utc).as_str(), expected);
+
+        let offset = FixedOffset::east_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2004, 6, 8, 18, 4, 23)
+            .unwrap();
+        assert_eq!(Timestamp::from(&offset).as_str(), expected);
+
+        let system_time = UNIX_EPOCH + Duration::from_secs(utc.timestamp() as u64);
+        assert_eq!(Timestamp::from(system_time).as_str(), expected);
+
+        assert_eq!(Timestamp::parse("2004-06-08T18:04:23+02:00").unwrap().as_str(), expected);
+        assert_eq!("2004-06-08T16:04:23Z".parse::<Timestamp>().unwrap().as_str(), expected);
+    }
+
+    #[test]
+    fn test_timestamp_parse_rejects_malformed() {
+        assert!(Timestamp::parse("2004-06-08T16:04:23").is_err());
+        assert!(Timestamp::parse("2004-06-08").is_err());
+        assert!(Timestamp::parse("yesterday").is_err());
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_embed_timestamp() {
+        let mut builder = CreateEmbed::default();
+        builder.timestamp(Utc.with_ymd_and_hms(2004, 6, 8, 16, 4, 23).unwrap());
+        assert_eq!(builder.0.get("timestamp"), Some(&json!("2004-06-08T16:04:23.000Z")));
+
+        builder.timestamp_now();
+        assert!(builder.0.get("timestamp").and_then(Value::as_str).unwrap_or("").ends_with('Z'));
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_embed_round_trip() {
+        let mut author = CreateEmbedAuthor::default();
+        author.name("hakase").url("https://example.com");
+
+        let mut builder = CreateEmbed::default();
+        builder
+            .set_author(author)
+            .colour(0xFF0011)
+            .field("a", "b", true)
+            .footer(|f| f.text("footer"))
+            .thumbnail("https://example.com/thumbnail.png")
+            .timestamp(Utc.with_ymd_and_hms(2004, 6, 8, 16, 4, 23).unwrap())
+            .title("title");
+
+        let json = serde_json::to_string(&builder).unwrap();
+        let parsed = CreateEmbed::try_from_json(&json).unwrap();
+        assert_eq!(parsed.0, builder.0);
+
+        let value = serde_json::to_value(&builder).unwrap();
+        assert_eq!(value["type"], "rich");
+        assert_eq!(CreateEmbed::from_value(value).unwrap().0, builder.0);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_embed_template() {
+        let embed = CreateEmbed::from_value(json!({"title": "template"})).unwrap();
+        assert_eq!(embed.0.len(), 2);
+        assert_eq!(embed.0["title"], "template");
+        assert_eq!(embed.0["type"], "rich");
+
+        let invalid = [
+            json!({"unknown": "field"}),
+            json!({"title": 5}),
+            json!({"color": 0x1000000}),
+            json!({"color": "red"}),
+            json!({"fields": [{"name": "missing a value"}]}),
+            json!({"fields": [{"name": "a", "value": "b", "inline": "yes"}]}),
+            json!({"footer": {"icon_url": "https://example.com"}}),
+            json!({"image": "https://example.com"}),
+            json!({"timestamp": "yesterday"}),
+            json!(["not", "an", "object"]),
+        ];
+
+        for value in &invalid {
+            assert!(CreateEmbed::from_value(value.clone()).is_err(), "{} was accepted", value);
+        }
+
+        assert!(CreateEmbed::try_from_json("{").is_err());
+    }
+
+    #[test]
+    fn test_fields_chunked() {
+        let fields = (0..30).map(|i| (i, "value", false));
+
+        let mut embed = CreateEmbed::default();
+        embed.field("existing", "value", true);
+        let overflow = embed.fields_chunked(fields, 10);
+
+        assert_eq!(embed.0["fields"].as_array().unwrap().len(), 10);
+        assert_eq!(embed.0["fields"][1]["name"], "0");
+        assert_eq!(overflow.len(), 21);
+        assert_eq!(overflow[0], ("9".to_string(), "value".to_string(), false));
+
+        let mut embed = CreateEmbed::default();
+        assert!(embed.fields_chunked(overflow, 100).is_empty());
+        assert_eq!(embed.0["fields"].as_array().unwrap().len(), 21);
+
+        // Fields beyond the total embed length stay in order in the overflow.
+        let value = "x".repeat(1024);
+        let fields = (0..10).map(|i| (i, value.clone(), false));
+
+        let mut embed = CreateEmbed::default();
+        let overflow = embed.fields_chunked(fields, 25);
+
+        assert_eq!(embed.0["fields"].as_array().unwrap().len(), 5);
+        assert_eq!(overflow.len(), 5);
+        assert_eq!(overflow[0].0, "5");
+        assert!(embed.try_build().is_ok());
+
+        // Fields beyond the limits of a single field are truncated, instead of
+        // never fitting.
+        let fields = vec![("n".repeat(300), "v".repeat(7000), false)];
+
+        let mut embed = CreateEmbed::default();
+        assert!(embed.fields_chunked(fields, 25).is_empty());
+
+        let name = embed.0["fields"][0]["name"].as_str().unwrap();
+        let value = embed.0["fields"][0]["value"].as_str().unwrap();
+        assert_eq!(name.chars().count(), 256);
+        assert!(name.ends_with("n…"));
+        assert_eq!(value.chars().count(), 1024);
+        assert!(value.ends_with("v…"));
+        assert!(embed.try_build().is_ok());
+    }
+
+    #[test]
+    fn test_description_from_pairs() {
+        let mut embed = CreateEmbed::default();
+        embed.description_from_pairs(vec![("a", 1), ("b", 2)]);
+        assert_eq!(embed.0["description"], "**a**: 1\n**b**: 2");
+
+        // The values of shorter keys are padded to line up.
+        let mut embed = CreateEmbed::default();
+        embed.description_from_pairs(vec![("id", "1"), ("name", "ab"), ("é", "c")]);
+        assert_eq!(embed.0["description"], "**id**:   1\n**name**: ab\n**é**:    c");
+
+        // Each line is 1022 characters long, so 4 of them would fit, but only
+        // 3 along with the indicator.
+        let value = "x".repeat(1022 - "**0**: ".len());
+        let mut embed = CreateEmbed::default();
+        embed.description_from_pairs((0..5).map(|i| (i, value.clone())));
+
+        let description = embed.0["description"].as_str().unwrap();
+        assert!(description.chars().count() <= 4096);
+        assert!(description.ends_with("\n… and 2 more"));
+        assert_eq!(description.lines().count(), 4);
+
+        let mut embed = CreateEmbed::default();
+        embed.description_from_pairs(vec![("a", "x".repeat(5000))]);
+        assert_eq!(embed.0["description"], "… and 1 more");
+    }
+
+    #[test]
+    fn test_try_build() {
+        let mut embed = CreateEmbed::default();
+        embed.title("title").field("name", "value", false);
+        let value = embed.try_build().unwrap();
+        assert_eq!(value["title"], "title");
+        assert_eq!(value["fields"][0]["value"], "value");
+
+        let mut embed = CreateEmbed::default();
+        embed.field("name", "x".repeat(1030), false);
+        assert_eq!(
+            embed.try_build().unwrap_err().to_string(),
+            ModelError::EmbedPartTooLong {
+                part: "field value",
+                overflow: 6,
+            }
+            .to_string()
+        );
+
+        let mut embed = CreateEmbed::default();
+        embed.title("t".repeat(257));
+        assert!(matches!(
+            embed.try_build(),
+            Err(Error::Model(ModelError::EmbedPartTooLong {
+                part: "title",
+                overflow: 1,
+            }))
+        ));
+
+        let mut embed = CreateEmbed::default();
+        embed.fields((0..26).map(|i| (i, i, false)));
+        assert!(matches!(embed.try_build(), Err(Error::Model(ModelError::EmbedFieldAmount))));
+
+        let mut embed = CreateEmbed::default();
+        embed.description("d".repeat(4096));
+        embed.fields((0..3).map(|i| (i, "v".repeat(1000), false)));
+        assert!(matches!(embed.try_build(), Err(Error::Model(ModelError::EmbedTooLarge(1099)))));
+    }
+
+    #[allow(clippy::unwrap_used)]
+    fn exceeded(embed: &CreateEmbed) -> Vec<EmbedLimit> {
+        embed.check_length().unwrap_err().exceeded().copied().collect()
+    }
+
+    #[test]
+    fn test_check_length_within_limits() {
+        let mut embed = CreateEmbed::default();
+        embed
+            .title("t".repeat(256))
+            .description("d".repeat(3440))
+            .author(|a| a.name("a".repeat(256)))
+            .footer(|f| f.text("f".repeat(2048)));
+
+        assert!(embed.check_length().is_ok());
+    }
+
+    #[test]
+    fn test_check_length_title() {
+        let mut embed = CreateEmbed::default();
+        embed.title("t".repeat(260));
+
+        assert_eq!(exceeded(&embed), vec![EmbedLimit {
+            part: EmbedPart::Title,
+            length: 260,
+            limit: 256,
+        }]);
+    }
+
+    #[test]
+    fn test_check_length_description() {
+        let mut embed = CreateEmbed::default();
+        embed.description("d".repeat(4097));
+
+        assert_eq!(exceeded(&embed), vec![EmbedLimit {
+            part: EmbedPart::Description,
+            length: 4097,
+            limit: 4096,
+        }]);
+    }
+
+    #[test]
+    fn test_check_length_author_name() {
+        let mut embed = CreateEmbed::default();
+        embed.author(|a| a.name("a".repeat(257)));
+
+        assert_eq!(exceeded(&embed), vec![EmbedLimit {
+            part: EmbedPart::AuthorName,
+            length: 257,
+            limit: 256,
+        }]);
+    }
+
+    #[test]
+    fn test_check_length_footer_text() {
+        let mut embed = CreateEmbed::default();
+        embed.footer(|f| f.text("f".repeat(2050)));
+
+        assert_eq!(exceeded(&embed), vec![EmbedLimit {
+            part: EmbedPart::FooterText,
+            length: 2050,
+            limit: 2048,
+        }]);
+    }
+
+    #[test]
+    fn test_check_length_field_name_and_value() {
+        let mut embed = CreateEmbed::default();
+        embed.field("name", "value", false).field("n".repeat(257), "v".repeat(1025), false);
+
+        assert_eq!(exceeded(&embed), vec![
+            EmbedLimit {
+                part: EmbedPart::FieldName(1),
+                length: 257,
+                limit: 256,
+            },
+            EmbedLimit {
+                part: EmbedPart::FieldValue(1),
+                length: 1025,
+                limit: 1024,
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_check_length_field_count() {
+        let mut embed = CreateEmbed::default();
+        embed.fields((0..26).map(|i| (i, i, false)));
+
+        assert_eq!(exceeded(&embed), vec![EmbedLimit {
+            part: EmbedPart::FieldCount,
+            length: 26,
+            limit: 25,
+        }]);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_check_length_total() {
+        let mut embed = CreateEmbed::default();
+        embed.description("d".repeat(4096)).footer(|f| f.text("f".repeat(2048)));
+        embed.fields((0..2).map(|i| (i, "v".repeat(47), false)));
+
+        let error = embed.check_length().unwrap_err();
+        assert_eq!(error.exceeded().copied().collect::<Vec<_>>(), vec![EmbedLimit {
+            part: EmbedPart::Total,
+            length: 6240,
+            limit: 6000,
+        }]);
+        assert_eq!(
+            error.to_string(),
+            "Embed exceeds its limits: title 0/256 ok, description 4096/4096 ok, author name \
+             0/256 ok, footer text 2048/2048 ok, fields 2/25 ok, total 6240/6000 over"
+        );
+        assert!(matches!(ModelError::from(error), ModelError::EmbedTooLarge(240)));
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_check_length_into_model_error() {
+        let mut embed = CreateEmbed::default();
+        embed.description("d".repeat(4100)).field("name", "v".repeat(1030), false);
+
+        let error = embed.check_length().unwrap_err();
+        assert_eq!(error.exceeded().count(), 2);
+        assert!(error.to_string().ends_with("field 0 value 1030/1024 over, total 5134/6000 ok"));
+        assert!(matches!(ModelError::from(error), ModelError::EmbedPartTooLong {
+            part: "description",
+            overflow: 4,
+        }));
+    }
 }
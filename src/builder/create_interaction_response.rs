@@ -4,6 +4,9 @@ use serde_json::{json, Value};
 
 use super::{CreateAllowedMentions, CreateEmbed};
 use crate::builder::CreateComponents;
+#[cfg(feature = "http")]
+use crate::http::AttachmentType;
+use crate::model::id::AttachmentId;
 use crate::{
     model::interactions::{
         InteractionApplicationCommandCallbackDataFlags,
@@ -13,7 +16,11 @@ use crate::{
 };
 
 #[derive(Clone, Debug)]
-pub struct CreateInteractionResponse(pub HashMap<&'static str, Value>);
+pub struct CreateInteractionResponse(
+    pub HashMap<&'static str, Value>,
+    #[cfg(feature = "http")] pub(crate) Vec<AttachmentType<'static>>,
+    #[cfg(not(feature = "http"))] (),
+);
 
 impl CreateInteractionResponse {
     /// Sets the InteractionResponseType of the message.
@@ -25,31 +32,56 @@ impl CreateInteractionResponse {
     }
 
     /// Sets the `InteractionApplicationCommandCallbackData` for the message.
+    ///
+    /// This replaces the files of any previously set response data.
     pub fn interaction_response_data<F>(&mut self, f: F) -> &mut Self
     where
         F: FnOnce(&mut CreateInteractionResponseData) -> &mut CreateInteractionResponseData,
     {
         let mut data = CreateInteractionResponseData::default();
         f(&mut data);
+
+        #[cfg(feature = "http")]
+        {
+            self.1 = data.1;
+        }
+
         let map = utils::hashmap_to_json_map(data.0);
         let data = Value::Object(map);
 
         self.0.insert("data", data);
         self
     }
+
+    /// Responds with an upgrade prompt, telling the user that the interaction
+    /// requires a premium subscription to the application.
+    ///
+    /// This removes any previously set response data.
+    pub fn premium_required(&mut self) -> &mut Self {
+        self.0.remove("data");
+
+        #[cfg(feature = "http")]
+        self.1.clear();
+
+        self.kind(InteractionResponseType::PremiumRequired)
+    }
 }
 
-impl<'a> Default for CreateInteractionResponse {
+impl Default for CreateInteractionResponse {
     fn default() -> CreateInteractionResponse {
         let mut map = HashMap::new();
         map.insert("type", Value::Number(serde_json::Number::from(4)));
 
-        CreateInteractionResponse(map)
+        CreateInteractionResponse(map, Default::default())
     }
 }
 
 #[derive(Clone, Debug, Default)]
-pub struct CreateInteractionResponseData(pub HashMap<&'static str, Value>);
+pub struct CreateInteractionResponseData(
+    pub HashMap<&'static str, Value>,
+    #[cfg(feature = "http")] pub(crate) Vec<AttachmentType<'static>>,
+    #[cfg(not(feature = "http"))] (),
+);
 
 impl CreateInteractionResponseData {
     /// Set whether the message is text-to-speech.
@@ -147,11 +179,20 @@ impl CreateInteractionResponseData {
     }
 
     /// Sets the flags for the message.
+    ///
+    /// This replaces any flags which were set, including by [`Self::ephemeral`].
     pub fn flags(&mut self, flags: InteractionApplicationCommandCallbackDataFlags) -> &mut Self {
         self.0.insert("flags", Value::Number(serde_json::Number::from(flags.bits())));
         self
     }
 
+    /// Sets whether the message is only visible to the user who triggered the
+    /// interaction, keeping any other flags which were set.
+    pub fn ephemeral(&mut self, ephemeral: bool) -> &mut Self {
+        set_ephemeral(&mut self.0, ephemeral);
+        self
+    }
+
     /// Creates components for this message.
     pub fn components<F>(&mut self, f: F) -> &mut Self
     where
@@ -181,6 +222,65 @@ impl CreateInteractionResponseData {
         self.0.insert("title", Value::String(title.to_string()));
         self
     }
+
+    /// Appends a file to the message.
+    ///
+    /// The file must not borrow data, such as a path which is not `'static`;
+    /// use an owned [`AttachmentType`] instead.
+    ///
+    /// When updating the message of a component with an
+    /// [`InteractionResponseType::UpdateMessage`] response, the file is added
+    /// to the files the message already has, unless
+    /// [`Self::keep_attachment`] or [`Self::clear_attachments`] were used.
+    #[cfg(feature = "http")]
+    pub fn add_file<T: Into<AttachmentType<'static>>>(&mut self, file: T) -> &mut Self {
+        self.1.push(file.into());
+        self
+    }
+
+    /// Appends a list of files to the message.
+    #[cfg(feature = "http")]
+    pub fn add_files<T: Into<AttachmentType<'static>>, It: IntoIterator<Item = T>>(
+        &mut self,
+        files: It,
+    ) -> &mut Self {
+        self.1.extend(files.into_iter().map(|f| f.into()));
+        self
+    }
+
+    /// Sets a list of files to include in the message.
+    ///
+    /// Calling this multiple times will overwrite the file list.
+    /// To append files, call [`Self::add_file`] or [`Self::add_files`] instead.
+    #[cfg(feature = "http")]
+    pub fn files<T: Into<AttachmentType<'static>>, It: IntoIterator<Item = T>>(
+        &mut self,
+        files: It,
+    ) -> &mut Self {
+        self.1 = files.into_iter().map(|f| f.into()).collect();
+        self
+    }
+
+    /// Keeps an existing attachment of the message when updating it.
+    ///
+    /// Once an attachment is kept, the attachments of the message which are
+    /// not kept are removed, the same as when editing a message.
+    pub fn keep_attachment(&mut self, id: impl Into<AttachmentId>) -> &mut Self {
+        let attachments = self.0.entry("attachments").or_insert_with(|| Value::Array(vec![]));
+
+        if let Some(attachments) = attachments.as_array_mut() {
+            attachments.push(json!({"id": id.into().0.to_string()}));
+        }
+
+        self
+    }
+
+    /// Removes all existing attachments of the message when updating it, so
+    /// that it only has the files added to the response.
+    pub fn clear_attachments(&mut self) -> &mut Self {
+        self.0.insert("attachments", Value::Array(vec![]));
+        self
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -246,3 +346,63 @@ impl CreateAutocompleteResponse {
         self
     }
 }
+
+/// Sets or removes the [`EPHEMERAL`] flag of a message, keeping its other
+/// flags.
+///
+/// [`EPHEMERAL`]: InteractionApplicationCommandCallbackDataFlags::EPHEMERAL
+pub(super) fn set_ephemeral(map: &mut HashMap<&'static str, Value>, ephemeral: bool) {
+    let flag = InteractionApplicationCommandCallbackDataFlags::EPHEMERAL.bits();
+    let flags = map.get("flags").and_then(Value::as_u64).unwrap_or_default();
+
+    let flags = if ephemeral {
+        flags | flag
+    } else {
+        flags & !flag
+    };
+
+    map.insert("flags", Value::Number(serde_json::Number::from(flags)));
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{CreateInteractionResponse, CreateInteractionResponseData};
+    use crate::model::id::AttachmentId;
+    use crate::model::interactions::{
+        InteractionApplicationCommandCallbackDataFlags,
+        InteractionResponseType,
+    };
+
+    #[test]
+    fn test_ephemeral_keeps_flags() {
+        let mut data = CreateInteractionResponseData::default();
+
+        data.flags(InteractionApplicationCommandCallbackDataFlags::SUPPRESS_EMBEDS).ephemeral(true);
+        assert_eq!(data.0["flags"], 1 << 2 | 1 << 6);
+
+        data.ephemeral(false);
+        assert_eq!(data.0["flags"], 1 << 2);
+
+        let mut data = CreateInteractionResponseData::default();
+        data.ephemeral(true);
+        assert_eq!(data.0["flags"], 1 << 6);
+    }
+
+    #[test]
+    fn test_update_message_attachments() {
+        let mut response = CreateInteractionResponse::default();
+        response.kind(InteractionResponseType::UpdateMessage).interaction_response_data(|d| {
+            d.keep_attachment(AttachmentId(1)).keep_attachment(2).add_file("./image.png")
+        });
+
+        assert_eq!(response.0["type"], 7);
+        assert_eq!(response.0["data"], json!({"attachments": [{"id": "1"}, {"id": "2"}]}));
+        assert_eq!(response.1.len(), 1);
+
+        response.interaction_response_data(|d| d.clear_attachments());
+        assert_eq!(response.0["data"], json!({"attachments": []}));
+        assert!(response.1.is_empty());
+    }
+}
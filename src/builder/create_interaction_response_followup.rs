@@ -4,6 +4,7 @@ use std::marker::PhantomData;
 
 use serde_json::Value;
 
+use super::create_interaction_response::set_ephemeral;
 use super::{CreateAllowedMentions, CreateEmbed};
 use crate::builder::CreateComponents;
 #[cfg(feature = "http")]
@@ -166,11 +167,20 @@ impl<'a> CreateInteractionResponseFollowup<'a> {
     }
 
     /// Sets the flags for the response.
+    ///
+    /// This replaces any flags which were set, including by [`Self::ephemeral`].
     pub fn flags(&mut self, flags: InteractionApplicationCommandCallbackDataFlags) -> &mut Self {
         self.0.insert("flags", Value::Number(serde_json::Number::from(flags.bits())));
         self
     }
 
+    /// Sets whether the response is only visible to the user who triggered the
+    /// interaction, keeping any other flags which were set.
+    pub fn ephemeral(&mut self, ephemeral: bool) -> &mut Self {
+        set_ephemeral(&mut self.0, ephemeral);
+        self
+    }
+
     /// Creates components for this message.
     pub fn components<F>(&mut self, f: F) -> &mut Self
     where
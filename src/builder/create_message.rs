@@ -1,11 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 #[cfg(not(feature = "http"))]
 use std::marker::PhantomData;
 
+use serde::de::{Deserialize, Deserializer, Error as DeError};
+use serde::ser::{Serialize, Serializer};
+
+use super::create_embed::known_keys;
 use super::CreateAllowedMentions;
 use super::CreateEmbed;
 #[cfg(feature = "unstable_discord_api")]
 use crate::builder::CreateComponents;
+#[cfg(all(feature = "model", feature = "utils"))]
+use crate::constants::limits;
 #[cfg(feature = "http")]
 use crate::http::AttachmentType;
 use crate::internal::prelude::*;
@@ -62,6 +68,7 @@ pub struct CreateMessage<'a>(
     pub Option<Vec<ReactionType>>,
     #[cfg(feature = "http")] pub Vec<AttachmentType<'a>>,
     #[cfg(not(feature = "http"))] PhantomData<&'a ()>,
+    pub(crate) Option<SplitStrategy>,
 );
 
 impl<'a> CreateMessage<'a> {
@@ -255,6 +262,267 @@ impl<'a> CreateMessage<'a> {
         self.0.insert("flags", Value::Number(serde_json::Number::from(flags.bits)));
         self
     }
+
+    /// Splits the content over multiple messages if it is too long, instead
+    /// of failing to send the message.
+    ///
+    /// Only the first message carries the embeds, components, files,
+    /// reactions and message reference; every message keeps the
+    /// [`Self::allowed_mentions`], [`Self::tts`] and [`Self::flags`].
+    ///
+    /// **Note**: This only has an effect when sending the message with
+    /// [`ChannelId::send_message_multi`].
+    ///
+    /// [`ChannelId::send_message_multi`]: crate::model::id::ChannelId::send_message_multi
+    pub fn split_on_overflow(&mut self, strategy: SplitStrategy) -> &mut Self {
+        self.3 = Some(strategy);
+        self
+    }
+
+    /// Overlays another partially built message onto this one, e.g. to fill
+    /// in a template loaded from a config file.
+    ///
+    /// Every field set on `other` replaces the one of this message as a whole,
+    /// so its embeds replace the embeds of this message rather than being
+    /// added to them. Its reactions, files and split strategy are taken if it
+    /// has any.
+    ///
+    /// **Note**: A message created with [`Default`] sets [`Self::tts`] to
+    /// `false`, which then also replaces the one of this message. Messages
+    /// deserialized from JSON only contain the fields present in it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::builder::CreateMessage;
+    ///
+    /// let template: CreateMessage<'_> = serde_json::from_str(
+    ///     r#"{"content": "Welcome!", "embeds": [{"title": "Getting started"}]}"#,
+    /// )?;
+    ///
+    /// let mut message: CreateMessage<'_> = serde_json::from_str(r#"{"content": "Hi!"}"#)?;
+    /// message.merge(&template);
+    ///
+    /// assert_eq!(message.0["content"], "Welcome!");
+    /// assert_eq!(message.0["embeds"][0]["title"], "Getting started");
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn merge(&mut self, other: &CreateMessage<'a>) -> &mut Self {
+        self.0.extend(other.0.iter().map(|(key, value)| (*key, value.clone())));
+
+        if other.1.is_some() {
+            self.1 = other.1.clone();
+        }
+
+        #[cfg(feature = "http")]
+        if !other.2.is_empty() {
+            self.2 = other.2.clone();
+        }
+
+        if other.3.is_some() {
+            self.3 = other.3;
+        }
+
+        self
+    }
+}
+
+/// The fields of a [`CreateMessage`] which are sent as JSON.
+const MESSAGE_KEYS: &[&str] =
+    &["allowed_mentions", "components", "content", "embeds", "flags", "message_reference", "tts"];
+
+/// Validates a field of a message, normalising the embeds.
+fn validate_message_value<E: DeError>(key: &str, value: Value) -> StdResult<Value, E> {
+    let valid = match key {
+        "content" => value.is_string(),
+        "tts" => value.is_boolean(),
+        "flags" => value.is_u64(),
+        "allowed_mentions" => value.is_object(),
+        "message_reference" => serde_json::from_value::<MessageReference>(value.clone()).is_ok(),
+        #[cfg(feature = "unstable_discord_api")]
+        "components" => serde_json::from_value::<CreateComponents>(value.clone()).is_ok(),
+        #[cfg(not(feature = "unstable_discord_api"))]
+        "components" => value.is_array(),
+        "embeds" => {
+            let embeds = Vec::<CreateEmbed>::deserialize(value).map_err(E::custom)?;
+            let embeds = embeds
+                .into_iter()
+                .map(|embed| Value::Object(utils::hashmap_to_json_map(embed.0)))
+                .collect();
+
+            return Ok(Value::Array(embeds));
+        },
+        _ => false,
+    };
+
+    if valid {
+        Ok(value)
+    } else {
+        Err(E::custom(format_args!("invalid message {}: {}", key, value)))
+    }
+}
+
+impl<'a> Serialize for CreateMessage<'a> {
+    /// Serializes the JSON fields of the message. Its reactions, files and
+    /// split strategy are not serialized.
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().collect::<BTreeMap<_, _>>())
+    }
+}
+
+impl<'de, 'a> Deserialize<'de> for CreateMessage<'a> {
+    /// Deserializes a partially built message, e.g. a template stored in a
+    /// config file, validating its fields.
+    ///
+    /// Unlike with [`Default`], [`CreateMessage::tts`] is only set if present.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        let map = known_keys(JsonMap::deserialize(deserializer)?, MESSAGE_KEYS)?
+            .into_iter()
+            .map(|(key, value)| Ok((key, validate_message_value(key, value)?)))
+            .collect::<StdResult<_, D::Error>>()?;
+
+        Ok(CreateMessage(map, None, Default::default(), None))
+    }
+}
+
+/// Keys of a message which are sent with every part of a split message.
+#[cfg(all(feature = "model", feature = "utils"))]
+const INHERITED_KEYS: [&str; 3] = ["allowed_mentions", "flags", "tts"];
+
+/// Splits a message's content over as many messages as needed to keep each
+/// under the length limit, according to the given strategy.
+///
+/// The first message keeps everything but the remaining content, the others
+/// only contain their part of the content and the [`INHERITED_KEYS`].
+#[cfg(all(feature = "model", feature = "utils"))]
+pub(crate) fn split_message(mut map: JsonMap, strategy: SplitStrategy) -> Vec<JsonMap> {
+    let content = match map.get("content") {
+        Some(Value::String(content))
+            if content.chars().count() > limits::MESSAGE_CONTENT_LENGTH =>
+        {
+            content.clone()
+        },
+        _ => return vec![map],
+    };
+
+    let mut chunks = strategy.split(&content, limits::MESSAGE_CONTENT_LENGTH).into_iter();
+
+    let mut inherited = JsonMap::new();
+    for key in &INHERITED_KEYS {
+        if let Some(value) = map.get(*key) {
+            inherited.insert((*key).to_string(), value.clone());
+        }
+    }
+
+    map.insert("content".to_string(), Value::from(chunks.next().unwrap_or_default()));
+
+    let mut maps = vec![map];
+    maps.extend(chunks.map(|chunk| {
+        let mut map = inherited.clone();
+        map.insert("content".to_string(), Value::from(chunk));
+        map
+    }));
+
+    maps
+}
+
+/// Where to split the content of a message that is too long to be sent as
+/// one, used with [`CreateMessage::split_on_overflow`].
+///
+/// Every strategy falls back to splitting at whitespace and finally at any
+/// character if it cannot find a suitable boundary.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SplitStrategy {
+    /// Splits at the last line break that fits.
+    Line,
+    /// Splits at the last whitespace that fits.
+    Word,
+    /// Splits at the last line break that fits. A code block that is open at
+    /// the split is closed and reopened, with the same language, in the next
+    /// part.
+    CodeBlock,
+}
+
+impl SplitStrategy {
+    /// Splits the content into parts of at most `limit` characters.
+    ///
+    /// The line break or whitespace a part was split at is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::builder::SplitStrategy;
+    ///
+    /// let parts = SplitStrategy::Word.split("lorem ipsum dolor", 12);
+    /// assert_eq!(parts, ["lorem ipsum", "dolor"]);
+    /// ```
+    #[must_use]
+    pub fn split(self, content: &str, limit: usize) -> Vec<String> {
+        const FENCE: &str = "```";
+
+        let mut parts = Vec::new();
+        let mut rest = content;
+        // The opening line of a code block left open by the previous part.
+        let mut open_fence: Option<String> = None;
+
+        while !rest.is_empty() {
+            let prefix =
+                open_fence.as_ref().map(|fence| format!("{}\n", fence)).unwrap_or_default();
+            let prefix_len = prefix.chars().count();
+
+            if prefix_len + rest.chars().count() <= limit {
+                parts.push(prefix + rest);
+                break;
+            }
+
+            // Leave room for closing a code block.
+            let reserved = if self == Self::CodeBlock { FENCE.len() + 1 } else { 0 };
+            let budget = limit.saturating_sub(prefix_len + reserved).max(1);
+            let window_end = rest.char_indices().nth(budget).map_or(rest.len(), |(i, _)| i);
+            // A separator right after the window still allows a full part.
+            let search_end =
+                rest[window_end..].chars().next().map_or(window_end, |c| window_end + c.len_utf8());
+            let window = &rest[..search_end];
+
+            let line_break = || window.rfind('\n').filter(|&i| i > 0);
+            let whitespace =
+                || window.char_indices().rev().find(|&(i, c)| i > 0 && c.is_whitespace());
+
+            let (end, next) = match self {
+                Self::Line | Self::CodeBlock => line_break().map(|i| (i, i + 1)),
+                Self::Word => None,
+            }
+            .or_else(|| whitespace().map(|(i, c)| (i, i + c.len_utf8())))
+            .unwrap_or((window_end, window_end));
+
+            let mut part = prefix + &rest[..end];
+            rest = &rest[next..];
+
+            if self == Self::CodeBlock {
+                // The part starts with the reopened code block, if any.
+                open_fence = None;
+
+                for line in part.lines() {
+                    if line.starts_with(FENCE) {
+                        open_fence = match open_fence {
+                            Some(_) => None,
+                            None => Some(line.to_string()),
+                        };
+                    }
+                }
+
+                if open_fence.is_some() {
+                    part.push('\n');
+                    part.push_str(FENCE);
+                }
+            }
+
+            parts.push(part);
+        }
+
+        parts
+    }
 }
 
 impl<'a> Default for CreateMessage<'a> {
@@ -266,6 +534,174 @@ impl<'a> Default for CreateMessage<'a> {
         let mut map = HashMap::new();
         map.insert("tts", Value::Bool(false));
 
-        CreateMessage(map, None, Default::default())
+        CreateMessage(map, None, Default::default(), None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    #[cfg(all(feature = "model", feature = "utils"))]
+    use super::split_message;
+    use super::{CreateMessage, SplitStrategy};
+    #[cfg(all(feature = "model", feature = "utils"))]
+    use crate::constants::limits::MESSAGE_CONTENT_LENGTH;
+    #[cfg(all(feature = "model", feature = "utils"))]
+    use crate::internal::prelude::*;
+    #[cfg(feature = "model")]
+    use crate::model::channel::MessageFlags;
+
+    #[test]
+    fn test_split_line() {
+        let parts = SplitStrategy::Line.split("first line\nsecond line\nthird", 24);
+        assert_eq!(parts, ["first line\nsecond line", "third"]);
+
+        // Falls back to whitespace, then to a hard cut.
+        let parts = SplitStrategy::Line.split("no line breaks here", 10);
+        assert_eq!(parts, ["no line", "breaks", "here"]);
+
+        let parts = SplitStrategy::Line.split("abcdefghij", 4);
+        assert_eq!(parts, ["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn test_split_word() {
+        assert_eq!(SplitStrategy::Word.split("lorem ipsum dolor", 11), ["lorem ipsum", "dolor"]);
+        assert_eq!(SplitStrategy::Word.split("lorem\nipsum dolor", 12), ["lorem\nipsum", "dolor"]);
+        assert_eq!(SplitStrategy::Word.split("short", 2000), ["short"]);
+    }
+
+    #[test]
+    fn test_split_counts_chars() {
+        let parts = SplitStrategy::Word.split("äöü äöü äöü", 7);
+        assert_eq!(parts, ["äöü äöü", "äöü"]);
+
+        let parts = SplitStrategy::Line.split("🦀🦀🦀🦀🦀", 2);
+        assert_eq!(parts, ["🦀🦀", "🦀🦀", "🦀"]);
+    }
+
+    #[test]
+    fn test_split_code_block() {
+        let content = "intro\n```rust\nlet a = 1;\nlet b = 2;\n```\noutro";
+        let parts = SplitStrategy::CodeBlock.split(content, 30);
+
+        assert_eq!(parts, ["intro\n```rust\nlet a = 1;\n```", "```rust\nlet b = 2;\n```\noutro"]);
+        assert!(parts.iter().all(|part| part.chars().count() <= 30));
+    }
+
+    #[test]
+    #[cfg(all(feature = "model", feature = "utils"))]
+    fn test_split_message() {
+        let content = "word ".repeat(MESSAGE_CONTENT_LENGTH / 4);
+
+        let map = json!({
+            "content": content,
+            "tts": false,
+            "allowed_mentions": {"parse": []},
+            "embeds": [{"title": "embed"}],
+            "message_reference": {"message_id": "1"},
+        });
+        let map = match map {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        };
+
+        let maps = split_message(map, SplitStrategy::Word);
+        assert_eq!(maps.len(), 2);
+
+        for map in &maps {
+            assert_eq!(map["allowed_mentions"], json!({"parse": []}));
+            assert_eq!(map["tts"], json!(false));
+
+            let content = map["content"].as_str().map_or(0, |content| content.chars().count());
+            assert!(content <= MESSAGE_CONTENT_LENGTH);
+        }
+
+        assert!(maps[0].contains_key("embeds"));
+        assert!(maps[0].contains_key("message_reference"));
+        assert!(!maps[1].contains_key("embeds"));
+        assert!(!maps[1].contains_key("message_reference"));
+    }
+
+    #[test]
+    #[cfg(all(feature = "model", feature = "utils"))]
+    fn test_split_message_short() {
+        let mut map = JsonMap::new();
+        map.insert("content".to_string(), Value::from("short"));
+
+        let maps = split_message(map.clone(), SplitStrategy::Line);
+        assert_eq!(maps, [map]);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_message_round_trip() {
+        let mut message = CreateMessage::default();
+        message.content("hello").tts(true);
+        #[cfg(feature = "model")]
+        message.flags(MessageFlags::SUPPRESS_EMBEDS);
+        message.embed(|e| e.title("title").field("a", "b", false));
+        message.allowed_mentions(|am| am.empty_parse());
+
+        let value = serde_json::to_value(&message).unwrap();
+        assert_eq!(value["embeds"][0]["type"], "rich");
+
+        let parsed: CreateMessage<'_> = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.0, message.0);
+        assert!(parsed.1.is_none());
+    }
+
+    #[test]
+    fn test_invalid_message() {
+        let invalid = [
+            json!({"unknown": "field"}),
+            json!({"content": 5}),
+            json!({"tts": "yes"}),
+            json!({"flags": -1}),
+            json!({"embeds": {"title": "not an array"}}),
+            json!({"embeds": [{"colour": 5}]}),
+            json!({"message_reference": "not an object"}),
+        ];
+
+        for value in &invalid {
+            assert!(
+                serde_json::from_value::<CreateMessage<'_>>(value.clone()).is_err(),
+                "{} was accepted",
+                value
+            );
+        }
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_merge() {
+        let mut template: CreateMessage<'_> = serde_json::from_value(json!({
+            "content": "template",
+            "tts": true,
+            "embeds": [{"title": "template"}],
+        }))
+        .unwrap();
+        template.reactions(vec!['👍']);
+
+        let mut message: CreateMessage<'_> =
+            serde_json::from_value(json!({"content": "message", "embeds": []})).unwrap();
+        message.split_on_overflow(SplitStrategy::Word);
+        message.merge(&template);
+
+        assert_eq!(message.0["content"], "template");
+        assert_eq!(message.0["tts"], true);
+        assert_eq!(message.0["embeds"][0]["title"], "template");
+        assert_eq!(message.1.as_ref().map(Vec::len), Some(1));
+        assert_eq!(message.3, Some(SplitStrategy::Word));
+
+        let mut overlay: CreateMessage<'_> =
+            serde_json::from_value(json!({"content": "overlay"})).unwrap();
+        overlay.split_on_overflow(SplitStrategy::Line);
+        template.merge(&overlay);
+
+        assert_eq!(template.0["content"], "overlay");
+        assert_eq!(template.0["tts"], true);
+        assert_eq!(template.3, Some(SplitStrategy::Line));
     }
 }
@@ -0,0 +1,499 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+use super::Timestamp;
+use crate::internal::prelude::*;
+use crate::model::guild::{
+    RecurrenceRuleFrequency,
+    RecurrenceRuleMonth,
+    RecurrenceRuleNWeekday,
+    RecurrenceRuleWeekday,
+    ScheduledEventType,
+    ScheduledEventViolation,
+};
+use crate::model::id::ChannelId;
+
+/// A builder for creating a [`ScheduledEvent`] in a guild.
+///
+/// Stage and voice events require a [`Self::channel_id`], while external
+/// events require a [`Self::location`] and an [`Self::end_time`] instead.
+///
+/// [`ScheduledEvent`]: crate::model::guild::ScheduledEvent
+#[derive(Clone, Debug)]
+pub struct CreateScheduledEvent(pub HashMap<&'static str, Value>);
+
+impl CreateScheduledEvent {
+    /// Sets the name of the event.
+    ///
+    /// **Note**: Must be between 1 and 100 characters long.
+    pub fn name<D: ToString>(&mut self, name: D) -> &mut Self {
+        self.0.insert("name", Value::String(name.to_string()));
+
+        self
+    }
+
+    /// Sets the description of the event.
+    ///
+    /// **Note**: Must be at most 1000 characters long.
+    pub fn description<D: ToString>(&mut self, description: D) -> &mut Self {
+        self.0.insert("description", Value::String(description.to_string()));
+
+        self
+    }
+
+    /// Sets the kind of the event, telling where it takes place.
+    pub fn kind(&mut self, kind: ScheduledEventType) -> &mut Self {
+        self.0.insert("entity_type", Value::Number(Number::from(kind as u8)));
+
+        self
+    }
+
+    /// Sets the stage or voice channel the event takes place in.
+    pub fn channel_id<C: Into<ChannelId>>(&mut self, channel_id: C) -> &mut Self {
+        self.0.insert("channel_id", Value::String(channel_id.into().0.to_string()));
+
+        self
+    }
+
+    /// Sets the location of an external event.
+    ///
+    /// **Note**: Must be between 1 and 100 characters long.
+    pub fn location<D: ToString>(&mut self, location: D) -> &mut Self {
+        self.0.insert("entity_metadata", json!({ "location": location.to_string() }));
+
+        self
+    }
+
+    /// Sets the time the event starts at.
+    pub fn start_time<T: Into<Timestamp>>(&mut self, timestamp: T) -> &mut Self {
+        let timestamp = timestamp.into();
+        self.0.insert("scheduled_start_time", Value::String(timestamp.to_string()));
+
+        self
+    }
+
+    /// Sets the time the event ends at.
+    pub fn end_time<T: Into<Timestamp>>(&mut self, timestamp: T) -> &mut Self {
+        let timestamp = timestamp.into();
+        self.0.insert("scheduled_end_time", Value::String(timestamp.to_string()));
+
+        self
+    }
+
+    /// Makes the event repeat by the rule built with the given closure.
+    ///
+    /// Refer to [`CreateRecurrenceRule`] for which rules Discord accepts.
+    pub fn recurrence<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut CreateRecurrenceRule) -> &mut CreateRecurrenceRule,
+    {
+        let mut rule = CreateRecurrenceRule::default();
+        f(&mut rule);
+
+        let map = rule.0.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+        self.0.insert("recurrence_rule", Value::Object(map));
+
+        self
+    }
+
+    /// Checks the event for fields Discord requires or rejects, without
+    /// sending it.
+    ///
+    /// The following is checked:
+    /// - the name, the start time and the kind are set;
+    /// - stage and voice events have a channel;
+    /// - external events have a location and an end time, but no channel;
+    /// - the recurrence rule, if any, is accepted by Discord, as described by
+    ///   [`CreateRecurrenceRule`].
+    ///
+    /// This is done automatically when creating the event.
+    ///
+    /// # Errors
+    ///
+    /// Returns every [`ScheduledEventViolation`] which was found, if any.
+    pub fn build_check(&self) -> StdResult<(), Vec<ScheduledEventViolation>> {
+        let mut violations = Vec::new();
+
+        for field in &["name", "scheduled_start_time", "entity_type"] {
+            if self.is_unset(field) {
+                violations.push(ScheduledEventViolation::MissingField(field));
+            }
+        }
+
+        let kind = self.0.get("entity_type").and_then(|v| serde_json::from_value(v.clone()).ok());
+        match kind {
+            Some(ScheduledEventType::StageInstance) | Some(ScheduledEventType::Voice)
+                if self.is_unset("channel_id") =>
+            {
+                violations.push(ScheduledEventViolation::MissingField("channel_id"));
+            },
+            Some(ScheduledEventType::External) => {
+                if !self.is_unset("channel_id") {
+                    violations.push(ScheduledEventViolation::UnexpectedField("channel_id"));
+                }
+
+                let location = self.0.get("entity_metadata").and_then(|m| m.get("location"));
+                if location.map_or(true, Value::is_null) {
+                    violations
+                        .push(ScheduledEventViolation::MissingField("entity_metadata.location"));
+                }
+
+                if self.is_unset("scheduled_end_time") {
+                    violations.push(ScheduledEventViolation::MissingField("scheduled_end_time"));
+                }
+            },
+            _ => {},
+        }
+
+        if let Some(Value::Object(rule)) = self.0.get("recurrence_rule") {
+            recurrence_violations(rule, &mut violations);
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    fn is_unset(&self, field: &str) -> bool {
+        self.0.get(field).map_or(true, Value::is_null)
+    }
+}
+
+impl Default for CreateScheduledEvent {
+    /// Creates a builder for an event which is only visible to members of the
+    /// guild, as Discord does not support any other privacy level.
+    fn default() -> Self {
+        let mut map = HashMap::new();
+        map.insert("privacy_level", Value::Number(Number::from(2)));
+
+        CreateScheduledEvent(map)
+    }
+}
+
+/// A builder for the [`RecurrenceRule`] of a scheduled event, set by
+/// [`CreateScheduledEvent::recurrence`].
+///
+/// Discord only accepts the following rules:
+/// - daily events, optionally on Monday to Friday, Tuesday to Saturday, Sunday
+///   to Thursday, Friday and Saturday, Saturday and Sunday, or Sunday and
+///   Monday;
+/// - weekly events on a single weekday, every week or every other week;
+/// - monthly events on a single day of a specific week, such as the second
+///   Tuesday;
+/// - yearly events on a single day of a single month.
+///
+/// Except for weekly events, the interval must be `1`.
+///
+/// [`RecurrenceRule`]: crate::model::guild::RecurrenceRule
+#[derive(Clone, Debug)]
+pub struct CreateRecurrenceRule(pub HashMap<&'static str, Value>);
+
+impl CreateRecurrenceRule {
+    /// Sets the time the first occurrence starts at.
+    pub fn start<T: Into<Timestamp>>(&mut self, timestamp: T) -> &mut Self {
+        let timestamp = timestamp.into();
+        self.0.insert("start", Value::String(timestamp.to_string()));
+
+        self
+    }
+
+    /// Sets how often the event repeats.
+    pub fn frequency(&mut self, frequency: RecurrenceRuleFrequency) -> &mut Self {
+        self.0.insert("frequency", Value::Number(Number::from(frequency as u8)));
+
+        self
+    }
+
+    /// Sets the number of [`Self::frequency`] units between occurrences.
+    ///
+    /// **Note**: This defaults to `1`.
+    pub fn interval(&mut self, interval: u64) -> &mut Self {
+        self.0.insert("interval", Value::Number(Number::from(interval)));
+
+        self
+    }
+
+    /// Sets the days of the week the event repeats on.
+    pub fn by_weekday<It>(&mut self, weekdays: It) -> &mut Self
+    where
+        It: IntoIterator<Item = RecurrenceRuleWeekday>,
+    {
+        let weekdays = weekdays.into_iter().map(|day| Value::from(day as u8)).collect();
+        self.0.insert("by_weekday", Value::Array(weekdays));
+
+        self
+    }
+
+    /// Sets the days of specific weeks of a month the event repeats on.
+    pub fn by_n_weekday<It>(&mut self, weekdays: It) -> &mut Self
+    where
+        It: IntoIterator<Item = RecurrenceRuleNWeekday>,
+    {
+        let weekdays =
+            weekdays.into_iter().map(|day| json!({"n": day.n, "day": day.day})).collect();
+        self.0.insert("by_n_weekday", Value::Array(weekdays));
+
+        self
+    }
+
+    /// Sets the months the event repeats in.
+    pub fn by_month<It>(&mut self, months: It) -> &mut Self
+    where
+        It: IntoIterator<Item = RecurrenceRuleMonth>,
+    {
+        let months = months.into_iter().map(|month| Value::from(month as u8)).collect();
+        self.0.insert("by_month", Value::Array(months));
+
+        self
+    }
+
+    /// Sets the days of the month the event repeats on.
+    pub fn by_month_day<It>(&mut self, days: It) -> &mut Self
+    where
+        It: IntoIterator<Item = u8>,
+    {
+        let days = days.into_iter().map(Value::from).collect();
+        self.0.insert("by_month_day", Value::Array(days));
+
+        self
+    }
+}
+
+impl Default for CreateRecurrenceRule {
+    /// Creates a rule with an [`Self::interval`] of `1`.
+    fn default() -> Self {
+        let mut map = HashMap::new();
+        map.insert("interval", Value::Number(Number::from(1)));
+
+        CreateRecurrenceRule(map)
+    }
+}
+
+/// The sets of weekdays Discord accepts for daily events, in ascending order.
+const DAILY_WEEKDAYS: &[&[u64]] = &[
+    &[0, 1, 2, 3, 4],
+    &[1, 2, 3, 4, 5],
+    &[0, 1, 2, 3, 6],
+    &[4, 5],
+    &[5, 6],
+    &[0, 6],
+];
+
+/// Finds every violation of a serialized recurrence rule.
+fn recurrence_violations(rule: &JsonMap, violations: &mut Vec<ScheduledEventViolation>) {
+    const FIELDS: &[(&str, &str)] = &[
+        ("end", "recurrence_rule.end"),
+        ("count", "recurrence_rule.count"),
+        ("by_year_day", "recurrence_rule.by_year_day"),
+        ("by_weekday", "recurrence_rule.by_weekday"),
+        ("by_n_weekday", "recurrence_rule.by_n_weekday"),
+        ("by_month", "recurrence_rule.by_month"),
+        ("by_month_day", "recurrence_rule.by_month_day"),
+    ];
+
+    let get = |field: &str| rule.get(field).filter(|v| !v.is_null());
+
+    if get("start").is_none() {
+        violations.push(ScheduledEventViolation::MissingField("recurrence_rule.start"));
+    }
+
+    let frequency = match get("frequency").and_then(|v| serde_json::from_value(v.clone()).ok()) {
+        Some(frequency) => frequency,
+        None => {
+            violations.push(ScheduledEventViolation::MissingField("recurrence_rule.frequency"));
+
+            return;
+        },
+    };
+
+    let interval = get("interval").and_then(Value::as_u64).unwrap_or(1);
+    let max_interval = if frequency == RecurrenceRuleFrequency::Weekly { 2 } else { 1 };
+    if interval == 0 || interval > max_interval {
+        violations.push(ScheduledEventViolation::InvalidRecurrenceInterval {
+            interval,
+            frequency,
+        });
+    }
+
+    // The fields which must be set for the frequency, and the fields which may.
+    let (required, optional): (&[&str], &[&str]) = match frequency {
+        RecurrenceRuleFrequency::Daily => (&[], &["by_weekday"]),
+        RecurrenceRuleFrequency::Weekly => (&["by_weekday"], &[]),
+        RecurrenceRuleFrequency::Monthly => (&["by_n_weekday"], &[]),
+        RecurrenceRuleFrequency::Yearly => (&["by_month", "by_month_day"], &[]),
+        _ => (&[], &[]),
+    };
+
+    for &(field, path) in FIELDS {
+        let value = match get(field) {
+            Some(value) => value,
+            None => {
+                if required.contains(&field) {
+                    violations.push(ScheduledEventViolation::MissingField(path));
+                }
+
+                continue;
+            },
+        };
+
+        if !required.contains(&field) && !optional.contains(&field) {
+            violations.push(ScheduledEventViolation::UnsupportedRecurrenceField {
+                field: path,
+                frequency,
+            });
+        } else if required.contains(&field) && value.as_array().map_or(true, |v| v.len() != 1) {
+            violations.push(ScheduledEventViolation::InvalidRecurrenceLength(path));
+        }
+    }
+
+    if frequency == RecurrenceRuleFrequency::Daily {
+        if let Some(weekdays) = get("by_weekday") {
+            let mut weekdays = weekdays
+                .as_array()
+                .map(|days| days.iter().filter_map(Value::as_u64).collect::<Vec<_>>())
+                .unwrap_or_default();
+            weekdays.sort_unstable();
+            weekdays.dedup();
+
+            if !DAILY_WEEKDAYS.contains(&&weekdays[..]) {
+                violations.push(ScheduledEventViolation::InvalidRecurrenceWeekdays);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CreateScheduledEvent, Timestamp};
+    use crate::model::guild::{
+        RecurrenceRuleFrequency as Frequency,
+        RecurrenceRuleMonth,
+        RecurrenceRuleNWeekday,
+        RecurrenceRuleWeekday as Weekday,
+        ScheduledEventType,
+        ScheduledEventViolation as Violation,
+    };
+
+    fn event(kind: ScheduledEventType) -> CreateScheduledEvent {
+        let start = Timestamp::parse("2024-05-06T18:00:00Z").unwrap();
+
+        let mut event = CreateScheduledEvent::default();
+        event.name("Meetup").kind(kind).start_time(start);
+        event
+    }
+
+    fn external() -> CreateScheduledEvent {
+        let mut event = event(ScheduledEventType::External);
+        event.location("The park").end_time(Timestamp::parse("2024-05-06T20:00:00Z").unwrap());
+        event
+    }
+
+    #[test]
+    fn test_event_kinds() {
+        assert_eq!(external().build_check(), Ok(()));
+
+        let mut unlocated = event(ScheduledEventType::External);
+        unlocated.channel_id(1);
+        assert_eq!(
+            unlocated.build_check(),
+            Err(vec![
+                Violation::UnexpectedField("channel_id"),
+                Violation::MissingField("entity_metadata.location"),
+                Violation::MissingField("scheduled_end_time"),
+            ])
+        );
+
+        for &kind in &[ScheduledEventType::StageInstance, ScheduledEventType::Voice] {
+            let mut event = event(kind);
+            assert_eq!(event.build_check(), Err(vec![Violation::MissingField("channel_id")]));
+
+            event.channel_id(1);
+            assert_eq!(event.build_check(), Ok(()));
+        }
+
+        let empty = CreateScheduledEvent::default();
+        assert_eq!(
+            empty.build_check(),
+            Err(vec![
+                Violation::MissingField("name"),
+                Violation::MissingField("scheduled_start_time"),
+                Violation::MissingField("entity_type"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_recurrence_rules() {
+        let start = Timestamp::parse("2024-05-06T18:00:00Z").unwrap();
+
+        let mut event = external();
+        event.recurrence(|r| {
+            r.start(start.clone())
+                .frequency(Frequency::Weekly)
+                .interval(2)
+                .by_weekday(vec![Weekday::Monday])
+        });
+        assert_eq!(event.build_check(), Ok(()));
+
+        event.recurrence(|r| {
+            r.start(start.clone())
+                .frequency(Frequency::Daily)
+                .by_weekday(vec![Weekday::Sunday, Weekday::Saturday])
+        });
+        assert_eq!(event.build_check(), Ok(()));
+
+        event.recurrence(|r| {
+            r.start(start.clone())
+                .frequency(Frequency::Yearly)
+                .by_month(vec![RecurrenceRuleMonth::May])
+                .by_month_day(vec![6])
+        });
+        assert_eq!(event.build_check(), Ok(()));
+
+        event.recurrence(|r| {
+            r.frequency(Frequency::Daily)
+                .interval(2)
+                .by_weekday(vec![Weekday::Monday, Weekday::Friday])
+        });
+        assert_eq!(
+            event.build_check(),
+            Err(vec![
+                Violation::MissingField("recurrence_rule.start"),
+                Violation::InvalidRecurrenceInterval {
+                    interval: 2,
+                    frequency: Frequency::Daily,
+                },
+                Violation::InvalidRecurrenceWeekdays,
+            ])
+        );
+
+        event.recurrence(|r| {
+            r.start(start.clone())
+                .frequency(Frequency::Monthly)
+                .by_n_weekday(vec![
+                    RecurrenceRuleNWeekday::new(1, Weekday::Monday),
+                    RecurrenceRuleNWeekday::new(3, Weekday::Monday),
+                ])
+                .by_month_day(vec![6])
+        });
+        assert_eq!(
+            event.build_check(),
+            Err(vec![
+                Violation::InvalidRecurrenceLength("recurrence_rule.by_n_weekday"),
+                Violation::UnsupportedRecurrenceField {
+                    field: "recurrence_rule.by_month_day",
+                    frequency: Frequency::Monthly,
+                },
+            ])
+        );
+
+        event.recurrence(|r| r.start(start.clone()).frequency(Frequency::Weekly));
+        assert_eq!(
+            event.build_check(),
+            Err(vec![Violation::MissingField("recurrence_rule.by_weekday")])
+        );
+    }
+}
@@ -1,7 +1,11 @@
 use std::collections::HashMap;
 
+#[cfg(feature = "unstable_discord_api")]
+use super::CreateMessage;
 use crate::internal::prelude::*;
 use crate::model::channel::ChannelType;
+#[cfg(feature = "unstable_discord_api")]
+use crate::model::id::ForumTagId;
 
 #[derive(Debug, Clone, Default)]
 pub struct CreateThread(pub HashMap<&'static str, Value>);
@@ -55,4 +59,82 @@ impl CreateThread {
 
         self
     }
+
+    /// Sets the starter message of a post in a forum or media channel.
+    ///
+    /// This is required when creating a post with
+    /// [`ChannelId::create_forum_post`], and ignored otherwise.
+    ///
+    /// **Note**: Attached files and reactions are not sent.
+    ///
+    /// [`ChannelId::create_forum_post`]: crate::model::id::ChannelId::create_forum_post
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn message<F>(&mut self, f: F) -> &mut Self
+    where
+        for<'b> F: FnOnce(&'b mut CreateMessage<'static>) -> &'b mut CreateMessage<'static>,
+    {
+        let mut message = CreateMessage::default();
+        f(&mut message);
+
+        let map = message.0.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+        self.0.insert("message", Value::Object(map));
+
+        self
+    }
+
+    /// Sets the tags to apply to a post in a forum or media channel.
+    ///
+    /// **Note**: If the channel [requires a tag], [`GuildChannel::create_forum_post`] returns
+    /// [`ModelError::ForumTagRequired`] unless at least one is set.
+    ///
+    /// [requires a tag]: crate::model::channel::GuildChannel::requires_tag
+    /// [`GuildChannel::create_forum_post`]: crate::model::channel::GuildChannel::create_forum_post
+    /// [`ModelError::ForumTagRequired`]: crate::model::error::Error::ForumTagRequired
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn applied_tags<It>(&mut self, tags: It) -> &mut Self
+    where
+        It: IntoIterator<Item = ForumTagId>,
+    {
+        let tags = tags.into_iter().map(|tag| Value::String(tag.0.to_string())).collect();
+
+        self.0.insert("applied_tags", Value::Array(tags));
+
+        self
+    }
+
+    /// Whether [`Self::applied_tags`] was set to at least one tag.
+    #[cfg(feature = "unstable_discord_api")]
+    pub(crate) fn has_applied_tags(&self) -> bool {
+        self.0.get("applied_tags").and_then(Value::as_array).map_or(false, |tags| !tags.is_empty())
+    }
+}
+
+#[cfg(all(test, feature = "unstable_discord_api"))]
+mod test {
+    use serde_json::json;
+
+    use super::CreateThread;
+    use crate::model::id::ForumTagId;
+
+    #[test]
+    fn test_forum_post_message() {
+        let mut builder = CreateThread::default();
+        builder.name("post").message(|m| m.content("hello"));
+
+        assert_eq!(builder.0["name"], "post");
+        assert_eq!(builder.0["message"], json!({"content": "hello", "tts": false}));
+    }
+
+    #[test]
+    fn test_applied_tags() {
+        let mut builder = CreateThread::default();
+        assert!(!builder.has_applied_tags());
+
+        builder.applied_tags(vec![]);
+        assert!(!builder.has_applied_tags());
+
+        builder.applied_tags(vec![ForumTagId(1), ForumTagId(2)]);
+        assert!(builder.has_applied_tags());
+        assert_eq!(builder.0["applied_tags"], json!(["1", "2"]));
+    }
 }
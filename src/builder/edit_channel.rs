@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use serde_json::{json, Value};
 
 use crate::internal::prelude::*;
+#[cfg(feature = "unstable_discord_api")]
+use crate::model::channel::{ChannelFlags, ForumLayout, SortOrder};
 use crate::model::channel::{PermissionOverwrite, PermissionOverwriteType, VideoQualityMode};
 use crate::model::id::ChannelId;
 
@@ -159,6 +161,48 @@ impl EditChannel {
         self
     }
 
+    /// The extra features of the channel.
+    ///
+    /// [`ChannelFlags::PINNED`] can only be set on posts in [forum] and [media] channels,
+    /// [`ChannelFlags::REQUIRE_TAG`] only on forum and media channels, and
+    /// [`ChannelFlags::HIDE_MEDIA_DOWNLOAD_OPTIONS`] only on media channels.
+    ///
+    /// **Note**: [`GuildChannel::edit`] returns [`ModelError::InvalidChannelFlags`] if a flag
+    /// cannot be set on the channel. Other ways of editing the channel do not check the flags.
+    ///
+    /// [forum]: crate::model::channel::ChannelType::Forum
+    /// [media]: crate::model::channel::ChannelType::Media
+    /// [`GuildChannel::edit`]: crate::model::channel::GuildChannel::edit
+    /// [`ModelError::InvalidChannelFlags`]: crate::model::error::Error::InvalidChannelFlags
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn flags(&mut self, flags: ChannelFlags) -> &mut Self {
+        self.0.insert("flags", Value::Number(Number::from(flags.bits())));
+        self
+    }
+
+    /// The default layout of the posts in the channel.
+    ///
+    /// This is for [forum] channels only.
+    ///
+    /// [forum]: crate::model::channel::ChannelType::Forum
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn default_forum_layout(&mut self, layout: ForumLayout) -> &mut Self {
+        self.0.insert("default_forum_layout", Value::Number(Number::from(layout as u8)));
+        self
+    }
+
+    /// The default order in which the posts in the channel are sorted.
+    ///
+    /// This is for [forum] and [media] channels only.
+    ///
+    /// [forum]: crate::model::channel::ChannelType::Forum
+    /// [media]: crate::model::channel::ChannelType::Media
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn default_sort_order(&mut self, order: SortOrder) -> &mut Self {
+        self.0.insert("default_sort_order", Value::Number(Number::from(order as u8)));
+        self
+    }
+
     /// A set of overwrites defining what a user or a user carrying a certain role can
     /// and cannot do.
     ///
@@ -324,4 +324,41 @@ impl EditGuild {
         self.0.insert("system_channel_flags", system_channel_flags.bits().into());
         self
     }
+
+    /// Sets or clears the given system channel flags, keeping the other flags
+    /// previously set via [`Self::system_channel_flags`].
+    ///
+    /// Flags not set through this builder are cleared by Discord, so the
+    /// guild's current [`Guild::system_channel_flags`] should be set first.
+    ///
+    /// ```rust,no_run
+    /// # use serenity::{http::Http, model::id::GuildId};
+    /// #
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let http = Http::default();
+    /// #     let mut guild = GuildId(0).to_partial_guild(&http).await?;
+    /// use serenity::model::guild::SystemChannelFlags;
+    ///
+    /// let flags = guild.system_channel_flags;
+    /// guild
+    ///     .edit(&http, |g| {
+    ///         g.system_channel_flags(flags)
+    ///             .system_channel_flag(SystemChannelFlags::SUPPRESS_JOIN_NOTIFICATIONS, true)
+    ///     })
+    ///     .await?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Guild::system_channel_flags`]: crate::model::guild::Guild::system_channel_flags
+    pub fn system_channel_flag(&mut self, flag: SystemChannelFlags, value: bool) -> &mut Self {
+        let mut flags = self
+            .0
+            .get("system_channel_flags")
+            .and_then(Value::as_u64)
+            .map_or_else(SystemChannelFlags::empty, SystemChannelFlags::from_bits_truncate);
+        flags.set(flag, value);
+
+        self.system_channel_flags(flags)
+    }
 }
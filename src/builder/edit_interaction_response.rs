@@ -1,15 +1,26 @@
 use std::collections::HashMap;
+#[cfg(not(feature = "http"))]
+use std::marker::PhantomData;
 
-use serde_json::Value;
+use serde_json::{json, Value};
 
+use super::create_interaction_response::set_ephemeral;
 use super::{CreateAllowedMentions, CreateEmbed};
 use crate::builder::CreateComponents;
+#[cfg(feature = "http")]
+use crate::http::AttachmentType;
+use crate::model::id::AttachmentId;
+use crate::model::interactions::InteractionApplicationCommandCallbackDataFlags;
 use crate::utils;
 
 #[derive(Clone, Debug, Default)]
-pub struct EditInteractionResponse(pub HashMap<&'static str, Value>);
+pub struct EditInteractionResponse<'a>(
+    pub HashMap<&'static str, Value>,
+    #[cfg(feature = "http")] pub(crate) Vec<AttachmentType<'a>>,
+    #[cfg(not(feature = "http"))] PhantomData<&'a ()>,
+);
 
-impl EditInteractionResponse {
+impl<'a> EditInteractionResponse<'a> {
     /// Sets the `InteractionApplicationCommandCallbackData` for the message.
 
     /// Set the content of the message.
@@ -99,6 +110,75 @@ impl EditInteractionResponse {
         self
     }
 
+    /// Sets the flags for the message.
+    ///
+    /// This replaces any flags which were set, including by [`Self::ephemeral`].
+    pub fn flags(&mut self, flags: InteractionApplicationCommandCallbackDataFlags) -> &mut Self {
+        self.0.insert("flags", Value::Number(serde_json::Number::from(flags.bits())));
+        self
+    }
+
+    /// Sets whether the message is only visible to the user who triggered the
+    /// interaction, keeping any other flags which were set.
+    ///
+    /// **Note**: Discord ignores changes to whether a message is ephemeral
+    /// once it was sent, which includes deferred responses.
+    pub fn ephemeral(&mut self, ephemeral: bool) -> &mut Self {
+        set_ephemeral(&mut self.0, ephemeral);
+        self
+    }
+
+    /// Appends a file to the message.
+    #[cfg(feature = "http")]
+    pub fn add_file<T: Into<AttachmentType<'a>>>(&mut self, file: T) -> &mut Self {
+        self.1.push(file.into());
+        self
+    }
+
+    /// Appends a list of files to the message.
+    #[cfg(feature = "http")]
+    pub fn add_files<T: Into<AttachmentType<'a>>, It: IntoIterator<Item = T>>(
+        &mut self,
+        files: It,
+    ) -> &mut Self {
+        self.1.extend(files.into_iter().map(|f| f.into()));
+        self
+    }
+
+    /// Sets a list of files to include in the message.
+    ///
+    /// Calling this multiple times will overwrite the file list.
+    /// To append files, call [`Self::add_file`] or [`Self::add_files`] instead.
+    #[cfg(feature = "http")]
+    pub fn files<T: Into<AttachmentType<'a>>, It: IntoIterator<Item = T>>(
+        &mut self,
+        files: It,
+    ) -> &mut Self {
+        self.1 = files.into_iter().map(|f| f.into()).collect();
+        self
+    }
+
+    /// Keeps an existing attachment of the message.
+    ///
+    /// Once an attachment is kept, the attachments of the message which are
+    /// not kept are removed. Files added to the message are always kept.
+    pub fn keep_attachment(&mut self, id: impl Into<AttachmentId>) -> &mut Self {
+        let attachments = self.0.entry("attachments").or_insert_with(|| Value::Array(vec![]));
+
+        if let Some(attachments) = attachments.as_array_mut() {
+            attachments.push(json!({"id": id.into().0.to_string()}));
+        }
+
+        self
+    }
+
+    /// Removes all existing attachments of the message, so that it only has
+    /// the files added to it.
+    pub fn clear_attachments(&mut self) -> &mut Self {
+        self.0.insert("attachments", Value::Array(vec![]));
+        self
+    }
+
     /// Sets the components of this message.
     #[cfg(feature = "unstable_discord_api")]
     pub fn components<F>(&mut self, f: F) -> &mut Self
@@ -112,3 +192,33 @@ impl EditInteractionResponse {
         self
     }
 }
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::EditInteractionResponse;
+    use crate::model::interactions::InteractionApplicationCommandCallbackDataFlags;
+
+    #[test]
+    fn test_ephemeral_keeps_flags() {
+        let mut response = EditInteractionResponse::default();
+        response
+            .flags(InteractionApplicationCommandCallbackDataFlags::SUPPRESS_EMBEDS)
+            .ephemeral(true);
+
+        assert_eq!(response.0["flags"], 1 << 2 | 1 << 6);
+    }
+
+    #[test]
+    fn test_keep_attachment() {
+        let mut response = EditInteractionResponse::default();
+        response.keep_attachment(1).keep_attachment(2);
+
+        assert_eq!(response.0["attachments"], json!([{"id": "1"}, {"id": "2"}]));
+
+        response.clear_attachments();
+
+        assert_eq!(response.0["attachments"], json!([]));
+    }
+}
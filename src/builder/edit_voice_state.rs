@@ -53,7 +53,8 @@ impl EditVoiceState {
         timestamp: Option<T>,
     ) -> &mut Self {
         if let Some(timestamp) = timestamp {
-            self.0.insert("request_to_speak_timestamp", Value::String(timestamp.into().ts));
+            self.0
+                .insert("request_to_speak_timestamp", Value::String(timestamp.into().to_string()));
         } else {
             self.0.insert("request_to_speak_timestamp", Value::Null);
         }
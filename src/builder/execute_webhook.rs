@@ -1,12 +1,21 @@
 use std::collections::HashMap;
 #[cfg(not(feature = "http"))]
 use std::marker::PhantomData;
+#[cfg(feature = "model")]
+use std::ops::Range;
 
 use serde_json::Value;
 
+use super::CreateAllowedMentions;
+#[cfg(feature = "unstable_discord_api")]
+use super::CreateComponents;
 #[cfg(feature = "http")]
 use crate::http::AttachmentType;
+#[cfg(feature = "model")]
+use crate::internal::prelude::JsonMap;
 use crate::model::channel::MessageFlags;
+use crate::model::id::ForumTagId;
+use crate::utils;
 
 /// A builder to create the inner content of a [`Webhook`]'s execution.
 ///
@@ -63,6 +72,7 @@ pub struct ExecuteWebhook<'a>(
     pub HashMap<&'static str, Value>,
     #[cfg(feature = "http")] pub Vec<AttachmentType<'a>>,
     #[cfg(not(feature = "http"))] PhantomData<&'a ()>,
+    pub(crate) UsernameStrategy,
 );
 
 impl<'a> ExecuteWebhook<'a> {
@@ -214,11 +224,38 @@ impl<'a> ExecuteWebhook<'a> {
     /// #     Ok(())
     /// # }
     /// ```
+    ///
+    /// **Note**: Must be between 1 and 80 characters, and may not contain
+    /// `"discord"` or `"clyde"`. Refer to [`Self::username_strategy`] for how
+    /// other names are handled.
     pub fn username<S: ToString>(&mut self, username: S) -> &mut Self {
         self.0.insert("username", Value::String(username.to_string()));
         self
     }
 
+    /// Sets how a [`Self::username`] which Discord does not accept is handled
+    /// when executing the webhook.
+    ///
+    /// Defaults to [`UsernameStrategy::Reject`].
+    pub fn username_strategy(&mut self, strategy: UsernameStrategy) -> &mut Self {
+        self.2 = strategy;
+        self
+    }
+
+    /// Set the allowed mentions for the message.
+    pub fn allowed_mentions<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut CreateAllowedMentions) -> &mut CreateAllowedMentions,
+    {
+        let mut allowed_mentions = CreateAllowedMentions::default();
+        f(&mut allowed_mentions);
+        let map = utils::hashmap_to_json_map(allowed_mentions.0);
+        let allowed_mentions = Value::Object(map);
+
+        self.0.insert("allowed_mentions", allowed_mentions);
+        self
+    }
+
     /// Sets the flags for the message.
     ///
     /// # Examples
@@ -250,12 +287,63 @@ impl<'a> ExecuteWebhook<'a> {
         self.0.insert("flags", Value::Number(serde_json::Number::from(flags.bits)));
         self
     }
+
+    /// Creates components for this message.
+    ///
+    /// **Note**: Interactive components are only accepted on webhooks owned by
+    /// an application, such as the ones of interactions. This is not checked,
+    /// Discord rejects the request for other webhooks.
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn components<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut CreateComponents) -> &mut CreateComponents,
+    {
+        let mut components = CreateComponents::default();
+        f(&mut components);
+
+        self.0.insert("components", Value::Array(components.0));
+        self
+    }
+
+    /// Sets the components of this message.
+    ///
+    /// **Note**: The same restrictions as for [`Self::components`] apply.
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn set_components(&mut self, components: CreateComponents) -> &mut Self {
+        self.0.insert("components", Value::Array(components.0));
+        self
+    }
+
+    /// Creates a new thread with the given name when the webhook belongs to a
+    /// forum or media channel, posting the message as its starter message.
+    pub fn thread_name<S: ToString>(&mut self, thread_name: S) -> &mut Self {
+        self.0.insert("thread_name", Value::String(thread_name.to_string()));
+        self
+    }
+
+    /// Sets the tags to apply to the forum post created by [`Self::thread_name`].
+    ///
+    /// **Note**: This is only valid together with [`Self::thread_name`].
+    /// Executing the webhook otherwise returns
+    /// [`ModelError::AppliedTagsWithoutThreadName`].
+    ///
+    /// [`ModelError::AppliedTagsWithoutThreadName`]: crate::model::error::Error::AppliedTagsWithoutThreadName
+    pub fn applied_tags<It>(&mut self, tags: It) -> &mut Self
+    where
+        It: IntoIterator<Item = ForumTagId>,
+    {
+        let tags = tags.into_iter().map(|tag| Value::String(tag.0.to_string())).collect();
+
+        self.0.insert("applied_tags", Value::Array(tags));
+        self
+    }
 }
 
 impl<'a> Default for ExecuteWebhook<'a> {
     /// Returns a default set of values for a [`Webhook`] execution.
     ///
-    /// The only default value is [`Self::tts`] being set to `false`.
+    /// The only default values are [`Self::tts`] being set to `false` and the
+    /// [`Self::username_strategy`] being [`UsernameStrategy::Reject`].
     ///
     /// # Examples
     ///
@@ -272,6 +360,166 @@ impl<'a> Default for ExecuteWebhook<'a> {
         let mut map = HashMap::new();
         map.insert("tts", Value::Bool(false));
 
-        ExecuteWebhook(map, Default::default())
+        ExecuteWebhook(map, Default::default(), UsernameStrategy::default())
+    }
+}
+
+/// How a webhook username which Discord does not accept is handled, used with
+/// [`ExecuteWebhook::username_strategy`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum UsernameStrategy {
+    /// Executing the webhook fails with
+    /// [`ModelError::InvalidWebhookUsername`], without sending a request.
+    ///
+    /// [`ModelError::InvalidWebhookUsername`]: crate::model::error::Error::InvalidWebhookUsername
+    Reject,
+    /// Removes every `"discord"` and `"clyde"` from the username, ignoring
+    /// case, and truncates it to 80 characters. If nothing is left, the
+    /// default username of the webhook is used.
+    Sanitize,
+}
+
+impl Default for UsernameStrategy {
+    fn default() -> Self {
+        UsernameStrategy::Reject
+    }
+}
+
+/// The maximum number of characters of a webhook username.
+#[cfg(feature = "model")]
+const USERNAME_LIMIT: usize = 80;
+
+/// The words Discord does not accept in webhook usernames.
+#[cfg(feature = "model")]
+const FORBIDDEN_USERNAME_WORDS: [&str; 2] = ["discord", "clyde"];
+
+/// Applies the strategy to the username of a serialized webhook execution, if
+/// it has one which Discord does not accept.
+///
+/// Returns `false` if the username is rejected.
+#[cfg(feature = "model")]
+pub(crate) fn check_username(map: &mut JsonMap, strategy: UsernameStrategy) -> bool {
+    let username = match map.get("username").and_then(Value::as_str) {
+        Some(username) if !is_valid_username(username) => username,
+        _ => return true,
+    };
+
+    match strategy {
+        UsernameStrategy::Reject => false,
+        UsernameStrategy::Sanitize => {
+            match sanitize_username(username) {
+                Some(username) => map.insert("username".to_string(), Value::String(username)),
+                None => map.remove("username"),
+            };
+
+            true
+        },
+    }
+}
+
+#[cfg(feature = "model")]
+fn is_valid_username(username: &str) -> bool {
+    let chars = username.chars().collect::<Vec<_>>();
+
+    (1..=USERNAME_LIMIT).contains(&chars.len()) && find_forbidden_word(&chars).is_none()
+}
+
+#[cfg(feature = "model")]
+fn sanitize_username(username: &str) -> Option<String> {
+    let mut chars = username.chars().collect::<Vec<_>>();
+
+    // Removing a word can join the characters around it into another one.
+    while let Some(range) = find_forbidden_word(&chars) {
+        chars.drain(range);
+    }
+
+    let username = chars.into_iter().collect::<String>();
+    let username = username.trim().chars().take(USERNAME_LIMIT).collect::<String>();
+    let username = username.trim_end();
+
+    if username.is_empty() {
+        None
+    } else {
+        Some(username.to_string())
+    }
+}
+
+/// Finds the first word Discord does not accept in a username, ignoring case.
+#[cfg(feature = "model")]
+fn find_forbidden_word(chars: &[char]) -> Option<Range<usize>> {
+    FORBIDDEN_USERNAME_WORDS.iter().find_map(|word| {
+        let word = word.chars().collect::<Vec<_>>();
+
+        chars
+            .windows(word.len())
+            .position(|window| window.iter().zip(&word).all(|(a, b)| a.eq_ignore_ascii_case(b)))
+            .map(|start| start..start + word.len())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{ExecuteWebhook, UsernameStrategy};
+    use crate::model::id::ForumTagId;
+    use crate::utils;
+
+    #[test]
+    fn test_forum_post_body() {
+        let mut builder = ExecuteWebhook::default();
+        builder
+            .content("hello")
+            .thread_name("A new post")
+            .applied_tags(vec![ForumTagId(1), ForumTagId(2)]);
+
+        let map = utils::hashmap_to_json_map(builder.0);
+
+        assert_eq!(
+            serde_json::Value::Object(map),
+            json!({
+                "tts": false,
+                "content": "hello",
+                "thread_name": "A new post",
+                "applied_tags": ["1", "2"],
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "model")]
+    fn test_username_strategy() {
+        use super::check_username;
+
+        fn check(username: &str, strategy: UsernameStrategy) -> Option<Option<String>> {
+            let mut builder = ExecuteWebhook::default();
+            builder.username(username).username_strategy(strategy);
+
+            let mut map = utils::hashmap_to_json_map(builder.0);
+            check_username(&mut map, builder.2)
+                .then(|| map.get("username").and_then(|u| u.as_str()).map(ToString::to_string))
+        }
+
+        assert_eq!(check("hakase", UsernameStrategy::Reject), Some(Some("hakase".to_string())));
+        assert_eq!(check("Discord Bot", UsernameStrategy::Reject), None);
+        assert_eq!(check(&"a".repeat(81), UsernameStrategy::Reject), None);
+        assert_eq!(check("", UsernameStrategy::Reject), None);
+
+        assert_eq!(
+            check("My DisCord bot", UsernameStrategy::Sanitize),
+            Some(Some("My  bot".to_string()))
+        );
+        assert_eq!(check("disclydecord", UsernameStrategy::Sanitize), Some(None));
+        assert_eq!(check(" clyde", UsernameStrategy::Sanitize), Some(None));
+        assert_eq!(check(&"a".repeat(81), UsernameStrategy::Sanitize), Some(Some("a".repeat(80))));
+    }
+
+    #[test]
+    fn test_allowed_mentions() {
+        let mut builder = ExecuteWebhook::default();
+        builder.content("<@210>").allowed_mentions(|am| am.empty_parse());
+
+        assert_eq!(builder.0["allowed_mentions"], json!({"parse": []}));
     }
 }
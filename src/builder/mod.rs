@@ -24,6 +24,7 @@ mod create_interaction_response;
 mod create_interaction_response_followup;
 mod create_invite;
 mod create_message;
+mod create_scheduled_event;
 mod create_stage_instance;
 mod create_thread;
 mod edit_channel;
@@ -49,9 +50,18 @@ pub use self::{
     create_allowed_mentions::CreateAllowedMentions,
     create_allowed_mentions::ParseValue,
     create_channel::CreateChannel,
-    create_embed::{CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, Timestamp},
+    create_embed::{
+        CreateEmbed,
+        CreateEmbedAuthor,
+        CreateEmbedFooter,
+        EmbedError,
+        EmbedLimit,
+        EmbedPart,
+        Timestamp,
+    },
     create_invite::CreateInvite,
-    create_message::CreateMessage,
+    create_message::{CreateMessage, SplitStrategy},
+    create_scheduled_event::{CreateRecurrenceRule, CreateScheduledEvent},
     create_stage_instance::CreateStageInstance,
     create_thread::CreateThread,
     edit_channel::EditChannel,
@@ -66,9 +76,18 @@ pub use self::{
     edit_thread::EditThread,
     edit_voice_state::EditVoiceState,
     edit_webhook_message::EditWebhookMessage,
-    execute_webhook::ExecuteWebhook,
+    execute_webhook::{ExecuteWebhook, UsernameStrategy},
     get_messages::GetMessages,
 };
+#[cfg(all(feature = "unstable_discord_api", feature = "model"))]
+pub(crate) use self::create_application_command::command_violations;
+#[cfg(all(feature = "unstable_discord_api", feature = "model"))]
+pub(crate) use self::create_components::component_violations;
+pub(crate) use self::create_embed::{check_embed, embed_length};
+#[cfg(all(feature = "model", feature = "utils"))]
+pub(crate) use self::create_message::split_message;
+#[cfg(feature = "model")]
+pub(crate) use self::execute_webhook::check_username;
 #[cfg(feature = "unstable_discord_api")]
 pub use self::{
     create_application_command::{
@@ -99,3 +118,31 @@ pub use self::{
     create_interaction_response_followup::CreateInteractionResponseFollowup,
     edit_interaction_response::EditInteractionResponse,
 };
+
+#[cfg(test)]
+mod test {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_send_sync() {
+        use super::*;
+
+        // Builders holding attachments are borrowed into the futures sending
+        // them, so they must be shareable regardless of the enabled features.
+        assert_send_sync::<CreateMessage<'static>>();
+        assert_send_sync::<EditMessage>();
+        assert_send_sync::<ExecuteWebhook<'static>>();
+        assert_send_sync::<EditGuild>();
+    }
+
+    #[test]
+    #[cfg(feature = "unstable_discord_api")]
+    fn test_interaction_send_sync() {
+        use super::*;
+
+        assert_send_sync::<CreateInteractionResponse>();
+        assert_send_sync::<CreateInteractionResponseData>();
+        assert_send_sync::<CreateInteractionResponseFollowup<'static>>();
+        assert_send_sync::<EditInteractionResponse<'static>>();
+    }
+}
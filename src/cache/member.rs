@@ -0,0 +1,122 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use super::AccessTime;
+use crate::model::prelude::*;
+
+/// The members of each cached guild.
+pub(crate) type MemberCache = HashMap<GuildId, HashMap<UserId, CachedMember>>;
+
+/// A [`Member`] as it is stored by the cache.
+///
+/// Instead of holding its own copy of the [`User`], the member shares the
+/// user stored in the cache's users map, so that a user who is in many cached
+/// guilds is only held in memory once.
+#[derive(Clone, Debug)]
+pub(crate) struct CachedMember {
+    pub(crate) deaf: bool,
+    pub(crate) joined_at: Option<DateTime<Utc>>,
+    pub(crate) mute: bool,
+    pub(crate) nick: Option<String>,
+    pub(crate) roles: Vec<RoleId>,
+    pub(crate) user: Arc<User>,
+    pub(crate) pending: bool,
+    pub(crate) premium_since: Option<DateTime<Utc>>,
+    #[cfg(feature = "unstable_discord_api")]
+    pub(crate) permissions: Option<Permissions>,
+    pub(crate) avatar: Option<String>,
+    pub(crate) communication_disabled_until: Option<DateTime<Utc>>,
+    /// When the member was last accessed, to evict it once its time-to-live
+    /// expired.
+    pub(crate) last_access: AccessTime,
+}
+
+impl CachedMember {
+    /// Creates a cached member out of `member`, sharing `user` in place of the
+    /// member's own user.
+    pub(crate) fn new(member: &Member, user: Arc<User>) -> Self {
+        Self {
+            deaf: member.deaf,
+            joined_at: member.joined_at,
+            mute: member.mute,
+            nick: member.nick.clone(),
+            roles: member.roles.clone(),
+            user,
+            pending: member.pending,
+            premium_since: member.premium_since,
+            #[cfg(feature = "unstable_discord_api")]
+            permissions: member.permissions,
+            avatar: member.avatar.clone(),
+            communication_disabled_until: member.communication_disabled_until,
+            last_access: AccessTime::default(),
+        }
+    }
+
+    /// Clones the cached member into a [`Member`] of the given guild.
+    pub(crate) fn to_member(&self, guild_id: GuildId) -> Member {
+        Member {
+            deaf: self.deaf,
+            guild_id,
+            joined_at: self.joined_at,
+            mute: self.mute,
+            nick: self.nick.clone(),
+            roles: self.roles.clone(),
+            user: User::clone(&self.user),
+            pending: self.pending,
+            premium_since: self.premium_since,
+            #[cfg(feature = "unstable_discord_api")]
+            permissions: self.permissions,
+            avatar: self.avatar.clone(),
+            communication_disabled_until: self.communication_disabled_until,
+        }
+    }
+}
+
+/// Returns the shared copy of `user` from `users`, inserting it or replacing it
+/// if its data changed, along with whether an existing entry was replaced.
+///
+/// An unchanged user keeps its existing allocation, so that every member
+/// referring to it keeps sharing it.
+pub(crate) fn intern_user(
+    users: &mut HashMap<UserId, Arc<User>>,
+    user: &User,
+) -> (Arc<User>, bool) {
+    match users.entry(user.id) {
+        Entry::Occupied(mut entry) => {
+            if same_user_data(entry.get(), user) {
+                return (Arc::clone(entry.get()), false);
+            }
+
+            let user = Arc::new(user.clone());
+            entry.insert(Arc::clone(&user));
+
+            (user, true)
+        },
+        Entry::Vacant(entry) => (Arc::clone(entry.insert(Arc::new(user.clone()))), false),
+    }
+}
+
+/// Points the members of `user` in every guild at its new shared copy.
+pub(crate) fn repoint_members(members: &mut MemberCache, user: &Arc<User>) {
+    for guild_members in members.values_mut() {
+        if let Some(member) = guild_members.get_mut(&user.id) {
+            member.user = Arc::clone(user);
+        }
+    }
+}
+
+/// Compares all fields of two users, as opposed to [`User`]'s [`PartialEq`]
+/// implementation which only compares their Ids.
+fn same_user_data(a: &User, b: &User) -> bool {
+    a.id == b.id
+        && a.avatar == b.avatar
+        && a.bot == b.bot
+        && a.discriminator == b.discriminator
+        && a.name == b.name
+        && a.public_flags == b.public_flags
+        && a.banner == b.banner
+        && a.accent_colour == b.accent_colour
+}
@@ -30,23 +30,34 @@
 //! [`Shard`]: crate::gateway::Shard
 //! [`http`]: crate::http
 
-use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::default::Default;
+use std::panic::{self, AssertUnwindSafe};
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
 use moka::dash::Cache as DashCache;
-use tokio::sync::RwLock;
-use tracing::instrument;
+use tokio::sync::{Notify, RwLock};
+use tokio::time::{timeout, Instant};
+use tracing::{instrument, warn};
 
 use crate::model::prelude::*;
 
 mod cache_update;
+mod member;
 mod settings;
+mod ttl;
 
 pub use self::cache_update::CacheUpdate;
-pub use self::settings::Settings;
+pub(crate) use self::member::CachedMember;
+use self::member::{intern_user, repoint_members, MemberCache};
+pub use self::settings::{GuildInsertHook, GuildRemoveHook, MemberInsertHook, Settings};
+#[cfg(feature = "client")]
+pub(crate) use self::ttl::CacheSweeper;
+use self::ttl::AccessTime;
+pub use self::ttl::SweepStats;
 
 type MessageCache = HashMap<ChannelId, HashMap<MessageId, Message>>;
 
@@ -132,7 +143,16 @@ pub struct Cache {
     pub(crate) categories: RwLock<HashMap<ChannelId, ChannelCategory>>,
     /// A map of guilds with full data available. This includes data like
     /// [`Role`]s and [`Emoji`]s that are not available through the REST API.
+    ///
+    /// The members of these guilds are not stored here, but in
+    /// [`Self::members`]. Their `members` maps are filled back in when a guild
+    /// is retrieved via [`Self::guild`].
     pub(crate) guilds: RwLock<HashMap<GuildId, Guild>>,
+    /// A map of the members of each guild in [`Self::guilds`].
+    ///
+    /// The members share their users with [`Self::users`], so that a user is
+    /// only held once no matter how many cached guilds they are a member of.
+    pub(crate) members: RwLock<MemberCache>,
     pub(crate) messages: RwLock<MessageCache>,
     /// A map of users' presences. This is updated in real-time. Note that
     /// status updates are often "eaten" by the gateway, and this should not
@@ -150,6 +170,11 @@ pub struct Cache {
     /// is received. Guilds are "sent in" over time through the receiving of
     /// [`Event::GuildCreate`]s.
     pub(crate) unavailable_guilds: RwLock<HashSet<GuildId>>,
+    /// The shards a [`Ready`] was received for.
+    pub(crate) ready_shards: RwLock<HashSet<u64>>,
+    /// Notified whenever a shard is ready or a guild becomes available, to
+    /// wake up [`Self::wait_until_ready`].
+    pub(crate) ready_changed: Notify,
     /// The current user "logged in" and for which events are being received
     /// for.
     ///
@@ -168,21 +193,41 @@ pub struct Cache {
     /// - [`GuildMembersChunk`][`GuildMembersChunkEvent`]
     /// - [`PresenceUpdate`][`PresenceUpdateEvent`]
     /// - [`Ready`][`ReadyEvent`]
+    /// - [`UserUpdate`][`UserUpdateEvent`]
     ///
     /// Note, however, that users are _not_ removed from the map on removal
     /// events such as [`GuildMemberRemove`][`GuildMemberRemoveEvent`], as other
     /// structs such as members or recipients may still exist.
-    pub(crate) users: RwLock<HashMap<UserId, User>>,
+    pub(crate) users: RwLock<HashMap<UserId, Arc<User>>>,
     /// Queue of message IDs for each channel.
     ///
     /// This is simply a vecdeque so we can keep track of the order of messages
     /// inserted into the cache. When a maximum number of messages are in a
     /// channel's cache, we can pop the front and remove that ID from the cache.
-    pub(crate) message_queue: RwLock<HashMap<ChannelId, VecDeque<MessageId>>>,
+    ///
+    /// Each ID is stored with the [minute] the message was inserted at, to
+    /// evict messages once their time-to-live expired.
+    ///
+    /// [minute]: Self::minute
+    pub(crate) message_queue: RwLock<HashMap<ChannelId, VecDeque<(MessageId, u32)>>>,
     /// Cache of users who have been fetched from `to_user`.
     ///
     /// Each value has a max TTL of 1 hour.
     pub(crate) temp_users: DashCache<UserId, User>,
+    /// An index of the users connected to each voice channel of a guild.
+    ///
+    /// This mirrors the voice states of the cached guilds, so that the users
+    /// in a voice channel can be looked up without iterating over all of the
+    /// guild's voice states.
+    pub(crate) voice_channel_users: RwLock<HashMap<GuildId, HashMap<ChannelId, HashSet<UserId>>>>,
+    /// When the users in [`Self::users`] were last accessed, to evict them
+    /// once their time-to-live expired.
+    pub(crate) user_access_times: RwLock<HashMap<UserId, AccessTime>>,
+    /// The totals of all sweeps of expired entries.
+    pub(crate) sweep_totals: RwLock<SweepStats>,
+    /// When the cache was created, which the timestamps of its entries are
+    /// relative to.
+    pub(crate) created_at: Instant,
     /// The settings for the cache.
     settings: RwLock<Settings>,
 }
@@ -263,8 +308,11 @@ impl Cache {
     pub async fn unknown_members(&self) -> u64 {
         let mut total = 0;
 
-        for guild in self.guilds.read().await.values() {
-            let members = guild.members.len() as u64;
+        let guilds = self.guilds.read().await;
+        let members = self.members.read().await;
+
+        for guild in guilds.values() {
+            let members = members.get(&guild.id).map_or(0, HashMap::len) as u64;
 
             if guild.member_count > members {
                 total += guild.member_count - members;
@@ -413,13 +461,19 @@ impl Cache {
     }
 
     async fn _guild(&self, id: GuildId) -> Option<Guild> {
-        self.guilds.read().await.get(&id).cloned()
+        let mut guild = self.guilds.read().await.get(&id).cloned()?;
+        guild.members = self.guild_members(id).await;
+
+        Some(guild)
     }
 
     /// This method allows to select a field of the guild instead of
     /// the entire guild by providing a `field_selector`-closure picking what
     /// you want to clone.
     ///
+    /// **Note**: The guild's [`members`] map is empty, as members are cached
+    /// separately. Use [`Self::member`] or [`Self::member_field`] instead.
+    ///
     /// ```rust,no_run
     /// # use serenity::cache::Cache;
     /// #
@@ -432,6 +486,8 @@ impl Cache {
     /// #   Ok(())
     /// # }
     /// ```
+    ///
+    /// [`members`]: crate::model::guild::Guild::members
     #[inline]
     pub async fn guild_field<Ret, Fun>(
         &self,
@@ -459,6 +515,32 @@ impl Cache {
         self.guilds.read().await.len()
     }
 
+    /// Returns the number of users connected to a voice channel.
+    ///
+    /// Returns [`None`] if the guild is not cached.
+    pub async fn voice_channel_user_count(
+        &self,
+        guild_id: impl Into<GuildId>,
+        channel_id: impl Into<ChannelId>,
+    ) -> Option<usize> {
+        let voice_channel_users = self.voice_channel_users.read().await;
+        let channels = voice_channel_users.get(&guild_id.into())?;
+
+        Some(channels.get(&channel_id.into()).map_or(0, HashSet::len))
+    }
+
+    /// Retrieves the voice state of a user in a guild, if the user is
+    /// connected to one of the guild's voice channels.
+    pub async fn voice_state(
+        &self,
+        guild_id: impl Into<GuildId>,
+        user_id: impl Into<UserId>,
+    ) -> Option<VoiceState> {
+        let guilds = self.guilds.read().await;
+
+        guilds.get(&guild_id.into())?.voice_states.get(&user_id.into()).cloned()
+    }
+
     /// Retrieves a reference to a [`Guild`]'s channel. Unlike [`Self::channel`],
     /// this will only search guilds for the given channel.
     ///
@@ -560,8 +642,8 @@ impl Cache {
     /// Retrieves a [`Guild`]'s member from the cache based on the guild's and
     /// user's given Ids.
     ///
-    /// **Note**: This will clone the entire member. Instead, retrieve the guild
-    /// and retrieve from the guild's [`members`] map to avoid this.
+    /// **Note**: This will clone the entire member. Instead, use
+    /// [`Self::member_field`] to only clone the fields you need.
     ///
     /// # Examples
     ///
@@ -610,7 +692,6 @@ impl Cache {
     /// ```
     ///
     /// [`EventHandler::message`]: crate::client::EventHandler::message
-    /// [`members`]: crate::model::guild::Guild::members
     #[inline]
     pub async fn member<G, U>(&self, guild_id: G, user_id: U) -> Option<Member>
     where
@@ -621,10 +702,11 @@ impl Cache {
     }
 
     async fn _member(&self, guild_id: GuildId, user_id: UserId) -> Option<Member> {
-        match self.guilds.read().await.get(&guild_id) {
-            Some(guild) => guild.members.get(&user_id).cloned(),
-            None => None,
-        }
+        let members = self.members.read().await;
+        let member = members.get(&guild_id)?.get(&user_id)?;
+        member.last_access.touch(self.minute());
+
+        Some(member.to_member(guild_id))
     }
 
     /// This method allows to only clone a field of a member instead of
@@ -665,11 +747,9 @@ impl Cache {
     where
         Fun: FnOnce(&Member) -> Ret,
     {
-        let guilds = &self.guilds.read().await;
-        let guild = guilds.get(&guild_id)?;
-        let member = guild.members.get(&user_id)?;
+        let member = self._member(guild_id, user_id).await?;
 
-        Some(field_selector(member))
+        Some(field_selector(&member))
     }
 
     #[inline]
@@ -687,6 +767,53 @@ impl Cache {
         self.unavailable_guilds.read().await.clone()
     }
 
+    /// Returns the number of unavailable guilds, such as the guilds which were
+    /// not received yet since the [`Ready`].
+    pub async fn unavailable_guild_count(&self) -> usize {
+        self.unavailable_guilds.read().await.len()
+    }
+
+    /// Whether a [`Ready`] was received for every shard, and all guilds which
+    /// were unavailable in it were received since.
+    ///
+    /// **Note**: If this process only runs some of the bot's shards, this stays
+    /// `false`, as the [`Ready`]s of the other shards are never received.
+    pub async fn is_ready(&self) -> bool {
+        let shard_count = *self.shard_count.read().await;
+
+        self.ready_shards.read().await.len() as u64 >= shard_count
+            && self.unavailable_guilds.read().await.is_empty()
+    }
+
+    /// Waits for up to `duration` until the cache [is ready], such as to not
+    /// serve requests before all guilds were received on startup.
+    ///
+    /// Returns the IDs of the guilds which are still unavailable, which is
+    /// empty if the cache became ready in time.
+    ///
+    /// [is ready]: Self::is_ready
+    pub async fn wait_until_ready(&self, duration: Duration) -> Vec<GuildId> {
+        let ready = async {
+            while !self.is_ready().await {
+                self.ready_changed.notified().await;
+            }
+        };
+
+        if timeout(duration, ready).await.is_ok() {
+            // Pass the notification on to other callers waiting.
+            self.notify_ready_changed();
+        }
+
+        self.unavailable_guilds.read().await.iter().copied().collect()
+    }
+
+    pub(crate) fn notify_ready_changed(&self) {
+        #[cfg(feature = "tokio")]
+        self.ready_changed.notify_one();
+        #[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
+        self.ready_changed.notify();
+    }
+
     /// This method returns all channels from a guild of with the given `guild_id`.
     #[inline]
     pub async fn guild_channels(
@@ -891,16 +1018,51 @@ impl Cache {
 
     async fn _user(&self, user_id: UserId) -> Option<User> {
         if let Some(user) = self.users.read().await.get(&user_id) {
-            Some(user.clone())
+            self.touch_user(user_id).await;
+
+            Some(User::clone(user))
         } else {
             self.temp_users.get(&user_id)
         }
     }
 
+    /// Calls `f` with every cached guild, one at a time.
+    ///
+    /// Unlike [`Self::guild`] this only clones one guild at a time, which
+    /// makes it suitable for exporting the cache to external storage.
+    ///
+    /// **Note**: The cache cannot be updated while this is running, so `f`
+    /// should return quickly.
+    ///
+    /// # Examples
+    ///
+    /// Sum up the members of all cached guilds:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::cache::Cache;
+    /// #
+    /// # async fn run() {
+    /// # let cache = Cache::default();
+    /// let mut members = 0;
+    /// cache.export_guilds(|guild| members += guild.members.len()).await;
+    /// # }
+    /// ```
+    pub async fn export_guilds<F>(&self, mut f: F)
+    where
+        F: FnMut(&Guild),
+    {
+        for guild in self.guilds.read().await.values() {
+            let mut guild = guild.clone();
+            guild.members = self.guild_members(guild.id).await;
+
+            f(&guild);
+        }
+    }
+
     /// Clones all users and returns them.
     #[inline]
     pub async fn users(&self) -> HashMap<UserId, User> {
-        self.users.read().await.clone()
+        self.users.read().await.iter().map(|(id, user)| (*id, User::clone(user))).collect()
     }
 
     /// Returns the amount of cached users.
@@ -990,15 +1152,136 @@ impl Cache {
         e.update(self).await
     }
 
-    pub(crate) async fn update_user_entry(&self, user: &User) {
-        match self.users.write().await.entry(user.id) {
-            Entry::Vacant(e) => {
-                e.insert(user.clone());
-            },
-            Entry::Occupied(mut e) => {
-                e.get_mut().clone_from(user);
-            },
+    /// Inserts or updates a user, returning the copy shared by the cache.
+    ///
+    /// If the user's data changed, the members of the user in every cached
+    /// guild are updated to share the new copy.
+    pub(crate) async fn update_user_entry(&self, user: &User) -> Arc<User> {
+        let (user, replaced) = intern_user(&mut *self.users.write().await, user);
+
+        if replaced {
+            repoint_members(&mut *self.members.write().await, &user);
         }
+
+        user
+    }
+
+    /// Inserts or replaces a member of a guild.
+    pub(crate) async fn insert_member(&self, guild_id: GuildId, member: &Member) {
+        let user = self.update_user_entry(&member.user).await;
+        let member = CachedMember::new(member, user);
+
+        self.members.write().await.entry(guild_id).or_default().insert(member.user.id, member);
+    }
+
+    /// Inserts or replaces members of a guild, all under the same locks.
+    pub(crate) async fn insert_members<'a>(
+        &self,
+        guild_id: GuildId,
+        members: impl IntoIterator<Item = &'a Member>,
+    ) {
+        let mut users = self.users.write().await;
+        let mut cached_members = self.members.write().await;
+        let mut replaced = Vec::new();
+
+        let guild_members = cached_members.entry(guild_id).or_default();
+
+        for member in members {
+            let (user, was_replaced) = intern_user(&mut users, &member.user);
+
+            if was_replaced {
+                replaced.push(Arc::clone(&user));
+            }
+
+            guild_members.insert(user.id, CachedMember::new(member, user));
+        }
+
+        for user in &replaced {
+            repoint_members(&mut cached_members, user);
+        }
+    }
+
+    /// Moves the members out of a guild about to be inserted into
+    /// [`Self::guilds`], replacing the guild's previously cached members.
+    pub(crate) async fn insert_guild_members(&self, guild: &mut Guild) {
+        let mut members = HashMap::with_capacity(guild.members.len());
+        let mut replaced = Vec::new();
+
+        {
+            let mut users = self.users.write().await;
+
+            for (user_id, member) in &guild.members {
+                let (user, was_replaced) = intern_user(&mut users, &member.user);
+
+                if was_replaced {
+                    replaced.push(Arc::clone(&user));
+                }
+
+                members.insert(*user_id, CachedMember::new(member, user));
+            }
+        }
+
+        guild.members = HashMap::new();
+
+        let mut cached_members = self.members.write().await;
+        cached_members.insert(guild.id, members);
+
+        for user in &replaced {
+            repoint_members(&mut cached_members, user);
+        }
+    }
+
+    /// Removes the members of a guild that was removed from [`Self::guilds`],
+    /// returning them.
+    pub(crate) async fn remove_guild_members(&self, guild_id: GuildId) -> HashMap<UserId, Member> {
+        self.members
+            .write()
+            .await
+            .remove(&guild_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(user_id, member)| (user_id, member.to_member(guild_id)))
+            .collect()
+    }
+
+    /// Clones the members of a guild.
+    pub(crate) async fn guild_members(&self, guild_id: GuildId) -> HashMap<UserId, Member> {
+        match self.members.read().await.get(&guild_id) {
+            Some(members) => members
+                .iter()
+                .map(|(user_id, member)| (*user_id, member.to_member(guild_id)))
+                .collect(),
+            None => HashMap::new(),
+        }
+    }
+
+    /// Calls the [`Settings::on_guild_insert`] hook, if set.
+    pub(crate) async fn guild_inserted(&self, guild: &Guild) {
+        if let Some(hook) = &self.settings.read().await.on_guild_insert {
+            call_hook("on_guild_insert", || hook(guild));
+        }
+    }
+
+    /// Calls the [`Settings::on_member_insert`] hook, if set.
+    pub(crate) async fn member_inserted(&self, guild_id: GuildId, member: &Member) {
+        if let Some(hook) = &self.settings.read().await.on_member_insert {
+            call_hook("on_member_insert", || hook(guild_id, member));
+        }
+    }
+
+    /// Calls the [`Settings::on_guild_remove`] hook, if set.
+    pub(crate) async fn guild_removed(&self, guild_id: GuildId) {
+        if let Some(hook) = &self.settings.read().await.on_guild_remove {
+            call_hook("on_guild_remove", || hook(guild_id));
+        }
+    }
+}
+
+/// Calls a persistence hook, logging a panic instead of unwinding into the
+/// shard that is updating the cache.
+fn call_hook(name: &str, hook: impl FnOnce()) {
+    if panic::catch_unwind(AssertUnwindSafe(hook)).is_err() {
+        warn!("[Cache] The {} hook panicked", name);
     }
 }
 
@@ -1009,16 +1292,23 @@ impl Default for Cache {
             temp_channels: DashCache::builder().time_to_live(Duration::from_secs(60 * 60)).build(),
             categories: RwLock::new(HashMap::default()),
             guilds: RwLock::new(HashMap::default()),
+            members: RwLock::new(HashMap::default()),
             messages: RwLock::new(HashMap::default()),
             presences: RwLock::new(HashMap::default()),
             private_channels: RwLock::new(HashMap::with_capacity(128)),
             settings: RwLock::new(Settings::default()),
             shard_count: RwLock::new(1),
             unavailable_guilds: RwLock::new(HashSet::default()),
+            ready_shards: RwLock::new(HashSet::default()),
+            ready_changed: Notify::new(),
             user: RwLock::new(CurrentUser::default()),
             users: RwLock::new(HashMap::default()),
             temp_users: DashCache::builder().time_to_live(Duration::from_secs(60 * 60)).build(),
             message_queue: RwLock::new(HashMap::default()),
+            voice_channel_users: RwLock::new(HashMap::default()),
+            user_access_times: RwLock::new(HashMap::default()),
+            sweep_totals: RwLock::new(SweepStats::default()),
+            created_at: Instant::now(),
         }
     }
 }
@@ -1026,6 +1316,8 @@ impl Default for Cache {
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
     use chrono::{DateTime, Utc};
     use serde_json::{Number, Value};
@@ -1057,6 +1349,7 @@ mod test {
                     bot: false,
                     discriminator: 1,
                     name: "user 1".to_owned(),
+                    global_name: None,
                     public_flags: None,
                     banner: None,
                     accent_colour: None,
@@ -1080,6 +1373,7 @@ mod test {
                 webhook_id: None,
                 activity: None,
                 application: None,
+                application_id: None,
                 message_reference: None,
                 flags: None,
                 stickers: vec![],
@@ -1087,6 +1381,8 @@ mod test {
                 #[cfg(feature = "unstable_discord_api")]
                 interaction: None,
                 #[cfg(feature = "unstable_discord_api")]
+                interaction_metadata: None,
+                #[cfg(feature = "unstable_discord_api")]
                 components: vec![],
             },
         };
@@ -1140,6 +1436,9 @@ mod test {
             thread_metadata: None,
             member: None,
             default_auto_archive_duration: None,
+            flags: ChannelFlags::default(),
+            default_forum_layout: None,
+            default_sort_order: None,
         };
 
         // Add a channel delete event to the cache, the cached messages for that
@@ -1225,4 +1524,618 @@ mod test {
         // Assert that the channel's message cache no longer exists.
         assert!(!cache.messages.read().await.contains_key(&ChannelId(2)));
     }
+
+    #[allow(clippy::unwrap_used)]
+    fn gen_guild() -> Guild {
+        let datetime =
+            DateTime::parse_from_rfc3339("2021-01-01T00:00:00+00:00").unwrap().with_timezone(&Utc);
+
+        #[allow(deprecated)]
+        Guild {
+            id: GuildId(1),
+            afk_channel_id: None,
+            afk_timeout: 300,
+            application_id: None,
+            default_message_notifications: DefaultMessageNotificationLevel::All,
+            emojis: HashMap::new(),
+            explicit_content_filter: ExplicitContentFilter::None,
+            features: vec![],
+            icon: None,
+            joined_at: datetime,
+            large: false,
+            member_count: 1,
+            members: HashMap::new(),
+            mfa_level: MfaLevel::None,
+            name: "old name".to_string(),
+            owner_id: UserId(3),
+            presences: HashMap::new(),
+            region: "us-west".to_string(),
+            roles: HashMap::new(),
+            splash: None,
+            discovery_splash: None,
+            system_channel_id: None,
+            system_channel_flags: Default::default(),
+            rules_channel_id: None,
+            public_updates_channel_id: None,
+            verification_level: VerificationLevel::Low,
+            voice_states: HashMap::new(),
+            description: None,
+            premium_tier: PremiumTier::Tier0,
+            channels: HashMap::new(),
+            premium_subscription_count: 0,
+            banner: None,
+            vanity_url_code: None,
+            preferred_locale: "en-US".to_string(),
+            welcome_screen: None,
+            approximate_member_count: None,
+            approximate_presence_count: None,
+            nsfw: false,
+            nsfw_level: NsfwLevel::Default,
+            max_video_channel_users: None,
+            max_presences: None,
+            max_members: None,
+            widget_enabled: None,
+            widget_channel_id: None,
+            stage_instances: vec![],
+            threads: vec![],
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_until_ready() {
+        let cache = Cache::default();
+
+        let mut ready = ReadyEvent {
+            ready: Ready {
+                application: PartialCurrentApplicationInfo {
+                    id: UserId(2),
+                    flags: 0,
+                },
+                guilds: vec![GuildStatus::Offline(GuildUnavailable {
+                    id: GuildId(1),
+                    unavailable: true,
+                })],
+                presences: HashMap::new(),
+                private_channels: HashMap::new(),
+                session_id: "session".to_string(),
+                shard: Some([0, 1]),
+                trace: vec![],
+                user: CurrentUser::default(),
+                version: 9,
+            },
+        };
+
+        // No shard is ready yet.
+        assert!(!cache.is_ready().await);
+
+        cache.update(&mut ready).await;
+
+        // The guild is still unavailable after the shard's Ready.
+        assert!(!cache.is_ready().await);
+        assert_eq!(cache.unavailable_guild_count().await, 1);
+        assert_eq!(cache.wait_until_ready(Duration::from_secs(5)).await, vec![GuildId(1)]);
+
+        let mut guild_create = GuildCreateEvent {
+            guild: gen_guild(),
+        };
+
+        let (unavailable, _) = tokio::join!(cache.wait_until_ready(Duration::from_secs(5)), async {
+            cache.update(&mut guild_create).await;
+        });
+
+        assert!(unavailable.is_empty());
+        assert!(cache.is_ready().await);
+    }
+
+    #[cfg(feature = "unstable_discord_api")]
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_media_channel_create() {
+        let cache = Cache::default();
+
+        let mut guild_create = GuildCreateEvent {
+            guild: gen_guild(),
+        };
+        cache.update(&mut guild_create).await;
+
+        let mut channel_create = ChannelCreateEvent {
+            channel: Channel::Guild(gen_channel(3, ChannelType::Media)),
+        };
+        assert!(cache.update(&mut channel_create).await.is_none());
+
+        let channels = cache.guild_channels(1).await.unwrap();
+        assert_eq!(channels[&ChannelId(3)].kind, ChannelType::Media);
+        assert_eq!(cache.guild_channel(3).await.unwrap().kind, ChannelType::Media);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_guild_update_diff() {
+        let cache = Cache::default();
+
+        let mut guild_create = GuildCreateEvent {
+            guild: gen_guild(),
+        };
+        assert!(cache.update(&mut guild_create).await.is_none());
+
+        // The same guild with a new name, owner and verification level.
+        let mut guild_update = GuildUpdateEvent {
+            guild: serde_json::from_value(serde_json::json!({
+                "id": "1",
+                "afk_channel_id": null,
+                "afk_timeout": 300,
+                "default_message_notifications": 0,
+                "emojis": [],
+                "features": [],
+                "icon": null,
+                "mfa_level": 0,
+                "name": "new name",
+                "owner_id": "4",
+                "region": "us-west",
+                "roles": [],
+                "splash": null,
+                "verification_level": 2,
+                "premium_tier": 0,
+                "premium_subscription_count": 0,
+                "nsfw": false,
+                "nsfw_level": 0,
+                "system_channel_flags": 0,
+            }))
+            .unwrap(),
+        };
+
+        // The update returns the guild as it was cached before.
+        let old = cache.update(&mut guild_update).await.unwrap();
+        assert_eq!(old.name, "old name");
+        assert_eq!(old.owner_id, UserId(3));
+
+        let diff = guild_update.diff(&old);
+        assert_eq!(diff.name, Some(("old name".to_string(), "new name".to_string())));
+        assert_eq!(diff.owner_id, Some((UserId(3), UserId(4))));
+        assert_eq!(
+            diff.verification_level,
+            Some((VerificationLevel::Low, VerificationLevel::Medium))
+        );
+        assert_eq!(
+            GuildUpdateDiff {
+                name: None,
+                owner_id: None,
+                verification_level: None,
+                ..diff
+            },
+            GuildUpdateDiff::default()
+        );
+
+        // The cached guild now holds the new data.
+        let cached = cache.guild(GuildId(1)).await.unwrap();
+        assert_eq!(cached.name, "new name");
+        assert_eq!(cached.owner_id, UserId(4));
+        assert!(guild_update.diff(&cached).is_empty());
+    }
+
+    fn gen_voice_state(user_id: u64, channel_id: Option<u64>) -> VoiceState {
+        VoiceState {
+            channel_id: channel_id.map(ChannelId),
+            deaf: false,
+            guild_id: Some(GuildId(1)),
+            member: None,
+            mute: false,
+            self_deaf: false,
+            self_mute: false,
+            self_stream: None,
+            self_video: false,
+            session_id: String::new(),
+            suppress: false,
+            token: None,
+            user_id: UserId(user_id),
+            request_to_speak_timestamp: None,
+        }
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_voice_channel_users() {
+        let cache = Cache::default();
+
+        assert_eq!(cache.voice_channel_user_count(1, 100).await, None);
+
+        let mut guild = gen_guild();
+        guild.voice_states.insert(UserId(10), gen_voice_state(10, Some(100)));
+        guild.voice_states.insert(UserId(11), gen_voice_state(11, Some(100)));
+
+        let mut guild_create = GuildCreateEvent {
+            guild,
+        };
+        assert!(cache.update(&mut guild_create).await.is_none());
+        assert_eq!(cache.voice_channel_user_count(1, 100).await, Some(2));
+        assert_eq!(cache.voice_channel_user_count(1, 200).await, Some(0));
+
+        // A user moves from one channel to another.
+        let mut moved = VoiceStateUpdateEvent {
+            guild_id: Some(GuildId(1)),
+            voice_state: gen_voice_state(11, Some(200)),
+        };
+        assert!(cache.update(&mut moved).await.is_some());
+        assert_eq!(cache.voice_channel_user_count(1, 100).await, Some(1));
+        assert_eq!(cache.voice_channel_user_count(1, 200).await, Some(1));
+
+        // A user joins, and another one leaves.
+        let mut joined = VoiceStateUpdateEvent {
+            guild_id: Some(GuildId(1)),
+            voice_state: gen_voice_state(12, Some(200)),
+        };
+        assert!(cache.update(&mut joined).await.is_none());
+        let mut left = VoiceStateUpdateEvent {
+            guild_id: Some(GuildId(1)),
+            voice_state: gen_voice_state(10, None),
+        };
+        assert!(cache.update(&mut left).await.is_some());
+        assert_eq!(cache.voice_channel_user_count(1, 100).await, Some(0));
+        assert_eq!(cache.voice_channel_user_count(1, 200).await, Some(2));
+
+        // The index agrees with the guild's voice states.
+        let guild = cache.guild(1).await.unwrap();
+        assert!(guild.voice_channel_members(100).is_empty());
+        assert_eq!(guild.voice_channel_members(200), vec![UserId(11), UserId(12)]);
+        assert_eq!(cache.voice_state(1, 11).await.unwrap().channel_id, Some(ChannelId(200)));
+        assert!(cache.voice_state(1, 10).await.is_none());
+
+        let mut guild_delete = GuildDeleteEvent {
+            guild: GuildUnavailable {
+                id: GuildId(1),
+                unavailable: false,
+            },
+        };
+        assert!(cache.update(&mut guild_delete).await.is_some());
+        assert_eq!(cache.voice_channel_user_count(1, 200).await, None);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_persistence_hooks() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let mut settings = Settings::new();
+        let guild_calls = Arc::clone(&calls);
+        settings.on_guild_insert(move |guild| {
+            guild_calls.lock().unwrap().push(format!("guild {} {}", guild.id, guild.name));
+        });
+        let member_calls = Arc::clone(&calls);
+        settings.on_member_insert(move |guild_id, member| {
+            member_calls.lock().unwrap().push(format!("member {} {}", guild_id, member.user.id));
+        });
+        settings.on_guild_remove(|_| panic!("hook panicked"));
+        let cache = Cache::new_with_settings(settings);
+
+        let mut guild_create = GuildCreateEvent {
+            guild: gen_guild(),
+        };
+        assert!(cache.update(&mut guild_create).await.is_none());
+
+        let mut member_add: GuildMemberAddEvent = serde_json::from_value(serde_json::json!({
+            "guild_id": "1",
+            "user": {
+                "id": "10",
+                "username": "test",
+                "discriminator": "0001",
+                "avatar": null,
+            },
+            "roles": [],
+            "joined_at": "2021-01-01T00:00:00+00:00",
+            "deaf": false,
+            "mute": false,
+        }))
+        .unwrap();
+        assert!(cache.update(&mut member_add).await.is_none());
+
+        // Members of unknown guilds are not inserted.
+        member_add.guild_id = GuildId(2);
+        assert!(cache.update(&mut member_add).await.is_none());
+
+        assert_eq!(*calls.lock().unwrap(), ["guild 1 old name", "member 1 10"]);
+
+        let mut exported = Vec::new();
+        cache.export_guilds(|guild| exported.push(guild.members.len())).await;
+        assert_eq!(exported, [1]);
+
+        // The panicking hook neither reaches the caller nor stops the update.
+        let mut guild_delete = GuildDeleteEvent {
+            guild: GuildUnavailable {
+                id: GuildId(1),
+                unavailable: false,
+            },
+        };
+        assert!(cache.update(&mut guild_delete).await.is_some());
+        assert!(cache.guild(1).await.is_none());
+    }
+
+    #[allow(clippy::unwrap_used)]
+    fn gen_member(guild_id: u64, user_id: u64) -> Member {
+        serde_json::from_value(serde_json::json!({
+            "guild_id": guild_id.to_string(),
+            "user": {
+                "id": user_id.to_string(),
+                "username": format!("user {}", user_id),
+                "discriminator": "0001",
+                "avatar": null,
+            },
+            "nick": format!("nick {} in {}", user_id, guild_id),
+            "roles": [],
+            "joined_at": "2021-01-01T00:00:00+00:00",
+            "deaf": false,
+            "mute": false,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_shared_member_users() {
+        const USERS: u64 = 1000;
+
+        let cache = Cache::default();
+
+        for guild_id in 1..=2 {
+            let mut guild = gen_guild();
+            guild.id = GuildId(guild_id);
+            for user_id in 1..=USERS {
+                guild.members.insert(UserId(user_id), gen_member(guild_id, user_id));
+            }
+
+            let mut guild_create = GuildCreateEvent {
+                guild,
+            };
+            assert!(cache.update(&mut guild_create).await.is_none());
+        }
+
+        // The members of both guilds share a single copy of each user.
+        assert_eq!(cache.user_count().await, USERS as usize);
+        {
+            let users = cache.users.read().await;
+            let members = cache.members.read().await;
+
+            for user_id in 1..=USERS {
+                let user = &users[&UserId(user_id)];
+                assert_eq!(Arc::strong_count(user), 3);
+                assert!(Arc::ptr_eq(&members[&GuildId(1)][&UserId(user_id)].user, user));
+                assert!(Arc::ptr_eq(&members[&GuildId(2)][&UserId(user_id)].user, user));
+            }
+
+            assert!(cache.guilds.read().await[&GuildId(1)].members.is_empty());
+        }
+
+        // Retrieved guilds and members hold their own copies.
+        let guild = cache.guild(1).await.unwrap();
+        assert_eq!(guild.members.len(), USERS as usize);
+        assert_eq!(guild.members[&UserId(7)].guild_id, GuildId(1));
+        assert_eq!(guild.members[&UserId(7)].nick.as_deref(), Some("nick 7 in 1"));
+        let member = cache.member(2, 7).await.unwrap();
+        assert_eq!(member.guild_id, GuildId(2));
+        assert_eq!(member.user.name, "user 7");
+        assert_eq!(
+            cache.member_field(2, 7, |m| m.nick.clone()).await.unwrap().unwrap(),
+            "nick 7 in 2"
+        );
+        assert_eq!(cache.unknown_members().await, 0);
+
+        // An unchanged user keeps sharing the same copy.
+        let user = Arc::clone(&cache.users.read().await[&UserId(8)]);
+        cache.update_user_entry(&cache.user(8).await.unwrap()).await;
+        assert!(Arc::ptr_eq(&user, &cache.users.read().await[&UserId(8)]));
+
+        // A USER_UPDATE replaces the user in every guild.
+        let mut user_update = UserUpdateEvent {
+            current_user: CurrentUser {
+                id: UserId(7),
+                name: "renamed".to_string(),
+                ..CurrentUser::default()
+            },
+        };
+        cache.update(&mut user_update).await;
+        assert_eq!(cache.user(7).await.unwrap().name, "renamed");
+        assert_eq!(cache.member(1, 7).await.unwrap().user.name, "renamed");
+        assert_eq!(cache.member(2, 7).await.unwrap().user.name, "renamed");
+        assert_eq!(Arc::strong_count(&cache.users.read().await[&UserId(7)]), 3);
+
+        // Removing a guild drops its members, but keeps the users.
+        let mut guild_delete = GuildDeleteEvent {
+            guild: GuildUnavailable {
+                id: GuildId(1),
+                unavailable: false,
+            },
+        };
+        let removed = cache.update(&mut guild_delete).await.unwrap();
+        assert_eq!(removed.members.len(), USERS as usize);
+        assert!(cache.member(1, 7).await.is_none());
+        assert_eq!(Arc::strong_count(&cache.users.read().await[&UserId(7)]), 2);
+        assert_eq!(cache.user_count().await, USERS as usize);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_members_chunk() {
+        let cache = Cache::default();
+
+        let mut guild = gen_guild();
+        guild.id = GuildId(1);
+        guild.members.insert(UserId(1), gen_member(1, 1));
+        let mut guild_create = GuildCreateEvent {
+            guild,
+        };
+        cache.update(&mut guild_create).await;
+
+        let chunk = |guild_id: u64| GuildMembersChunkEvent {
+            guild_id: GuildId(guild_id),
+            members: (1..=3)
+                .map(|user_id| (UserId(user_id), gen_member(guild_id, user_id)))
+                .collect(),
+            chunk_index: 0,
+            chunk_count: 1,
+            nonce: None,
+        };
+
+        // New users are inserted without replacing an entry.
+        let mut users = HashMap::new();
+        let (user, replaced) = super::intern_user(&mut users, &gen_member(1, 4).user);
+        assert!(!replaced);
+        assert!(Arc::ptr_eq(&user, &users[&UserId(4)]));
+
+        let user = Arc::clone(&cache.users.read().await[&UserId(1)]);
+        cache.update(&mut chunk(1)).await;
+        assert_eq!(cache.members.read().await[&GuildId(1)].len(), 3);
+        assert_eq!(cache.user_count().await, 3);
+        assert!(Arc::ptr_eq(&user, &cache.users.read().await[&UserId(1)]));
+
+        // The members of an uncached guild are not cached, only their users.
+        cache.update(&mut chunk(2)).await;
+        assert!(!cache.members.read().await.contains_key(&GuildId(2)));
+        assert_eq!(cache.user_count().await, 3);
+    }
+
+    #[cfg(feature = "unstable_discord_api")]
+    fn gen_channel(id: u64, kind: ChannelType) -> GuildChannel {
+        GuildChannel {
+            id: ChannelId(id),
+            bitrate: None,
+            category_id: None,
+            guild_id: GuildId(1),
+            kind,
+            last_message_id: None,
+            last_pin_timestamp: None,
+            name: String::new(),
+            permission_overwrites: vec![],
+            position: 0,
+            topic: None,
+            user_limit: None,
+            nsfw: false,
+            slow_mode_rate: Some(0),
+            rtc_region: None,
+            video_quality_mode: None,
+            message_count: None,
+            member_count: None,
+            thread_metadata: None,
+            member: None,
+            default_auto_archive_duration: None,
+            flags: ChannelFlags::default(),
+            default_forum_layout: None,
+            default_sort_order: None,
+        }
+    }
+
+    fn gen_message(id: u64, channel_id: u64) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "id": id.to_string(),
+            "channel_id": channel_id.to_string(),
+            "author": {
+                "id": "1",
+                "username": "user 1",
+                "discriminator": "0001",
+                "avatar": null,
+            },
+            "content": "",
+            "timestamp": "2021-01-01T00:00:00+00:00",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "embeds": [],
+            "pinned": false,
+            "type": 0,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    #[allow(clippy::unwrap_used)]
+    async fn test_message_ttl() {
+        let mut settings = Settings::new();
+        settings.max_messages(10).message_ttl(Duration::from_secs(60 * 60));
+        let cache = Cache::new_with_settings(settings);
+
+        let mut message_create = MessageCreateEvent {
+            message: gen_message(1, 2),
+        };
+        cache.update(&mut message_create).await;
+
+        tokio::time::advance(Duration::from_secs(30 * 60)).await;
+        message_create.message = gen_message(3, 2);
+        cache.update(&mut message_create).await;
+        message_create.message = gen_message(4, 5);
+        cache.update(&mut message_create).await;
+
+        // Nothing expired yet.
+        assert_eq!(cache.sweep_expired().await.evicted(), 0);
+
+        // Only the first message is older than an hour.
+        tokio::time::advance(Duration::from_secs(31 * 60)).await;
+        assert_eq!(cache.sweep_expired().await.messages, 1);
+        assert!(cache.message(2, 1).await.is_none());
+        assert!(cache.message(2, 3).await.is_some());
+        assert!(cache.message(5, 4).await.is_some());
+
+        // Empty channels are removed along with their last message.
+        tokio::time::advance(Duration::from_secs(30 * 60)).await;
+        assert_eq!(cache.sweep_expired().await.messages, 2);
+        assert!(cache.messages.read().await.is_empty());
+        assert!(cache.message_queue.read().await.is_empty());
+
+        let totals = cache.sweep_stats().await;
+        assert_eq!(totals.sweeps, 3);
+        assert_eq!(totals.messages, 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    #[allow(clippy::unwrap_used)]
+    async fn test_member_and_user_ttl() {
+        let mut settings = Settings::new();
+        settings.member_ttl(Duration::from_secs(60 * 60)).user_ttl(Duration::from_secs(60 * 60));
+        let cache = Cache::new_with_settings(settings);
+        cache.user.write().await.id = UserId(4);
+
+        for user_id in 2..=4 {
+            cache.insert_member(GuildId(1), &gen_member(1, user_id)).await;
+        }
+        let orphan = gen_member(1, 5).user;
+        cache.update_user_entry(&orphan).await;
+
+        // The first sweep only timestamps the entries.
+        assert_eq!(cache.sweep_expired().await.evicted(), 0);
+
+        tokio::time::advance(Duration::from_secs(40 * 60)).await;
+        assert!(cache.member(1, 2).await.is_some());
+
+        // The member which was not accessed is evicted, and so is its user
+        // once no member refers to it anymore, unlike the accessed member and
+        // the current user's member.
+        tokio::time::advance(Duration::from_secs(30 * 60)).await;
+        let stats = cache.sweep_expired().await;
+        assert_eq!(stats.members, 1);
+        assert_eq!(stats.users, 2);
+        assert!(cache.member(1, 2).await.is_some());
+        assert!(cache.member(1, 3).await.is_none());
+        assert!(cache.member(1, 4).await.is_some());
+        assert!(cache.user(3).await.is_none());
+        assert!(cache.user(5).await.is_none());
+        assert!(cache.user(2).await.is_some());
+        assert_eq!(cache.sweep_stats().await.evicted(), 3);
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test(start_paused = true)]
+    async fn test_cache_sweeper() {
+        use super::CacheSweeper;
+
+        let cache = Arc::new(Cache::default());
+        let sweeper = CacheSweeper::spawn(&cache, Duration::from_secs(60));
+
+        tokio::time::sleep(Duration::from_secs(150)).await;
+        assert_eq!(cache.sweep_stats().await.sweeps, 2);
+
+        // The task stops along with the sweeper.
+        drop(sweeper);
+        tokio::time::sleep(Duration::from_secs(150)).await;
+        assert_eq!(cache.sweep_stats().await.sweeps, 2);
+    }
 }
@@ -1,3 +1,24 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::model::prelude::*;
+
+/// A hook called with a guild that was inserted into or updated in the cache.
+///
+/// Refer to [`Settings::on_guild_insert`] for more information.
+pub type GuildInsertHook = Arc<dyn Fn(&Guild) + Send + Sync>;
+
+/// A hook called with a member that was inserted into or updated in the cache.
+///
+/// Refer to [`Settings::on_member_insert`] for more information.
+pub type MemberInsertHook = Arc<dyn Fn(GuildId, &Member) + Send + Sync>;
+
+/// A hook called with the Id of a guild that was removed from the cache.
+///
+/// Refer to [`Settings::on_guild_remove`] for more information.
+pub type GuildRemoveHook = Arc<dyn Fn(GuildId) + Send + Sync>;
+
 /// Settings for the cache.
 ///
 /// # Examples
@@ -10,23 +31,116 @@
 /// let mut settings = CacheSettings::new();
 /// settings.max_messages(10);
 /// ```
-#[derive(Clone, Debug)]
+///
+/// # Persistence hooks
+///
+/// The `on_*` hooks allow writing parts of the cache to external storage, such
+/// as a database, as the cache is updated. They are called synchronously by
+/// the shard processing the event, as part of the cache update, so they
+/// should return quickly, e.g. by queueing the write for a background task.
+///
+/// The hooks may be called while some of the cache's locks are held. As they
+/// are not async, they cannot wait on the cache, and so cannot deadlock it;
+/// they should not block on anything else the cache relies on either. A
+/// panicking hook does not poison the cache: the panic is caught and logged.
+///
+/// An unset hook is skipped after a read of the settings.
+///
+/// # Time-to-live
+///
+/// Besides limiting the number of messages, entries can be evicted from the
+/// cache once they expired, to keep the memory usage of a long-running bot
+/// flat. Messages expire a given time after they were inserted, while users
+/// and members expire once they were not accessed through the cache for a
+/// given time. A user is only evicted once no cached member refers to it
+/// anymore.
+///
+/// If any time-to-live is set, the [`Client`] evicts the expired entries
+/// every [`Self::sweep_interval`]. Refer to [`Cache::sweep_expired`] for more
+/// information.
+///
+/// [`Client`]: crate::Client
+/// [`Cache::sweep_expired`]: super::Cache::sweep_expired
+#[derive(Clone)]
 #[non_exhaustive]
 pub struct Settings {
     /// The maximum number of messages to store in a channel's message cache.
     ///
     /// Defaults to 0.
     pub max_messages: usize,
+    /// How long messages are held after they were inserted into the cache.
+    ///
+    /// Defaults to `None`, holding messages until they are replaced by newer
+    /// ones as per [`Self::max_messages`].
+    pub message_ttl: Option<Duration>,
+    /// How long users are held after they were last accessed, as long as no
+    /// cached member refers to them.
+    ///
+    /// Defaults to `None`, holding users indefinitely.
+    pub user_ttl: Option<Duration>,
+    /// How long members are held after they were last accessed, except for
+    /// the members of the current user.
+    ///
+    /// **Note**: Evicted members are missing from [`Guild::members`], and
+    /// their permissions cannot be computed from the cache anymore.
+    ///
+    /// Defaults to `None`, holding members until they leave the guild.
+    pub member_ttl: Option<Duration>,
+    /// How often the [`Client`] evicts the entries whose time-to-live expired.
+    ///
+    /// Defaults to 60 seconds.
+    ///
+    /// [`Client`]: crate::Client
+    pub sweep_interval: Duration,
+    /// Called whenever a guild is inserted into the cache, or its settings
+    /// are updated.
+    ///
+    /// Defaults to `None`.
+    pub on_guild_insert: Option<GuildInsertHook>,
+    /// Called whenever a member is inserted into the cache, or updated.
+    ///
+    /// Members received as part of a guild are not passed to this hook, but
+    /// only to [`Self::on_guild_insert`].
+    ///
+    /// Defaults to `None`.
+    pub on_member_insert: Option<MemberInsertHook>,
+    /// Called whenever a guild is removed from the cache, because the current
+    /// user left the guild, it was deleted or became unavailable.
+    ///
+    /// Defaults to `None`.
+    pub on_guild_remove: Option<GuildRemoveHook>,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Settings {
             max_messages: usize::default(),
+            message_ttl: None,
+            user_ttl: None,
+            member_ttl: None,
+            sweep_interval: Duration::from_secs(60),
+            on_guild_insert: None,
+            on_member_insert: None,
+            on_guild_remove: None,
         }
     }
 }
 
+impl fmt::Debug for Settings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Settings")
+            .field("max_messages", &self.max_messages)
+            .field("message_ttl", &self.message_ttl)
+            .field("user_ttl", &self.user_ttl)
+            .field("member_ttl", &self.member_ttl)
+            .field("sweep_interval", &self.sweep_interval)
+            .field("on_guild_insert", &self.on_guild_insert.is_some())
+            .field("on_member_insert", &self.on_member_insert.is_some())
+            .field("on_guild_remove", &self.on_guild_remove.is_some())
+            .finish()
+    }
+}
+
 impl Settings {
     /// Creates new settings to be used with a cache.
     #[inline]
@@ -55,4 +169,119 @@ impl Settings {
 
         self
     }
+
+    /// Sets how long messages are held after they were inserted.
+    ///
+    /// Refer to [`message_ttl`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Hold up to 100 messages per channel, for up to an hour:
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use serenity::cache::Settings;
+    ///
+    /// let mut settings = Settings::new();
+    /// settings.max_messages(100).message_ttl(Duration::from_secs(60 * 60));
+    /// ```
+    ///
+    /// [`message_ttl`]: #structfield.message_ttl
+    pub fn message_ttl(&mut self, ttl: Duration) -> &mut Self {
+        self.message_ttl = Some(ttl);
+
+        self
+    }
+
+    /// Sets how long users are held after they were last accessed.
+    ///
+    /// Refer to [`user_ttl`] for more information.
+    ///
+    /// [`user_ttl`]: #structfield.user_ttl
+    pub fn user_ttl(&mut self, ttl: Duration) -> &mut Self {
+        self.user_ttl = Some(ttl);
+
+        self
+    }
+
+    /// Sets how long members are held after they were last accessed.
+    ///
+    /// Refer to [`member_ttl`] for more information.
+    ///
+    /// [`member_ttl`]: #structfield.member_ttl
+    pub fn member_ttl(&mut self, ttl: Duration) -> &mut Self {
+        self.member_ttl = Some(ttl);
+
+        self
+    }
+
+    /// Sets how often the entries whose time-to-live expired are evicted.
+    ///
+    /// Refer to [`sweep_interval`] for more information.
+    ///
+    /// [`sweep_interval`]: #structfield.sweep_interval
+    pub fn sweep_interval(&mut self, interval: Duration) -> &mut Self {
+        self.sweep_interval = interval;
+
+        self
+    }
+
+    /// Whether any time-to-live is set, so that the cache needs to be swept.
+    pub(crate) fn has_ttl(&self) -> bool {
+        self.message_ttl.is_some() || self.user_ttl.is_some() || self.member_ttl.is_some()
+    }
+
+    /// Sets the hook called whenever a guild is inserted into the cache.
+    ///
+    /// Refer to [`on_guild_insert`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Log the name of every guild added to the cache:
+    ///
+    /// ```rust
+    /// use serenity::cache::Settings;
+    ///
+    /// let mut settings = Settings::new();
+    /// settings.on_guild_insert(|guild| println!("Caching {}", guild.name));
+    /// ```
+    ///
+    /// [`on_guild_insert`]: #structfield.on_guild_insert
+    pub fn on_guild_insert<F>(&mut self, hook: F) -> &mut Self
+    where
+        F: Fn(&Guild) + Send + Sync + 'static,
+    {
+        self.on_guild_insert = Some(Arc::new(hook));
+
+        self
+    }
+
+    /// Sets the hook called whenever a member is inserted into the cache.
+    ///
+    /// Refer to [`on_member_insert`] for more information.
+    ///
+    /// [`on_member_insert`]: #structfield.on_member_insert
+    pub fn on_member_insert<F>(&mut self, hook: F) -> &mut Self
+    where
+        F: Fn(GuildId, &Member) + Send + Sync + 'static,
+    {
+        self.on_member_insert = Some(Arc::new(hook));
+
+        self
+    }
+
+    /// Sets the hook called whenever a guild is removed from the cache.
+    ///
+    /// Refer to [`on_guild_remove`] for more information.
+    ///
+    /// [`on_guild_remove`]: #structfield.on_guild_remove
+    pub fn on_guild_remove<F>(&mut self, hook: F) -> &mut Self
+    where
+        F: Fn(GuildId) + Send + Sync + 'static,
+    {
+        self.on_guild_remove = Some(Arc::new(hook));
+
+        self
+    }
 }
@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+#[cfg(feature = "client")]
+use std::sync::Weak;
+use std::time::Duration;
+
+#[cfg(feature = "client")]
+use tokio::sync::oneshot::{self, Sender};
+use tokio::task::yield_now;
+#[cfg(feature = "client")]
+use tokio::time::timeout;
+#[cfg(feature = "client")]
+use tracing::debug;
+
+use super::Cache;
+#[cfg(feature = "client")]
+use crate::internal::tokio::spawn_named;
+use crate::model::prelude::*;
+
+/// How many entries are checked for each acquisition of the cache's locks
+/// while sweeping, so that other tasks can access the cache in between.
+const SWEEP_BATCH_SIZE: usize = 1000;
+
+/// Marks an [`AccessTime`] which the sweeper has not seen yet.
+const UNSEEN: u32 = u32::MAX;
+
+/// Statistics of the entries evicted from the cache for having expired.
+///
+/// Refer to [`Cache::sweep_expired`] for more information.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct SweepStats {
+    /// The number of sweeps performed.
+    pub sweeps: u64,
+    /// The number of messages which were evicted.
+    pub messages: u64,
+    /// The number of users which were evicted.
+    pub users: u64,
+    /// The number of members which were evicted.
+    pub members: u64,
+}
+
+impl SweepStats {
+    /// The total number of entries which were evicted.
+    pub fn evicted(&self) -> u64 {
+        self.messages + self.users + self.members
+    }
+
+    pub(crate) fn add(&mut self, other: SweepStats) {
+        self.sweeps += other.sweeps;
+        self.messages += other.messages;
+        self.users += other.users;
+        self.members += other.members;
+    }
+}
+
+/// When a cache entry was last accessed, in minutes since the cache was
+/// created.
+///
+/// Entries start out unseen, and are timestamped by the sweeper the first time
+/// it sees them, so that they do not need to be timestamped everywhere they
+/// are inserted.
+#[derive(Debug)]
+pub(crate) struct AccessTime(AtomicU32);
+
+impl AccessTime {
+    pub(crate) fn touch(&self, minute: u32) {
+        self.0.store(minute, Ordering::Relaxed);
+    }
+
+    /// Whether the entry was not accessed for longer than `ttl` minutes,
+    /// timestamping it if it was not seen before.
+    fn is_expired(&self, now: u32, ttl: u32) -> bool {
+        match self.0.load(Ordering::Relaxed) {
+            UNSEEN => {
+                self.touch(now);
+
+                false
+            },
+            minute => now.saturating_sub(minute) > ttl,
+        }
+    }
+}
+
+impl Default for AccessTime {
+    fn default() -> Self {
+        Self(AtomicU32::new(UNSEEN))
+    }
+}
+
+impl Clone for AccessTime {
+    fn clone(&self) -> Self {
+        Self(AtomicU32::new(self.0.load(Ordering::Relaxed)))
+    }
+}
+
+/// Converts a time-to-live into whole minutes, rounding up.
+fn ttl_minutes(ttl: Duration) -> u32 {
+    ((ttl.as_secs() + 59) / 60) as u32
+}
+
+impl Cache {
+    /// The number of minutes since the cache was created, the resolution of
+    /// the timestamps used for evicting expired entries.
+    pub(crate) fn minute(&self) -> u32 {
+        (self.created_at.elapsed().as_secs() / 60) as u32
+    }
+
+    /// Evicts the entries which expired according to the time-to-live
+    /// [settings] of the cache, returning how many entries were evicted.
+    ///
+    /// The entries are checked in small batches, releasing the cache's locks
+    /// in between, so that the cache can still be used during a sweep.
+    ///
+    /// This is called periodically by the [`Client`] if any time-to-live is
+    /// set, so it only has to be called when using the cache without a client.
+    ///
+    /// **Note**: Timestamps have a resolution of one minute, and entries are
+    /// only evicted once a sweep runs, so entries may be held for up to a
+    /// minute plus the [sweep interval] longer than their time-to-live.
+    ///
+    /// [settings]: super::Settings::message_ttl
+    /// [`Client`]: crate::Client
+    /// [sweep interval]: super::Settings::sweep_interval
+    pub async fn sweep_expired(&self) -> SweepStats {
+        let (message_ttl, user_ttl, member_ttl) = {
+            let settings = self.settings.read().await;
+
+            (settings.message_ttl, settings.user_ttl, settings.member_ttl)
+        };
+
+        let mut stats = SweepStats {
+            sweeps: 1,
+            ..SweepStats::default()
+        };
+
+        if let Some(ttl) = message_ttl {
+            stats.messages = self.sweep_messages(ttl_minutes(ttl)).await;
+        }
+
+        // Members are swept before users, as a user is only evicted once no
+        // cached member refers to it anymore.
+        if let Some(ttl) = member_ttl {
+            stats.members = self.sweep_members(ttl_minutes(ttl)).await;
+        }
+
+        if let Some(ttl) = user_ttl {
+            stats.users = self.sweep_users(ttl_minutes(ttl)).await;
+        }
+
+        self.sweep_totals.write().await.add(stats);
+
+        stats
+    }
+
+    /// Returns the totals of all sweeps of the cache so far.
+    ///
+    /// Refer to [`Self::sweep_expired`] for more information.
+    pub async fn sweep_stats(&self) -> SweepStats {
+        *self.sweep_totals.read().await
+    }
+
+    /// Evicts the messages which were inserted more than `ttl` minutes ago.
+    async fn sweep_messages(&self, ttl: u32) -> u64 {
+        let now = self.minute();
+        let channel_ids = self.message_queue.read().await.keys().copied().collect::<Vec<_>>();
+        let mut evicted = 0;
+
+        for channel_ids in channel_ids.chunks(SWEEP_BATCH_SIZE) {
+            {
+                let mut messages = self.messages.write().await;
+                let mut queues = self.message_queue.write().await;
+
+                for channel_id in channel_ids {
+                    let queue = match queues.get_mut(channel_id) {
+                        Some(queue) => queue,
+                        None => continue,
+                    };
+
+                    // The queue is ordered by insertion, so the expired
+                    // messages are at its front.
+                    while let Some(&(message_id, inserted)) = queue.front() {
+                        if now.saturating_sub(inserted) <= ttl {
+                            break;
+                        }
+
+                        queue.pop_front();
+
+                        if let Some(channel_messages) = messages.get_mut(channel_id) {
+                            if channel_messages.remove(&message_id).is_some() {
+                                evicted += 1;
+                            }
+                        }
+                    }
+
+                    if queue.is_empty() {
+                        queues.remove(channel_id);
+
+                        if messages.get(channel_id).map_or(false, HashMap::is_empty) {
+                            messages.remove(channel_id);
+                        }
+                    }
+                }
+            }
+
+            yield_now().await;
+        }
+
+        evicted
+    }
+
+    /// Evicts the members which were not accessed for more than `ttl` minutes,
+    /// except for the members of the current user.
+    async fn sweep_members(&self, ttl: u32) -> u64 {
+        let now = self.minute();
+        let current_user_id = self.current_user_id().await;
+        let keys = {
+            let members = self.members.read().await;
+
+            members
+                .iter()
+                .flat_map(|(guild_id, members)| members.keys().map(move |id| (*guild_id, *id)))
+                .filter(|(_, user_id)| *user_id != current_user_id)
+                .collect::<Vec<_>>()
+        };
+        let mut evicted = 0;
+
+        for keys in keys.chunks(SWEEP_BATCH_SIZE) {
+            {
+                let mut members = self.members.write().await;
+
+                for (guild_id, user_id) in keys {
+                    let guild_members = match members.get_mut(guild_id) {
+                        Some(guild_members) => guild_members,
+                        None => continue,
+                    };
+
+                    let is_expired = guild_members
+                        .get(user_id)
+                        .map_or(false, |member| member.last_access.is_expired(now, ttl));
+
+                    if is_expired {
+                        guild_members.remove(user_id);
+                        evicted += 1;
+                    }
+                }
+            }
+
+            yield_now().await;
+        }
+
+        evicted
+    }
+
+    /// Evicts the users which were not accessed for more than `ttl` minutes,
+    /// and which no cached member refers to.
+    async fn sweep_users(&self, ttl: u32) -> u64 {
+        let now = self.minute();
+        let user_ids = self.users.read().await.keys().copied().collect::<Vec<_>>();
+        let mut evicted = 0;
+
+        for user_ids in user_ids.chunks(SWEEP_BATCH_SIZE) {
+            {
+                let mut users = self.users.write().await;
+                let mut access_times = self.user_access_times.write().await;
+
+                for user_id in user_ids {
+                    // The users map holds the only reference to users which no
+                    // member refers to.
+                    let is_referenced = match users.get(user_id) {
+                        Some(user) => Arc::strong_count(user) > 1,
+                        None => continue,
+                    };
+
+                    let is_expired = access_times.entry(*user_id).or_default().is_expired(now, ttl);
+
+                    if is_expired && !is_referenced {
+                        users.remove(user_id);
+                        access_times.remove(user_id);
+                        evicted += 1;
+                    }
+                }
+            }
+
+            yield_now().await;
+        }
+
+        evicted
+    }
+
+    /// Marks a user as accessed, so that it is not evicted.
+    pub(crate) async fn touch_user(&self, user_id: UserId) {
+        if let Some(access_time) = self.user_access_times.read().await.get(&user_id) {
+            access_time.touch(self.minute());
+        }
+    }
+}
+
+/// Periodically evicts the expired entries of a cache, until it is dropped.
+///
+/// Refer to [`Cache::sweep_expired`] for more information.
+#[cfg(feature = "client")]
+#[derive(Debug)]
+pub(crate) struct CacheSweeper {
+    _stop: Sender<()>,
+}
+
+#[cfg(feature = "client")]
+impl CacheSweeper {
+    /// Spawns a task sweeping the `cache` every `interval`.
+    ///
+    /// The task stops once the returned sweeper or the cache is dropped.
+    pub(crate) fn spawn(cache: &Arc<Cache>, interval: Duration) -> Self {
+        let (stop, mut stopped) = oneshot::channel::<()>();
+        let cache = Arc::downgrade(cache);
+
+        spawn_named("cache::sweeper", async move {
+            // The sender is never used, so this only completes once it is
+            // dropped.
+            while timeout(interval, &mut stopped).await.is_err() {
+                let cache = match Weak::upgrade(&cache) {
+                    Some(cache) => cache,
+                    None => break,
+                };
+
+                let stats = cache.sweep_expired().await;
+
+                if stats.evicted() > 0 {
+                    debug!("Evicted expired cache entries: {:?}", stats);
+                }
+            }
+        });
+
+        Self {
+            _stop: stop,
+        }
+    }
+}
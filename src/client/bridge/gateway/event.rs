@@ -1,6 +1,8 @@
 //! A collection of events created by the client, not a part of the Discord API
 //! itself.
 
+use std::time::Duration;
+
 use super::ShardId;
 use crate::gateway::ConnectionStage;
 
@@ -8,6 +10,7 @@ use crate::gateway::ConnectionStage;
 #[derive(Clone, Debug)]
 pub(crate) enum ClientEvent {
     ShardStageUpdate(ShardStageUpdateEvent),
+    ShardLifecycle(ShardLifecycleEvent),
 }
 
 /// An event denoting that a shard's connection stage was changed.
@@ -25,3 +28,18 @@ pub struct ShardStageUpdateEvent {
     /// The ID of the shard that had its connection stage change.
     pub shard_id: ShardId,
 }
+
+/// An event denoting that something happened to a shard's connection which
+/// the shard recovers from by itself.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum ShardLifecycleEvent {
+    /// The shard received no gateway frame for longer than the timeout of its
+    /// [`StallWatchdog`], and is reconnecting.
+    ///
+    /// [`StallWatchdog`]: super::StallWatchdog
+    Stalled {
+        /// For how long no frame was received.
+        silent_for: Duration,
+    },
+}
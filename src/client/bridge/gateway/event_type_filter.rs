@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+
+use crate::model::event::EventType;
+
+/// A filter deciding which gateway events a [`ShardRunner`] fully deserializes
+/// and dispatches.
+///
+/// Events that are filtered out are skipped based on their raw dispatch name,
+/// before the payload is deserialized into an [`Event`]. This saves the cost of
+/// deserializing high-volume events a bot never handles, such as
+/// [`EventType::PresenceUpdate`] or [`EventType::TypingStart`].
+///
+/// Filtered events never reach the cache, the framework or the
+/// [`EventHandler`]. If a [`RawEventHandler`] is installed, filtered events
+/// are still deserialized and passed to it, but to nothing else.
+///
+/// [`EventType::Ready`] and [`EventType::Resumed`] are needed by the shard
+/// itself and are never filtered. Event types a running collector waits for are
+/// exempted as well.
+///
+/// [`ShardRunner`]: super::ShardRunner
+/// [`Event`]: crate::model::event::Event
+/// [`EventHandler`]: crate::client::EventHandler
+/// [`RawEventHandler`]: crate::client::RawEventHandler
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum EventTypeFilter {
+    /// Skips the given event types, and dispatches all others.
+    Deny(HashSet<EventType>),
+    /// Dispatches only the given event types, and skips all others.
+    Allow(HashSet<EventType>),
+}
+
+impl EventTypeFilter {
+    /// Whether events of the given type are filtered out, i.e. should not be
+    /// deserialized and dispatched.
+    #[must_use]
+    pub fn is_filtered(&self, kind: &EventType) -> bool {
+        if matches!(kind, EventType::Ready | EventType::Resumed) {
+            return false;
+        }
+
+        match self {
+            Self::Deny(kinds) => kinds.contains(kind),
+            Self::Allow(kinds) => !kinds.contains(kind),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::EventTypeFilter;
+    use crate::model::event::EventType;
+
+    #[test]
+    fn test_deny() {
+        let filter = EventTypeFilter::Deny(
+            vec![EventType::PresenceUpdate, EventType::TypingStart].into_iter().collect(),
+        );
+
+        assert!(filter.is_filtered(&EventType::PresenceUpdate));
+        assert!(filter.is_filtered(&EventType::TypingStart));
+        assert!(!filter.is_filtered(&EventType::MessageCreate));
+        assert!(!filter.is_filtered(&EventType::Other("UNKNOWN".to_string())));
+    }
+
+    #[test]
+    fn test_allow() {
+        let filter = EventTypeFilter::Allow(vec![EventType::MessageCreate].into_iter().collect());
+
+        assert!(!filter.is_filtered(&EventType::MessageCreate));
+        assert!(filter.is_filtered(&EventType::PresenceUpdate));
+        assert!(filter.is_filtered(&EventType::Other("UNKNOWN".to_string())));
+    }
+
+    #[test]
+    fn test_shard_events_are_never_filtered() {
+        let filter = EventTypeFilter::Allow(HashSet::new());
+
+        assert!(!filter.is_filtered(&EventType::Ready));
+        assert!(!filter.is_filtered(&EventType::Resumed));
+
+        let filter =
+            EventTypeFilter::Deny(vec![EventType::Ready, EventType::Resumed].into_iter().collect());
+
+        assert!(!filter.is_filtered(&EventType::Ready));
+        assert!(!filter.is_filtered(&EventType::Resumed));
+    }
+}
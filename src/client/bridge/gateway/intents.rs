@@ -1,7 +1,12 @@
+use std::convert::TryFrom;
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
 use bitflags::__impl_bitflags;
 use serde::{
-    de::{Deserialize, Deserializer},
-    ser::{Serialize, Serializer},
+    de::{Deserialize, Deserializer, Error as DeError, SeqAccess, Unexpected, Visitor},
+    ser::{Serialize, SerializeSeq, Serializer},
 };
 
 /// [Gateway Intents] will limit the events your bot will receive via the gateway.
@@ -18,8 +23,8 @@ use serde::{
 ///
 /// # Privileged Intents
 ///
-/// The intents [`GatewayIntents::GUILD_PRESENCES`] and [`GatewayIntents::GUILD_MEMBERS`]
-/// are [Privileged Intents]. They need to be enabled in the
+/// The intents [`GatewayIntents::GUILD_PRESENCES`], [`GatewayIntents::GUILD_MEMBERS`]
+/// and [`GatewayIntents::MESSAGE_CONTENT`] are [Privileged Intents]. They need to be enabled in the
 /// *developer portal*.
 ///
 /// **Note**:
@@ -154,6 +159,15 @@ __impl_bitflags! {
         ///
         /// - TYPING_START
         DIRECT_MESSAGE_TYPING = 1 << 14;
+        /// Enables receiving the content, embeds, attachments and components
+        /// of messages which do not mention the bot and are not sent to it
+        /// directly.
+        ///
+        /// **Info**:
+        /// This intent is *privileged*.
+        /// In order to use it, you must head to your application in the
+        /// Developer Portal and enable the toggle for *Privileged Intents*.
+        MESSAGE_CONTENT = 1 << 15;
         /// Enable following gateway events:
         ///
         /// - GUILD_SCHEDULED_EVENT_CREATE
@@ -171,18 +185,218 @@ impl Default for GatewayIntents {
     }
 }
 
+/// The names of all intents, in the order of their bits.
+const NAMES: [(&str, GatewayIntents); 17] = [
+    ("GUILDS", GatewayIntents::GUILDS),
+    ("GUILD_MEMBERS", GatewayIntents::GUILD_MEMBERS),
+    ("GUILD_BANS", GatewayIntents::GUILD_BANS),
+    ("GUILD_EMOJIS", GatewayIntents::GUILD_EMOJIS),
+    ("GUILD_INTEGRATIONS", GatewayIntents::GUILD_INTEGRATIONS),
+    ("GUILD_WEBHOOKS", GatewayIntents::GUILD_WEBHOOKS),
+    ("GUILD_INVITES", GatewayIntents::GUILD_INVITES),
+    ("GUILD_VOICE_STATES", GatewayIntents::GUILD_VOICE_STATES),
+    ("GUILD_PRESENCES", GatewayIntents::GUILD_PRESENCES),
+    ("GUILD_MESSAGES", GatewayIntents::GUILD_MESSAGES),
+    ("GUILD_MESSAGE_REACTIONS", GatewayIntents::GUILD_MESSAGE_REACTIONS),
+    ("GUILD_MESSAGE_TYPING", GatewayIntents::GUILD_MESSAGE_TYPING),
+    ("DIRECT_MESSAGES", GatewayIntents::DIRECT_MESSAGES),
+    ("DIRECT_MESSAGE_REACTIONS", GatewayIntents::DIRECT_MESSAGE_REACTIONS),
+    ("DIRECT_MESSAGE_TYPING", GatewayIntents::DIRECT_MESSAGE_TYPING),
+    ("MESSAGE_CONTENT", GatewayIntents::MESSAGE_CONTENT),
+    ("GUILD_SCHEDULED_EVENTS", GatewayIntents::GUILD_SCHEDULED_EVENTS),
+];
+
+impl GatewayIntents {
+    /// Iterates over the names of the contained intents, such as
+    /// `"GUILD_MESSAGES"`.
+    ///
+    /// Bits that do not belong to a known intent are skipped.
+    pub fn names(self) -> impl Iterator<Item = &'static str> {
+        NAMES.iter().filter(move |(_, intent)| self.contains(*intent)).map(|(name, _)| *name)
+    }
+
+    /// Looks up a single intent by its name, ignoring case.
+    fn from_name(name: &str) -> Result<Self, ParseIntentsError> {
+        NAMES
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(name))
+            .map(|(_, intent)| *intent)
+            .ok_or_else(|| ParseIntentsError {
+                name: name.to_string(),
+            })
+    }
+
+    /// Serializes the intents as their raw bits instead of the list of their
+    /// names.
+    ///
+    /// This is meant for use with `#[serde(serialize_with)]`; deserializing
+    /// accepts the raw bits either way.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serenity::client::bridge::gateway::GatewayIntents;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Config {
+    ///     #[serde(serialize_with = "GatewayIntents::serialize_bits")]
+    ///     intents: GatewayIntents,
+    /// }
+    ///
+    /// let config = Config {
+    ///     intents: GatewayIntents::GUILDS | GatewayIntents::GUILD_MESSAGES,
+    /// };
+    ///
+    /// assert_eq!(serde_json::to_string(&config).unwrap(), r#"{"intents":513}"#);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the serializer, if any.
+    pub fn serialize_bits<S>(intents: &GatewayIntents, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(intents.bits())
+    }
+}
+
+impl Display for GatewayIntents {
+    /// Writes the names of the contained intents, separated by `" | "`, e.g.
+    /// `GUILDS | GUILD_MESSAGES`. This can be parsed back using [`FromStr`].
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, name) in self.names().enumerate() {
+            if i > 0 {
+                f.write_str(" | ")?;
+            }
+
+            f.write_str(name)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for GatewayIntents {
+    type Err = ParseIntentsError;
+
+    /// Parses a list of intent names, separated by commas or pipes.
+    ///
+    /// Names are matched ignoring case, and surrounding whitespace is
+    /// ignored. An empty string parses to no intents.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::client::bridge::gateway::GatewayIntents;
+    ///
+    /// let intents: GatewayIntents = "GUILDS, guild_messages | DIRECT_MESSAGES".parse().unwrap();
+    ///
+    /// assert_eq!(
+    ///     intents,
+    ///     GatewayIntents::GUILDS | GatewayIntents::GUILD_MESSAGES | GatewayIntents::DIRECT_MESSAGES,
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(|c| c == ',' || c == '|')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .try_fold(Self::empty(), |intents, name| Ok(intents | Self::from_name(name)?))
+    }
+}
+
+/// The error returned when parsing [`GatewayIntents`] from a string fails,
+/// because of a name that does not belong to any intent.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseIntentsError {
+    name: String,
+}
+
+impl ParseIntentsError {
+    /// The unknown intent name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Display for ParseIntentsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown gateway intent `{}`, expected one of: ", self.name)?;
+
+        for (i, (name, _)) in NAMES.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            f.write_str(name)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl StdError for ParseIntentsError {}
+
+struct GatewayIntentsVisitor;
+
+impl<'de> Visitor<'de> for GatewayIntentsVisitor {
+    type Value = GatewayIntents;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("gateway intent bits, or a list of gateway intent names")
+    }
+
+    fn visit_u64<E: DeError>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(GatewayIntents::from_bits_truncate(value))
+    }
+
+    fn visit_i64<E: DeError>(self, value: i64) -> Result<Self::Value, E> {
+        u64::try_from(value)
+            .map(GatewayIntents::from_bits_truncate)
+            .map_err(|_| E::invalid_value(Unexpected::Signed(value), &self))
+    }
+
+    fn visit_str<E: DeError>(self, value: &str) -> Result<Self::Value, E> {
+        value.parse().map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut intents = GatewayIntents::empty();
+
+        while let Some(name) = seq.next_element::<String>()? {
+            intents |= GatewayIntents::from_name(name.trim()).map_err(A::Error::custom)?;
+        }
+
+        Ok(intents)
+    }
+}
+
 impl<'de> Deserialize<'de> for GatewayIntents {
+    /// Deserializes the intents from either their raw bits, a string as
+    /// accepted by [`FromStr`], or a list of intent names.
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        Ok(Self::from_bits_truncate(u64::deserialize(deserializer)?))
+        deserializer.deserialize_any(GatewayIntentsVisitor)
     }
 }
 
 impl Serialize for GatewayIntents {
+    /// Serializes the intents as the list of their names.
+    ///
+    /// Use [`GatewayIntents::serialize_bits`] to serialize the raw bits
+    /// instead.
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_u64(self.bits())
+        let mut seq = serializer.serialize_seq(Some(self.names().count()))?;
+
+        for name in self.names() {
+            seq.serialize_element(name)?;
+        }
+
+        seq.end()
     }
 }
 
@@ -200,15 +414,20 @@ impl GatewayIntents {
     pub const fn privileged() -> GatewayIntents {
         // bitflags don't support const evaluation. Workaround.
         // See: https://github.com/bitflags/bitflags/issues/180
-        Self::from_bits_truncate(Self::GUILD_MEMBERS.bits() | Self::GUILD_PRESENCES.bits())
+        Self::from_bits_truncate(
+            Self::GUILD_MEMBERS.bits()
+                | Self::GUILD_PRESENCES.bits()
+                | Self::MESSAGE_CONTENT.bits(),
+        )
     }
 
     /// Checks if any of the included intents are privileged
     ///
     /// [GUILD_MEMBERS]: #associatedconstant.GUILD_MEMBERS
     /// [GUILD_PRESENCES]: #associatedconstant.GUILD_PRESENCES
+    /// [MESSAGE_CONTENT]: #associatedconstant.MESSAGE_CONTENT
     pub fn is_privileged(self) -> bool {
-        self.guild_members() || self.guild_presences()
+        self.guild_members() || self.guild_presences() || self.message_content()
     }
 
     /// Shorthand for checking that the set of intents contains the
@@ -330,4 +549,115 @@ impl GatewayIntents {
     pub fn direct_message_typing(self) -> bool {
         self.contains(Self::DIRECT_MESSAGE_TYPING)
     }
+
+    /// Shorthand for checking that the set of intents contains the
+    /// [MESSAGE_CONTENT] intent.
+    ///
+    /// [MESSAGE_CONTENT]: Self::MESSAGE_CONTENT
+    pub fn message_content(self) -> bool {
+        self.contains(Self::MESSAGE_CONTENT)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{GatewayIntents, NAMES};
+
+    #[test]
+    fn test_names_cover_all_intents() {
+        let all = NAMES.iter().fold(GatewayIntents::empty(), |all, (_, intent)| all | *intent);
+        assert_eq!(all, GatewayIntents::all());
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_round_trip() {
+        for (name, intent) in &NAMES {
+            assert_eq!(intent.to_string(), *name);
+            assert_eq!(name.parse::<GatewayIntents>().unwrap(), *intent);
+            assert_eq!(name.to_lowercase().parse::<GatewayIntents>().unwrap(), *intent);
+
+            let value = serde_json::to_value(intent).unwrap();
+            assert_eq!(value, json!([name]));
+            assert_eq!(serde_json::from_value::<GatewayIntents>(value).unwrap(), *intent);
+        }
+
+        let all = GatewayIntents::all();
+        assert_eq!(all.to_string().parse::<GatewayIntents>().unwrap(), all);
+        assert_eq!(serde_json::from_value::<GatewayIntents>(json!(all)).unwrap(), all);
+
+        let empty = GatewayIntents::empty();
+        assert_eq!(empty.to_string(), "");
+        assert_eq!("".parse::<GatewayIntents>().unwrap(), empty);
+        assert_eq!(json!(empty), json!([]));
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_parse() {
+        let expected = GatewayIntents::GUILDS
+            | GatewayIntents::GUILD_MESSAGES
+            | GatewayIntents::DIRECT_MESSAGES;
+
+        for s in &[
+            "GUILDS,GUILD_MESSAGES,DIRECT_MESSAGES",
+            "guilds | guild_messages | direct_messages",
+            " Guilds,GUILD_MESSAGES|direct_messages, ",
+        ] {
+            assert_eq!(s.parse::<GatewayIntents>().unwrap(), expected);
+        }
+
+        assert_eq!(expected.to_string(), "GUILDS | GUILD_MESSAGES | DIRECT_MESSAGES");
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_deserialize() {
+        let expected = GatewayIntents::GUILDS | GatewayIntents::GUILD_MESSAGES;
+
+        for value in
+            &[json!(513), json!("GUILDS, GUILD_MESSAGES"), json!(["guilds", "GUILD_MESSAGES"])]
+        {
+            assert_eq!(serde_json::from_value::<GatewayIntents>(value.clone()).unwrap(), expected);
+        }
+
+        assert!(serde_json::from_value::<GatewayIntents>(json!(-1)).is_err());
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_unknown_name() {
+        let err = "GUILDS,GUILD_MESAGES".parse::<GatewayIntents>().unwrap_err();
+        assert_eq!(err.name(), "GUILD_MESAGES");
+        assert!(err.to_string().starts_with("unknown gateway intent `GUILD_MESAGES`"));
+        assert!(err.to_string().contains("GUILD_MESSAGES"));
+
+        let err = serde_json::from_value::<GatewayIntents>(json!(["GUILDS", "GUILD_STICKERS"]))
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown gateway intent `GUILD_STICKERS`"));
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_message_content() {
+        let expected = GatewayIntents::GUILDS
+            | GatewayIntents::GUILD_MESSAGES
+            | GatewayIntents::MESSAGE_CONTENT;
+
+        let parsed = "GUILDS,GUILD_MESSAGES,MESSAGE_CONTENT".parse::<GatewayIntents>().unwrap();
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.to_string().parse::<GatewayIntents>().unwrap(), expected);
+
+        let value = serde_json::to_value(expected).unwrap();
+        assert_eq!(value, json!(["GUILDS", "GUILD_MESSAGES", "MESSAGE_CONTENT"]));
+        assert_eq!(serde_json::from_value::<GatewayIntents>(value).unwrap(), expected);
+
+        #[cfg(feature = "model")]
+        {
+            assert!(parsed.is_privileged());
+            assert!(!GatewayIntents::non_privileged().message_content());
+        }
+    }
 }
@@ -0,0 +1,225 @@
+//! A gateway standing in for Discord's in tests, which shards connect to over
+//! a local WebSocket.
+//!
+//! Shards are sent a `READY` when they identify, and a `RESUMED` when they
+//! resume. Heartbeats are acknowledged, everything else sent by the shards is
+//! ignored.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use async_tungstenite::tokio::accept_async;
+use async_tungstenite::tungstenite::Message;
+use futures::channel::mpsc::{self, UnboundedSender as Sender};
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::sleep;
+
+/// The heartbeat interval sent to the shards, long enough for shards not to
+/// miss an acknowledgement when the clock is paused and advances quickly.
+const HEARTBEAT_INTERVAL: u64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Default)]
+struct State {
+    /// The connections of the shards which identified or resumed.
+    connections: HashMap<u64, Sender<Value>>,
+    /// The data of every identify, in order.
+    identifies: Vec<Value>,
+    /// The shard of every resume, in order.
+    resumed: Vec<u64>,
+    /// The shard and close code of every connection closed by a shard, in
+    /// order.
+    closed: Vec<(u64, Option<u16>)>,
+}
+
+#[derive(Debug)]
+pub(crate) struct MockGateway {
+    url: String,
+    state: Arc<Mutex<State>>,
+}
+
+impl MockGateway {
+    /// Starts listening for shards on a free local port.
+    #[allow(clippy::unwrap_used)]
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let shared = Arc::clone(&state);
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(serve(socket, Arc::clone(&shared)));
+            }
+        });
+
+        Self {
+            url,
+            state,
+        }
+    }
+
+    /// The URL to connect the shards to.
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// The shard of every identify so far, in order.
+    pub fn identified(&self) -> Vec<u64> {
+        let state = lock(&self.state);
+
+        state.identifies.iter().map(|data| data["shard"][0].as_u64().unwrap_or_default()).collect()
+    }
+
+    /// The data of every identify so far, in order.
+    pub fn identifies(&self) -> Vec<Value> {
+        lock(&self.state).identifies.clone()
+    }
+
+    /// The shard of every resume so far, in order.
+    pub fn resumed(&self) -> Vec<u64> {
+        lock(&self.state).resumed.clone()
+    }
+
+    /// The shard and close code of every connection closed by a shard so far,
+    /// in order.
+    pub fn closed(&self) -> Vec<(u64, Option<u16>)> {
+        lock(&self.state).closed.clone()
+    }
+
+    /// Sends a dispatch to a shard, once it is connected.
+    pub async fn dispatch(&self, shard_id: u64, kind: &str, data: Value) {
+        self.send(shard_id, json!({"op": 0, "t": kind, "d": data})).await;
+    }
+
+    /// Sends a payload to a shard, once it is connected. The sequence of a
+    /// dispatch is filled in.
+    pub async fn send(&self, shard_id: u64, payload: Value) {
+        loop {
+            if let Some(connection) = lock(&self.state).connections.get(&shard_id) {
+                if connection.unbounded_send(payload.clone()).is_ok() {
+                    return;
+                }
+            }
+
+            sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    /// Waits until the number of identifies reaches `count`.
+    pub async fn wait_identified(&self, count: usize) {
+        while lock(&self.state).identifies.len() < count {
+            sleep(Duration::from_millis(10)).await;
+        }
+    }
+}
+
+fn lock(state: &Mutex<State>) -> std::sync::MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The `READY` sent to a shard which identified.
+fn ready(shard: &Value) -> Value {
+    json!({
+        "v": 9,
+        "user": {
+            "id": "210",
+            "username": "Mock",
+            "discriminator": "0001",
+            "avatar": null,
+            "bot": true,
+            "mfa_enabled": false,
+        },
+        "guilds": [],
+        "session_id": format!("session-{}", shard[0]),
+        "shard": shard,
+        "application": {"id": "210", "flags": 0},
+    })
+}
+
+async fn serve(socket: TcpStream, state: Arc<Mutex<State>>) {
+    let mut ws = match accept_async(socket).await {
+        Ok(ws) => ws,
+        Err(_) => return,
+    };
+    let (tx, mut rx) = mpsc::unbounded::<Value>();
+    let mut shard_id = None;
+    let mut seq = 0;
+
+    let hello = json!({"op": 10, "d": {"heartbeat_interval": HEARTBEAT_INTERVAL}});
+    if ws.send(Message::Text(hello.to_string())).await.is_err() {
+        return;
+    }
+
+    loop {
+        let mut payload = tokio::select! {
+            message = ws.next() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(frame))) => {
+                        if let Some(id) = shard_id {
+                            let code = frame.map(|frame| u16::from(frame.code));
+                            lock(&state).closed.push((id, code));
+                        }
+
+                        break;
+                    },
+                    Some(Ok(_)) => continue,
+                    Some(Err(_)) | None => break,
+                };
+                let received = match serde_json::from_str::<Value>(&text) {
+                    Ok(received) => received,
+                    Err(_) => continue,
+                };
+
+                match received["op"].as_u64() {
+                    Some(1) => json!({"op": 11}),
+                    Some(2) => {
+                        let shard = received["d"]["shard"].clone();
+                        let id = shard[0].as_u64().unwrap_or_default();
+
+                        let mut state = lock(&state);
+                        state.identifies.push(received["d"].clone());
+                        state.connections.insert(id, tx.clone());
+                        shard_id = Some(id);
+
+                        json!({"op": 0, "t": "READY", "d": ready(&shard)})
+                    },
+                    Some(6) => {
+                        let session_id = received["d"]["session_id"].as_str().unwrap_or_default();
+                        let id =
+                            session_id.trim_start_matches("session-").parse().unwrap_or_default();
+
+                        let mut state = lock(&state);
+                        state.resumed.push(id);
+                        state.connections.insert(id, tx.clone());
+                        shard_id = Some(id);
+
+                        json!({"op": 0, "t": "RESUMED", "d": {"_trace": []}})
+                    },
+                    _ => continue,
+                }
+            },
+            Some(payload) = rx.next() => payload,
+        };
+
+        if payload["op"] == 0 {
+            seq += 1;
+            payload["s"] = json!(seq);
+        }
+
+        if ws.send(Message::Text(payload.to_string())).await.is_err() {
+            break;
+        }
+    }
+
+    if let Some(id) = shard_id {
+        let mut state = lock(&state);
+
+        if state.connections.get(&id).map_or(false, |connection| connection.same_receiver(&tx)) {
+            state.connections.remove(&id);
+        }
+    }
+}
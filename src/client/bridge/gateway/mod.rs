@@ -44,26 +44,36 @@
 
 pub mod event;
 
+mod event_type_filter;
 mod intents;
+#[cfg(test)]
+pub(crate) mod mock_gateway;
 mod shard_manager;
 mod shard_manager_monitor;
 mod shard_messenger;
 mod shard_queuer;
 mod shard_runner;
 mod shard_runner_message;
+mod stall_watchdog;
 
 use std::{
     fmt::{Display, Formatter, Result as FmtResult},
     time::Duration as StdDuration,
 };
 
-pub use self::intents::GatewayIntents;
+pub use self::event_type_filter::EventTypeFilter;
+pub use self::intents::{GatewayIntents, ParseIntentsError};
 pub use self::shard_manager::{ShardManager, ShardManagerOptions};
 pub use self::shard_manager_monitor::{ShardManagerError, ShardManagerMonitor};
 pub use self::shard_messenger::ShardMessenger;
 pub use self::shard_queuer::ShardQueuer;
 pub use self::shard_runner::{ShardRunner, ShardRunnerOptions};
-pub use self::shard_runner_message::{ChunkGuildFilter, ShardRunnerMessage};
+#[cfg(feature = "test_utils")]
+pub(crate) use self::shard_runner::dispatching;
+#[cfg(all(feature = "test_utils", feature = "collector"))]
+pub(crate) use self::shard_runner::CollectorFilters;
+pub use self::shard_runner_message::{Acknowledgement, ChunkGuildFilter, ShardRunnerMessage};
+pub use self::stall_watchdog::StallWatchdog;
 use crate::gateway::ConnectionStage;
 
 /// A message either for a [`ShardManager`] or a [`ShardRunner`].
@@ -127,6 +137,8 @@ pub enum ShardQueuerMessage {
     Shutdown,
     /// Message to dequeue/shutdown a shard.
     ShutdownShard(ShardId, u16),
+    /// Message to identify shards started from now on with the given intents.
+    SetIntents(GatewayIntents),
 }
 
 /// A light tuplestruct wrapper around a u64 to verify type correctness when
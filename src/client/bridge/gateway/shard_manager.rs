@@ -6,11 +6,16 @@ use std::{
 use futures::channel::mpsc::{self, UnboundedReceiver as Receiver, UnboundedSender as Sender};
 use futures::StreamExt;
 use tokio::sync::{Mutex, RwLock};
-use tokio::time::timeout;
+#[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
+use tokio::time::delay_for as sleep;
+#[cfg(feature = "tokio")]
+use tokio::time::sleep;
+use tokio::time::{timeout, Duration, Instant};
 use tracing::{info, instrument, warn};
 use typemap_rev::TypeMap;
 
 use super::{
+    EventTypeFilter,
     GatewayIntents,
     ShardId,
     ShardManagerMessage,
@@ -18,12 +23,15 @@ use super::{
     ShardQueuer,
     ShardQueuerMessage,
     ShardRunnerInfo,
+    StallWatchdog,
 };
 #[cfg(feature = "voice")]
 use crate::client::bridge::voice::VoiceGatewayManager;
-use crate::client::{EventHandler, RawEventHandler};
+use crate::client::{EventHandler, RawEventHandler, TaskTracker};
+use crate::constants::DEFAULT_MAX_PAYLOAD_SIZE;
 #[cfg(feature = "framework")]
 use crate::framework::Framework;
+use crate::http::Http;
 use crate::internal::prelude::*;
 use crate::internal::tokio::spawn_named;
 use crate::CacheAndHttp;
@@ -53,8 +61,8 @@ use crate::CacheAndHttp;
 /// use std::env;
 /// use std::sync::Arc;
 ///
-/// use serenity::client::bridge::gateway::{GatewayIntents, ShardManager, ShardManagerOptions};
-/// use serenity::client::{EventHandler, RawEventHandler};
+/// use serenity::client::bridge::gateway::{ShardManager, ShardManagerOptions};
+/// use serenity::client::{EventHandler, RawEventHandler, TaskTracker};
 /// use serenity::framework::{Framework, StandardFramework};
 /// use serenity::http::Http;
 /// use serenity::prelude::*;
@@ -70,27 +78,22 @@ use crate::CacheAndHttp;
 /// # let http = &cache_and_http.http;
 /// let gateway_url = Arc::new(Mutex::new(http.get_gateway().await?.url));
 /// let data = Arc::new(RwLock::new(TypeMap::new()));
-/// let event_handler = Arc::new(Handler) as Arc<dyn EventHandler>;
+/// let event_handler = Some(Arc::new(Handler) as Arc<dyn EventHandler>);
 /// let framework =
 ///     Arc::new(Box::new(StandardFramework::new()) as Box<dyn Framework + 'static + Send + Sync>);
+/// let task_tracker = TaskTracker::default();
 ///
-/// ShardManager::new(ShardManagerOptions {
-///     data: &data,
-///     event_handler: &Some(event_handler),
-///     raw_event_handler: &None,
-///     framework: &framework,
-///     // the shard index to start initiating from
-///     shard_index: 0,
-///     // the number of shards to initiate (this initiates 0, 1, and 2)
-///     shard_init: 3,
-///     // the total number of shards in use
-///     shard_total: 5,
-///     # #[cfg(feature = "voice")]
-///     # voice_manager: &None,
-///     ws_url: &gateway_url,
-///     # cache_and_http: &cache_and_http,
-///     intents: GatewayIntents::non_privileged(),
-/// });
+/// let mut options =
+///     ShardManagerOptions::new(&data, &framework, &gateway_url, &cache_and_http, &task_tracker);
+/// options.event_handler = &event_handler;
+/// // the shard index to start initiating from
+/// options.shard_index = 0;
+/// // the number of shards to initiate (this initiates 0, 1, and 2)
+/// options.shard_init = 3;
+/// // the total number of shards in use
+/// options.shard_total = 5;
+///
+/// ShardManager::new(options).await;
 /// #     Ok(())
 /// # }
 /// ```
@@ -113,6 +116,7 @@ pub struct ShardManager {
     shard_total: u64,
     shard_queuer: Sender<ShardQueuerMessage>,
     shard_shutdown: Receiver<ShardId>,
+    http: Arc<Http>,
 }
 
 impl ShardManager {
@@ -141,6 +145,11 @@ impl ShardManager {
             ws_url: Arc::clone(opt.ws_url),
             cache_and_http: Arc::clone(opt.cache_and_http),
             intents: opt.intents,
+            event_type_filter: opt.event_type_filter.clone(),
+            shard_data_init: opt.shard_data_init.clone(),
+            max_payload_size: opt.max_payload_size,
+            stall_watchdog: opt.stall_watchdog,
+            task_tracker: opt.task_tracker.clone(),
         };
 
         spawn_named("shard_queuer::run", async move {
@@ -155,6 +164,7 @@ impl ShardManager {
             shard_total: opt.shard_total,
             shard_shutdown: shutdown_recv,
             runners,
+            http: Arc::clone(&opt.cache_and_http.http),
         }));
 
         (Arc::clone(&manager), ShardManagerMonitor {
@@ -249,6 +259,122 @@ impl ShardManager {
         self.boot([shard_id, ShardId(shard_total)]);
     }
 
+    /// Replaces the bot token, e.g. after it was regenerated, without
+    /// restarting the process.
+    ///
+    /// The token of the [`Http`] client shared with the shards is replaced
+    /// first, so all later requests use the new token. Then all instantiated
+    /// shards are restarted one after another, identifying with the new
+    /// token. Their previous sessions are closed instead of resumed.
+    ///
+    /// Returns whether each shard was started again within 30 seconds. Errors
+    /// while identifying, such as an invalid token, are reported through the
+    /// [`ShardManagerMonitor`] as usual.
+    ///
+    /// The `manager` is only locked while a shard is restarted, so it can be
+    /// used in the meantime, and the monitor keeps handling messages.
+    ///
+    /// **Note**: As shards are started 5 seconds apart, this takes at least
+    /// 5 seconds per shard.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::client::Client;
+    /// use serenity::client::bridge::gateway::ShardManager;
+    ///
+    /// # async fn run(client: Client) {
+    /// let new_token = std::env::var("DISCORD_TOKEN").expect("token");
+    /// let restarted = ShardManager::update_token(&client.shard_manager, &new_token).await;
+    ///
+    /// for (shard_id, success) in restarted {
+    ///     if !success {
+    ///         println!("Shard {} did not restart", shard_id);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    #[instrument(skip(manager, token))]
+    pub async fn update_token(manager: &Mutex<Self>, token: &str) -> HashMap<ShardId, bool> {
+        info!("Updating the token");
+        manager.lock().await.http.set_token(token);
+
+        Self::restart_rolling(manager).await
+    }
+
+    /// Changes the gateway intents without restarting the process.
+    ///
+    /// As the intents can only be set when identifying, all instantiated
+    /// shards are restarted one after another, identifying with the new
+    /// intents. Their previous sessions are closed instead of resumed.
+    ///
+    /// Returns whether each shard was started again, refer to
+    /// [`Self::update_token`].
+    #[instrument(skip(manager))]
+    #[allow(clippy::let_underscore_must_use)]
+    pub async fn update_intents(
+        manager: &Mutex<Self>,
+        intents: GatewayIntents,
+    ) -> HashMap<ShardId, bool> {
+        info!("Updating the intents");
+        let _ = manager
+            .lock()
+            .await
+            .shard_queuer
+            .unbounded_send(ShardQueuerMessage::SetIntents(intents));
+
+        Self::restart_rolling(manager).await
+    }
+
+    /// Restarts all instantiated shards, waiting for each to be started again
+    /// before restarting the next.
+    ///
+    /// The `manager` is unlocked while waiting for a shard to start.
+    async fn restart_rolling(manager: &Mutex<Self>) -> HashMap<ShardId, bool> {
+        const TIMEOUT: Duration = Duration::from_secs(30);
+        const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+        let mut shard_ids = manager.lock().await.shards_instantiated().await;
+        shard_ids.sort();
+
+        let mut restarted = HashMap::with_capacity(shard_ids.len());
+
+        for shard_id in shard_ids {
+            info!("Restarting shard {} with a new session", shard_id);
+
+            {
+                let mut manager = manager.lock().await;
+
+                // Closing with 1000 invalidates the session, so the shard does
+                // not resume it with the previous token or intents.
+                manager.shutdown(shard_id, 1000).await;
+
+                let shard_total = manager.shard_total;
+                manager.boot([shard_id, ShardId(shard_total)]);
+            }
+
+            let started = Instant::now();
+            let mut success = false;
+
+            while started.elapsed() < TIMEOUT {
+                if manager.lock().await.has(shard_id).await {
+                    success = true;
+                    break;
+                }
+
+                sleep(POLL_INTERVAL).await;
+            }
+
+            if !success {
+                warn!("Shard {} was not restarted within {:?}", shard_id, TIMEOUT);
+            }
+
+            restarted.insert(shard_id, success);
+        }
+
+        restarted
+    }
+
     /// Returns the [`ShardId`]s of the shards that have been instantiated and
     /// currently have a valid [`ShardRunner`].
     ///
@@ -347,6 +473,11 @@ impl Drop for ShardManager {
     }
 }
 
+/// The options to create a [`ShardManager`] with.
+///
+/// New options may be added in minor releases, so create these with
+/// [`Self::new`] and set the options differing from the defaults afterwards.
+#[non_exhaustive]
 pub struct ShardManagerOptions<'a> {
     pub data: &'a Arc<RwLock<TypeMap>>,
     pub event_handler: &'a Option<Arc<dyn EventHandler>>,
@@ -361,4 +492,251 @@ pub struct ShardManagerOptions<'a> {
     pub ws_url: &'a Arc<Mutex<String>>,
     pub cache_and_http: &'a Arc<CacheAndHttp>,
     pub intents: GatewayIntents,
+    pub event_type_filter: &'a Option<Arc<EventTypeFilter>>,
+    pub shard_data_init: &'a Option<Arc<dyn Fn(ShardId) -> TypeMap + Send + Sync>>,
+    pub max_payload_size: usize,
+    pub stall_watchdog: Option<StallWatchdog>,
+    pub task_tracker: &'a TaskTracker,
+}
+
+impl<'a> ShardManagerOptions<'a> {
+    /// Creates the options with the given required values.
+    ///
+    /// No shards are started, no handlers are set, and the
+    /// [non-privileged intents] are used. Every other option has the same
+    /// default as in [`ClientBuilder`].
+    ///
+    /// [non-privileged intents]: GatewayIntents::non_privileged
+    /// [`ClientBuilder`]: crate::client::ClientBuilder
+    pub fn new(
+        data: &'a Arc<RwLock<TypeMap>>,
+        #[cfg(feature = "framework")] framework: &'a Arc<Box<dyn Framework + Send + Sync>>,
+        ws_url: &'a Arc<Mutex<String>>,
+        cache_and_http: &'a Arc<CacheAndHttp>,
+        task_tracker: &'a TaskTracker,
+    ) -> Self {
+        Self {
+            data,
+            event_handler: &None,
+            raw_event_handler: &None,
+            #[cfg(feature = "framework")]
+            framework,
+            shard_index: 0,
+            shard_init: 0,
+            shard_total: 0,
+            #[cfg(feature = "voice")]
+            voice_manager: &None,
+            ws_url,
+            cache_and_http,
+            intents: GatewayIntents::non_privileged(),
+            event_type_filter: &None,
+            shard_data_init: &None,
+            max_payload_size: DEFAULT_MAX_PAYLOAD_SIZE,
+            stall_watchdog: None,
+            task_tracker,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex as StdMutex, PoisonError};
+
+    use serde_json::json;
+    use tokio::sync::{Mutex, RwLock};
+    use typemap_rev::{TypeMap, TypeMapKey};
+
+    use super::{ShardManager, ShardManagerOptions};
+    use crate::client::bridge::gateway::mock_gateway::MockGateway;
+    use crate::client::bridge::gateway::{GatewayIntents, ShardId};
+    use crate::client::{Context, EventHandler, TaskTracker};
+    #[cfg(feature = "framework")]
+    use crate::framework::Framework;
+    use crate::http::Http;
+    #[cfg(feature = "framework")]
+    use crate::model::channel::Message;
+    use crate::model::event::ResumedEvent;
+    use crate::model::gateway::Ready;
+    use crate::CacheAndHttp;
+
+    #[cfg(feature = "framework")]
+    struct NoFramework;
+
+    #[cfg(feature = "framework")]
+    #[async_trait::async_trait]
+    impl Framework for NoFramework {
+        async fn dispatch(&self, _: Context, _: Message) {}
+    }
+
+    /// Starts a manager connecting `shards` shards to the `gateway`, and its
+    /// monitor.
+    async fn start(gateway: &MockGateway, shards: u64) -> Arc<Mutex<ShardManager>> {
+        start_with(gateway, shards, None, None).await
+    }
+
+    /// Starts a manager like [`start`], with an event handler and a shard
+    /// data initializer.
+    async fn start_with(
+        gateway: &MockGateway,
+        shards: u64,
+        event_handler: Option<Arc<dyn EventHandler>>,
+        shard_data_init: Option<Arc<dyn Fn(ShardId) -> TypeMap + Send + Sync>>,
+    ) -> Arc<Mutex<ShardManager>> {
+        let data = Arc::new(RwLock::new(TypeMap::new()));
+        #[cfg(feature = "framework")]
+        let framework = Arc::new(Box::new(NoFramework) as Box<dyn Framework + Send + Sync>);
+        let ws_url = Arc::new(Mutex::new(gateway.url()));
+        let cache_and_http = Arc::new(CacheAndHttp {
+            http: Arc::new(Http::new_with_token("old")),
+            ..CacheAndHttp::default()
+        });
+        let task_tracker = TaskTracker::default();
+
+        let mut options = ShardManagerOptions::new(
+            &data,
+            #[cfg(feature = "framework")]
+            &framework,
+            &ws_url,
+            &cache_and_http,
+            &task_tracker,
+        );
+        options.shard_init = shards;
+        options.shard_total = shards;
+        options.event_handler = &event_handler;
+        options.shard_data_init = &shard_data_init;
+
+        let (manager, mut monitor) = ShardManager::new(options).await;
+        tokio::spawn(async move { monitor.run().await });
+        manager.lock().await.initialize().ok();
+
+        manager
+    }
+
+    fn all_restarted(shards: u64) -> HashMap<ShardId, bool> {
+        (0..shards).map(|shard| (ShardId(shard), true)).collect()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_update_intents() {
+        let gateway = MockGateway::start().await;
+        let manager = start(&gateway, 2).await;
+        gateway.wait_identified(2).await;
+
+        let intents = GatewayIntents::GUILDS | GatewayIntents::GUILD_MESSAGES;
+        let restarted = ShardManager::update_intents(&manager, intents).await;
+        assert_eq!(restarted, all_restarted(2));
+
+        // Each shard is closed with a new session before the next one is.
+        gateway.wait_identified(4).await;
+        assert_eq!(gateway.identified(), vec![0, 1, 0, 1]);
+        assert_eq!(gateway.closed(), vec![(0, Some(1000)), (1, Some(1000))]);
+        assert!(gateway.resumed().is_empty());
+
+        let identifies = gateway.identifies();
+        assert_ne!(identifies[1]["intents"], intents.bits());
+        assert_eq!(identifies[2]["intents"], intents.bits());
+        assert_eq!(identifies[3]["intents"], intents.bits());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_update_token() {
+        let gateway = MockGateway::start().await;
+        let manager = start(&gateway, 2).await;
+        gateway.wait_identified(2).await;
+
+        let restarted = ShardManager::update_token(&manager, "new").await;
+        assert_eq!(restarted, all_restarted(2));
+
+        gateway.wait_identified(4).await;
+        assert_eq!(gateway.identified(), vec![0, 1, 0, 1]);
+        assert_eq!(gateway.closed(), vec![(0, Some(1000)), (1, Some(1000))]);
+
+        let identifies = gateway.identifies();
+        let tokens = identifies.iter().map(|data| data["token"].clone()).collect::<Vec<_>>();
+        assert_eq!(tokens, vec!["Bot old", "Bot old", "Bot new", "Bot new"]);
+    }
+
+    /// The number of the call to the shard data initializer.
+    struct InitCall;
+
+    impl TypeMapKey for InitCall {
+        type Value = usize;
+    }
+
+    /// The shard and the initializer call of its shard data, as seen by
+    /// the event handler.
+    type Records = StdMutex<Vec<(u64, usize)>>;
+
+    /// Records the shard and initializer call of the shard data seen by each
+    /// ready and resume.
+    #[derive(Default)]
+    struct ShardDataHandler {
+        readies: Records,
+        resumes: Records,
+    }
+
+    async fn init_call(ctx: &Context) -> usize {
+        ctx.shard_data.read().await.get::<InitCall>().copied().unwrap_or_default()
+    }
+
+    #[async_trait::async_trait]
+    impl EventHandler for ShardDataHandler {
+        async fn ready(&self, ctx: Context, _: Ready) {
+            let call = init_call(&ctx).await;
+            self.readies.lock().unwrap_or_else(PoisonError::into_inner).push((ctx.shard_id, call));
+        }
+
+        async fn resume(&self, ctx: Context, _: ResumedEvent) {
+            let call = init_call(&ctx).await;
+            self.resumes.lock().unwrap_or_else(PoisonError::into_inner).push((ctx.shard_id, call));
+        }
+    }
+
+    /// Waits until `records` holds `count` of them, returning them sorted.
+    async fn wait_records(records: &Records, count: usize) -> Vec<(u64, usize)> {
+        loop {
+            let mut records = records.lock().unwrap_or_else(PoisonError::into_inner).clone();
+
+            if records.len() >= count {
+                records.sort_unstable();
+
+                return records;
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_shard_data_kept_across_reconnects() {
+        let gateway = MockGateway::start().await;
+        let handler = Arc::new(ShardDataHandler::default());
+        let event_handler = Some(Arc::clone(&handler) as Arc<dyn EventHandler>);
+
+        let calls = Arc::new(StdMutex::new(Vec::new()));
+        let init_calls = Arc::clone(&calls);
+        let shard_data_init: Option<Arc<dyn Fn(ShardId) -> TypeMap + Send + Sync>> =
+            Some(Arc::new(move |shard_id: ShardId| {
+                let mut calls = init_calls.lock().unwrap_or_else(PoisonError::into_inner);
+                calls.push(shard_id.0);
+
+                let mut data = TypeMap::new();
+                data.insert::<InitCall>(calls.len());
+                data
+            }));
+
+        let _manager = start_with(&gateway, 2, event_handler, shard_data_init).await;
+
+        let readies = wait_records(&handler.readies, 2).await;
+        assert_eq!(readies, vec![(0, 1), (1, 2)]);
+
+        // Shard 0 is asked to reconnect, and resumes its session.
+        gateway.send(0, json!({"op": 7, "d": null})).await;
+
+        let resumes = wait_records(&handler.resumes, 1).await;
+        assert_eq!(resumes, vec![(0, 1)]);
+        assert_eq!(gateway.resumed(), vec![0]);
+        assert_eq!(*calls.lock().unwrap_or_else(PoisonError::into_inner), vec![0, 1]);
+    }
 }
@@ -59,6 +59,10 @@ impl ShardManagerMonitor {
     pub async fn run(&mut self) -> Result<()> {
         debug!("Starting shard manager worker");
 
+        // Updating the runners does not wait for the manager, which may be
+        // locked while it waits for this monitor to confirm a shutdown.
+        let runners = Arc::clone(&self.manager.lock().await.runners);
+
         while let Some(value) = self.rx.next().await {
             match value {
                 ShardManagerMessage::Restart(shard_id) => {
@@ -72,8 +76,7 @@ impl ShardManagerMonitor {
                     latency,
                     stage,
                 } => {
-                    let manager = self.manager.lock().await;
-                    let mut runners = manager.runners.lock().await;
+                    let mut runners = runners.lock().await;
 
                     if let Some(runner) = runners.get_mut(&id) {
                         runner.latency = latency;
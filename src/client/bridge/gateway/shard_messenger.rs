@@ -1,11 +1,21 @@
 use async_tungstenite::tungstenite::Message;
 use futures::channel::mpsc::{TrySendError, UnboundedSender as Sender};
 
-use super::{ChunkGuildFilter, ShardClientMessage, ShardRunnerMessage};
+use super::shard_runner::is_dispatching;
+use super::{Acknowledgement, ChunkGuildFilter, ShardClientMessage, ShardRunnerMessage};
 #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
 use crate::collector::{ComponentInteractionFilter, ModalInteractionFilter};
 #[cfg(feature = "collector")]
-use crate::collector::{EventFilter, MessageFilter, ReactionFilter};
+use crate::collector::{
+    EventFilter,
+    FilterId,
+    GuildMemberFilter,
+    MessageEventFilter,
+    MessageFilter,
+    ReactionFilter,
+    TypingFilter,
+    VoiceStateFilter,
+};
 use crate::gateway::InterMessage;
 use crate::model::prelude::*;
 
@@ -250,6 +260,41 @@ impl ShardMessenger {
         self.tx.unbounded_send(InterMessage::Client(Box::new(ShardClientMessage::Runner(msg))))
     }
 
+    /// Sends a message to the shard, waiting until the shard runner handled
+    /// it.
+    ///
+    /// The runner handles messages in the order they were sent, so once this
+    /// returns, all messages sent before were handled as well. This returns
+    /// early if the runner stops before handling the message.
+    ///
+    /// **Note**: If called from a [`RawEventHandler`], this does not wait, as
+    /// the runner waits for the handler to return before handling messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TrySendError`] if the shard's receiver was closed.
+    ///
+    /// [`RawEventHandler`]: crate::client::RawEventHandler
+    pub async fn send_to_shard_sync(
+        &self,
+        msg: ShardRunnerMessage,
+    ) -> Result<(), TrySendError<InterMessage>> {
+        self.send_to_shard(msg)?;
+
+        if is_dispatching() {
+            return Ok(());
+        }
+
+        let (ack, ack_rx) = Acknowledgement::new();
+        self.send_to_shard(ShardRunnerMessage::Acknowledge(ack))?;
+
+        // Fails if the runner dropped the message without handling it.
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = ack_rx.await;
+
+        Ok(())
+    }
+
     /// Sets a new filter for an event collector.
     #[inline]
     #[cfg(feature = "collector")]
@@ -258,6 +303,17 @@ impl ShardMessenger {
         let _ = self.send_to_shard(ShardRunnerMessage::SetEventFilter(collector));
     }
 
+    /// Sets a new filter for an event collector, waiting until the shard runner
+    /// installed it.
+    ///
+    /// Once this returns, every event the shard receives is checked by the
+    /// filter. Refer to [`Self::send_to_shard_sync`] for more information.
+    #[cfg(feature = "collector")]
+    pub async fn set_event_filter_sync(&self, collector: EventFilter) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard_sync(ShardRunnerMessage::SetEventFilter(collector)).await;
+    }
+
     /// Sets a new filter for a message collector.
     #[inline]
     #[cfg(feature = "collector")]
@@ -266,6 +322,17 @@ impl ShardMessenger {
         let _ = self.send_to_shard(ShardRunnerMessage::SetMessageFilter(collector));
     }
 
+    /// Sets a new filter for a message collector, waiting until the shard runner
+    /// installed it.
+    ///
+    /// Once this returns, every event the shard receives is checked by the
+    /// filter. Refer to [`Self::send_to_shard_sync`] for more information.
+    #[cfg(feature = "collector")]
+    pub async fn set_message_filter_sync(&self, collector: MessageFilter) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard_sync(ShardRunnerMessage::SetMessageFilter(collector)).await;
+    }
+
     /// Sets a new filter for a reaction collector.
     #[cfg(feature = "collector")]
     pub fn set_reaction_filter(&self, collector: ReactionFilter) {
@@ -273,6 +340,89 @@ impl ShardMessenger {
         let _ = self.send_to_shard(ShardRunnerMessage::SetReactionFilter(collector));
     }
 
+    /// Sets a new filter for a reaction collector, waiting until the shard runner
+    /// installed it.
+    ///
+    /// Once this returns, every event the shard receives is checked by the
+    /// filter. Refer to [`Self::send_to_shard_sync`] for more information.
+    #[cfg(feature = "collector")]
+    pub async fn set_reaction_filter_sync(&self, collector: ReactionFilter) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard_sync(ShardRunnerMessage::SetReactionFilter(collector)).await;
+    }
+
+    /// Sets a new filter for a guild member collector.
+    #[cfg(feature = "collector")]
+    pub fn set_guild_member_filter(&self, collector: GuildMemberFilter) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::SetGuildMemberFilter(collector));
+    }
+
+    /// Sets a new filter for a guild member collector, waiting until the shard runner
+    /// installed it.
+    ///
+    /// Once this returns, every event the shard receives is checked by the
+    /// filter. Refer to [`Self::send_to_shard_sync`] for more information.
+    #[cfg(feature = "collector")]
+    pub async fn set_guild_member_filter_sync(&self, collector: GuildMemberFilter) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard_sync(ShardRunnerMessage::SetGuildMemberFilter(collector)).await;
+    }
+
+    /// Sets a new filter for a typing collector.
+    #[cfg(feature = "collector")]
+    pub fn set_typing_filter(&self, collector: TypingFilter) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::SetTypingFilter(collector));
+    }
+
+    /// Sets a new filter for a typing collector, waiting until the shard runner
+    /// installed it.
+    ///
+    /// Once this returns, every event the shard receives is checked by the
+    /// filter. Refer to [`Self::send_to_shard_sync`] for more information.
+    #[cfg(feature = "collector")]
+    pub async fn set_typing_filter_sync(&self, collector: TypingFilter) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard_sync(ShardRunnerMessage::SetTypingFilter(collector)).await;
+    }
+
+    /// Sets a new filter for a voice state collector.
+    #[cfg(feature = "collector")]
+    pub fn set_voice_state_filter(&self, collector: VoiceStateFilter) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::SetVoiceStateFilter(collector));
+    }
+
+    /// Sets a new filter for a voice state collector, waiting until the shard
+    /// runner installed it.
+    ///
+    /// Once this returns, every event the shard receives is checked by the
+    /// filter. Refer to [`Self::send_to_shard_sync`] for more information.
+    #[cfg(feature = "collector")]
+    pub async fn set_voice_state_filter_sync(&self, collector: VoiceStateFilter) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard_sync(ShardRunnerMessage::SetVoiceStateFilter(collector)).await;
+    }
+
+    /// Sets a new filter for a message event collector.
+    #[cfg(feature = "collector")]
+    pub fn set_message_event_filter(&self, collector: MessageEventFilter) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::SetMessageEventFilter(collector));
+    }
+
+    /// Sets a new filter for a message event collector, waiting until the
+    /// shard runner installed it.
+    ///
+    /// Once this returns, every event the shard receives is checked by the
+    /// filter. Refer to [`Self::send_to_shard_sync`] for more information.
+    #[cfg(feature = "collector")]
+    pub async fn set_message_event_filter_sync(&self, collector: MessageEventFilter) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard_sync(ShardRunnerMessage::SetMessageEventFilter(collector)).await;
+    }
+
     /// Sets a new filter for a component interaction collector.
     #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
     pub fn set_component_interaction_filter(&self, collector: ComponentInteractionFilter) {
@@ -280,12 +430,103 @@ impl ShardMessenger {
         let _ = self.send_to_shard(ShardRunnerMessage::SetComponentInteractionFilter(collector));
     }
 
+    /// Sets a new filter for a component interaction collector, waiting until the shard runner
+    /// installed it.
+    ///
+    /// Once this returns, every event the shard receives is checked by the
+    /// filter. Refer to [`Self::send_to_shard_sync`] for more information.
+    #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
+    pub async fn set_component_interaction_filter_sync(
+        &self,
+        collector: ComponentInteractionFilter,
+    ) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self
+            .send_to_shard_sync(ShardRunnerMessage::SetComponentInteractionFilter(collector))
+            .await;
+    }
+
     /// Sets a new filter for a modal interaction collector.
     #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
     pub fn set_modal_interaction_filter(&self, collector: ModalInteractionFilter) {
         #[allow(clippy::let_underscore_must_use)]
         let _ = self.send_to_shard(ShardRunnerMessage::SetModalInteractionFilter(collector));
     }
+
+    /// Sets a new filter for a modal interaction collector, waiting until the shard runner
+    /// installed it.
+    ///
+    /// Once this returns, every event the shard receives is checked by the
+    /// filter. Refer to [`Self::send_to_shard_sync`] for more information.
+    #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
+    pub async fn set_modal_interaction_filter_sync(&self, collector: ModalInteractionFilter) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ =
+            self.send_to_shard_sync(ShardRunnerMessage::SetModalInteractionFilter(collector)).await;
+    }
+
+    /// Removes the filter of an event collector, such as once the collector is dropped.
+    #[cfg(feature = "collector")]
+    pub fn remove_event_filter(&self, id: FilterId) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::RemoveEventFilter(id));
+    }
+
+    /// Removes the filter of a message collector, such as once the collector is dropped.
+    #[cfg(feature = "collector")]
+    pub fn remove_message_filter(&self, id: FilterId) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::RemoveMessageFilter(id));
+    }
+
+    /// Removes the filter of a reaction collector, such as once the collector is dropped.
+    #[cfg(feature = "collector")]
+    pub fn remove_reaction_filter(&self, id: FilterId) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::RemoveReactionFilter(id));
+    }
+
+    /// Removes the filter of a guild member collector, such as once the collector is dropped.
+    #[cfg(feature = "collector")]
+    pub fn remove_guild_member_filter(&self, id: FilterId) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::RemoveGuildMemberFilter(id));
+    }
+
+    /// Removes the filter of a typing collector, such as once the collector is dropped.
+    #[cfg(feature = "collector")]
+    pub fn remove_typing_filter(&self, id: FilterId) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::RemoveTypingFilter(id));
+    }
+
+    /// Removes the filter of a voice state collector, such as once the collector is dropped.
+    #[cfg(feature = "collector")]
+    pub fn remove_voice_state_filter(&self, id: FilterId) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::RemoveVoiceStateFilter(id));
+    }
+
+    /// Removes the filter of a message event collector, such as once the collector is dropped.
+    #[cfg(feature = "collector")]
+    pub fn remove_message_event_filter(&self, id: FilterId) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::RemoveMessageEventFilter(id));
+    }
+
+    /// Removes the filter of a component interaction collector, such as once the collector is dropped.
+    #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
+    pub fn remove_component_interaction_filter(&self, id: FilterId) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::RemoveComponentInteractionFilter(id));
+    }
+
+    /// Removes the filter of a modal interaction collector, such as once the collector is dropped.
+    #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
+    pub fn remove_modal_interaction_filter(&self, id: FilterId) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.send_to_shard(ShardRunnerMessage::RemoveModalInteractionFilter(id));
+    }
 }
 
 impl AsRef<ShardMessenger> for ShardMessenger {
@@ -17,6 +17,7 @@ use tracing::{debug, info, instrument, warn};
 use typemap_rev::TypeMap;
 
 use super::{
+    EventTypeFilter,
     GatewayIntents,
     ShardClientMessage,
     ShardId,
@@ -26,10 +27,11 @@ use super::{
     ShardRunner,
     ShardRunnerInfo,
     ShardRunnerOptions,
+    StallWatchdog,
 };
 #[cfg(feature = "voice")]
 use crate::client::bridge::voice::VoiceGatewayManager;
-use crate::client::{EventHandler, RawEventHandler};
+use crate::client::{EventHandler, RawEventHandler, TaskTracker};
 #[cfg(feature = "framework")]
 use crate::framework::Framework;
 use crate::gateway::ConnectionStage;
@@ -89,6 +91,17 @@ pub struct ShardQueuer {
     pub ws_url: Arc<Mutex<String>>,
     pub cache_and_http: Arc<CacheAndHttp>,
     pub intents: GatewayIntents,
+    /// The filter deciding which events the shard runners skip, if any.
+    pub event_type_filter: Option<Arc<EventTypeFilter>>,
+    /// The callback creating the data of each shard, if any.
+    pub shard_data_init: Option<Arc<dyn Fn(ShardId) -> TypeMap + Send + Sync>>,
+    /// The maximum size of a payload the shards receive, in bytes.
+    pub max_payload_size: usize,
+    /// The watchdog reconnecting shards which stopped receiving frames, if
+    /// any.
+    pub stall_watchdog: Option<StallWatchdog>,
+    /// The tracker of the tasks dispatching events to the handlers.
+    pub task_tracker: TaskTracker,
 }
 
 impl ShardQueuer {
@@ -128,6 +141,10 @@ impl ShardQueuer {
                     debug!("[Shard Queuer] Received to shutdown shard {} with {}.", shard.0, code);
                     self.shutdown(shard, code).await;
                 },
+                Ok(Some(ShardQueuerMessage::SetIntents(intents))) => {
+                    debug!("[Shard Queuer] Received to set intents to {:?}.", intents);
+                    self.intents = intents;
+                },
                 Ok(Some(ShardQueuerMessage::Start(id, total))) => {
                     debug!("[Shard Queuer] Received to start shard {} of {}.", id.0, total.0);
                     self.checked_start(id.0, total.0).await;
@@ -182,16 +199,24 @@ impl ShardQueuer {
     async fn start(&mut self, shard_id: u64, shard_total: u64) -> Result<()> {
         let shard_info = [shard_id, shard_total];
 
-        let shard = Shard::new(
+        let shard = Shard::new_with_api_version(
             Arc::clone(&self.ws_url),
-            &self.cache_and_http.http.token,
+            &self.cache_and_http.http.token(),
             shard_info,
             self.intents,
+            self.max_payload_size,
+            self.cache_and_http.http.api_version,
         )
         .await?;
 
+        let shard_data = match &self.shard_data_init {
+            Some(init) => init(ShardId(shard_id)),
+            None => TypeMap::new(),
+        };
+
         let mut runner = ShardRunner::new(ShardRunnerOptions {
             data: Arc::clone(&self.data),
+            shard_data: Arc::new(RwLock::new(shard_data)),
             event_handler: self.event_handler.as_ref().map(|eh| Arc::clone(eh)),
             raw_event_handler: self.raw_event_handler.as_ref().map(|rh| Arc::clone(rh)),
             #[cfg(feature = "framework")]
@@ -201,6 +226,9 @@ impl ShardQueuer {
             voice_manager: self.voice_manager.clone(),
             shard,
             cache_and_http: Arc::clone(&self.cache_and_http),
+            event_type_filter: self.event_type_filter.clone(),
+            stall_watchdog: self.stall_watchdog,
+            task_tracker: self.task_tracker.clone(),
         });
 
         let runner_info = ShardRunnerInfo {
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, sync::Arc};
+use std::{borrow::Cow, future::Future, sync::Arc, time::Duration as StdDuration};
 
 use async_tungstenite::tungstenite::{
     self,
@@ -9,32 +9,86 @@ use futures::channel::mpsc::{self, UnboundedReceiver as Receiver, UnboundedSende
 use futures::{SinkExt, StreamExt};
 use serde::Deserialize;
 use tokio::sync::RwLock;
+#[cfg(feature = "collector")]
+use tokio::time::Instant;
 use tracing::{debug, error, info, instrument, trace, warn};
 use typemap_rev::TypeMap;
 
-use super::event::{ClientEvent, ShardStageUpdateEvent};
-use super::{ShardClientMessage, ShardId, ShardManagerMessage, ShardRunnerMessage};
+use super::event::{ClientEvent, ShardLifecycleEvent, ShardStageUpdateEvent};
+use super::stall_watchdog::StallDetector;
+use super::{
+    EventTypeFilter,
+    ShardClientMessage,
+    ShardId,
+    ShardManagerMessage,
+    ShardRunnerMessage,
+    StallWatchdog,
+};
 #[cfg(feature = "voice")]
 use crate::client::bridge::voice::VoiceGatewayManager;
-use crate::client::dispatch::{dispatch, DispatchEvent};
-use crate::client::{EventHandler, RawEventHandler};
+use crate::client::dispatch::{dispatch, dispatch_raw, DispatchEvent};
+use crate::client::{EventHandler, RawEventHandler, TaskTracker};
 #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
 use crate::collector::{ComponentInteractionFilter, ModalInteractionFilter};
 #[cfg(feature = "collector")]
-use crate::collector::{EventFilter, LazyArc, LazyReactionAction, MessageFilter, ReactionFilter};
+use crate::collector::{
+    EventFilter,
+    GuildMemberFilter,
+    LazyArc,
+    LazyGuildMemberAction,
+    LazyMessageAction,
+    LazyReactionAction,
+    MessageEventFilter,
+    MessageFilter,
+    ReactionFilter,
+    TypingFilter,
+    VoiceStateAction,
+    VoiceStateFilter,
+};
+use crate::constants::OpCode;
 #[cfg(feature = "framework")]
 use crate::framework::Framework;
 use crate::gateway::{GatewayError, InterMessage, ReconnectType, Shard, ShardAction};
+use crate::http::utils::json_snippet;
 use crate::internal::prelude::*;
 use crate::internal::ws_impl::{ReceiverExt, SenderExt};
-use crate::model::event::{Event, GatewayEvent};
+#[cfg(feature = "collector")]
+use crate::model::channel::Message;
+#[cfg(feature = "collector")]
+use crate::model::event::VoiceStateUpdateEvent;
+use crate::model::event::{Event, EventType, GatewayEvent};
+#[cfg(feature = "collector")]
+use crate::model::id::{ChannelId, MessageId};
 #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
 use crate::model::interactions::Interaction;
+#[cfg(feature = "collector")]
+use crate::model::voice::VoiceState;
 use crate::CacheAndHttp;
 
+tokio::task_local! {
+    /// Set while a runner awaits the dispatch of an event, such as to a raw
+    /// event handler. Meanwhile, it cannot handle the messages sent to it.
+    static DISPATCHING: ();
+}
+
+/// Whether the current task is a shard runner dispatching an event, which
+/// would deadlock waiting for the runner to acknowledge a message.
+pub(crate) fn is_dispatching() -> bool {
+    DISPATCHING.try_with(|_| ()).is_ok()
+}
+
+/// Awaits the dispatch of an event, during which the current task cannot
+/// handle the messages sent to it.
+///
+/// Refer to [`is_dispatching`] for why this matters.
+pub(crate) async fn dispatching<F: Future>(dispatch: F) -> F::Output {
+    DISPATCHING.scope((), dispatch).await
+}
+
 /// A runner for managing a [`Shard`] and its respective WebSocket client.
 pub struct ShardRunner {
     data: Arc<RwLock<TypeMap>>,
+    shard_data: Arc<RwLock<TypeMap>>,
     event_handler: Option<Arc<dyn EventHandler>>,
     raw_event_handler: Option<Arc<dyn RawEventHandler>>,
     #[cfg(feature = "framework")]
@@ -48,16 +102,11 @@ pub struct ShardRunner {
     #[cfg(feature = "voice")]
     voice_manager: Option<Arc<dyn VoiceGatewayManager + Send + Sync + 'static>>,
     cache_and_http: Arc<CacheAndHttp>,
+    event_type_filter: Option<Arc<EventTypeFilter>>,
+    stall_detector: Option<StallDetector>,
+    task_tracker: TaskTracker,
     #[cfg(feature = "collector")]
-    event_filters: Vec<EventFilter>,
-    #[cfg(feature = "collector")]
-    message_filters: Vec<MessageFilter>,
-    #[cfg(feature = "collector")]
-    reaction_filters: Vec<ReactionFilter>,
-    #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
-    component_interaction_filters: Vec<ComponentInteractionFilter>,
-    #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
-    modal_interaction_filters: Vec<ModalInteractionFilter>,
+    collector_filters: CollectorFilters,
 }
 
 impl ShardRunner {
@@ -69,6 +118,7 @@ impl ShardRunner {
             runner_rx: rx,
             runner_tx: tx,
             data: opt.data,
+            shard_data: opt.shard_data,
             event_handler: opt.event_handler,
             raw_event_handler: opt.raw_event_handler,
             #[cfg(feature = "framework")]
@@ -78,16 +128,11 @@ impl ShardRunner {
             #[cfg(feature = "voice")]
             voice_manager: opt.voice_manager,
             cache_and_http: opt.cache_and_http,
+            event_type_filter: opt.event_type_filter,
+            stall_detector: opt.stall_watchdog.map(StallDetector::new),
+            task_tracker: opt.task_tracker,
             #[cfg(feature = "collector")]
-            event_filters: Vec::new(),
-            #[cfg(feature = "collector")]
-            message_filters: Vec::new(),
-            #[cfg(feature = "collector")]
-            reaction_filters: Vec::new(),
-            #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
-            component_interaction_filters: vec![],
-            #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
-            modal_interaction_filters: vec![],
+            collector_filters: CollectorFilters::new(),
         }
     }
 
@@ -124,6 +169,15 @@ impl ShardRunner {
                 return Ok(());
             }
 
+            #[cfg(feature = "collector")]
+            self.collector_filters.purge_filters();
+
+            if let Some(silent_for) = self.stall_detector.as_mut().and_then(StallDetector::check) {
+                if !self.handle_stall(silent_for).await {
+                    return self.request_restart().await;
+                }
+            }
+
             // check heartbeat
             if !self.shard.check_heartbeat().await {
                 warn!("[ShardRunner {:?}] Error heartbeating", self.shard.shard_info(),);
@@ -179,12 +233,17 @@ impl ShardRunner {
             }
 
             if let Some(event) = event {
-                #[cfg(feature = "collector")]
-                {
-                    self.handle_filters(&event);
-                }
+                if self.is_skipped(&event.event_type()) {
+                    // Only reached if there is a raw event handler, see `recv_event`.
+                    self.dispatch_raw(event).await;
+                } else {
+                    #[cfg(feature = "collector")]
+                    {
+                        self.collector_filters.handle_filters(&event, &self.cache_and_http).await;
+                    }
 
-                self.dispatch(DispatchEvent::Model(event)).await;
+                    self.dispatch(DispatchEvent::Model(event)).await;
+                }
             }
 
             if !successful && !self.shard.stage().is_connecting() {
@@ -194,70 +253,76 @@ impl ShardRunner {
         }
     }
 
-    /// Lets filters check the `event` to send them to collectors if the `event`
-    /// is accepted by them.
+    /// Whether events of the given type are filtered out by the configured
+    /// [`EventTypeFilter`], taking collectors waiting for them into account.
+    fn is_skipped(&self, kind: &EventType) -> bool {
+        match &self.event_type_filter {
+            Some(filter) => filter.is_filtered(kind) && !self.is_collected(kind),
+            None => false,
+        }
+    }
+
+    /// Whether a running collector waits for events of the given type.
     #[cfg(feature = "collector")]
-    fn handle_filters(&mut self, event: &Event) {
-        /// Unlike [`Vec`]'s `retain`, allows mutable references in `f`.
-        fn retain<T, F>(vec: &mut Vec<T>, mut f: F)
-        where
-            F: FnMut(&mut T) -> bool,
-        {
-            let len = vec.len();
-            let mut del = 0;
-            {
-                let v = &mut **vec;
+    fn is_collected(&self, kind: &EventType) -> bool {
+        self.collector_filters.is_collected(kind)
+    }
 
-                for i in 0..len {
-                    if !f(&mut v[i]) {
-                        del += 1;
-                    } else if del > 0 {
-                        v.swap(i - del, i);
-                    }
-                }
-            }
+    #[cfg(not(feature = "collector"))]
+    fn is_collected(&self, _kind: &EventType) -> bool {
+        false
+    }
 
-            if del > 0 {
-                vec.truncate(len - del);
-            }
+    /// Whether the raw gateway payload is a dispatch that should be skipped
+    /// without being deserialized.
+    ///
+    /// Skipped dispatches are still deserialized if there is a raw event
+    /// handler, as they are passed on to it.
+    fn is_skipped_dispatch(&self, value: &Value) -> bool {
+        if self.event_type_filter.is_none() || self.raw_event_handler.is_some() {
+            return false;
         }
 
-        match &event {
-            Event::MessageCreate(ref msg_event) => {
-                let mut msg = LazyArc::new(&msg_event.message);
-                retain(&mut self.message_filters, |f| f.send_message(&mut msg));
-            },
-            Event::ReactionAdd(ref reaction_event) => {
-                let mut reaction = LazyReactionAction::new(&reaction_event.reaction, true);
-                retain(&mut self.reaction_filters, |f| f.send_reaction(&mut reaction));
-            },
-            Event::ReactionRemove(ref reaction_event) => {
-                let mut reaction = LazyReactionAction::new(&reaction_event.reaction, false);
-                retain(&mut self.reaction_filters, |f| f.send_reaction(&mut reaction));
-            },
-            #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
-            Event::InteractionCreate(ref interaction_event) => {
-                match &interaction_event.interaction {
-                    Interaction::MessageComponent(interaction) => {
-                        let mut interaction = LazyArc::new(interaction);
-                        retain(&mut self.component_interaction_filters, |f| {
-                            f.send_interaction(&mut interaction)
-                        });
-                    },
-                    Interaction::ModalSubmit(interaction) => {
-                        let mut interaction = LazyArc::new(interaction);
-                        retain(&mut self.modal_interaction_filters, |f| {
-                            f.send_interaction(&mut interaction)
-                        });
-                    },
-                    _ => (),
-                }
-            },
-            _ => {},
+        let map = match value.as_object() {
+            Some(map) => map,
+            None => return false,
+        };
+
+        match map.get("op").map(OpCode::deserialize) {
+            Some(Ok(OpCode::Event)) => {},
+            _ => return false,
         }
 
-        let mut event = LazyArc::new(event);
-        retain(&mut self.event_filters, |f| f.send_event(&mut event));
+        match map.get("t").map(EventType::deserialize) {
+            Some(Ok(kind)) => self.is_skipped(&kind),
+            _ => false,
+        }
+    }
+
+    /// Deserializes a received payload into a [`GatewayEvent`].
+    ///
+    /// If this fails, the failure is logged with the event's type and returned
+    /// as a [`GatewayError::DeserializationFailed`], which keeps the start of
+    /// the payload unless [`Http::body_snippet_limit`] is `None`.
+    ///
+    /// [`Http::body_snippet_limit`]: crate::http::Http::body_snippet_limit
+    fn deserialize_event(&self, value: Value) -> Result<GatewayEvent> {
+        let limit = self.cache_and_http.http.body_snippet_limit;
+        let event_type = value.get("t").and_then(Value::as_str).map(ToString::to_string);
+        // The deserializer consumes the payload, so the snippet is taken
+        // beforehand, serializing no more of the payload than it keeps.
+        let payload_snippet = limit.map(|limit| json_snippet(&value, limit));
+
+        GatewayEvent::deserialize(value).map_err(|why| {
+            let error = GatewayError::DeserializationFailed {
+                event_type,
+                error: why.to_string(),
+                payload_snippet,
+            };
+            warn!("[ShardRunner {:?}] {}", self.shard.shard_info(), error);
+
+            Error::Gateway(error)
+        })
     }
 
     /// Clones the internal copy of the Sender to the shard runner.
@@ -343,18 +408,39 @@ impl ShardRunner {
     #[inline]
     #[instrument(skip(self, event))]
     async fn dispatch(&self, event: DispatchEvent) {
-        dispatch(
+        let dispatch = dispatch(
             event,
             #[cfg(feature = "framework")]
             &self.framework,
             &self.data,
+            &self.shard_data,
             &self.event_handler,
             &self.raw_event_handler,
             &self.runner_tx,
             self.shard.shard_info()[0],
             Arc::clone(&self.cache_and_http),
-        )
-        .await;
+            &self.task_tracker,
+        );
+
+        dispatching(dispatch).await;
+    }
+
+    #[inline]
+    #[instrument(skip(self, event))]
+    async fn dispatch_raw(&self, event: Event) {
+        if let Some(raw_event_handler) = &self.raw_event_handler {
+            let dispatch = dispatch_raw(
+                event,
+                &self.data,
+                &self.shard_data,
+                raw_event_handler,
+                &self.runner_tx,
+                self.shard.shard_info()[0],
+                &self.cache_and_http,
+            );
+
+            dispatching(dispatch).await;
+        }
     }
 
     // Handles a received value over the shard runner rx channel.
@@ -404,92 +490,81 @@ impl ShardRunner {
 
                     true
                 },
-                ShardClientMessage::Runner(ShardRunnerMessage::ChunkGuild {
-                    guild_id,
-                    limit,
-                    filter,
-                    nonce,
-                }) => {
-                    self.shard.chunk_guild(guild_id, limit, filter, nonce.as_deref()).await.is_ok()
-                },
-                ShardClientMessage::Runner(ShardRunnerMessage::Close(code, reason)) => {
-                    let reason = reason.unwrap_or_else(String::new);
-                    let close = CloseFrame {
-                        code: code.into(),
-                        reason: Cow::from(reason),
-                    };
-                    self.shard.client.close(Some(close)).await.is_ok()
-                },
-                ShardClientMessage::Runner(ShardRunnerMessage::Message(msg)) => {
-                    self.shard.client.send(msg).await.is_ok()
-                },
-                ShardClientMessage::Runner(ShardRunnerMessage::SetActivity(activity)) => {
-                    // To avoid a clone of `activity`, we do a little bit of
-                    // trickery here:
-                    //
-                    // First, we obtain a reference to the current presence of
-                    // the shard, and create a new presence tuple of the new
-                    // activity we received over the channel as well as the
-                    // online status that the shard already had.
-                    //
-                    // We then (attempt to) send the websocket message with the
-                    // status update, expressively returning:
-                    //
-                    // - whether the message successfully sent
-                    // - the original activity we received over the channel
-                    self.shard.set_activity(activity);
-
-                    self.shard.update_presence().await.is_ok()
-                },
-                ShardClientMessage::Runner(ShardRunnerMessage::SetPresence(status, activity)) => {
-                    self.shard.set_presence(status, activity);
-
-                    self.shard.update_presence().await.is_ok()
-                },
-                ShardClientMessage::Runner(ShardRunnerMessage::SetStatus(status)) => {
-                    self.shard.set_status(status);
-
-                    self.shard.update_presence().await.is_ok()
-                },
-                #[cfg(feature = "collector")]
-                ShardClientMessage::Runner(ShardRunnerMessage::SetEventFilter(collector)) => {
-                    self.event_filters.push(collector);
-
-                    true
-                },
-                #[cfg(feature = "collector")]
-                ShardClientMessage::Runner(ShardRunnerMessage::SetMessageFilter(collector)) => {
-                    self.message_filters.push(collector);
+                ShardClientMessage::Runner(message) => self.handle_runner_message(message).await,
+            },
+            InterMessage::Json(value) => {
+                // Value must be forwarded over the websocket
+                self.shard.client.send_json(&value).await.is_ok()
+            },
+        }
+    }
 
-                    true
-                },
-                #[cfg(feature = "collector")]
-                ShardClientMessage::Runner(ShardRunnerMessage::SetReactionFilter(collector)) => {
-                    self.reaction_filters.push(collector);
+    // Handles a message sent to the shard runner, such as by a
+    // `ShardMessenger`.
+    //
+    // Returns whether the message was handled successfully.
+    #[instrument(skip(self))]
+    async fn handle_runner_message(&mut self, message: ShardRunnerMessage) -> bool {
+        #[cfg(feature = "collector")]
+        let message = match self.collector_filters.handle_message(message) {
+            Some(message) => message,
+            None => return true,
+        };
 
-                    true
-                },
-                #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
-                ShardClientMessage::Runner(ShardRunnerMessage::SetComponentInteractionFilter(
-                    collector,
-                )) => {
-                    self.component_interaction_filters.push(collector);
+        match message {
+            ShardRunnerMessage::ChunkGuild {
+                guild_id,
+                limit,
+                filter,
+                nonce,
+            } => {
+                self.shard.chunk_guild(guild_id, limit, filter, nonce.as_deref()).await.is_ok()
+            },
+            ShardRunnerMessage::Close(code, reason) => {
+                let reason = reason.unwrap_or_else(String::new);
+                let close = CloseFrame {
+                    code: code.into(),
+                    reason: Cow::from(reason),
+                };
+                self.shard.client.close(Some(close)).await.is_ok()
+            },
+            ShardRunnerMessage::Message(msg) => self.shard.client.send(msg).await.is_ok(),
+            ShardRunnerMessage::SetActivity(activity) => {
+                // To avoid a clone of `activity`, we do a little bit of
+                // trickery here:
+                //
+                // First, we obtain a reference to the current presence of
+                // the shard, and create a new presence tuple of the new
+                // activity we received over the channel as well as the
+                // online status that the shard already had.
+                //
+                // We then (attempt to) send the websocket message with the
+                // status update, expressively returning:
+                //
+                // - whether the message successfully sent
+                // - the original activity we received over the channel
+                self.shard.set_activity(activity);
+
+                self.shard.update_presence().await.is_ok()
+            },
+            ShardRunnerMessage::SetPresence(status, activity) => {
+                self.shard.set_presence(status, activity);
 
-                    true
-                },
-                #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
-                ShardClientMessage::Runner(ShardRunnerMessage::SetModalInteractionFilter(
-                    collector,
-                )) => {
-                    self.modal_interaction_filters.push(collector);
+                self.shard.update_presence().await.is_ok()
+            },
+            ShardRunnerMessage::SetStatus(status) => {
+                self.shard.set_status(status);
 
-                    true
-                },
+                self.shard.update_presence().await.is_ok()
             },
-            InterMessage::Json(value) => {
-                // Value must be forwarded over the websocket
-                self.shard.client.send_json(&value).await.is_ok()
+            ShardRunnerMessage::Acknowledge(ack) => {
+                ack.send();
+
+                true
             },
+            // The messages adding or removing collector filters were handled above.
+            #[cfg(feature = "collector")]
+            _ => true,
         }
     }
 
@@ -560,8 +635,25 @@ impl ShardRunner {
     /// present event was successful.
     #[instrument(skip(self))]
     async fn recv_event(&mut self) -> Result<(Option<Event>, Option<ShardAction>, bool)> {
-        let gw_event = match self.shard.client.recv_json().await {
-            Ok(Some(value)) => GatewayEvent::deserialize(value).map(Some).map_err(From::from),
+        let max_payload_size = self.shard.max_payload_size();
+        let gw_event = match self.shard.client.recv_json(max_payload_size).await {
+            Ok(Some(value)) => {
+                if let Some(detector) = &mut self.stall_detector {
+                    detector.frame_received();
+                }
+
+                self.shard.handle_frame(&value);
+
+                if let Some(raw_event_handler) = &self.raw_event_handler {
+                    raw_event_handler.raw_payload(&value);
+                }
+
+                if self.is_skipped_dispatch(&value) {
+                    return Ok((None, None, true));
+                }
+
+                self.deserialize_event(value).map(Some)
+            },
             Ok(None) => Ok(None),
             Err(Error::Tungstenite(TungsteniteError::Io(_))) => {
                 debug!("Attempting to auto-reconnect");
@@ -601,8 +693,9 @@ impl ShardRunner {
                             .unbounded_send(ShardManagerMessage::ShardInvalidAuthentication)
                             .is_err()
                         {
-                            panic!(
-                                "Failed sending InvalidAuthentication error to the shard manager."
+                            warn!(
+                                "[ShardRunner {:?}] Failed sending InvalidAuthentication error to the shard manager",
+                                self.shard.shard_info(),
                             );
                         }
 
@@ -614,8 +707,9 @@ impl ShardRunner {
                             .unbounded_send(ShardManagerMessage::ShardInvalidGatewayIntents)
                             .is_err()
                         {
-                            panic!(
-                                "Failed sending InvalidGatewayIntents error to the shard manager."
+                            warn!(
+                                "[ShardRunner {:?}] Failed sending InvalidGatewayIntents error to the shard manager",
+                                self.shard.shard_info(),
                             );
                         }
 
@@ -627,7 +721,10 @@ impl ShardRunner {
                             .unbounded_send(ShardManagerMessage::ShardDisallowedGatewayIntents)
                             .is_err()
                         {
-                            panic!("Failed sending DisallowedGatewayIntents error to the shard manager.");
+                            warn!(
+                                "[ShardRunner {:?}] Failed sending DisallowedGatewayIntents error to the shard manager",
+                                self.shard.shard_info(),
+                            );
                         }
 
                         return Err(why);
@@ -656,6 +753,47 @@ impl ShardRunner {
         Ok((event, action, true))
     }
 
+    /// Announces that the shard stalled and resumes its session, if the
+    /// watchdog is configured to do so.
+    ///
+    /// Returns whether the shard resumed, otherwise it needs to be restarted.
+    #[instrument(skip(self))]
+    async fn handle_stall(&mut self, silent_for: StdDuration) -> bool {
+        warn!(
+            "[ShardRunner {:?}] Received no gateway frame for {:?}; reconnecting",
+            self.shard.shard_info(),
+            silent_for,
+        );
+
+        let event = ClientEvent::ShardLifecycle(ShardLifecycleEvent::Stalled {
+            silent_for,
+        });
+        self.dispatch(DispatchEvent::Client(event)).await;
+
+        let reconnect = match &self.stall_detector {
+            Some(detector) => detector.reconnect_type(),
+            None => return false,
+        };
+
+        if reconnect == ReconnectType::Reidentify
+            || self.shard.reconnection_type() == ReconnectType::Reidentify
+        {
+            return false;
+        }
+
+        if let Err(why) = self.shard.resume().await {
+            warn!(
+                "[ShardRunner {:?}] Resume after stall failed, reidentifying: {:?}",
+                self.shard.shard_info(),
+                why
+            );
+
+            return false;
+        }
+
+        true
+    }
+
     #[instrument(skip(self))]
     async fn request_restart(&mut self) -> Result<()> {
         self.update_manager();
@@ -690,6 +828,7 @@ impl ShardRunner {
 /// Options to be passed to [`ShardRunner::new`].
 pub struct ShardRunnerOptions {
     pub data: Arc<RwLock<TypeMap>>,
+    pub shard_data: Arc<RwLock<TypeMap>>,
     pub event_handler: Option<Arc<dyn EventHandler>>,
     pub raw_event_handler: Option<Arc<dyn RawEventHandler>>,
     #[cfg(feature = "framework")]
@@ -699,4 +838,315 @@ pub struct ShardRunnerOptions {
     #[cfg(feature = "voice")]
     pub voice_manager: Option<Arc<dyn VoiceGatewayManager + Send + Sync>>,
     pub cache_and_http: Arc<CacheAndHttp>,
+    pub event_type_filter: Option<Arc<EventTypeFilter>>,
+    pub stall_watchdog: Option<StallWatchdog>,
+    pub task_tracker: TaskTracker,
+}
+
+/// How often the filters of collectors that were dropped without removing
+/// them, such as while their builder was awaited, are removed.
+#[cfg(feature = "collector")]
+const FILTER_PURGE_INTERVAL: StdDuration = StdDuration::from_secs(60);
+
+/// The filters of the collectors receiving events from a shard.
+#[cfg(feature = "collector")]
+#[derive(Debug)]
+pub(crate) struct CollectorFilters {
+    event_filters: Vec<EventFilter>,
+    message_filters: Vec<MessageFilter>,
+    reaction_filters: Vec<ReactionFilter>,
+    guild_member_filters: Vec<GuildMemberFilter>,
+    typing_filters: Vec<TypingFilter>,
+    voice_state_filters: Vec<VoiceStateFilter>,
+    message_event_filters: Vec<MessageEventFilter>,
+    #[cfg(feature = "unstable_discord_api")]
+    component_interaction_filters: Vec<ComponentInteractionFilter>,
+    #[cfg(feature = "unstable_discord_api")]
+    modal_interaction_filters: Vec<ModalInteractionFilter>,
+    last_filter_purge: Instant,
+}
+
+#[cfg(feature = "collector")]
+impl CollectorFilters {
+    pub(crate) fn new() -> Self {
+        Self {
+            event_filters: Vec::new(),
+            message_filters: Vec::new(),
+            reaction_filters: Vec::new(),
+            guild_member_filters: Vec::new(),
+            typing_filters: Vec::new(),
+            voice_state_filters: Vec::new(),
+            message_event_filters: Vec::new(),
+            #[cfg(feature = "unstable_discord_api")]
+            component_interaction_filters: Vec::new(),
+            #[cfg(feature = "unstable_discord_api")]
+            modal_interaction_filters: Vec::new(),
+            last_filter_purge: Instant::now(),
+        }
+    }
+
+    /// Adds or removes a filter if the `message` asks to do so, otherwise
+    /// returns the `message`.
+    pub(crate) fn handle_message(
+        &mut self,
+        message: ShardRunnerMessage,
+    ) -> Option<ShardRunnerMessage> {
+        match message {
+            ShardRunnerMessage::SetEventFilter(filter) => self.event_filters.push(filter),
+            ShardRunnerMessage::SetMessageFilter(filter) => self.message_filters.push(filter),
+            ShardRunnerMessage::SetReactionFilter(filter) => self.reaction_filters.push(filter),
+            ShardRunnerMessage::SetGuildMemberFilter(filter) => {
+                self.guild_member_filters.push(filter);
+            },
+            ShardRunnerMessage::SetTypingFilter(filter) => self.typing_filters.push(filter),
+            ShardRunnerMessage::SetVoiceStateFilter(filter) => {
+                self.voice_state_filters.push(filter);
+            },
+            ShardRunnerMessage::SetMessageEventFilter(filter) => {
+                self.message_event_filters.push(filter);
+            },
+            #[cfg(feature = "unstable_discord_api")]
+            ShardRunnerMessage::SetComponentInteractionFilter(filter) => {
+                self.component_interaction_filters.push(filter);
+            },
+            #[cfg(feature = "unstable_discord_api")]
+            ShardRunnerMessage::SetModalInteractionFilter(filter) => {
+                self.modal_interaction_filters.push(filter);
+            },
+            ShardRunnerMessage::RemoveEventFilter(id) => {
+                self.event_filters.retain(|f| f.id() != id);
+            },
+            ShardRunnerMessage::RemoveMessageFilter(id) => {
+                self.message_filters.retain(|f| f.id() != id);
+            },
+            ShardRunnerMessage::RemoveReactionFilter(id) => {
+                self.reaction_filters.retain(|f| f.id() != id);
+            },
+            ShardRunnerMessage::RemoveGuildMemberFilter(id) => {
+                self.guild_member_filters.retain(|f| f.id() != id);
+            },
+            ShardRunnerMessage::RemoveTypingFilter(id) => {
+                self.typing_filters.retain(|f| f.id() != id);
+            },
+            ShardRunnerMessage::RemoveVoiceStateFilter(id) => {
+                self.voice_state_filters.retain(|f| f.id() != id);
+            },
+            ShardRunnerMessage::RemoveMessageEventFilter(id) => {
+                self.message_event_filters.retain(|f| f.id() != id);
+            },
+            #[cfg(feature = "unstable_discord_api")]
+            ShardRunnerMessage::RemoveComponentInteractionFilter(id) => {
+                self.component_interaction_filters.retain(|f| f.id() != id);
+            },
+            #[cfg(feature = "unstable_discord_api")]
+            ShardRunnerMessage::RemoveModalInteractionFilter(id) => {
+                self.modal_interaction_filters.retain(|f| f.id() != id);
+            },
+            other => return Some(other),
+        }
+
+        None
+    }
+
+    /// Whether a running collector waits for events of the given type.
+    pub(crate) fn is_collected(&self, kind: &EventType) -> bool {
+        let collected = match kind {
+            EventType::MessageCreate => !self.message_filters.is_empty(),
+            EventType::ReactionAdd | EventType::ReactionRemove => !self.reaction_filters.is_empty(),
+            EventType::GuildMemberAdd | EventType::GuildMemberRemove => {
+                !self.guild_member_filters.is_empty()
+            },
+            EventType::TypingStart => !self.typing_filters.is_empty(),
+            EventType::VoiceStateUpdate => !self.voice_state_filters.is_empty(),
+            EventType::MessageUpdate | EventType::MessageDelete | EventType::MessageDeleteBulk => {
+                !self.message_event_filters.is_empty()
+            },
+            #[cfg(feature = "unstable_discord_api")]
+            EventType::InteractionCreate => {
+                !self.component_interaction_filters.is_empty()
+                    || !self.modal_interaction_filters.is_empty()
+            },
+            _ => false,
+        };
+
+        collected || self.event_filters.iter().any(|f| f.event_types().contains(kind))
+    }
+
+    /// Lets filters check the `event` to send them to collectors if the `event`
+    /// is accepted by them.
+    ///
+    /// This must be called before the `event` updates the cache, as the cache
+    /// is checked for the state the `event` replaces.
+    pub(crate) async fn handle_filters(&mut self, event: &Event, cache_and_http: &CacheAndHttp) {
+        /// Unlike [`Vec`]'s `retain`, allows mutable references in `f`.
+        fn retain<T, F>(vec: &mut Vec<T>, mut f: F)
+        where
+            F: FnMut(&mut T) -> bool,
+        {
+            let len = vec.len();
+            let mut del = 0;
+            {
+                let v = &mut **vec;
+
+                for i in 0..len {
+                    if !f(&mut v[i]) {
+                        del += 1;
+                    } else if del > 0 {
+                        v.swap(i - del, i);
+                    }
+                }
+            }
+
+            if del > 0 {
+                vec.truncate(len - del);
+            }
+        }
+
+        match &event {
+            Event::MessageCreate(ref msg_event) => {
+                let mut msg = LazyArc::new(&msg_event.message);
+                retain(&mut self.message_filters, |f| f.send_message(&mut msg));
+            },
+            Event::ReactionAdd(ref reaction_event) => {
+                let mut reaction = LazyReactionAction::new(&reaction_event.reaction, true);
+                retain(&mut self.reaction_filters, |f| f.send_reaction(&mut reaction));
+            },
+            Event::ReactionRemove(ref reaction_event) => {
+                let mut reaction = LazyReactionAction::new(&reaction_event.reaction, false);
+                retain(&mut self.reaction_filters, |f| f.send_reaction(&mut reaction));
+            },
+            Event::GuildMemberAdd(ref member_event) => {
+                let mut action = LazyGuildMemberAction::joined(member_event);
+                retain(&mut self.guild_member_filters, |f| f.send_member(&mut action));
+            },
+            Event::GuildMemberRemove(ref member_event) => {
+                let mut action = LazyGuildMemberAction::left(member_event);
+                retain(&mut self.guild_member_filters, |f| f.send_member(&mut action));
+            },
+            Event::TypingStart(ref typing_event) => {
+                let mut typing = LazyArc::new(typing_event);
+                retain(&mut self.typing_filters, |f| f.send_typing(&mut typing));
+            },
+            Event::VoiceStateUpdate(ref voice_state_event)
+                if !self.voice_state_filters.is_empty() =>
+            {
+                let action = Arc::new(VoiceStateAction {
+                    guild_id: voice_state_event.guild_id,
+                    old: old_voice_state(voice_state_event, cache_and_http).await,
+                    new: voice_state_event.voice_state.clone(),
+                });
+                retain(&mut self.voice_state_filters, |f| f.send_voice_state(&action));
+            },
+            Event::MessageUpdate(ref update_event) if !self.message_event_filters.is_empty() => {
+                let old =
+                    cached_message(update_event.channel_id, update_event.id, cache_and_http).await;
+                let mut action = LazyMessageAction::updated(update_event, old);
+                retain(&mut self.message_event_filters, |f| f.send_message_event(&mut action));
+            },
+            Event::MessageDelete(ref delete_event) if !self.message_event_filters.is_empty() => {
+                let (channel_id, message_id) = (delete_event.channel_id, delete_event.message_id);
+                let old = cached_message(channel_id, message_id, cache_and_http).await;
+                let mut action =
+                    LazyMessageAction::deleted(delete_event.guild_id, channel_id, message_id, old);
+                retain(&mut self.message_event_filters, |f| f.send_message_event(&mut action));
+            },
+            Event::MessageDeleteBulk(ref delete_event)
+                if !self.message_event_filters.is_empty() =>
+            {
+                let (guild_id, channel_id) = (delete_event.guild_id, delete_event.channel_id);
+
+                for &message_id in &delete_event.ids {
+                    let old = cached_message(channel_id, message_id, cache_and_http).await;
+                    let mut action =
+                        LazyMessageAction::deleted(guild_id, channel_id, message_id, old);
+                    retain(&mut self.message_event_filters, |f| f.send_message_event(&mut action));
+                }
+            },
+            #[cfg(feature = "unstable_discord_api")]
+            Event::InteractionCreate(ref interaction_event) => {
+                match &interaction_event.interaction {
+                    Interaction::MessageComponent(interaction) => {
+                        let mut interaction = LazyArc::new(interaction);
+                        retain(&mut self.component_interaction_filters, |f| {
+                            f.send_interaction(&mut interaction)
+                        });
+                    },
+                    Interaction::ModalSubmit(interaction) => {
+                        let mut interaction = LazyArc::new(interaction);
+                        retain(&mut self.modal_interaction_filters, |f| {
+                            f.send_interaction(&mut interaction)
+                        });
+                    },
+                    _ => (),
+                }
+            },
+            _ => {},
+        }
+
+        let mut event = LazyArc::new(event);
+        retain(&mut self.event_filters, |f| f.send_event(&mut event));
+    }
+
+    /// Removes the filters of collectors which were dropped, in case they
+    /// did not remove their filter themselves and no event passed them since.
+    ///
+    /// This is only done once per [`FILTER_PURGE_INTERVAL`].
+    pub(crate) fn purge_filters(&mut self) {
+        if self.last_filter_purge.elapsed() < FILTER_PURGE_INTERVAL {
+            return;
+        }
+
+        self.last_filter_purge = Instant::now();
+
+        self.event_filters.retain(|f| !f.is_closed());
+        self.message_filters.retain(|f| !f.is_closed());
+        self.reaction_filters.retain(|f| !f.is_closed());
+        self.guild_member_filters.retain(|f| !f.is_closed());
+        self.typing_filters.retain(|f| !f.is_closed());
+        self.voice_state_filters.retain(|f| !f.is_closed());
+        self.message_event_filters.retain(|f| !f.is_closed());
+        #[cfg(feature = "unstable_discord_api")]
+        {
+            self.component_interaction_filters.retain(|f| !f.is_closed());
+            self.modal_interaction_filters.retain(|f| !f.is_closed());
+        }
+    }
+}
+
+/// Looks up the cached voice state which the `event` replaces.
+#[cfg(all(feature = "cache", feature = "collector"))]
+async fn old_voice_state(
+    event: &VoiceStateUpdateEvent,
+    cache_and_http: &CacheAndHttp,
+) -> Option<VoiceState> {
+    let guild_id = event.guild_id?;
+
+    cache_and_http.cache.voice_state(guild_id, event.voice_state.user_id).await
+}
+
+#[cfg(all(not(feature = "cache"), feature = "collector"))]
+async fn old_voice_state(
+    _event: &VoiceStateUpdateEvent,
+    _cache_and_http: &CacheAndHttp,
+) -> Option<VoiceState> {
+    None
+}
+
+/// Looks up the cached message which an event edits or deletes.
+#[cfg(all(feature = "cache", feature = "collector"))]
+async fn cached_message(
+    channel_id: ChannelId,
+    message_id: MessageId,
+    cache_and_http: &CacheAndHttp,
+) -> Option<Message> {
+    cache_and_http.cache.message(channel_id, message_id).await
+}
+
+#[cfg(all(not(feature = "cache"), feature = "collector"))]
+async fn cached_message(
+    _channel_id: ChannelId,
+    _message_id: MessageId,
+    _cache_and_http: &CacheAndHttp,
+) -> Option<Message> {
+    None
 }
@@ -1,9 +1,21 @@
+use std::sync::{Arc, Mutex};
+
 use async_tungstenite::tungstenite::Message;
+use tokio::sync::oneshot::{self, Receiver, Sender};
 
 #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
 use crate::collector::{ComponentInteractionFilter, ModalInteractionFilter};
 #[cfg(feature = "collector")]
-use crate::collector::{EventFilter, MessageFilter, ReactionFilter};
+use crate::collector::{
+    EventFilter,
+    FilterId,
+    GuildMemberFilter,
+    MessageEventFilter,
+    MessageFilter,
+    ReactionFilter,
+    TypingFilter,
+    VoiceStateFilter,
+};
 use crate::model::{
     gateway::Activity,
     id::{GuildId, UserId},
@@ -70,10 +82,92 @@ pub enum ShardRunnerMessage {
     /// Sends a new filter for reactions to the shard.
     #[cfg(feature = "collector")]
     SetReactionFilter(ReactionFilter),
+    /// Sends a new filter for members joining or leaving guilds to the shard.
+    #[cfg(feature = "collector")]
+    SetGuildMemberFilter(GuildMemberFilter),
+    /// Sends a new filter for users starting to type to the shard.
+    #[cfg(feature = "collector")]
+    SetTypingFilter(TypingFilter),
+    /// Sends a new filter for voice state updates to the shard.
+    #[cfg(feature = "collector")]
+    SetVoiceStateFilter(VoiceStateFilter),
+    /// Sends a new filter for messages being edited or deleted to the shard.
+    #[cfg(feature = "collector")]
+    SetMessageEventFilter(MessageEventFilter),
     /// Sends a new filter for component interactions to the shard.
     #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
     SetComponentInteractionFilter(ComponentInteractionFilter),
     /// Sends a new filter for modal interactions to the shard.
     #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
     SetModalInteractionFilter(ModalInteractionFilter),
+    /// Removes the filter of a dropped event collector from the shard.
+    #[cfg(feature = "collector")]
+    RemoveEventFilter(FilterId),
+    /// Removes the filter of a dropped message collector from the shard.
+    #[cfg(feature = "collector")]
+    RemoveMessageFilter(FilterId),
+    /// Removes the filter of a dropped reaction collector from the shard.
+    #[cfg(feature = "collector")]
+    RemoveReactionFilter(FilterId),
+    /// Removes the filter of a dropped guild member collector from the shard.
+    #[cfg(feature = "collector")]
+    RemoveGuildMemberFilter(FilterId),
+    /// Removes the filter of a dropped typing collector from the shard.
+    #[cfg(feature = "collector")]
+    RemoveTypingFilter(FilterId),
+    /// Removes the filter of a dropped voice state collector from the shard.
+    #[cfg(feature = "collector")]
+    RemoveVoiceStateFilter(FilterId),
+    /// Removes the filter of a dropped message event collector from the shard.
+    #[cfg(feature = "collector")]
+    RemoveMessageEventFilter(FilterId),
+    /// Removes the filter of a dropped component interaction collector from
+    /// the shard.
+    #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
+    RemoveComponentInteractionFilter(FilterId),
+    /// Removes the filter of a dropped modal interaction collector from the
+    /// shard.
+    #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
+    RemoveModalInteractionFilter(FilterId),
+    /// Indicates that the runner is to send the acknowledgement once it
+    /// handles this message, acknowledging that all messages sent before were
+    /// handled.
+    ///
+    /// Refer to [`ShardMessenger::send_to_shard_sync`].
+    ///
+    /// [`ShardMessenger::send_to_shard_sync`]: super::ShardMessenger::send_to_shard_sync
+    Acknowledge(Acknowledgement),
+}
+
+/// The sending half of a [`ShardRunnerMessage::Acknowledge`].
+///
+/// Clones share the same acknowledgement, which is only sent once.
+#[derive(Clone, Debug)]
+pub struct Acknowledgement(Arc<Mutex<Option<Sender<()>>>>);
+
+impl Acknowledgement {
+    /// Creates an acknowledgement, along with the receiver resolving once it
+    /// was sent.
+    ///
+    /// The receiver resolves to an error if the acknowledgement was dropped
+    /// without being sent.
+    pub fn new() -> (Self, Receiver<()>) {
+        let (tx, rx) = oneshot::channel();
+
+        (Self(Arc::new(Mutex::new(Some(tx)))), rx)
+    }
+
+    /// Sends the acknowledgement, unless it was sent already.
+    pub fn send(&self) {
+        let tx = match self.0.lock() {
+            Ok(mut tx) => tx.take(),
+            Err(poisoned) => poisoned.into_inner().take(),
+        };
+
+        if let Some(tx) = tx {
+            // The receiver may have stopped waiting, which is fine.
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = tx.send(());
+        }
+    }
 }
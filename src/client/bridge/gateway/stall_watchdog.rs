@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::gateway::ReconnectType;
+
+/// Reconnects a shard whose connection silently stopped delivering gateway
+/// frames, configured with [`ClientBuilder::gateway_stall_timeout`].
+///
+/// A healthy connection receives a frame at least once per heartbeat
+/// interval, as Discord acknowledges every heartbeat, so the timeout should be
+/// well above that interval, which is usually around 41 seconds.
+///
+/// [`ClientBuilder::gateway_stall_timeout`]: crate::client::ClientBuilder::gateway_stall_timeout
+#[derive(Clone, Copy, Debug)]
+pub struct StallWatchdog {
+    timeout: Duration,
+    reconnect: ReconnectType,
+}
+
+impl StallWatchdog {
+    /// Creates a watchdog which resumes the session of a shard once it
+    /// received no frame for `timeout`.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            reconnect: ReconnectType::Resume,
+        }
+    }
+
+    /// Sets how a stalled shard reconnects. Defaults to
+    /// [`ReconnectType::Resume`], which falls back to identifying anew if the
+    /// shard has no session to resume.
+    pub fn reconnect(mut self, reconnect: ReconnectType) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// The time without a frame after which a shard counts as stalled.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// How a stalled shard reconnects.
+    pub fn reconnect_type(&self) -> ReconnectType {
+        self.reconnect
+    }
+}
+
+/// Tracks when a shard runner last received a gateway frame.
+#[derive(Debug)]
+pub(crate) struct StallDetector {
+    watchdog: StallWatchdog,
+    last_frame: Instant,
+}
+
+impl StallDetector {
+    pub(crate) fn new(watchdog: StallWatchdog) -> Self {
+        Self {
+            watchdog,
+            last_frame: Instant::now(),
+        }
+    }
+
+    pub(crate) fn frame_received(&mut self) {
+        self.last_frame = Instant::now();
+    }
+
+    /// Returns for how long no frame was received if that exceeds the
+    /// timeout, restarting the count so that a reconnect is given as much
+    /// time as the original connection.
+    pub(crate) fn check(&mut self) -> Option<Duration> {
+        let silent_for = self.last_frame.elapsed();
+
+        if silent_for < self.watchdog.timeout {
+            return None;
+        }
+
+        self.frame_received();
+
+        Some(silent_for)
+    }
+
+    pub(crate) fn reconnect_type(&self) -> ReconnectType {
+        self.watchdog.reconnect
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use tokio::time;
+
+    use super::{StallDetector, StallWatchdog};
+    use crate::gateway::ReconnectType;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_frame_starvation() {
+        let watchdog = StallWatchdog::new(Duration::from_secs(90));
+        let mut detector = StallDetector::new(watchdog);
+
+        time::advance(Duration::from_secs(60)).await;
+        assert_eq!(detector.check(), None);
+
+        // Frames keep arriving, pushing the stall back.
+        detector.frame_received();
+        time::advance(Duration::from_secs(60)).await;
+        assert_eq!(detector.check(), None);
+
+        // No more frames arrive.
+        time::advance(Duration::from_secs(45)).await;
+        assert_eq!(detector.check(), Some(Duration::from_secs(105)));
+
+        // The reconnect gets a full timeout before counting as stalled again.
+        time::advance(Duration::from_secs(89)).await;
+        assert_eq!(detector.check(), None);
+        time::advance(Duration::from_secs(1)).await;
+        assert_eq!(detector.check(), Some(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn test_reconnect_type() {
+        let watchdog = StallWatchdog::new(Duration::from_secs(90));
+        assert_eq!(watchdog.reconnect_type(), ReconnectType::Resume);
+
+        let watchdog = watchdog.reconnect(ReconnectType::Reidentify);
+        assert_eq!(StallDetector::new(watchdog).reconnect_type(), ReconnectType::Reidentify);
+    }
+}
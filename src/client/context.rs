@@ -38,6 +38,19 @@ pub struct Context {
     ///
     /// [`Client::data`]: super::Client::data
     pub data: Arc<RwLock<TypeMap>>,
+    /// The data of the shard this context is related to.
+    ///
+    /// Unlike [`Self::data`], every shard has its own map, which is created
+    /// when the shard boots and dropped when it shuts down. It can be used for
+    /// per-shard state, such as database connections or metrics labels, and
+    /// is accessed in the same way, using a [`TypeMapKey`].
+    ///
+    /// Each map is initialised by the callback passed to
+    /// [`ClientBuilder::shard_data_init`], and is empty otherwise.
+    ///
+    /// [`TypeMapKey`]: crate::prelude::TypeMapKey
+    /// [`ClientBuilder::shard_data_init`]: super::ClientBuilder::shard_data_init
+    pub shard_data: Arc<RwLock<TypeMap>>,
     /// The messenger to communicate with the shard runner.
     pub shard: ShardMessenger,
     /// The ID of the shard this context is related to.
@@ -52,6 +65,7 @@ impl Context {
     #[cfg(all(feature = "cache", feature = "gateway"))]
     pub(crate) fn new(
         data: Arc<RwLock<TypeMap>>,
+        shard_data: Arc<RwLock<TypeMap>>,
         runner_tx: Sender<InterMessage>,
         shard_id: u64,
         http: Arc<Http>,
@@ -61,6 +75,7 @@ impl Context {
             shard: ShardMessenger::new(runner_tx),
             shard_id,
             data,
+            shard_data,
             http,
             cache,
         }
@@ -71,6 +86,7 @@ impl Context {
         Context {
             shard_id,
             data,
+            shard_data: Arc::new(RwLock::new(TypeMap::new())),
             http,
         }
     }
@@ -79,6 +95,7 @@ impl Context {
     #[cfg(all(not(feature = "cache"), feature = "gateway"))]
     pub(crate) fn new(
         data: Arc<RwLock<TypeMap>>,
+        shard_data: Arc<RwLock<TypeMap>>,
         runner_tx: Sender<InterMessage>,
         shard_id: u64,
         http: Arc<Http>,
@@ -87,6 +104,7 @@ impl Context {
             shard: ShardMessenger::new(runner_tx),
             shard_id,
             data,
+            shard_data,
             http,
         }
     }
@@ -390,7 +408,7 @@ impl Context {
     #[inline]
     #[cfg(feature = "collector")]
     pub async fn set_message_filter(&self, filter: MessageFilter) {
-        self.shard.set_message_filter(filter);
+        self.shard.set_message_filter_sync(filter).await;
     }
 
     /// Sets a new `filter` for the shard to check if a reaction event shall be
@@ -398,7 +416,7 @@ impl Context {
     #[inline]
     #[cfg(feature = "collector")]
     pub async fn set_reaction_filter(&self, filter: ReactionFilter) {
-        self.shard.set_reaction_filter(filter);
+        self.shard.set_reaction_filter_sync(filter).await;
     }
 
     /// Sets a new `filter` for the shard to check if an interaction event shall be
@@ -406,7 +424,7 @@ impl Context {
     #[inline]
     #[cfg(all(feature = "unstable_discord_api", feature = "collector"))]
     pub async fn set_component_interaction_filter(&self, filter: ComponentInteractionFilter) {
-        self.shard.set_component_interaction_filter(filter);
+        self.shard.set_component_interaction_filter_sync(filter).await;
     }
 }
 
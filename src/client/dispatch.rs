@@ -15,6 +15,7 @@ use super::Context;
 use super::{
     bridge::gateway::event::ClientEvent,
     event_handler::{EventHandler, RawEventHandler},
+    task_tracker::TaskTracker,
 };
 #[cfg(feature = "cache")]
 use crate::cache::{Cache, CacheUpdate};
@@ -22,7 +23,6 @@ use crate::cache::{Cache, CacheUpdate};
 use crate::framework::Framework;
 use crate::gateway::InterMessage;
 use crate::http::Http;
-use crate::internal::tokio::spawn_named;
 #[cfg(feature = "cache")]
 use crate::model::id::GuildId;
 use crate::model::{
@@ -50,22 +50,37 @@ async fn update<E>(_cache_and_http: &Arc<CacheAndHttp>, _event: &mut E) -> Optio
 #[cfg(feature = "cache")]
 fn context(
     data: &Arc<RwLock<TypeMap>>,
+    shard_data: &Arc<RwLock<TypeMap>>,
     runner_tx: &Sender<InterMessage>,
     shard_id: u64,
     http: &Arc<Http>,
     cache: &Arc<Cache>,
 ) -> Context {
-    Context::new(Arc::clone(data), runner_tx.clone(), shard_id, Arc::clone(http), Arc::clone(cache))
+    Context::new(
+        Arc::clone(data),
+        Arc::clone(shard_data),
+        runner_tx.clone(),
+        shard_id,
+        Arc::clone(http),
+        Arc::clone(cache),
+    )
 }
 
 #[cfg(not(feature = "cache"))]
 fn context(
     data: &Arc<RwLock<TypeMap>>,
+    shard_data: &Arc<RwLock<TypeMap>>,
     runner_tx: &Sender<InterMessage>,
     shard_id: u64,
     http: &Arc<Http>,
 ) -> Context {
-    Context::new(Arc::clone(data), runner_tx.clone(), shard_id, Arc::clone(http))
+    Context::new(
+        Arc::clone(data),
+        Arc::clone(shard_data),
+        runner_tx.clone(),
+        shard_id,
+        Arc::clone(http),
+    )
 }
 
 // Once we can use `Box` as part of a pattern, we will reconsider boxing.
@@ -153,11 +168,13 @@ pub(crate) fn dispatch<'rec>(
     mut event: DispatchEvent,
     #[cfg(feature = "framework")] framework: &'rec Arc<Box<dyn Framework + Send + Sync>>,
     data: &'rec Arc<RwLock<TypeMap>>,
+    shard_data: &'rec Arc<RwLock<TypeMap>>,
     event_handler: &'rec Option<Arc<dyn EventHandler>>,
     raw_event_handler: &'rec Option<Arc<dyn RawEventHandler>>,
     runner_tx: &'rec Sender<InterMessage>,
     shard_id: u64,
     cache_and_http: Arc<CacheAndHttp>,
+    task_tracker: &'rec TaskTracker,
 ) -> BoxFuture<'rec, ()> {
     async move {
         match (event_handler, raw_event_handler) {
@@ -168,10 +185,12 @@ pub(crate) fn dispatch<'rec>(
                     #[cfg(feature = "framework")]
                     {
                         #[cfg(not(feature = "cache"))]
-                        let context = context(data, runner_tx, shard_id, &cache_and_http.http);
+                        let context =
+                            context(data, shard_data, runner_tx, shard_id, &cache_and_http.http);
                         #[cfg(feature = "cache")]
                         let context = context(
                             data,
+                            shard_data,
                             runner_tx,
                             shard_id,
                             &cache_and_http.http,
@@ -180,7 +199,7 @@ pub(crate) fn dispatch<'rec>(
 
                         let framework = Arc::clone(framework);
 
-                        spawn_named("dispatch::framework::message", async move {
+                        task_tracker.spawn("dispatch::framework::message", async move {
                             framework.dispatch(context, event.message).await;
                         });
                     }
@@ -191,10 +210,12 @@ pub(crate) fn dispatch<'rec>(
                     update(&cache_and_http, &mut event).await;
 
                     #[cfg(not(feature = "cache"))]
-                    let context = context(data, runner_tx, shard_id, &cache_and_http.http);
+                    let context =
+                        context(data, shard_data, runner_tx, shard_id, &cache_and_http.http);
                     #[cfg(feature = "cache")]
                     let context = context(
                         data,
+                        shard_data,
                         runner_tx,
                         shard_id,
                         &cache_and_http.http,
@@ -204,22 +225,33 @@ pub(crate) fn dispatch<'rec>(
                     #[cfg(not(feature = "framework"))]
                     {
                         // Avoid cloning if there will be no framework dispatch.
-                        dispatch_message(context, event.message, h).await;
+                        dispatch_message(context, event.message, h, task_tracker).await;
                     }
 
                     #[cfg(feature = "framework")]
                     {
-                        dispatch_message(context.clone(), event.message.clone(), h).await;
+                        dispatch_message(context.clone(), event.message.clone(), h, task_tracker)
+                            .await;
 
                         let framework = Arc::clone(framework);
 
-                        spawn_named("dispatch::framework::message", async move {
+                        task_tracker.spawn("dispatch::framework::message", async move {
                             framework.dispatch(context, event.message).await;
                         });
                     }
                 },
                 other => {
-                    handle_event(other, data, h, runner_tx, shard_id, cache_and_http).await;
+                    handle_event(
+                        other,
+                        data,
+                        shard_data,
+                        h,
+                        runner_tx,
+                        shard_id,
+                        cache_and_http,
+                        task_tracker,
+                    )
+                    .await;
                 },
             },
             (None, Some(ref rh)) => {
@@ -229,10 +261,12 @@ pub(crate) fn dispatch<'rec>(
                     let event_handler = Arc::clone(rh);
 
                     #[cfg(not(feature = "cache"))]
-                    let context = context(data, runner_tx, shard_id, &cache_and_http.http);
+                    let context =
+                        context(data, shard_data, runner_tx, shard_id, &cache_and_http.http);
                     #[cfg(feature = "cache")]
                     let context = context(
                         data,
+                        shard_data,
                         runner_tx,
                         shard_id,
                         &cache_and_http.http,
@@ -254,7 +288,7 @@ pub(crate) fn dispatch<'rec>(
 
                             let framework = Arc::clone(framework);
 
-                            spawn_named("dispatch::framework::message", async move {
+                            task_tracker.spawn("dispatch::framework::message", async move {
                                 framework.dispatch(context, message).await;
                             });
                         } else {
@@ -268,10 +302,16 @@ pub(crate) fn dispatch<'rec>(
             // and passing no framework, as we dispatch once we are done right here.
             (Some(ref handler), Some(ref raw_handler)) => {
                 #[cfg(not(feature = "cache"))]
-                let context = context(data, runner_tx, shard_id, &cache_and_http.http);
+                let context = context(data, shard_data, runner_tx, shard_id, &cache_and_http.http);
                 #[cfg(feature = "cache")]
-                let context =
-                    context(data, runner_tx, shard_id, &cache_and_http.http, &cache_and_http.cache);
+                let context = context(
+                    data,
+                    shard_data,
+                    runner_tx,
+                    shard_id,
+                    &cache_and_http.http,
+                    &cache_and_http.cache,
+                );
 
                 if let DispatchEvent::Model(ref event) = event {
                     raw_handler.raw_event(context.clone(), event.clone()).await;
@@ -282,23 +322,38 @@ pub(crate) fn dispatch<'rec>(
                         #[cfg(not(feature = "framework"))]
                         {
                             // Avoid cloning if there will be no framework dispatch.
-                            dispatch_message(context, event.message, handler).await;
+                            dispatch_message(context, event.message, handler, task_tracker).await;
                         }
 
                         #[cfg(feature = "framework")]
                         {
-                            dispatch_message(context.clone(), event.message.clone(), handler).await;
+                            dispatch_message(
+                                context.clone(),
+                                event.message.clone(),
+                                handler,
+                                task_tracker,
+                            )
+                            .await;
 
                             let framework = Arc::clone(framework);
                             let message = event.message;
-                            spawn_named("dispatch::framework::message", async move {
+                            task_tracker.spawn("dispatch::framework::message", async move {
                                 framework.dispatch(context, message).await;
                             });
                         }
                     },
                     other => {
-                        handle_event(other, data, handler, runner_tx, shard_id, cache_and_http)
-                            .await
+                        handle_event(
+                            other,
+                            data,
+                            shard_data,
+                            handler,
+                            runner_tx,
+                            shard_id,
+                            cache_and_http,
+                            task_tracker,
+                        )
+                        .await
                     },
                 }
             },
@@ -307,10 +362,36 @@ pub(crate) fn dispatch<'rec>(
     .boxed()
 }
 
+/// Dispatches an event to the raw event handler only, skipping the cache, the
+/// framework and the event handler.
+///
+/// This is used for events skipped by an [`EventTypeFilter`].
+///
+/// [`EventTypeFilter`]: super::bridge::gateway::EventTypeFilter
+#[cfg(feature = "gateway")]
+pub(crate) async fn dispatch_raw(
+    event: Event,
+    data: &Arc<RwLock<TypeMap>>,
+    shard_data: &Arc<RwLock<TypeMap>>,
+    raw_event_handler: &Arc<dyn RawEventHandler>,
+    runner_tx: &Sender<InterMessage>,
+    shard_id: u64,
+    cache_and_http: &Arc<CacheAndHttp>,
+) {
+    #[cfg(not(feature = "cache"))]
+    let context = context(data, shard_data, runner_tx, shard_id, &cache_and_http.http);
+    #[cfg(feature = "cache")]
+    let context =
+        context(data, shard_data, runner_tx, shard_id, &cache_and_http.http, &cache_and_http.cache);
+
+    raw_event_handler.raw_event(context, event).await;
+}
+
 async fn dispatch_message(
     context: Context,
     mut message: Message,
     event_handler: &Arc<dyn EventHandler>,
+    task_tracker: &TaskTracker,
 ) {
     #[cfg(feature = "model")]
     {
@@ -319,48 +400,58 @@ async fn dispatch_message(
 
     let event_handler = Arc::clone(event_handler);
 
-    spawn_named("dispatch::event_handler::message", async move {
+    task_tracker.spawn("dispatch::event_handler::message", async move {
         event_handler.message(context, message).await;
     });
 }
 // Once we can use `Box` as part of a pattern, we will reconsider boxing.
 #[allow(clippy::too_many_arguments)]
-#[instrument(skip(event, data, event_handler, cache_and_http))]
+#[instrument(skip(event, data, shard_data, event_handler, cache_and_http, task_tracker))]
 async fn handle_event(
     event: DispatchEvent,
     data: &Arc<RwLock<TypeMap>>,
+    shard_data: &Arc<RwLock<TypeMap>>,
     event_handler: &Arc<dyn EventHandler>,
     runner_tx: &Sender<InterMessage>,
     shard_id: u64,
     cache_and_http: Arc<CacheAndHttp>,
+    task_tracker: &TaskTracker,
 ) {
     #[cfg(not(feature = "cache"))]
-    let context = context(data, runner_tx, shard_id, &cache_and_http.http);
+    let context = context(data, shard_data, runner_tx, shard_id, &cache_and_http.http);
     #[cfg(feature = "cache")]
-    let context = context(data, runner_tx, shard_id, &cache_and_http.http, &cache_and_http.cache);
+    let context =
+        context(data, shard_data, runner_tx, shard_id, &cache_and_http.http, &cache_and_http.cache);
 
     match event {
         DispatchEvent::Client(ClientEvent::ShardStageUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::shard_stage_update", async move {
+            task_tracker.spawn("dispatch::event_handler::shard_stage_update", async move {
                 event_handler.shard_stage_update(context, event).await;
             });
         },
+        DispatchEvent::Client(ClientEvent::ShardLifecycle(event)) => {
+            let event_handler = Arc::clone(event_handler);
+
+            task_tracker.spawn("dispatch::event_handler::shard_lifecycle", async move {
+                event_handler.shard_lifecycle(context, event).await;
+            });
+        },
         DispatchEvent::Model(Event::ChannelCreate(mut event)) => {
             update(&cache_and_http, &mut event).await;
             match event.channel {
                 Channel::Guild(channel) => {
                     let event_handler = Arc::clone(event_handler);
 
-                    spawn_named("dispatch::event_handler::channel_create", async move {
+                    task_tracker.spawn("dispatch::event_handler::channel_create", async move {
                         event_handler.channel_create(context, &channel).await;
                     });
                 },
                 Channel::Category(channel) => {
                     let event_handler = Arc::clone(event_handler);
 
-                    spawn_named("dispatch::event_handler::category_create", async move {
+                    task_tracker.spawn("dispatch::event_handler::category_create", async move {
                         event_handler.category_create(context, &channel).await;
                     });
                 },
@@ -376,14 +467,14 @@ async fn handle_event(
                 Channel::Guild(channel) => {
                     let event_handler = Arc::clone(event_handler);
 
-                    spawn_named("dispatch::event_handler::channel_delete", async move {
+                    task_tracker.spawn("dispatch::event_handler::channel_delete", async move {
                         event_handler.channel_delete(context, &channel).await;
                     });
                 },
                 Channel::Category(channel) => {
                     let event_handler = Arc::clone(event_handler);
 
-                    spawn_named("dispatch::event_handler::category_delete", async move {
+                    task_tracker.spawn("dispatch::event_handler::category_delete", async move {
                         event_handler.category_delete(context, &channel).await;
                     });
                 },
@@ -392,14 +483,14 @@ async fn handle_event(
         DispatchEvent::Model(Event::ChannelPinsUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::channel_pins_update", async move {
+            task_tracker.spawn("dispatch::event_handler::channel_pins_update", async move {
                 event_handler.channel_pins_update(context, event).await;
             });
         },
         DispatchEvent::Model(Event::ChannelUpdate(mut event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::channel_update", async move {
+            task_tracker.spawn("dispatch::event_handler::channel_update", async move {
                 feature_cache! {{
                     let old_channel = cache_and_http.cache.as_ref().channel(event.channel.id()).await;
                     update(&cache_and_http, &mut event).await;
@@ -415,14 +506,14 @@ async fn handle_event(
         DispatchEvent::Model(Event::GuildBanAdd(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_ban_addition", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_ban_addition", async move {
                 event_handler.guild_ban_addition(context, event.guild_id, event.user).await;
             });
         },
         DispatchEvent::Model(Event::GuildBanRemove(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_ban_removal", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_ban_removal", async move {
                 event_handler.guild_ban_removal(context, event.guild_id, event.user).await;
             });
         },
@@ -448,7 +539,7 @@ async fn handle_event(
                         .collect::<Vec<GuildId>>();
                     let event_handler = Arc::clone(event_handler);
 
-                    spawn_named("dispatch::event_handler::cache_ready", async move {
+                    task_tracker.spawn("dispatch::event_handler::cache_ready", async move {
                         event_handler.cache_ready(context, guild_amount).await;
                     });
                 }
@@ -456,8 +547,9 @@ async fn handle_event(
 
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_create", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_create", async move {
                 feature_cache! {{
+                    event_handler.guild_available(context.clone(), &event.guild, !_is_new).await;
                     event_handler.guild_create(context, event.guild, _is_new).await;
                 } else {
                     event_handler.guild_create(context, event.guild).await;
@@ -468,7 +560,7 @@ async fn handle_event(
             let _full = update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_delete", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_delete", async move {
                 feature_cache! {{
                     event_handler.guild_delete(context, event.guild, _full).await;
                 } else {
@@ -480,14 +572,14 @@ async fn handle_event(
             update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_emojis_update", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_emojis_update", async move {
                 event_handler.guild_emojis_update(context, event.guild_id, event.emojis).await;
             });
         },
         DispatchEvent::Model(Event::GuildIntegrationsUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_integrations_update", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_integrations_update", async move {
                 event_handler.guild_integrations_update(context, event.guild_id).await;
             });
         },
@@ -496,7 +588,7 @@ async fn handle_event(
 
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_member_addition", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_member_addition", async move {
                 event_handler.guild_member_addition(context, event.guild_id, event.member).await;
             });
         },
@@ -504,7 +596,7 @@ async fn handle_event(
             let _member = update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_member_removal", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_member_removal", async move {
                 feature_cache! {{
                     event_handler.guild_member_removal(context, event.guild_id, event.user, _member).await;
                 } else {
@@ -522,7 +614,7 @@ async fn handle_event(
 
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_member_update", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_member_update", async move {
                 feature_cache! {{
                     if let Some(after) = _after {
                         event_handler.guild_member_update(context, _before, after).await;
@@ -536,7 +628,7 @@ async fn handle_event(
             update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_members_chunk", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_members_chunk", async move {
                 event_handler.guild_members_chunk(context, event).await;
             });
         },
@@ -544,7 +636,7 @@ async fn handle_event(
             update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_role_create", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_role_create", async move {
                 event_handler.guild_role_create(context, event.guild_id, event.role).await;
             });
         },
@@ -552,7 +644,7 @@ async fn handle_event(
             let _role = update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_role_delete", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_role_delete", async move {
                 feature_cache! {{
                     event_handler.guild_role_delete(context, event.guild_id, event.role_id, _role).await;
                 } else {
@@ -564,7 +656,7 @@ async fn handle_event(
             let _before = update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_role_update", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_role_update", async move {
                 feature_cache! {{
                     event_handler.guild_role_update(context, event.guild_id, _before, event.role).await;
                 } else {
@@ -576,20 +668,16 @@ async fn handle_event(
             update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_unavailable", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_unavailable", async move {
                 event_handler.guild_unavailable(context, event.guild_id).await;
             });
         },
         DispatchEvent::Model(Event::GuildUpdate(mut event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::guild_update", async move {
+            task_tracker.spawn("dispatch::event_handler::guild_update", async move {
                 feature_cache! {{
-                    let before = cache_and_http.cache
-                        .guild(&event.guild.id)
-                        .await;
-
-                    update(&cache_and_http, &mut event).await;
+                    let before = update(&cache_and_http, &mut event).await;
 
                     event_handler.guild_update(context, before, event.guild).await;
                 } else {
@@ -602,14 +690,14 @@ async fn handle_event(
         DispatchEvent::Model(Event::InviteCreate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::invite_create", async move {
+            task_tracker.spawn("dispatch::event_handler::invite_create", async move {
                 event_handler.invite_create(context, event).await;
             });
         },
         DispatchEvent::Model(Event::InviteDelete(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::invite_delete", async move {
+            task_tracker.spawn("dispatch::event_handler::invite_delete", async move {
                 event_handler.invite_delete(context, event).await;
             });
         },
@@ -618,7 +706,7 @@ async fn handle_event(
         DispatchEvent::Model(Event::MessageDeleteBulk(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::message_delete_bulk", async move {
+            task_tracker.spawn("dispatch::event_handler::message_delete_bulk", async move {
                 event_handler
                     .message_delete_bulk(context, event.channel_id, event.ids, event.guild_id)
                     .await;
@@ -627,7 +715,7 @@ async fn handle_event(
         DispatchEvent::Model(Event::MessageDelete(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::message_delete", async move {
+            task_tracker.spawn("dispatch::event_handler::message_delete", async move {
                 event_handler
                     .message_delete(context, event.channel_id, event.message_id, event.guild_id)
                     .await;
@@ -637,7 +725,7 @@ async fn handle_event(
             let _before = update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::message_update", async move {
+            task_tracker.spawn("dispatch::event_handler::message_update", async move {
                 feature_cache! {{
                     let _after = cache_and_http.cache.message(event.channel_id, event.id).await;
                     event_handler.message_update(context, _before, _after, event).await;
@@ -650,7 +738,7 @@ async fn handle_event(
             update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::presence_replace", async move {
+            task_tracker.spawn("dispatch::event_handler::presence_replace", async move {
                 event_handler.presence_replace(context, event.presences).await;
             });
         },
@@ -659,28 +747,28 @@ async fn handle_event(
 
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::presence_update", async move {
+            task_tracker.spawn("dispatch::event_handler::presence_update", async move {
                 event_handler.presence_update(context, event).await;
             });
         },
         DispatchEvent::Model(Event::ReactionAdd(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::reaction_add", async move {
+            task_tracker.spawn("dispatch::event_handler::reaction_add", async move {
                 event_handler.reaction_add(context, event.reaction).await;
             });
         },
         DispatchEvent::Model(Event::ReactionRemove(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::reaction_remove", async move {
+            task_tracker.spawn("dispatch::event_handler::reaction_remove", async move {
                 event_handler.reaction_remove(context, event.reaction).await;
             });
         },
         DispatchEvent::Model(Event::ReactionRemoveAll(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::remove_all", async move {
+            task_tracker.spawn("dispatch::event_handler::remove_all", async move {
                 event_handler
                     .reaction_remove_all(context, event.channel_id, event.message_id)
                     .await;
@@ -690,28 +778,28 @@ async fn handle_event(
             update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::ready", async move {
+            task_tracker.spawn("dispatch::event_handler::ready", async move {
                 event_handler.ready(context, event.ready).await;
             });
         },
         DispatchEvent::Model(Event::Resumed(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::resume", async move {
+            task_tracker.spawn("dispatch::event_handler::resume", async move {
                 event_handler.resume(context, event).await;
             });
         },
         DispatchEvent::Model(Event::TypingStart(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::typing_start", async move {
+            task_tracker.spawn("dispatch::event_handler::typing_start", async move {
                 event_handler.typing_start(context, event).await;
             });
         },
         DispatchEvent::Model(Event::Unknown(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::unknown", async move {
+            task_tracker.spawn("dispatch::event_handler::unknown", async move {
                 event_handler.unknown(context, event.kind, event.value).await;
             });
         },
@@ -719,7 +807,7 @@ async fn handle_event(
             let _before = update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::user_update", async move {
+            task_tracker.spawn("dispatch::event_handler::user_update", async move {
                 feature_cache! {{
                     event_handler.user_update(context, _before.expect("missing old user"), event.current_user).await;
                 } else {
@@ -730,7 +818,7 @@ async fn handle_event(
         DispatchEvent::Model(Event::VoiceServerUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::voice_server_update", async move {
+            task_tracker.spawn("dispatch::event_handler::voice_server_update", async move {
                 event_handler.voice_server_update(context, event).await;
             });
         },
@@ -738,7 +826,7 @@ async fn handle_event(
             let _before = update(&cache_and_http, &mut event).await;
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::voice_state_update", async move {
+            task_tracker.spawn("dispatch::event_handler::voice_state_update", async move {
                 feature_cache! {{
                     event_handler.voice_state_update(context, event.guild_id, _before, event.voice_state).await;
                 } else {
@@ -746,10 +834,20 @@ async fn handle_event(
                 }}
             });
         },
+        DispatchEvent::Model(Event::VoiceChannelStatusUpdate(event)) => {
+            let event_handler = Arc::clone(event_handler);
+
+            task_tracker.spawn(
+                "dispatch::event_handler::voice_channel_status_update",
+                async move {
+                    event_handler.voice_channel_status_update(context, event).await;
+                },
+            );
+        },
         DispatchEvent::Model(Event::WebhookUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::webhook_update", async move {
+            task_tracker.spawn("dispatch::event_handler::webhook_update", async move {
                 event_handler.webhook_update(context, event.guild_id, event.channel_id).await;
             });
         },
@@ -757,7 +855,7 @@ async fn handle_event(
         DispatchEvent::Model(Event::InteractionCreate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::interaction_create", async move {
+            task_tracker.spawn("dispatch::event_handler::interaction_create", async move {
                 event_handler.interaction_create(context, event.interaction).await;
             });
         },
@@ -765,7 +863,7 @@ async fn handle_event(
         DispatchEvent::Model(Event::IntegrationCreate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::integration_create", async move {
+            task_tracker.spawn("dispatch::event_handler::integration_create", async move {
                 event_handler.integration_create(context, event.integration).await;
             });
         },
@@ -773,7 +871,7 @@ async fn handle_event(
         DispatchEvent::Model(Event::IntegrationUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::integration_update", async move {
+            task_tracker.spawn("dispatch::event_handler::integration_update", async move {
                 event_handler.integration_update(context, event.integration).await;
             });
         },
@@ -781,18 +879,42 @@ async fn handle_event(
         DispatchEvent::Model(Event::IntegrationDelete(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::integration_delete", async move {
+            task_tracker.spawn("dispatch::event_handler::integration_delete", async move {
                 event_handler
                     .integration_delete(context, event.id, event.guild_id, event.application_id)
                     .await;
             });
         },
         #[cfg(feature = "unstable_discord_api")]
+        DispatchEvent::Model(Event::EntitlementCreate(event)) => {
+            let event_handler = Arc::clone(event_handler);
+
+            task_tracker.spawn("dispatch::event_handler::entitlement_create", async move {
+                event_handler.entitlement_create(context, event.entitlement).await;
+            });
+        },
+        #[cfg(feature = "unstable_discord_api")]
+        DispatchEvent::Model(Event::EntitlementUpdate(event)) => {
+            let event_handler = Arc::clone(event_handler);
+
+            task_tracker.spawn("dispatch::event_handler::entitlement_update", async move {
+                event_handler.entitlement_update(context, event.entitlement).await;
+            });
+        },
+        #[cfg(feature = "unstable_discord_api")]
+        DispatchEvent::Model(Event::EntitlementDelete(event)) => {
+            let event_handler = Arc::clone(event_handler);
+
+            task_tracker.spawn("dispatch::event_handler::entitlement_delete", async move {
+                event_handler.entitlement_delete(context, event.entitlement).await;
+            });
+        },
+        #[cfg(feature = "unstable_discord_api")]
         #[allow(deprecated)]
         DispatchEvent::Model(Event::ApplicationCommandCreate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::application_command_create", async move {
+            task_tracker.spawn("dispatch::event_handler::application_command_create", async move {
                 event_handler.application_command_create(context, event.application_command).await;
             });
         },
@@ -801,7 +923,7 @@ async fn handle_event(
         DispatchEvent::Model(Event::ApplicationCommandUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::application_command_update", async move {
+            task_tracker.spawn("dispatch::event_handler::application_command_update", async move {
                 event_handler.application_command_update(context, event.application_command).await;
             });
         },
@@ -810,70 +932,70 @@ async fn handle_event(
         DispatchEvent::Model(Event::ApplicationCommandDelete(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::application_command_delete", async move {
+            task_tracker.spawn("dispatch::event_handler::application_command_delete", async move {
                 event_handler.application_command_delete(context, event.application_command).await;
             });
         },
         DispatchEvent::Model(Event::StageInstanceCreate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::stage_instance_create", async move {
+            task_tracker.spawn("dispatch::event_handler::stage_instance_create", async move {
                 event_handler.stage_instance_create(context, event.stage_instance).await;
             });
         },
         DispatchEvent::Model(Event::StageInstanceUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::stage_instance_update", async move {
+            task_tracker.spawn("dispatch::event_handler::stage_instance_update", async move {
                 event_handler.stage_instance_update(context, event.stage_instance).await;
             });
         },
         DispatchEvent::Model(Event::StageInstanceDelete(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::stage_instance_delete", async move {
+            task_tracker.spawn("dispatch::event_handler::stage_instance_delete", async move {
                 event_handler.stage_instance_delete(context, event.stage_instance).await;
             });
         },
         DispatchEvent::Model(Event::ThreadCreate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::thread_create", async move {
+            task_tracker.spawn("dispatch::event_handler::thread_create", async move {
                 event_handler.thread_create(context, event.thread).await;
             });
         },
         DispatchEvent::Model(Event::ThreadUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::thread_update", async move {
+            task_tracker.spawn("dispatch::event_handler::thread_update", async move {
                 event_handler.thread_update(context, event.thread).await;
             });
         },
         DispatchEvent::Model(Event::ThreadDelete(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::thread_delete", async move {
+            task_tracker.spawn("dispatch::event_handler::thread_delete", async move {
                 event_handler.thread_delete(context, event.thread).await;
             });
         },
         DispatchEvent::Model(Event::ThreadListSync(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::thread_list_sync", async move {
+            task_tracker.spawn("dispatch::event_handler::thread_list_sync", async move {
                 event_handler.thread_list_sync(context, event).await;
             });
         },
         DispatchEvent::Model(Event::ThreadMemberUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::thread_member_update", async move {
+            task_tracker.spawn("dispatch::event_handler::thread_member_update", async move {
                 event_handler.thread_member_update(context, event.member).await;
             });
         },
         DispatchEvent::Model(Event::ThreadMembersUpdate(event)) => {
             let event_handler = Arc::clone(event_handler);
 
-            spawn_named("dispatch::event_handler::thread_members_update", async move {
+            task_tracker.spawn("dispatch::event_handler::thread_members_update", async move {
                 event_handler.thread_members_update(context, event).await;
             });
         },
@@ -62,7 +62,8 @@ pub trait EventHandler: Send + Sync {
 
     /// Dispatched when a user is banned from a guild.
     ///
-    /// Provides the guild's id and the banned user's data.
+    /// Provides the guild's id and the banned user's data. The reason of the
+    /// ban can be retrieved with [`GuildId::find_ban_audit_entry`].
     async fn guild_ban_addition(&self, _ctx: Context, _guild_id: GuildId, _banned_user: User) {}
 
     /// Dispatched when a user's ban is lifted from a guild.
@@ -84,6 +85,20 @@ pub trait EventHandler: Send + Sync {
     #[cfg(not(feature = "cache"))]
     async fn guild_create(&self, _ctx: Context, _guild: Guild) {}
 
+    /// Dispatched when a guild becomes available, right before
+    /// [`Self::guild_create`].
+    ///
+    /// Provides the guild's data and whether it was unavailable before, such
+    /// as the guilds received after the [`Ready`] on startup or after an
+    /// outage, as opposed to guilds the current user just joined.
+    ///
+    /// To wait until all guilds are available, refer to
+    /// [`Client::wait_until_cache_ready`].
+    ///
+    /// [`Client::wait_until_cache_ready`]: super::Client::wait_until_cache_ready
+    #[cfg(feature = "cache")]
+    async fn guild_available(&self, _ctx: Context, _guild: &Guild, _was_unavailable: bool) {}
+
     /// Dispatched when a guild is deleted.
     ///
     /// Provides the partial data of the guild sent by discord,
@@ -247,6 +262,11 @@ pub trait EventHandler: Send + Sync {
     /// Dispatched when the guild is updated.
     ///
     /// Provides the guild's old full data (if available) and the new, albeit partial data.
+    ///
+    /// To find out which fields changed, compare the two with
+    /// [`GuildUpdateDiff::new`].
+    ///
+    /// [`GuildUpdateDiff::new`]: crate::model::event::GuildUpdateDiff::new
     #[cfg(feature = "cache")]
     async fn guild_update(
         &self,
@@ -367,6 +387,12 @@ pub trait EventHandler: Send + Sync {
     /// Provides the context of the shard and the event information about the update.
     async fn shard_stage_update(&self, _ctx: Context, _: ShardStageUpdateEvent) {}
 
+    /// Dispatched when a shard recovers from a problem with its connection,
+    /// such as a stall detected by the [`StallWatchdog`].
+    ///
+    /// [`StallWatchdog`]: crate::client::bridge::gateway::StallWatchdog
+    async fn shard_lifecycle(&self, _ctx: Context, _: ShardLifecycleEvent) {}
+
     /// Dispatched when a user starts typing.
     async fn typing_start(&self, _ctx: Context, _: TypingStartEvent) {}
 
@@ -413,6 +439,11 @@ pub trait EventHandler: Send + Sync {
     #[cfg(not(feature = "cache"))]
     async fn voice_state_update(&self, _ctx: Context, _: Option<GuildId>, _: VoiceState) {}
 
+    /// Dispatched when the status of a voice channel is set or cleared.
+    ///
+    /// Provides the channel's id, the guild's id and the new status.
+    async fn voice_channel_status_update(&self, _ctx: Context, _: VoiceChannelStatusUpdateEvent) {}
+
     /// Dispatched when a guild's webhook is updated.
     ///
     /// Provides the guild's id and the channel's id the webhook belongs in.
@@ -455,6 +486,24 @@ pub trait EventHandler: Send + Sync {
     ) {
     }
 
+    /// Dispatched when a user subscribes to a SKU or is otherwise granted an entitlement.
+    ///
+    /// Provides the created entitlement.
+    #[cfg(feature = "unstable_discord_api")]
+    async fn entitlement_create(&self, _ctx: Context, _entitlement: Entitlement) {}
+
+    /// Dispatched when an entitlement is updated, e.g. when a subscription renews.
+    ///
+    /// Provides the updated entitlement.
+    #[cfg(feature = "unstable_discord_api")]
+    async fn entitlement_update(&self, _ctx: Context, _entitlement: Entitlement) {}
+
+    /// Dispatched when an entitlement is deleted, e.g. when it was refunded.
+    ///
+    /// Provides the deleted entitlement.
+    #[cfg(feature = "unstable_discord_api")]
+    async fn entitlement_delete(&self, _ctx: Context, _entitlement: Entitlement) {}
+
     /// Dispatched when an application command is created.
     ///
     /// Provides the created application command.
@@ -553,4 +602,11 @@ pub trait EventHandler: Send + Sync {
 pub trait RawEventHandler: Send + Sync {
     /// Dispatched when any event occurs
     async fn raw_event(&self, _ctx: Context, _ev: Event) {}
+
+    /// Called with every payload received from the gateway, before it is
+    /// deserialized into an [`Event`], such as to record the fields serenity
+    /// does not know of.
+    ///
+    /// This is called by the shard runner itself, so it must not block.
+    fn raw_payload(&self, _payload: &Value) {}
 }
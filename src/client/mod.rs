@@ -20,35 +20,41 @@ pub mod bridge;
 
 mod context;
 #[cfg(feature = "gateway")]
-mod dispatch;
+pub(crate) mod dispatch;
 mod error;
 #[cfg(feature = "gateway")]
 mod event_handler;
 #[cfg(feature = "gateway")]
 mod extras;
+#[cfg(feature = "gateway")]
+mod task_tracker;
 
-#[cfg(all(feature = "cache", feature = "gateway"))]
+#[cfg(feature = "gateway")]
 use std::time::Duration;
 use std::{
     boxed::Box,
+    collections::HashSet,
     future::Future,
     pin::Pin,
     sync::Arc,
     task::{Context as FutContext, Poll},
 };
 
-use futures::future::BoxFuture;
+use futures::future::{self, BoxFuture, Either};
 use tokio::sync::{Mutex, RwLock};
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, error, info, instrument, warn};
 use typemap_rev::{TypeMap, TypeMapKey};
 
 #[cfg(feature = "gateway")]
 use self::bridge::gateway::{
+    EventTypeFilter,
     GatewayIntents,
     ShardManager,
     ShardManagerError,
     ShardManagerMonitor,
+    ShardId,
     ShardManagerOptions,
+    StallWatchdog,
 };
 #[cfg(feature = "voice")]
 use self::bridge::voice::VoiceGatewayManager;
@@ -57,19 +63,27 @@ pub use self::{context::Context, error::Error as ClientError};
 pub use self::{
     event_handler::{EventHandler, RawEventHandler},
     extras::Extras,
+    task_tracker::{ShutdownReport, TaskTracker},
 };
 #[cfg(feature = "gateway")]
-use super::gateway::GatewayError;
+use super::gateway::{GatewayError, ReconnectType};
 #[cfg(feature = "cache")]
 pub use crate::cache::Cache;
 #[cfg(feature = "cache")]
 use crate::cache::Settings as CacheSettings;
+#[cfg(feature = "cache")]
+use crate::cache::CacheSweeper;
+#[cfg(feature = "gateway")]
+use crate::constants::{ApiVersion, DEFAULT_MAX_PAYLOAD_SIZE};
 #[cfg(feature = "framework")]
 use crate::framework::Framework;
 use crate::http::Http;
 use crate::internal::prelude::*;
 #[cfg(feature = "unstable_discord_api")]
 use crate::model::id::ApplicationId;
+use crate::model::event::EventType;
+#[cfg(feature = "cache")]
+use crate::model::id::GuildId;
 use crate::model::id::UserId;
 pub use crate::CacheAndHttp;
 
@@ -93,6 +107,14 @@ pub struct ClientBuilder<'a> {
     voice_manager: Option<Arc<dyn VoiceGatewayManager + Send + Sync + 'static>>,
     event_handler: Option<Arc<dyn EventHandler>>,
     raw_event_handler: Option<Arc<dyn RawEventHandler>>,
+    event_type_filter: Option<EventTypeFilter>,
+    shard_data_init: Option<Arc<dyn Fn(ShardId) -> TypeMap + Send + Sync>>,
+    max_payload_size: usize,
+    gateway_url: Option<String>,
+    stall_timeout: Option<Duration>,
+    stall_reconnect: ReconnectType,
+    shutdown_signal: Option<BoxFuture<'static, ()>>,
+    shutdown_timeout: Duration,
 }
 
 #[cfg(feature = "gateway")]
@@ -115,6 +137,14 @@ impl<'a> ClientBuilder<'a> {
             voice_manager: None,
             event_handler: None,
             raw_event_handler: None,
+            event_type_filter: None,
+            shard_data_init: None,
+            max_payload_size: DEFAULT_MAX_PAYLOAD_SIZE,
+            gateway_url: None,
+            stall_timeout: None,
+            stall_reconnect: ReconnectType::Resume,
+            shutdown_signal: None,
+            shutdown_timeout: Duration::from_secs(10),
         }
     }
 
@@ -153,8 +183,8 @@ impl<'a> ClientBuilder<'a> {
     /// Gets the current token used for the [`Http`] client.
     ///
     /// [`Http`]: crate::http::Http
-    pub fn get_token(&self) -> &str {
-        &self.http.token
+    pub fn get_token(&self) -> String {
+        self.http.token()
     }
 
     /// Sets the application id.
@@ -162,7 +192,7 @@ impl<'a> ClientBuilder<'a> {
     pub fn application_id(mut self, application_id: u64) -> Self {
         self.application_id = Some(ApplicationId(application_id));
 
-        self.http = Http::new_with_token_application_id(self.get_token(), application_id);
+        self.http = Http::new_with_token_application_id(&self.get_token(), application_id);
 
         self
     }
@@ -390,6 +420,230 @@ impl<'a> ClientBuilder<'a> {
     pub fn get_raw_event_handler(&self) -> Option<Arc<dyn RawEventHandler>> {
         self.raw_event_handler.clone()
     }
+
+    /// Sets the gateway events that shards skip instead of deserializing and
+    /// dispatching them, e.g. high-volume events such as
+    /// [`EventType::PresenceUpdate`] or [`EventType::TypingStart`] that are
+    /// never handled.
+    ///
+    /// Skipped events are not seen by the cache, the framework and the event
+    /// handler. They are still passed to the raw event handler, if one is set.
+    /// Events a running collector waits for are never skipped.
+    ///
+    /// This replaces a filter set via [`Self::allow_events`]. Refer to
+    /// [`EventTypeFilter`] for more info.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// use serenity::model::event::EventType;
+    /// use serenity::Client;
+    ///
+    /// let events = vec![EventType::PresenceUpdate, EventType::TypingStart];
+    /// let client = Client::builder("token").filter_events(events.into_iter().collect()).await?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn filter_events(mut self, event_types: HashSet<EventType>) -> Self {
+        self.event_type_filter = Some(EventTypeFilter::Deny(event_types));
+
+        self
+    }
+
+    /// Sets the only gateway events that shards deserialize and dispatch,
+    /// skipping all others.
+    ///
+    /// This replaces a filter set via [`Self::filter_events`], which describes
+    /// how skipped events are handled.
+    pub fn allow_events(mut self, event_types: HashSet<EventType>) -> Self {
+        self.event_type_filter = Some(EventTypeFilter::Allow(event_types));
+
+        self
+    }
+
+    /// Gets the event type filter, if set. See [`Self::filter_events`] for more
+    /// info.
+    pub fn get_event_type_filter(&self) -> Option<&EventTypeFilter> {
+        self.event_type_filter.as_ref()
+    }
+
+    /// Sets the callback creating the [`TypeMap`] of each shard, available in
+    /// [`Context::shard_data`].
+    ///
+    /// The callback is called with the Id of the shard every time a shard
+    /// boots, including when it is restarted. Reconnects of a running shard
+    /// keep their map.
+    ///
+    /// # Examples
+    ///
+    /// Give every shard its own label for metrics:
+    ///
+    /// ```rust,no_run
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// use serenity::prelude::*;
+    ///
+    /// struct ShardLabel;
+    ///
+    /// impl TypeMapKey for ShardLabel {
+    ///     type Value = String;
+    /// }
+    ///
+    /// let client = Client::builder("token")
+    ///     .shard_data_init(|shard_id| {
+    ///         let mut data = TypeMap::new();
+    ///         data.insert::<ShardLabel>(format!("shard-{}", shard_id));
+    ///         data
+    ///     })
+    ///     .await?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn shard_data_init<F>(mut self, init: F) -> Self
+    where
+        F: Fn(ShardId) -> TypeMap + Send + Sync + 'static,
+    {
+        self.shard_data_init = Some(Arc::new(init));
+
+        self
+    }
+
+    /// Sets the maximum size of a payload received from the gateway, in
+    /// bytes. Defaults to [`DEFAULT_MAX_PAYLOAD_SIZE`].
+    ///
+    /// The limit applies to a payload once it was reassembled from its
+    /// websocket frames, and again once it was decompressed, guarding against
+    /// payloads which would take up excessive memory. A shard receiving a
+    /// larger payload drops the connection and identifies anew.
+    ///
+    /// [`DEFAULT_MAX_PAYLOAD_SIZE`]: crate::constants::DEFAULT_MAX_PAYLOAD_SIZE
+    pub fn max_payload_size(mut self, max_payload_size: usize) -> Self {
+        self.max_payload_size = max_payload_size;
+
+        self
+    }
+
+    /// Sets the URL of the gateway the shards connect to, instead of the one
+    /// retrieved via [`Http::get_gateway`].
+    ///
+    /// Shards keep connecting to this URL when reconnecting, including to
+    /// resume a session. This is mainly intended for a local gateway mock or
+    /// a compatibility proxy, such as in integration tests.
+    pub fn gateway_url(mut self, url: impl Into<String>) -> Self {
+        self.gateway_url = Some(url.into());
+
+        self
+    }
+
+    /// Sets the version of the API used both for REST requests and to
+    /// connect to the gateway. Defaults to the version the library was built
+    /// against.
+    ///
+    /// A version number given at runtime, such as read from a configuration
+    /// file, can be checked for support with [`ApiVersion::from_num`].
+    pub fn api_version(mut self, version: ApiVersion) -> Self {
+        self.http.api_version = version;
+
+        self
+    }
+
+    /// Reconnects a shard once it received no gateway frame for `timeout`,
+    /// such as when its connection stalled without being closed. Disabled by
+    /// default.
+    ///
+    /// The reconnect is announced to [`EventHandler::shard_lifecycle`] with a
+    /// [`ShardLifecycleEvent::Stalled`].
+    ///
+    /// Discord acknowledges every heartbeat, so the timeout should be well
+    /// above the heartbeat interval, which is usually around 41 seconds.
+    ///
+    /// [`ShardLifecycleEvent::Stalled`]: crate::client::bridge::gateway::event::ShardLifecycleEvent::Stalled
+    pub fn gateway_stall_timeout(mut self, timeout: Duration) -> Self {
+        self.stall_timeout = Some(timeout);
+
+        self
+    }
+
+    /// Sets how a shard reconnects after its connection stalled, refer to
+    /// [`Self::gateway_stall_timeout`].
+    ///
+    /// Defaults to [`ReconnectType::Resume`], which falls back to identifying
+    /// anew if the shard has no session to resume.
+    pub fn stall_reconnect_type(mut self, reconnect: ReconnectType) -> Self {
+        self.stall_reconnect = reconnect;
+
+        self
+    }
+
+    /// Shuts the client down gracefully once `signal` resolves, such as when a
+    /// service receives a termination signal or its own shutdown is initiated.
+    ///
+    /// All shards are shut down, after which the tasks dispatching events are
+    /// awaited for up to the [shutdown timeout], as with
+    /// [`Client::shutdown_gracefully`]. [`Client::start`] and its siblings
+    /// then return `Ok`, unless starting the shards failed beforehand.
+    ///
+    /// The library does not handle Ctrl+C by itself, which terminates the
+    /// process right away by default. To shut down gracefully on Ctrl+C
+    /// instead, pass a future awaiting [`tokio::signal::ctrl_c`], as shown in
+    /// [example 18].
+    ///
+    /// The signal is only awaited by the first call to start the client.
+    ///
+    /// # Examples
+    ///
+    /// Shutting down once the rest of the service asks to:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// # use serenity::prelude::EventHandler;
+    /// use serenity::Client;
+    /// use tokio::sync::oneshot;
+    ///
+    /// struct Handler;
+    ///
+    /// impl EventHandler for Handler {}
+    ///
+    /// # async fn run() -> Result<(), Box<dyn Error>> {
+    /// let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    ///
+    /// let token = std::env::var("DISCORD_TOKEN")?;
+    /// let mut client = Client::builder(&token)
+    ///     .event_handler(Handler)
+    ///     .shutdown_signal(async {
+    ///         // Also shut down if the sender is dropped.
+    ///         let _ = shutdown_rx.await;
+    ///     })
+    ///     .await?;
+    ///
+    /// // Hand `shutdown_tx` to the rest of the service.
+    /// # drop(shutdown_tx);
+    ///
+    /// client.start().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [shutdown timeout]: Self::shutdown_timeout
+    /// [`tokio::signal::ctrl_c`]: https://docs.rs/tokio/1/tokio/signal/fn.ctrl_c.html
+    /// [example 18]: https://github.com/serenity-rs/serenity/tree/current/examples/e18_graceful_shutdown
+    pub fn shutdown_signal<F>(mut self, signal: F) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.shutdown_signal = Some(Box::pin(signal));
+
+        self
+    }
+
+    /// Sets how long to wait for the tasks dispatching events to complete
+    /// when shutting down due to the [`Self::shutdown_signal`]. Defaults to
+    /// 10 seconds.
+    pub fn shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+
+        self
+    }
 }
 
 #[cfg(feature = "gateway")]
@@ -407,7 +661,16 @@ impl<'a> Future for ClientBuilder<'a> {
                 If you don't want to use the command framework, disable default features and specify all features you want to use.");
             let event_handler = self.event_handler.take();
             let raw_event_handler = self.raw_event_handler.take();
+            let event_type_filter = self.event_type_filter.take().map(Arc::new);
+            let shard_data_init = self.shard_data_init.take();
+            let max_payload_size = self.max_payload_size;
+            let gateway_url = self.gateway_url.take();
+            let stall_watchdog = self
+                .stall_timeout
+                .map(|timeout| StallWatchdog::new(timeout).reconnect(self.stall_reconnect));
             let intents = self.intents;
+            let shutdown_signal = self.shutdown_signal.take();
+            let shutdown_timeout = self.shutdown_timeout;
             let http = Arc::new(std::mem::take(&mut self.http));
 
             #[cfg(feature = "unstable_discord_api")]
@@ -418,16 +681,30 @@ impl<'a> Future for ClientBuilder<'a> {
             #[cfg(feature = "voice")]
             let voice_manager = self.voice_manager.take();
 
+            #[cfg(feature = "cache")]
+            let cache_settings = self.cache_settings.take().unwrap();
+            #[cfg(feature = "cache")]
+            let sweep_interval = cache_settings.has_ttl().then(|| cache_settings.sweep_interval);
+
             let cache_and_http = Arc::new(CacheAndHttp {
                 #[cfg(feature = "cache")]
-                cache: Arc::new(Cache::new_with_settings(self.cache_settings.take().unwrap())),
+                cache: Arc::new(Cache::new_with_settings(cache_settings)),
                 #[cfg(feature = "cache")]
                 update_cache_timeout: self.timeout.take(),
                 http: Arc::clone(&http),
             });
 
             self.fut = Some(Box::pin(async move {
-                let url = Arc::new(Mutex::new(http.get_gateway().await?.url));
+                let url = match gateway_url {
+                    Some(url) => url,
+                    None => http.get_gateway().await?.url,
+                };
+                let url = Arc::new(Mutex::new(url));
+                let task_tracker = TaskTracker::default();
+
+                #[cfg(feature = "cache")]
+                let cache_sweeper = sweep_interval
+                    .map(|interval| CacheSweeper::spawn(&cache_and_http.cache, interval));
 
                 let (shard_manager, shard_manager_worker) = {
                     ShardManager::new(ShardManagerOptions {
@@ -444,6 +721,11 @@ impl<'a> Future for ClientBuilder<'a> {
                         ws_url: &url,
                         cache_and_http: &cache_and_http,
                         intents,
+                        event_type_filter: &event_type_filter,
+                        shard_data_init: &shard_data_init,
+                        max_payload_size,
+                        stall_watchdog,
+                        task_tracker: &task_tracker,
                     })
                     .await
                 };
@@ -453,9 +735,14 @@ impl<'a> Future for ClientBuilder<'a> {
                     data,
                     shard_manager,
                     shard_manager_worker,
+                    task_tracker,
+                    shutdown_signal: shutdown_signal.map(Mutex::new),
+                    shutdown_timeout,
                     #[cfg(feature = "voice")]
                     voice_manager,
                     cache_and_http,
+                    #[cfg(feature = "cache")]
+                    cache_sweeper,
                 })
             }))
         }
@@ -676,6 +963,47 @@ pub struct Client {
     /// ```
     pub shard_manager: Arc<Mutex<ShardManager>>,
     shard_manager_worker: ShardManagerMonitor,
+    /// The tracker of the tasks dispatching events to the [`EventHandler`]
+    /// and the framework.
+    ///
+    /// Refer to [`Self::shutdown_gracefully`] for awaiting them on shutdown.
+    /// A clone of this can be used to do so while the client is running, such
+    /// as alongside the [`Self::shard_manager`]:
+    ///
+    /// ```rust,no_run
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::time::Duration;
+    ///
+    /// use serenity::client::{Client, EventHandler};
+    ///
+    /// struct Handler;
+    ///
+    /// impl EventHandler for Handler {}
+    ///
+    /// let token = std::env::var("DISCORD_TOKEN")?;
+    /// let mut client = Client::builder(&token).event_handler(Handler).await?;
+    ///
+    /// let shard_manager = client.shard_manager.clone();
+    /// let task_tracker = client.task_tracker.clone();
+    ///
+    /// tokio::spawn(async move {
+    ///     tokio::time::sleep(Duration::from_secs(60)).await;
+    ///
+    ///     task_tracker.close();
+    ///     shard_manager.lock().await.shutdown_all().await;
+    /// });
+    ///
+    /// client.start().await?;
+    ///
+    /// let report = client.task_tracker.wait(Duration::from_secs(10)).await;
+    /// println!("{} handlers completed, {} abandoned", report.completed, report.abandoned);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub task_tracker: TaskTracker,
+    // Only ever taken out again, the mutex just keeps the client `Sync`.
+    shutdown_signal: Option<Mutex<BoxFuture<'static, ()>>>,
+    shutdown_timeout: Duration,
     /// The voice manager for the client.
     ///
     /// This is an ergonomic structure for interfacing over shards' voice
@@ -693,6 +1021,11 @@ pub struct Client {
     /// A container for an optional cache and HTTP client.
     /// It also contains the cache update timeout.
     pub cache_and_http: Arc<CacheAndHttp>,
+    /// Evicts the expired entries of the cache until the client is dropped,
+    /// if any time-to-live is set in its settings.
+    #[cfg(feature = "cache")]
+    #[allow(dead_code)]
+    cache_sweeper: Option<CacheSweeper>,
 }
 
 impl Client {
@@ -700,6 +1033,61 @@ impl Client {
         ClientBuilder::new(token)
     }
 
+    /// Returns a future which waits for up to `timeout` until all guilds were
+    /// received after the [`Ready`]s of all shards, such as to not serve
+    /// requests before the cache is warm.
+    ///
+    /// Resolves to the IDs of the guilds which are still unavailable, which is
+    /// empty if all guilds were received in time. Refer to
+    /// [`Cache::wait_until_ready`].
+    ///
+    /// As starting the client runs until it shuts down, the future should be
+    /// created beforehand and awaited in another task.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// # use std::time::Duration;
+    /// # use serenity::prelude::EventHandler;
+    /// use serenity::Client;
+    ///
+    /// struct Handler;
+    ///
+    /// impl EventHandler for Handler {}
+    ///
+    /// # async fn run() -> Result<(), Box<dyn Error>> {
+    /// let token = std::env::var("DISCORD_TOKEN")?;
+    /// let mut client = Client::builder(&token).event_handler(Handler).await?;
+    ///
+    /// let cache_ready = client.wait_until_cache_ready(Duration::from_secs(60));
+    /// tokio::spawn(async move {
+    ///     let unavailable = cache_ready.await;
+    ///
+    ///     if !unavailable.is_empty() {
+    ///         println!("{} guilds are still unavailable", unavailable.len());
+    ///     }
+    ///
+    ///     // Start serving requests.
+    /// });
+    ///
+    /// client.start().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Ready`]: crate::model::gateway::Ready
+    /// [`Cache::wait_until_ready`]: crate::cache::Cache::wait_until_ready
+    #[cfg(feature = "cache")]
+    pub fn wait_until_cache_ready(
+        &self,
+        timeout: Duration,
+    ) -> impl Future<Output = Vec<GuildId>> + Send + 'static {
+        let cache = Arc::clone(&self.cache_and_http.cache);
+
+        async move { cache.wait_until_ready(timeout).await }
+    }
+
     /// Establish the connection and start listening for events.
     ///
     /// This will start receiving events in a loop and start dispatching the
@@ -960,6 +1348,66 @@ impl Client {
         self.start_connection([range[0], range[1], total_shards]).await
     }
 
+    /// Shuts down all shards, then waits for up to `timeout` for the tasks
+    /// dispatching events to the [`EventHandler`] and the framework to
+    /// complete.
+    ///
+    /// New events are no longer dispatched once this is called, and the
+    /// returned [`ShutdownReport`] tells how many of the running tasks
+    /// completed in time. The remaining ones are abandoned, and keep running
+    /// until the runtime shuts down.
+    ///
+    /// As [`Self::start`] borrows the client mutably, this can be called once
+    /// it returned. To shut down from another task while the client is
+    /// running, use clones of the [`Self::shard_manager`] and the
+    /// [`Self::task_tracker`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// # use serenity::prelude::EventHandler;
+    /// use std::time::Duration;
+    ///
+    /// use serenity::Client;
+    ///
+    /// struct Handler;
+    ///
+    /// impl EventHandler for Handler {}
+    ///
+    /// # async fn run() -> Result<(), Box<dyn Error>> {
+    /// let token = std::env::var("DISCORD_TOKEN")?;
+    /// let mut client = Client::builder(&token).event_handler(Handler).await?;
+    ///
+    /// if let Err(why) = client.start().await {
+    ///     println!("Err with client: {:?}", why);
+    /// }
+    ///
+    /// let report = client.shutdown_gracefully(Duration::from_secs(10)).await;
+    /// println!("{} handlers abandoned", report.abandoned);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn shutdown_gracefully(&self, timeout: Duration) -> ShutdownReport {
+        self.task_tracker.close();
+        self.shard_manager.lock().await.shutdown_all().await;
+
+        self.wait_for_tasks(timeout).await
+    }
+
+    /// Waits for the tasks of the closed [`Self::task_tracker`], warning about
+    /// those abandoned after the `timeout`.
+    async fn wait_for_tasks(&self, timeout: Duration) -> ShutdownReport {
+        let report = self.task_tracker.wait(timeout).await;
+
+        if report.abandoned > 0 {
+            warn!("Abandoned {} event handler tasks on shutdown", report.abandoned);
+        }
+
+        report
+    }
+
     /// Shard data layout is:
     /// 0: first shard number to initialize
     /// 1: shard number to initialize up to and including
@@ -999,7 +1447,44 @@ impl Client {
             }
         }
 
-        if let Err(why) = self.shard_manager_worker.run().await {
+        let result = match self.shutdown_signal.take().map(Mutex::into_inner) {
+            Some(signal) => {
+                let task_tracker = &self.task_tracker;
+                let shard_manager = &self.shard_manager;
+                let mut signalled = false;
+
+                // The monitor keeps running while the shards shut down, as it
+                // forwards their confirmations to the shard manager.
+                let result = {
+                    let shutdown = async {
+                        signal.await;
+                        signalled = true;
+
+                        info!("Received the shutdown signal");
+                        task_tracker.close();
+                        shard_manager.lock().await.shutdown_all().await;
+                    };
+
+                    let run = self.shard_manager_worker.run();
+                    tokio::pin!(run, shutdown);
+
+                    match future::select(&mut run, &mut shutdown).await {
+                        Either::Left((result, _)) => result,
+                        Either::Right(((), _)) => run.await,
+                    }
+                };
+
+                // The shards were already shut down along with the monitor.
+                if signalled {
+                    self.wait_for_tasks(self.shutdown_timeout).await;
+                }
+
+                result
+            },
+            None => self.shard_manager_worker.run().await,
+        };
+
+        if let Err(why) = result {
             let err = match why {
                 ShardManagerError::DisallowedGatewayIntents => {
                     GatewayError::DisallowedGatewayIntents
@@ -1094,3 +1579,135 @@ pub fn parse_token(token: impl AsRef<str>) -> Option<TokenComponents> {
         creation_time: timestamp,
     })
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use futures::channel::oneshot::{self, Sender};
+    use tokio::sync::Mutex;
+
+    use super::{Client, ClientBuilder, Context, EventHandler, ShutdownReport};
+    use crate::client::bridge::gateway::mock_gateway::MockGateway;
+    #[cfg(feature = "framework")]
+    use crate::framework::Framework;
+    #[cfg(feature = "framework")]
+    use crate::model::channel::Message;
+    use crate::model::gateway::Ready;
+
+    #[cfg(feature = "framework")]
+    struct NoFramework;
+
+    #[cfg(feature = "framework")]
+    #[async_trait::async_trait]
+    impl Framework for NoFramework {
+        async fn dispatch(&self, _: Context, _: Message) {}
+    }
+
+    /// Signals the shutdown on ready, then takes a while to finish.
+    struct SlowHandler {
+        signal: Mutex<Option<Sender<()>>>,
+        finished: Arc<AtomicBool>,
+    }
+
+    #[async_trait::async_trait]
+    impl EventHandler for SlowHandler {
+        async fn ready(&self, _: Context, _: Ready) {
+            if let Some(signal) = self.signal.lock().await.take() {
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = signal.send(());
+            }
+
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            self.finished.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Returns a handler signalling once ready, and whether it finished.
+    fn slow_handler() -> (SlowHandler, oneshot::Receiver<()>, Arc<AtomicBool>) {
+        let (signal_tx, signal_rx) = oneshot::channel();
+        let finished = Arc::new(AtomicBool::new(false));
+
+        let handler = SlowHandler {
+            signal: Mutex::new(Some(signal_tx)),
+            finished: Arc::clone(&finished),
+        };
+
+        (handler, signal_rx, finished)
+    }
+
+    /// Builds a client connecting to the `gateway`.
+    fn builder<'a>(gateway: &MockGateway, handler: SlowHandler) -> ClientBuilder<'a> {
+        let builder = Client::builder("token").event_handler(handler).gateway_url(gateway.url());
+        #[cfg(feature = "framework")]
+        let builder = builder.framework(NoFramework);
+        #[cfg(feature = "unstable_discord_api")]
+        let builder = builder.application_id(1);
+
+        builder
+    }
+
+    #[tokio::test(start_paused = true)]
+    #[allow(clippy::unwrap_used)]
+    async fn test_shutdown_signal() {
+        let gateway = MockGateway::start().await;
+        let (handler, signal_rx, finished) = slow_handler();
+        let mut client = builder(&gateway, handler)
+            .shutdown_signal(async {
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = signal_rx.await;
+            })
+            .shutdown_timeout(Duration::from_secs(120))
+            .await
+            .unwrap();
+
+        // The client returns once the shards were shut down, and the handler
+        // still running got to finish.
+        client.start().await.unwrap();
+        assert!(finished.load(Ordering::SeqCst));
+        assert!(client.shard_manager.lock().await.runners.lock().await.is_empty());
+        assert_eq!(gateway.closed(), vec![(0, Some(1000))]);
+    }
+
+    /// Starts a client, shuts its shards down from another task once ready,
+    /// and then shuts the client down gracefully, waiting up to the `timeout`.
+    ///
+    /// Returns the report and whether the ready handler finished.
+    #[allow(clippy::unwrap_used)]
+    async fn shut_down_gracefully(timeout: Duration) -> (ShutdownReport, bool) {
+        let gateway = MockGateway::start().await;
+        let (handler, signal_rx, finished) = slow_handler();
+        let mut client = builder(&gateway, handler).await.unwrap();
+
+        let shard_manager = Arc::clone(&client.shard_manager);
+        tokio::spawn(async move {
+            if signal_rx.await.is_ok() {
+                shard_manager.lock().await.shutdown_all().await;
+            }
+        });
+
+        client.start().await.unwrap();
+        let report = client.shutdown_gracefully(timeout).await;
+
+        (report, finished.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_shutdown_gracefully() {
+        let (report, finished) = shut_down_gracefully(Duration::from_secs(120)).await;
+
+        assert!(finished);
+        assert!(report.completed >= 1);
+        assert_eq!(report.abandoned, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_shutdown_gracefully_timeout() {
+        let (report, finished) = shut_down_gracefully(Duration::from_secs(1)).await;
+
+        assert!(!finished);
+        assert_eq!(report.abandoned, 1);
+    }
+}
@@ -0,0 +1,206 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Notify;
+use tokio::time::timeout;
+use tracing::debug;
+
+use crate::internal::tokio::spawn_named;
+
+/// Keeps track of the tasks spawned to dispatch events to the
+/// [`EventHandler`] and the framework, so that they can be awaited on
+/// shutdown.
+///
+/// Only the number of running tasks is kept, so a long-running bot does not
+/// accumulate anything for the tasks that already completed.
+///
+/// Refer to [`Client::shutdown_gracefully`] for how this is used.
+///
+/// [`EventHandler`]: super::EventHandler
+/// [`Client::shutdown_gracefully`]: super::Client::shutdown_gracefully
+#[derive(Clone, Debug, Default)]
+pub struct TaskTracker {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    closed: AtomicBool,
+    running: AtomicUsize,
+    idle: Notify,
+}
+
+/// The outcome of awaiting the tasks of a [`TaskTracker`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ShutdownReport {
+    /// The number of tasks which completed while being awaited.
+    pub completed: usize,
+    /// The number of tasks which were still running once the timeout elapsed.
+    ///
+    /// These keep running in the background until the runtime shuts down.
+    pub abandoned: usize,
+}
+
+impl TaskTracker {
+    /// Spawns a tracked task, unless the tracker has been [closed].
+    ///
+    /// Returns whether the task was spawned.
+    ///
+    /// [closed]: Self::close
+    pub(crate) fn spawn<F>(&self, name: &str, future: F) -> bool
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        // Counting the task before checking whether the tracker is closed
+        // ensures that `wait` cannot miss a task spawned while closing.
+        self.inner.running.fetch_add(1, Ordering::SeqCst);
+
+        let guard = TaskGuard(Arc::clone(&self.inner));
+
+        if self.is_closed() {
+            debug!("Not spawning {} as the task tracker is closed", name);
+
+            return false;
+        }
+
+        spawn_named(name, async move {
+            // Dropped once the future completes, panics, or is cancelled.
+            let _guard = guard;
+
+            future.await;
+        });
+
+        true
+    }
+
+    /// Stops spawning new tasks, such as to stop dispatching events while
+    /// shutting down.
+    pub fn close(&self) {
+        self.inner.closed.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether the tracker has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.inner.closed.load(Ordering::SeqCst)
+    }
+
+    /// The number of tracked tasks which are still running.
+    pub fn len(&self) -> usize {
+        self.inner.running.load(Ordering::SeqCst)
+    }
+
+    /// Whether no tracked tasks are running.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Waits for up to `duration` for the running tasks to complete.
+    ///
+    /// This should be called after [closing] the tracker, as tasks spawned
+    /// meanwhile are awaited as well.
+    ///
+    /// [closing]: Self::close
+    pub async fn wait(&self, duration: Duration) -> ShutdownReport {
+        let running = self.len();
+
+        let idle = async {
+            while !self.is_empty() {
+                self.inner.idle.notified().await;
+            }
+        };
+
+        if timeout(duration, idle).await.is_ok() {
+            // Pass the notification on to other callers waiting.
+            self.inner.notify_idle();
+        }
+
+        let abandoned = self.len();
+
+        ShutdownReport {
+            completed: running.saturating_sub(abandoned),
+            abandoned,
+        }
+    }
+}
+
+impl Inner {
+    fn notify_idle(&self) {
+        #[cfg(feature = "tokio")]
+        self.idle.notify_one();
+        #[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
+        self.idle.notify();
+    }
+}
+
+/// Marks a tracked task as completed when dropped.
+struct TaskGuard(Arc<Inner>);
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        if self.0.running.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.notify_idle();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use tokio::time::sleep;
+
+    use super::{ShutdownReport, TaskTracker};
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_for_tasks() {
+        let tracker = TaskTracker::default();
+
+        for secs in 1..=3 {
+            assert!(tracker.spawn("test", sleep(Duration::from_secs(secs))));
+        }
+        assert_eq!(tracker.len(), 3);
+
+        tracker.close();
+        assert!(!tracker.spawn("test", sleep(Duration::from_secs(1))));
+        assert_eq!(tracker.len(), 3);
+
+        let report = tracker.wait(Duration::from_secs(5)).await;
+        assert_eq!(
+            report,
+            ShutdownReport {
+                completed: 3,
+                abandoned: 0,
+            }
+        );
+        assert!(tracker.is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_timeout() {
+        let tracker = TaskTracker::default();
+
+        assert!(tracker.spawn("test", sleep(Duration::from_secs(1))));
+        assert!(tracker.spawn("test", sleep(Duration::from_secs(60))));
+
+        tracker.close();
+
+        let report = tracker.wait(Duration::from_secs(5)).await;
+        assert_eq!(
+            report,
+            ShutdownReport {
+                completed: 1,
+                abandoned: 1,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_without_tasks() {
+        let tracker = TaskTracker::default();
+
+        assert_eq!(tracker.wait(Duration::from_secs(5)).await, ShutdownReport::default());
+    }
+}
@@ -11,19 +11,37 @@ use futures::{
     future::BoxFuture,
     stream::{Stream, StreamExt},
 };
-use tokio::sync::mpsc::{
-    unbounded_channel,
-    UnboundedReceiver as Receiver,
-    UnboundedSender as Sender,
-};
 #[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
 use tokio::time::{delay_for as sleep, Delay as Sleep};
 #[cfg(feature = "tokio")]
 use tokio::time::{sleep, Sleep};
 
 use crate::client::bridge::gateway::ShardMessenger;
-use crate::collector::LazyArc;
-use crate::model::interactions::message_component::MessageComponentInteraction;
+use crate::collector::{
+    add_ids,
+    channel,
+    collect_n,
+    collect_within,
+    is_id_accepted,
+    lower_collect_limit,
+    poll_recv,
+    AsyncFilter,
+    AsyncFilterFn,
+    CollectorHandle,
+    CollectorReceiver,
+    CollectorSender,
+    CollectorStats,
+    CompletionReason,
+    FilterFn,
+    FilterId,
+    IdleTimeout,
+    LazyArc,
+};
+use crate::model::interactions::message_component::{
+    ComponentType,
+    MessageComponentInteraction,
+    MessageComponentInteractionData,
+};
 
 macro_rules! impl_component_interaction_collector {
     ($($name:ident;)*) => {
@@ -39,6 +57,16 @@ macro_rules! impl_component_interaction_collector {
                     self
                 }
 
+                /// Sets whether only near misses count towards the [`Self::filter_limit`],
+                /// which match all other constraints, but are rejected by [`Self::filter`].
+                ///
+                /// By default, every interaction the filter checks counts towards the limit.
+                pub fn filter_limit_matching_only(mut self, matching_only: bool) -> Self {
+                    self.filter.as_mut().unwrap().filter_limit_matching_only = matching_only;
+
+                    self
+                }
+
                 /// Limits how many interactions can be collected.
                 ///
                 /// An interaction is considered *collected*, if the interaction
@@ -49,21 +77,66 @@ macro_rules! impl_component_interaction_collector {
                     self
                 }
 
+                /// Limits how many interactions can wait to be received from the collector.
+                /// Once reached, further interactions are dropped rather than waiting, such as
+                /// while the receiving task is busy during a spam wave.
+                ///
+                /// The dropped interactions are counted by [`ComponentInteractionCollector::dropped_count`].
+                /// By default, any number of interactions can wait.
+                /// A capacity of `0` is treated as `1`.
+                pub fn channel_capacity(mut self, capacity: usize) -> Self {
+                    self.filter.as_mut().unwrap().channel_capacity = Some(capacity);
+
+                    self
+                }
+
                 /// Sets a filter function where interactions passed to the function must
                 /// return `true`, otherwise the interaction won't be collected.
                 /// This is the last instance to pass for an interaction to count as *collected*.
                 ///
                 /// This function is intended to be an interaction filter.
-                pub fn filter<F: Fn(&Arc<MessageComponentInteraction>) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
-                    self.filter.as_mut().unwrap().filter = Some(Arc::new(function));
+                pub fn filter<F: Fn(&MessageComponentInteraction) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.filter.as_mut().unwrap().filter = Some(FilterFn::new(function));
+
+                    self
+                }
+
+                /// Sets a filter function taking the interaction wrapped in an [`Arc`].
+                ///
+                /// Prefer [`Self::filter`], which only clones the interaction into an
+                /// [`Arc`] once it is known to be collected.
+                #[deprecated(note = "use `filter`, which takes `&MessageComponentInteraction` instead")]
+                pub fn filter_arc<F: Fn(&Arc<MessageComponentInteraction>) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.filter.as_mut().unwrap().filter = Some(FilterFn::new_arc(function));
+
+                    self
+                }
+
+                /// Sets an asynchronous filter function where interactions passed to the
+                /// function must resolve to `true`, otherwise they won't be collected.
+                ///
+                /// This runs on the collector's end once all other requirements passed,
+                /// including [`Self::filter`], so it may wait on a database or HTTP request.
+                /// Rejected interactions do not count towards [`Self::collect_limit`].
+                pub fn async_filter<F: Fn(&Arc<MessageComponentInteraction>) -> BoxFuture<'static, bool> + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.async_filter = Some(AsyncFilterFn::new(function));
 
                     self
                 }
 
                 /// Sets the required author ID of an interaction.
                 /// If an interaction is not triggered by a user with this ID, it won't be received.
+                ///
+                /// Calling this multiple times accepts interactions matching any of the IDs.
                 pub fn author_id(mut self, author_id: impl Into<u64>) -> Self {
-                    self.filter.as_mut().unwrap().author_id = Some(author_id.into());
+                    add_ids(&mut self.filter.as_mut().unwrap().author_id, Some(author_id));
+
+                    self
+                }
+
+                /// Like [`Self::author_id`], but adds multiple IDs at once.
+                pub fn author_ids(mut self, author_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().author_id, author_ids);
 
                     self
                 }
@@ -78,16 +151,62 @@ macro_rules! impl_component_interaction_collector {
 
                 /// Sets the guild in which the interaction must occur.
                 /// If an interaction is not on a message with this guild ID, it won't be received.
+                ///
+                /// Calling this multiple times accepts interactions matching any of the IDs.
                 pub fn guild_id(mut self, guild_id: impl Into<u64>) -> Self {
-                    self.filter.as_mut().unwrap().guild_id = Some(guild_id.into());
+                    add_ids(&mut self.filter.as_mut().unwrap().guild_id, Some(guild_id));
+
+                    self
+                }
+
+                /// Like [`Self::guild_id`], but adds multiple IDs at once.
+                pub fn guild_ids(mut self, guild_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().guild_id, guild_ids);
 
                     self
                 }
 
                 /// Sets the channel on which the interaction must occur.
                 /// If an interaction is not on a message with this channel ID, it won't be received.
+                ///
+                /// Calling this multiple times accepts interactions matching any of the IDs.
                 pub fn channel_id(mut self, channel_id: impl Into<u64>) -> Self {
-                    self.filter.as_mut().unwrap().channel_id = Some(channel_id.into());
+                    add_ids(&mut self.filter.as_mut().unwrap().channel_id, Some(channel_id));
+
+                    self
+                }
+
+                /// Like [`Self::channel_id`], but adds multiple IDs at once.
+                pub fn channel_ids(mut self, channel_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().channel_id, channel_ids);
+
+                    self
+                }
+
+                /// Sets the custom ID the interacted component must have.
+                /// If an interaction's custom ID is not this one, it won't be received.
+                ///
+                /// Calling this multiple times accepts interactions matching any of the custom IDs.
+                pub fn custom_id(mut self, custom_id: impl Into<String>) -> Self {
+                    self.filter.as_mut().unwrap().custom_ids.get_or_insert_with(Vec::new).push(custom_id.into());
+
+                    self
+                }
+
+                /// Sets the prefix the interacted component's custom ID must start with, such as
+                /// `page:` for buttons encoding state in their custom IDs.
+                /// If an interaction's custom ID does not start with it, it won't be received.
+                pub fn custom_id_prefix(mut self, prefix: impl Into<String>) -> Self {
+                    self.filter.as_mut().unwrap().custom_id_prefix = Some(prefix.into());
+
+                    self
+                }
+
+                /// Sets the type of component which must be interacted with, such as
+                /// [`ComponentType::SelectMenu`].
+                /// If an interaction is on another type of component, it won't be received.
+                pub fn component_type(mut self, component_type: ComponentType) -> Self {
+                    self.filter.as_mut().unwrap().component_type = Some(component_type);
 
                     self
                 }
@@ -99,6 +218,23 @@ macro_rules! impl_component_interaction_collector {
 
                     self
                 }
+
+                /// Sets a `duration` after which the collector stops if it has not
+                /// collected an interaction meanwhile, restarting whenever one is collected.
+                ///
+                /// This can be combined with [`Self::timeout`], stopping the collector
+                /// once either of them elapsed.
+                pub fn idle_timeout(mut self, duration: Duration) -> Self {
+                    self.idle_timeout = Some(duration);
+
+                    self
+                }
+
+                /// Returns a handle to the statistics of the collector, which stays
+                /// usable once this is consumed, such as by awaiting it.
+                pub fn stats(&self) -> CollectorStats {
+                    self.stats.clone()
+                }
             }
         )*
     }
@@ -107,20 +243,23 @@ macro_rules! impl_component_interaction_collector {
 /// Filters events on the shard's end and sends them to the collector.
 #[derive(Clone, Debug)]
 pub struct ComponentInteractionFilter {
-    filtered: u32,
-    collected: u32,
+    id: FilterId,
+    stats: CollectorStats,
     options: FilterOptions,
-    sender: Sender<Arc<MessageComponentInteraction>>,
+    sender: CollectorSender<Arc<MessageComponentInteraction>>,
 }
 
 impl ComponentInteractionFilter {
     /// Creates a new filter
-    fn new(options: FilterOptions) -> (Self, Receiver<Arc<MessageComponentInteraction>>) {
-        let (sender, receiver) = unbounded_channel();
+    fn new(
+        options: FilterOptions,
+        stats: CollectorStats,
+    ) -> (Self, CollectorReceiver<Arc<MessageComponentInteraction>>) {
+        let (sender, receiver) = channel(options.channel_capacity);
 
         let filter = Self {
-            filtered: 0,
-            collected: 0,
+            id: FilterId::next(),
+            stats,
             sender,
             options,
         };
@@ -128,21 +267,30 @@ impl ComponentInteractionFilter {
         (filter, receiver)
     }
 
+    /// The ID of the filter, identifying it on the shard runner.
+    pub fn id(&self) -> FilterId {
+        self.id
+    }
+
+    /// Whether the collector receiving from this filter was dropped.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
     /// Sends an `interaction` to the consuming collector if the `interaction` conforms
     /// to the constraints and the limits are not reached yet.
     pub(crate) fn send_interaction(
         &mut self,
         interaction: &mut LazyArc<'_, MessageComponentInteraction>,
     ) -> bool {
-        if self.is_passing_constraints(interaction) {
-            self.collected += 1;
+        let is_matching = self.is_passing_constraints(interaction);
+        let is_passing = is_matching && self.is_passing_filter(interaction);
 
-            if self.sender.send(interaction.as_arc()).is_err() {
-                return false;
-            }
+        if is_passing && !self.sender.send(interaction.as_arc(), &self.stats) {
+            return false;
         }
 
-        self.filtered += 1;
+        self.stats.add_checked(self.options.filter_limit_matching_only, is_matching && !is_passing);
 
         self.is_within_limits() && !self.sender.is_closed()
     }
@@ -150,46 +298,70 @@ impl ComponentInteractionFilter {
     /// Checks if the `interaction` passes set constraints.
     /// Constraints are optional, as it is possible to limit interactions to
     /// be sent by a specific author or in a specific guild.
-    fn is_passing_constraints(
+    fn is_passing_constraints(&self, interaction: &MessageComponentInteraction) -> bool {
+        is_id_accepted(self.options.guild_id.as_deref(), interaction.guild_id.map(|g| g.0))
+            && self.options.message_id.map_or(true, |id| interaction.message.id.0 == id)
+            && is_id_accepted(self.options.channel_id.as_deref(), Some(interaction.channel_id.0))
+            && is_id_accepted(self.options.author_id.as_deref(), Some(interaction.user.id.0))
+            && self.options.is_data_matching(&interaction.data)
+    }
+
+    /// Checks if the `interaction` passes the filter function, if any.
+    fn is_passing_filter(
         &self,
         interaction: &mut LazyArc<'_, MessageComponentInteraction>,
     ) -> bool {
-        // TODO: On next branch, switch filter arg to &T so this as_arc() call can be removed.
-        self.options.guild_id.map_or(true, |id| Some(id) == interaction.guild_id.map(|g| g.0))
-            && self.options.message_id.map_or(true, |id| interaction.message.id.0 == id)
-            && self.options.channel_id.map_or(true, |id| id == interaction.channel_id.as_ref().0)
-            && self.options.author_id.map_or(true, |id| id == interaction.user.id.0)
-            && self.options.filter.as_ref().map_or(true, |f| f(&interaction.as_arc()))
+        self.options.filter.as_ref().map_or(true, |f| f.call(interaction))
     }
 
     /// Checks if the filter is within set receive and collect limits.
     /// An interaction is considered *received* even when it does not meet the
-    /// constraints.
+    /// constraints, unless only near misses are counted.
     fn is_within_limits(&self) -> bool {
-        self.options.filter_limit.map_or(true, |limit| self.filtered < limit)
-            && self.options.collect_limit.map_or(true, |limit| self.collected < limit)
+        self.stats.is_within_limits(self.options.filter_limit, self.options.collect_limit)
     }
 }
 
 #[derive(Clone, Default)]
 struct FilterOptions {
     filter_limit: Option<u32>,
+    filter_limit_matching_only: bool,
     collect_limit: Option<u32>,
-    filter: Option<Arc<dyn Fn(&Arc<MessageComponentInteraction>) -> bool + 'static + Send + Sync>>,
-    channel_id: Option<u64>,
-    guild_id: Option<u64>,
-    author_id: Option<u64>,
+    channel_capacity: Option<usize>,
+    filter: Option<FilterFn<MessageComponentInteraction>>,
+    channel_id: Option<Vec<u64>>,
+    guild_id: Option<Vec<u64>>,
+    author_id: Option<Vec<u64>>,
     message_id: Option<u64>,
+    custom_ids: Option<Vec<String>>,
+    custom_id_prefix: Option<String>,
+    component_type: Option<ComponentType>,
+}
+
+impl FilterOptions {
+    /// Checks if the interacted component's custom ID and type match the set
+    /// ones, if any.
+    fn is_data_matching(&self, data: &MessageComponentInteractionData) -> bool {
+        let custom_id = &data.custom_id;
+
+        self.custom_ids.as_ref().map_or(true, |ids| ids.iter().any(|id| id == custom_id))
+            && self.custom_id_prefix.as_ref().map_or(true, |prefix| custom_id.starts_with(prefix))
+            && self.component_type.map_or(true, |kind| kind == data.component_type)
+    }
 }
 
 impl std::fmt::Debug for FilterOptions {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ComponentInteractionFilter")
             .field("collect_limit", &self.collect_limit)
-            .field("filter", &"Option<Arc<dyn Fn(&Arc<Reaction>) -> bool + 'static + Send + Sync>>")
+            .field("channel_capacity", &self.channel_capacity)
+            .field("filter", &self.filter)
             .field("channel_id", &self.channel_id)
             .field("guild_id", &self.guild_id)
             .field("author_id", &self.author_id)
+            .field("custom_ids", &self.custom_ids)
+            .field("custom_id_prefix", &self.custom_id_prefix)
+            .field("component_type", &self.component_type)
             .finish()
     }
 }
@@ -206,6 +378,9 @@ pub struct ComponentInteractionCollectorBuilder<'a> {
     filter: Option<FilterOptions>,
     shard: Option<ShardMessenger>,
     timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<MessageComponentInteraction>>,
+    stats: CollectorStats,
     fut: Option<BoxFuture<'a, ComponentInteractionCollector>>,
 }
 
@@ -215,9 +390,40 @@ impl<'a> ComponentInteractionCollectorBuilder<'a> {
             filter: Some(FilterOptions::default()),
             shard: Some(shard_messenger.as_ref().clone()),
             timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
             fut: None,
         }
     }
+
+    /// Builds the collector and waits for up to `n` interactions.
+    ///
+    /// Collection ends early once the [`Self::timeout`], the
+    /// [`Self::idle_timeout`], the [`Self::collect_limit`] or the
+    /// [`Self::filter_limit`] is reached, whichever comes first, returning the
+    /// interactions collected so far.
+    ///
+    /// The collect limit is lowered to `n` as well, so the collector ends on
+    /// its own once `n` interactions were collected, with its [`CollectorStats`]
+    /// reporting [`CompletionReason::CollectLimit`].
+    ///
+    /// Dropping the returned future stops the collector, discarding the
+    /// interactions collected so far.
+    #[allow(clippy::unwrap_used)]
+    pub async fn collect_n(mut self, n: usize) -> Vec<Arc<MessageComponentInteraction>> {
+        lower_collect_limit(&mut self.filter.as_mut().unwrap().collect_limit, n);
+
+        collect_n(self.await, n).await
+    }
+
+    /// Builds the collector and returns all interactions collected within the
+    /// given `duration`.
+    ///
+    /// Refer to [`Self::collect_n`] for when collection ends early.
+    pub async fn collect_within(self, duration: Duration) -> Vec<Arc<MessageComponentInteraction>> {
+        collect_within(self.await, duration).await
+    }
 }
 
 impl<'a> Future for ComponentInteractionCollectorBuilder<'a> {
@@ -226,15 +432,30 @@ impl<'a> Future for ComponentInteractionCollectorBuilder<'a> {
     fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
         if self.fut.is_none() {
             let shard_messenger = self.shard.take().unwrap();
-            let (filter, receiver) = ComponentInteractionFilter::new(self.filter.take().unwrap());
+            let collect_limit = self.filter.as_ref().unwrap().collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) =
+                ComponentInteractionFilter::new(self.filter.take().unwrap(), self.stats.clone());
             let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
 
             self.fut = Some(Box::pin(async move {
-                shard_messenger.set_component_interaction_filter(filter);
+                let filter_id = filter.id();
+                shard_messenger.set_component_interaction_filter_sync(filter).await;
 
                 ComponentInteractionCollector {
                     receiver: Box::pin(receiver),
                     timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
                 }
             }))
         }
@@ -247,6 +468,9 @@ pub struct CollectComponentInteraction<'a> {
     filter: Option<FilterOptions>,
     shard: Option<ShardMessenger>,
     timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<MessageComponentInteraction>>,
+    stats: CollectorStats,
     fut: Option<BoxFuture<'a, Option<Arc<MessageComponentInteraction>>>>,
 }
 
@@ -256,6 +480,9 @@ impl<'a> CollectComponentInteraction<'a> {
             filter: Some(FilterOptions::default()),
             shard: Some(shard_messenger.as_ref().clone()),
             timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
             fut: None,
         }
     }
@@ -267,15 +494,30 @@ impl<'a> Future for CollectComponentInteraction<'a> {
     fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
         if self.fut.is_none() {
             let shard_messenger = self.shard.take().unwrap();
-            let (filter, receiver) = ComponentInteractionFilter::new(self.filter.take().unwrap());
+            let collect_limit = self.filter.as_ref().unwrap().collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) =
+                ComponentInteractionFilter::new(self.filter.take().unwrap(), self.stats.clone());
             let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
 
             self.fut = Some(Box::pin(async move {
-                shard_messenger.set_component_interaction_filter(filter);
+                let filter_id = filter.id();
+                shard_messenger.set_component_interaction_filter_sync(filter).await;
 
                 ComponentInteractionCollector {
                     receiver: Box::pin(receiver),
                     timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
                 }
                 .next()
                 .await
@@ -289,8 +531,14 @@ impl<'a> Future for CollectComponentInteraction<'a> {
 /// A component interaction collector receives interactions matching a the given filter for a
 /// set duration.
 pub struct ComponentInteractionCollector {
-    receiver: Pin<Box<Receiver<Arc<MessageComponentInteraction>>>>,
+    receiver: Pin<Box<CollectorReceiver<Arc<MessageComponentInteraction>>>>,
     timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<IdleTimeout>,
+    async_filter: Option<AsyncFilter<MessageComponentInteraction>>,
+    shard: ShardMessenger,
+    filter_id: FilterId,
+    handle: CollectorHandle,
+    stats: CollectorStats,
 }
 
 impl ComponentInteractionCollector {
@@ -298,29 +546,135 @@ impl ComponentInteractionCollector {
     /// collector drops.
     /// In case the drop does not appear until later, it is preferred to
     /// stop the collector early.
+    ///
+    /// Either way, the collector's filter is removed from the shard.
     pub fn stop(mut self) {
         self.receiver.close();
     }
+
+    /// Returns a handle to stop the collector from another task, such as
+    /// while this one is waiting for the collector's next item.
+    pub fn handle(&self) -> CollectorHandle {
+        self.handle.clone()
+    }
+
+    /// Returns a handle to the statistics of the collector, such as to check
+    /// why it ended.
+    pub fn stats(&self) -> CollectorStats {
+        self.stats.clone()
+    }
+
+    /// The number of interactions which were dropped as the [channel capacity] was
+    /// reached.
+    ///
+    /// [channel capacity]: ComponentInteractionCollectorBuilder::channel_capacity
+    pub fn dropped_count(&self) -> u64 {
+        self.receiver.dropped_count()
+    }
 }
 
 impl Stream for ComponentInteractionCollector {
     type Item = Arc<MessageComponentInteraction>;
     fn poll_next(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Option<Self::Item>> {
+        if self.handle.poll_stopped(ctx) {
+            self.receiver.close();
+            self.stats.complete(CompletionReason::Stopped);
+
+            return Poll::Ready(None);
+        }
+
         if let Some(ref mut timeout) = self.timeout {
             match timeout.as_mut().poll(ctx) {
                 Poll::Ready(_) => {
+                    self.stats.complete(CompletionReason::Timeout);
+
                     return Poll::Ready(None);
                 },
                 Poll::Pending => (),
             }
         }
 
-        self.receiver.as_mut().poll_recv(ctx)
+        if let Some(ref mut idle_timeout) = self.idle_timeout {
+            if idle_timeout.is_elapsed(ctx) {
+                self.stats.complete(CompletionReason::IdleTimeout);
+
+                return Poll::Ready(None);
+            }
+        }
+
+        let this = &mut *self;
+        let item = poll_recv(&mut this.receiver, this.async_filter.as_mut(), &this.stats, ctx);
+
+        if let Poll::Ready(None) = item {
+            self.stats.complete(CompletionReason::Disconnected);
+        }
+
+        if let (Poll::Ready(Some(_)), Some(idle_timeout)) = (&item, &mut self.idle_timeout) {
+            idle_timeout.reset();
+        }
+
+        item
     }
 }
 
 impl Drop for ComponentInteractionCollector {
     fn drop(&mut self) {
         self.receiver.close();
+        self.stats.complete(CompletionReason::Stopped);
+        self.shard.remove_component_interaction_filter(self.filter_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::FilterOptions;
+    use crate::model::interactions::message_component::{
+        ComponentType,
+        MessageComponentInteractionData,
+    };
+
+    fn data(custom_id: &str, component_type: ComponentType) -> MessageComponentInteractionData {
+        serde_json::from_value(json!({
+            "custom_id": custom_id,
+            "component_type": component_type as u8,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_custom_id() {
+        let filter = FilterOptions {
+            custom_ids: Some(vec!["confirm".to_string(), "cancel".to_string()]),
+            ..Default::default()
+        };
+        assert!(filter.is_data_matching(&data("confirm", ComponentType::Button)));
+        assert!(filter.is_data_matching(&data("cancel", ComponentType::Button)));
+        assert!(!filter.is_data_matching(&data("confirm:2", ComponentType::Button)));
+    }
+
+    #[test]
+    fn test_custom_id_prefix() {
+        let filter = FilterOptions {
+            custom_id_prefix: Some("page:123:".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.is_data_matching(&data("page:123:2", ComponentType::Button)));
+        assert!(!filter.is_data_matching(&data("page:456:2", ComponentType::Button)));
+        assert!(!filter.is_data_matching(&data("page:123", ComponentType::Button)));
+    }
+
+    #[test]
+    fn test_component_type() {
+        let filter = FilterOptions {
+            custom_id_prefix: Some("page:".to_string()),
+            component_type: Some(ComponentType::Button),
+            ..Default::default()
+        };
+        assert!(filter.is_data_matching(&data("page:1", ComponentType::Button)));
+        assert!(!filter.is_data_matching(&data("page:1", ComponentType::SelectMenu)));
+
+        assert!(FilterOptions::default().is_data_matching(&data("any", ComponentType::SelectMenu)));
     }
 }
@@ -8,11 +8,6 @@ use std::{
 };
 
 use futures::{future::BoxFuture, stream::Stream};
-use tokio::sync::mpsc::{
-    unbounded_channel,
-    UnboundedReceiver as Receiver,
-    UnboundedSender as Sender,
-};
 #[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
 use tokio::time::{delay_for as sleep, Delay as Sleep};
 #[cfg(feature = "tokio")]
@@ -20,7 +15,25 @@ use tokio::time::{sleep, Sleep};
 
 use crate::{
     client::bridge::gateway::ShardMessenger,
-    collector::{CollectorError, LazyArc},
+    collector::{
+        channel,
+        collect_n,
+        collect_within,
+        lower_collect_limit,
+        poll_recv,
+        AsyncFilter,
+        AsyncFilterFn,
+        CollectorError,
+        CollectorHandle,
+        CollectorReceiver,
+        CollectorSender,
+        CollectorStats,
+        CompletionReason,
+        FilterFn,
+        FilterId,
+        IdleTimeout,
+        LazyArc,
+    },
     model::{
         event::{Event, EventType, RelatedIdsForEventType},
         id::{ChannelId, GuildId, MessageId, UserId},
@@ -32,22 +45,25 @@ use crate::{
 /// Filters events on the shard's end and sends them to the collector.
 #[derive(Clone, Debug)]
 pub struct EventFilter {
-    filtered: u32,
-    collected: u32,
+    id: FilterId,
+    stats: CollectorStats,
     options: FilterOptions,
-    sender: Sender<Arc<Event>>,
+    sender: CollectorSender<Arc<Event>>,
 }
 
 impl EventFilter {
     /// Creates a new filter
-    fn new(options: FilterOptions) -> Result<(Self, Receiver<Arc<Event>>)> {
+    fn new(
+        options: FilterOptions,
+        stats: CollectorStats,
+    ) -> Result<(Self, CollectorReceiver<Arc<Event>>)> {
         Self::validate_options(&options)?;
 
-        let (sender, receiver) = unbounded_channel();
+        let (sender, receiver) = channel(options.channel_capacity);
 
         let filter = Self {
-            filtered: 0,
-            collected: 0,
+            id: FilterId::next(),
+            stats,
             sender,
             options,
         };
@@ -55,6 +71,16 @@ impl EventFilter {
         Ok((filter, receiver))
     }
 
+    /// The ID of the filter, identifying it on the shard runner.
+    pub fn id(&self) -> FilterId {
+        self.id
+    }
+
+    /// Whether the collector receiving from this filter was dropped.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
     fn validate_options(options: &FilterOptions) -> Result<()> {
         if options.event_types.is_empty() {
             return Err(Error::Collector(CollectorError::NoEventTypes));
@@ -88,15 +114,14 @@ impl EventFilter {
             return !self.sender.is_closed();
         }
 
-        if self.is_passing_constraints(event) {
-            self.collected += 1;
+        let is_matching = self.is_passing_constraints(event);
+        let is_passing = is_matching && self.is_passing_filter(event);
 
-            if let Err(_) = self.sender.send(event.as_arc()) {
-                return false;
-            }
+        if is_passing && !self.sender.send(event.as_arc(), &self.stats) {
+            return false;
         }
 
-        self.filtered += 1;
+        self.stats.add_checked(self.options.filter_limit_matching_only, is_matching && !is_passing);
 
         self.is_within_limits() && !self.sender.is_closed()
     }
@@ -106,10 +131,15 @@ impl EventFilter {
         self.options.event_types.contains(&event.event_type())
     }
 
+    /// The event types this filter waits for.
+    pub(crate) fn event_types(&self) -> &[EventType] {
+        &self.options.event_types
+    }
+
     /// Checks if the `event` passes set constraints.
     /// Constraints are optional, as it is possible to limit events to
     /// be sent by a specific user or in a specific guild.
-    fn is_passing_constraints(&mut self, event: &mut LazyArc<'_, Event>) -> bool {
+    fn is_passing_constraints(&self, event: &Event) -> bool {
         fn empty_or_any<T, F>(slice: &[T], f: F) -> bool
         where
             F: Fn(&T) -> bool,
@@ -117,29 +147,22 @@ impl EventFilter {
             slice.is_empty() || slice.iter().any(f)
         }
 
-        // TODO: On next branch, switch filter arg to &T so this as_arc() call can be removed.
         empty_or_any(&self.options.guild_id, |id| event.guild_id().contains(id))
             && empty_or_any(&self.options.user_id, |id| event.user_id().contains(id))
             && empty_or_any(&self.options.channel_id, |id| event.channel_id().contains(id))
             && empty_or_any(&self.options.message_id, |id| event.message_id().contains(id))
-            && self.options.filter.as_mut().map_or(true, |f| f.0(&event.as_arc()))
+    }
+
+    /// Checks if the `event` passes the filter function, if any.
+    fn is_passing_filter(&self, event: &mut LazyArc<'_, Event>) -> bool {
+        self.options.filter.as_ref().map_or(true, |f| f.call(event))
     }
 
     /// Checks if the filter is within set receive and collect limits.
     /// A event is considered *received* even when it does not meet the
-    /// constraints.
+    /// constraints, unless only near misses are counted.
     fn is_within_limits(&self) -> bool {
-        self.options.filter_limit.as_ref().map_or(true, |limit| self.filtered < *limit)
-            && self.options.collect_limit.as_ref().map_or(true, |limit| self.collected < *limit)
-    }
-}
-
-#[derive(Clone)]
-struct FilterFn(Arc<dyn Fn(&Arc<Event>) -> bool + 'static + Send + Sync>);
-
-impl std::fmt::Debug for FilterFn {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("Arc<dyn Fn(&Arc<Event>) -> bool + 'static + Send + Sync>")
+        self.stats.is_within_limits(self.options.filter_limit, self.options.collect_limit)
     }
 }
 
@@ -147,8 +170,10 @@ impl std::fmt::Debug for FilterFn {
 struct FilterOptions {
     event_types: Vec<EventType>,
     filter_limit: Option<u32>,
+    filter_limit_matching_only: bool,
     collect_limit: Option<u32>,
-    filter: Option<FilterFn>,
+    channel_capacity: Option<usize>,
+    filter: Option<FilterFn<Event>>,
     channel_id: Vec<ChannelId>,
     guild_id: Vec<GuildId>,
     user_id: Vec<UserId>,
@@ -160,6 +185,9 @@ pub struct EventCollectorBuilder<'a> {
     filter: Option<FilterOptions>,
     shard: Option<ShardMessenger>,
     timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<Event>>,
+    stats: CollectorStats,
     fut: Option<BoxFuture<'a, Result<EventCollector>>>,
 }
 
@@ -170,6 +198,9 @@ impl<'a> EventCollectorBuilder<'a> {
             filter: Some(FilterOptions::default()),
             shard: Some(shard_messenger.as_ref().clone()),
             timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
             fut: None,
         }
     }
@@ -185,6 +216,17 @@ impl<'a> EventCollectorBuilder<'a> {
         self
     }
 
+    /// Sets whether only near misses count towards the [`Self::filter_limit`],
+    /// which match all other constraints, but are rejected by [`Self::filter`].
+    ///
+    /// By default, every event the filter checks counts towards the limit.
+    #[allow(clippy::unwrap_used)]
+    pub fn filter_limit_matching_only(mut self, matching_only: bool) -> Self {
+        self.filter.as_mut().unwrap().filter_limit_matching_only = matching_only;
+
+        self
+    }
+
     /// Limits how many events can be collected.
     ///
     /// An event is considered *collected*, if the event
@@ -196,16 +238,57 @@ impl<'a> EventCollectorBuilder<'a> {
         self
     }
 
+    /// Limits how many events can wait to be received from the collector.
+    /// Once reached, further events are dropped rather than waiting, such as
+    /// while the receiving task is busy during a spam wave.
+    ///
+    /// The dropped events are counted by [`EventCollector::dropped_count`].
+    /// By default, any number of events can wait.
+    /// A capacity of `0` is treated as `1`.
+    #[allow(clippy::unwrap_used)]
+    pub fn channel_capacity(mut self, capacity: usize) -> Self {
+        self.filter.as_mut().unwrap().channel_capacity = Some(capacity);
+
+        self
+    }
+
     /// Sets a filter function where events passed to the `function` must
     /// return `true`, otherwise the event won't be collected and failed the filter
     /// process.
     /// This is the last step to pass for a event to count as *collected*.
     #[allow(clippy::unwrap_used)]
-    pub fn filter<F: Fn(&Arc<Event>) -> bool + 'static + Send + Sync>(
+    pub fn filter<F: Fn(&Event) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
+        self.filter.as_mut().unwrap().filter = Some(FilterFn::new(function));
+
+        self
+    }
+
+    /// Sets a filter function taking the event wrapped in an [`Arc`].
+    ///
+    /// Prefer [`Self::filter`], which only clones the event into an [`Arc`]
+    /// once it is known to be collected.
+    #[deprecated(note = "use `filter`, which takes `&Event` instead")]
+    #[allow(clippy::unwrap_used)]
+    pub fn filter_arc<F: Fn(&Arc<Event>) -> bool + 'static + Send + Sync>(
         mut self,
         function: F,
     ) -> Self {
-        self.filter.as_mut().unwrap().filter = Some(FilterFn(Arc::new(function)));
+        self.filter.as_mut().unwrap().filter = Some(FilterFn::new_arc(function));
+
+        self
+    }
+
+    /// Sets an asynchronous filter function where events passed to the
+    /// `function` must resolve to `true`, otherwise they won't be collected.
+    ///
+    /// This runs on the collector's end once all other requirements passed,
+    /// including [`Self::filter`], so it may wait on a database or HTTP request.
+    /// Rejected events do not count towards [`Self::collect_limit`].
+    pub fn async_filter<F>(mut self, function: F) -> Self
+    where
+        F: Fn(&Arc<Event>) -> BoxFuture<'static, bool> + 'static + Send + Sync,
+    {
+        self.async_filter = Some(AsyncFilterFn::new(function));
 
         self
     }
@@ -262,6 +345,97 @@ impl<'a> EventCollectorBuilder<'a> {
 
         self
     }
+
+    /// Sets a `duration` after which the collector stops if it has not
+    /// collected an event meanwhile, restarting whenever one is collected.
+    ///
+    /// This can be combined with [`Self::timeout`], stopping the collector
+    /// once either of them elapsed.
+    pub fn idle_timeout(mut self, duration: Duration) -> Self {
+        self.idle_timeout = Some(duration);
+
+        self
+    }
+
+    /// Returns a handle to the statistics of the collector, which stays
+    /// usable once the builder is consumed, such as by [`Self::collect_n`].
+    pub fn stats(&self) -> CollectorStats {
+        self.stats.clone()
+    }
+
+    /// Builds the collector and waits for up to `n` events.
+    ///
+    /// Collection ends early once the [`Self::timeout`], the
+    /// [`Self::idle_timeout`], the [`Self::collect_limit`] or the
+    /// [`Self::filter_limit`] is reached, whichever comes first, returning the
+    /// events collected so far.
+    ///
+    /// The collect limit is lowered to `n` as well, so the collector ends on
+    /// its own once `n` events were collected, with its [`CollectorStats`]
+    /// reporting [`CompletionReason::CollectLimit`].
+    ///
+    /// Dropping the returned future stops the collector, discarding the
+    /// events collected so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as awaiting the builder itself.
+    #[allow(clippy::unwrap_used)]
+    pub async fn collect_n(mut self, n: usize) -> Result<Vec<Arc<Event>>> {
+        lower_collect_limit(&mut self.filter.as_mut().unwrap().collect_limit, n);
+
+        Ok(collect_n(self.await?, n).await)
+    }
+
+    /// Builds the collector and returns all events collected within the
+    /// given `duration`.
+    ///
+    /// Refer to [`Self::collect_n`] for when collection ends early.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as awaiting the builder itself.
+    pub async fn collect_within(self, duration: Duration) -> Result<Vec<Arc<Event>>> {
+        Ok(collect_within(self.await?, duration).await)
+    }
+
+    /// Builds the collector and waits for a single event, such as the next
+    /// update of a certain member.
+    ///
+    /// Returns [`None`] if collection ended before an event was collected,
+    /// refer to [`Self::collect_n`] for when that happens.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::time::Duration;
+    /// #
+    /// # use serenity::client::Context;
+    /// # use serenity::collector::EventCollectorBuilder;
+    /// # use serenity::model::event::{Event, EventType};
+    /// # use serenity::model::id::UserId;
+    /// #
+    /// # async fn run(ctx: &Context) -> serenity::Result<()> {
+    /// let event = EventCollectorBuilder::new(ctx)
+    ///     .add_event_type(EventType::GuildMemberUpdate)
+    ///     .add_user_id(UserId(210))
+    ///     .timeout(Duration::from_secs(60))
+    ///     .collect_single()
+    ///     .await?;
+    ///
+    /// if let Some(Event::GuildMemberUpdate(update)) = event.as_deref() {
+    ///     println!("{} was updated", update.user.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as awaiting the builder itself.
+    pub async fn collect_single(self) -> Result<Option<Arc<Event>>> {
+        Ok(self.collect_n(1).await?.pop())
+    }
 }
 
 impl<'a> Future for EventCollectorBuilder<'a> {
@@ -270,18 +444,33 @@ impl<'a> Future for EventCollectorBuilder<'a> {
     fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
         if self.fut.is_none() {
             let shard_messenger = self.shard.take().unwrap();
-            let (filter, receiver) = match EventFilter::new(self.filter.take().unwrap()) {
+            let options = self.filter.take().unwrap();
+            let collect_limit = options.collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) = match EventFilter::new(options, self.stats.clone()) {
                 Ok(ret) => ret,
                 Err(err) => return Poll::Ready(Err(err)),
             };
             let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
 
             self.fut = Some(Box::pin(async move {
-                shard_messenger.set_event_filter(filter);
+                let filter_id = filter.id();
+                shard_messenger.set_event_filter_sync(filter).await;
 
                 Ok(EventCollector {
                     receiver: Box::pin(receiver),
                     timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
                 })
             }))
         }
@@ -292,8 +481,14 @@ impl<'a> Future for EventCollectorBuilder<'a> {
 
 /// An event collector receives events matching the given filter for a set duration.
 pub struct EventCollector {
-    receiver: Pin<Box<Receiver<Arc<Event>>>>,
+    receiver: Pin<Box<CollectorReceiver<Arc<Event>>>>,
     timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<IdleTimeout>,
+    async_filter: Option<AsyncFilter<Event>>,
+    shard: ShardMessenger,
+    filter_id: FilterId,
+    handle: CollectorHandle,
+    stats: CollectorStats,
 }
 
 impl EventCollector {
@@ -301,30 +496,82 @@ impl EventCollector {
     /// collector drops.
     /// In case the drop does not appear until later, it is preferred to
     /// stop the collector early.
+    ///
+    /// Either way, the collector's filter is removed from the shard.
     pub fn stop(mut self) {
         self.receiver.close();
     }
+
+    /// Returns a handle to stop the collector from another task, such as
+    /// while this one is waiting for the collector's next item.
+    pub fn handle(&self) -> CollectorHandle {
+        self.handle.clone()
+    }
+
+    /// Returns a handle to the statistics of the collector, such as to check
+    /// why it ended.
+    pub fn stats(&self) -> CollectorStats {
+        self.stats.clone()
+    }
+
+    /// The number of events which were dropped as the [channel capacity] was
+    /// reached.
+    ///
+    /// [channel capacity]: EventCollectorBuilder::channel_capacity
+    pub fn dropped_count(&self) -> u64 {
+        self.receiver.dropped_count()
+    }
 }
 
 impl Stream for EventCollector {
     type Item = Arc<Event>;
     fn poll_next(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Option<Self::Item>> {
+        if self.handle.poll_stopped(ctx) {
+            self.receiver.close();
+            self.stats.complete(CompletionReason::Stopped);
+
+            return Poll::Ready(None);
+        }
+
         if let Some(ref mut timeout) = self.timeout {
             match timeout.as_mut().poll(ctx) {
                 Poll::Ready(_) => {
+                    self.stats.complete(CompletionReason::Timeout);
+
                     return Poll::Ready(None);
                 },
                 Poll::Pending => (),
             }
         }
 
-        self.receiver.as_mut().poll_recv(ctx)
+        if let Some(ref mut idle_timeout) = self.idle_timeout {
+            if idle_timeout.is_elapsed(ctx) {
+                self.stats.complete(CompletionReason::IdleTimeout);
+
+                return Poll::Ready(None);
+            }
+        }
+
+        let this = &mut *self;
+        let item = poll_recv(&mut this.receiver, this.async_filter.as_mut(), &this.stats, ctx);
+
+        if let Poll::Ready(None) = item {
+            self.stats.complete(CompletionReason::Disconnected);
+        }
+
+        if let (Poll::Ready(Some(_)), Some(idle_timeout)) = (&item, &mut self.idle_timeout) {
+            idle_timeout.reset();
+        }
+
+        item
     }
 }
 
 impl Drop for EventCollector {
     fn drop(&mut self) {
         self.receiver.close();
+        self.stats.complete(CompletionReason::Stopped);
+        self.shard.remove_event_filter(self.filter_id);
     }
 }
 
@@ -332,8 +579,12 @@ impl Drop for EventCollector {
 mod test {
     use futures::channel::mpsc::unbounded;
 
+    use serde_json::Value;
+
     use super::*;
-    use crate::client::bridge::gateway::ShardMessenger;
+    use crate::client::bridge::gateway::{ShardClientMessage, ShardMessenger, ShardRunnerMessage};
+    use crate::gateway::InterMessage;
+    use crate::model::event::UnknownEvent;
 
     #[tokio::test]
     async fn test_no_event_types() {
@@ -349,6 +600,75 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_channel_capacity() {
+        let options = FilterOptions {
+            event_types: vec![EventType::Other("TEST".to_string())],
+            channel_capacity: Some(1),
+            ..FilterOptions::default()
+        };
+        let (mut filter, mut receiver) =
+            EventFilter::new(options, CollectorStats::default()).unwrap();
+        let event = Event::Unknown(UnknownEvent {
+            kind: "TEST".to_string(),
+            value: Value::Null,
+        });
+
+        assert!(filter.send_event(&mut LazyArc::new(&event)));
+        assert!(filter.send_event(&mut LazyArc::new(&event)));
+        assert_eq!(receiver.dropped_count(), 1);
+
+        receiver.close();
+        assert!(!filter.send_event(&mut LazyArc::new(&event)));
+    }
+
+    fn runner_message(message: Option<InterMessage>) -> ShardRunnerMessage {
+        match message {
+            Some(InterMessage::Client(message)) => match *message {
+                ShardClientMessage::Runner(message) => message,
+                other => panic!("unexpected message: {:?}", other),
+            },
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_collect_single() {
+        let (sender, mut receiver) = unbounded();
+        let messenger = ShardMessenger::new(sender);
+
+        let builder = EventCollectorBuilder::new(&messenger)
+            .add_event_type(EventType::Other("TEST".to_string()))
+            .collect_single();
+        let mut single = tokio_test::task::spawn(builder);
+        assert!(single.poll().is_pending());
+
+        // Act as the shard runner, installing the filter before acknowledging.
+        let mut filter = match runner_message(receiver.try_next().unwrap()) {
+            ShardRunnerMessage::SetEventFilter(filter) => filter,
+            other => panic!("unexpected message: {:?}", other),
+        };
+        match runner_message(receiver.try_next().unwrap()) {
+            ShardRunnerMessage::Acknowledge(ack) => ack.send(),
+            other => panic!("unexpected message: {:?}", other),
+        }
+        assert!(single.poll().is_pending());
+
+        let event = Event::Unknown(UnknownEvent {
+            kind: "TEST".to_string(),
+            value: Value::Null,
+        });
+
+        // The collect limit is reached by the first event.
+        assert!(!filter.send_event(&mut LazyArc::new(&event)));
+
+        match single.await.unwrap().as_deref() {
+            Some(Event::Unknown(event)) => assert_eq!(event.kind, "TEST"),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
     #[tokio::test]
     async fn test_build_with_single_id_filter() {
         let (sender, _) = unbounded();
@@ -0,0 +1,802 @@
+use std::{
+    boxed::Box,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context as FutContext, Poll},
+    time::Duration,
+};
+
+use futures::{
+    future::BoxFuture,
+    stream::{Stream, StreamExt},
+};
+#[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
+use tokio::time::{delay_for as sleep, Delay as Sleep};
+#[cfg(feature = "tokio")]
+use tokio::time::{sleep, Sleep};
+
+use crate::{
+    client::bridge::gateway::ShardMessenger,
+    collector::{
+        add_ids,
+        channel,
+        collect_n,
+        collect_within,
+        is_id_accepted,
+        lower_collect_limit,
+        poll_recv,
+        AsyncFilter,
+        AsyncFilterFn,
+        CollectorHandle,
+        CollectorReceiver,
+        CollectorSender,
+        CollectorStats,
+        CompletionReason,
+        FilterFn,
+        FilterId,
+        IdleTimeout,
+        LazyArc,
+    },
+    model::{
+        event::{GuildMemberAddEvent, GuildMemberRemoveEvent},
+        guild::Member,
+        id::GuildId,
+        user::User,
+    },
+};
+
+macro_rules! impl_guild_member_collector {
+    ($($name:ident;)*) => {
+        $(
+            impl<'a> $name<'a> {
+                /// Limits how many members joining or leaving will attempt to be filtered.
+                ///
+                /// The filter checks whether the member joined or left the
+                /// right guild, and is the right user.
+                pub fn filter_limit(mut self, limit: u32) -> Self {
+                    self.filter.as_mut().unwrap().filter_limit = Some(limit);
+
+                    self
+                }
+
+                /// Sets whether only near misses count towards the [`Self::filter_limit`],
+                /// which match all other constraints, but are rejected by
+                /// [`Self::filter_joined`] or [`Self::filter_left`].
+                ///
+                /// By default, every member joining or leaving counts towards the limit.
+                pub fn filter_limit_matching_only(mut self, matching_only: bool) -> Self {
+                    self.filter.as_mut().unwrap().filter_limit_matching_only = matching_only;
+
+                    self
+                }
+
+                /// Limits how many members joining or leaving can be collected.
+                ///
+                /// A member joining or leaving is considered *collected*, if it
+                /// passes all the requirements.
+                pub fn collect_limit(mut self, limit: u32) -> Self {
+                    self.filter.as_mut().unwrap().collect_limit = Some(limit);
+
+                    self
+                }
+
+                /// Limits how many members joining or leaving can wait to be received from
+                /// the collector. Once reached, further ones are dropped rather than waiting,
+                /// such as while the receiving task is busy during a raid.
+                ///
+                /// The dropped ones are counted by [`GuildMemberCollector::dropped_count`].
+                /// By default, any number of them can wait.
+                /// A capacity of `0` is treated as `1`.
+                pub fn channel_capacity(mut self, capacity: usize) -> Self {
+                    self.filter.as_mut().unwrap().channel_capacity = Some(capacity);
+
+                    self
+                }
+
+                /// Sets a filter function where members joining passed to the function must
+                /// return `true`, otherwise the member won't be collected.
+                /// This is the last instance to pass for a member joining to count as *collected*.
+                ///
+                /// Members leaving are checked by [`Self::filter_left`] instead.
+                pub fn filter_joined<F: Fn(&Member) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.filter.as_mut().unwrap().filter_joined = Some(FilterFn::new(function));
+
+                    self
+                }
+
+                /// Sets a filter function where users leaving passed to the function must
+                /// return `true`, otherwise the user won't be collected.
+                /// This is the last instance to pass for a member leaving to count as *collected*.
+                ///
+                /// Members joining are checked by [`Self::filter_joined`] instead.
+                pub fn filter_left<F: Fn(&User) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.filter.as_mut().unwrap().filter_left = Some(FilterFn::new(function));
+
+                    self
+                }
+
+                /// Sets an asynchronous filter function where members joining or leaving passed to the
+                /// function must resolve to `true`, otherwise they won't be collected.
+                ///
+                /// This runs on the collector's end once all other requirements passed,
+                /// including [`Self::filter_joined`] and [`Self::filter_left`], so it may
+                /// wait on a database or HTTP request.
+                /// Rejected members joining or leaving do not count towards [`Self::collect_limit`].
+                pub fn async_filter<F: Fn(&Arc<GuildMemberAction>) -> BoxFuture<'static, bool> + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.async_filter = Some(AsyncFilterFn::new(function));
+
+                    self
+                }
+
+                /// Sets the guild the member must join or leave.
+                /// If a member joins or leaves another guild, it won't be received.
+                ///
+                /// Calling this multiple times accepts members joining or leaving any of the guilds.
+                pub fn guild_id(mut self, guild_id: impl Into<u64>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().guild_id, Some(guild_id));
+
+                    self
+                }
+
+                /// Like [`Self::guild_id`], but adds multiple IDs at once.
+                pub fn guild_ids(mut self, guild_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().guild_id, guild_ids);
+
+                    self
+                }
+
+                /// Sets the user who must join or leave.
+                /// If another user joins or leaves, it won't be received.
+                ///
+                /// Calling this multiple times accepts any of the users.
+                pub fn user_id(mut self, user_id: impl Into<u64>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().user_id, Some(user_id));
+
+                    self
+                }
+
+                /// Like [`Self::user_id`], but adds multiple IDs at once.
+                pub fn user_ids(mut self, user_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().user_id, user_ids);
+
+                    self
+                }
+
+                /// If set to `true`, members joining will be collected.
+                ///
+                /// Set to `true` by default.
+                pub fn joined(mut self, is_accepted: bool) -> Self {
+                    self.filter.as_mut().unwrap().accept_joined = is_accepted;
+
+                    self
+                }
+
+                /// If set to `true`, members leaving will be collected, including
+                /// members who were kicked or banned.
+                ///
+                /// Set to `false` by default.
+                pub fn left(mut self, is_accepted: bool) -> Self {
+                    self.filter.as_mut().unwrap().accept_left = is_accepted;
+
+                    self
+                }
+
+                /// Sets a `duration` for how long the collector shall receive
+                /// members joining or leaving.
+                pub fn timeout(mut self, duration: Duration) -> Self {
+                    self.timeout = Some(Box::pin(sleep(duration)));
+
+                    self
+                }
+
+                /// Sets a `duration` after which the collector stops if it has not
+                /// collected a member joining or leaving meanwhile, restarting whenever
+                /// one is collected.
+                ///
+                /// This can be combined with [`Self::timeout`], stopping the collector
+                /// once either of them elapsed.
+                pub fn idle_timeout(mut self, duration: Duration) -> Self {
+                    self.idle_timeout = Some(duration);
+
+                    self
+                }
+
+                /// Returns a handle to the statistics of the collector, which stays
+                /// usable once this is consumed, such as by awaiting it.
+                pub fn stats(&self) -> CollectorStats {
+                    self.stats.clone()
+                }
+            }
+        )*
+    }
+}
+
+/// Marks whether a member joined or left a guild.
+#[derive(Debug)]
+pub enum GuildMemberAction {
+    /// The member joined the guild.
+    Joined(Arc<Member>),
+    /// The user left the guild, or was kicked or banned from it.
+    Left {
+        guild_id: GuildId,
+        user: Arc<User>,
+    },
+}
+
+impl GuildMemberAction {
+    /// The ID of the guild the member joined or left.
+    pub fn guild_id(&self) -> GuildId {
+        match self {
+            Self::Joined(member) => member.guild_id,
+            Self::Left {
+                guild_id, ..
+            } => *guild_id,
+        }
+    }
+
+    /// The user who joined or left.
+    pub fn user(&self) -> &User {
+        match self {
+            Self::Joined(member) => &member.user,
+            Self::Left {
+                user, ..
+            } => user,
+        }
+    }
+
+    pub fn is_joined(&self) -> bool {
+        matches!(self, Self::Joined(_))
+    }
+
+    pub fn is_left(&self) -> bool {
+        matches!(self, Self::Left { .. })
+    }
+}
+
+/// Wraps a member joining or leaving, only cloning it into a
+/// [`GuildMemberAction`] once a filter accepts it.
+#[derive(Debug)]
+pub(crate) struct LazyGuildMemberAction<'a> {
+    guild_id: GuildId,
+    member: Option<&'a Member>,
+    user: &'a User,
+    arc: Option<Arc<GuildMemberAction>>,
+}
+
+impl<'a> LazyGuildMemberAction<'a> {
+    pub fn joined(event: &'a GuildMemberAddEvent) -> Self {
+        Self {
+            guild_id: event.guild_id,
+            member: Some(&event.member),
+            user: &event.member.user,
+            arc: None,
+        }
+    }
+
+    pub fn left(event: &'a GuildMemberRemoveEvent) -> Self {
+        Self {
+            guild_id: event.guild_id,
+            member: None,
+            user: &event.user,
+            arc: None,
+        }
+    }
+
+    pub fn as_arc(&mut self) -> Arc<GuildMemberAction> {
+        let (guild_id, member, user) = (self.guild_id, self.member, self.user);
+        self.arc
+            .get_or_insert_with(|| {
+                Arc::new(match member {
+                    Some(member) => GuildMemberAction::Joined(Arc::new(member.clone())),
+                    None => GuildMemberAction::Left {
+                        guild_id,
+                        user: Arc::new(user.clone()),
+                    },
+                })
+            })
+            .clone()
+    }
+}
+
+/// Filters events on the shard's end and sends them to the collector.
+#[derive(Clone, Debug)]
+pub struct GuildMemberFilter {
+    id: FilterId,
+    stats: CollectorStats,
+    options: FilterOptions,
+    sender: CollectorSender<Arc<GuildMemberAction>>,
+}
+
+impl GuildMemberFilter {
+    /// Creates a new filter
+    fn new(
+        options: FilterOptions,
+        stats: CollectorStats,
+    ) -> (Self, CollectorReceiver<Arc<GuildMemberAction>>) {
+        let (sender, receiver) = channel(options.channel_capacity);
+
+        let filter = Self {
+            id: FilterId::next(),
+            stats,
+            sender,
+            options,
+        };
+
+        (filter, receiver)
+    }
+
+    /// The ID of the filter, identifying it on the shard runner.
+    pub fn id(&self) -> FilterId {
+        self.id
+    }
+
+    /// Whether the collector receiving from this filter was dropped.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
+    /// Sends a member joining or leaving to the consuming collector if it
+    /// conforms to the constraints and the limits are not reached yet.
+    pub(crate) fn send_member(&mut self, action: &mut LazyGuildMemberAction<'_>) -> bool {
+        let is_matching = self.is_passing_constraints(action);
+        let is_passing = is_matching && self.is_passing_filter(action);
+
+        if is_passing && !self.sender.send(action.as_arc(), &self.stats) {
+            return false;
+        }
+
+        self.stats.add_checked(self.options.filter_limit_matching_only, is_matching && !is_passing);
+
+        self.is_within_limits() && !self.sender.is_closed()
+    }
+
+    /// Checks if the member joining or leaving passes set constraints.
+    /// Constraints are optional, as it is possible to limit members to
+    /// a specific guild or user.
+    fn is_passing_constraints(&self, action: &LazyGuildMemberAction<'_>) -> bool {
+        let is_passing_kind = match action.member {
+            Some(_) => self.options.accept_joined,
+            None => self.options.accept_left,
+        };
+
+        is_passing_kind
+            && is_id_accepted(self.options.guild_id.as_deref(), Some(action.guild_id.0))
+            && is_id_accepted(self.options.user_id.as_deref(), Some(action.user.id.0))
+    }
+
+    /// Checks if the `action` passes the filter function set for its kind.
+    fn is_passing_filter(&self, action: &LazyGuildMemberAction<'_>) -> bool {
+        match action.member {
+            Some(member) => {
+                let member = &mut LazyArc::new(member);
+                self.options.filter_joined.as_ref().map_or(true, |f| f.call(member))
+            },
+            None => {
+                let user = &mut LazyArc::new(action.user);
+                self.options.filter_left.as_ref().map_or(true, |f| f.call(user))
+            },
+        }
+    }
+
+    /// Checks if the filter is within set receive and collect limits.
+    /// A member joining or leaving is considered *received* even when it does
+    /// not meet the constraints, unless only near misses are counted.
+    fn is_within_limits(&self) -> bool {
+        self.stats.is_within_limits(self.options.filter_limit, self.options.collect_limit)
+    }
+}
+
+#[derive(Clone)]
+struct FilterOptions {
+    filter_limit: Option<u32>,
+    filter_limit_matching_only: bool,
+    collect_limit: Option<u32>,
+    channel_capacity: Option<usize>,
+    filter_joined: Option<FilterFn<Member>>,
+    filter_left: Option<FilterFn<User>>,
+    guild_id: Option<Vec<u64>>,
+    user_id: Option<Vec<u64>>,
+    accept_joined: bool,
+    accept_left: bool,
+}
+
+impl Default for FilterOptions {
+    fn default() -> Self {
+        Self {
+            filter_limit: None,
+            filter_limit_matching_only: false,
+            collect_limit: None,
+            channel_capacity: None,
+            filter_joined: None,
+            filter_left: None,
+            guild_id: None,
+            user_id: None,
+            accept_joined: true,
+            accept_left: false,
+        }
+    }
+}
+
+impl std::fmt::Debug for FilterOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GuildMemberFilter")
+            .field("collect_limit", &self.collect_limit)
+            .field("channel_capacity", &self.channel_capacity)
+            .field("filter_joined", &self.filter_joined)
+            .field("filter_left", &self.filter_left)
+            .field("guild_id", &self.guild_id)
+            .field("user_id", &self.user_id)
+            .field("accept_joined", &self.accept_joined)
+            .field("accept_left", &self.accept_left)
+            .finish()
+    }
+}
+
+// Implement the common setters for all guild member collector types.
+// This avoids using a trait that the user would need to import in
+// order to use any of these methods.
+impl_guild_member_collector! {
+    CollectGuildMember;
+    GuildMemberCollectorBuilder;
+}
+
+/// Builds a [`GuildMemberCollector`], collecting members joining or leaving
+/// guilds.
+///
+/// **Note**: This requires the [`GatewayIntents::GUILD_MEMBERS`] intent.
+///
+/// [`GatewayIntents::GUILD_MEMBERS`]: crate::client::bridge::gateway::GatewayIntents::GUILD_MEMBERS
+pub struct GuildMemberCollectorBuilder<'a> {
+    filter: Option<FilterOptions>,
+    shard: Option<ShardMessenger>,
+    timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<GuildMemberAction>>,
+    stats: CollectorStats,
+    fut: Option<BoxFuture<'a, GuildMemberCollector>>,
+}
+
+impl<'a> GuildMemberCollectorBuilder<'a> {
+    pub fn new(shard_messenger: impl AsRef<ShardMessenger>) -> Self {
+        Self {
+            filter: Some(FilterOptions::default()),
+            shard: Some(shard_messenger.as_ref().clone()),
+            timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
+            fut: None,
+        }
+    }
+
+    /// Builds the collector and waits for up to `n` members joining or leaving.
+    ///
+    /// Collection ends early once the [`Self::timeout`], the
+    /// [`Self::idle_timeout`], the [`Self::collect_limit`] or the
+    /// [`Self::filter_limit`] is reached, whichever comes first, returning the
+    /// members collected so far.
+    ///
+    /// The collect limit is lowered to `n` as well, so the collector ends on
+    /// its own once `n` member actions were collected, with its [`CollectorStats`]
+    /// reporting [`CompletionReason::CollectLimit`].
+    ///
+    /// Dropping the returned future stops the collector, discarding the
+    /// members collected so far.
+    #[allow(clippy::unwrap_used)]
+    pub async fn collect_n(mut self, n: usize) -> Vec<Arc<GuildMemberAction>> {
+        lower_collect_limit(&mut self.filter.as_mut().unwrap().collect_limit, n);
+
+        collect_n(self.await, n).await
+    }
+
+    /// Builds the collector and returns all members joining or leaving within
+    /// the given `duration`.
+    ///
+    /// Refer to [`Self::collect_n`] for when collection ends early.
+    pub async fn collect_within(self, duration: Duration) -> Vec<Arc<GuildMemberAction>> {
+        collect_within(self.await, duration).await
+    }
+}
+
+impl<'a> Future for GuildMemberCollectorBuilder<'a> {
+    type Output = GuildMemberCollector;
+    #[allow(clippy::unwrap_used)]
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
+        if self.fut.is_none() {
+            let shard_messenger = self.shard.take().unwrap();
+            let collect_limit = self.filter.as_ref().unwrap().collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) =
+                GuildMemberFilter::new(self.filter.take().unwrap(), self.stats.clone());
+            let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
+
+            self.fut = Some(Box::pin(async move {
+                let filter_id = filter.id();
+                shard_messenger.set_guild_member_filter_sync(filter).await;
+
+                GuildMemberCollector {
+                    receiver: Box::pin(receiver),
+                    timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
+                }
+            }))
+        }
+
+        self.fut.as_mut().unwrap().as_mut().poll(ctx)
+    }
+}
+
+/// Waits for a single member joining or leaving a guild, such as the next
+/// member joining a guild to verify.
+///
+/// **Note**: This requires the [`GatewayIntents::GUILD_MEMBERS`] intent.
+///
+/// [`GatewayIntents::GUILD_MEMBERS`]: crate::client::bridge::gateway::GatewayIntents::GUILD_MEMBERS
+pub struct CollectGuildMember<'a> {
+    filter: Option<FilterOptions>,
+    shard: Option<ShardMessenger>,
+    timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<GuildMemberAction>>,
+    stats: CollectorStats,
+    fut: Option<BoxFuture<'a, Option<Arc<GuildMemberAction>>>>,
+}
+
+impl<'a> CollectGuildMember<'a> {
+    pub fn new(shard_messenger: impl AsRef<ShardMessenger>) -> Self {
+        Self {
+            filter: Some(FilterOptions::default()),
+            shard: Some(shard_messenger.as_ref().clone()),
+            timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
+            fut: None,
+        }
+    }
+}
+
+impl<'a> Future for CollectGuildMember<'a> {
+    type Output = Option<Arc<GuildMemberAction>>;
+    #[allow(clippy::unwrap_used)]
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
+        if self.fut.is_none() {
+            let shard_messenger = self.shard.take().unwrap();
+            let collect_limit = self.filter.as_ref().unwrap().collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) =
+                GuildMemberFilter::new(self.filter.take().unwrap(), self.stats.clone());
+            let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
+
+            self.fut = Some(Box::pin(async move {
+                let filter_id = filter.id();
+                shard_messenger.set_guild_member_filter_sync(filter).await;
+
+                GuildMemberCollector {
+                    receiver: Box::pin(receiver),
+                    timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
+                }
+                .next()
+                .await
+            }))
+        }
+
+        self.fut.as_mut().unwrap().as_mut().poll(ctx)
+    }
+}
+
+/// A guild member collector receives members joining or leaving guilds
+/// matching the given filter for a set duration.
+pub struct GuildMemberCollector {
+    receiver: Pin<Box<CollectorReceiver<Arc<GuildMemberAction>>>>,
+    timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<IdleTimeout>,
+    async_filter: Option<AsyncFilter<GuildMemberAction>>,
+    shard: ShardMessenger,
+    filter_id: FilterId,
+    handle: CollectorHandle,
+    stats: CollectorStats,
+}
+
+impl GuildMemberCollector {
+    /// Stops collecting, this will implicitly be done once the
+    /// collector drops.
+    /// In case the drop does not appear until later, it is preferred to
+    /// stop the collector early.
+    ///
+    /// Either way, the collector's filter is removed from the shard.
+    pub fn stop(mut self) {
+        self.receiver.close();
+    }
+
+    /// Returns a handle to stop the collector from another task, such as
+    /// while this one is waiting for the collector's next item.
+    pub fn handle(&self) -> CollectorHandle {
+        self.handle.clone()
+    }
+
+    /// Returns a handle to the statistics of the collector, such as to check
+    /// why it ended.
+    pub fn stats(&self) -> CollectorStats {
+        self.stats.clone()
+    }
+
+    /// The number of members joining or leaving which were dropped as the
+    /// [channel capacity] was reached.
+    ///
+    /// [channel capacity]: GuildMemberCollectorBuilder::channel_capacity
+    pub fn dropped_count(&self) -> u64 {
+        self.receiver.dropped_count()
+    }
+}
+
+impl Stream for GuildMemberCollector {
+    type Item = Arc<GuildMemberAction>;
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Option<Self::Item>> {
+        if self.handle.poll_stopped(ctx) {
+            self.receiver.close();
+            self.stats.complete(CompletionReason::Stopped);
+
+            return Poll::Ready(None);
+        }
+
+        if let Some(ref mut timeout) = self.timeout {
+            match timeout.as_mut().poll(ctx) {
+                Poll::Ready(_) => {
+                    self.stats.complete(CompletionReason::Timeout);
+
+                    return Poll::Ready(None);
+                },
+                Poll::Pending => (),
+            }
+        }
+
+        if let Some(ref mut idle_timeout) = self.idle_timeout {
+            if idle_timeout.is_elapsed(ctx) {
+                self.stats.complete(CompletionReason::IdleTimeout);
+
+                return Poll::Ready(None);
+            }
+        }
+
+        let this = &mut *self;
+        let item = poll_recv(&mut this.receiver, this.async_filter.as_mut(), &this.stats, ctx);
+
+        if let Poll::Ready(None) = item {
+            self.stats.complete(CompletionReason::Disconnected);
+        }
+
+        if let (Poll::Ready(Some(_)), Some(idle_timeout)) = (&item, &mut self.idle_timeout) {
+            idle_timeout.reset();
+        }
+
+        item
+    }
+}
+
+impl Drop for GuildMemberCollector {
+    fn drop(&mut self) {
+        self.receiver.close();
+        self.stats.complete(CompletionReason::Stopped);
+        self.shard.remove_guild_member_filter(self.filter_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{FilterOptions, GuildMemberFilter, LazyGuildMemberAction};
+    use crate::collector::{CollectorStats, FilterFn};
+    use crate::model::event::{GuildMemberAddEvent, GuildMemberRemoveEvent};
+    use crate::model::guild::Member;
+    use crate::model::id::{GuildId, UserId};
+    use crate::model::user::User;
+
+    fn user(id: u64) -> serde_json::Value {
+        json!({
+            "id": id.to_string(),
+            "username": "user",
+            "discriminator": "0001",
+            "avatar": null,
+        })
+    }
+
+    fn member_add(guild_id: u64, user_id: u64) -> GuildMemberAddEvent {
+        serde_json::from_value(json!({
+            "guild_id": guild_id.to_string(),
+            "user": user(user_id),
+            "roles": [],
+            "joined_at": "2021-01-01T00:00:00+00:00",
+            "deaf": false,
+            "mute": false,
+        }))
+        .unwrap()
+    }
+
+    fn member_remove(guild_id: u64, user_id: u64) -> GuildMemberRemoveEvent {
+        serde_json::from_value(json!({
+            "guild_id": guild_id.to_string(),
+            "user": user(user_id),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_joined_by_default() {
+        let options = FilterOptions {
+            guild_id: Some(vec![1]),
+            ..Default::default()
+        };
+        let (filter, _receiver) = GuildMemberFilter::new(options, CollectorStats::default());
+
+        let joined = member_add(1, 2);
+        assert!(filter.is_passing_constraints(&LazyGuildMemberAction::joined(&joined)));
+        assert!(!filter.is_passing_constraints(&LazyGuildMemberAction::left(&member_remove(1, 2))));
+        assert!(!filter.is_passing_constraints(&LazyGuildMemberAction::joined(&member_add(3, 2))));
+
+        let action = LazyGuildMemberAction::joined(&joined).as_arc();
+        assert!(action.is_joined());
+        assert_eq!(action.guild_id(), GuildId(1));
+        assert_eq!(action.user().id, UserId(2));
+    }
+
+    #[test]
+    fn test_left_user() {
+        let options = FilterOptions {
+            user_id: Some(vec![2]),
+            accept_joined: false,
+            accept_left: true,
+            ..Default::default()
+        };
+        let (filter, _receiver) = GuildMemberFilter::new(options, CollectorStats::default());
+
+        let left = member_remove(1, 2);
+        assert!(filter.is_passing_constraints(&LazyGuildMemberAction::left(&left)));
+        assert!(!filter.is_passing_constraints(&LazyGuildMemberAction::left(&member_remove(1, 3))));
+        assert!(!filter.is_passing_constraints(&LazyGuildMemberAction::joined(&member_add(1, 2))));
+
+        let action = LazyGuildMemberAction::left(&left).as_arc();
+        assert!(action.is_left());
+        assert_eq!(action.guild_id(), GuildId(1));
+        assert_eq!(action.user().id, UserId(2));
+    }
+
+    #[test]
+    fn test_filter_functions() {
+        let options = FilterOptions {
+            filter_joined: Some(FilterFn::new(|member: &Member| member.user.id == UserId(2))),
+            filter_left: Some(FilterFn::new(|user: &User| user.id == UserId(3))),
+            accept_left: true,
+            ..Default::default()
+        };
+        let (filter, _receiver) = GuildMemberFilter::new(options, CollectorStats::default());
+
+        assert!(filter.is_passing_filter(&LazyGuildMemberAction::joined(&member_add(1, 2))));
+        assert!(!filter.is_passing_filter(&LazyGuildMemberAction::joined(&member_add(1, 3))));
+        assert!(filter.is_passing_filter(&LazyGuildMemberAction::left(&member_remove(1, 3))));
+        assert!(!filter.is_passing_filter(&LazyGuildMemberAction::left(&member_remove(1, 2))));
+    }
+}
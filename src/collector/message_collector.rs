@@ -11,17 +11,37 @@ use futures::{
     future::BoxFuture,
     stream::{Stream, StreamExt},
 };
-use tokio::sync::mpsc::{
-    unbounded_channel,
-    UnboundedReceiver as Receiver,
-    UnboundedSender as Sender,
-};
+#[cfg(feature = "regex")]
+use regex::Regex;
 #[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
 use tokio::time::{delay_for as sleep, Delay as Sleep};
 #[cfg(feature = "tokio")]
 use tokio::time::{sleep, Sleep};
 
-use crate::{client::bridge::gateway::ShardMessenger, collector::LazyArc, model::channel::Message};
+use crate::{
+    client::bridge::gateway::ShardMessenger,
+    collector::{
+        add_ids,
+        channel,
+        collect_n,
+        collect_within,
+        is_id_accepted,
+        lower_collect_limit,
+        poll_recv,
+        AsyncFilter,
+        AsyncFilterFn,
+        CollectorHandle,
+        CollectorReceiver,
+        CollectorSender,
+        CollectorStats,
+        CompletionReason,
+        FilterFn,
+        FilterId,
+        IdleTimeout,
+        LazyArc,
+    },
+    model::{channel::Message, id::MessageId},
+};
 
 macro_rules! impl_message_collector {
     ($($name:ident;)*) => {
@@ -37,38 +57,178 @@ macro_rules! impl_message_collector {
                     self
                 }
 
+                /// Sets whether only near misses count towards the [`Self::filter_limit`],
+                /// which match all other constraints, but are rejected by [`Self::filter`].
+                ///
+                /// By default, every message the filter checks counts towards the limit.
+                pub fn filter_limit_matching_only(mut self, matching_only: bool) -> Self {
+                    self.filter.as_mut().unwrap().filter_limit_matching_only = matching_only;
+
+                    self
+                }
+
+                /// Limits how many messages can be collected.
+                ///
+                /// A message is considered *collected*, if the message
+                /// passes all the requirements.
+                pub fn collect_limit(mut self, limit: u32) -> Self {
+                    self.filter.as_mut().unwrap().collect_limit = Some(limit);
+
+                    self
+                }
+
+                /// Limits how many messages can wait to be received from the collector.
+                /// Once reached, further messages are dropped rather than waiting, such as
+                /// while the receiving task is busy during a spam wave.
+                ///
+                /// The dropped messages are counted by [`MessageCollector::dropped_count`].
+                /// By default, any number of messages can wait.
+                /// A capacity of `0` is treated as `1`.
+                pub fn channel_capacity(mut self, capacity: usize) -> Self {
+                    self.filter.as_mut().unwrap().channel_capacity = Some(capacity);
+
+                    self
+                }
+
                 /// Sets a filter function where messages passed to the `function` must
                 /// return `true`, otherwise the message won't be collected and failed the filter
                 /// process.
                 /// This is the last instance to pass for a message to count as *collected*.
                 ///
                 /// This function is intended to be a message content filter.
-                pub fn filter<F: Fn(&Arc<Message>) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
-                    self.filter.as_mut().unwrap().filter = Some(Arc::new(function));
+                pub fn filter<F: Fn(&Message) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.filter.as_mut().unwrap().filter = Some(FilterFn::new(function));
+
+                    self
+                }
+
+                /// Sets a filter function taking the message wrapped in an [`Arc`].
+                ///
+                /// Prefer [`Self::filter`], which only clones the message into an
+                /// [`Arc`] once it is known to be collected.
+                #[deprecated(note = "use `filter`, which takes `&Message` instead")]
+                pub fn filter_arc<F: Fn(&Arc<Message>) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.filter.as_mut().unwrap().filter = Some(FilterFn::new_arc(function));
+
+                    self
+                }
+
+                /// Sets an asynchronous filter function where messages passed to the
+                /// function must resolve to `true`, otherwise they won't be collected.
+                ///
+                /// This runs on the collector's end once all other requirements passed,
+                /// including [`Self::filter`], so it may wait on a database or HTTP request.
+                /// Rejected messages do not count towards [`Self::collect_limit`].
+                pub fn async_filter<F: Fn(&Arc<Message>) -> BoxFuture<'static, bool> + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.async_filter = Some(AsyncFilterFn::new(function));
+
+                    self
+                }
+
+                /// Only collects messages whose content equals one of the
+                /// given `values`, such as `["yes", "no"]`.
+                ///
+                /// This is checked before the [`Self::filter`] function, and
+                /// replaces any content filter set before.
+                pub fn content_equals<S: Into<String>>(mut self, values: impl IntoIterator<Item = S>) -> Self {
+                    let values = values.into_iter().map(Into::into).collect();
+                    self.filter.as_mut().unwrap().content = Some(ContentMatch::Equals(values));
+
+                    self
+                }
+
+                /// Only collects messages whose content starts with one of the
+                /// given `prefixes`.
+                ///
+                /// This is checked before the [`Self::filter`] function, and
+                /// replaces any content filter set before.
+                pub fn content_starts_with<S: Into<String>>(mut self, prefixes: impl IntoIterator<Item = S>) -> Self {
+                    let prefixes = prefixes.into_iter().map(Into::into).collect();
+                    self.filter.as_mut().unwrap().content = Some(ContentMatch::StartsWith(prefixes));
+
+                    self
+                }
+
+                /// Only collects messages whose content matches the `regex`.
+                ///
+                /// This is checked before the [`Self::filter`] function, and
+                /// replaces any content filter set before.
+                ///
+                /// **Note**: [`Self::case_insensitive`] does not apply to
+                /// regular expressions; use the `(?i)` flag instead.
+                #[cfg(feature = "regex")]
+                pub fn content_matches(mut self, regex: Regex) -> Self {
+                    self.filter.as_mut().unwrap().content = Some(ContentMatch::Regex(regex));
+
+                    self
+                }
+
+                /// Sets whether [`Self::content_equals`] and
+                /// [`Self::content_starts_with`] ignore case.
+                ///
+                /// **Note**: This defaults to `false`.
+                pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+                    self.filter.as_mut().unwrap().case_insensitive = case_insensitive;
 
                     self
                 }
 
                 /// Sets the required author ID of a message.
                 /// If a message does not meet this ID, it won't be received.
+                ///
+                /// Calling this multiple times accepts messages matching any of the IDs.
                 pub fn author_id(mut self, author_id: impl Into<u64>) -> Self {
-                    self.filter.as_mut().unwrap().author_id = Some(author_id.into());
+                    add_ids(&mut self.filter.as_mut().unwrap().author_id, Some(author_id));
+
+                    self
+                }
+
+                /// Like [`Self::author_id`], but adds multiple IDs at once.
+                pub fn author_ids(mut self, author_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().author_id, author_ids);
 
                     self
                 }
 
                 /// Sets the required channel ID of a message.
                 /// If a message does not meet this ID, it won't be received.
+                ///
+                /// Calling this multiple times accepts messages matching any of the IDs.
                 pub fn channel_id(mut self, channel_id: impl Into<u64>) -> Self {
-                    self.filter.as_mut().unwrap().channel_id = Some(channel_id.into());
+                    add_ids(&mut self.filter.as_mut().unwrap().channel_id, Some(channel_id));
+
+                    self
+                }
+
+                /// Like [`Self::channel_id`], but adds multiple IDs at once.
+                pub fn channel_ids(mut self, channel_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().channel_id, channel_ids);
 
                     self
                 }
 
                 /// Sets the required guild ID of a message.
                 /// If a message does not meet this ID, it won't be received.
+                ///
+                /// Calling this multiple times accepts messages matching any of the IDs.
                 pub fn guild_id(mut self, guild_id: impl Into<u64>) -> Self {
-                    self.filter.as_mut().unwrap().guild_id = Some(guild_id.into());
+                    add_ids(&mut self.filter.as_mut().unwrap().guild_id, Some(guild_id));
+
+                    self
+                }
+
+                /// Like [`Self::guild_id`], but adds multiple IDs at once.
+                pub fn guild_ids(mut self, guild_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().guild_id, guild_ids);
+
+                    self
+                }
+
+                /// Sets the required ID of the message a message replies to.
+                /// If a message does not reply to this message, it won't be
+                /// received.
+                pub fn message_id(mut self, message_id: impl Into<u64>) -> Self {
+                    self.filter.as_mut().unwrap().message_id = Some(message_id.into());
 
                     self
                 }
@@ -80,6 +240,23 @@ macro_rules! impl_message_collector {
 
                     self
                 }
+
+                /// Sets a `duration` after which the collector stops if it has not
+                /// collected a message meanwhile, restarting whenever one is collected.
+                ///
+                /// This can be combined with [`Self::timeout`], stopping the collector
+                /// once either of them elapsed.
+                pub fn idle_timeout(mut self, duration: Duration) -> Self {
+                    self.idle_timeout = Some(duration);
+
+                    self
+                }
+
+                /// Returns a handle to the statistics of the collector, which stays
+                /// usable once this is consumed, such as by awaiting it.
+                pub fn stats(&self) -> CollectorStats {
+                    self.stats.clone()
+                }
             }
         )*
     }
@@ -88,20 +265,23 @@ macro_rules! impl_message_collector {
 /// Filters events on the shard's end and sends them to the collector.
 #[derive(Clone, Debug)]
 pub struct MessageFilter {
-    filtered: u32,
-    collected: u32,
+    id: FilterId,
+    stats: CollectorStats,
     options: FilterOptions,
-    sender: Sender<Arc<Message>>,
+    sender: CollectorSender<Arc<Message>>,
 }
 
 impl MessageFilter {
     /// Creates a new filter
-    fn new(options: FilterOptions) -> (Self, Receiver<Arc<Message>>) {
-        let (sender, receiver) = unbounded_channel();
+    fn new(
+        options: FilterOptions,
+        stats: CollectorStats,
+    ) -> (Self, CollectorReceiver<Arc<Message>>) {
+        let (sender, receiver) = channel(options.channel_capacity);
 
         let filter = Self {
-            filtered: 0,
-            collected: 0,
+            id: FilterId::next(),
+            stats,
             sender,
             options,
         };
@@ -109,21 +289,27 @@ impl MessageFilter {
         (filter, receiver)
     }
 
+    /// The ID of the filter, identifying it on the shard runner.
+    pub fn id(&self) -> FilterId {
+        self.id
+    }
+
+    /// Whether the collector receiving from this filter was dropped.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
     /// Sends a `message` to the consuming collector if the `message` conforms
     /// to the constraints and the limits are not reached yet.
     pub(crate) fn send_message(&mut self, message: &mut LazyArc<'_, Message>) -> bool {
-        if self.is_passing_constraints(message) {
-            // TODO: On next branch, switch filter arg to &T so this as_arc() call can be removed.
-            if self.options.filter.as_ref().map_or(true, |f| f(&message.as_arc())) {
-                self.collected += 1;
+        let is_matching = self.is_passing_constraints(message);
+        let is_passing = is_matching && self.is_passing_filter(message);
 
-                if let Err(_) = self.sender.send(message.as_arc()) {
-                    return false;
-                }
-            }
+        if is_passing && !self.sender.send(message.as_arc(), &self.stats) {
+            return false;
         }
 
-        self.filtered += 1;
+        self.stats.add_checked(self.options.filter_limit_matching_only, is_matching && !is_passing);
 
         self.is_within_limits() && !self.sender.is_closed()
     }
@@ -132,28 +318,76 @@ impl MessageFilter {
     /// Constraints are optional, as it is possible to limit messages to
     /// be sent by a specific author or in a specific guild.
     fn is_passing_constraints(&self, message: &Message) -> bool {
-        self.options.guild_id.map_or(true, |g| Some(g) == message.guild_id.map(|g| g.0))
-            && self.options.channel_id.map_or(true, |g| g == message.channel_id.0)
-            && self.options.author_id.map_or(true, |g| g == message.author.id.0)
+        is_id_accepted(self.options.guild_id.as_deref(), message.guild_id.map(|g| g.0))
+            && is_id_accepted(self.options.channel_id.as_deref(), Some(message.channel_id.0))
+            && is_id_accepted(self.options.author_id.as_deref(), Some(message.author.id.0))
+            && self.options.message_id.map_or(true, |g| {
+                message.message_reference.as_ref().and_then(|r| r.message_id) == Some(MessageId(g))
+            })
+            && self.options.is_content_matching(&message.content)
+    }
+
+    /// Checks if the `message` passes the filter function, if any.
+    fn is_passing_filter(&self, message: &mut LazyArc<'_, Message>) -> bool {
+        self.options.filter.as_ref().map_or(true, |f| f.call(message))
     }
 
     /// Checks if the filter is within set receive and collect limits.
     /// A message is considered *received* even when it does not meet the
-    /// constraints.
+    /// constraints, unless only near misses are counted.
     fn is_within_limits(&self) -> bool {
-        self.options.filter_limit.as_ref().map_or(true, |limit| self.filtered < *limit)
-            && self.options.collect_limit.as_ref().map_or(true, |limit| self.collected < *limit)
+        self.stats.is_within_limits(self.options.filter_limit, self.options.collect_limit)
     }
 }
 
 #[derive(Clone, Default)]
 struct FilterOptions {
     filter_limit: Option<u32>,
+    filter_limit_matching_only: bool,
     collect_limit: Option<u32>,
-    filter: Option<Arc<dyn Fn(&Arc<Message>) -> bool + 'static + Send + Sync>>,
-    channel_id: Option<u64>,
-    guild_id: Option<u64>,
-    author_id: Option<u64>,
+    channel_capacity: Option<usize>,
+    filter: Option<FilterFn<Message>>,
+    channel_id: Option<Vec<u64>>,
+    guild_id: Option<Vec<u64>>,
+    author_id: Option<Vec<u64>>,
+    message_id: Option<u64>,
+    content: Option<ContentMatch>,
+    case_insensitive: bool,
+}
+
+impl FilterOptions {
+    /// Checks if the `content` passes the content filter, if any.
+    fn is_content_matching(&self, content: &str) -> bool {
+        let content_match = match &self.content {
+            Some(content_match) => content_match,
+            None => return true,
+        };
+
+        let normalize =
+            |s: &str| if self.case_insensitive { s.to_lowercase() } else { s.to_string() };
+
+        match content_match {
+            ContentMatch::Equals(values) => {
+                let content = normalize(content);
+                values.iter().any(|value| normalize(value) == content)
+            },
+            ContentMatch::StartsWith(prefixes) => {
+                let content = normalize(content);
+                prefixes.iter().any(|prefix| content.starts_with(&normalize(prefix)))
+            },
+            #[cfg(feature = "regex")]
+            ContentMatch::Regex(regex) => regex.is_match(content),
+        }
+    }
+}
+
+/// A declarative filter on the content of a message.
+#[derive(Clone, Debug)]
+enum ContentMatch {
+    Equals(Vec<String>),
+    StartsWith(Vec<String>),
+    #[cfg(feature = "regex")]
+    Regex(Regex),
 }
 
 // Implement the common setters for all message collector types.
@@ -167,6 +401,9 @@ pub struct MessageCollectorBuilder<'a> {
     filter: Option<FilterOptions>,
     shard: Option<ShardMessenger>,
     timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<Message>>,
+    stats: CollectorStats,
     fut: Option<BoxFuture<'a, MessageCollector>>,
 }
 
@@ -177,19 +414,62 @@ impl<'a> MessageCollectorBuilder<'a> {
             filter: Some(FilterOptions::default()),
             shard: Some(shard_messenger.as_ref().clone()),
             timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
             fut: None,
         }
     }
 
-    /// Limits how many messages can be collected.
+    /// Builds the collector and waits for up to `n` messages.
+    ///
+    /// Collection ends early once the [`Self::timeout`], the
+    /// [`Self::idle_timeout`], the [`Self::collect_limit`] or the
+    /// [`Self::filter_limit`] is reached, whichever comes first, returning the
+    /// messages collected so far.
+    ///
+    /// The collect limit is lowered to `n` as well, so the collector ends on
+    /// its own once `n` messages were collected, with its [`CollectorStats`]
+    /// reporting [`CompletionReason::CollectLimit`].
+    ///
+    /// Dropping the returned future stops the collector, discarding the
+    /// messages collected so far.
+    ///
+    /// # Examples
+    ///
+    /// Collect the next 5 messages sent in a channel, waiting for at most 30
+    /// seconds:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # use serenity::model::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # async fn run(ctx: Context, channel_id: ChannelId) {
+    /// let messages = channel_id
+    ///     .await_replies(&ctx)
+    ///     .timeout(Duration::from_secs(30))
+    ///     .collect_n(5)
+    ///     .await;
     ///
-    /// A message is considered *collected*, if the message
-    /// passes all the requirements.
+    /// for message in messages {
+    ///     println!("{}: {}", message.author.name, message.content);
+    /// }
+    /// # }
+    /// ```
     #[allow(clippy::unwrap_used)]
-    pub fn collect_limit(mut self, limit: u32) -> Self {
-        self.filter.as_mut().unwrap().collect_limit = Some(limit);
+    pub async fn collect_n(mut self, n: usize) -> Vec<Arc<Message>> {
+        lower_collect_limit(&mut self.filter.as_mut().unwrap().collect_limit, n);
 
-        self
+        collect_n(self.await, n).await
+    }
+
+    /// Builds the collector and returns all messages collected within the
+    /// given `duration`.
+    ///
+    /// Refer to [`Self::collect_n`] for when collection ends early.
+    pub async fn collect_within(self, duration: Duration) -> Vec<Arc<Message>> {
+        collect_within(self.await, duration).await
     }
 }
 
@@ -199,15 +479,30 @@ impl<'a> Future for MessageCollectorBuilder<'a> {
     fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
         if self.fut.is_none() {
             let shard_messenger = self.shard.take().unwrap();
-            let (filter, receiver) = MessageFilter::new(self.filter.take().unwrap());
+            let collect_limit = self.filter.as_ref().unwrap().collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) =
+                MessageFilter::new(self.filter.take().unwrap(), self.stats.clone());
             let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
 
             self.fut = Some(Box::pin(async move {
-                shard_messenger.set_message_filter(filter);
+                let filter_id = filter.id();
+                shard_messenger.set_message_filter_sync(filter).await;
 
                 MessageCollector {
                     receiver: Box::pin(receiver),
                     timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
                 }
             }))
         }
@@ -220,6 +515,9 @@ pub struct CollectReply<'a> {
     filter: Option<FilterOptions>,
     shard: Option<ShardMessenger>,
     timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<Message>>,
+    stats: CollectorStats,
     fut: Option<BoxFuture<'a, Option<Arc<Message>>>>,
 }
 
@@ -229,6 +527,9 @@ impl<'a> CollectReply<'a> {
             filter: Some(FilterOptions::default()),
             shard: Some(shard_messenger.as_ref().clone()),
             timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
             fut: None,
         }
     }
@@ -240,15 +541,30 @@ impl<'a> Future for CollectReply<'a> {
     fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
         if self.fut.is_none() {
             let shard_messenger = self.shard.take().unwrap();
-            let (filter, receiver) = MessageFilter::new(self.filter.take().unwrap());
+            let collect_limit = self.filter.as_ref().unwrap().collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) =
+                MessageFilter::new(self.filter.take().unwrap(), self.stats.clone());
             let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
 
             self.fut = Some(Box::pin(async move {
-                shard_messenger.set_message_filter(filter);
+                let filter_id = filter.id();
+                shard_messenger.set_message_filter_sync(filter).await;
 
                 MessageCollector {
                     receiver: Box::pin(receiver),
                     timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
                 }
                 .next()
                 .await
@@ -263,10 +579,14 @@ impl std::fmt::Debug for FilterOptions {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MessageFilter")
             .field("collect_limit", &self.collect_limit)
-            .field("filter", &"Option<Arc<dyn Fn(&Arc<Message>) -> bool + 'static + Send + Sync>>")
+            .field("channel_capacity", &self.channel_capacity)
+            .field("filter", &self.filter)
             .field("channel_id", &self.channel_id)
             .field("guild_id", &self.guild_id)
             .field("author_id", &self.author_id)
+            .field("message_id", &self.message_id)
+            .field("content", &self.content)
+            .field("case_insensitive", &self.case_insensitive)
             .finish()
     }
 }
@@ -274,8 +594,14 @@ impl std::fmt::Debug for FilterOptions {
 /// A message collector receives messages matching the given filter for a
 /// set duration.
 pub struct MessageCollector {
-    receiver: Pin<Box<Receiver<Arc<Message>>>>,
+    receiver: Pin<Box<CollectorReceiver<Arc<Message>>>>,
     timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<IdleTimeout>,
+    async_filter: Option<AsyncFilter<Message>>,
+    shard: ShardMessenger,
+    filter_id: FilterId,
+    handle: CollectorHandle,
+    stats: CollectorStats,
 }
 
 impl MessageCollector {
@@ -283,29 +609,473 @@ impl MessageCollector {
     /// collector drops.
     /// In case the drop does not appear until later, it is preferred to
     /// stop the collector early.
+    ///
+    /// Either way, the collector's filter is removed from the shard.
     pub fn stop(mut self) {
         self.receiver.close();
     }
+
+    /// Returns a handle to stop the collector from another task, such as
+    /// while this one is waiting for the collector's next item.
+    pub fn handle(&self) -> CollectorHandle {
+        self.handle.clone()
+    }
+
+    /// Returns a handle to the statistics of the collector, such as to check
+    /// why it ended.
+    pub fn stats(&self) -> CollectorStats {
+        self.stats.clone()
+    }
+
+    /// The number of messages which were dropped as the [channel capacity] was
+    /// reached.
+    ///
+    /// [channel capacity]: MessageCollectorBuilder::channel_capacity
+    pub fn dropped_count(&self) -> u64 {
+        self.receiver.dropped_count()
+    }
 }
 
 impl Stream for MessageCollector {
     type Item = Arc<Message>;
     fn poll_next(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Option<Self::Item>> {
+        if self.handle.poll_stopped(ctx) {
+            self.receiver.close();
+            self.stats.complete(CompletionReason::Stopped);
+
+            return Poll::Ready(None);
+        }
+
         if let Some(ref mut timeout) = self.timeout {
             match timeout.as_mut().poll(ctx) {
                 Poll::Ready(_) => {
+                    self.stats.complete(CompletionReason::Timeout);
+
                     return Poll::Ready(None);
                 },
                 Poll::Pending => (),
             }
         }
 
-        self.receiver.as_mut().poll_recv(ctx)
+        if let Some(ref mut idle_timeout) = self.idle_timeout {
+            if idle_timeout.is_elapsed(ctx) {
+                self.stats.complete(CompletionReason::IdleTimeout);
+
+                return Poll::Ready(None);
+            }
+        }
+
+        let this = &mut *self;
+        let item = poll_recv(&mut this.receiver, this.async_filter.as_mut(), &this.stats, ctx);
+
+        if let Poll::Ready(None) = item {
+            self.stats.complete(CompletionReason::Disconnected);
+        }
+
+        if let (Poll::Ready(Some(_)), Some(idle_timeout)) = (&item, &mut self.idle_timeout) {
+            idle_timeout.reset();
+        }
+
+        item
     }
 }
 
 impl Drop for MessageCollector {
     fn drop(&mut self) {
         self.receiver.close();
+        self.stats.complete(CompletionReason::Stopped);
+        self.shard.remove_message_filter(self.filter_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::task::Poll;
+    use std::time::Duration;
+
+    use futures::channel::mpsc::unbounded;
+    use futures::StreamExt;
+    use serde_json::json;
+    use tokio::time::{sleep, Instant};
+
+    use super::{
+        ContentMatch,
+        FilterOptions,
+        MessageCollector,
+        MessageCollectorBuilder,
+        MessageFilter,
+    };
+    use crate::client::bridge::gateway::{ShardClientMessage, ShardMessenger, ShardRunnerMessage};
+    use crate::collector::{
+        channel,
+        CollectorHandle,
+        CollectorSender,
+        CollectorStats,
+        CompletionReason,
+        FilterId,
+        IdleTimeout,
+        LazyArc,
+    };
+    use crate::gateway::InterMessage;
+    use crate::model::channel::Message;
+
+    fn options(content: ContentMatch, case_insensitive: bool) -> FilterOptions {
+        FilterOptions {
+            content: Some(content),
+            case_insensitive,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_content_equals() {
+        let values = vec!["yes".to_string(), "no".to_string()];
+
+        let filter = options(ContentMatch::Equals(values.clone()), false);
+        assert!(filter.is_content_matching("yes"));
+        assert!(filter.is_content_matching("no"));
+        assert!(!filter.is_content_matching("YES"));
+        assert!(!filter.is_content_matching("yes please"));
+
+        let filter = options(ContentMatch::Equals(values), true);
+        assert!(filter.is_content_matching("YES"));
+        assert!(filter.is_content_matching("No"));
+        assert!(!filter.is_content_matching("maybe"));
+    }
+
+    #[test]
+    fn test_content_starts_with() {
+        let prefixes = vec!["y".to_string(), "N".to_string()];
+
+        let filter = options(ContentMatch::StartsWith(prefixes.clone()), false);
+        assert!(filter.is_content_matching("yes"));
+        assert!(filter.is_content_matching("Nope"));
+        assert!(!filter.is_content_matching("nope"));
+
+        let filter = options(ContentMatch::StartsWith(prefixes), true);
+        assert!(filter.is_content_matching("Yeah"));
+        assert!(filter.is_content_matching("nope"));
+        assert!(!filter.is_content_matching("maybe"));
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    #[allow(clippy::unwrap_used)]
+    fn test_content_matches() {
+        use regex::Regex;
+
+        let filter = options(ContentMatch::Regex(Regex::new(r"^\d+$").unwrap()), false);
+        assert!(filter.is_content_matching("42"));
+        assert!(!filter.is_content_matching("42 apples"));
+    }
+
+    #[test]
+    fn test_no_content_filter() {
+        assert!(FilterOptions::default().is_content_matching("anything"));
+    }
+
+    #[allow(clippy::unwrap_used)]
+    fn message() -> Message {
+        serde_json::from_value(json!({
+            "id": "846962698468081685",
+            "channel_id": "381880193700069377",
+            "author": {
+                "id": "210104891282489345",
+                "username": "fake",
+                "discriminator": "0001",
+                "avatar": null,
+            },
+            "content": "hello",
+            "timestamp": "2021-05-25T00:00:00+00:00",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "embeds": [],
+            "pinned": false,
+            "type": 0,
+        }))
+        .unwrap()
+    }
+
+    fn runner_message(message: Option<InterMessage>) -> ShardRunnerMessage {
+        match message {
+            Some(InterMessage::Client(message)) => match *message {
+                ShardClientMessage::Runner(message) => message,
+                other => panic!("unexpected message: {:?}", other),
+            },
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    #[allow(clippy::unwrap_used)]
+    async fn test_builder_waits_for_filter() {
+        let (sender, mut receiver) = unbounded();
+        let messenger = ShardMessenger::new(sender);
+
+        let builder = MessageCollectorBuilder::new(&messenger);
+        let stats = builder.stats();
+        let mut builder = tokio_test::task::spawn(builder);
+        assert!(builder.poll().is_pending());
+
+        // Act as the shard runner, installing the filter before acknowledging.
+        let mut filter = match runner_message(receiver.try_next().unwrap()) {
+            ShardRunnerMessage::SetMessageFilter(filter) => filter,
+            other => panic!("unexpected message: {:?}", other),
+        };
+        assert!(builder.poll().is_pending());
+
+        // The builder waits for the acknowledgement, however long it takes.
+        sleep(Duration::from_secs(60)).await;
+        assert!(builder.poll().is_pending());
+
+        match runner_message(receiver.try_next().unwrap()) {
+            ShardRunnerMessage::Acknowledge(ack) => ack.send(),
+            other => panic!("unexpected message: {:?}", other),
+        }
+
+        let mut collector = match builder.poll() {
+            Poll::Ready(collector) => collector,
+            Poll::Pending => panic!("the builder did not resolve after the acknowledgement"),
+        };
+
+        // A message received right after the builder resolved is collected.
+        let message = message();
+
+        assert!(filter.send_message(&mut LazyArc::new(&message)));
+        assert_eq!(collector.next().await.unwrap().id, message.id);
+        assert_eq!((stats.filtered(), stats.collected()), (1, 1));
+        assert_eq!(stats.completion_reason(), None);
+
+        // Dropping the collector removes its filter from the runner.
+        drop(collector);
+        assert_eq!(stats.completion_reason(), Some(CompletionReason::Stopped));
+
+        match runner_message(receiver.try_next().unwrap()) {
+            ShardRunnerMessage::RemoveMessageFilter(id) => assert_eq!(id, filter.id()),
+            other => panic!("unexpected message: {:?}", other),
+        }
+        assert!(filter.is_closed());
+    }
+
+    #[tokio::test(start_paused = true)]
+    #[allow(clippy::unwrap_used)]
+    async fn test_collect_n_sets_collect_limit() {
+        let (sender, mut receiver) = unbounded();
+        let messenger = ShardMessenger::new(sender);
+
+        let builder = MessageCollectorBuilder::new(&messenger).collect_limit(5);
+        let stats = builder.stats();
+        let mut collected = tokio_test::task::spawn(builder.collect_n(2));
+        assert!(collected.poll().is_pending());
+
+        let mut filter = match runner_message(receiver.try_next().unwrap()) {
+            ShardRunnerMessage::SetMessageFilter(filter) => filter,
+            other => panic!("unexpected message: {:?}", other),
+        };
+        assert_eq!(filter.options.collect_limit, Some(2));
+        assert!(collected.poll().is_pending());
+
+        match runner_message(receiver.try_next().unwrap()) {
+            ShardRunnerMessage::Acknowledge(ack) => ack.send(),
+            other => panic!("unexpected message: {:?}", other),
+        }
+        assert!(collected.poll().is_pending());
+
+        // The filter is removed by the collect limit, rather than by dropping
+        // the collector once enough messages were taken.
+        let message = message();
+        assert!(filter.send_message(&mut LazyArc::new(&message)));
+        assert!(!filter.send_message(&mut LazyArc::new(&message)));
+
+        match collected.poll() {
+            Poll::Ready(messages) => assert_eq!(messages.len(), 2),
+            Poll::Pending => panic!("the collector did not end at its collect limit"),
+        }
+        assert_eq!(stats.completion_reason(), Some(CompletionReason::CollectLimit));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_builder_resolves_without_runner() {
+        let (sender, receiver) = unbounded();
+        let messenger = ShardMessenger::new(sender);
+
+        let mut builder = tokio_test::task::spawn(MessageCollectorBuilder::new(&messenger));
+        assert!(builder.poll().is_pending());
+
+        // The runner stopping without handling the messages must not hang the
+        // builder.
+        drop(receiver);
+        assert!(builder.is_woken());
+        assert!(builder.poll().is_ready());
+    }
+
+    fn collector(
+        timeout: Option<Duration>,
+        idle_timeout: Option<Duration>,
+    ) -> (CollectorSender<Arc<Message>>, MessageCollector) {
+        let (sender, receiver) = channel(None);
+        let collector = MessageCollector {
+            receiver: Box::pin(receiver),
+            timeout: timeout.map(|duration| Box::pin(sleep(duration))),
+            idle_timeout: idle_timeout.map(IdleTimeout::new),
+            async_filter: None,
+            shard: ShardMessenger::new(unbounded().0),
+            filter_id: FilterId::next(),
+            handle: CollectorHandle::new(),
+            stats: CollectorStats::default(),
+        };
+
+        (sender, collector)
+    }
+
+    #[tokio::test(start_paused = true)]
+    #[allow(clippy::unwrap_used)]
+    async fn test_idle_timeout_rearms() {
+        let message = Arc::new(message());
+        let stats = CollectorStats::default();
+        let (sender, mut collector) = collector(None, Some(Duration::from_secs(10)));
+
+        // Every collected message restarts the idle timeout, so the collector
+        // outlives it as long as messages keep coming in.
+        for _ in 0..3 {
+            sleep(Duration::from_secs(8)).await;
+            assert!(sender.send(Arc::clone(&message), &stats));
+            assert!(collector.next().await.is_some());
+        }
+
+        let start = Instant::now();
+        assert!(collector.next().await.is_none());
+        assert_eq!(start.elapsed().as_secs(), 10);
+        assert_eq!(collector.stats().completion_reason(), Some(CompletionReason::IdleTimeout));
+    }
+
+    #[tokio::test(start_paused = true)]
+    #[allow(clippy::unwrap_used)]
+    async fn test_idle_timeout_with_timeout() {
+        let message = Arc::new(message());
+        let stats = CollectorStats::default();
+        let (sender, mut collector) =
+            collector(Some(Duration::from_secs(20)), Some(Duration::from_secs(10)));
+
+        for _ in 0..2 {
+            sleep(Duration::from_secs(8)).await;
+            assert!(sender.send(Arc::clone(&message), &stats));
+            assert!(collector.next().await.is_some());
+        }
+
+        // The timeout ends the collector regardless of its activity.
+        sleep(Duration::from_secs(5)).await;
+        assert!(sender.send(Arc::clone(&message), &stats));
+        assert!(collector.next().await.is_none());
+        assert_eq!(collector.stats().completion_reason(), Some(CompletionReason::Timeout));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_handle_stops_collector() {
+        let message = Arc::new(message());
+        let stats = CollectorStats::default();
+        let (sender, mut collector) = collector(None, None);
+        let handle = collector.handle();
+
+        tokio::spawn(async move {
+            sleep(Duration::from_secs(5)).await;
+            handle.stop();
+        });
+
+        assert!(sender.send(Arc::clone(&message), &stats));
+        assert!(collector.next().await.is_some());
+
+        // The collector waiting for its next message ends once stopped.
+        let start = Instant::now();
+        assert!(collector.next().await.is_none());
+        assert_eq!(start.elapsed().as_secs(), 5);
+        assert!(collector.handle().is_stopped());
+        assert!(!sender.send(message, &stats));
+        assert_eq!(collector.stats().completion_reason(), Some(CompletionReason::Stopped));
+    }
+
+    #[tokio::test]
+    async fn test_completion_by_filter() {
+        let message = message();
+        let stats = CollectorStats::default();
+        let options = FilterOptions {
+            collect_limit: Some(2),
+            ..Default::default()
+        };
+        let (mut filter, _receiver) = MessageFilter::new(options, stats.clone());
+
+        // The filter is removed once its collect limit is reached.
+        assert!(filter.send_message(&mut LazyArc::new(&message)));
+        assert!(!filter.send_message(&mut LazyArc::new(&message)));
+        assert_eq!((stats.filtered(), stats.collected()), (2, 2));
+        assert_eq!(stats.completion_reason(), Some(CompletionReason::CollectLimit));
+
+        // The shard dropping the filter otherwise disconnects the collector.
+        let (sender, mut collector) = collector(None, None);
+        drop(sender);
+        assert!(collector.next().await.is_none());
+        assert_eq!(collector.stats().completion_reason(), Some(CompletionReason::Disconnected));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_sync_and_async_filter() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        use futures::future::{self, FutureExt};
+
+        use crate::collector::{AsyncFilter, AsyncFilterFn, FilterFn};
+
+        let stats = CollectorStats::default();
+        let options = FilterOptions {
+            collect_limit: Some(1),
+            filter: Some(FilterFn::new(|message: &Message| message.content != "sync")),
+            ..Default::default()
+        };
+
+        let awaited = Arc::new(AtomicUsize::new(0));
+        let async_awaited = Arc::clone(&awaited);
+        let function = AsyncFilterFn::new(move |message: &Arc<Message>| {
+            async_awaited.fetch_add(1, Ordering::Relaxed);
+
+            future::ready(message.content == "accepted").boxed()
+        });
+        let async_filter = AsyncFilter::new(function, options.collect_limit, &stats);
+
+        let (mut filter, receiver) = MessageFilter::new(options, stats.clone());
+        let mut collector = MessageCollector {
+            receiver: Box::pin(receiver),
+            timeout: None,
+            idle_timeout: None,
+            async_filter: Some(async_filter),
+            shard: ShardMessenger::new(unbounded().0),
+            filter_id: filter.id(),
+            handle: CollectorHandle::new(),
+            stats: stats.clone(),
+        };
+
+        // Messages rejected by the async filter do not reach the collect limit.
+        for content in &["sync", "async", "accepted", "late"] {
+            let mut message = message();
+            message.content = (*content).to_string();
+
+            assert!(filter.send_message(&mut LazyArc::new(&message)));
+        }
+        assert_eq!((stats.filtered(), stats.collected()), (4, 0));
+
+        assert_eq!(collector.next().await.unwrap().content, "accepted");
+        assert_eq!((stats.filtered(), stats.collected()), (4, 1));
+
+        // The async filter only sees the messages the sync filter passed.
+        assert_eq!(awaited.load(Ordering::Relaxed), 2);
+
+        assert!(collector.next().await.is_none());
+        assert_eq!(stats.completion_reason(), Some(CompletionReason::CollectLimit));
+        assert!(!filter.send_message(&mut LazyArc::new(&message())));
     }
 }
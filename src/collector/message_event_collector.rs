@@ -0,0 +1,998 @@
+use std::{
+    boxed::Box,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context as FutContext, Poll},
+    time::Duration,
+};
+
+use futures::{
+    future::BoxFuture,
+    stream::{Stream, StreamExt},
+};
+#[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
+use tokio::time::{delay_for as sleep, Delay as Sleep};
+#[cfg(feature = "tokio")]
+use tokio::time::{sleep, Sleep};
+
+use crate::{
+    client::bridge::gateway::ShardMessenger,
+    collector::{
+        add_ids,
+        channel,
+        collect_n,
+        collect_within,
+        is_id_accepted,
+        lower_collect_limit,
+        poll_recv,
+        AsyncFilter,
+        AsyncFilterFn,
+        CollectorHandle,
+        CollectorReceiver,
+        CollectorSender,
+        CollectorStats,
+        CompletionReason,
+        FilterFn,
+        FilterId,
+        IdleTimeout,
+        LazyArc,
+    },
+    model::{
+        channel::Message,
+        event::MessageUpdateEvent,
+        id::{ChannelId, GuildId, MessageId, UserId},
+    },
+};
+
+macro_rules! impl_message_event_collector {
+    ($($name:ident;)*) => {
+        $(
+            impl<'a> $name<'a> {
+                /// Limits how many message events will attempt to be filtered.
+                ///
+                /// The filter checks whether the message is the right message,
+                /// sent by the right author in the right guild and channel.
+                pub fn filter_limit(mut self, limit: u32) -> Self {
+                    self.filter.as_mut().unwrap().filter_limit = Some(limit);
+
+                    self
+                }
+
+                /// Sets whether only near misses count towards the [`Self::filter_limit`],
+                /// which match all other constraints, but are rejected by [`Self::filter`].
+                ///
+                /// By default, every message event the filter checks counts towards the limit.
+                pub fn filter_limit_matching_only(mut self, matching_only: bool) -> Self {
+                    self.filter.as_mut().unwrap().filter_limit_matching_only = matching_only;
+
+                    self
+                }
+
+                /// Limits how many message events can be collected.
+                ///
+                /// A message event is considered *collected*, if the event
+                /// passes all the requirements.
+                pub fn collect_limit(mut self, limit: u32) -> Self {
+                    self.filter.as_mut().unwrap().collect_limit = Some(limit);
+
+                    self
+                }
+
+                /// Limits how many message events can wait to be received from the
+                /// collector. Once reached, further events are dropped rather than waiting,
+                /// such as while the receiving task is busy.
+                ///
+                /// The dropped events are counted by [`MessageEventCollector::dropped_count`].
+                /// By default, any number of events can wait.
+                /// A capacity of `0` is treated as `1`.
+                pub fn channel_capacity(mut self, capacity: usize) -> Self {
+                    self.filter.as_mut().unwrap().channel_capacity = Some(capacity);
+
+                    self
+                }
+
+                /// Sets a filter function where message events passed to the function
+                /// must return `true`, otherwise the event won't be collected.
+                /// This is the last instance to pass for an event to count as *collected*.
+                pub fn filter<F: Fn(&MessageAction) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.filter.as_mut().unwrap().filter = Some(FilterFn::new(function));
+
+                    self
+                }
+
+                /// Sets an asynchronous filter function where message events passed to the
+                /// function must resolve to `true`, otherwise they won't be collected.
+                ///
+                /// This runs on the collector's end once all other requirements passed,
+                /// including [`Self::filter`], so it may wait on a database or HTTP request.
+                /// Rejected message events do not count towards [`Self::collect_limit`].
+                pub fn async_filter<F: Fn(&Arc<MessageAction>) -> BoxFuture<'static, bool> + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.async_filter = Some(AsyncFilterFn::new(function));
+
+                    self
+                }
+
+                /// Sets the message which must be edited or deleted.
+                /// If another message is edited or deleted, it won't be received.
+                ///
+                /// Calling this multiple times accepts any of the messages.
+                pub fn message_id(mut self, message_id: impl Into<u64>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().message_id, Some(message_id));
+
+                    self
+                }
+
+                /// Like [`Self::message_id`], but adds multiple IDs at once.
+                pub fn message_ids(mut self, message_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().message_id, message_ids);
+
+                    self
+                }
+
+                /// Sets the channel in which the message must be edited or deleted.
+                /// If a message is edited or deleted in another channel, it won't be received.
+                ///
+                /// Calling this multiple times accepts any of the channels.
+                pub fn channel_id(mut self, channel_id: impl Into<u64>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().channel_id, Some(channel_id));
+
+                    self
+                }
+
+                /// Like [`Self::channel_id`], but adds multiple IDs at once.
+                pub fn channel_ids(mut self, channel_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().channel_id, channel_ids);
+
+                    self
+                }
+
+                /// Sets the guild in which the message must be edited or deleted.
+                /// If a message is edited or deleted in another guild, it won't be received.
+                ///
+                /// Calling this multiple times accepts any of the guilds.
+                pub fn guild_id(mut self, guild_id: impl Into<u64>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().guild_id, Some(guild_id));
+
+                    self
+                }
+
+                /// Like [`Self::guild_id`], but adds multiple IDs at once.
+                pub fn guild_ids(mut self, guild_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().guild_id, guild_ids);
+
+                    self
+                }
+
+                /// Sets the author of the message which must be edited or deleted.
+                /// If a message of another author is edited or deleted, it won't be received.
+                ///
+                /// Calling this multiple times accepts any of the authors.
+                ///
+                /// **Note**: The author of a deleted message is only known if the
+                /// message was cached, so deletions of uncached messages are not
+                /// received once an author is set.
+                pub fn author_id(mut self, author_id: impl Into<u64>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().author_id, Some(author_id));
+
+                    self
+                }
+
+                /// Like [`Self::author_id`], but adds multiple IDs at once.
+                pub fn author_ids(mut self, author_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().author_id, author_ids);
+
+                    self
+                }
+
+                /// If set to `true`, edited messages will be collected.
+                ///
+                /// Set to `true` by default.
+                pub fn updated(mut self, is_accepted: bool) -> Self {
+                    self.filter.as_mut().unwrap().accept_updated = is_accepted;
+
+                    self
+                }
+
+                /// If set to `true`, deleted messages will be collected, including
+                /// messages deleted in bulk.
+                ///
+                /// Set to `true` by default.
+                pub fn deleted(mut self, is_accepted: bool) -> Self {
+                    self.filter.as_mut().unwrap().accept_deleted = is_accepted;
+
+                    self
+                }
+
+                /// Sets a `duration` for how long the collector shall receive
+                /// message events.
+                pub fn timeout(mut self, duration: Duration) -> Self {
+                    self.timeout = Some(Box::pin(sleep(duration)));
+
+                    self
+                }
+
+                /// Sets a `duration` after which the collector stops if it has not
+                /// collected a message event meanwhile, restarting whenever one
+                /// is collected.
+                ///
+                /// This can be combined with [`Self::timeout`], stopping the collector
+                /// once either of them elapsed.
+                pub fn idle_timeout(mut self, duration: Duration) -> Self {
+                    self.idle_timeout = Some(duration);
+
+                    self
+                }
+
+                /// Returns a handle to the statistics of the collector, which stays
+                /// usable once this is consumed, such as by awaiting it.
+                pub fn stats(&self) -> CollectorStats {
+                    self.stats.clone()
+                }
+            }
+        )*
+    }
+}
+
+/// A message being edited or deleted, along with the message as it was
+/// cached before.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum MessageAction {
+    /// A message was edited.
+    Updated {
+        /// The partial update of the message.
+        event: MessageUpdateEvent,
+        /// The message before the update, if it was cached.
+        ///
+        /// **Note**: This is always [`None`] without the `cache` feature.
+        old: Option<Message>,
+    },
+    /// A message was deleted, either on its own or in bulk.
+    Deleted {
+        /// The ID of the guild the message was deleted in.
+        guild_id: Option<GuildId>,
+        /// The ID of the channel the message was deleted in.
+        channel_id: ChannelId,
+        /// The ID of the deleted message.
+        message_id: MessageId,
+        /// The message before it was deleted, if it was cached.
+        ///
+        /// **Note**: This is always [`None`] without the `cache` feature.
+        old: Option<Message>,
+    },
+}
+
+impl MessageAction {
+    /// The ID of the message which was edited or deleted.
+    pub fn message_id(&self) -> MessageId {
+        match self {
+            Self::Updated {
+                event, ..
+            } => event.id,
+            Self::Deleted {
+                message_id, ..
+            } => *message_id,
+        }
+    }
+
+    /// The ID of the channel the message was sent in.
+    pub fn channel_id(&self) -> ChannelId {
+        match self {
+            Self::Updated {
+                event, ..
+            } => event.channel_id,
+            Self::Deleted {
+                channel_id, ..
+            } => *channel_id,
+        }
+    }
+
+    /// The ID of the guild the message was sent in.
+    pub fn guild_id(&self) -> Option<GuildId> {
+        match self {
+            Self::Updated {
+                event, ..
+            } => event.guild_id,
+            Self::Deleted {
+                guild_id, ..
+            } => *guild_id,
+        }
+    }
+
+    /// The ID of the message's author, if known.
+    ///
+    /// This is taken from the cached message if the update does not carry
+    /// the author, and is only known for cached messages if they were deleted.
+    pub fn author_id(&self) -> Option<UserId> {
+        match self {
+            Self::Updated {
+                event,
+                old,
+            } => event.author.as_ref().or_else(|| old.as_ref().map(|m| &m.author)).map(|a| a.id),
+            Self::Deleted {
+                old, ..
+            } => old.as_ref().map(|m| m.author.id),
+        }
+    }
+
+    /// The message before it was edited or deleted, if it was cached.
+    pub fn old(&self) -> Option<&Message> {
+        match self {
+            Self::Updated {
+                old, ..
+            }
+            | Self::Deleted {
+                old, ..
+            } => old.as_ref(),
+        }
+    }
+
+    /// Whether the message was edited.
+    pub fn is_updated(&self) -> bool {
+        matches!(self, Self::Updated { .. })
+    }
+
+    /// Whether the message was deleted.
+    pub fn is_deleted(&self) -> bool {
+        matches!(self, Self::Deleted { .. })
+    }
+}
+
+/// Wraps a message being edited or deleted, only cloning it into a
+/// [`MessageAction`] once it passes the constraints of a filter.
+#[derive(Debug)]
+pub(crate) struct LazyMessageAction<'a> {
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    author_id: Option<UserId>,
+    event: Option<&'a MessageUpdateEvent>,
+    old: Option<Message>,
+    arc: Option<Arc<MessageAction>>,
+}
+
+impl<'a> LazyMessageAction<'a> {
+    pub fn updated(event: &'a MessageUpdateEvent, old: Option<Message>) -> Self {
+        let author = event.author.as_ref().or_else(|| old.as_ref().map(|m| &m.author));
+
+        Self {
+            guild_id: event.guild_id,
+            channel_id: event.channel_id,
+            message_id: event.id,
+            author_id: author.map(|a| a.id),
+            event: Some(event),
+            old,
+            arc: None,
+        }
+    }
+
+    pub fn deleted(
+        guild_id: Option<GuildId>,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        old: Option<Message>,
+    ) -> Self {
+        Self {
+            guild_id,
+            channel_id,
+            message_id,
+            author_id: old.as_ref().map(|m| m.author.id),
+            event: None,
+            old,
+            arc: None,
+        }
+    }
+
+    pub fn as_arc(&mut self) -> Arc<MessageAction> {
+        let (guild_id, channel_id, message_id, event) =
+            (self.guild_id, self.channel_id, self.message_id, self.event);
+        let old = &mut self.old;
+
+        self.arc
+            .get_or_insert_with(|| {
+                Arc::new(match event {
+                    Some(event) => MessageAction::Updated {
+                        event: event.clone(),
+                        old: old.take(),
+                    },
+                    None => MessageAction::Deleted {
+                        guild_id,
+                        channel_id,
+                        message_id,
+                        old: old.take(),
+                    },
+                })
+            })
+            .clone()
+    }
+}
+
+/// Filters events on the shard's end and sends them to the collector.
+#[derive(Clone, Debug)]
+pub struct MessageEventFilter {
+    id: FilterId,
+    stats: CollectorStats,
+    options: FilterOptions,
+    sender: CollectorSender<Arc<MessageAction>>,
+}
+
+impl MessageEventFilter {
+    /// Creates a new filter
+    fn new(
+        options: FilterOptions,
+        stats: CollectorStats,
+    ) -> (Self, CollectorReceiver<Arc<MessageAction>>) {
+        let (sender, receiver) = channel(options.channel_capacity);
+
+        let filter = Self {
+            id: FilterId::next(),
+            stats,
+            sender,
+            options,
+        };
+
+        (filter, receiver)
+    }
+
+    /// The ID of the filter, identifying it on the shard runner.
+    pub fn id(&self) -> FilterId {
+        self.id
+    }
+
+    /// Whether the collector receiving from this filter was dropped.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
+    /// Sends a message event to the consuming collector if the event
+    /// conforms to the constraints and the limits are not reached yet.
+    pub(crate) fn send_message_event(&mut self, action: &mut LazyMessageAction<'_>) -> bool {
+        let is_matching = self.is_passing_constraints(action);
+        let is_passing = is_matching && self.is_passing_filter(action);
+
+        if is_passing && !self.sender.send(action.as_arc(), &self.stats) {
+            return false;
+        }
+
+        self.stats.add_checked(self.options.filter_limit_matching_only, is_matching && !is_passing);
+
+        self.is_within_limits() && !self.sender.is_closed()
+    }
+
+    /// Checks if the message event passes set constraints.
+    /// Constraints are optional, as it is possible to limit events to
+    /// a specific message, author, guild or channel.
+    fn is_passing_constraints(&self, action: &LazyMessageAction<'_>) -> bool {
+        let is_passing_kind = if action.event.is_some() {
+            self.options.accept_updated
+        } else {
+            self.options.accept_deleted
+        };
+
+        is_passing_kind
+            && is_id_accepted(self.options.message_id.as_deref(), Some(action.message_id.0))
+            && is_id_accepted(self.options.channel_id.as_deref(), Some(action.channel_id.0))
+            && is_id_accepted(self.options.guild_id.as_deref(), action.guild_id.map(|g| g.0))
+            && is_id_accepted(self.options.author_id.as_deref(), action.author_id.map(|a| a.0))
+    }
+
+    /// Checks if the `action` passes the filter function, if any.
+    ///
+    /// The filter function takes a [`MessageAction`], so the action is only
+    /// cloned into one once it passed the constraints.
+    fn is_passing_filter(&self, action: &mut LazyMessageAction<'_>) -> bool {
+        self.options.filter.as_ref().map_or(true, |f| f.call(&mut LazyArc::new(&action.as_arc())))
+    }
+
+    /// Checks if the filter is within set receive and collect limits.
+    /// A message event is considered *received* even when it does not
+    /// meet the constraints, unless only near misses are counted.
+    fn is_within_limits(&self) -> bool {
+        self.stats.is_within_limits(self.options.filter_limit, self.options.collect_limit)
+    }
+}
+
+#[derive(Clone)]
+struct FilterOptions {
+    filter_limit: Option<u32>,
+    filter_limit_matching_only: bool,
+    collect_limit: Option<u32>,
+    channel_capacity: Option<usize>,
+    filter: Option<FilterFn<MessageAction>>,
+    message_id: Option<Vec<u64>>,
+    channel_id: Option<Vec<u64>>,
+    guild_id: Option<Vec<u64>>,
+    author_id: Option<Vec<u64>>,
+    accept_updated: bool,
+    accept_deleted: bool,
+}
+
+impl Default for FilterOptions {
+    fn default() -> Self {
+        Self {
+            filter_limit: None,
+            filter_limit_matching_only: false,
+            collect_limit: None,
+            channel_capacity: None,
+            filter: None,
+            message_id: None,
+            channel_id: None,
+            guild_id: None,
+            author_id: None,
+            accept_updated: true,
+            accept_deleted: true,
+        }
+    }
+}
+
+impl std::fmt::Debug for FilterOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageEventFilter")
+            .field("collect_limit", &self.collect_limit)
+            .field("channel_capacity", &self.channel_capacity)
+            .field("filter", &self.filter)
+            .field("message_id", &self.message_id)
+            .field("channel_id", &self.channel_id)
+            .field("guild_id", &self.guild_id)
+            .field("author_id", &self.author_id)
+            .field("accept_updated", &self.accept_updated)
+            .field("accept_deleted", &self.accept_deleted)
+            .finish()
+    }
+}
+
+// Implement the common setters for all message event collector types.
+// This avoids using a trait that the user would need to import in
+// order to use any of these methods.
+impl_message_event_collector! {
+    CollectMessageEvent;
+    MessageEventCollectorBuilder;
+}
+
+/// Builds a [`MessageEventCollector`], collecting messages being edited or
+/// deleted, such as to watch a message for a while after it was sent.
+///
+/// **Note**: This requires the [`GatewayIntents::GUILD_MESSAGES`] or
+/// [`GatewayIntents::DIRECT_MESSAGES`] intent, depending on where the messages
+/// are sent.
+///
+/// [`GatewayIntents::GUILD_MESSAGES`]: crate::client::bridge::gateway::GatewayIntents::GUILD_MESSAGES
+/// [`GatewayIntents::DIRECT_MESSAGES`]: crate::client::bridge::gateway::GatewayIntents::DIRECT_MESSAGES
+pub struct MessageEventCollectorBuilder<'a> {
+    filter: Option<FilterOptions>,
+    shard: Option<ShardMessenger>,
+    timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<MessageAction>>,
+    stats: CollectorStats,
+    fut: Option<BoxFuture<'a, MessageEventCollector>>,
+}
+
+impl<'a> MessageEventCollectorBuilder<'a> {
+    pub fn new(shard_messenger: impl AsRef<ShardMessenger>) -> Self {
+        Self {
+            filter: Some(FilterOptions::default()),
+            shard: Some(shard_messenger.as_ref().clone()),
+            timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
+            fut: None,
+        }
+    }
+
+    /// Builds the collector and waits for up to `n` message events.
+    ///
+    /// Collection ends early once the [`Self::timeout`], the
+    /// [`Self::idle_timeout`], the [`Self::collect_limit`] or the
+    /// [`Self::filter_limit`] is reached, whichever comes first, returning the
+    /// events collected so far.
+    ///
+    /// The collect limit is lowered to `n` as well, so the collector ends on
+    /// its own once `n` message events were collected, with its [`CollectorStats`]
+    /// reporting [`CompletionReason::CollectLimit`].
+    ///
+    /// Dropping the returned future stops the collector, discarding the
+    /// events collected so far.
+    #[allow(clippy::unwrap_used)]
+    pub async fn collect_n(mut self, n: usize) -> Vec<Arc<MessageAction>> {
+        lower_collect_limit(&mut self.filter.as_mut().unwrap().collect_limit, n);
+
+        collect_n(self.await, n).await
+    }
+
+    /// Builds the collector and returns all message events within the
+    /// given `duration`.
+    ///
+    /// Refer to [`Self::collect_n`] for when collection ends early.
+    pub async fn collect_within(self, duration: Duration) -> Vec<Arc<MessageAction>> {
+        collect_within(self.await, duration).await
+    }
+}
+
+impl<'a> Future for MessageEventCollectorBuilder<'a> {
+    type Output = MessageEventCollector;
+    #[allow(clippy::unwrap_used)]
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
+        if self.fut.is_none() {
+            let shard_messenger = self.shard.take().unwrap();
+            let collect_limit = self.filter.as_ref().unwrap().collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) =
+                MessageEventFilter::new(self.filter.take().unwrap(), self.stats.clone());
+            let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
+
+            self.fut = Some(Box::pin(async move {
+                let filter_id = filter.id();
+                shard_messenger.set_message_event_filter_sync(filter).await;
+
+                MessageEventCollector {
+                    receiver: Box::pin(receiver),
+                    timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
+                }
+            }))
+        }
+
+        self.fut.as_mut().unwrap().as_mut().poll(ctx)
+    }
+}
+
+/// Waits for a single message event, such as for a message to be deleted.
+///
+/// Refer to [`MessageEventCollectorBuilder`] for the required intent.
+pub struct CollectMessageEvent<'a> {
+    filter: Option<FilterOptions>,
+    shard: Option<ShardMessenger>,
+    timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<MessageAction>>,
+    stats: CollectorStats,
+    fut: Option<BoxFuture<'a, Option<Arc<MessageAction>>>>,
+}
+
+impl<'a> CollectMessageEvent<'a> {
+    pub fn new(shard_messenger: impl AsRef<ShardMessenger>) -> Self {
+        Self {
+            filter: Some(FilterOptions::default()),
+            shard: Some(shard_messenger.as_ref().clone()),
+            timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
+            fut: None,
+        }
+    }
+}
+
+impl<'a> Future for CollectMessageEvent<'a> {
+    type Output = Option<Arc<MessageAction>>;
+    #[allow(clippy::unwrap_used)]
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
+        if self.fut.is_none() {
+            let shard_messenger = self.shard.take().unwrap();
+            let collect_limit = self.filter.as_ref().unwrap().collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) =
+                MessageEventFilter::new(self.filter.take().unwrap(), self.stats.clone());
+            let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
+
+            self.fut = Some(Box::pin(async move {
+                let filter_id = filter.id();
+                shard_messenger.set_message_event_filter_sync(filter).await;
+
+                MessageEventCollector {
+                    receiver: Box::pin(receiver),
+                    timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
+                }
+                .next()
+                .await
+            }))
+        }
+
+        self.fut.as_mut().unwrap().as_mut().poll(ctx)
+    }
+}
+
+/// A message event collector receives message events matching the given
+/// filter for a set duration.
+pub struct MessageEventCollector {
+    receiver: Pin<Box<CollectorReceiver<Arc<MessageAction>>>>,
+    timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<IdleTimeout>,
+    async_filter: Option<AsyncFilter<MessageAction>>,
+    shard: ShardMessenger,
+    filter_id: FilterId,
+    handle: CollectorHandle,
+    stats: CollectorStats,
+}
+
+impl MessageEventCollector {
+    /// Stops collecting, this will implicitly be done once the
+    /// collector drops.
+    /// In case the drop does not appear until later, it is preferred to
+    /// stop the collector early.
+    ///
+    /// Either way, the collector's filter is removed from the shard.
+    pub fn stop(mut self) {
+        self.receiver.close();
+    }
+
+    /// Returns a handle to stop the collector from another task, such as
+    /// while this one is waiting for the collector's next item.
+    pub fn handle(&self) -> CollectorHandle {
+        self.handle.clone()
+    }
+
+    /// Returns a handle to the statistics of the collector, such as to check
+    /// why it ended.
+    pub fn stats(&self) -> CollectorStats {
+        self.stats.clone()
+    }
+
+    /// The number of message events which were dropped as the
+    /// [channel capacity] was reached.
+    ///
+    /// [channel capacity]: MessageEventCollectorBuilder::channel_capacity
+    pub fn dropped_count(&self) -> u64 {
+        self.receiver.dropped_count()
+    }
+}
+
+impl Stream for MessageEventCollector {
+    type Item = Arc<MessageAction>;
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Option<Self::Item>> {
+        if self.handle.poll_stopped(ctx) {
+            self.receiver.close();
+            self.stats.complete(CompletionReason::Stopped);
+
+            return Poll::Ready(None);
+        }
+
+        if let Some(ref mut timeout) = self.timeout {
+            match timeout.as_mut().poll(ctx) {
+                Poll::Ready(_) => {
+                    self.stats.complete(CompletionReason::Timeout);
+
+                    return Poll::Ready(None);
+                },
+                Poll::Pending => (),
+            }
+        }
+
+        if let Some(ref mut idle_timeout) = self.idle_timeout {
+            if idle_timeout.is_elapsed(ctx) {
+                self.stats.complete(CompletionReason::IdleTimeout);
+
+                return Poll::Ready(None);
+            }
+        }
+
+        let this = &mut *self;
+        let item = poll_recv(&mut this.receiver, this.async_filter.as_mut(), &this.stats, ctx);
+
+        if let Poll::Ready(None) = item {
+            self.stats.complete(CompletionReason::Disconnected);
+        }
+
+        if let (Poll::Ready(Some(_)), Some(idle_timeout)) = (&item, &mut self.idle_timeout) {
+            idle_timeout.reset();
+        }
+
+        item
+    }
+}
+
+impl Drop for MessageEventCollector {
+    fn drop(&mut self) {
+        self.receiver.close();
+        self.stats.complete(CompletionReason::Stopped);
+        self.shard.remove_message_event_filter(self.filter_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{FilterOptions, LazyMessageAction, MessageAction, MessageEventFilter};
+    use crate::collector::{CollectorStats, FilterFn};
+    use crate::model::channel::Message;
+    use crate::model::event::MessageUpdateEvent;
+    use crate::model::id::{ChannelId, GuildId, MessageId, UserId};
+
+    fn message(author_id: u64) -> Message {
+        serde_json::from_value(json!({
+            "id": "4",
+            "attachments": [],
+            "author": {
+                "id": author_id.to_string(),
+                "username": "Author",
+                "discriminator": "0001",
+                "avatar": null
+            },
+            "channel_id": "3",
+            "guild_id": "1",
+            "content": "@everyone",
+            "edited_timestamp": null,
+            "embeds": [],
+            "type": 0,
+            "mention_everyone": true,
+            "mention_roles": [],
+            "mentions": [],
+            "pinned": false,
+            "timestamp": "2021-08-27T16:13:33.482000+00:00",
+            "tts": false
+        }))
+        .unwrap()
+    }
+
+    fn update_event(author_id: Option<u64>) -> MessageUpdateEvent {
+        serde_json::from_value(json!({
+            "id": "4",
+            "channel_id": "3",
+            "guild_id": "1",
+            "content": "edited",
+            "author": author_id.map(|id| json!({
+                "id": id.to_string(),
+                "username": "Author",
+                "discriminator": "0001",
+                "avatar": null
+            }))
+        }))
+        .unwrap()
+    }
+
+    fn updated(author_id: Option<u64>, old: Option<u64>) -> MessageAction {
+        MessageAction::Updated {
+            event: update_event(author_id),
+            old: old.map(message),
+        }
+    }
+
+    fn lazy_updated(event: &MessageUpdateEvent, old: Option<u64>) -> LazyMessageAction<'_> {
+        LazyMessageAction::updated(event, old.map(message))
+    }
+
+    fn deleted(old: Option<u64>) -> MessageAction {
+        MessageAction::Deleted {
+            guild_id: Some(GuildId(1)),
+            channel_id: ChannelId(3),
+            message_id: MessageId(4),
+            old: old.map(message),
+        }
+    }
+
+    fn lazy_deleted(old: Option<u64>) -> LazyMessageAction<'static> {
+        LazyMessageAction::deleted(Some(GuildId(1)), ChannelId(3), MessageId(4), old.map(message))
+    }
+
+    #[test]
+    fn test_action_ids() {
+        let action = updated(None, Some(2));
+        assert!(action.is_updated() && !action.is_deleted());
+        assert_eq!(action.message_id(), MessageId(4));
+        assert_eq!(action.channel_id(), ChannelId(3));
+        assert_eq!(action.guild_id(), Some(GuildId(1)));
+        assert_eq!(action.author_id(), Some(UserId(2)));
+        assert_eq!(action.old().map(|m| m.content.as_str()), Some("@everyone"));
+
+        // The author of the update takes precedence over the cached one.
+        assert_eq!(updated(Some(5), Some(2)).author_id(), Some(UserId(5)));
+
+        let action = deleted(None);
+        assert!(!action.is_updated() && action.is_deleted());
+        assert_eq!(action.message_id(), MessageId(4));
+        assert!(action.author_id().is_none());
+        assert_eq!(deleted(Some(2)).author_id(), Some(UserId(2)));
+    }
+
+    #[test]
+    fn test_kinds() {
+        let options = FilterOptions {
+            accept_updated: false,
+            ..Default::default()
+        };
+        let (filter, _receiver) = MessageEventFilter::new(options, CollectorStats::default());
+
+        assert!(!filter.is_passing_constraints(&lazy_updated(&update_event(Some(2)), None)));
+        assert!(filter.is_passing_constraints(&lazy_deleted(None)));
+    }
+
+    #[test]
+    fn test_ids() {
+        let options = FilterOptions {
+            message_id: Some(vec![4]),
+            channel_id: Some(vec![3]),
+            guild_id: Some(vec![1]),
+            ..Default::default()
+        };
+        let (filter, _receiver) = MessageEventFilter::new(options, CollectorStats::default());
+        assert!(filter.is_passing_constraints(&lazy_updated(&update_event(None), None)));
+        assert!(filter.is_passing_constraints(&lazy_deleted(None)));
+
+        let options = FilterOptions {
+            message_id: Some(vec![5]),
+            ..Default::default()
+        };
+        let (filter, _receiver) = MessageEventFilter::new(options, CollectorStats::default());
+        assert!(!filter.is_passing_constraints(&lazy_updated(&update_event(None), None)));
+    }
+
+    #[test]
+    fn test_author() {
+        let options = FilterOptions {
+            author_id: Some(vec![2]),
+            ..Default::default()
+        };
+        let (filter, _receiver) = MessageEventFilter::new(options, CollectorStats::default());
+
+        assert!(filter.is_passing_constraints(&lazy_updated(&update_event(Some(2)), None)));
+        assert!(filter.is_passing_constraints(&lazy_updated(&update_event(None), Some(2))));
+        assert!(!filter.is_passing_constraints(&lazy_updated(&update_event(Some(5)), None)));
+        assert!(filter.is_passing_constraints(&lazy_deleted(Some(2))));
+
+        // The author of uncached deleted messages is unknown.
+        assert!(!filter.is_passing_constraints(&lazy_deleted(None)));
+    }
+
+    #[test]
+    fn test_filter_function() {
+        let options = FilterOptions {
+            filter: Some(FilterFn::new(|action: &MessageAction| {
+                action.old().map_or(false, |m| m.mention_everyone)
+            })),
+            ..Default::default()
+        };
+        let (filter, _receiver) = MessageEventFilter::new(options, CollectorStats::default());
+
+        assert!(filter.is_passing_filter(&mut lazy_deleted(Some(2))));
+        assert!(!filter.is_passing_filter(&mut lazy_deleted(None)));
+    }
+
+    #[test]
+    fn test_rejected_not_cloned() {
+        let options = FilterOptions {
+            author_id: Some(vec![2]),
+            ..Default::default()
+        };
+        let (mut filter, _receiver) = MessageEventFilter::new(options, CollectorStats::default());
+
+        let event = update_event(Some(5));
+        let mut action = lazy_updated(&event, None);
+        assert!(filter.send_message_event(&mut action));
+        assert!(action.arc.is_none());
+
+        let mut action = lazy_updated(&event, Some(2));
+        // The author of the update takes precedence over the cached one.
+        assert!(filter.send_message_event(&mut action));
+        assert!(action.arc.is_none());
+
+        let mut action = lazy_deleted(Some(2));
+        assert!(filter.send_message_event(&mut action));
+        assert_eq!(action.as_arc().author_id(), Some(UserId(2)));
+    }
+}
@@ -1,8 +1,61 @@
 //! Collectors will receive events from the contextual shard, check if the
 //! filter lets them pass, and collects if the receive, collect, or time limits
 //! are not reached yet.
+//!
+//! Awaiting a collector builder only resolves once the shard runner has
+//! installed the collector's filter. Every event the shard receives after
+//! that is seen by the collector, so a prompt sent after building the
+//! collector cannot be answered too early to be collected. Building a
+//! collector may therefore take until the runner's next loop iteration.
+//!
+//! **Note**: Within a [`RawEventHandler`], which the runner waits for, the
+//! builder resolves immediately instead, without this guarantee.
+//!
+//! Filters set with a builder's `filter` method run on the shard, which must
+//! not wait for them. Checks which need to await something, such as a database
+//! query or a HTTP request, can be set with the `async_filter` method instead.
+//! These run on the collector's end when it is polled, and only see the items
+//! that passed all other requirements, including the `filter`. Items they
+//! reject are skipped without counting towards the `collect_limit`, while
+//! still counting towards the `filter_limit`.
+//!
+//! By default, every item a collector's filter checks counts towards its
+//! `filter_limit`, even if it is from an unrelated guild or channel, so the
+//! limit can be used up within seconds on a busy shard. Enabling a builder's
+//! `filter_limit_matching_only` only counts the near misses instead, which
+//! match all constraints of the collector, such as its IDs, but are rejected
+//! by its `filter`. Items rejected by an `async_filter` are not counted in
+//! this mode, as they are only checked after the shard counted them.
+//!
+//! [`RawEventHandler`]: crate::client::RawEventHandler
 
-use std::sync::Arc;
+use std::{
+    convert::TryFrom,
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering},
+    sync::Arc,
+    task::{Context as FutContext, Poll},
+    time::Duration,
+};
+
+use futures::future::BoxFuture;
+use futures::lock::Mutex;
+use futures::stream::{Stream, StreamExt};
+use futures::task::AtomicWaker;
+use tokio::sync::mpsc::{
+    self,
+    error::TrySendError,
+    unbounded_channel,
+    Receiver,
+    Sender,
+    UnboundedReceiver,
+    UnboundedSender,
+};
+#[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
+use tokio::time::{delay_for as sleep, Delay as Sleep};
+#[cfg(feature = "tokio")]
+use tokio::time::{sleep, Sleep};
 
 mod error;
 pub use error::Error as CollectorError;
@@ -10,18 +63,26 @@ pub use error::Error as CollectorError;
 #[cfg(feature = "unstable_discord_api")]
 pub mod component_interaction_collector;
 pub mod event_collector;
+pub mod guild_member_collector;
 pub mod message_collector;
+pub mod message_event_collector;
 #[cfg(feature = "unstable_discord_api")]
 pub mod modal_interaction_collector;
 pub mod reaction_collector;
+pub mod typing_collector;
+pub mod voice_state_collector;
 
 #[cfg(feature = "unstable_discord_api")]
 pub use component_interaction_collector::*;
 pub use event_collector::*;
+pub use guild_member_collector::*;
 pub use message_collector::*;
+pub use message_event_collector::*;
 #[cfg(feature = "unstable_discord_api")]
 pub use modal_interaction_collector::*;
 pub use reaction_collector::*;
+pub use typing_collector::*;
+pub use voice_state_collector::*;
 
 /// Wraps a &T and clones the value into an Arc<T> lazily. Used with collectors to allow inspecting
 /// the value in filters while only cloning values that actually match.
@@ -52,3 +113,835 @@ impl<'a, T> std::ops::Deref for LazyArc<'a, T> {
         self.value
     }
 }
+
+type FilterFunction<T> = dyn Fn(&T) -> bool + 'static + Send + Sync;
+
+type ArcFilterFunction<T> = dyn Fn(&Arc<T>) -> bool + 'static + Send + Sync;
+
+/// A user-provided filter function of a collector.
+///
+/// Filters taking `&T` are preferred, as they let rejected values go without
+/// ever being cloned into an [`Arc`]. Filters with the deprecated `&Arc<T>`
+/// signature share the [`Arc`] that will be sent to the collector.
+pub(crate) enum FilterFn<T> {
+    Ref(Arc<FilterFunction<T>>),
+    Arc(Arc<ArcFilterFunction<T>>),
+}
+
+impl<T> FilterFn<T> {
+    pub fn new<F: Fn(&T) -> bool + 'static + Send + Sync>(function: F) -> Self {
+        Self::Ref(Arc::new(function))
+    }
+
+    pub fn new_arc<F: Fn(&Arc<T>) -> bool + 'static + Send + Sync>(function: F) -> Self {
+        Self::Arc(Arc::new(function))
+    }
+}
+
+impl<T: Clone> FilterFn<T> {
+    pub fn call(&self, value: &mut LazyArc<'_, T>) -> bool {
+        match self {
+            Self::Ref(function) => function(value.value),
+            Self::Arc(function) => function(&value.as_arc()),
+        }
+    }
+}
+
+impl<T> Clone for FilterFn<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Ref(function) => Self::Ref(Arc::clone(function)),
+            Self::Arc(function) => Self::Arc(Arc::clone(function)),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for FilterFn<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ref(_) => f.write_str("Arc<dyn Fn(&T) -> bool + 'static + Send + Sync>"),
+            Self::Arc(_) => f.write_str("Arc<dyn Fn(&Arc<T>) -> bool + 'static + Send + Sync>"),
+        }
+    }
+}
+
+type AsyncFilterFunction<T> = dyn Fn(&Arc<T>) -> BoxFuture<'static, bool> + 'static + Send + Sync;
+
+/// A user-provided asynchronous filter function of a collector, awaited on the
+/// collector's end before yielding an item.
+pub(crate) struct AsyncFilterFn<T>(Arc<AsyncFilterFunction<T>>);
+
+impl<T> AsyncFilterFn<T> {
+    pub fn new<F: Fn(&Arc<T>) -> BoxFuture<'static, bool> + 'static + Send + Sync>(
+        function: F,
+    ) -> Self {
+        Self(Arc::new(function))
+    }
+}
+
+impl<T> Clone for AsyncFilterFn<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> std::fmt::Debug for AsyncFilterFn<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Arc<dyn Fn(&Arc<T>) -> BoxFuture<'static, bool> + 'static + Send + Sync>")
+    }
+}
+
+/// Awaits a collector's [`AsyncFilterFn`] for the items it receives, counting
+/// the accepted ones as collected.
+pub(crate) struct AsyncFilter<T> {
+    function: AsyncFilterFn<T>,
+    collect_limit: Option<u32>,
+    /// The item being checked, and the function's future checking it.
+    ///
+    /// The future is only accessed mutably, the [`Mutex`] merely keeps the
+    /// collector [`Sync`]. Unlike [`std::sync::Mutex`], it cannot be poisoned.
+    pending: Option<(Arc<T>, Mutex<BoxFuture<'static, bool>>)>,
+}
+
+impl<T> AsyncFilter<T> {
+    /// Creates the filter, leaving it to the filter to count collected items
+    /// instead of the shard.
+    pub fn new(function: AsyncFilterFn<T>, collect_limit: Option<u32>, stats: &CollectorStats) -> Self {
+        stats.collect_on_receive();
+
+        Self {
+            function,
+            collect_limit,
+            pending: None,
+        }
+    }
+
+    /// Receives the next item from the `receiver` which the function accepts.
+    ///
+    /// Ends once the collect limit is reached.
+    pub fn poll_recv(
+        &mut self,
+        receiver: &mut CollectorReceiver<Arc<T>>,
+        stats: &CollectorStats,
+        ctx: &mut FutContext<'_>,
+    ) -> Poll<Option<Arc<T>>> {
+        loop {
+            if self.pending.is_none() {
+                if !stats.is_within_limits(None, self.collect_limit) {
+                    receiver.close();
+
+                    return Poll::Ready(None);
+                }
+
+                let item = match receiver.poll_recv(ctx) {
+                    Poll::Ready(Some(item)) => item,
+                    other => return other,
+                };
+                let accepted = (self.function.0)(&item);
+
+                self.pending = Some((item, Mutex::new(accepted)));
+            }
+
+            if let Some((_, accepted)) = &mut self.pending {
+                match accepted.get_mut().as_mut().poll(ctx) {
+                    Poll::Ready(true) => {
+                        stats.add_collected();
+
+                        return Poll::Ready(self.pending.take().map(|(item, _)| item));
+                    },
+                    Poll::Ready(false) => self.pending = None,
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+        }
+    }
+}
+
+/// Receives a collector's next item, awaiting its `async_filter` if it has
+/// one.
+pub(crate) fn poll_recv<T>(
+    receiver: &mut CollectorReceiver<Arc<T>>,
+    async_filter: Option<&mut AsyncFilter<T>>,
+    stats: &CollectorStats,
+    ctx: &mut FutContext<'_>,
+) -> Poll<Option<Arc<T>>> {
+    match async_filter {
+        Some(async_filter) => async_filter.poll_recv(receiver, stats, ctx),
+        None => receiver.poll_recv(ctx),
+    }
+}
+
+/// Identifies the filter of a collector, such as to remove it from the shard
+/// runner once the collector is dropped.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FilterId(u64);
+
+impl FilterId {
+    /// Returns an ID that was not handed out before.
+    pub(crate) fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// A handle to stop a collector from another task than the one consuming it,
+/// obtained from the collector's `handle` method.
+///
+/// The handle is cheap to clone, and all collector kinds share this type, so
+/// the handles of different collectors can be stored together, such as to
+/// stop all of them on shutdown.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use serenity::collector::MessageCollectorBuilder;
+/// use serenity::futures::StreamExt;
+/// use serenity::prelude::*;
+///
+/// # async fn run(ctx: &Context) {
+/// let mut collector = MessageCollectorBuilder::new(&ctx).await;
+/// let handle = collector.handle();
+///
+/// tokio::spawn(async move {
+///     tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+///     handle.stop();
+/// });
+///
+/// while let Some(message) = collector.next().await {
+///     println!("{}", message.content);
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CollectorHandle(Arc<HandleState>);
+
+#[derive(Debug, Default)]
+struct HandleState {
+    stopped: AtomicBool,
+    waker: AtomicWaker,
+}
+
+impl CollectorHandle {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops the collector, ending its stream once it is polled next. Items
+    /// which were not yet received are discarded.
+    ///
+    /// The collector's filter is removed from the shard as it is dropped.
+    pub fn stop(&self) {
+        self.0.stopped.store(true, Ordering::Release);
+        self.0.waker.wake();
+    }
+
+    /// Whether [`Self::stop`] was called on this handle or one of its clones.
+    pub fn is_stopped(&self) -> bool {
+        self.0.stopped.load(Ordering::Acquire)
+    }
+
+    /// Checks if the collector was stopped, registering the waker of `ctx`
+    /// otherwise.
+    pub(crate) fn poll_stopped(&self, ctx: &mut FutContext<'_>) -> bool {
+        self.0.waker.register(ctx.waker());
+
+        self.is_stopped()
+    }
+}
+
+/// A timeout ending a collector once it has not collected anything for a
+/// while, as it is restarted whenever an item is collected.
+pub(crate) struct IdleTimeout {
+    duration: Duration,
+    sleep: Pin<Box<Sleep>>,
+}
+
+impl IdleTimeout {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            sleep: Box::pin(sleep(duration)),
+        }
+    }
+
+    /// Restarts the timeout, such as after an item was collected.
+    pub fn reset(&mut self) {
+        self.sleep = Box::pin(sleep(self.duration));
+    }
+
+    /// Checks if the timeout elapsed, registering the waker of `ctx` otherwise.
+    pub fn is_elapsed(&mut self, ctx: &mut FutContext<'_>) -> bool {
+        self.sleep.as_mut().poll(ctx).is_ready()
+    }
+}
+
+/// Why a collector's stream ended.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum CompletionReason {
+    /// The collector's timeout elapsed.
+    Timeout,
+    /// The collector's idle timeout elapsed without anything being collected.
+    IdleTimeout,
+    /// The collector's collect limit was reached.
+    CollectLimit,
+    /// The collector's filter limit was reached.
+    FilterLimit,
+    /// The collector was stopped, either explicitly, through its
+    /// [`CollectorHandle`], or by dropping it before its stream ended.
+    Stopped,
+    /// The shard stopped sending events to the collector, such as after the
+    /// shard was shut down.
+    Disconnected,
+}
+
+impl CompletionReason {
+    const ALL: [Self; 6] = [
+        Self::Timeout,
+        Self::IdleTimeout,
+        Self::CollectLimit,
+        Self::FilterLimit,
+        Self::Stopped,
+        Self::Disconnected,
+    ];
+
+    fn from_index(index: u8) -> Option<Self> {
+        Self::ALL.get(usize::from(index).checked_sub(1)?).copied()
+    }
+
+    fn index(self) -> u8 {
+        self as u8 + 1
+    }
+}
+
+/// The statistics of a collector, shared between the collector, its builder
+/// and its filter on the shard, obtained from their `stats` methods.
+///
+/// The handle is cheap to clone, and is updated while the collector runs, so
+/// it can be kept to inspect a collector once it ended, such as one consumed
+/// by `collect_n`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use serenity::collector::MessageCollectorBuilder;
+/// use serenity::prelude::*;
+///
+/// # async fn run(ctx: &Context) {
+/// let builder = MessageCollectorBuilder::new(&ctx)
+///     .collect_limit(100)
+///     .timeout(std::time::Duration::from_secs(600));
+/// let stats = builder.stats();
+/// let entries = builder.collect_n(100).await;
+///
+/// println!(
+///     "Collected {} entries out of {} messages, ended by {:?}",
+///     entries.len(),
+///     stats.filtered(),
+///     stats.completion_reason(),
+/// );
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CollectorStats(Arc<StatsState>);
+
+#[derive(Debug, Default)]
+struct StatsState {
+    filtered: AtomicU32,
+    collected: AtomicU32,
+    /// The index of the [`CompletionReason`], `0` while the collector runs.
+    completion_reason: AtomicU8,
+    /// Whether items are counted as collected by the collector's
+    /// [`AsyncFilter`] rather than on the shard.
+    is_collected_on_receive: AtomicBool,
+}
+
+impl CollectorStats {
+    /// The number of items the filter checked, whether they were collected or
+    /// not.
+    ///
+    /// **Note**: If the builder's `filter_limit_matching_only` was enabled,
+    /// only the near misses are counted instead, which matched the collector's
+    /// constraints but were rejected by its filter function.
+    pub fn filtered(&self) -> u32 {
+        self.0.filtered.load(Ordering::Relaxed)
+    }
+
+    /// The number of items which passed the filter.
+    ///
+    /// **Note**: This includes items dropped as the collector's channel
+    /// capacity was reached, unless the collector has an async filter, in
+    /// which case only the items it accepted are counted.
+    pub fn collected(&self) -> u32 {
+        self.0.collected.load(Ordering::Relaxed)
+    }
+
+    /// Why the collector ended, or [`None`] while it is still running.
+    pub fn completion_reason(&self) -> Option<CompletionReason> {
+        CompletionReason::from_index(self.0.completion_reason.load(Ordering::Acquire))
+    }
+
+    /// Whether the collector ended.
+    pub fn is_completed(&self) -> bool {
+        self.completion_reason().is_some()
+    }
+
+    pub(crate) fn add_filtered(&self) {
+        self.0.filtered.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts an item the filter checked towards the `filter_limit`.
+    ///
+    /// With `matching_only`, only near misses are counted, which matched the
+    /// filter's constraints but were rejected by its filter function.
+    pub(crate) fn add_checked(&self, matching_only: bool, is_near_miss: bool) {
+        if !matching_only || is_near_miss {
+            self.add_filtered();
+        }
+    }
+
+    pub(crate) fn add_collected(&self) {
+        self.0.collected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts an item which passed the filter on the shard as collected,
+    /// unless the collector's [`AsyncFilter`] decides on it later.
+    pub(crate) fn add_passed(&self) {
+        if !self.0.is_collected_on_receive.load(Ordering::Relaxed) {
+            self.add_collected();
+        }
+    }
+
+    pub(crate) fn collect_on_receive(&self) {
+        self.0.is_collected_on_receive.store(true, Ordering::Relaxed);
+    }
+
+    /// Records why the collector ended, unless it already ended before.
+    pub(crate) fn complete(&self, reason: CompletionReason) {
+        #[allow(clippy::let_underscore_must_use)]
+        let _ = self.0.completion_reason.compare_exchange(
+            0,
+            reason.index(),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        );
+    }
+
+    /// Checks if the collector is within the given limits, completing it
+    /// otherwise.
+    pub(crate) fn is_within_limits(
+        &self,
+        filter_limit: Option<u32>,
+        collect_limit: Option<u32>,
+    ) -> bool {
+        if collect_limit.map_or(false, |limit| self.collected() >= limit) {
+            self.complete(CompletionReason::CollectLimit);
+
+            false
+        } else if filter_limit.map_or(false, |limit| self.filtered() >= limit) {
+            self.complete(CompletionReason::FilterLimit);
+
+            false
+        } else {
+            true
+        }
+    }
+}
+
+/// Creates the channel a filter sends collected items to its collector over,
+/// bounded to `capacity` items if one is set. A capacity of `0` is treated as
+/// `1`, as the channel could not hold any item otherwise.
+pub(crate) fn channel<T>(capacity: Option<usize>) -> (CollectorSender<T>, CollectorReceiver<T>) {
+    match capacity {
+        Some(capacity) => {
+            let (sender, receiver) = mpsc::channel(capacity.max(1));
+            let dropped = Arc::new(AtomicU64::new(0));
+
+            (
+                CollectorSender::Bounded(sender, Arc::clone(&dropped)),
+                CollectorReceiver::Bounded(receiver, dropped),
+            )
+        },
+        None => {
+            let (sender, receiver) = unbounded_channel();
+
+            (CollectorSender::Unbounded(sender), CollectorReceiver::Unbounded(receiver))
+        },
+    }
+}
+
+/// The sending half of a collector's channel, held by its filter.
+#[derive(Clone, Debug)]
+pub(crate) enum CollectorSender<T> {
+    Unbounded(UnboundedSender<T>),
+    /// Counts the items dropped as the channel was full.
+    Bounded(Sender<T>, Arc<AtomicU64>),
+}
+
+impl<T> CollectorSender<T> {
+    /// Sends an `item` which passed the filter to the collector, dropping it
+    /// if the channel is full so that the shard is never blocked by a slow
+    /// collector.
+    ///
+    /// The item is only counted by [`CollectorStats::add_passed`] once it was
+    /// sent, so dropped items do not count towards the collect limit.
+    ///
+    /// Returns `false` if the collector was dropped.
+    pub fn send(&self, item: T, stats: &CollectorStats) -> bool {
+        let result = match self {
+            Self::Unbounded(sender) => sender.send(item).map_err(|_| false),
+            Self::Bounded(sender, dropped) => {
+                sender.try_send(item).map_err(|error| match error {
+                    TrySendError::Full(_) => {
+                        dropped.fetch_add(1, Ordering::Relaxed);
+
+                        true
+                    },
+                    TrySendError::Closed(_) => false,
+                })
+            },
+        };
+
+        match result {
+            Ok(()) => {
+                stats.add_passed();
+
+                true
+            },
+            Err(is_open) => is_open,
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        match self {
+            Self::Unbounded(sender) => sender.is_closed(),
+            Self::Bounded(sender, _) => sender.is_closed(),
+        }
+    }
+}
+
+/// The receiving half of a collector's channel.
+pub(crate) enum CollectorReceiver<T> {
+    Unbounded(UnboundedReceiver<T>),
+    Bounded(Receiver<T>, Arc<AtomicU64>),
+}
+
+impl<T> CollectorReceiver<T> {
+    pub fn poll_recv(&mut self, ctx: &mut FutContext<'_>) -> Poll<Option<T>> {
+        match self {
+            Self::Unbounded(receiver) => receiver.poll_recv(ctx),
+            Self::Bounded(receiver, _) => receiver.poll_recv(ctx),
+        }
+    }
+
+    pub fn close(&mut self) {
+        match self {
+            Self::Unbounded(receiver) => receiver.close(),
+            Self::Bounded(receiver, _) => receiver.close(),
+        }
+    }
+
+    /// The number of items dropped as the channel was full.
+    pub fn dropped_count(&self) -> u64 {
+        match self {
+            Self::Unbounded(_) => 0,
+            Self::Bounded(_, dropped) => dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Adds `ids` to the IDs accepted by a collector's filter.
+pub(crate) fn add_ids(
+    accepted: &mut Option<Vec<u64>>,
+    ids: impl IntoIterator<Item = impl Into<u64>>,
+) {
+    accepted.get_or_insert_with(Vec::new).extend(ids.into_iter().map(Into::into));
+}
+
+/// Checks if `id` is among the IDs accepted by a collector's filter, which
+/// accepts any ID if none were set.
+pub(crate) fn is_id_accepted(accepted: Option<&[u64]>, id: Option<u64>) -> bool {
+    accepted.map_or(true, |ids| id.map_or(false, |id| ids.contains(&id)))
+}
+
+/// Lowers a collector's `collect_limit` to `n`, keeping a lower limit that
+/// was already set.
+pub(crate) fn lower_collect_limit(collect_limit: &mut Option<u32>, n: usize) {
+    let n = u32::try_from(n).unwrap_or(u32::MAX);
+
+    *collect_limit = Some(collect_limit.map_or(n, |limit| limit.min(n)));
+}
+
+/// Drives a collector until it yielded `n` items or ended.
+pub(crate) async fn collect_n<S: Stream>(collector: S, n: usize) -> Vec<S::Item> {
+    collector.take(n).collect().await
+}
+
+/// Drives a collector until `duration` elapsed or it ended.
+pub(crate) async fn collect_within<S: Stream>(collector: S, duration: Duration) -> Vec<S::Item> {
+    collector.take_until(sleep(duration)).collect().await
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::task::{Context as FutContext, Poll};
+    use std::time::Duration;
+
+    use futures::{channel::mpsc::unbounded, stream, FutureExt};
+
+    use super::{
+        add_ids,
+        channel,
+        collect_n,
+        collect_within,
+        is_id_accepted,
+        lower_collect_limit,
+        AsyncFilter,
+        AsyncFilterFn,
+        CollectorStats,
+        CompletionReason,
+        FilterFn,
+        LazyArc,
+    };
+
+    #[test]
+    fn test_id_filter() {
+        let mut accepted = None;
+        assert!(is_id_accepted(accepted.as_deref(), Some(1)));
+        assert!(is_id_accepted(accepted.as_deref(), None));
+
+        add_ids(&mut accepted, Some(1_u64));
+        add_ids(&mut accepted, vec![2_u64, 3]);
+        assert_eq!(accepted.as_deref(), Some(&[1, 2, 3][..]));
+        assert!(is_id_accepted(accepted.as_deref(), Some(1)));
+        assert!(is_id_accepted(accepted.as_deref(), Some(3)));
+        assert!(!is_id_accepted(accepted.as_deref(), Some(4)));
+        assert!(!is_id_accepted(accepted.as_deref(), None));
+    }
+
+    #[test]
+    fn test_stats_limits() {
+        let stats = CollectorStats::default();
+        assert!(stats.is_within_limits(Some(1), None));
+
+        stats.add_filtered();
+        assert!(stats.is_within_limits(None, Some(1)));
+        assert!(!stats.is_completed());
+        assert!(!stats.is_within_limits(Some(1), None));
+        assert_eq!(stats.completion_reason(), Some(CompletionReason::FilterLimit));
+
+        // Only the first reason is kept.
+        stats.complete(CompletionReason::Timeout);
+        assert_eq!(stats.completion_reason(), Some(CompletionReason::FilterLimit));
+
+        // The collect limit takes precedence once both are reached.
+        let stats = CollectorStats::default();
+        stats.add_filtered();
+        stats.add_collected();
+        assert!(!stats.is_within_limits(Some(1), Some(1)));
+        assert_eq!(stats.completion_reason(), Some(CompletionReason::CollectLimit));
+    }
+
+    #[test]
+    fn test_bounded_channel() {
+        let stats = CollectorStats::default();
+        let (sender, mut receiver) = channel(Some(2));
+
+        // Items that do not fit are dropped without blocking the sender, and
+        // are not counted as collected.
+        for i in 0..5 {
+            assert!(sender.send(i, &stats));
+        }
+        assert_eq!(receiver.dropped_count(), 3);
+        assert_eq!(stats.collected(), 2);
+
+        let waker = futures::task::noop_waker();
+        let mut ctx = FutContext::from_waker(&waker);
+        assert_eq!(receiver.poll_recv(&mut ctx), Poll::Ready(Some(0)));
+        assert_eq!(receiver.poll_recv(&mut ctx), Poll::Ready(Some(1)));
+        assert!(receiver.poll_recv(&mut ctx).is_pending());
+
+        // There is room again once items were received.
+        assert!(sender.send(5, &stats));
+        assert_eq!(receiver.dropped_count(), 3);
+        assert_eq!(stats.collected(), 3);
+
+        drop(receiver);
+        assert!(sender.is_closed());
+        assert!(!sender.send(6, &stats));
+        assert_eq!(stats.collected(), 3);
+    }
+
+    #[test]
+    fn test_zero_capacity_channel() {
+        let stats = CollectorStats::default();
+        let (sender, receiver) = channel(Some(0));
+
+        assert!(sender.send(1, &stats));
+        assert!(sender.send(2, &stats));
+        assert_eq!(receiver.dropped_count(), 1);
+        assert_eq!(stats.collected(), 1);
+    }
+
+    #[test]
+    fn test_unbounded_channel() {
+        let stats = CollectorStats::default();
+        let (sender, receiver) = channel(None);
+
+        for i in 0..100 {
+            assert!(sender.send(i, &stats));
+        }
+        assert_eq!(receiver.dropped_count(), 0);
+        assert_eq!(stats.collected(), 100);
+    }
+
+    #[test]
+    fn test_arc_filter_shares_arc() {
+        let value = String::from("accepted");
+        let mut lazy = LazyArc::new(&value);
+
+        let filter: FilterFn<String> = FilterFn::new_arc(|value| **value == "accepted");
+
+        assert!(filter.call(&mut lazy));
+
+        let arc = lazy.as_arc();
+        // One reference held by `lazy`, one by `arc`.
+        assert_eq!(Arc::strong_count(&arc), 2);
+    }
+
+    fn assert_send<T: Send>() {}
+
+    fn assert_sync<T: Sync>() {}
+
+    #[test]
+    fn test_send_sync() {
+        use super::*;
+
+        // Builders hold boxed futures, so they are only `Send`.
+        assert_send::<CollectReply<'static>>();
+        assert_send::<MessageCollectorBuilder<'static>>();
+        assert_send::<CollectReaction<'static>>();
+        assert_send::<ReactionCollectorBuilder<'static>>();
+        assert_send::<EventCollectorBuilder<'static>>();
+        assert_send::<CollectGuildMember<'static>>();
+        assert_send::<GuildMemberCollectorBuilder<'static>>();
+        assert_send::<CollectTyping<'static>>();
+        assert_send::<TypingCollectorBuilder<'static>>();
+        assert_send::<CollectVoiceState<'static>>();
+        assert_send::<VoiceStateCollectorBuilder<'static>>();
+        assert_send::<CollectMessageEvent<'static>>();
+        assert_send::<MessageEventCollectorBuilder<'static>>();
+
+        assert_send::<MessageCollector>();
+        assert_sync::<MessageCollector>();
+        assert_send::<ReactionCollector>();
+        assert_sync::<ReactionCollector>();
+        assert_send::<EventCollector>();
+        assert_sync::<EventCollector>();
+        assert_send::<GuildMemberCollector>();
+        assert_sync::<GuildMemberCollector>();
+        assert_send::<TypingCollector>();
+        assert_sync::<TypingCollector>();
+        assert_send::<VoiceStateCollector>();
+        assert_sync::<VoiceStateCollector>();
+        assert_send::<MessageEventCollector>();
+        assert_sync::<MessageEventCollector>();
+
+        assert_send::<CollectorHandle>();
+        assert_sync::<CollectorHandle>();
+        assert_send::<CollectorStats>();
+        assert_sync::<CollectorStats>();
+    }
+
+    #[test]
+    #[cfg(feature = "unstable_discord_api")]
+    fn test_interaction_send_sync() {
+        use super::*;
+
+        assert_send::<CollectComponentInteraction<'static>>();
+        assert_send::<ComponentInteractionCollectorBuilder<'static>>();
+        assert_send::<ComponentInteractionCollector>();
+        assert_sync::<ComponentInteractionCollector>();
+        assert_send::<CollectModalInteraction<'static>>();
+        assert_send::<ModalInteractionCollectorBuilder<'static>>();
+        assert_send::<ModalInteractionCollector>();
+        assert_sync::<ModalInteractionCollector>();
+    }
+
+    #[test]
+    fn test_async_filter() {
+        let stats = CollectorStats::default();
+        let (sender, mut receiver) = channel(None);
+        let function = AsyncFilterFn::new(|value: &Arc<u32>| {
+            let value = **value;
+
+            async move { value % 2 == 0 }.boxed()
+        });
+        let mut filter = AsyncFilter::new(function, Some(2), &stats);
+
+        // The shard leaves counting collected items to the async filter.
+        for i in 1..6 {
+            assert!(sender.send(Arc::new(i), &stats));
+        }
+        assert_eq!(stats.collected(), 0);
+
+        let waker = futures::task::noop_waker();
+        let mut ctx = FutContext::from_waker(&waker);
+        let mut next = || match filter.poll_recv(&mut receiver, &stats, &mut ctx) {
+            Poll::Ready(item) => item.map(|item| *item),
+            Poll::Pending => panic!("the filter is pending"),
+        };
+
+        assert_eq!(next(), Some(2));
+        assert_eq!(next(), Some(4));
+        assert_eq!(stats.collected(), 2);
+
+        // The collect limit ends the collector before receiving the next item.
+        assert_eq!(next(), None);
+        assert_eq!(stats.completion_reason(), Some(CompletionReason::CollectLimit));
+    }
+
+    #[test]
+    fn test_lower_collect_limit() {
+        let mut limit = None;
+        lower_collect_limit(&mut limit, 5);
+        assert_eq!(limit, Some(5));
+
+        // A lower limit which was already set is kept.
+        lower_collect_limit(&mut limit, 10);
+        assert_eq!(limit, Some(5));
+        lower_collect_limit(&mut limit, 2);
+        assert_eq!(limit, Some(2));
+
+        let mut limit = None;
+        lower_collect_limit(&mut limit, usize::MAX);
+        assert_eq!(limit, Some(u32::MAX));
+    }
+
+    #[tokio::test]
+    async fn test_collect_n() {
+        assert_eq!(collect_n(stream::iter(1..10), 3).await, [1, 2, 3]);
+
+        // The collector ending first, e.g. due to a limit or its timeout.
+        assert_eq!(collect_n(stream::iter(1..3), 5).await, [1, 2]);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_collect_within() {
+        let (sender, receiver) = unbounded();
+        sender.unbounded_send(1).unwrap();
+        sender.unbounded_send(2).unwrap();
+
+        // The sender is still alive, so only the duration ends collection.
+        assert_eq!(collect_within(receiver, Duration::from_millis(10)).await, [1, 2]);
+
+        let (sender, receiver) = unbounded();
+        sender.unbounded_send(1).unwrap();
+        drop(sender);
+
+        assert_eq!(collect_within(receiver, Duration::from_secs(60)).await, [1]);
+    }
+}
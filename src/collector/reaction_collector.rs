@@ -11,11 +11,6 @@ use futures::{
     future::BoxFuture,
     stream::{Stream, StreamExt},
 };
-use tokio::sync::mpsc::{
-    unbounded_channel,
-    UnboundedReceiver as Receiver,
-    UnboundedSender as Sender,
-};
 #[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
 use tokio::time::{delay_for as sleep, Delay as Sleep};
 #[cfg(feature = "tokio")]
@@ -23,9 +18,27 @@ use tokio::time::{sleep, Sleep};
 
 use crate::{
     client::bridge::gateway::ShardMessenger,
-    collector::LazyArc,
-    model::channel::Reaction,
-    model::id::UserId,
+    collector::{
+        add_ids,
+        channel,
+        collect_n,
+        collect_within,
+        is_id_accepted,
+        lower_collect_limit,
+        poll_recv,
+        AsyncFilter,
+        AsyncFilterFn,
+        CollectorHandle,
+        CollectorReceiver,
+        CollectorSender,
+        CollectorStats,
+        CompletionReason,
+        FilterFn,
+        FilterId,
+        IdleTimeout,
+        LazyArc,
+    },
+    model::channel::{Reaction, ReactionType},
 };
 
 macro_rules! impl_reaction_collector {
@@ -42,6 +55,16 @@ macro_rules! impl_reaction_collector {
                     self
                 }
 
+                /// Sets whether only near misses count towards the [`Self::filter_limit`],
+                /// which match all other constraints, but are rejected by [`Self::filter`].
+                ///
+                /// By default, every reaction the filter checks counts towards the limit.
+                pub fn filter_limit_matching_only(mut self, matching_only: bool) -> Self {
+                    self.filter.as_mut().unwrap().filter_limit_matching_only = matching_only;
+
+                    self
+                }
+
                 /// Limits how many reactions can be collected.
                 ///
                 /// A reaction is considered *collected*, if the reaction
@@ -52,21 +75,66 @@ macro_rules! impl_reaction_collector {
                     self
                 }
 
+                /// Limits how many reactions can wait to be received from the collector.
+                /// Once reached, further reactions are dropped rather than waiting, such as
+                /// while the receiving task is busy during a spam wave.
+                ///
+                /// The dropped reactions are counted by [`ReactionCollector::dropped_count`].
+                /// By default, any number of reactions can wait.
+                /// A capacity of `0` is treated as `1`.
+                pub fn channel_capacity(mut self, capacity: usize) -> Self {
+                    self.filter.as_mut().unwrap().channel_capacity = Some(capacity);
+
+                    self
+                }
+
                 /// Sets a filter function where reactions passed to the function must
                 /// return `true`, otherwise the reaction won't be collected.
                 /// This is the last instance to pass for a reaction to count as *collected*.
                 ///
                 /// This function is intended to be a reaction content filter.
-                pub fn filter<F: Fn(&Arc<Reaction>) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
-                    self.filter.as_mut().unwrap().filter = Some(Arc::new(function));
+                pub fn filter<F: Fn(&Reaction) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.filter.as_mut().unwrap().filter = Some(FilterFn::new(function));
+
+                    self
+                }
+
+                /// Sets a filter function taking the reaction wrapped in an [`Arc`].
+                ///
+                /// Prefer [`Self::filter`], which only clones the reaction into an
+                /// [`Arc`] once it is known to be collected.
+                #[deprecated(note = "use `filter`, which takes `&Reaction` instead")]
+                pub fn filter_arc<F: Fn(&Arc<Reaction>) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.filter.as_mut().unwrap().filter = Some(FilterFn::new_arc(function));
+
+                    self
+                }
+
+                /// Sets an asynchronous filter function where reactions passed to the
+                /// function must resolve to `true`, otherwise they won't be collected.
+                ///
+                /// This runs on the collector's end once all other requirements passed,
+                /// including [`Self::filter`], so it may wait on a database or HTTP request.
+                /// Rejected reactions do not count towards [`Self::collect_limit`].
+                pub fn async_filter<F: Fn(&Arc<ReactionAction>) -> BoxFuture<'static, bool> + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.async_filter = Some(AsyncFilterFn::new(function));
 
                     self
                 }
 
                 /// Sets the required author ID of a reaction.
                 /// If a reaction is not issued by a user with this ID, it won't be received.
+                ///
+                /// Calling this multiple times accepts reactions matching any of the IDs.
                 pub fn author_id(mut self, author_id: impl Into<u64>) -> Self {
-                    self.filter.as_mut().unwrap().author_id = Some(author_id.into());
+                    add_ids(&mut self.filter.as_mut().unwrap().author_id, Some(author_id));
+
+                    self
+                }
+
+                /// Like [`Self::author_id`], but adds multiple IDs at once.
+                pub fn author_ids(mut self, author_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().author_id, author_ids);
 
                     self
                 }
@@ -81,16 +149,70 @@ macro_rules! impl_reaction_collector {
 
                 /// Sets the guild in which the reaction must occur.
                 /// If a reaction is not on a message with this guild ID, it won't be received.
+                ///
+                /// Calling this multiple times accepts reactions matching any of the IDs.
                 pub fn guild_id(mut self, guild_id: impl Into<u64>) -> Self {
-                    self.filter.as_mut().unwrap().guild_id = Some(guild_id.into());
+                    add_ids(&mut self.filter.as_mut().unwrap().guild_id, Some(guild_id));
+
+                    self
+                }
+
+                /// Like [`Self::guild_id`], but adds multiple IDs at once.
+                pub fn guild_ids(mut self, guild_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().guild_id, guild_ids);
 
                     self
                 }
 
                 /// Sets the channel on which the reaction must occur.
                 /// If a reaction is not on a message with this channel ID, it won't be received.
+                ///
+                /// Calling this multiple times accepts reactions matching any of the IDs.
                 pub fn channel_id(mut self, channel_id: impl Into<u64>) -> Self {
-                    self.filter.as_mut().unwrap().channel_id = Some(channel_id.into());
+                    add_ids(&mut self.filter.as_mut().unwrap().channel_id, Some(channel_id));
+
+                    self
+                }
+
+                /// Like [`Self::channel_id`], but adds multiple IDs at once.
+                pub fn channel_ids(mut self, channel_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().channel_id, channel_ids);
+
+                    self
+                }
+
+                /// Sets an emoji the reaction must use.
+                /// If a reaction does not use this emoji, it won't be received.
+                ///
+                /// Custom emojis are compared by their ID only, ignoring their
+                /// name and whether they are animated. Unicode emojis are
+                /// compared ignoring emoji presentation selectors (`U+FE0F`).
+                ///
+                /// Calling this multiple times accepts reactions using any of the emojis.
+                pub fn emoji(mut self, emoji: impl Into<ReactionType>) -> Self {
+                    self.filter.as_mut().unwrap().emoji.get_or_insert_with(Vec::new).push(emoji.into());
+
+                    self
+                }
+
+                /// Like [`Self::emoji`], but adds multiple emojis at once.
+                pub fn emojis(mut self, emojis: impl IntoIterator<Item = impl Into<ReactionType>>) -> Self {
+                    let emojis = emojis.into_iter().map(Into::into);
+                    self.filter.as_mut().unwrap().emoji.get_or_insert_with(Vec::new).extend(emojis);
+
+                    self
+                }
+
+                /// Sets whether added reactions, removed reactions, or both will
+                /// be collected.
+                ///
+                /// This overrides [`Self::added`] and [`Self::removed`], and
+                /// defaults to [`CollectKind::Add`]. Whether a collected reaction
+                /// was added or removed is told by its [`ReactionAction`].
+                pub fn collect_kind(mut self, kind: CollectKind) -> Self {
+                    let filter = self.filter.as_mut().unwrap();
+                    filter.accept_added = matches!(kind, CollectKind::Add | CollectKind::Both);
+                    filter.accept_removed = matches!(kind, CollectKind::Remove | CollectKind::Both);
 
                     self
                 }
@@ -120,11 +242,45 @@ macro_rules! impl_reaction_collector {
 
                     self
                 }
+
+                /// Sets a `duration` after which the collector stops if it has not
+                /// collected a reaction meanwhile, restarting whenever one is collected.
+                ///
+                /// This can be combined with [`Self::timeout`], stopping the collector
+                /// once either of them elapsed.
+                pub fn idle_timeout(mut self, duration: Duration) -> Self {
+                    self.idle_timeout = Some(duration);
+
+                    self
+                }
+
+                /// Returns a handle to the statistics of the collector, which stays
+                /// usable once this is consumed, such as by awaiting it.
+                pub fn stats(&self) -> CollectorStats {
+                    self.stats.clone()
+                }
             }
         )*
     }
 }
 
+/// Which reaction events a reaction collector collects.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CollectKind {
+    /// Only added reactions are collected.
+    Add,
+    /// Only removed reactions are collected.
+    Remove,
+    /// Both added and removed reactions are collected.
+    Both,
+}
+
+impl Default for CollectKind {
+    fn default() -> Self {
+        Self::Add
+    }
+}
+
 /// Marks whether the reaction has been added or removed.
 #[derive(Debug)]
 pub enum ReactionAction {
@@ -191,20 +347,23 @@ impl<'a> LazyReactionAction<'a> {
 /// Filters events on the shard's end and sends them to the collector.
 #[derive(Clone, Debug)]
 pub struct ReactionFilter {
-    filtered: u32,
-    collected: u32,
+    id: FilterId,
+    stats: CollectorStats,
     options: FilterOptions,
-    sender: Sender<Arc<ReactionAction>>,
+    sender: CollectorSender<Arc<ReactionAction>>,
 }
 
 impl ReactionFilter {
     /// Creates a new filter
-    fn new(options: FilterOptions) -> (Self, Receiver<Arc<ReactionAction>>) {
-        let (sender, receiver) = unbounded_channel();
+    fn new(
+        options: FilterOptions,
+        stats: CollectorStats,
+    ) -> (Self, CollectorReceiver<Arc<ReactionAction>>) {
+        let (sender, receiver) = channel(options.channel_capacity);
 
         let filter = Self {
-            filtered: 0,
-            collected: 0,
+            id: FilterId::next(),
+            stats,
             sender,
             options,
         };
@@ -212,18 +371,27 @@ impl ReactionFilter {
         (filter, receiver)
     }
 
+    /// The ID of the filter, identifying it on the shard runner.
+    pub fn id(&self) -> FilterId {
+        self.id
+    }
+
+    /// Whether the collector receiving from this filter was dropped.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
     /// Sends a `reaction` to the consuming collector if the `reaction` conforms
     /// to the constraints and the limits are not reached yet.
     pub(crate) fn send_reaction(&mut self, reaction: &mut LazyReactionAction<'_>) -> bool {
-        if self.is_passing_constraints(reaction) {
-            self.collected += 1;
+        let is_matching = self.is_passing_constraints(reaction);
+        let is_passing = is_matching && self.is_passing_filter(&mut reaction.reaction);
 
-            if self.sender.send(reaction.as_arc()).is_err() {
-                return false;
-            }
+        if is_passing && !self.sender.send(reaction.as_arc(), &self.stats) {
+            return false;
         }
 
-        self.filtered += 1;
+        self.stats.add_checked(self.options.filter_limit_matching_only, is_matching && !is_passing);
 
         self.is_within_limits() && !self.sender.is_closed()
     }
@@ -231,67 +399,93 @@ impl ReactionFilter {
     /// Checks if the `reaction` passes set constraints.
     /// Constraints are optional, as it is possible to limit reactions to
     /// be sent by a specific author or in a specific guild.
-    fn is_passing_constraints(&self, reaction: &mut LazyReactionAction<'_>) -> bool {
-        let reaction = match (reaction.added, &mut reaction.reaction) {
-            (true, reaction) => {
-                if self.options.accept_added {
-                    reaction
-                } else {
-                    return false;
-                }
-            },
-            (false, reaction) => {
-                if self.options.accept_removed {
-                    reaction
-                } else {
-                    return false;
-                }
-            },
+    fn is_passing_constraints(&self, reaction: &LazyReactionAction<'_>) -> bool {
+        let is_passing_kind = if reaction.added {
+            self.options.accept_added
+        } else {
+            self.options.accept_removed
         };
+        let reaction = &reaction.reaction;
 
-        // TODO: On next branch, switch filter arg to &T so this as_arc() call can be removed.
-        self.options.guild_id.map_or(true, |id| Some(id) == reaction.guild_id.map(|g| g.0))
+        is_passing_kind
+            && is_id_accepted(self.options.guild_id.as_deref(), reaction.guild_id.map(|g| g.0))
             && self.options.message_id.map_or(true, |id| id == reaction.message_id.0)
-            && self.options.channel_id.map_or(true, |id| id == reaction.channel_id.0)
-            && self
-                .options
-                .author_id
-                .map_or(true, |id| id == reaction.user_id.unwrap_or(UserId(0)).0)
-            && self.options.filter.as_ref().map_or(true, |f| f(&reaction.as_arc()))
+            && is_id_accepted(self.options.channel_id.as_deref(), Some(reaction.channel_id.0))
+            && is_id_accepted(self.options.author_id.as_deref(), reaction.user_id.map(|u| u.0))
+            && self.options.is_emoji_accepted(&reaction.emoji)
+    }
+
+    /// Checks if the `reaction` passes the filter function, if any.
+    fn is_passing_filter(&self, reaction: &mut LazyArc<'_, Reaction>) -> bool {
+        self.options.filter.as_ref().map_or(true, |f| f.call(reaction))
     }
 
     /// Checks if the filter is within set receive and collect limits.
     /// A reaction is considered *received* even when it does not meet the
-    /// constraints.
+    /// constraints, unless only near misses are counted.
     fn is_within_limits(&self) -> bool {
-        self.options.filter_limit.map_or(true, |limit| self.filtered < limit)
-            && self.options.collect_limit.map_or(true, |limit| self.collected < limit)
+        self.stats.is_within_limits(self.options.filter_limit, self.options.collect_limit)
     }
 }
 
 #[derive(Clone)]
 struct FilterOptions {
     filter_limit: Option<u32>,
+    filter_limit_matching_only: bool,
     collect_limit: Option<u32>,
-    filter: Option<Arc<dyn Fn(&Arc<Reaction>) -> bool + 'static + Send + Sync>>,
-    channel_id: Option<u64>,
-    guild_id: Option<u64>,
-    author_id: Option<u64>,
+    channel_capacity: Option<usize>,
+    filter: Option<FilterFn<Reaction>>,
+    channel_id: Option<Vec<u64>>,
+    guild_id: Option<Vec<u64>>,
+    author_id: Option<Vec<u64>>,
     message_id: Option<u64>,
+    emoji: Option<Vec<ReactionType>>,
     accept_added: bool,
     accept_removed: bool,
 }
 
+impl FilterOptions {
+    /// Checks if the `emoji` is among the accepted emojis, if any were set.
+    fn is_emoji_accepted(&self, emoji: &ReactionType) -> bool {
+        self.emoji.as_ref().map_or(true, |emojis| emojis.iter().any(|e| is_same_emoji(e, emoji)))
+    }
+}
+
+/// Compares custom emojis by their ID and unicode emojis by their characters,
+/// ignoring emoji presentation selectors, as clients may or may not send them.
+fn is_same_emoji(a: &ReactionType, b: &ReactionType) -> bool {
+    const PRESENTATION_SELECTOR: char = '\u{FE0F}';
+
+    match (a, b) {
+        (
+            ReactionType::Custom {
+                id: a, ..
+            },
+            ReactionType::Custom {
+                id: b, ..
+            },
+        ) => a == b,
+        (ReactionType::Unicode(a), ReactionType::Unicode(b)) => {
+            let strip = |s: &str| s.replace(PRESENTATION_SELECTOR, "");
+            strip(a) == strip(b)
+        },
+        _ => false,
+    }
+}
+
 impl Default for FilterOptions {
     fn default() -> Self {
         Self {
             filter_limit: None,
+            filter_limit_matching_only: false,
             collect_limit: None,
+            channel_capacity: None,
             filter: None,
             channel_id: None,
             guild_id: None,
             author_id: None,
             message_id: None,
+            emoji: None,
             accept_added: true,
             accept_removed: false,
         }
@@ -310,6 +504,9 @@ pub struct ReactionCollectorBuilder<'a> {
     filter: Option<FilterOptions>,
     shard: Option<ShardMessenger>,
     timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<ReactionAction>>,
+    stats: CollectorStats,
     fut: Option<BoxFuture<'a, ReactionCollector>>,
 }
 
@@ -319,9 +516,40 @@ impl<'a> ReactionCollectorBuilder<'a> {
             filter: Some(FilterOptions::default()),
             shard: Some(shard_messenger.as_ref().clone()),
             timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
             fut: None,
         }
     }
+
+    /// Builds the collector and waits for up to `n` reactions.
+    ///
+    /// Collection ends early once the [`Self::timeout`], the
+    /// [`Self::idle_timeout`], the [`Self::collect_limit`] or the
+    /// [`Self::filter_limit`] is reached, whichever comes first, returning the
+    /// reactions collected so far.
+    ///
+    /// The collect limit is lowered to `n` as well, so the collector ends on
+    /// its own once `n` reactions were collected, with its [`CollectorStats`]
+    /// reporting [`CompletionReason::CollectLimit`].
+    ///
+    /// Dropping the returned future stops the collector, discarding the
+    /// reactions collected so far.
+    #[allow(clippy::unwrap_used)]
+    pub async fn collect_n(mut self, n: usize) -> Vec<Arc<ReactionAction>> {
+        lower_collect_limit(&mut self.filter.as_mut().unwrap().collect_limit, n);
+
+        collect_n(self.await, n).await
+    }
+
+    /// Builds the collector and returns all reactions collected within the
+    /// given `duration`.
+    ///
+    /// Refer to [`Self::collect_n`] for when collection ends early.
+    pub async fn collect_within(self, duration: Duration) -> Vec<Arc<ReactionAction>> {
+        collect_within(self.await, duration).await
+    }
 }
 
 impl<'a> Future for ReactionCollectorBuilder<'a> {
@@ -330,15 +558,30 @@ impl<'a> Future for ReactionCollectorBuilder<'a> {
     fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
         if self.fut.is_none() {
             let shard_messenger = self.shard.take().unwrap();
-            let (filter, receiver) = ReactionFilter::new(self.filter.take().unwrap());
+            let collect_limit = self.filter.as_ref().unwrap().collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) =
+                ReactionFilter::new(self.filter.take().unwrap(), self.stats.clone());
             let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
 
             self.fut = Some(Box::pin(async move {
-                shard_messenger.set_reaction_filter(filter);
+                let filter_id = filter.id();
+                shard_messenger.set_reaction_filter_sync(filter).await;
 
                 ReactionCollector {
                     receiver: Box::pin(receiver),
                     timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
                 }
             }))
         }
@@ -351,6 +594,9 @@ pub struct CollectReaction<'a> {
     filter: Option<FilterOptions>,
     shard: Option<ShardMessenger>,
     timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<ReactionAction>>,
+    stats: CollectorStats,
     fut: Option<BoxFuture<'a, Option<Arc<ReactionAction>>>>,
 }
 
@@ -360,6 +606,9 @@ impl<'a> CollectReaction<'a> {
             filter: Some(FilterOptions::default()),
             shard: Some(shard_messenger.as_ref().clone()),
             timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
             fut: None,
         }
     }
@@ -371,15 +620,30 @@ impl<'a> Future for CollectReaction<'a> {
     fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
         if self.fut.is_none() {
             let shard_messenger = self.shard.take().unwrap();
-            let (filter, receiver) = ReactionFilter::new(self.filter.take().unwrap());
+            let collect_limit = self.filter.as_ref().unwrap().collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) =
+                ReactionFilter::new(self.filter.take().unwrap(), self.stats.clone());
             let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
 
             self.fut = Some(Box::pin(async move {
-                shard_messenger.set_reaction_filter(filter);
+                let filter_id = filter.id();
+                shard_messenger.set_reaction_filter_sync(filter).await;
 
                 ReactionCollector {
                     receiver: Box::pin(receiver),
                     timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
                 }
                 .next()
                 .await
@@ -394,10 +658,12 @@ impl std::fmt::Debug for FilterOptions {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ReactionFilter")
             .field("collect_limit", &self.collect_limit)
-            .field("filter", &"Option<Arc<dyn Fn(&Arc<Reaction>) -> bool + 'static + Send + Sync>>")
+            .field("channel_capacity", &self.channel_capacity)
+            .field("filter", &self.filter)
             .field("channel_id", &self.channel_id)
             .field("guild_id", &self.guild_id)
             .field("author_id", &self.author_id)
+            .field("emoji", &self.emoji)
             .finish()
     }
 }
@@ -405,8 +671,14 @@ impl std::fmt::Debug for FilterOptions {
 /// A reaction collector receives reactions matching a the given filter for a
 /// set duration.
 pub struct ReactionCollector {
-    receiver: Pin<Box<Receiver<Arc<ReactionAction>>>>,
+    receiver: Pin<Box<CollectorReceiver<Arc<ReactionAction>>>>,
     timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<IdleTimeout>,
+    async_filter: Option<AsyncFilter<ReactionAction>>,
+    shard: ShardMessenger,
+    filter_id: FilterId,
+    handle: CollectorHandle,
+    stats: CollectorStats,
 }
 
 impl ReactionCollector {
@@ -414,29 +686,144 @@ impl ReactionCollector {
     /// collector drops.
     /// In case the drop does not appear until later, it is preferred to
     /// stop the collector early.
+    ///
+    /// Either way, the collector's filter is removed from the shard.
     pub fn stop(mut self) {
         self.receiver.close();
     }
+
+    /// Returns a handle to stop the collector from another task, such as
+    /// while this one is waiting for the collector's next item.
+    pub fn handle(&self) -> CollectorHandle {
+        self.handle.clone()
+    }
+
+    /// Returns a handle to the statistics of the collector, such as to check
+    /// why it ended.
+    pub fn stats(&self) -> CollectorStats {
+        self.stats.clone()
+    }
+
+    /// The number of reactions which were dropped as the [channel capacity] was
+    /// reached.
+    ///
+    /// [channel capacity]: ReactionCollectorBuilder::channel_capacity
+    pub fn dropped_count(&self) -> u64 {
+        self.receiver.dropped_count()
+    }
 }
 
 impl Stream for ReactionCollector {
     type Item = Arc<ReactionAction>;
     fn poll_next(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Option<Self::Item>> {
+        if self.handle.poll_stopped(ctx) {
+            self.receiver.close();
+            self.stats.complete(CompletionReason::Stopped);
+
+            return Poll::Ready(None);
+        }
+
         if let Some(ref mut timeout) = self.timeout {
             match timeout.as_mut().poll(ctx) {
                 Poll::Ready(_) => {
+                    self.stats.complete(CompletionReason::Timeout);
+
                     return Poll::Ready(None);
                 },
                 Poll::Pending => (),
             }
         }
 
-        self.receiver.as_mut().poll_recv(ctx)
+        if let Some(ref mut idle_timeout) = self.idle_timeout {
+            if idle_timeout.is_elapsed(ctx) {
+                self.stats.complete(CompletionReason::IdleTimeout);
+
+                return Poll::Ready(None);
+            }
+        }
+
+        let this = &mut *self;
+        let item = poll_recv(&mut this.receiver, this.async_filter.as_mut(), &this.stats, ctx);
+
+        if let Poll::Ready(None) = item {
+            self.stats.complete(CompletionReason::Disconnected);
+        }
+
+        if let (Poll::Ready(Some(_)), Some(idle_timeout)) = (&item, &mut self.idle_timeout) {
+            idle_timeout.reset();
+        }
+
+        item
     }
 }
 
 impl Drop for ReactionCollector {
     fn drop(&mut self) {
         self.receiver.close();
+        self.stats.complete(CompletionReason::Stopped);
+        self.shard.remove_reaction_filter(self.filter_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{is_same_emoji, FilterOptions, LazyReactionAction, ReactionFilter};
+    use crate::collector::CollectorStats;
+    use crate::model::channel::{Reaction, ReactionType};
+    use crate::model::id::EmojiId;
+
+    fn reaction(emoji: serde_json::Value) -> Reaction {
+        serde_json::from_value(json!({
+            "channel_id": "1",
+            "message_id": "2",
+            "user_id": "3",
+            "emoji": emoji,
+        }))
+        .unwrap()
+    }
+
+    fn custom(id: u64, animated: bool, name: &str) -> ReactionType {
+        ReactionType::Custom {
+            animated,
+            id: EmojiId(id),
+            name: Some(name.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_same_emoji() {
+        assert!(is_same_emoji(&custom(1, false, "a"), &custom(1, true, "b")));
+        assert!(!is_same_emoji(&custom(1, false, "a"), &custom(2, false, "a")));
+
+        let heart = ReactionType::Unicode("\u{2764}".to_string());
+        let heart_emoji = ReactionType::Unicode("\u{2764}\u{FE0F}".to_string());
+        assert!(is_same_emoji(&heart, &heart_emoji));
+        assert!(!is_same_emoji(&heart, &ReactionType::Unicode("\u{1F44D}".to_string())));
+        assert!(!is_same_emoji(&heart, &custom(1, false, "\u{2764}")));
+    }
+
+    #[test]
+    fn test_emoji_and_kind() {
+        let options = FilterOptions {
+            emoji: Some(vec!['\u{1F44D}'.into(), custom(5, false, "down")]),
+            accept_added: false,
+            accept_removed: true,
+            ..Default::default()
+        };
+        let (filter, _receiver) = ReactionFilter::new(options, CollectorStats::default());
+
+        let up = reaction(json!({"name": "\u{1F44D}"}));
+        let down = reaction(json!({"id": "5", "name": "downvote", "animated": true}));
+        let other = reaction(json!({"name": "\u{1F440}"}));
+
+        assert!(filter.is_passing_constraints(&LazyReactionAction::new(&up, false)));
+        assert!(filter.is_passing_constraints(&LazyReactionAction::new(&down, false)));
+        assert!(!filter.is_passing_constraints(&LazyReactionAction::new(&other, false)));
+        assert!(!filter.is_passing_constraints(&LazyReactionAction::new(&up, true)));
+
+        let mut action = LazyReactionAction::new(&up, false);
+        assert!(action.as_arc().is_removed());
     }
 }
@@ -0,0 +1,608 @@
+use std::{
+    boxed::Box,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context as FutContext, Poll},
+    time::Duration,
+};
+
+use futures::{
+    future::BoxFuture,
+    stream::{Stream, StreamExt},
+};
+#[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
+use tokio::time::{delay_for as sleep, Delay as Sleep};
+#[cfg(feature = "tokio")]
+use tokio::time::{sleep, Sleep};
+
+use crate::{
+    client::bridge::gateway::ShardMessenger,
+    collector::{
+        add_ids,
+        channel,
+        collect_n,
+        collect_within,
+        is_id_accepted,
+        lower_collect_limit,
+        poll_recv,
+        AsyncFilter,
+        AsyncFilterFn,
+        CollectorHandle,
+        CollectorReceiver,
+        CollectorSender,
+        CollectorStats,
+        CompletionReason,
+        FilterFn,
+        FilterId,
+        IdleTimeout,
+        LazyArc,
+    },
+    model::event::TypingStartEvent,
+};
+
+macro_rules! impl_typing_collector {
+    ($($name:ident;)*) => {
+        $(
+            impl<'a> $name<'a> {
+                /// Limits how many typing events will attempt to be filtered.
+                ///
+                /// The filter checks whether the user has started typing
+                /// in the right guild and channel, and is the right user.
+                pub fn filter_limit(mut self, limit: u32) -> Self {
+                    self.filter.as_mut().unwrap().filter_limit = Some(limit);
+
+                    self
+                }
+
+                /// Sets whether only near misses count towards the [`Self::filter_limit`],
+                /// which match all other constraints, but are rejected by [`Self::filter`].
+                ///
+                /// By default, every typing event the filter checks counts towards the limit.
+                pub fn filter_limit_matching_only(mut self, matching_only: bool) -> Self {
+                    self.filter.as_mut().unwrap().filter_limit_matching_only = matching_only;
+
+                    self
+                }
+
+                /// Limits how many typing events can be collected.
+                ///
+                /// A typing event is considered *collected*, if the event
+                /// passes all the requirements.
+                pub fn collect_limit(mut self, limit: u32) -> Self {
+                    self.filter.as_mut().unwrap().collect_limit = Some(limit);
+
+                    self
+                }
+
+                /// Limits how many typing events can wait to be received from the collector.
+                /// Once reached, further events are dropped rather than waiting, such as
+                /// while the receiving task is busy.
+                ///
+                /// The dropped events are counted by [`TypingCollector::dropped_count`].
+                /// By default, any number of events can wait.
+                /// A capacity of `0` is treated as `1`.
+                pub fn channel_capacity(mut self, capacity: usize) -> Self {
+                    self.filter.as_mut().unwrap().channel_capacity = Some(capacity);
+
+                    self
+                }
+
+                /// Sets a filter function where typing events passed to the function must
+                /// return `true`, otherwise the event won't be collected.
+                /// This is the last instance to pass for an event to count as *collected*.
+                pub fn filter<F: Fn(&TypingStartEvent) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.filter.as_mut().unwrap().filter = Some(FilterFn::new(function));
+
+                    self
+                }
+
+                /// Sets an asynchronous filter function where typing events passed to the
+                /// function must resolve to `true`, otherwise they won't be collected.
+                ///
+                /// This runs on the collector's end once all other requirements passed,
+                /// including [`Self::filter`], so it may wait on a database or HTTP request.
+                /// Rejected typing events do not count towards [`Self::collect_limit`].
+                pub fn async_filter<F: Fn(&Arc<TypingStartEvent>) -> BoxFuture<'static, bool> + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.async_filter = Some(AsyncFilterFn::new(function));
+
+                    self
+                }
+
+                /// Sets the user who must start typing.
+                /// If another user starts typing, it won't be received.
+                ///
+                /// Calling this multiple times accepts any of the users.
+                pub fn user_id(mut self, user_id: impl Into<u64>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().user_id, Some(user_id));
+
+                    self
+                }
+
+                /// Like [`Self::user_id`], but adds multiple IDs at once.
+                pub fn user_ids(mut self, user_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().user_id, user_ids);
+
+                    self
+                }
+
+                /// Sets the guild in which the user must start typing.
+                /// If a user starts typing in another guild, it won't be received.
+                ///
+                /// Calling this multiple times accepts typing in any of the guilds.
+                pub fn guild_id(mut self, guild_id: impl Into<u64>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().guild_id, Some(guild_id));
+
+                    self
+                }
+
+                /// Like [`Self::guild_id`], but adds multiple IDs at once.
+                pub fn guild_ids(mut self, guild_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().guild_id, guild_ids);
+
+                    self
+                }
+
+                /// Sets the channel in which the user must start typing.
+                /// If a user starts typing in another channel, it won't be received.
+                ///
+                /// Calling this multiple times accepts typing in any of the channels.
+                pub fn channel_id(mut self, channel_id: impl Into<u64>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().channel_id, Some(channel_id));
+
+                    self
+                }
+
+                /// Like [`Self::channel_id`], but adds multiple IDs at once.
+                pub fn channel_ids(mut self, channel_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().channel_id, channel_ids);
+
+                    self
+                }
+
+                /// Sets a `duration` for how long the collector shall receive
+                /// typing events.
+                pub fn timeout(mut self, duration: Duration) -> Self {
+                    self.timeout = Some(Box::pin(sleep(duration)));
+
+                    self
+                }
+
+                /// Sets a `duration` after which the collector stops if it has not
+                /// collected a typing event meanwhile, restarting whenever one is collected.
+                ///
+                /// This can be combined with [`Self::timeout`], stopping the collector
+                /// once either of them elapsed.
+                pub fn idle_timeout(mut self, duration: Duration) -> Self {
+                    self.idle_timeout = Some(duration);
+
+                    self
+                }
+
+                /// Returns a handle to the statistics of the collector, which stays
+                /// usable once this is consumed, such as by awaiting it.
+                pub fn stats(&self) -> CollectorStats {
+                    self.stats.clone()
+                }
+            }
+        )*
+    }
+}
+
+/// Filters events on the shard's end and sends them to the collector.
+#[derive(Clone, Debug)]
+pub struct TypingFilter {
+    id: FilterId,
+    stats: CollectorStats,
+    options: FilterOptions,
+    sender: CollectorSender<Arc<TypingStartEvent>>,
+}
+
+impl TypingFilter {
+    /// Creates a new filter
+    fn new(
+        options: FilterOptions,
+        stats: CollectorStats,
+    ) -> (Self, CollectorReceiver<Arc<TypingStartEvent>>) {
+        let (sender, receiver) = channel(options.channel_capacity);
+
+        let filter = Self {
+            id: FilterId::next(),
+            stats,
+            sender,
+            options,
+        };
+
+        (filter, receiver)
+    }
+
+    /// The ID of the filter, identifying it on the shard runner.
+    pub fn id(&self) -> FilterId {
+        self.id
+    }
+
+    /// Whether the collector receiving from this filter was dropped.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
+    /// Sends a typing `event` to the consuming collector if the `event`
+    /// conforms to the constraints and the limits are not reached yet.
+    pub(crate) fn send_typing(&mut self, event: &mut LazyArc<'_, TypingStartEvent>) -> bool {
+        let is_matching = self.is_passing_constraints(event);
+        let is_passing = is_matching && self.is_passing_filter(event);
+
+        if is_passing && !self.sender.send(event.as_arc(), &self.stats) {
+            return false;
+        }
+
+        self.stats.add_checked(self.options.filter_limit_matching_only, is_matching && !is_passing);
+
+        self.is_within_limits() && !self.sender.is_closed()
+    }
+
+    /// Checks if the typing `event` passes set constraints.
+    /// Constraints are optional, as it is possible to limit typing events to
+    /// a specific user or in a specific guild or channel.
+    fn is_passing_constraints(&self, event: &TypingStartEvent) -> bool {
+        is_id_accepted(self.options.guild_id.as_deref(), event.guild_id.map(|g| g.0))
+            && is_id_accepted(self.options.channel_id.as_deref(), Some(event.channel_id.0))
+            && is_id_accepted(self.options.user_id.as_deref(), Some(event.user_id.0))
+    }
+
+    /// Checks if the `event` passes the filter function, if any.
+    fn is_passing_filter(&self, event: &TypingStartEvent) -> bool {
+        self.options.filter.as_ref().map_or(true, |f| f.call(&mut LazyArc::new(event)))
+    }
+
+    /// Checks if the filter is within set receive and collect limits.
+    /// A typing event is considered *received* even when it does not meet the
+    /// constraints, unless only near misses are counted.
+    fn is_within_limits(&self) -> bool {
+        self.stats.is_within_limits(self.options.filter_limit, self.options.collect_limit)
+    }
+}
+
+#[derive(Clone, Default)]
+struct FilterOptions {
+    filter_limit: Option<u32>,
+    filter_limit_matching_only: bool,
+    collect_limit: Option<u32>,
+    channel_capacity: Option<usize>,
+    filter: Option<FilterFn<TypingStartEvent>>,
+    channel_id: Option<Vec<u64>>,
+    guild_id: Option<Vec<u64>>,
+    user_id: Option<Vec<u64>>,
+}
+
+impl std::fmt::Debug for FilterOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TypingFilter")
+            .field("collect_limit", &self.collect_limit)
+            .field("channel_capacity", &self.channel_capacity)
+            .field("filter", &self.filter)
+            .field("channel_id", &self.channel_id)
+            .field("guild_id", &self.guild_id)
+            .field("user_id", &self.user_id)
+            .finish()
+    }
+}
+
+// Implement the common setters for all typing collector types.
+// This avoids using a trait that the user would need to import in
+// order to use any of these methods.
+impl_typing_collector! {
+    CollectTyping;
+    TypingCollectorBuilder;
+}
+
+/// Builds a [`TypingCollector`], collecting users starting to type.
+///
+/// **Note**: This requires the [`GatewayIntents::GUILD_MESSAGE_TYPING`]
+/// intent for guild channels, and the
+/// [`GatewayIntents::DIRECT_MESSAGE_TYPING`] intent for private channels.
+///
+/// [`GatewayIntents::GUILD_MESSAGE_TYPING`]: crate::client::bridge::gateway::GatewayIntents::GUILD_MESSAGE_TYPING
+/// [`GatewayIntents::DIRECT_MESSAGE_TYPING`]: crate::client::bridge::gateway::GatewayIntents::DIRECT_MESSAGE_TYPING
+pub struct TypingCollectorBuilder<'a> {
+    filter: Option<FilterOptions>,
+    shard: Option<ShardMessenger>,
+    timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<TypingStartEvent>>,
+    stats: CollectorStats,
+    fut: Option<BoxFuture<'a, TypingCollector>>,
+}
+
+impl<'a> TypingCollectorBuilder<'a> {
+    pub fn new(shard_messenger: impl AsRef<ShardMessenger>) -> Self {
+        Self {
+            filter: Some(FilterOptions::default()),
+            shard: Some(shard_messenger.as_ref().clone()),
+            timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
+            fut: None,
+        }
+    }
+
+    /// Builds the collector and waits for up to `n` users starting to type.
+    ///
+    /// Collection ends early once the [`Self::timeout`], the
+    /// [`Self::idle_timeout`], the [`Self::collect_limit`] or the
+    /// [`Self::filter_limit`] is reached, whichever comes first, returning the
+    /// events collected so far.
+    ///
+    /// The collect limit is lowered to `n` as well, so the collector ends on
+    /// its own once `n` typing events were collected, with its [`CollectorStats`]
+    /// reporting [`CompletionReason::CollectLimit`].
+    ///
+    /// Dropping the returned future stops the collector, discarding the
+    /// events collected so far.
+    #[allow(clippy::unwrap_used)]
+    pub async fn collect_n(mut self, n: usize) -> Vec<Arc<TypingStartEvent>> {
+        lower_collect_limit(&mut self.filter.as_mut().unwrap().collect_limit, n);
+
+        collect_n(self.await, n).await
+    }
+
+    /// Builds the collector and returns all users starting to type within the
+    /// given `duration`.
+    ///
+    /// Refer to [`Self::collect_n`] for when collection ends early.
+    pub async fn collect_within(self, duration: Duration) -> Vec<Arc<TypingStartEvent>> {
+        collect_within(self.await, duration).await
+    }
+}
+
+impl<'a> Future for TypingCollectorBuilder<'a> {
+    type Output = TypingCollector;
+    #[allow(clippy::unwrap_used)]
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
+        if self.fut.is_none() {
+            let shard_messenger = self.shard.take().unwrap();
+            let collect_limit = self.filter.as_ref().unwrap().collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) =
+                TypingFilter::new(self.filter.take().unwrap(), self.stats.clone());
+            let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
+
+            self.fut = Some(Box::pin(async move {
+                let filter_id = filter.id();
+                shard_messenger.set_typing_filter_sync(filter).await;
+
+                TypingCollector {
+                    receiver: Box::pin(receiver),
+                    timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
+                }
+            }))
+        }
+
+        self.fut.as_mut().unwrap().as_mut().poll(ctx)
+    }
+}
+
+/// Waits for a single user starting to type, such as to hold back a prompt
+/// while the user is typing a reply.
+///
+/// Refer to [`TypingCollectorBuilder`] for the required intents.
+pub struct CollectTyping<'a> {
+    filter: Option<FilterOptions>,
+    shard: Option<ShardMessenger>,
+    timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<TypingStartEvent>>,
+    stats: CollectorStats,
+    fut: Option<BoxFuture<'a, Option<Arc<TypingStartEvent>>>>,
+}
+
+impl<'a> CollectTyping<'a> {
+    pub fn new(shard_messenger: impl AsRef<ShardMessenger>) -> Self {
+        Self {
+            filter: Some(FilterOptions::default()),
+            shard: Some(shard_messenger.as_ref().clone()),
+            timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
+            fut: None,
+        }
+    }
+}
+
+impl<'a> Future for CollectTyping<'a> {
+    type Output = Option<Arc<TypingStartEvent>>;
+    #[allow(clippy::unwrap_used)]
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
+        if self.fut.is_none() {
+            let shard_messenger = self.shard.take().unwrap();
+            let collect_limit = self.filter.as_ref().unwrap().collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) =
+                TypingFilter::new(self.filter.take().unwrap(), self.stats.clone());
+            let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
+
+            self.fut = Some(Box::pin(async move {
+                let filter_id = filter.id();
+                shard_messenger.set_typing_filter_sync(filter).await;
+
+                TypingCollector {
+                    receiver: Box::pin(receiver),
+                    timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
+                }
+                .next()
+                .await
+            }))
+        }
+
+        self.fut.as_mut().unwrap().as_mut().poll(ctx)
+    }
+}
+
+/// A typing collector receives users starting to type matching the given
+/// filter for a set duration.
+pub struct TypingCollector {
+    receiver: Pin<Box<CollectorReceiver<Arc<TypingStartEvent>>>>,
+    timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<IdleTimeout>,
+    async_filter: Option<AsyncFilter<TypingStartEvent>>,
+    shard: ShardMessenger,
+    filter_id: FilterId,
+    handle: CollectorHandle,
+    stats: CollectorStats,
+}
+
+impl TypingCollector {
+    /// Stops collecting, this will implicitly be done once the
+    /// collector drops.
+    /// In case the drop does not appear until later, it is preferred to
+    /// stop the collector early.
+    ///
+    /// Either way, the collector's filter is removed from the shard.
+    pub fn stop(mut self) {
+        self.receiver.close();
+    }
+
+    /// Returns a handle to stop the collector from another task, such as
+    /// while this one is waiting for the collector's next item.
+    pub fn handle(&self) -> CollectorHandle {
+        self.handle.clone()
+    }
+
+    /// Returns a handle to the statistics of the collector, such as to check
+    /// why it ended.
+    pub fn stats(&self) -> CollectorStats {
+        self.stats.clone()
+    }
+
+    /// The number of typing events which were dropped as the [channel capacity]
+    /// was reached.
+    ///
+    /// [channel capacity]: TypingCollectorBuilder::channel_capacity
+    pub fn dropped_count(&self) -> u64 {
+        self.receiver.dropped_count()
+    }
+}
+
+impl Stream for TypingCollector {
+    type Item = Arc<TypingStartEvent>;
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Option<Self::Item>> {
+        if self.handle.poll_stopped(ctx) {
+            self.receiver.close();
+            self.stats.complete(CompletionReason::Stopped);
+
+            return Poll::Ready(None);
+        }
+
+        if let Some(ref mut timeout) = self.timeout {
+            match timeout.as_mut().poll(ctx) {
+                Poll::Ready(_) => {
+                    self.stats.complete(CompletionReason::Timeout);
+
+                    return Poll::Ready(None);
+                },
+                Poll::Pending => (),
+            }
+        }
+
+        if let Some(ref mut idle_timeout) = self.idle_timeout {
+            if idle_timeout.is_elapsed(ctx) {
+                self.stats.complete(CompletionReason::IdleTimeout);
+
+                return Poll::Ready(None);
+            }
+        }
+
+        let this = &mut *self;
+        let item = poll_recv(&mut this.receiver, this.async_filter.as_mut(), &this.stats, ctx);
+
+        if let Poll::Ready(None) = item {
+            self.stats.complete(CompletionReason::Disconnected);
+        }
+
+        if let (Poll::Ready(Some(_)), Some(idle_timeout)) = (&item, &mut self.idle_timeout) {
+            idle_timeout.reset();
+        }
+
+        item
+    }
+}
+
+impl Drop for TypingCollector {
+    fn drop(&mut self) {
+        self.receiver.close();
+        self.stats.complete(CompletionReason::Stopped);
+        self.shard.remove_typing_filter(self.filter_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{FilterOptions, TypingFilter};
+    use crate::collector::{CollectorStats, FilterFn};
+    use crate::model::event::TypingStartEvent;
+
+    fn typing(guild_id: Option<u64>, channel_id: u64, user_id: u64) -> TypingStartEvent {
+        serde_json::from_value(json!({
+            "guild_id": guild_id.map(|id| id.to_string()),
+            "channel_id": channel_id.to_string(),
+            "user_id": user_id.to_string(),
+            "timestamp": 1609459200,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_ids() {
+        let options = FilterOptions {
+            channel_id: Some(vec![2]),
+            user_id: Some(vec![3, 4]),
+            ..Default::default()
+        };
+        let (filter, _receiver) = TypingFilter::new(options, CollectorStats::default());
+
+        assert!(filter.is_passing_constraints(&typing(Some(1), 2, 3)));
+        assert!(filter.is_passing_constraints(&typing(None, 2, 4)));
+        assert!(!filter.is_passing_constraints(&typing(Some(1), 5, 3)));
+        assert!(!filter.is_passing_constraints(&typing(Some(1), 2, 5)));
+    }
+
+    #[test]
+    fn test_guild_and_filter() {
+        let options = FilterOptions {
+            guild_id: Some(vec![1]),
+            filter: Some(FilterFn::new(|event: &TypingStartEvent| event.timestamp > 0)),
+            ..Default::default()
+        };
+        let (filter, _receiver) = TypingFilter::new(options, CollectorStats::default());
+
+        assert!(filter.is_passing_constraints(&typing(Some(1), 2, 3)));
+        // Typing in private channels is not in any guild.
+        assert!(!filter.is_passing_constraints(&typing(None, 2, 3)));
+    }
+}
@@ -0,0 +1,758 @@
+use std::{
+    boxed::Box,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context as FutContext, Poll},
+    time::Duration,
+};
+
+use futures::{
+    future::BoxFuture,
+    stream::{Stream, StreamExt},
+};
+#[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
+use tokio::time::{delay_for as sleep, Delay as Sleep};
+#[cfg(feature = "tokio")]
+use tokio::time::{sleep, Sleep};
+
+use crate::{
+    client::bridge::gateway::ShardMessenger,
+    collector::{
+        add_ids,
+        channel,
+        collect_n,
+        collect_within,
+        is_id_accepted,
+        lower_collect_limit,
+        poll_recv,
+        AsyncFilter,
+        AsyncFilterFn,
+        CollectorHandle,
+        CollectorReceiver,
+        CollectorSender,
+        CollectorStats,
+        CompletionReason,
+        FilterFn,
+        FilterId,
+        IdleTimeout,
+        LazyArc,
+    },
+    model::{
+        id::{ChannelId, GuildId},
+        voice::VoiceState,
+    },
+};
+
+macro_rules! impl_voice_state_collector {
+    ($($name:ident;)*) => {
+        $(
+            impl<'a> $name<'a> {
+                /// Limits how many voice state updates will attempt to be filtered.
+                ///
+                /// The filter checks whether the user is the right user,
+                /// and connected to the right guild and channel.
+                pub fn filter_limit(mut self, limit: u32) -> Self {
+                    self.filter.as_mut().unwrap().filter_limit = Some(limit);
+
+                    self
+                }
+
+                /// Sets whether only near misses count towards the [`Self::filter_limit`],
+                /// which match all other constraints, but are rejected by [`Self::filter`].
+                ///
+                /// By default, every voice state update the filter checks counts towards the limit.
+                pub fn filter_limit_matching_only(mut self, matching_only: bool) -> Self {
+                    self.filter.as_mut().unwrap().filter_limit_matching_only = matching_only;
+
+                    self
+                }
+
+                /// Limits how many voice state updates can be collected.
+                ///
+                /// A voice state update is considered *collected*, if the update
+                /// passes all the requirements.
+                pub fn collect_limit(mut self, limit: u32) -> Self {
+                    self.filter.as_mut().unwrap().collect_limit = Some(limit);
+
+                    self
+                }
+
+                /// Limits how many voice state updates can wait to be received from the
+                /// collector. Once reached, further updates are dropped rather than waiting,
+                /// such as while the receiving task is busy.
+                ///
+                /// The dropped updates are counted by [`VoiceStateCollector::dropped_count`].
+                /// By default, any number of updates can wait.
+                /// A capacity of `0` is treated as `1`.
+                pub fn channel_capacity(mut self, capacity: usize) -> Self {
+                    self.filter.as_mut().unwrap().channel_capacity = Some(capacity);
+
+                    self
+                }
+
+                /// Sets a filter function where voice state updates passed to the function
+                /// must return `true`, otherwise the update won't be collected.
+                /// This is the last instance to pass for an update to count as *collected*.
+                pub fn filter<F: Fn(&VoiceStateAction) -> bool + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.filter.as_mut().unwrap().filter = Some(FilterFn::new(function));
+
+                    self
+                }
+
+                /// Sets an asynchronous filter function where voice state updates passed to the
+                /// function must resolve to `true`, otherwise they won't be collected.
+                ///
+                /// This runs on the collector's end once all other requirements passed,
+                /// including [`Self::filter`], so it may wait on a database or HTTP request.
+                /// Rejected voice state updates do not count towards [`Self::collect_limit`].
+                pub fn async_filter<F: Fn(&Arc<VoiceStateAction>) -> BoxFuture<'static, bool> + 'static + Send + Sync>(mut self, function: F) -> Self {
+                    self.async_filter = Some(AsyncFilterFn::new(function));
+
+                    self
+                }
+
+                /// Sets the user whose voice state must be updated.
+                /// If the voice state of another user is updated, it won't be received.
+                ///
+                /// Calling this multiple times accepts any of the users.
+                pub fn user_id(mut self, user_id: impl Into<u64>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().user_id, Some(user_id));
+
+                    self
+                }
+
+                /// Like [`Self::user_id`], but adds multiple IDs at once.
+                pub fn user_ids(mut self, user_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().user_id, user_ids);
+
+                    self
+                }
+
+                /// Sets the guild in which the voice state must be updated.
+                /// If a voice state is updated in another guild, it won't be received.
+                ///
+                /// Calling this multiple times accepts updates in any of the guilds.
+                pub fn guild_id(mut self, guild_id: impl Into<u64>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().guild_id, Some(guild_id));
+
+                    self
+                }
+
+                /// Like [`Self::guild_id`], but adds multiple IDs at once.
+                pub fn guild_ids(mut self, guild_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().guild_id, guild_ids);
+
+                    self
+                }
+
+                /// Sets the channel the user must be connected to after the update.
+                /// If a user is connected to another channel, it won't be received.
+                ///
+                /// Calling this multiple times accepts any of the channels. This does
+                /// not apply to users disconnecting, refer to [`Self::disconnected`].
+                pub fn channel_id(mut self, channel_id: impl Into<u64>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().channel_id, Some(channel_id));
+
+                    self
+                }
+
+                /// Like [`Self::channel_id`], but adds multiple IDs at once.
+                pub fn channel_ids(mut self, channel_ids: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+                    add_ids(&mut self.filter.as_mut().unwrap().channel_id, channel_ids);
+
+                    self
+                }
+
+                /// If set to `true`, updates of users connected to a voice channel
+                /// will be collected, such as users joining or moving to a channel.
+                ///
+                /// Set to `true` by default.
+                pub fn connected(mut self, is_accepted: bool) -> Self {
+                    self.filter.as_mut().unwrap().accept_connected = is_accepted;
+
+                    self
+                }
+
+                /// If set to `true`, users leaving voice entirely will be collected,
+                /// regardless of the channels set by [`Self::channel_id`].
+                ///
+                /// Set to `false` by default.
+                pub fn disconnected(mut self, is_accepted: bool) -> Self {
+                    self.filter.as_mut().unwrap().accept_disconnected = is_accepted;
+
+                    self
+                }
+
+                /// Sets a `duration` for how long the collector shall receive
+                /// voice state updates.
+                pub fn timeout(mut self, duration: Duration) -> Self {
+                    self.timeout = Some(Box::pin(sleep(duration)));
+
+                    self
+                }
+
+                /// Sets a `duration` after which the collector stops if it has not
+                /// collected a voice state update meanwhile, restarting whenever one
+                /// is collected.
+                ///
+                /// This can be combined with [`Self::timeout`], stopping the collector
+                /// once either of them elapsed.
+                pub fn idle_timeout(mut self, duration: Duration) -> Self {
+                    self.idle_timeout = Some(duration);
+
+                    self
+                }
+
+                /// Returns a handle to the statistics of the collector, which stays
+                /// usable once this is consumed, such as by awaiting it.
+                pub fn stats(&self) -> CollectorStats {
+                    self.stats.clone()
+                }
+            }
+        )*
+    }
+}
+
+/// A user's voice state before and after an update, telling users joining a
+/// voice channel apart from users moving between channels or leaving.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct VoiceStateAction {
+    /// The ID of the guild the voice state belongs to.
+    pub guild_id: Option<GuildId>,
+    /// The voice state before the update, if it was cached.
+    ///
+    /// **Note**: This is always [`None`] without the `cache` feature, and when
+    /// the user was not connected to a voice channel before.
+    pub old: Option<VoiceState>,
+    /// The voice state after the update.
+    pub new: VoiceState,
+}
+
+impl VoiceStateAction {
+    /// The ID of the channel the user was connected to before the update.
+    pub fn old_channel_id(&self) -> Option<ChannelId> {
+        self.old.as_ref().and_then(|old| old.channel_id)
+    }
+
+    /// The ID of the channel the user is connected to after the update.
+    pub fn channel_id(&self) -> Option<ChannelId> {
+        self.new.channel_id
+    }
+
+    /// Whether the user joined a voice channel, without being connected to
+    /// another one before.
+    ///
+    /// **Note**: Users moving between channels are considered to join if their
+    /// old voice state was not cached.
+    pub fn is_joined(&self) -> bool {
+        self.old_channel_id().is_none() && self.channel_id().is_some()
+    }
+
+    /// Whether the user moved from one voice channel to another.
+    pub fn is_moved(&self) -> bool {
+        match (self.old_channel_id(), self.channel_id()) {
+            (Some(old), Some(new)) => old != new,
+            _ => false,
+        }
+    }
+
+    /// Whether the user left voice entirely.
+    pub fn is_left(&self) -> bool {
+        self.channel_id().is_none()
+    }
+}
+
+/// Filters events on the shard's end and sends them to the collector.
+#[derive(Clone, Debug)]
+pub struct VoiceStateFilter {
+    id: FilterId,
+    stats: CollectorStats,
+    options: FilterOptions,
+    sender: CollectorSender<Arc<VoiceStateAction>>,
+}
+
+impl VoiceStateFilter {
+    /// Creates a new filter
+    fn new(
+        options: FilterOptions,
+        stats: CollectorStats,
+    ) -> (Self, CollectorReceiver<Arc<VoiceStateAction>>) {
+        let (sender, receiver) = channel(options.channel_capacity);
+
+        let filter = Self {
+            id: FilterId::next(),
+            stats,
+            sender,
+            options,
+        };
+
+        (filter, receiver)
+    }
+
+    /// The ID of the filter, identifying it on the shard runner.
+    pub fn id(&self) -> FilterId {
+        self.id
+    }
+
+    /// Whether the collector receiving from this filter was dropped.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
+    /// Sends a voice state update to the consuming collector if the update
+    /// conforms to the constraints and the limits are not reached yet.
+    pub(crate) fn send_voice_state(&mut self, action: &Arc<VoiceStateAction>) -> bool {
+        let is_matching = self.is_passing_constraints(action);
+        let is_passing = is_matching && self.is_passing_filter(action);
+
+        if is_passing && !self.sender.send(Arc::clone(action), &self.stats) {
+            return false;
+        }
+
+        self.stats.add_checked(self.options.filter_limit_matching_only, is_matching && !is_passing);
+
+        self.is_within_limits() && !self.sender.is_closed()
+    }
+
+    /// Checks if the voice state update passes set constraints.
+    /// Constraints are optional, as it is possible to limit updates to
+    /// a specific user or in a specific guild or channel.
+    fn is_passing_constraints(&self, action: &VoiceStateAction) -> bool {
+        let is_passing_channel = match action.channel_id() {
+            Some(channel_id) => {
+                self.options.accept_connected
+                    && is_id_accepted(self.options.channel_id.as_deref(), Some(channel_id.0))
+            },
+            None => self.options.accept_disconnected,
+        };
+
+        is_passing_channel
+            && is_id_accepted(self.options.guild_id.as_deref(), action.guild_id.map(|g| g.0))
+            && is_id_accepted(self.options.user_id.as_deref(), Some(action.new.user_id.0))
+    }
+
+    /// Checks if the `action` passes the filter function, if any.
+    fn is_passing_filter(&self, action: &VoiceStateAction) -> bool {
+        self.options.filter.as_ref().map_or(true, |f| f.call(&mut LazyArc::new(action)))
+    }
+
+    /// Checks if the filter is within set receive and collect limits.
+    /// A voice state update is considered *received* even when it does not
+    /// meet the constraints, unless only near misses are counted.
+    fn is_within_limits(&self) -> bool {
+        self.stats.is_within_limits(self.options.filter_limit, self.options.collect_limit)
+    }
+}
+
+#[derive(Clone)]
+struct FilterOptions {
+    filter_limit: Option<u32>,
+    filter_limit_matching_only: bool,
+    collect_limit: Option<u32>,
+    channel_capacity: Option<usize>,
+    filter: Option<FilterFn<VoiceStateAction>>,
+    channel_id: Option<Vec<u64>>,
+    guild_id: Option<Vec<u64>>,
+    user_id: Option<Vec<u64>>,
+    accept_connected: bool,
+    accept_disconnected: bool,
+}
+
+impl Default for FilterOptions {
+    fn default() -> Self {
+        Self {
+            filter_limit: None,
+            filter_limit_matching_only: false,
+            collect_limit: None,
+            channel_capacity: None,
+            filter: None,
+            channel_id: None,
+            guild_id: None,
+            user_id: None,
+            accept_connected: true,
+            accept_disconnected: false,
+        }
+    }
+}
+
+impl std::fmt::Debug for FilterOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VoiceStateFilter")
+            .field("collect_limit", &self.collect_limit)
+            .field("channel_capacity", &self.channel_capacity)
+            .field("filter", &self.filter)
+            .field("channel_id", &self.channel_id)
+            .field("guild_id", &self.guild_id)
+            .field("user_id", &self.user_id)
+            .field("accept_connected", &self.accept_connected)
+            .field("accept_disconnected", &self.accept_disconnected)
+            .finish()
+    }
+}
+
+// Implement the common setters for all voice state collector types.
+// This avoids using a trait that the user would need to import in
+// order to use any of these methods.
+impl_voice_state_collector! {
+    CollectVoiceState;
+    VoiceStateCollectorBuilder;
+}
+
+/// Builds a [`VoiceStateCollector`], collecting users joining, moving between
+/// or leaving voice channels.
+///
+/// **Note**: This requires the [`GatewayIntents::GUILD_VOICE_STATES`] intent.
+///
+/// [`GatewayIntents::GUILD_VOICE_STATES`]: crate::client::bridge::gateway::GatewayIntents::GUILD_VOICE_STATES
+pub struct VoiceStateCollectorBuilder<'a> {
+    filter: Option<FilterOptions>,
+    shard: Option<ShardMessenger>,
+    timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<VoiceStateAction>>,
+    stats: CollectorStats,
+    fut: Option<BoxFuture<'a, VoiceStateCollector>>,
+}
+
+impl<'a> VoiceStateCollectorBuilder<'a> {
+    pub fn new(shard_messenger: impl AsRef<ShardMessenger>) -> Self {
+        Self {
+            filter: Some(FilterOptions::default()),
+            shard: Some(shard_messenger.as_ref().clone()),
+            timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
+            fut: None,
+        }
+    }
+
+    /// Builds the collector and waits for up to `n` voice state updates.
+    ///
+    /// Collection ends early once the [`Self::timeout`], the
+    /// [`Self::idle_timeout`], the [`Self::collect_limit`] or the
+    /// [`Self::filter_limit`] is reached, whichever comes first, returning the
+    /// updates collected so far.
+    ///
+    /// The collect limit is lowered to `n` as well, so the collector ends on
+    /// its own once `n` voice state updates were collected, with its [`CollectorStats`]
+    /// reporting [`CompletionReason::CollectLimit`].
+    ///
+    /// Dropping the returned future stops the collector, discarding the
+    /// updates collected so far.
+    #[allow(clippy::unwrap_used)]
+    pub async fn collect_n(mut self, n: usize) -> Vec<Arc<VoiceStateAction>> {
+        lower_collect_limit(&mut self.filter.as_mut().unwrap().collect_limit, n);
+
+        collect_n(self.await, n).await
+    }
+
+    /// Builds the collector and returns all voice state updates within the
+    /// given `duration`.
+    ///
+    /// Refer to [`Self::collect_n`] for when collection ends early.
+    pub async fn collect_within(self, duration: Duration) -> Vec<Arc<VoiceStateAction>> {
+        collect_within(self.await, duration).await
+    }
+}
+
+impl<'a> Future for VoiceStateCollectorBuilder<'a> {
+    type Output = VoiceStateCollector;
+    #[allow(clippy::unwrap_used)]
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
+        if self.fut.is_none() {
+            let shard_messenger = self.shard.take().unwrap();
+            let collect_limit = self.filter.as_ref().unwrap().collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) =
+                VoiceStateFilter::new(self.filter.take().unwrap(), self.stats.clone());
+            let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
+
+            self.fut = Some(Box::pin(async move {
+                let filter_id = filter.id();
+                shard_messenger.set_voice_state_filter_sync(filter).await;
+
+                VoiceStateCollector {
+                    receiver: Box::pin(receiver),
+                    timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
+                }
+            }))
+        }
+
+        self.fut.as_mut().unwrap().as_mut().poll(ctx)
+    }
+}
+
+/// Waits for a single voice state update, such as for a user to join a voice
+/// channel before connecting to it.
+///
+/// Refer to [`VoiceStateCollectorBuilder`] for the required intent.
+pub struct CollectVoiceState<'a> {
+    filter: Option<FilterOptions>,
+    shard: Option<ShardMessenger>,
+    timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<Duration>,
+    async_filter: Option<AsyncFilterFn<VoiceStateAction>>,
+    stats: CollectorStats,
+    fut: Option<BoxFuture<'a, Option<Arc<VoiceStateAction>>>>,
+}
+
+impl<'a> CollectVoiceState<'a> {
+    pub fn new(shard_messenger: impl AsRef<ShardMessenger>) -> Self {
+        Self {
+            filter: Some(FilterOptions::default()),
+            shard: Some(shard_messenger.as_ref().clone()),
+            timeout: None,
+            idle_timeout: None,
+            async_filter: None,
+            stats: CollectorStats::default(),
+            fut: None,
+        }
+    }
+}
+
+impl<'a> Future for CollectVoiceState<'a> {
+    type Output = Option<Arc<VoiceStateAction>>;
+    #[allow(clippy::unwrap_used)]
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
+        if self.fut.is_none() {
+            let shard_messenger = self.shard.take().unwrap();
+            let collect_limit = self.filter.as_ref().unwrap().collect_limit;
+            let async_filter = self
+                .async_filter
+                .take()
+                .map(|function| AsyncFilter::new(function, collect_limit, &self.stats));
+            let (filter, receiver) =
+                VoiceStateFilter::new(self.filter.take().unwrap(), self.stats.clone());
+            let timeout = self.timeout.take();
+            let idle_timeout = self.idle_timeout.map(IdleTimeout::new);
+            let stats = self.stats.clone();
+
+            self.fut = Some(Box::pin(async move {
+                let filter_id = filter.id();
+                shard_messenger.set_voice_state_filter_sync(filter).await;
+
+                VoiceStateCollector {
+                    receiver: Box::pin(receiver),
+                    timeout,
+                    idle_timeout,
+                    async_filter,
+                    shard: shard_messenger,
+                    filter_id,
+                    handle: CollectorHandle::new(),
+                    stats,
+                }
+                .next()
+                .await
+            }))
+        }
+
+        self.fut.as_mut().unwrap().as_mut().poll(ctx)
+    }
+}
+
+/// A voice state collector receives voice state updates matching the given
+/// filter for a set duration.
+pub struct VoiceStateCollector {
+    receiver: Pin<Box<CollectorReceiver<Arc<VoiceStateAction>>>>,
+    timeout: Option<Pin<Box<Sleep>>>,
+    idle_timeout: Option<IdleTimeout>,
+    async_filter: Option<AsyncFilter<VoiceStateAction>>,
+    shard: ShardMessenger,
+    filter_id: FilterId,
+    handle: CollectorHandle,
+    stats: CollectorStats,
+}
+
+impl VoiceStateCollector {
+    /// Stops collecting, this will implicitly be done once the
+    /// collector drops.
+    /// In case the drop does not appear until later, it is preferred to
+    /// stop the collector early.
+    ///
+    /// Either way, the collector's filter is removed from the shard.
+    pub fn stop(mut self) {
+        self.receiver.close();
+    }
+
+    /// Returns a handle to stop the collector from another task, such as
+    /// while this one is waiting for the collector's next item.
+    pub fn handle(&self) -> CollectorHandle {
+        self.handle.clone()
+    }
+
+    /// Returns a handle to the statistics of the collector, such as to check
+    /// why it ended.
+    pub fn stats(&self) -> CollectorStats {
+        self.stats.clone()
+    }
+
+    /// The number of voice state updates which were dropped as the
+    /// [channel capacity] was reached.
+    ///
+    /// [channel capacity]: VoiceStateCollectorBuilder::channel_capacity
+    pub fn dropped_count(&self) -> u64 {
+        self.receiver.dropped_count()
+    }
+}
+
+impl Stream for VoiceStateCollector {
+    type Item = Arc<VoiceStateAction>;
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Option<Self::Item>> {
+        if self.handle.poll_stopped(ctx) {
+            self.receiver.close();
+            self.stats.complete(CompletionReason::Stopped);
+
+            return Poll::Ready(None);
+        }
+
+        if let Some(ref mut timeout) = self.timeout {
+            match timeout.as_mut().poll(ctx) {
+                Poll::Ready(_) => {
+                    self.stats.complete(CompletionReason::Timeout);
+
+                    return Poll::Ready(None);
+                },
+                Poll::Pending => (),
+            }
+        }
+
+        if let Some(ref mut idle_timeout) = self.idle_timeout {
+            if idle_timeout.is_elapsed(ctx) {
+                self.stats.complete(CompletionReason::IdleTimeout);
+
+                return Poll::Ready(None);
+            }
+        }
+
+        let this = &mut *self;
+        let item = poll_recv(&mut this.receiver, this.async_filter.as_mut(), &this.stats, ctx);
+
+        if let Poll::Ready(None) = item {
+            self.stats.complete(CompletionReason::Disconnected);
+        }
+
+        if let (Poll::Ready(Some(_)), Some(idle_timeout)) = (&item, &mut self.idle_timeout) {
+            idle_timeout.reset();
+        }
+
+        item
+    }
+}
+
+impl Drop for VoiceStateCollector {
+    fn drop(&mut self) {
+        self.receiver.close();
+        self.stats.complete(CompletionReason::Stopped);
+        self.shard.remove_voice_state_filter(self.filter_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{FilterOptions, VoiceStateAction, VoiceStateFilter};
+    use crate::collector::{CollectorStats, FilterFn};
+    use crate::model::id::{ChannelId, GuildId};
+    use crate::model::voice::VoiceState;
+
+    fn voice_state(user_id: u64, channel_id: Option<u64>) -> VoiceState {
+        serde_json::from_value(json!({
+            "guild_id": "1",
+            "channel_id": channel_id.map(|id| id.to_string()),
+            "user_id": user_id.to_string(),
+            "session_id": "session",
+            "deaf": false,
+            "mute": false,
+            "self_deaf": false,
+            "self_mute": false,
+            "self_video": false,
+            "suppress": false,
+            "request_to_speak_timestamp": null,
+        }))
+        .unwrap()
+    }
+
+    fn action(user_id: u64, old: Option<u64>, new: Option<u64>) -> VoiceStateAction {
+        VoiceStateAction {
+            guild_id: Some(GuildId(1)),
+            old: old.map(|channel_id| voice_state(user_id, Some(channel_id))),
+            new: voice_state(user_id, new),
+        }
+    }
+
+    #[test]
+    fn test_action_kinds() {
+        let joined = action(2, None, Some(3));
+        assert!(joined.is_joined() && !joined.is_moved() && !joined.is_left());
+
+        let moved = action(2, Some(3), Some(4));
+        assert!(!moved.is_joined() && moved.is_moved() && !moved.is_left());
+        assert_eq!(moved.old_channel_id(), Some(ChannelId(3)));
+        assert_eq!(moved.channel_id(), Some(ChannelId(4)));
+
+        let left = action(2, Some(3), None);
+        assert!(!left.is_joined() && !left.is_moved() && left.is_left());
+
+        // A state update within the same channel, such as muting.
+        let updated = action(2, Some(3), Some(3));
+        assert!(!updated.is_joined() && !updated.is_moved() && !updated.is_left());
+    }
+
+    #[test]
+    fn test_connected_by_default() {
+        let options = FilterOptions {
+            channel_id: Some(vec![3]),
+            user_id: Some(vec![2]),
+            ..Default::default()
+        };
+        let (filter, _receiver) = VoiceStateFilter::new(options, CollectorStats::default());
+
+        assert!(filter.is_passing_constraints(&action(2, None, Some(3))));
+        assert!(filter.is_passing_constraints(&action(2, Some(4), Some(3))));
+        assert!(!filter.is_passing_constraints(&action(2, Some(3), Some(4))));
+        assert!(!filter.is_passing_constraints(&action(2, Some(3), None)));
+        assert!(!filter.is_passing_constraints(&action(5, None, Some(3))));
+    }
+
+    #[test]
+    fn test_disconnected() {
+        let options = FilterOptions {
+            guild_id: Some(vec![1]),
+            channel_id: Some(vec![3]),
+            accept_connected: false,
+            accept_disconnected: true,
+            ..Default::default()
+        };
+        let (filter, _receiver) = VoiceStateFilter::new(options, CollectorStats::default());
+
+        assert!(filter.is_passing_constraints(&action(2, Some(4), None)));
+        assert!(!filter.is_passing_constraints(&action(2, None, Some(3))));
+    }
+
+    #[test]
+    fn test_filter_function() {
+        let options = FilterOptions {
+            filter: Some(FilterFn::new(VoiceStateAction::is_moved)),
+            ..Default::default()
+        };
+        let (filter, _receiver) = VoiceStateFilter::new(options, CollectorStats::default());
+
+        assert!(filter.is_passing_filter(&action(2, Some(3), Some(4))));
+        assert!(!filter.is_passing_filter(&action(2, None, Some(4))));
+    }
+}
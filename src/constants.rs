@@ -1,24 +1,80 @@
 //! A set of constants used by the library.
 
 /// The maximum length of the textual size of an embed.
-pub const EMBED_MAX_LENGTH: usize = 6000;
+///
+/// Equivalent to [`limits::EMBED_TOTAL_LENGTH`].
+pub const EMBED_MAX_LENGTH: usize = limits::EMBED_TOTAL_LENGTH;
 
 /// The maximum number of embeds in a message.
-pub const EMBED_MAX_COUNT: usize = 10;
+///
+/// Equivalent to [`limits::EMBED_COUNT`].
+pub const EMBED_MAX_COUNT: usize = limits::EMBED_COUNT;
 
 /// The gateway version used by the library. The gateway URI is retrieved via
 /// the REST API.
+///
+/// Equivalent to the default [`ApiVersion`].
 pub const GATEWAY_VERSION: u8 = 9;
 
+/// A version of Discord's API, used both for the base path of REST requests
+/// and to connect to the gateway.
+///
+/// Defaults to the version the library was built against, which is
+/// [`GATEWAY_VERSION`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum ApiVersion {
+    V9 = 9,
+    V10 = 10,
+}
+
+impl ApiVersion {
+    /// Returns the number of the version.
+    pub fn num(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the version with the given number, or [`None`] if the library
+    /// does not support it.
+    pub fn from_num(num: u8) -> Option<Self> {
+        match num {
+            9 => Some(Self::V9),
+            10 => Some(Self::V10),
+            _ => None,
+        }
+    }
+}
+
+impl Default for ApiVersion {
+    fn default() -> Self {
+        Self::V9
+    }
+}
+
 /// The large threshold to send on identify.
 pub const LARGE_THRESHOLD: u8 = 250;
 
+/// The default maximum size of a gateway payload, in bytes, after it was
+/// reassembled and decompressed.
+pub const DEFAULT_MAX_PAYLOAD_SIZE: usize = 64 * 1024 * 1024;
+
 /// The maximum unicode code points allowed within a message by Discord.
-pub const MESSAGE_CODE_LIMIT: usize = 2000;
+///
+/// Equivalent to [`limits::MESSAGE_CONTENT_LENGTH`].
+pub const MESSAGE_CODE_LIMIT: usize = limits::MESSAGE_CONTENT_LENGTH;
 
 /// The maximum number of members the bot can fetch at once
 pub const MEMBER_FETCH_LIMIT: u64 = 1000;
 
+/// The maximum number of messages fetched at once by
+/// [`ChannelId::messages_by_ids`].
+///
+/// All of them share a ratelimit bucket, so fetching more at once would only
+/// have them wait on the ratelimiter.
+///
+/// [`ChannelId::messages_by_ids`]: crate::model::id::ChannelId::messages_by_ids
+pub const MESSAGE_FETCH_CONCURRENCY: usize = 5;
+
 /// The [UserAgent] sent along with every request.
 ///
 /// [UserAgent]: ::reqwest::header::USER_AGENT
@@ -28,6 +84,64 @@ pub const USER_AGENT: &str = concat!(
     ")"
 );
 
+/// The limits Discord imposes on the messages and embeds it accepts.
+///
+/// Lengths are counted in unicode code points, not bytes.
+pub mod limits {
+    /// The maximum length of the content of a message.
+    pub const MESSAGE_CONTENT_LENGTH: usize = 2000;
+
+    /// The maximum number of embeds in a message.
+    pub const EMBED_COUNT: usize = 10;
+
+    /// The maximum combined length of the title, description, author name,
+    /// footer text, and field names and values of all embeds of a message.
+    pub const EMBED_TOTAL_LENGTH: usize = 6000;
+
+    /// The maximum length of the title of an embed.
+    pub const EMBED_TITLE_LENGTH: usize = 256;
+
+    /// The maximum length of the description of an embed.
+    pub const EMBED_DESCRIPTION_LENGTH: usize = 4096;
+
+    /// The maximum length of the name of the author of an embed.
+    pub const EMBED_AUTHOR_NAME_LENGTH: usize = 256;
+
+    /// The maximum length of the footer text of an embed.
+    pub const EMBED_FOOTER_TEXT_LENGTH: usize = 2048;
+
+    /// The maximum number of fields in an embed.
+    pub const EMBED_FIELD_COUNT: usize = 25;
+
+    /// The maximum length of the name of an embed field.
+    pub const EMBED_FIELD_NAME_LENGTH: usize = 256;
+
+    /// The maximum length of the value of an embed field.
+    pub const EMBED_FIELD_VALUE_LENGTH: usize = 1024;
+
+    /// The maximum number of action rows of components in a message.
+    pub const ACTION_ROW_COUNT: usize = 5;
+
+    /// The maximum number of buttons in an action row.
+    pub const ACTION_ROW_BUTTON_COUNT: usize = 5;
+
+    /// The maximum number of options of a select menu.
+    pub const SELECT_MENU_OPTION_COUNT: usize = 25;
+
+    /// The maximum length of the label of an input text.
+    pub const INPUT_TEXT_LABEL_LENGTH: usize = 45;
+
+    /// The maximum length of the value of an input text, which also bounds
+    /// its minimum and maximum length.
+    pub const INPUT_TEXT_LENGTH: usize = 4000;
+
+    /// The maximum number of files attached to a message.
+    pub const FILE_COUNT: usize = 10;
+
+    /// The maximum number of stickers in a message.
+    pub const STICKER_COUNT: usize = 3;
+}
+
 /// List of messages Discord shows on member join.
 #[allow(clippy::non_ascii_literal)] // allow for discord join messages
 pub static JOIN_MESSAGES: &[&str] = &[
@@ -27,6 +27,7 @@ use crate::internal::prelude::*;
     not(feature = "native_tls_backend_marker")
 ))]
 use crate::internal::ws_impl::RustlsError;
+use crate::model::guild::HierarchyError;
 use crate::model::ModelError;
 
 /// The common result type between most library functions.
@@ -153,6 +154,12 @@ impl From<ModelError> for Error {
     }
 }
 
+impl From<HierarchyError> for Error {
+    fn from(e: HierarchyError) -> Error {
+        Error::Model(e.into())
+    }
+}
+
 #[cfg(all(
     feature = "gateway",
     feature = "rustls_backend_marker",
@@ -188,7 +195,7 @@ impl From<InvalidHeaderValue> for Error {
 #[cfg(feature = "http")]
 impl From<ReqwestError> for Error {
     fn from(e: ReqwestError) -> Error {
-        HttpError::Request(e).into()
+        HttpError::from(e).into()
     }
 }
 
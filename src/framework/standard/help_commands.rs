@@ -339,8 +339,13 @@ async fn check_common_behaviour(
         return help_options.lacking_permissions;
     }
 
+    let member = match msg.guild_id {
+        Some(guild_id) => cache_http.as_ref().member(guild_id, msg.author.id).await,
+        None => None,
+    };
+
     msg.guild_field(&cache_http, |guild| {
-        if let Some(member) = guild.members.get(&msg.author.id) {
+        if let Some(member) = &member {
             if !has_correct_roles(options, &guild.roles, member) {
                 return help_options.lacking_role;
             }
@@ -839,6 +839,16 @@ pub(crate) async fn has_correct_permissions(
     if options.required_permissions().is_empty() {
         true
     } else {
+        let member = match message.guild_id {
+            Some(guild_id) => cache.as_ref().member(guild_id, message.author.id).await,
+            None => None,
+        };
+
+        let member = match member {
+            Some(member) => member,
+            None => return false,
+        };
+
         message
             .guild_field(cache, |guild| {
                 let channel = match guild.channels.get(&message.channel_id) {
@@ -846,12 +856,7 @@ pub(crate) async fn has_correct_permissions(
                     None => return false,
                 };
 
-                let member = match guild.members.get(&message.author.id) {
-                    Some(member) => member,
-                    None => return false,
-                };
-
-                match guild.user_permissions_in(channel, member) {
+                match guild.user_permissions_in(channel, &member) {
                     Ok(perms) => perms.contains(*options.required_permissions()),
                     Err(e) => {
                         tracing::error!(
@@ -262,19 +262,18 @@ async fn check_discrepancy(
     #[cfg(feature = "cache")]
     {
         if let Some(guild_id) = msg.guild_id {
-            let member = match ctx
-                .cache
-                .guild_field(guild_id, |guild| guild.members.get(&msg.author.id).cloned())
-                .await
-            {
-                Some(Some(member)) => member,
+            // Guild not found.
+            if !ctx.cache.guilds.read().await.contains_key(&guild_id) {
+                return Ok(());
+            }
+
+            let member = match ctx.cache.member(guild_id, msg.author.id).await {
+                Some(member) => member,
                 // Member not found.
-                Some(None) => match ctx.http.get_member(guild_id.0, msg.author.id.0).await {
+                None => match ctx.http.get_member(guild_id.0, msg.author.id.0).await {
                     Ok(member) => member,
                     Err(_) => return Ok(()),
                 },
-                // Guild not found.
-                None => return Ok(()),
             };
             #[allow(clippy::unwrap_used)] // Allowing unwrap because should always return Some()
             let roles = ctx.cache.guild_field(guild_id, |guild| guild.roles.clone()).await.unwrap();
@@ -536,3 +535,43 @@ pub enum Invoke {
     Command { group: &'static CommandGroup, command: &'static Command },
     Help(&'static str),
 }
+
+#[cfg(test)]
+mod test {
+    use uwl::Stream;
+
+    use super::mention;
+    use crate::framework::standard::Configuration;
+    use crate::model::id::UserId;
+
+    #[test]
+    fn test_mention() {
+        let mut config = Configuration::default();
+        config.on_mention(Some(UserId(210)));
+
+        let parse = |content: &str| {
+            let mut stream = Stream::new(content);
+            let id = mention(&mut stream, &config).map(ToString::to_string);
+
+            (id, stream.offset())
+        };
+
+        assert_eq!(parse("<@210> ping"), (Some("210".to_string()), 6));
+        assert_eq!(parse("<@!210> ping"), (Some("210".to_string()), 7));
+
+        // Other users, roles and malformed mentions are not matched, leaving
+        // the stream untouched.
+        assert_eq!(parse("<@2101> ping"), (None, 0));
+        assert_eq!(parse("<@&210> ping"), (None, 0));
+        assert_eq!(parse("<@!!210> ping"), (None, 0));
+        assert_eq!(parse("<@210 ping"), (None, 0));
+        assert_eq!(parse("<#210> ping"), (None, 0));
+    }
+
+    #[test]
+    fn test_mention_disabled() {
+        let mut stream = Stream::new("<@210> ping");
+
+        assert_eq!(mention(&mut stream, &Configuration::default()), None);
+    }
+}
@@ -55,6 +55,32 @@ pub enum Error {
     /// If an connection has been established but privileged gateway intents
     /// were provided without enabling them prior.
     DisallowedGatewayIntents,
+    /// When a received payload could not be deserialized into a gateway event,
+    /// e.g. because Discord changed the shape of the event.
+    ///
+    /// The payload is truncated according to
+    /// [`HttpBuilder::body_snippet_limit`].
+    ///
+    /// [`HttpBuilder::body_snippet_limit`]: crate::http::HttpBuilder::body_snippet_limit
+    DeserializationFailed {
+        /// The type name of the event, e.g. `MESSAGE_CREATE`, if it was a
+        /// dispatch.
+        event_type: Option<String>,
+        /// The message of the error returned by the deserializer.
+        error: String,
+        /// The start of the payload, or `None` if it was omitted.
+        payload_snippet: Option<String>,
+    },
+    /// When a received payload exceeded the maximum size, either as sent or
+    /// once decompressed. The shard drops the connection and identifies anew.
+    ///
+    /// The limit defaults to [`DEFAULT_MAX_PAYLOAD_SIZE`].
+    ///
+    /// [`DEFAULT_MAX_PAYLOAD_SIZE`]: crate::constants::DEFAULT_MAX_PAYLOAD_SIZE
+    PayloadTooLarge {
+        /// The maximum size of a payload, in bytes.
+        limit: usize,
+    },
 }
 
 impl Display for Error {
@@ -76,6 +102,27 @@ impl Display for Error {
             Error::DisallowedGatewayIntents => {
                 f.write_str("Disallowed gateway intents were provided")
             },
+            Error::DeserializationFailed {
+                event_type,
+                error,
+                payload_snippet,
+            } => {
+                match event_type {
+                    Some(event_type) => write!(f, "Error deserializing a {} event", event_type)?,
+                    None => f.write_str("Error deserializing a gateway event")?,
+                }
+
+                write!(f, ": {}", error)?;
+
+                if let Some(payload_snippet) = payload_snippet {
+                    write!(f, "; payload: {}", payload_snippet)?;
+                }
+
+                Ok(())
+            },
+            Error::PayloadTooLarge {
+                limit,
+            } => write!(f, "Payload exceeded the maximum size of {} bytes", limit),
         }
     }
 }
@@ -174,7 +174,7 @@ pub enum ShardAction {
 }
 
 /// The type of reconnection that should be performed.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum ReconnectType {
     /// Indicator that a new connection should be made by sending an IDENTIFY.
@@ -7,6 +7,7 @@ use async_tungstenite::tungstenite::{
     error::Error as TungsteniteError,
     protocol::frame::CloseFrame,
 };
+use serde::Deserialize;
 use tokio::sync::Mutex;
 use tracing::{debug, error, info, instrument, trace, warn};
 use url::Url;
@@ -21,7 +22,7 @@ use super::{
     WsStream,
 };
 use crate::client::bridge::gateway::{ChunkGuildFilter, GatewayIntents};
-use crate::constants::{self, close_codes};
+use crate::constants::{self, close_codes, ApiVersion, OpCode};
 use crate::internal::prelude::*;
 #[cfg(feature = "native_tls_backend_marker")]
 use crate::internal::ws_impl::create_native_tls_client;
@@ -84,8 +85,7 @@ pub struct Shard {
     // This _must_ be set to `true` in `Shard::handle_event`'s
     // `Ok(GatewayEvent::HeartbeatAck)` arm.
     last_heartbeat_acknowledged: bool,
-    seq: u64,
-    session_id: Option<String>,
+    session: Session,
     shard_info: [u64; 2],
     /// Whether the shard has permanently shutdown.
     shutdown: bool,
@@ -97,6 +97,8 @@ pub struct Shard {
     pub token: String,
     ws_url: Arc<Mutex<String>>,
     pub intents: GatewayIntents,
+    max_payload_size: usize,
+    api_version: ApiVersion,
 }
 
 impl Shard {
@@ -136,22 +138,83 @@ impl Shard {
     /// or a Rustls/native TLS error.
     ///
     /// [`Error::Gateway`]: crate::Error::Gateway
+    #[inline]
     pub async fn new(
         ws_url: Arc<Mutex<String>>,
         token: &str,
         shard_info: [u64; 2],
         intents: GatewayIntents,
+    ) -> Result<Shard> {
+        Self::new_with_max_payload_size(
+            ws_url,
+            token,
+            shard_info,
+            intents,
+            constants::DEFAULT_MAX_PAYLOAD_SIZE,
+        )
+        .await
+    }
+
+    /// Instantiates a new instance of a Shard like [`Self::new`], limiting
+    /// the size of received payloads to `max_payload_size` bytes.
+    ///
+    /// The limit applies to a payload once it was reassembled from its
+    /// websocket frames, and again once it was decompressed. A payload
+    /// exceeding it results in a [`GatewayError::PayloadTooLarge`] instead
+    /// of being read into memory, after which the shard reconnects.
+    ///
+    /// # Errors
+    /// On Error, will return either [`Error::Gateway`], [`Error::Tungstenite`]
+    /// or a Rustls/native TLS error.
+    ///
+    /// [`Error::Gateway`]: crate::Error::Gateway
+    #[inline]
+    pub async fn new_with_max_payload_size(
+        ws_url: Arc<Mutex<String>>,
+        token: &str,
+        shard_info: [u64; 2],
+        intents: GatewayIntents,
+        max_payload_size: usize,
+    ) -> Result<Shard> {
+        Self::new_with_api_version(
+            ws_url,
+            token,
+            shard_info,
+            intents,
+            max_payload_size,
+            ApiVersion::default(),
+        )
+        .await
+    }
+
+    /// Instantiates a new instance of a Shard like
+    /// [`Self::new_with_max_payload_size`], connecting to the gateway with
+    /// the given version of the API.
+    ///
+    /// The version is passed as the `v` query parameter of the gateway URL,
+    /// both when connecting and when reconnecting to resume a session.
+    ///
+    /// # Errors
+    /// On Error, will return either [`Error::Gateway`], [`Error::Tungstenite`]
+    /// or a Rustls/native TLS error.
+    ///
+    /// [`Error::Gateway`]: crate::Error::Gateway
+    pub async fn new_with_api_version(
+        ws_url: Arc<Mutex<String>>,
+        token: &str,
+        shard_info: [u64; 2],
+        intents: GatewayIntents,
+        max_payload_size: usize,
+        api_version: ApiVersion,
     ) -> Result<Shard> {
         let url = ws_url.lock().await.clone();
-        let client = connect(&url).await?;
+        let client = connect(&url, max_payload_size, api_version).await?;
 
         let current_presence = (None, OnlineStatus::Online);
         let heartbeat_instants = (None, None);
         let heartbeat_interval = None;
         let last_heartbeat_acknowledged = true;
-        let seq = 0;
         let stage = ConnectionStage::Handshake;
-        let session_id = None;
 
         Ok(Shard {
             shutdown: false,
@@ -160,17 +223,30 @@ impl Shard {
             heartbeat_instants,
             heartbeat_interval,
             last_heartbeat_acknowledged,
-            seq,
+            session: Session::default(),
             stage,
             started: Instant::now(),
             token: token.to_string(),
-            session_id,
             shard_info,
             ws_url,
             intents,
+            max_payload_size,
+            api_version,
         })
     }
 
+    /// The maximum size of a received payload, in bytes.
+    #[inline]
+    pub fn max_payload_size(&self) -> usize {
+        self.max_payload_size
+    }
+
+    /// The version of the API the shard connects to the gateway with.
+    #[inline]
+    pub fn api_version(&self) -> ApiVersion {
+        self.api_version
+    }
+
     /// Retrieves the current presence of the shard.
     #[inline]
     pub fn current_presence(&self) -> &CurrentPresence {
@@ -215,7 +291,7 @@ impl Shard {
     /// a heartbeat.
     #[instrument(skip(self))]
     pub async fn heartbeat(&mut self) -> Result<()> {
-        match self.client.send_heartbeat(&self.shard_info, Some(self.seq)).await {
+        match self.client.send_heartbeat(&self.shard_info, Some(self.session.seq)).await {
             Ok(()) => {
                 self.heartbeat_instants.0 = Some(Instant::now());
                 self.last_heartbeat_acknowledged = false;
@@ -250,13 +326,23 @@ impl Shard {
     }
 
     #[inline]
+    #[deprecated(note = "use `sequence` instead")]
     pub fn seq(&self) -> u64 {
-        self.seq
+        self.session.seq
+    }
+
+    /// Returns the sequence of the last dispatch received in the current
+    /// session, which is sent with heartbeats and when resuming.
+    ///
+    /// This also counts dispatches which failed to deserialize.
+    #[inline]
+    pub fn sequence(&self) -> u64 {
+        self.session.seq
     }
 
     #[inline]
     pub fn session_id(&self) -> Option<&String> {
-        self.session_id.as_ref()
+        self.session.id.as_ref()
     }
 
     #[inline]
@@ -327,17 +413,15 @@ impl Shard {
         self.stage
     }
 
+    /// Handles a dispatch whose sequence was already tracked by
+    /// [`Self::handle_frame`].
     #[instrument(skip(self))]
-    fn handle_gateway_dispatch(&mut self, seq: u64, event: &Event) -> Option<ShardAction> {
-        if seq > self.seq + 1 {
-            warn!("[Shard {:?}] Sequence off; them: {}, us: {}", self.shard_info, seq, self.seq);
-        }
-
+    fn handle_gateway_dispatch(&mut self, event: &Event) -> Option<ShardAction> {
         match event {
             Event::Ready(ref ready) => {
                 debug!("[Shard {:?}] Received Ready", self.shard_info);
 
-                self.session_id = Some(ready.ready.session_id.clone());
+                self.session.start(ready.ready.session_id.clone());
                 self.stage = ConnectionStage::Connected;
             },
             Event::Resumed(_) => {
@@ -350,20 +434,38 @@ impl Shard {
             _ => {},
         }
 
-        self.seq = seq;
-
         None
     }
 
+    /// Keeps track of the sequence of a received frame if it is a dispatch.
+    ///
+    /// This is done before the frame is deserialized, so that the sequence
+    /// advances even if the dispatch is skipped by an event type filter or
+    /// fails to deserialize. Otherwise, the next heartbeat or resume would
+    /// refer to an outdated sequence, replaying events.
+    pub(crate) fn handle_frame(&mut self, frame: &Value) {
+        let previous = self.session.seq;
+
+        if let Some(seq) = self.session.track_dispatch(frame) {
+            self.check_seq(previous, seq);
+        }
+    }
+
+    fn check_seq(&self, previous: u64, seq: u64) {
+        if seq > previous.saturating_add(1) {
+            warn!("[Shard {:?}] Sequence off; them: {}, us: {}", self.shard_info, seq, previous);
+        }
+    }
+
     #[instrument(skip(self))]
     fn handle_heartbeat_event(&mut self, s: u64) -> ShardAction {
         info!("[Shard {:?}] Received shard heartbeat", self.shard_info);
 
         // Received seq is off -- attempt to resume.
-        if s > self.seq + 1 {
+        if s > self.session.seq.saturating_add(1) {
             info!(
                 "[Shard {:?}] Received off sequence (them: {}; us: {}); resuming",
-                self.shard_info, s, self.seq
+                self.shard_info, s, self.session.seq
             );
 
             if self.stage == ConnectionStage::Handshake {
@@ -415,9 +517,7 @@ impl Shard {
                 warn!("[Shard {:?}] Already authenticated.", self.shard_info);
             },
             Some(close_codes::INVALID_SEQUENCE) => {
-                warn!("[Shard {:?}] Sent invalid seq: {}.", self.shard_info, self.seq);
-
-                self.seq = 0;
+                warn!("[Shard {:?}] Sent invalid seq: {}.", self.shard_info, self.session.seq);
             },
             Some(close_codes::RATE_LIMITED) => {
                 warn!("[Shard {:?}] Gateway ratelimited.", self.shard_info);
@@ -434,8 +534,6 @@ impl Shard {
             },
             Some(4006) | Some(close_codes::SESSION_TIMEOUT) => {
                 info!("[Shard {:?}] Invalid session.", self.shard_info);
-
-                self.session_id = None;
             },
             Some(close_codes::INVALID_GATEWAY_INTENTS) => {
                 error!("[Shard {:?}] Invalid gateway intents have been provided.", self.shard_info);
@@ -461,15 +559,7 @@ impl Shard {
             _ => {},
         }
 
-        let resume = num
-            .map(|x| x != close_codes::AUTHENTICATION_FAILED && self.session_id.is_some())
-            .unwrap_or(true);
-
-        Ok(Some(if resume {
-            ShardAction::Reconnect(ReconnectType::Resume)
-        } else {
-            ShardAction::Reconnect(ReconnectType::Reidentify)
-        }))
+        Ok(Some(ShardAction::Reconnect(self.session.closed(num))))
     }
 
     /// Handles an event from the gateway over the receiver, requiring the
@@ -502,9 +592,7 @@ impl Shard {
         event: &Result<GatewayEvent>,
     ) -> Result<Option<ShardAction>> {
         match *event {
-            Ok(GatewayEvent::Dispatch(seq, ref event)) => {
-                Ok(self.handle_gateway_dispatch(seq, event))
-            },
+            Ok(GatewayEvent::Dispatch(_, ref event)) => Ok(self.handle_gateway_dispatch(event)),
             Ok(GatewayEvent::Heartbeat(s)) => Ok(Some(self.handle_heartbeat_event(s))),
             Ok(GatewayEvent::HeartbeatAck) => {
                 self.heartbeat_instants.1 = Some(Instant::now());
@@ -536,14 +624,27 @@ impl Shard {
             Ok(GatewayEvent::InvalidateSession(resumable)) => {
                 info!("[Shard {:?}] Received session invalidation", self.shard_info,);
 
-                Ok(Some(if resumable {
-                    ShardAction::Reconnect(ReconnectType::Resume)
-                } else {
-                    ShardAction::Reconnect(ReconnectType::Reidentify)
-                }))
+                Ok(Some(ShardAction::Reconnect(self.session.invalidated(resumable))))
+            },
+            Ok(GatewayEvent::Reconnect) => {
+                Ok(Some(ShardAction::Reconnect(self.reconnection_type())))
             },
-            Ok(GatewayEvent::Reconnect) => Ok(Some(ShardAction::Reconnect(ReconnectType::Resume))),
             Err(Error::Gateway(GatewayError::Closed(ref data))) => self.handle_gateway_closed(data),
+            // Already logged with the event's payload by the shard runner.
+            Err(Error::Gateway(GatewayError::DeserializationFailed {
+                ..
+            })) => Ok(None),
+            Err(Error::Gateway(GatewayError::PayloadTooLarge {
+                limit,
+            })) => {
+                warn!(
+                    "[Shard {:?}] Received a payload over the limit of {} bytes",
+                    self.shard_info, limit
+                );
+
+                // Resuming would replay the same payload.
+                Ok(Some(ShardAction::Reconnect(ReconnectType::Reidentify)))
+            },
             Err(Error::Tungstenite(ref why)) => {
                 warn!("[Shard {:?}] Websocket error: {:?}", self.shard_info, why);
                 info!("[Shard {:?}] Will attempt to auto-reconnect", self.shard_info);
@@ -644,11 +745,7 @@ impl Shard {
     }
 
     pub fn reconnection_type(&self) -> ReconnectType {
-        if self.session_id().is_some() {
-            ReconnectType::Resume
-        } else {
-            ReconnectType::Reidentify
-        }
+        self.session.reconnection_type()
     }
 
     /// Requests that one or multiple [`Guild`]s be chunked.
@@ -737,8 +834,11 @@ impl Shard {
     ///
     /// - the time that the last heartbeat sent as being now
     /// - the `stage` to [`ConnectionStage::Identifying`]
+    ///
+    /// Any previous session is discarded, as identifying starts a new one.
     #[instrument(skip(self))]
     pub async fn identify(&mut self) -> Result<()> {
+        self.session.clear();
         self.client.send_identify(&self.shard_info, &self.token, self.intents).await?;
 
         self.heartbeat_instants.0 = Some(Instant::now());
@@ -766,7 +866,7 @@ impl Shard {
         self.stage = ConnectionStage::Connecting;
         self.started = Instant::now();
         let url = &self.ws_url.lock().await.clone();
-        let client = connect(url).await?;
+        let client = connect(url, self.max_payload_size, self.api_version).await?;
         self.stage = ConnectionStage::Handshake;
 
         Ok(client)
@@ -777,9 +877,8 @@ impl Shard {
         self.heartbeat_instants = (Some(Instant::now()), None);
         self.heartbeat_interval = None;
         self.last_heartbeat_acknowledged = true;
-        self.session_id = None;
+        self.session.clear();
         self.stage = ConnectionStage::Disconnected;
-        self.seq = 0;
     }
 
     #[instrument(skip(self))]
@@ -789,9 +888,11 @@ impl Shard {
         self.client = self.initialize().await?;
         self.stage = ConnectionStage::Resuming;
 
-        match self.session_id.as_ref() {
+        match self.session.id.as_ref() {
             Some(session_id) => {
-                self.client.send_resume(&self.shard_info, session_id, self.seq, &self.token).await
+                let seq = self.session.seq;
+
+                self.client.send_resume(&self.shard_info, session_id, seq, &self.token).await
             },
             None => Err(Error::Gateway(GatewayError::NoSessionId)),
         }
@@ -813,24 +914,205 @@ impl Shard {
     }
 }
 
-#[cfg(all(feature = "rustls_backend_marker", not(feature = "native_tls_backend_marker")))]
-async fn connect(base_url: &str) -> Result<WsStream> {
-    let url = build_gateway_url(base_url)?;
+/// The gateway session of a shard, along with the sequence of the last
+/// dispatch received within it.
+///
+/// A sequence is only meaningful within the session it was received in, so
+/// both are always cleared together.
+#[derive(Debug, Default)]
+struct Session {
+    id: Option<String>,
+    seq: u64,
+}
+
+impl Session {
+    /// Starts the session with the given Id, as received in a Ready.
+    fn start(&mut self, id: String) {
+        self.id = Some(id);
+    }
+
+    fn clear(&mut self) {
+        self.id = None;
+        self.seq = 0;
+    }
+
+    /// Advances the sequence to that of a raw `frame` if it is a dispatch,
+    /// returning the new sequence.
+    fn track_dispatch(&mut self, frame: &Value) -> Option<u64> {
+        let map = frame.as_object()?;
+
+        match map.get("op").map(OpCode::deserialize) {
+            Some(Ok(OpCode::Event)) => {},
+            _ => return None,
+        }
+
+        self.seq = map.get("s").and_then(Value::as_u64)?;
+
+        Some(self.seq)
+    }
+
+    fn reconnection_type(&self) -> ReconnectType {
+        if self.id.is_some() {
+            ReconnectType::Resume
+        } else {
+            ReconnectType::Reidentify
+        }
+    }
+
+    /// Handles an Invalid Session, returning how to reconnect.
+    ///
+    /// A non-resumable session is discarded, so that neither its Id nor its
+    /// sequence are sent once the shard identified anew.
+    fn invalidated(&mut self, resumable: bool) -> ReconnectType {
+        if !resumable {
+            self.clear();
+        }
+
+        self.reconnection_type()
+    }
 
-    Ok(create_rustls_client(url).await?)
+    /// Handles the connection closing with the given close code, returning
+    /// how to reconnect.
+    fn closed(&mut self, code: Option<u16>) -> ReconnectType {
+        match code {
+            Some(close_codes::INVALID_SEQUENCE)
+            | Some(4006)
+            | Some(close_codes::SESSION_TIMEOUT) => self.clear(),
+            Some(close_codes::AUTHENTICATION_FAILED) => return ReconnectType::Reidentify,
+            _ => {},
+        }
+
+        self.reconnection_type()
+    }
 }
 
-#[cfg(feature = "native_tls_backend_marker")]
-async fn connect(base_url: &str) -> Result<WsStream> {
-    let url = build_gateway_url(base_url)?;
+#[cfg(all(feature = "rustls_backend_marker", not(feature = "native_tls_backend_marker")))]
+async fn connect(
+    base_url: &str,
+    max_payload_size: usize,
+    api_version: ApiVersion,
+) -> Result<WsStream> {
+    let url = build_gateway_url(base_url, api_version)?;
+
+    Ok(create_rustls_client(url, max_payload_size).await?)
+}
 
-    Ok(create_native_tls_client(url).await?)
+#[cfg(feature = "native_tls_backend_marker")]
+async fn connect(
+    base_url: &str,
+    max_payload_size: usize,
+    api_version: ApiVersion,
+) -> Result<WsStream> {
+    let url = build_gateway_url(base_url, api_version)?;
+
+    Ok(create_native_tls_client(url, max_payload_size).await?)
 }
 
-fn build_gateway_url(base: &str) -> Result<Url> {
-    Url::parse(&format!("{}?v={}", base, constants::GATEWAY_VERSION)).map_err(|why| {
+fn build_gateway_url(base: &str, api_version: ApiVersion) -> Result<Url> {
+    Url::parse(&format!("{}?v={}", base, api_version.num())).map_err(|why| {
         warn!("Error building gateway URL with base `{}`: {:?}", base, why);
 
         Error::Gateway(GatewayError::BuildingUrl)
     })
 }
+
+#[cfg(test)]
+mod test {
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::{build_gateway_url, Session};
+    use crate::constants::{close_codes, ApiVersion};
+    use crate::gateway::ReconnectType;
+    use crate::model::event::GatewayEvent;
+
+    fn ready_session() -> Session {
+        let mut session = Session::default();
+        session.start("session".to_string());
+        session.seq = 42;
+
+        session
+    }
+
+    #[test]
+    fn test_invalid_session_reidentifies() {
+        let mut session = ready_session();
+        assert_eq!(session.reconnection_type(), ReconnectType::Resume);
+
+        assert_eq!(session.invalidated(false), ReconnectType::Reidentify);
+        assert_eq!(session.id, None);
+        assert_eq!(session.seq, 0);
+        assert_eq!(session.reconnection_type(), ReconnectType::Reidentify);
+    }
+
+    #[test]
+    fn test_resumable_invalid_session() {
+        let mut session = ready_session();
+        assert_eq!(session.invalidated(true), ReconnectType::Resume);
+        assert_eq!(session.id.as_deref(), Some("session"));
+        assert_eq!(session.seq, 42);
+
+        // There is nothing to resume without a session.
+        let mut session = Session::default();
+        assert_eq!(session.invalidated(true), ReconnectType::Reidentify);
+    }
+
+    #[test]
+    fn test_invalid_sequence_close_reidentifies() {
+        let mut session = ready_session();
+        assert_eq!(session.closed(Some(close_codes::INVALID_SEQUENCE)), ReconnectType::Reidentify);
+        assert_eq!(session.id, None);
+        assert_eq!(session.seq, 0);
+    }
+
+    #[test]
+    fn test_other_closes() {
+        let mut session = ready_session();
+        assert_eq!(session.closed(None), ReconnectType::Resume);
+        assert_eq!(session.closed(Some(1000)), ReconnectType::Resume);
+        assert_eq!(session.seq, 42);
+
+        assert_eq!(
+            session.closed(Some(close_codes::AUTHENTICATION_FAILED)),
+            ReconnectType::Reidentify
+        );
+        assert_eq!(session.closed(Some(close_codes::SESSION_TIMEOUT)), ReconnectType::Reidentify);
+        assert_eq!(session.id, None);
+    }
+
+    #[test]
+    fn test_undeserializable_dispatch_advances_seq() {
+        let mut session = ready_session();
+        let frame = json!({
+            "op": 0,
+            "s": 43,
+            "t": "MESSAGE_CREATE",
+            "d": {"id": "not a message"},
+        });
+        assert!(GatewayEvent::deserialize(frame.clone()).is_err());
+
+        assert_eq!(session.track_dispatch(&frame), Some(43));
+        assert_eq!(session.seq, 43);
+    }
+
+    #[test]
+    fn test_non_dispatch_keeps_seq() {
+        let mut session = ready_session();
+
+        assert_eq!(session.track_dispatch(&json!({"op": 11})), None);
+        assert_eq!(session.track_dispatch(&json!({"op": 1, "d": 50})), None);
+        assert_eq!(session.track_dispatch(&json!({"op": 0, "s": null, "t": "READY"})), None);
+        assert_eq!(session.track_dispatch(&json!("garbage")), None);
+        assert_eq!(session.seq, 42);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_gateway_url_version() {
+        let url = build_gateway_url("wss://gateway.discord.gg", ApiVersion::default()).unwrap();
+        assert_eq!(url.as_str(), "wss://gateway.discord.gg/?v=9");
+
+        let url = build_gateway_url("ws://127.0.0.1:9000", ApiVersion::V10).unwrap();
+        assert_eq!(url.as_str(), "ws://127.0.0.1:9000/?v=10");
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(clippy::missing_errors_doc)]
 use std::{
+    borrow::Cow,
     collections::BTreeMap,
     fmt,
     future::Future,
@@ -7,32 +8,40 @@ use std::{
     str::FromStr,
     sync::Arc,
     task::{Context as FutContext, Poll},
+    time::Duration,
 };
 
-use bytes::buf::Buf;
 use futures::future::BoxFuture;
-use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use reqwest::{
     header::{HeaderMap as Headers, HeaderValue, AUTHORIZATION, CONTENT_TYPE, USER_AGENT},
     StatusCode,
     Url,
 };
-use reqwest::{multipart::Part, Client, ClientBuilder, Response as ReqwestResponse};
+use reqwest::{
+    Client,
+    ClientBuilder,
+    RequestBuilder as ReqwestRequestBuilder,
+    Response as ReqwestResponse,
+};
 use serde::de::DeserializeOwned;
 use serde_json::json;
-use tokio::{fs::File, io::AsyncReadExt};
-use tracing::{debug, instrument, trace};
+use tracing::{debug, instrument, trace, warn};
 
 use super::{
+    multipart::Multipart,
     ratelimiting::{RatelimitedRequest, Ratelimiter},
     request::Request,
     routing::RouteInfo,
     typing::Typing,
+    utils::{truncate_snippet, versioned_url},
     AttachmentType,
+    AuditLogFilter,
+    BanFilter,
     GuildPagination,
     HttpError,
+    MemberFilter,
 };
-use crate::constants;
+use crate::constants::{self, ApiVersion};
 use crate::http::routing::Route;
 use crate::internal::prelude::*;
 #[cfg(feature = "unstable_discord_api")]
@@ -69,6 +78,10 @@ pub struct HttpBuilder<'a> {
     ratelimiter_disabled: Option<bool>,
     token: Option<String>,
     proxy: Option<Url>,
+    default_timeout: Option<Duration>,
+    upload_timeout: Option<Duration>,
+    body_snippet_limit: Option<usize>,
+    api_version: ApiVersion,
     fut: Option<BoxFuture<'a, Result<Http>>>,
     #[cfg(feature = "unstable_discord_api")]
     application_id: Option<u64>,
@@ -82,6 +95,10 @@ impl<'a> HttpBuilder<'a> {
             ratelimiter_disabled: Some(false),
             token: None,
             proxy: None,
+            default_timeout: None,
+            upload_timeout: None,
+            body_snippet_limit: Some(DEFAULT_BODY_SNIPPET_LIMIT),
+            api_version: ApiVersion::default(),
             fut: None,
             #[cfg(feature = "unstable_discord_api")]
             application_id: None,
@@ -167,12 +184,84 @@ impl<'a> HttpBuilder<'a> {
 
         Ok(self)
     }
+
+    /// Sets the timeout of requests. By default, requests do not time out.
+    ///
+    /// Requests uploading files use [`Self::upload_timeout`] instead, which
+    /// defaults to [`UPLOAD_TIMEOUT_MULTIPLIER`] times this timeout. A timeout
+    /// set on a [`Request`] itself takes precedence over both.
+    ///
+    /// A request that times out fails with [`HttpError::RequestTimeout`].
+    ///
+    /// [`Request`]: super::request::Request
+    pub fn default_timeout(mut self, timeout: Duration) -> Self {
+        self.default_timeout = Some(timeout);
+
+        self
+    }
+
+    /// Sets the timeout of requests uploading files, such as
+    /// [`Http::send_files`].
+    ///
+    /// Refer to [`Self::default_timeout`] for more information.
+    pub fn upload_timeout(mut self, timeout: Duration) -> Self {
+        self.upload_timeout = Some(timeout);
+
+        self
+    }
+
+    /// Sets how many characters of a response body are kept in an
+    /// [`HttpError::DeserializationFailed`], and logged alongside it. Defaults
+    /// to [`DEFAULT_BODY_SNIPPET_LIMIT`].
+    ///
+    /// Pass `None` to omit the body entirely, e.g. if responses may contain
+    /// data that must not end up in logs or bug reports.
+    pub fn body_snippet_limit(mut self, limit: Option<usize>) -> Self {
+        self.body_snippet_limit = limit;
+
+        self
+    }
+
+    /// Sets the version of the API requests are sent to. Defaults to the
+    /// version the library was built against.
+    ///
+    /// When the [`Client`] is built with this [`Http`] client, its shards
+    /// connect to the gateway with the same version.
+    ///
+    /// [`Client`]: crate::Client
+    pub fn api_version(mut self, version: ApiVersion) -> Self {
+        self.api_version = version;
+
+        self
+    }
 }
 
+/// Prefixes the token with "Bot ", unless it already is a bot or bearer
+/// token.
+fn prefix_token(token: &str) -> String {
+    let trimmed = token.trim();
+
+    if trimmed.starts_with("Bot ") || trimmed.starts_with("Bearer ") {
+        token.to_string()
+    } else {
+        format!("Bot {}", token)
+    }
+}
+
+/// The factor by which the timeout of requests uploading files exceeds
+/// [`HttpBuilder::default_timeout`], unless [`HttpBuilder::upload_timeout`] is
+/// set.
+pub const UPLOAD_TIMEOUT_MULTIPLIER: u32 = 10;
+
+/// The number of characters of a response body kept in an
+/// [`HttpError::DeserializationFailed`], unless
+/// [`HttpBuilder::body_snippet_limit`] is set.
+pub const DEFAULT_BODY_SNIPPET_LIMIT: usize = 500;
+
 impl<'a> Future for HttpBuilder<'a> {
     type Output = Result<Http>;
 
-    #[allow(clippy::unwrap_used)]
+    #[allow(clippy::unwrap_used, deprecated)]
     #[instrument(skip(self))]
     fn poll(mut self: Pin<&mut Self>, ctx: &mut FutContext<'_>) -> Poll<Self::Output> {
         if self.fut.is_none() {
@@ -188,13 +277,23 @@ impl<'a> Future for HttpBuilder<'a> {
                 Arc::new(builder.build().expect("Cannot build reqwest::Client"))
             });
 
-            let ratelimiter = self.ratelimiter.take().unwrap_or_else(|| {
-                let client = Arc::clone(&client);
-                Ratelimiter::new(client, token.to_string())
-            });
+            let ratelimiter = match self.ratelimiter.take() {
+                Some(ratelimiter) => {
+                    ratelimiter.set_token(token.clone());
+                    ratelimiter
+                },
+                None => Ratelimiter::new(Arc::clone(&client), token.clone()),
+            };
 
             let ratelimiter_disabled = self.ratelimiter_disabled.take().unwrap();
             let proxy = self.proxy.take();
+            let default_timeout = self.default_timeout.take();
+            let upload_timeout = self
+                .upload_timeout
+                .take()
+                .or_else(|| default_timeout.map(|timeout| timeout * UPLOAD_TIMEOUT_MULTIPLIER));
+            let body_snippet_limit = self.body_snippet_limit;
+            let api_version = self.api_version;
 
             self.fut = Some(Box::pin(async move {
                 Ok(Http {
@@ -202,6 +301,10 @@ impl<'a> Future for HttpBuilder<'a> {
                     ratelimiter,
                     ratelimiter_disabled,
                     proxy,
+                    default_timeout,
+                    upload_timeout,
+                    body_snippet_limit,
+                    api_version,
                     token,
                     #[cfg(feature = "unstable_discord_api")]
                     application_id,
@@ -223,6 +326,20 @@ pub struct Http {
     pub ratelimiter: Ratelimiter,
     pub ratelimiter_disabled: bool,
     pub proxy: Option<Url>,
+    /// The timeout of requests that do not set their own.
+    pub default_timeout: Option<Duration>,
+    /// The timeout of requests uploading files.
+    pub upload_timeout: Option<Duration>,
+    /// How many characters of a response body are kept when it cannot be
+    /// deserialized, or `None` to omit it.
+    pub body_snippet_limit: Option<usize>,
+    /// The version of the API requests are sent to.
+    pub api_version: ApiVersion,
+    /// The token the client was created with.
+    ///
+    /// **Note**: This is not updated by [`Self::set_token`]. The token used
+    /// for requests is returned by [`Http::token`](fn@Self::token).
+    #[deprecated(note = "use `Http::token` instead, which reflects `Http::set_token`")]
     pub token: String,
     #[cfg(feature = "unstable_discord_api")]
     pub application_id: u64,
@@ -235,11 +352,16 @@ impl fmt::Debug for Http {
             .field("ratelimiter", &self.ratelimiter)
             .field("ratelimiter_disabled", &self.ratelimiter_disabled)
             .field("proxy", &self.proxy)
+            .field("default_timeout", &self.default_timeout)
+            .field("upload_timeout", &self.upload_timeout)
+            .field("body_snippet_limit", &self.body_snippet_limit)
+            .field("api_version", &self.api_version)
             .finish()
     }
 }
 
 impl Http {
+    #[allow(deprecated)]
     pub fn new(client: Arc<Client>, token: &str) -> Self {
         let client2 = Arc::clone(&client);
 
@@ -248,6 +370,10 @@ impl Http {
             ratelimiter: Ratelimiter::new(client2, token.to_string()),
             ratelimiter_disabled: false,
             proxy: None,
+            default_timeout: None,
+            upload_timeout: None,
+            body_snippet_limit: Some(DEFAULT_BODY_SNIPPET_LIMIT),
+            api_version: ApiVersion::default(),
             token: token.to_string(),
             #[cfg(feature = "unstable_discord_api")]
             application_id: 0,
@@ -270,16 +396,57 @@ impl Http {
         let builder = configure_client_backend(Client::builder());
         let built = builder.build().expect("Cannot build reqwest::Client");
 
-        let trimmed = token.trim();
-        let token = if trimmed.starts_with("Bot ") || trimmed.starts_with("Bearer ") {
+        Self::new(Arc::new(built), &prefix_token(token))
+    }
+
+    /// Creates a client performing requests on behalf of a user, with an
+    /// OAuth2 access `token` such as one returned by
+    /// [`oauth2::exchange_code`]. The `token` will automatically be prefixed
+    /// "Bearer " if not already.
+    ///
+    /// Only the endpoints for the scopes the user authorized can be used, such
+    /// as [`Self::get_current_user`] with the `identify` scope or
+    /// [`Self::get_user_connections`] with the `connections` scope, or
+    /// [`Self::edit_current_user_application_role_connection`] with the
+    /// `role_connections.write` scope.
+    ///
+    /// [`oauth2::exchange_code`]: super::oauth2::exchange_code
+    pub fn new_with_bearer(token: &str) -> Self {
+        let token = token.trim();
+        let token = if token.starts_with("Bearer ") {
             token.to_string()
         } else {
-            format!("Bot {}", token)
+            format!("Bearer {}", token)
         };
 
+        let builder = configure_client_backend(Client::builder());
+        let built = builder.build().expect("Cannot build reqwest::Client");
+
         Self::new(Arc::new(built), &token)
     }
 
+    /// Returns the token used for requests, including its `"Bot "` or
+    /// `"Bearer "` prefix.
+    ///
+    /// The token is stored by the [`Self::ratelimiter`].
+    pub fn token(&self) -> String {
+        self.ratelimiter.token()
+    }
+
+    /// Replaces the token used for requests, e.g. after it was regenerated.
+    /// The `token` will automatically be prefixed "Bot " if not already.
+    ///
+    /// Requests that are already being sent keep the token they were built
+    /// with, all later requests use the new one.
+    ///
+    /// To also reconnect the shards with the new token, refer to
+    /// [`ShardManager::update_token`].
+    ///
+    /// [`ShardManager::update_token`]: crate::client::bridge::gateway::ShardManager::update_token
+    pub fn set_token(&self, token: &str) {
+        self.ratelimiter.set_token(prefix_token(token));
+    }
+
     #[cfg(feature = "unstable_discord_api")]
     pub fn new_with_token_application_id(token: &str, application_id: u64) -> Self {
         let mut base = Self::new_with_token(token);
@@ -304,6 +471,7 @@ impl Http {
             .request(Request {
                 body: Some(&body),
                 headers: None,
+                timeout: None,
                 route: RouteInfo::AddGuildMember {
                     guild_id,
                     user_id,
@@ -314,7 +482,7 @@ impl Http {
         if response.status() == 204 {
             Ok(None)
         } else {
-            Ok(Some(response.json().await?))
+            self.deserialize_response(Route::GuildsIdMembersId(guild_id), response).await.map(Some)
         }
     }
 
@@ -328,6 +496,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::AddMemberRole {
                 guild_id,
                 role_id,
@@ -353,16 +522,21 @@ impl Http {
         delete_message_days: u8,
         reason: &str,
     ) -> Result<()> {
-        self.wind(204, Request {
-            body: None,
-            headers: None,
-            route: RouteInfo::GuildBanUser {
-                delete_message_days: Some(delete_message_days),
-                reason: Some(&utf8_percent_encode(reason, NON_ALPHANUMERIC).to_string()),
-                guild_id,
-                user_id,
-            },
-        })
+        self.wind(
+            204,
+            Request {
+                body: None,
+                headers: None,
+                timeout: None,
+                route: RouteInfo::GuildBanUser {
+                    delete_message_days: Some(delete_message_days),
+                    reason: None,
+                    guild_id,
+                    user_id,
+                },
+            }
+            .reason(Some(reason)),
+        )
         .await
     }
 
@@ -377,6 +551,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::BroadcastTyping {
                 channel_id,
             },
@@ -398,6 +573,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateChannel {
                 guild_id,
             },
@@ -410,6 +586,7 @@ impl Http {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateStageInstance,
         })
         .await
@@ -428,6 +605,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreatePublicThread {
                 channel_id,
                 message_id,
@@ -447,6 +625,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreatePrivateThread {
                 channel_id,
             },
@@ -454,6 +633,23 @@ impl Http {
         .await
     }
 
+    /// Creates a post in the forum or media [`GuildChannel`] given its Id,
+    /// which is a thread together with its starter message.
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn create_forum_post(&self, channel_id: u64, map: &JsonMap) -> Result<GuildChannel> {
+        let body = serde_json::to_vec(map)?;
+
+        self.fire(Request {
+            body: Some(&body),
+            headers: None,
+            timeout: None,
+            route: RouteInfo::CreateForumPost {
+                channel_id,
+            },
+        })
+        .await
+    }
+
     /// Creates an emoji in the given [`Guild`] with the given data.
     ///
     /// View the source code for [`Guild::create_emoji`] method to see what
@@ -466,6 +662,7 @@ impl Http {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateEmoji {
                 guild_id,
             },
@@ -475,18 +672,40 @@ impl Http {
 
     /// Create a follow-up message for an Interaction.
     ///
+    /// Uses the application Id the client was created with. Prefer
+    /// [`Self::create_followup_message_for`] with the Id of the interaction.
+    ///
     /// Functions the same as [`Self::execute_webhook`]
     #[cfg(feature = "unstable_discord_api")]
+    #[deprecated(note = "use `create_followup_message_for` instead")]
     pub async fn create_followup_message(
         &self,
         interaction_token: &str,
         map: &Value,
+    ) -> Result<Message> {
+        self.create_followup_message_for(self.application_id, interaction_token, map).await
+    }
+
+    /// Create a follow-up message for an Interaction.
+    ///
+    /// The interaction belongs to the application with the given Id, which
+    /// should be taken from the interaction itself rather than from the
+    /// application Id the client was created with.
+    ///
+    /// Functions the same as [`Self::execute_webhook`]
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn create_followup_message_for(
+        &self,
+        application_id: u64,
+        interaction_token: &str,
+        map: &Value,
     ) -> Result<Message> {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateFollowupMessage {
-                application_id: self.application_id,
+                application_id,
                 interaction_token,
             },
         })
@@ -512,6 +731,7 @@ impl Http {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateGlobalApplicationCommand {
                 application_id: self.application_id,
             },
@@ -528,6 +748,7 @@ impl Http {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateGlobalApplicationCommands {
                 application_id: self.application_id,
             },
@@ -545,6 +766,7 @@ impl Http {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateGuildApplicationCommands {
                 application_id: self.application_id,
                 guild_id,
@@ -592,6 +814,7 @@ impl Http {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateGuild,
         })
         .await
@@ -613,6 +836,7 @@ impl Http {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateGuildApplicationCommand {
                 application_id: self.application_id,
                 guild_id,
@@ -638,6 +862,7 @@ impl Http {
         self.wind(204, Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateGuildIntegration {
                 guild_id,
                 integration_id,
@@ -661,6 +886,7 @@ impl Http {
         self.wind(204, Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateInteractionResponse {
                 interaction_id,
                 interaction_token,
@@ -669,6 +895,47 @@ impl Http {
         .await
     }
 
+    /// Creates a response to an [`Interaction`] from the gateway, uploading
+    /// files along with it.
+    ///
+    /// If there are no `files`, this is the same as
+    /// [`Self::create_interaction_response`]. Otherwise, the `attachments`
+    /// array referencing the files is added to the `data` of the response,
+    /// after any existing attachments it already lists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an
+    /// [`HttpError::UnsuccessfulRequest(ErrorResponse)`][`HttpError::UnsuccessfulRequest`]
+    /// if the files are too large to send.
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn create_interaction_response_with_files(
+        &self,
+        interaction_id: u64,
+        interaction_token: &str,
+        files: Vec<AttachmentType<'_>>,
+        map: JsonMap,
+    ) -> Result<()> {
+        if files.is_empty() {
+            let map = Value::Object(map);
+
+            return self.create_interaction_response(interaction_id, interaction_token, &map).await;
+        }
+
+        let url = self.upload_url(Route::interaction_response(interaction_id, interaction_token))?;
+        let multipart =
+            Multipart::new(&self.client, files, map).await?.attachments_in("data").build_form()?;
+
+        let request = self.client.post(url).multipart(multipart);
+        let response = self.with_upload_timeout(request).send().await?;
+
+        if !response.status().is_success() {
+            return Err(HttpError::from_response(response).await.into());
+        }
+
+        Ok(())
+    }
+
     /// Creates a [`RichInvite`] for the given [channel][`GuildChannel`].
     ///
     /// Refer to Discord's [docs] for field information.
@@ -685,6 +952,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateInvite {
                 channel_id,
             },
@@ -704,6 +972,7 @@ impl Http {
         self.wind(204, Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreatePermission {
                 channel_id,
                 target_id,
@@ -719,6 +988,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreatePrivateChannel,
         })
         .await
@@ -734,6 +1004,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateReaction {
                 // Escape emojis like '#️⃣' that contain a hash
                 reaction: &reaction_type.as_data().replace('#', "%23"),
@@ -744,6 +1015,25 @@ impl Http {
         .await
     }
 
+    /// Creates a scheduled event in a guild.
+    pub async fn create_scheduled_event(
+        &self,
+        guild_id: u64,
+        map: &JsonMap,
+    ) -> Result<ScheduledEvent> {
+        let body = serde_json::to_vec(map)?;
+
+        self.fire(Request {
+            body: Some(&body),
+            headers: None,
+            timeout: None,
+            route: RouteInfo::CreateScheduledEvent {
+                guild_id,
+            },
+        })
+        .await
+    }
+
     /// Creates a role.
     pub async fn create_role(&self, guild_id: u64, map: &JsonMap) -> Result<Role> {
         let body = serde_json::to_vec(map)?;
@@ -751,6 +1041,7 @@ impl Http {
             .request(Request {
                 body: Some(&body),
                 headers: None,
+                timeout: None,
                 route: RouteInfo::CreateRole {
                     guild_id,
                 },
@@ -766,6 +1057,34 @@ impl Http {
         serde_json::from_value(value).map_err(From::from)
     }
 
+    /// Creates a test entitlement to a SKU for the given owner.
+    ///
+    /// Test entitlements do not have a `starts_at` or `ends_at` date, and are
+    /// granted until deleted via [`Self::delete_test_entitlement`].
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn create_test_entitlement(
+        &self,
+        sku_id: u64,
+        owner: EntitlementOwner,
+    ) -> Result<Entitlement> {
+        let (owner_id, owner_type) = owner.id_and_type();
+        let map = json!({
+            "sku_id": sku_id.to_string(),
+            "owner_id": owner_id.to_string(),
+            "owner_type": owner_type,
+        });
+
+        self.fire(Request {
+            body: Some(map.to_string().as_bytes()),
+            headers: None,
+            timeout: None,
+            route: RouteInfo::CreateTestEntitlement {
+                application_id: self.application_id,
+            },
+        })
+        .await
+    }
+
     /// Creates a webhook for the given [channel][`GuildChannel`]'s Id, passing in
     /// the given data.
     ///
@@ -801,6 +1120,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateWebhook {
                 channel_id,
             },
@@ -813,6 +1133,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteChannel {
                 channel_id,
             },
@@ -825,6 +1146,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteStageInstance {
                 channel_id,
             },
@@ -832,31 +1154,81 @@ impl Http {
         .await
     }
 
-    /// Deletes an emoji from a server.
-    pub async fn delete_emoji(&self, guild_id: u64, emoji_id: u64) -> Result<()> {
+    /// Deletes a test entitlement created via [`Self::create_test_entitlement`].
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn delete_test_entitlement(&self, entitlement_id: u64) -> Result<()> {
         self.wind(204, Request {
             body: None,
             headers: None,
-            route: RouteInfo::DeleteEmoji {
-                guild_id,
-                emoji_id,
+            timeout: None,
+            route: RouteInfo::DeleteTestEntitlement {
+                application_id: self.application_id,
+                entitlement_id,
             },
         })
         .await
     }
 
+    /// Deletes an emoji from a server.
+    pub async fn delete_emoji(&self, guild_id: u64, emoji_id: u64) -> Result<()> {
+        self.delete_emoji_with_reason(guild_id, emoji_id, "").await
+    }
+
+    /// Deletes an emoji from a server with a provided reason.
+    pub async fn delete_emoji_with_reason(
+        &self,
+        guild_id: u64,
+        emoji_id: u64,
+        reason: &str,
+    ) -> Result<()> {
+        self.wind(
+            204,
+            Request {
+                body: None,
+                headers: None,
+                timeout: None,
+                route: RouteInfo::DeleteEmoji {
+                    guild_id,
+                    emoji_id,
+                },
+            }
+            .reason(Some(reason)),
+        )
+        .await
+    }
+
     /// Deletes a follow-up message for an interaction.
+    ///
+    /// Uses the application Id the client was created with. Prefer
+    /// [`Self::delete_followup_message_for`] with the Id of the interaction.
     #[cfg(feature = "unstable_discord_api")]
+    #[deprecated(note = "use `delete_followup_message_for` instead")]
     pub async fn delete_followup_message(
         &self,
         interaction_token: &str,
         message_id: u64,
+    ) -> Result<()> {
+        self.delete_followup_message_for(self.application_id, interaction_token, message_id).await
+    }
+
+    /// Deletes a follow-up message for an interaction.
+    ///
+    /// The interaction belongs to the application with the given Id, which
+    /// should be taken from the interaction itself rather than from the
+    /// application Id the client was created with.
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn delete_followup_message_for(
+        &self,
+        application_id: u64,
+        interaction_token: &str,
+        message_id: u64,
     ) -> Result<()> {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteFollowupMessage {
-                application_id: self.application_id,
+                application_id,
                 interaction_token,
                 message_id,
             },
@@ -870,6 +1242,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteGlobalApplicationCommand {
                 application_id: self.application_id,
                 command_id,
@@ -883,6 +1256,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteGuild {
                 guild_id,
             },
@@ -900,6 +1274,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteGuildApplicationCommand {
                 application_id: self.application_id,
                 guild_id,
@@ -914,6 +1289,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteGuildIntegration {
                 guild_id,
                 integration_id,
@@ -927,6 +1303,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteInvite {
                 code,
             },
@@ -940,6 +1317,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteMessage {
                 channel_id,
                 message_id,
@@ -953,6 +1331,7 @@ impl Http {
         self.wind(204, Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteMessages {
                 channel_id,
             },
@@ -982,6 +1361,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteMessageReactions {
                 channel_id,
                 message_id,
@@ -1000,6 +1380,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteMessageReactionEmoji {
                 reaction: &reaction_type.as_data(),
                 channel_id,
@@ -1010,16 +1391,35 @@ impl Http {
     }
 
     /// Deletes the initial interaction response.
+    ///
+    /// Uses the application Id the client was created with. Prefer
+    /// [`Self::delete_original_interaction_response_for`] with the Id of the interaction.
     #[cfg(feature = "unstable_discord_api")]
+    #[deprecated(note = "use `delete_original_interaction_response_for` instead")]
     pub async fn delete_original_interaction_response(
         &self,
         interaction_token: &str,
+    ) -> Result<()> {
+        self.delete_original_interaction_response_for(self.application_id, interaction_token).await
+    }
+
+    /// Deletes the initial interaction response.
+    ///
+    /// The interaction belongs to the application with the given Id, which
+    /// should be taken from the interaction itself rather than from the
+    /// application Id the client was created with.
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn delete_original_interaction_response_for(
+        &self,
+        application_id: u64,
+        interaction_token: &str,
     ) -> Result<()> {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteOriginalInteractionResponse {
-                application_id: self.application_id,
+                application_id,
                 interaction_token,
             },
         })
@@ -1031,6 +1431,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeletePermission {
                 channel_id,
                 target_id,
@@ -1053,6 +1454,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteReaction {
                 // Escape emojis like '#️⃣' that contain a hash
                 reaction: &reaction_type.as_data().replace('#', "%23"),
@@ -1069,6 +1471,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteRole {
                 guild_id,
                 role_id,
@@ -1077,6 +1480,62 @@ impl Http {
         .await
     }
 
+    /// Deletes a scheduled event from a server.
+    pub async fn delete_scheduled_event(&self, guild_id: u64, event_id: u64) -> Result<()> {
+        self.delete_scheduled_event_with_reason(guild_id, event_id, "").await
+    }
+
+    /// Deletes a scheduled event from a server with a provided reason.
+    pub async fn delete_scheduled_event_with_reason(
+        &self,
+        guild_id: u64,
+        event_id: u64,
+        reason: &str,
+    ) -> Result<()> {
+        self.wind(
+            204,
+            Request {
+                body: None,
+                headers: None,
+                timeout: None,
+                route: RouteInfo::DeleteScheduledEvent {
+                    guild_id,
+                    event_id,
+                },
+            }
+            .reason(Some(reason)),
+        )
+        .await
+    }
+
+    /// Deletes a sticker from a server.
+    pub async fn delete_sticker(&self, guild_id: u64, sticker_id: u64) -> Result<()> {
+        self.delete_sticker_with_reason(guild_id, sticker_id, "").await
+    }
+
+    /// Deletes a sticker from a server with a provided reason.
+    pub async fn delete_sticker_with_reason(
+        &self,
+        guild_id: u64,
+        sticker_id: u64,
+        reason: &str,
+    ) -> Result<()> {
+        self.wind(
+            204,
+            Request {
+                body: None,
+                headers: None,
+                timeout: None,
+                route: RouteInfo::DeleteSticker {
+                    guild_id,
+                    sticker_id,
+                },
+            }
+            .reason(Some(reason)),
+        )
+        .await
+    }
+
     /// Deletes a [`Webhook`] given its Id.
     ///
     /// This method requires authentication, whereas [`Self::delete_webhook_with_token`]
@@ -1099,13 +1558,25 @@ impl Http {
     /// # }
     /// ```
     pub async fn delete_webhook(&self, webhook_id: u64) -> Result<()> {
-        self.wind(204, Request {
-            body: None,
-            headers: None,
-            route: RouteInfo::DeleteWebhook {
-                webhook_id,
-            },
-        })
+        self.delete_webhook_with_reason(webhook_id, "").await
+    }
+
+    /// Deletes a [`Webhook`] given its Id with a provided reason.
+    ///
+    /// Refer to [`Self::delete_webhook`] for more information.
+    pub async fn delete_webhook_with_reason(&self, webhook_id: u64, reason: &str) -> Result<()> {
+        self.wind(
+            204,
+            Request {
+                body: None,
+                headers: None,
+                timeout: None,
+                route: RouteInfo::DeleteWebhook {
+                    webhook_id,
+                },
+            }
+            .reason(Some(reason)),
+        )
         .await
     }
 
@@ -1133,6 +1604,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteWebhookWithToken {
                 token,
                 webhook_id,
@@ -1148,6 +1620,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditChannel {
                 channel_id,
             },
@@ -1160,6 +1633,7 @@ impl Http {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditStageInstance {
                 channel_id,
             },
@@ -1174,6 +1648,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditEmoji {
                 guild_id,
                 emoji_id,
@@ -1184,21 +1659,47 @@ impl Http {
 
     /// Edits a follow-up message for an interaction.
     ///
+    /// Uses the application Id the client was created with. Prefer
+    /// [`Self::edit_followup_message_for`] with the Id of the interaction.
+    ///
     /// Refer to Discord's [docs] for Edit Webhook Message for field information.
     ///
     /// [docs]: https://discord.com/developers/docs/resources/webhook#edit-webhook-message
     #[cfg(feature = "unstable_discord_api")]
+    #[deprecated(note = "use `edit_followup_message_for` instead")]
     pub async fn edit_followup_message(
         &self,
         interaction_token: &str,
         message_id: u64,
         map: &Value,
+    ) -> Result<Message> {
+        self.edit_followup_message_for(self.application_id, interaction_token, message_id, map)
+            .await
+    }
+
+    /// Edits a follow-up message for an interaction.
+    ///
+    /// The interaction belongs to the application with the given Id, which
+    /// should be taken from the interaction itself rather than from the
+    /// application Id the client was created with.
+    ///
+    /// Refer to Discord's [docs] for Edit Webhook Message for field information.
+    ///
+    /// [docs]: https://discord.com/developers/docs/resources/webhook#edit-webhook-message
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn edit_followup_message_for(
+        &self,
+        application_id: u64,
+        interaction_token: &str,
+        message_id: u64,
+        map: &Value,
     ) -> Result<Message> {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditFollowupMessage {
-                application_id: self.application_id,
+                application_id,
                 interaction_token,
                 message_id,
             },
@@ -1206,22 +1707,81 @@ impl Http {
         .await
     }
 
+    /// Edits a follow-up message for an interaction, uploading files along
+    /// with it.
+    ///
+    /// The interaction belongs to the application with the given Id, as with
+    /// [`Self::edit_followup_message_for`], which is used instead if there
+    /// are no `files`. The `attachments` array referencing the files is added
+    /// after any existing attachments it already lists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an
+    /// [`HttpError::UnsuccessfulRequest(ErrorResponse)`][`HttpError::UnsuccessfulRequest`]
+    /// if the files are too large to send.
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn edit_followup_message_with_files_for(
+        &self,
+        application_id: u64,
+        interaction_token: &str,
+        message_id: u64,
+        files: Vec<AttachmentType<'_>>,
+        map: JsonMap,
+    ) -> Result<Message> {
+        if files.is_empty() {
+            let map = Value::Object(map);
+
+            return self
+                .edit_followup_message_for(application_id, interaction_token, message_id, &map)
+                .await;
+        }
+
+        let route = Route::webhook_followup_message(application_id, interaction_token, message_id);
+
+        self.edit_message_with_files(route, files, map).await
+    }
+
     /// Get a follow-up message for an interaction.
     ///
+    /// Uses the application Id the client was created with. Prefer
+    /// [`Self::get_followup_message_for`] with the Id of the interaction.
+    ///
     /// Refer to Discord's [docs] for Get Webhook Message for field information.
     ///
     /// [docs]: https://discord.com/developers/docs/resources/webhook#get-webhook-message
     #[cfg(feature = "unstable_discord_api")]
+    #[deprecated(note = "use `get_followup_message_for` instead")]
     pub async fn get_followup_message(
         &self,
         interaction_token: &str,
         message_id: u64,
+    ) -> Result<Message> {
+        self.get_followup_message_for(self.application_id, interaction_token, message_id).await
+    }
+
+    /// Get a follow-up message for an interaction.
+    ///
+    /// The interaction belongs to the application with the given Id, which
+    /// should be taken from the interaction itself rather than from the
+    /// application Id the client was created with.
+    ///
+    /// Refer to Discord's [docs] for Get Webhook Message for field information.
+    ///
+    /// [docs]: https://discord.com/developers/docs/resources/webhook#get-webhook-message
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn get_followup_message_for(
+        &self,
+        application_id: u64,
+        interaction_token: &str,
+        message_id: u64,
     ) -> Result<Message> {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetFollowupMessage {
-                application_id: self.application_id,
+                application_id,
                 interaction_token,
                 message_id,
             },
@@ -1245,6 +1805,7 @@ impl Http {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditGlobalApplicationCommand {
                 application_id: self.application_id,
                 command_id,
@@ -1260,6 +1821,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditGuild {
                 guild_id,
             },
@@ -1284,6 +1846,7 @@ impl Http {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditGuildApplicationCommand {
                 application_id: self.application_id,
                 guild_id,
@@ -1310,6 +1873,7 @@ impl Http {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditGuildApplicationCommandPermission {
                 application_id: self.application_id,
                 guild_id,
@@ -1335,6 +1899,7 @@ impl Http {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditGuildApplicationCommandsPermissions {
                 application_id: self.application_id,
                 guild_id,
@@ -1350,6 +1915,7 @@ impl Http {
         self.wind(204, Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditGuildChannels {
                 guild_id,
             },
@@ -1364,6 +1930,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditGuildWidget {
                 guild_id,
             },
@@ -1382,6 +1949,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditGuildWelcomeScreen {
                 guild_id,
             },
@@ -1397,6 +1965,7 @@ impl Http {
             .request(Request {
                 body: Some(&body),
                 headers: None,
+                timeout: None,
                 route: RouteInfo::EditMember {
                     guild_id,
                     user_id,
@@ -1427,6 +1996,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditMessage {
                 channel_id,
                 message_id,
@@ -1442,6 +2012,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::CrosspostMessage {
                 channel_id,
                 message_id,
@@ -1457,6 +2028,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditMemberMe {
                 guild_id,
             },
@@ -1474,6 +2046,7 @@ impl Http {
         self.wind(200, Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditMemberMe {
                 guild_id,
             },
@@ -1482,16 +2055,35 @@ impl Http {
     }
 
     /// Gets the initial interaction response.
+    ///
+    /// Uses the application Id the client was created with. Prefer
+    /// [`Self::get_original_interaction_response_for`] with the Id of the interaction.
     #[cfg(feature = "unstable_discord_api")]
+    #[deprecated(note = "use `get_original_interaction_response_for` instead")]
     pub async fn get_original_interaction_response(
         &self,
         interaction_token: &str,
+    ) -> Result<Message> {
+        self.get_original_interaction_response_for(self.application_id, interaction_token).await
+    }
+
+    /// Gets the initial interaction response.
+    ///
+    /// The interaction belongs to the application with the given Id, which
+    /// should be taken from the interaction itself rather than from the
+    /// application Id the client was created with.
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn get_original_interaction_response_for(
+        &self,
+        application_id: u64,
+        interaction_token: &str,
     ) -> Result<Message> {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetOriginalInteractionResponse {
-                application_id: self.application_id,
+                application_id,
                 interaction_token,
             },
         })
@@ -1500,26 +2092,114 @@ impl Http {
 
     /// Edits the initial interaction response.
     ///
+    /// Uses the application Id the client was created with. Prefer
+    /// [`Self::edit_original_interaction_response_for`] with the Id of the interaction.
+    ///
     /// Refer to Discord's [docs] for Edit Webhook Message for field information.
     ///
     /// [docs]: https://discord.com/developers/docs/resources/webhook#edit-webhook-message
     #[cfg(feature = "unstable_discord_api")]
+    #[deprecated(note = "use `edit_original_interaction_response_for` instead")]
     pub async fn edit_original_interaction_response(
         &self,
         interaction_token: &str,
         map: &Value,
+    ) -> Result<Message> {
+        self.edit_original_interaction_response_for(self.application_id, interaction_token, map)
+            .await
+    }
+
+    /// Edits the initial interaction response.
+    ///
+    /// The interaction belongs to the application with the given Id, which
+    /// should be taken from the interaction itself rather than from the
+    /// application Id the client was created with.
+    ///
+    /// Refer to Discord's [docs] for Edit Webhook Message for field information.
+    ///
+    /// [docs]: https://discord.com/developers/docs/resources/webhook#edit-webhook-message
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn edit_original_interaction_response_for(
+        &self,
+        application_id: u64,
+        interaction_token: &str,
+        map: &Value,
     ) -> Result<Message> {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditOriginalInteractionResponse {
-                application_id: self.application_id,
+                application_id,
                 interaction_token,
             },
         })
         .await
     }
 
+    /// Edits the initial interaction response, uploading files along with it.
+    ///
+    /// The interaction belongs to the application with the given Id, as with
+    /// [`Self::edit_original_interaction_response_for`], which is used
+    /// instead if there are no `files`. The `attachments` array referencing
+    /// the files is added after any existing attachments it already lists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an
+    /// [`HttpError::UnsuccessfulRequest(ErrorResponse)`][`HttpError::UnsuccessfulRequest`]
+    /// if the files are too large to send.
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn edit_original_interaction_response_with_files_for(
+        &self,
+        application_id: u64,
+        interaction_token: &str,
+        files: Vec<AttachmentType<'_>>,
+        map: JsonMap,
+    ) -> Result<Message> {
+        if files.is_empty() {
+            let map = Value::Object(map);
+
+            return self
+                .edit_original_interaction_response_for(application_id, interaction_token, &map)
+                .await;
+        }
+
+        let route = Route::webhook_original_interaction_response(application_id, interaction_token);
+
+        self.edit_message_with_files(route, files, map).await
+    }
+
+    /// Updates the current user's role connection to an application, which
+    /// the guilds using linked roles of the application check the user's
+    /// roles against.
+    ///
+    /// Refer to [Discord's docs] for field information.
+    ///
+    /// This method only works for bearer tokens with the
+    /// [`RoleConnectionsWrite`] OAuth2 scope, such as a client created with
+    /// [`Self::new_with_bearer`].
+    ///
+    /// [Discord's docs]: https://discord.com/developers/docs/resources/user#update-user-application-role-connection
+    /// [`RoleConnectionsWrite`]: crate::model::oauth2::OAuth2Scope::RoleConnectionsWrite
+    pub async fn edit_current_user_application_role_connection(
+        &self,
+        application_id: u64,
+        map: &JsonMap,
+    ) -> Result<ApplicationRoleConnection> {
+        let body = serde_json::to_vec(map)?;
+
+        self.fire(Request {
+            body: Some(&body),
+            headers: None,
+            timeout: None,
+            route: RouteInfo::EditCurrentUserApplicationRoleConnection {
+                application_id,
+            },
+        })
+        .await
+    }
+
     /// Edits the current user's profile settings.
     pub async fn edit_profile(&self, map: &JsonMap) -> Result<CurrentUser> {
         let body = serde_json::to_vec(map)?;
@@ -1528,11 +2208,12 @@ impl Http {
             .request(Request {
                 body: Some(&body),
                 headers: None,
+                timeout: None,
                 route: RouteInfo::EditProfile,
             })
             .await?;
 
-        Ok(request.json::<CurrentUser>().await?)
+        self.deserialize_response(Route::UsersMe, request).await
     }
 
     /// Changes a role in a guild.
@@ -1542,6 +2223,7 @@ impl Http {
             .request(Request {
                 body: Some(&body),
                 headers: None,
+                timeout: None,
                 route: RouteInfo::EditRole {
                     guild_id,
                     role_id,
@@ -1574,6 +2256,7 @@ impl Http {
             .request(Request {
                 body: Some(&body),
                 headers: None,
+                timeout: None,
                 route: RouteInfo::EditRolePosition {
                     guild_id,
                 },
@@ -1600,6 +2283,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditThread {
                 channel_id,
             },
@@ -1646,6 +2330,7 @@ impl Http {
         self.wind(204, Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditVoiceState {
                 guild_id,
                 user_id,
@@ -1695,8 +2380,35 @@ impl Http {
         self.wind(204, Request {
             body: Some(&body),
             headers: None,
-            route: RouteInfo::EditVoiceStateMe {
-                guild_id,
+            timeout: None,
+            route: RouteInfo::EditVoiceStateMe {
+                guild_id,
+            },
+        })
+        .await
+    }
+
+    /// Sets the status of a voice channel.
+    ///
+    /// The Value is a map with the value of:
+    ///
+    /// - **status**: the new status of the channel, up to 500 characters long,
+    ///   or `null` to clear it.
+    ///
+    /// **Note**: Requires the [Manage Channels] permission, or the
+    /// [Set Voice Channel Status] permission while connected to the channel.
+    ///
+    /// [Manage Channels]: Permissions::MANAGE_CHANNELS
+    /// [Set Voice Channel Status]: Permissions::SET_VOICE_CHANNEL_STATUS
+    pub async fn edit_voice_status(&self, channel_id: u64, map: &JsonMap) -> Result<()> {
+        let body = serde_json::to_vec(map)?;
+
+        self.wind(204, Request {
+            body: Some(&body),
+            headers: None,
+            timeout: None,
+            route: RouteInfo::EditVoiceStatus {
+                channel_id,
             },
         })
         .await
@@ -1740,6 +2452,7 @@ impl Http {
         self.fire(Request {
             body: Some(map.to_string().as_bytes()),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditWebhook {
                 webhook_id,
             },
@@ -1783,6 +2496,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditWebhookWithToken {
                 token,
                 webhook_id,
@@ -1808,6 +2522,10 @@ impl Http {
     /// - `avatar_url`: Override the default avatar of the webhook with a URL.
     /// - `tts`: Whether this is a text-to-speech message (defaults to `false`).
     /// - `username`: Override the default username of the webhook.
+    /// - `thread_name`: Create a forum post with the given name.
+    /// - `applied_tags`: The forum tags to apply to the post, along with `thread_name`.
+    /// - `components`: The message components. The `with_components` query
+    ///   parameter is set automatically when these are present.
     ///
     /// Additionally, _at least one_ of the following must be given:
     ///
@@ -1855,10 +2573,12 @@ impl Http {
             .request(Request {
                 body: Some(&body),
                 headers: Some(headers),
+                timeout: None,
                 route: RouteInfo::ExecuteWebhook {
                     token,
                     wait,
                     webhook_id,
+                    with_components: map.contains_key("components"),
                 },
             })
             .await?;
@@ -1867,7 +2587,7 @@ impl Http {
             return Ok(None);
         }
 
-        response.json::<Message>().await.map(Some).map_err(From::from)
+        self.deserialize_response(Route::WebhooksId(webhook_id), response).await.map(Some)
     }
 
     /// Send file(s) over a webhook.
@@ -1888,70 +2608,17 @@ impl Http {
     where
         T: Into<AttachmentType<'a>>,
     {
-        let mut multipart = reqwest::multipart::Form::new();
-
-        for (file_num, file) in files.into_iter().enumerate() {
-            match file.into() {
-                AttachmentType::Bytes {
-                    data,
-                    filename,
-                } => {
-                    multipart = multipart.part(
-                        file_num.to_string(),
-                        Part::bytes(data.into_owned()).file_name(filename),
-                    );
-                },
-                AttachmentType::File {
-                    file,
-                    filename,
-                } => {
-                    let mut buf = Vec::new();
-                    file.try_clone().await?.read_to_end(&mut buf).await?;
-
-                    multipart =
-                        multipart.part(file_num.to_string(), Part::stream(buf).file_name(filename));
-                },
-                AttachmentType::Path(path) => {
-                    let filename =
-                        path.file_name().map(|filename| filename.to_string_lossy().into_owned());
-                    let mut file = File::open(path).await?;
-                    let mut buf = vec![];
-                    file.read_to_end(&mut buf).await?;
-
-                    let part = match filename {
-                        Some(filename) => Part::bytes(buf).file_name(filename),
-                        None => Part::bytes(buf),
-                    };
-
-                    multipart = multipart.part(file_num.to_string(), part);
-                },
-                AttachmentType::Image(url) => {
-                    let url = Url::parse(url).map_err(|_| Error::Url(url.to_string()))?;
-                    let filename = url
-                        .path_segments()
-                        .and_then(|segments| segments.last().map(ToString::to_string))
-                        .ok_or_else(|| Error::Url(url.to_string()))?;
-                    let response = self.client.get(url).send().await?;
-                    let mut bytes = response.bytes().await?;
-                    let mut picture: Vec<u8> = vec![0; bytes.len()];
-                    bytes.copy_to_slice(&mut picture[..]);
-                    multipart = multipart.part(
-                        file_num.to_string(),
-                        Part::bytes(picture).file_name(filename.to_string()),
-                    );
-                },
-            }
-        }
-
-        multipart = multipart.text("payload_json", serde_json::to_string(&map)?);
-
-        let response = self
-            .client
-            .post(&Route::webhook_with_token_optioned(webhook_id, token, wait))
-            .multipart(multipart)
-            .header(CONTENT_TYPE, HeaderValue::from_static("multipart/form-data"))
-            .send()
-            .await?;
+        let with_components = map.contains_key("components");
+        let url = self.upload_url(Route::webhook_with_token_optioned_components(
+            webhook_id,
+            token,
+            wait,
+            with_components,
+        ))?;
+        let multipart = Multipart::new(&self.client, files, map).await?.build_form()?;
+
+        let request = self.client.post(url).multipart(multipart);
+        let response = self.with_upload_timeout(request).send().await?;
 
         if !response.status().is_success() {
             return Err(HttpError::from_response(response).await.into());
@@ -1970,6 +2637,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetWebhookMessage {
                 token,
                 webhook_id,
@@ -1992,10 +2660,12 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::EditWebhookMessage {
                 token,
                 webhook_id,
                 message_id,
+                with_components: map.contains_key("components"),
             },
         })
         .await
@@ -2011,6 +2681,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::DeleteWebhookMessage {
                 token,
                 webhook_id,
@@ -2028,6 +2699,7 @@ impl Http {
             .request(Request {
                 body: None,
                 headers: None,
+                timeout: None,
                 route: RouteInfo::GetActiveMaintenance,
             })
             .await?;
@@ -2045,6 +2717,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetBans {
                 guild_id,
             },
@@ -2052,7 +2725,42 @@ impl Http {
         .await
     }
 
+    /// Gets the bans in a guild that match the given [`BanFilter`].
+    ///
+    /// ```rust,no_run
+    /// # use serenity::http::{BanFilter, Http};
+    /// # use serenity::model::id::UserId;
+    /// #
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let http = Http::default();
+    /// let filter = BanFilter::new().after(UserId(80351110224678912)).limit(100);
+    /// let bans = http.get_bans_filtered(81384788765712384, filter).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_bans_filtered(
+        &self,
+        guild_id: u64,
+        filter: impl Into<BanFilter>,
+    ) -> Result<Vec<Ban>> {
+        let filter = filter.into();
+
+        self.fire(Request {
+            body: None,
+            headers: None,
+            timeout: None,
+            route: RouteInfo::GetBansOptioned {
+                after: filter.after.map(|id| id.0),
+                before: filter.before.map(|id| id.0),
+                guild_id,
+                limit: filter.limit,
+            },
+        })
+        .await
+    }
+
     /// Gets all audit logs in a specific guild.
+    #[deprecated(note = "use `get_audit_logs_filtered` instead")]
     pub async fn get_audit_logs(
         &self,
         guild_id: u64,
@@ -2061,15 +2769,34 @@ impl Http {
         before: Option<u64>,
         limit: Option<u8>,
     ) -> Result<AuditLogs> {
+        let filter = AuditLogFilter {
+            action_type,
+            before: before.map(AuditLogEntryId),
+            limit,
+            user_id: user_id.map(UserId),
+        };
+
+        self.get_audit_logs_filtered(guild_id, filter).await
+    }
+
+    /// Gets the audit logs in a guild that match the given [`AuditLogFilter`].
+    pub async fn get_audit_logs_filtered(
+        &self,
+        guild_id: u64,
+        filter: impl Into<AuditLogFilter>,
+    ) -> Result<AuditLogs> {
+        let filter = filter.into();
+
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetAuditLogs {
-                action_type,
-                before,
+                action_type: filter.action_type,
+                before: filter.before.map(|id| id.0),
                 guild_id,
-                limit,
-                user_id,
+                limit: filter.limit,
+                user_id: filter.user_id.map(|id| id.0),
             },
         })
         .await
@@ -2080,6 +2807,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetBotGateway,
         })
         .await
@@ -2090,6 +2818,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetChannelInvites {
                 channel_id,
             },
@@ -2102,6 +2831,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetChannelThreadMembers {
                 channel_id,
             },
@@ -2114,6 +2844,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildActiveThreads {
                 guild_id,
             },
@@ -2127,6 +2858,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetChannelActiveThreads {
                 channel_id,
             },
@@ -2144,6 +2876,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetChannelArchivedPublicThreads {
                 channel_id,
                 before,
@@ -2163,6 +2896,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetChannelArchivedPrivateThreads {
                 channel_id,
                 before,
@@ -2182,6 +2916,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetChannelJoinedPrivateArchivedThreads {
                 channel_id,
                 before,
@@ -2196,6 +2931,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::JoinThread {
                 channel_id,
             },
@@ -2208,6 +2944,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::LeaveThread {
                 channel_id,
             },
@@ -2220,6 +2957,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::AddThreadMember {
                 channel_id,
                 user_id,
@@ -2233,6 +2971,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::RemoveThreadMember {
                 channel_id,
                 user_id,
@@ -2264,6 +3003,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetChannelWebhooks {
                 channel_id,
             },
@@ -2276,6 +3016,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetChannel {
                 channel_id,
             },
@@ -2288,6 +3029,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetChannels {
                 guild_id,
             },
@@ -2300,6 +3042,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetStageInstance {
                 channel_id,
             },
@@ -2314,6 +3057,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetCurrentApplicationInfo,
         })
         .await
@@ -2324,6 +3068,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetCurrentUser,
         })
         .await
@@ -2334,6 +3079,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetEmojis {
                 guild_id,
             },
@@ -2346,6 +3092,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetEmoji {
                 guild_id,
                 emoji_id,
@@ -2354,22 +3101,74 @@ impl Http {
         .await
     }
 
+    /// Gets the entitlements of the current application, optionally filtered
+    /// by user, guild or SKU.
+    ///
+    /// Pagination is done via `before` and `after`, which are entitlement Ids.
+    /// The `limit` may be at most 100. If `exclude_ended` is `true`, entitlements
+    /// that have ended are not returned.
+    #[cfg(feature = "unstable_discord_api")]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_entitlements(
+        &self,
+        user_id: Option<u64>,
+        sku_ids: &[u64],
+        before: Option<u64>,
+        after: Option<u64>,
+        limit: Option<u8>,
+        guild_id: Option<u64>,
+        exclude_ended: bool,
+    ) -> Result<Vec<Entitlement>> {
+        self.fire(Request {
+            body: None,
+            headers: None,
+            timeout: None,
+            route: RouteInfo::GetEntitlements {
+                application_id: self.application_id,
+                user_id,
+                sku_ids,
+                before,
+                after,
+                limit,
+                guild_id,
+                exclude_ended,
+            },
+        })
+        .await
+    }
+
     /// Gets current gateway.
     pub async fn get_gateway(&self) -> Result<Gateway> {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGateway,
         })
         .await
     }
 
+    /// Gets all SKUs of the current application.
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn get_skus(&self) -> Result<Vec<Sku>> {
+        self.fire(Request {
+            body: None,
+            headers: None,
+            timeout: None,
+            route: RouteInfo::GetSkus {
+                application_id: self.application_id,
+            },
+        })
+        .await
+    }
+
     /// Fetches all of the global commands for your application.
     #[cfg(feature = "unstable_discord_api")]
     pub async fn get_global_application_commands(&self) -> Result<Vec<ApplicationCommand>> {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGlobalApplicationCommands {
                 application_id: self.application_id,
             },
@@ -2386,6 +3185,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGlobalApplicationCommand {
                 application_id: self.application_id,
                 command_id,
@@ -2399,6 +3199,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuild {
                 guild_id,
             },
@@ -2411,6 +3212,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildWithCounts {
                 guild_id,
             },
@@ -2427,6 +3229,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildApplicationCommands {
                 application_id: self.application_id,
                 guild_id,
@@ -2445,6 +3248,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildApplicationCommand {
                 application_id: self.application_id,
                 guild_id,
@@ -2463,6 +3267,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildApplicationCommandsPermissions {
                 application_id: self.application_id,
                 guild_id,
@@ -2481,6 +3286,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildApplicationCommandPermissions {
                 application_id: self.application_id,
                 guild_id,
@@ -2497,6 +3303,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildWidget {
                 guild_id,
             },
@@ -2509,6 +3316,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildWidget {
                 guild_id,
             },
@@ -2521,6 +3329,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildPreview {
                 guild_id,
             },
@@ -2533,6 +3342,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildWelcomeScreen {
                 guild_id,
             },
@@ -2545,6 +3355,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildIntegrations {
                 guild_id,
             },
@@ -2557,6 +3368,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildInvites {
                 guild_id,
             },
@@ -2574,6 +3386,7 @@ impl Http {
         self.request(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildVanityUrl {
                 guild_id,
             },
@@ -2587,12 +3400,40 @@ impl Http {
 
     /// Gets the members of a guild. Optionally pass a `limit` and the Id of the
     /// user to offset the result by.
+    #[deprecated(note = "use `get_guild_members_filtered` instead")]
     pub async fn get_guild_members(
         &self,
         guild_id: u64,
         limit: Option<u64>,
         after: Option<u64>,
     ) -> Result<Vec<Member>> {
+        let filter = MemberFilter {
+            after: after.map(UserId),
+            limit,
+        };
+
+        self.get_guild_members_filtered(guild_id, filter).await
+    }
+
+    /// Gets the members of a guild that match the given [`MemberFilter`].
+    ///
+    /// Requests 1000 members if the filter sets no limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::NotInRange`] if the limit is not between 1 and
+    /// [`constants::MEMBER_FETCH_LIMIT`].
+    pub async fn get_guild_members_filtered(
+        &self,
+        guild_id: u64,
+        filter: impl Into<MemberFilter>,
+    ) -> Result<Vec<Member>> {
+        let MemberFilter {
+            after,
+            limit,
+        } = filter.into();
+        let after = after.map(|id| id.0);
+
         if let Some(l) = limit {
             if !(1..=constants::MEMBER_FETCH_LIMIT).contains(&l) {
                 return Err(Error::NotInRange("limit", l, 1, constants::MEMBER_FETCH_LIMIT));
@@ -2603,6 +3444,7 @@ impl Http {
             .request(Request {
                 body: None,
                 headers: None,
+                timeout: None,
                 route: RouteInfo::GetGuildMembers {
                     after,
                     guild_id,
@@ -2639,6 +3481,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildPruneCount {
                 days: req.days,
                 guild_id,
@@ -2653,6 +3496,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildRegions {
                 guild_id,
             },
@@ -2660,12 +3504,36 @@ impl Http {
         .await
     }
 
+    /// Retrieves a single role in a [`Guild`].
+    pub async fn get_guild_role(&self, guild_id: u64, role_id: u64) -> Result<Role> {
+        let mut value = self
+            .request(Request {
+                body: None,
+                headers: None,
+                timeout: None,
+                route: RouteInfo::GetGuildRole {
+                    guild_id,
+                    role_id,
+                },
+            })
+            .await?
+            .json::<Value>()
+            .await?;
+
+        if let Some(map) = value.as_object_mut() {
+            map.insert("guild_id".to_string(), Value::Number(Number::from(guild_id)));
+        }
+
+        serde_json::from_value(value).map_err(From::from)
+    }
+
     /// Retrieves a list of roles in a [`Guild`].
     pub async fn get_guild_roles(&self, guild_id: u64) -> Result<Vec<Role>> {
         let mut value = self
             .request(Request {
                 body: None,
                 headers: None,
+                timeout: None,
                 route: RouteInfo::GetGuildRoles {
                     guild_id,
                 },
@@ -2685,6 +3553,25 @@ impl Http {
         serde_json::from_value(value).map_err(From::from)
     }
 
+    /// Gets the scheduled events of a guild, optionally with the number of
+    /// users subscribed to each of them.
+    pub async fn get_scheduled_events(
+        &self,
+        guild_id: u64,
+        with_user_count: bool,
+    ) -> Result<Vec<ScheduledEvent>> {
+        self.fire(Request {
+            body: None,
+            headers: None,
+            timeout: None,
+            route: RouteInfo::GetGuildScheduledEvents {
+                guild_id,
+                with_user_count,
+            },
+        })
+        .await
+    }
+
     /// Retrieves the webhooks for the given [guild][`Guild`]'s Id.
     ///
     /// This method requires authentication.
@@ -2708,6 +3595,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuildWebhooks {
                 guild_id,
             },
@@ -2749,6 +3637,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetGuilds {
                 after,
                 before,
@@ -2768,6 +3657,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetInvite {
                 code,
                 stats,
@@ -2782,6 +3672,7 @@ impl Http {
             .request(Request {
                 body: None,
                 headers: None,
+                timeout: None,
                 route: RouteInfo::GetMember {
                     guild_id,
                     user_id,
@@ -2803,6 +3694,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetMessage {
                 channel_id,
                 message_id,
@@ -2816,6 +3708,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetMessages {
                 query: query.to_owned(),
                 channel_id,
@@ -2829,6 +3722,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetPins {
                 channel_id,
             },
@@ -2850,6 +3744,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetReactionUsers {
                 after,
                 channel_id,
@@ -2869,6 +3764,7 @@ impl Http {
             .request(Request {
                 body: None,
                 headers: None,
+                timeout: None,
                 route: RouteInfo::GetUnresolvedIncidents,
             })
             .await?;
@@ -2889,6 +3785,7 @@ impl Http {
             .request(Request {
                 body: None,
                 headers: None,
+                timeout: None,
                 route: RouteInfo::GetUpcomingMaintenances,
             })
             .await?;
@@ -2906,6 +3803,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetUser {
                 user_id,
             },
@@ -2923,6 +3821,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetUserConnections,
         })
         .await
@@ -2933,6 +3832,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetUserDmChannels,
         })
         .await
@@ -2943,6 +3843,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetVoiceRegions,
         })
         .await
@@ -2971,6 +3872,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetWebhook {
                 webhook_id,
             },
@@ -3002,6 +3904,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetWebhookWithToken {
                 token,
                 webhook_id,
@@ -3035,6 +3938,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::GetWebhookWithToken {
                 token,
                 webhook_id,
@@ -3055,15 +3959,19 @@ impl Http {
         user_id: u64,
         reason: &str,
     ) -> Result<()> {
-        self.wind(204, Request {
-            body: None,
-            headers: None,
-            route: RouteInfo::KickMember {
-                guild_id,
-                user_id,
-                reason: &utf8_percent_encode(reason, NON_ALPHANUMERIC).to_string(),
-            },
-        })
+        self.wind(
+            204,
+            Request {
+                body: None,
+                headers: None,
+                timeout: None,
+                route: RouteInfo::KickMember {
+                    guild_id,
+                    user_id,
+                },
+            }
+            .reason(Some(reason)),
+        )
         .await
     }
 
@@ -3072,6 +3980,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::LeaveGuild {
                 guild_id,
             },
@@ -3095,83 +4004,16 @@ impl Http {
     where
         T: Into<AttachmentType<'a>>,
     {
-        let uri = api!("/channels/{}/messages", channel_id);
-        let mut url = match Url::parse(&uri) {
-            Ok(url) => url,
-            Err(_) => return Err(Error::Url(uri)),
-        };
-
-        if let Some(proxy) = &self.proxy {
-            url.set_host(proxy.host_str()).map_err(HttpError::Url)?;
-            url.set_scheme(proxy.scheme()).map_err(|_| HttpError::InvalidScheme)?;
-            url.set_port(proxy.port()).map_err(|_| HttpError::InvalidPort)?;
-        }
-
-        let mut multipart = reqwest::multipart::Form::new();
-
-        for (file_num, file) in files.into_iter().enumerate() {
-            match file.into() {
-                AttachmentType::Bytes {
-                    data,
-                    filename,
-                } => {
-                    multipart = multipart.part(
-                        file_num.to_string(),
-                        Part::bytes(data.into_owned()).file_name(filename),
-                    );
-                },
-                AttachmentType::File {
-                    file,
-                    filename,
-                } => {
-                    let mut buf = Vec::new();
-                    file.try_clone().await?.read_to_end(&mut buf).await?;
-
-                    multipart =
-                        multipart.part(file_num.to_string(), Part::stream(buf).file_name(filename));
-                },
-                AttachmentType::Path(path) => {
-                    let filename =
-                        path.file_name().map(|filename| filename.to_string_lossy().into_owned());
-                    let mut file = File::open(path).await?;
-                    let mut buf = vec![];
-                    file.read_to_end(&mut buf).await?;
-
-                    let part = match filename {
-                        Some(filename) => Part::bytes(buf).file_name(filename),
-                        None => Part::bytes(buf),
-                    };
-
-                    multipart = multipart.part(file_num.to_string(), part);
-                },
-                AttachmentType::Image(url) => {
-                    let url = Url::parse(url).map_err(|_| Error::Url(url.to_string()))?;
-                    let filename = url
-                        .path_segments()
-                        .and_then(|segments| segments.last().map(ToString::to_string))
-                        .ok_or_else(|| Error::Url(url.to_string()))?;
-                    let response = self.client.get(url).send().await?;
-                    let mut bytes = response.bytes().await?;
-                    let mut picture: Vec<u8> = vec![0; bytes.len()];
-                    bytes.copy_to_slice(&mut picture[..]);
-                    multipart = multipart.part(
-                        file_num.to_string(),
-                        Part::bytes(picture).file_name(filename.to_string()),
-                    );
-                },
-            }
-        }
-
-        multipart = multipart.text("payload_json", serde_json::to_string(&map)?);
+        let url = self.upload_url(api!("/channels/{}/messages", channel_id))?;
+        let multipart = Multipart::new(&self.client, files, map).await?.build_form()?;
 
-        let response = self
+        let request = self
             .client
             .post(url)
-            .header(AUTHORIZATION, HeaderValue::from_str(&self.token)?)
+            .header(AUTHORIZATION, HeaderValue::from_str(&self.token())?)
             .header(USER_AGENT, HeaderValue::from_static(constants::USER_AGENT))
-            .multipart(multipart)
-            .send()
-            .await?;
+            .multipart(multipart);
+        let response = self.with_upload_timeout(request).send().await?;
 
         if !response.status().is_success() {
             return Err(HttpError::from_response(response).await.into());
@@ -3187,6 +4029,7 @@ impl Http {
         self.fire(Request {
             body: Some(&body),
             headers: None,
+            timeout: None,
             route: RouteInfo::CreateMessage {
                 channel_id,
             },
@@ -3199,6 +4042,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::PinMessage {
                 channel_id,
                 message_id,
@@ -3212,6 +4056,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::RemoveBan {
                 guild_id,
                 user_id,
@@ -3235,6 +4080,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::RemoveMemberRole {
                 guild_id,
                 user_id,
@@ -3256,6 +4102,7 @@ impl Http {
             .request(Request {
                 body: None,
                 headers: None,
+                timeout: None,
                 route: RouteInfo::SearchGuildMembers {
                     guild_id,
                     query,
@@ -3290,6 +4137,7 @@ impl Http {
         self.fire(Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::StartGuildPrune {
                 days: req.days,
                 guild_id,
@@ -3299,16 +4147,28 @@ impl Http {
     }
 
     /// Starts syncing an integration with a guild.
-    pub async fn start_integration_sync(&self, guild_id: u64, integration_id: u64) -> Result<()> {
-        self.wind(204, Request {
-            body: None,
-            headers: None,
-            route: RouteInfo::StartIntegrationSync {
-                guild_id,
-                integration_id,
-            },
-        })
-        .await
+    ///
+    /// # Errors
+    ///
+    /// Always returns [`HttpError::EndpointRemoved`] without sending a request,
+    /// as Discord removed the endpoint.
+    ///
+    /// # Examples
+    ///
+    /// Calls to this method are flagged by the `deprecated` lint:
+    ///
+    /// ```rust,compile_fail
+    /// # #![deny(deprecated)]
+    /// # use serenity::http::Http;
+    /// # async fn run(http: &Http) -> serenity::Result<()> {
+    /// http.start_integration_sync(81384788765712384, 1).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[deprecated(note = "Discord removed the integration sync endpoint")]
+    pub async fn start_integration_sync(&self, _guild_id: u64, _integration_id: u64) -> Result<()> {
+        Err(HttpError::EndpointRemoved("/guilds/{guild.id}/integrations/{integration.id}/sync")
+            .into())
     }
 
     /// Starts typing in the specified [`Channel`] for an indefinite period of time.
@@ -3354,6 +4214,7 @@ impl Http {
         self.wind(204, Request {
             body: None,
             headers: None,
+            timeout: None,
             route: RouteInfo::UnpinMessage {
                 channel_id,
                 message_id,
@@ -3410,9 +4271,40 @@ impl Http {
     /// [`Error::Http`]: crate::error::Error::Http
     /// [`Error::Json`]: crate::error::Error::Json
     pub async fn fire<T: DeserializeOwned>(&self, req: Request<'_>) -> Result<T> {
+        let route = req.route.deconstruct().1;
         let response = self.request(req).await?;
 
-        response.json::<T>().await.map_err(From::from)
+        self.deserialize_response(route, response).await
+    }
+
+    /// Deserializes the body of a successful response.
+    ///
+    /// If this fails, the failure is logged with the route and returned as an
+    /// [`HttpError::DeserializationFailed`], which keeps the start of the body
+    /// unless [`Self::body_snippet_limit`] is `None`.
+    async fn deserialize_response<T: DeserializeOwned>(
+        &self,
+        route: Route,
+        response: ReqwestResponse,
+    ) -> Result<T> {
+        let status = response.status();
+        let body = response.bytes().await?;
+
+        serde_json::from_slice(&body).map_err(|error| {
+            let body_snippet = self
+                .body_snippet_limit
+                .map(|limit| truncate_snippet(&String::from_utf8_lossy(&body), limit));
+
+            let error = HttpError::DeserializationFailed {
+                route,
+                status,
+                error,
+                body_snippet,
+            };
+            warn!("{}", error);
+
+            Error::Http(Box::new(error))
+        })
     }
 
     /// Performs a request, ratelimiting it if necessary.
@@ -3451,12 +4343,20 @@ impl Http {
     /// # }
     /// ```
     #[instrument]
-    pub async fn request(&self, req: Request<'_>) -> Result<ReqwestResponse> {
+    pub async fn request(&self, mut req: Request<'_>) -> Result<ReqwestResponse> {
+        if req.timeout.is_none() {
+            req.timeout = self.default_timeout;
+        }
+
         let response = if self.ratelimiter_disabled {
-            let request = req.build(&self.client, &self.token, self.proxy.as_ref())?.build()?;
+            let proxy = self.proxy.as_ref();
+            let request = req
+                .build_with_version(&self.client, &self.token(), proxy, self.api_version)?
+                .build()?;
             self.client.execute(request).await?
         } else {
-            let ratelimiting_req = RatelimitedRequest::from(req);
+            let mut ratelimiting_req = RatelimitedRequest::from(req);
+            ratelimiting_req.api_version = self.api_version;
             self.ratelimiter.perform(ratelimiting_req).await?
         };
 
@@ -3467,6 +4367,54 @@ impl Http {
         }
     }
 
+    /// Edits the message at the webhook `route`, uploading the `files` along
+    /// with it.
+    #[cfg(feature = "unstable_discord_api")]
+    async fn edit_message_with_files(
+        &self,
+        route: String,
+        files: Vec<AttachmentType<'_>>,
+        map: JsonMap,
+    ) -> Result<Message> {
+        let url = self.upload_url(route)?;
+        let multipart = Multipart::new(&self.client, files, map).await?.build_form()?;
+
+        let request = self.client.patch(url).multipart(multipart);
+        let response = self.with_upload_timeout(request).send().await?;
+
+        if !response.status().is_success() {
+            return Err(HttpError::from_response(response).await.into());
+        }
+
+        response.json::<Message>().await.map_err(From::from)
+    }
+
+    /// Points the URL of a request uploading files at the API version and the
+    /// proxy of the client, like other requests are when they are built.
+    fn upload_url(&self, uri: String) -> Result<Url> {
+        let uri = versioned_url(Cow::Owned(uri), self.api_version);
+        let mut url = match Url::parse(&uri) {
+            Ok(url) => url,
+            Err(_) => return Err(Error::Url(uri.into_owned())),
+        };
+
+        if let Some(proxy) = &self.proxy {
+            url.set_host(proxy.host_str()).map_err(HttpError::Url)?;
+            url.set_scheme(proxy.scheme()).map_err(|_| HttpError::InvalidScheme)?;
+            url.set_port(proxy.port()).map_err(|_| HttpError::InvalidPort)?;
+        }
+
+        Ok(url)
+    }
+
+    /// Applies [`Self::upload_timeout`] to a request uploading files.
+    fn with_upload_timeout(&self, builder: ReqwestRequestBuilder) -> ReqwestRequestBuilder {
+        match self.upload_timeout {
+            Some(timeout) => builder.timeout(timeout),
+            None => builder,
+        }
+    }
+
     /// Performs a request and then verifies that the response status code is equal
     /// to the expected value.
     ///
@@ -3503,6 +4451,7 @@ impl AsRef<Http> for Http {
 }
 
 impl Default for Http {
+    #[allow(deprecated)]
     fn default() -> Self {
         let built = Client::builder().build().expect("Cannot build Reqwest::Client.");
         let client = Arc::new(built);
@@ -3513,9 +4462,163 @@ impl Default for Http {
             ratelimiter: Ratelimiter::new(client2, ""),
             ratelimiter_disabled: false,
             proxy: None,
-            token: "".to_string(),
+            default_timeout: None,
+            upload_timeout: None,
+            body_snippet_limit: Some(DEFAULT_BODY_SNIPPET_LIMIT),
+            api_version: ApiVersion::default(),
+            token: String::new(),
             #[cfg(feature = "unstable_discord_api")]
             application_id: 0,
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use http_crate::response::Builder;
+
+    use super::Http;
+    use crate::constants::ApiVersion;
+    use crate::http::request::Request;
+    use crate::http::routing::{Route, RouteInfo};
+    use crate::http::HttpError;
+    use crate::model::user::CurrentUser;
+    use crate::Error;
+
+    fn response(body: &str) -> reqwest::Response {
+        Builder::new().status(200).body(body.to_string().into_bytes()).unwrap().into()
+    }
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    #[test]
+    fn test_futures_are_send() {
+        use serde_json::{json, Map};
+
+        use crate::http::AttachmentType;
+
+        let http = Http::new_with_token("token");
+        let map = json!({"content": "hello"});
+
+        assert_send(&http.get_current_user());
+        assert_send(&http.send_message(1, &map));
+        assert_send(&http.get_messages(1, ""));
+        assert_send(&http.execute_webhook(1, "token", true, &Map::new()));
+        assert_send(&http.send_files(1, vec![AttachmentType::Bytes {
+            data: b"data"[..].into(),
+            filename: "data.txt".to_string(),
+        }], Map::new()));
+        assert_send(&http.request(Request {
+            body: None,
+            headers: None,
+            timeout: None,
+            route: RouteInfo::GetCurrentUser,
+        }));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_set_token() {
+        let http = Http::new_with_token("old");
+        assert_eq!(http.token(), "Bot old");
+
+        http.set_token("new");
+        assert_eq!(http.token(), "Bot new");
+        assert_eq!(http.ratelimiter.token(), "Bot new");
+        assert_eq!(http.token, "Bot old");
+
+        http.set_token("Bearer oauth");
+        assert_eq!(http.token(), "Bearer oauth");
+        assert_eq!(http.ratelimiter.token(), "Bearer oauth");
+    }
+
+    #[test]
+    fn test_new_with_bearer() {
+        let http = Http::new_with_bearer("oauth");
+        assert_eq!(http.token(), "Bearer oauth");
+        assert_eq!(http.ratelimiter.token(), "Bearer oauth");
+
+        assert_eq!(Http::new_with_bearer("Bearer oauth").token(), "Bearer oauth");
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_deserialization_failure() {
+        let mut http = Http::new_with_token("token");
+        http.body_snippet_limit = Some(9);
+
+        let error = http
+            .deserialize_response::<CurrentUser>(Route::UsersMe, response(r#"{"id": "é12345678"}"#))
+            .await
+            .unwrap_err();
+
+        match error {
+            Error::Http(error) => {
+                assert!(error.is_deserialization_failure());
+                assert_eq!(error.status_code(), Some(reqwest::StatusCode::OK));
+
+                match *error {
+                    HttpError::DeserializationFailed {
+                        route,
+                        body_snippet,
+                        ..
+                    } => {
+                        assert_eq!(route, Route::UsersMe);
+                        assert_eq!(body_snippet.as_deref(), Some(r#"{"id": "é…"#));
+                    },
+                    _ => panic!("unexpected error"),
+                }
+            },
+            _ => panic!("unexpected error"),
+        }
+
+        http.body_snippet_limit = None;
+
+        let error = http
+            .deserialize_response::<CurrentUser>(Route::UsersMe, response("not json"))
+            .await
+            .unwrap_err();
+
+        match error {
+            Error::Http(error) => match *error {
+                HttpError::DeserializationFailed {
+                    body_snippet,
+                    ..
+                } => assert_eq!(body_snippet, None),
+                _ => panic!("unexpected error"),
+            },
+            _ => panic!("unexpected error"),
+        }
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_upload_url() {
+        let mut http = Http::new_with_token("token");
+        let uri = api!("/channels/{}/messages", 1);
+
+        let url = http.upload_url(uri.clone()).unwrap();
+        assert_eq!(url.as_str(), "https://discord.com/api/v9/channels/1/messages");
+
+        http.api_version = ApiVersion::V10;
+        http.proxy = Some("http://127.0.0.1:3000".parse().unwrap());
+        let url = http.upload_url(uri).unwrap();
+        assert_eq!(url.as_str(), "http://127.0.0.1:3000/api/v10/channels/1/messages");
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn test_removed_endpoint() {
+        let http = Http::new_with_token("token");
+
+        let error = http.start_integration_sync(81384788765712384, 1).await.unwrap_err();
+
+        match error {
+            Error::Http(error) => {
+                assert!(error.is_endpoint_removed());
+                assert!(error.status_code().is_none());
+            },
+            _ => panic!("unexpected error"),
+        }
+    }
+}
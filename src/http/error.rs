@@ -5,8 +5,10 @@ use std::{
 
 use reqwest::{header::InvalidHeaderValue, Error as ReqwestError, Response, StatusCode, Url};
 use serde::de::{Deserialize, Deserializer, Error as DeError};
+use serde_json::Error as JsonError;
 use url::ParseError as UrlError;
 
+use crate::http::routing::Route;
 use crate::http::utils::deserialize_errors;
 use crate::internal::prelude::{JsonMap, StdResult};
 
@@ -109,10 +111,38 @@ pub enum Error {
     InvalidHeader(InvalidHeaderValue),
     /// Reqwest's Error contain information on why sending a request failed.
     Request(ReqwestError),
+    /// When a request did not complete within its timeout.
+    ///
+    /// Refer to [`HttpBuilder::default_timeout`] for how timeouts are set.
+    ///
+    /// [`HttpBuilder::default_timeout`]: super::HttpBuilder::default_timeout
+    RequestTimeout(ReqwestError),
     /// When using a proxy with an invalid scheme.
     InvalidScheme,
     /// When using a proxy with an invalid port.
     InvalidPort,
+    /// When a successful response could not be deserialized into the expected
+    /// type, e.g. because Discord changed the shape of the response.
+    ///
+    /// Refer to [`HttpBuilder::body_snippet_limit`] for how much of the body is
+    /// kept.
+    ///
+    /// [`HttpBuilder::body_snippet_limit`]: super::HttpBuilder::body_snippet_limit
+    DeserializationFailed {
+        /// The route the request was sent to.
+        route: Route,
+        /// The status code of the response.
+        status: StatusCode,
+        /// The error returned by the deserializer.
+        error: JsonError,
+        /// The start of the response body, or `None` if it was omitted.
+        body_snippet: Option<String>,
+    },
+    /// When calling a method whose endpoint was removed by Discord. Contains
+    /// the path of the removed endpoint.
+    ///
+    /// The request is not sent.
+    EndpointRemoved(&'static str),
 }
 
 impl Error {
@@ -137,10 +167,31 @@ impl Error {
         matches!(self, Self::InvalidHeader(_))
     }
 
-    /// Returns the status code if the error is an unsuccessful request
+    /// Returns true when the error is caused by a request timing out
+    pub fn is_request_timeout(&self) -> bool {
+        matches!(self, Self::RequestTimeout(_))
+    }
+
+    /// Returns true when the error is caused by a response body which could
+    /// not be deserialized
+    pub fn is_deserialization_failure(&self) -> bool {
+        matches!(self, Self::DeserializationFailed { .. })
+    }
+
+    /// Returns true when the error is caused by calling a method whose endpoint
+    /// was removed by Discord
+    pub fn is_endpoint_removed(&self) -> bool {
+        matches!(self, Self::EndpointRemoved(_))
+    }
+
+    /// Returns the status code if the error is an unsuccessful request, or a
+    /// response which could not be deserialized
     pub fn status_code(&self) -> Option<StatusCode> {
         match self {
             Self::UnsuccessfulRequest(res) => Some(res.status_code),
+            Self::DeserializationFailed {
+                status, ..
+            } => Some(*status),
             _ => None,
         }
     }
@@ -154,7 +205,11 @@ impl From<ErrorResponse> for Error {
 
 impl From<ReqwestError> for Error {
     fn from(error: ReqwestError) -> Error {
-        Error::Request(error)
+        if error.is_timeout() {
+            Error::RequestTimeout(error)
+        } else {
+            Error::Request(error)
+        }
     }
 }
 
@@ -200,8 +255,26 @@ impl Display for Error {
             Error::InvalidWebhook => f.write_str("Provided URL is not a valid webhook."),
             Error::InvalidHeader(_) => f.write_str("Provided value is an invalid header value."),
             Error::Request(_) => f.write_str("Error while sending HTTP request."),
+            Error::RequestTimeout(_) => f.write_str("HTTP request timed out."),
             Error::InvalidScheme => f.write_str("Invalid Url scheme."),
             Error::InvalidPort => f.write_str("Invalid port."),
+            Error::DeserializationFailed {
+                route,
+                status,
+                error,
+                body_snippet,
+            } => {
+                write!(f, "Error deserializing the {} response of {:?}: {}", status, route, error)?;
+
+                if let Some(body_snippet) = body_snippet {
+                    write!(f, "; body: {}", body_snippet)?;
+                }
+
+                Ok(())
+            },
+            Error::EndpointRemoved(path) => {
+                write!(f, "The `{}` endpoint was removed by Discord.", path)
+            },
         }
     }
 }
@@ -210,7 +283,10 @@ impl StdError for Error {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match self {
             Error::Url(inner) => Some(inner),
-            Error::Request(inner) => Some(inner),
+            Error::Request(inner) | Error::RequestTimeout(inner) => Some(inner),
+            Error::DeserializationFailed {
+                error, ..
+            } => Some(error),
             _ => None,
         }
     }
@@ -249,4 +325,27 @@ mod test {
 
         assert_eq!(error_response, known);
     }
+
+    #[tokio::test]
+    async fn test_timeout_is_distinct() {
+        // A server which accepts the connection, but never responds.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (_socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        });
+
+        let error = reqwest::Client::new()
+            .get(format!("http://{}/", addr))
+            .timeout(std::time::Duration::from_millis(50))
+            .send()
+            .await
+            .unwrap_err();
+        server.abort();
+
+        let error = Error::from(error);
+        assert!(error.is_request_timeout());
+        assert!(matches!(error, Error::RequestTimeout(_)));
+    }
 }
@@ -0,0 +1,222 @@
+//! Query parameters for the endpoints that list the entries of a guild.
+//!
+//! Each filter starts out empty, in which case Discord's defaults are used,
+//! and is narrowed down with chained setters:
+//!
+//! ```rust
+//! use serenity::http::AuditLogFilter;
+//! use serenity::model::id::UserId;
+//!
+//! let filter = AuditLogFilter::new().user_id(UserId(7)).limit(10);
+//!
+//! assert_eq!(filter.to_query(), "limit=10&user_id=7");
+//! ```
+
+use std::fmt::Display;
+
+use crate::model::id::{AuditLogEntryId, UserId};
+
+/// Appends `key=value` to a query string, separating it from the previous
+/// parameter.
+fn push_param(query: &mut String, key: &str, value: Option<impl Display>) {
+    if let Some(value) = value {
+        if !query.is_empty() {
+            query.push('&');
+        }
+
+        query.push_str(key);
+        query.push('=');
+        query.push_str(&value.to_string());
+    }
+}
+
+/// Filters the entries returned by [`Http::get_audit_logs_filtered`].
+///
+/// [`Http::get_audit_logs_filtered`]: super::Http::get_audit_logs_filtered
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AuditLogFilter {
+    pub(super) action_type: Option<u8>,
+    pub(super) before: Option<AuditLogEntryId>,
+    pub(super) limit: Option<u8>,
+    pub(super) user_id: Option<UserId>,
+}
+
+impl AuditLogFilter {
+    /// Creates a filter that does not filter out any entries.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only returns entries of the given [`Action`] number.
+    ///
+    /// [`Action`]: crate::model::guild::audit_log::Action
+    pub fn action_type(mut self, action_type: u8) -> Self {
+        self.action_type = Some(action_type);
+        self
+    }
+
+    /// Only returns entries older than the given entry.
+    pub fn before(mut self, before: impl Into<AuditLogEntryId>) -> Self {
+        self.before = Some(before.into());
+        self
+    }
+
+    /// Returns at most `limit` entries, between 1 and 100. Defaults to 50.
+    pub fn limit(mut self, limit: u8) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Only returns entries of actions made by the given user.
+    pub fn user_id(mut self, user_id: impl Into<UserId>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Serializes the filter into a query string, without a leading `?`.
+    ///
+    /// Parameters that were not set are left out.
+    pub fn to_query(&self) -> String {
+        let mut query = String::new();
+
+        push_param(&mut query, "action_type", self.action_type);
+        push_param(&mut query, "before", self.before.map(|id| id.0));
+        push_param(&mut query, "limit", self.limit);
+        push_param(&mut query, "user_id", self.user_id.map(|id| id.0));
+
+        query
+    }
+}
+
+/// Filters the bans returned by [`Http::get_bans_filtered`].
+///
+/// Bans are ordered by the Id of the banned user.
+///
+/// [`Http::get_bans_filtered`]: super::Http::get_bans_filtered
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BanFilter {
+    pub(super) after: Option<UserId>,
+    pub(super) before: Option<UserId>,
+    pub(super) limit: Option<u16>,
+}
+
+impl BanFilter {
+    /// Creates a filter that does not filter out any bans.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only returns the bans of users with a greater Id than the given user.
+    pub fn after(mut self, after: impl Into<UserId>) -> Self {
+        self.after = Some(after.into());
+        self
+    }
+
+    /// Only returns the bans of users with a smaller Id than the given user.
+    pub fn before(mut self, before: impl Into<UserId>) -> Self {
+        self.before = Some(before.into());
+        self
+    }
+
+    /// Returns at most `limit` bans, between 1 and 1000. Defaults to 1000.
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Serializes the filter into a query string, without a leading `?`.
+    ///
+    /// Parameters that were not set are left out.
+    pub fn to_query(&self) -> String {
+        let mut query = String::new();
+
+        push_param(&mut query, "after", self.after.map(|id| id.0));
+        push_param(&mut query, "before", self.before.map(|id| id.0));
+        push_param(&mut query, "limit", self.limit);
+
+        query
+    }
+}
+
+/// Filters the members returned by [`Http::get_guild_members_filtered`].
+///
+/// Members are ordered by their user Id.
+///
+/// [`Http::get_guild_members_filtered`]: super::Http::get_guild_members_filtered
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MemberFilter {
+    pub(super) after: Option<UserId>,
+    pub(super) limit: Option<u64>,
+}
+
+impl MemberFilter {
+    /// Creates a filter that does not filter out any members.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only returns members with a greater user Id than the given user.
+    pub fn after(mut self, after: impl Into<UserId>) -> Self {
+        self.after = Some(after.into());
+        self
+    }
+
+    /// Returns at most `limit` members, between 1 and 1000.
+    ///
+    /// **Note**: Discord defaults to 1, but [`Http::get_guild_members_filtered`]
+    /// requests 1000 members if no limit was set.
+    ///
+    /// [`Http::get_guild_members_filtered`]: super::Http::get_guild_members_filtered
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Serializes the filter into a query string, without a leading `?`.
+    ///
+    /// Parameters that were not set are left out.
+    pub fn to_query(&self) -> String {
+        let mut query = String::new();
+
+        push_param(&mut query, "after", self.after.map(|id| id.0));
+        push_param(&mut query, "limit", self.limit);
+
+        query
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AuditLogFilter, BanFilter, MemberFilter};
+    use crate::model::id::{AuditLogEntryId, UserId};
+
+    #[test]
+    fn test_audit_log_filter() {
+        assert_eq!(AuditLogFilter::new().to_query(), "");
+        assert_eq!(AuditLogFilter::new().limit(10).to_query(), "limit=10");
+
+        let filter = AuditLogFilter::new()
+            .user_id(UserId(7))
+            .limit(10)
+            .before(AuditLogEntryId(3))
+            .action_type(22);
+        assert_eq!(filter.to_query(), "action_type=22&before=3&limit=10&user_id=7");
+    }
+
+    #[test]
+    fn test_ban_filter() {
+        assert_eq!(BanFilter::new().to_query(), "");
+        assert_eq!(BanFilter::new().before(UserId(9)).to_query(), "before=9");
+        assert_eq!(BanFilter::new().limit(1000).after(UserId(5)).to_query(), "after=5&limit=1000");
+    }
+
+    #[test]
+    fn test_member_filter() {
+        assert_eq!(MemberFilter::new().to_query(), "");
+        assert_eq!(MemberFilter::new().limit(1).to_query(), "limit=1");
+        assert_eq!(
+            MemberFilter::new().after(UserId(5)).limit(1000).to_query(),
+            "after=5&limit=1000"
+        );
+    }
+}
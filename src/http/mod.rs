@@ -25,6 +25,10 @@
 
 pub mod client;
 pub mod error;
+mod filter;
+mod multipart;
+#[cfg(feature = "oauth2")]
+pub mod oauth2;
 pub mod ratelimiting;
 pub mod request;
 pub mod routing;
@@ -43,6 +47,7 @@ use tokio::fs::File;
 
 pub use self::client::*;
 pub use self::error::Error as HttpError;
+pub use self::filter::{AuditLogFilter, BanFilter, MemberFilter};
 use self::request::Request;
 pub use self::typing::*;
 #[cfg(feature = "cache")]
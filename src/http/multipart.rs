@@ -0,0 +1,303 @@
+use bytes::buf::Buf;
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, Url};
+use tokio::{fs::File, io::AsyncReadExt};
+
+use super::AttachmentType;
+use crate::internal::prelude::*;
+
+/// A file loaded into memory, to be sent as a part of a [`Multipart`] form.
+#[derive(Clone, Debug)]
+pub(crate) struct MultipartFile {
+    pub data: Vec<u8>,
+    pub filename: Option<String>,
+}
+
+impl MultipartFile {
+    /// Loads the data of an attachment, downloading it first if it is an
+    /// [`AttachmentType::Image`].
+    pub(crate) async fn from_attachment(
+        client: &Client,
+        attachment: AttachmentType<'_>,
+    ) -> Result<Self> {
+        Ok(match attachment {
+            AttachmentType::Bytes {
+                data,
+                filename,
+            } => Self {
+                data: data.into_owned(),
+                filename: Some(filename),
+            },
+            AttachmentType::File {
+                file,
+                filename,
+            } => {
+                let mut data = Vec::new();
+                file.try_clone().await?.read_to_end(&mut data).await?;
+
+                Self {
+                    data,
+                    filename: Some(filename),
+                }
+            },
+            AttachmentType::Path(path) => {
+                let filename =
+                    path.file_name().map(|filename| filename.to_string_lossy().into_owned());
+                let mut data = Vec::new();
+                File::open(path).await?.read_to_end(&mut data).await?;
+
+                Self {
+                    data,
+                    filename,
+                }
+            },
+            AttachmentType::Image(url) => {
+                let url = Url::parse(url).map_err(|_| Error::Url(url.to_string()))?;
+                let filename = url
+                    .path_segments()
+                    .and_then(|mut segments| segments.next_back().map(ToString::to_string))
+                    .ok_or_else(|| Error::Url(url.to_string()))?;
+                let mut bytes = client.get(url).send().await?.bytes().await?;
+                let mut data = vec![0; bytes.len()];
+                bytes.copy_to_slice(&mut data[..]);
+
+                Self {
+                    data,
+                    filename: Some(filename),
+                }
+            },
+        })
+    }
+}
+
+/// The contents of a `multipart/form-data` request uploading files.
+///
+/// Every file is sent as a part named `files[n]`, where `n` is its index. The
+/// JSON payload is sent as the `payload_json` part, with an `attachments`
+/// array referencing each file by its index and filename.
+#[derive(Clone, Debug)]
+pub(crate) struct Multipart {
+    pub files: Vec<MultipartFile>,
+    pub payload_json: JsonMap,
+    /// The key of the object within the payload holding the `attachments`
+    /// array, such as the `data` of an interaction response, if it is not
+    /// the payload itself.
+    pub attachments_key: Option<&'static str>,
+}
+
+impl Multipart {
+    /// Loads all attachments, keeping their order.
+    pub(crate) async fn new<'a, T, It>(
+        client: &Client,
+        files: It,
+        payload_json: JsonMap,
+    ) -> Result<Self>
+    where
+        T: Into<AttachmentType<'a>>,
+        It: IntoIterator<Item = T>,
+    {
+        let mut loaded = Vec::new();
+
+        for file in files {
+            loaded.push(MultipartFile::from_attachment(client, file.into()).await?);
+        }
+
+        Ok(Self {
+            files: loaded,
+            payload_json,
+            attachments_key: None,
+        })
+    }
+
+    /// Places the `attachments` array within the object at `key` of the
+    /// payload, instead of the payload itself.
+    #[cfg(feature = "unstable_discord_api")]
+    pub(crate) fn attachments_in(mut self, key: &'static str) -> Self {
+        self.attachments_key = Some(key);
+        self
+    }
+
+    /// Splits the form into its named file parts and the serialized
+    /// `payload_json` part.
+    pub(crate) fn into_parts(self) -> Result<(Vec<(String, MultipartFile)>, String)> {
+        let mut payload_json = self.payload_json;
+
+        let target = match self.attachments_key {
+            Some(key) => match payload_json
+                .entry(key)
+                .or_insert_with(|| Value::Object(JsonMap::new()))
+                .as_object_mut()
+            {
+                Some(object) => object,
+                None => return Err(Error::Other("Multipart attachments key is not an object")),
+            },
+            None => &mut payload_json,
+        };
+
+        let mut attachments = match target.remove("attachments") {
+            Some(Value::Array(attachments)) => attachments,
+            _ => Vec::new(),
+        };
+
+        for (index, file) in self.files.iter().enumerate() {
+            let mut attachment = JsonMap::new();
+            attachment.insert("id".to_string(), Value::from(index));
+
+            if let Some(filename) = &file.filename {
+                attachment.insert("filename".to_string(), Value::from(filename.clone()));
+            }
+
+            attachments.push(Value::Object(attachment));
+        }
+
+        if !attachments.is_empty() {
+            target.insert("attachments".to_string(), Value::Array(attachments));
+        }
+
+        let parts = self
+            .files
+            .into_iter()
+            .enumerate()
+            .map(|(index, file)| (format!("files[{}]", index), file))
+            .collect();
+
+        Ok((parts, serde_json::to_string(&payload_json)?))
+    }
+
+    /// Builds the form to send.
+    pub(crate) fn build_form(self) -> Result<Form> {
+        let (parts, payload_json) = self.into_parts()?;
+
+        let mut form = Form::new();
+
+        for (name, file) in parts {
+            let part = match file.filename {
+                Some(filename) => Part::bytes(file.data).file_name(filename),
+                None => Part::bytes(file.data),
+            };
+
+            form = form.part(name, part);
+        }
+
+        Ok(form.text("payload_json", payload_json))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{Multipart, MultipartFile};
+    use crate::internal::prelude::*;
+
+    fn file(data: &[u8], filename: Option<&str>) -> MultipartFile {
+        MultipartFile {
+            data: data.to_vec(),
+            filename: filename.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_indexed_part_names() {
+        let mut payload_json = JsonMap::new();
+        payload_json.insert("content".to_string(), Value::from("hello"));
+
+        let multipart = Multipart {
+            files: vec![
+                file(b"first", Some("a.png")),
+                file(b"second", Some("b.txt")),
+                file(b"third", None),
+            ],
+            payload_json,
+            attachments_key: None,
+        };
+
+        let (parts, payload_json) = multipart.into_parts().unwrap();
+
+        let names = parts.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["files[0]", "files[1]", "files[2]"]);
+        assert_eq!(parts[0].1.data, b"first");
+        assert_eq!(parts[1].1.data, b"second");
+        assert_eq!(parts[1].1.filename.as_deref(), Some("b.txt"));
+        assert_eq!(parts[2].1.data, b"third");
+
+        let payload_json: Value = serde_json::from_str(&payload_json).unwrap();
+        assert_eq!(
+            payload_json,
+            json!({
+                "content": "hello",
+                "attachments": [
+                    {"id": 0, "filename": "a.png"},
+                    {"id": 1, "filename": "b.txt"},
+                    {"id": 2},
+                ],
+            })
+        );
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_existing_attachments_are_kept() {
+        let mut payload_json = JsonMap::new();
+        payload_json.insert("attachments".to_string(), json!([{"id": "123456"}]));
+
+        let multipart = Multipart {
+            files: vec![file(b"data", Some("a.png"))],
+            payload_json,
+            attachments_key: None,
+        };
+
+        let (parts, payload_json) = multipart.into_parts().unwrap();
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].0, "files[0]");
+
+        let payload_json: Value = serde_json::from_str(&payload_json).unwrap();
+        assert_eq!(
+            payload_json,
+            json!({"attachments": [{"id": "123456"}, {"id": 0, "filename": "a.png"}]})
+        );
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_no_files() {
+        let multipart = Multipart {
+            files: Vec::new(),
+            payload_json: JsonMap::new(),
+            attachments_key: None,
+        };
+
+        let (parts, payload_json) = multipart.into_parts().unwrap();
+        assert!(parts.is_empty());
+        assert_eq!(payload_json, "{}");
+    }
+
+    #[test]
+    #[cfg(feature = "unstable_discord_api")]
+    #[allow(clippy::unwrap_used)]
+    fn test_attachments_in_data() {
+        let mut payload_json = JsonMap::new();
+        payload_json.insert("type".to_string(), Value::from(7));
+        payload_json.insert("data".to_string(), json!({"attachments": [{"id": "123456"}]}));
+
+        let multipart = Multipart {
+            files: vec![file(b"data", Some("a.png"))],
+            payload_json,
+            attachments_key: None,
+        }
+        .attachments_in("data");
+
+        let (parts, payload_json) = multipart.into_parts().unwrap();
+        assert_eq!(parts[0].0, "files[0]");
+
+        let payload_json: Value = serde_json::from_str(&payload_json).unwrap();
+        assert_eq!(
+            payload_json,
+            json!({
+                "type": 7,
+                "data": {"attachments": [{"id": "123456"}, {"id": 0, "filename": "a.png"}]},
+            })
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! Requests to Discord's OAuth2 token endpoints, for applications which
+//! authorize users, such as web dashboards or linked roles.
+//!
+//! An authorization code received on the redirect URI is exchanged for an
+//! access token with [`exchange_code`]. The access token can then be used to
+//! perform requests on behalf of the user with [`Http::new_with_bearer`], and
+//! be renewed with [`refresh_token`] before it expires.
+//!
+//! These requests are authenticated with the application's client ID and
+//! secret instead of the token of the [`Http`] client, and are not part of
+//! the ratelimit buckets of the API routes, so they bypass the
+//! [`Ratelimiter`]. If Discord ratelimits one anyway, it is retried once after
+//! the delay Discord asks for.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use serenity::http::Http;
+//! #
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! # let http = Http::default();
+//! use serenity::http::oauth2;
+//!
+//! let token = oauth2::exchange_code(
+//!     &http,
+//!     381880193700069377,
+//!     "client secret",
+//!     "code from the redirect",
+//!     "https://example.com/callback",
+//! )
+//! .await?;
+//!
+//! let user_http = Http::new_with_bearer(&token.access_token);
+//! let user = user_http.get_current_user().await?;
+//!
+//! println!("Authorized by {}", user.name);
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! [`Ratelimiter`]: super::ratelimiting::Ratelimiter
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use reqwest::{
+    header::{HeaderValue, USER_AGENT},
+    Request as ReqwestRequest,
+    Response as ReqwestResponse,
+    StatusCode,
+    Url,
+};
+use serde::de::DeserializeOwned;
+#[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
+use tokio::time::delay_for as sleep;
+#[cfg(feature = "tokio")]
+use tokio::time::sleep;
+use tracing::debug;
+
+use super::utils::versioned_url;
+use super::{Http, HttpError};
+use crate::constants;
+use crate::internal::prelude::*;
+
+const TOKEN_URL: &str = api!("/oauth2/token");
+const REVOKE_URL: &str = api!("/oauth2/token/revoke");
+
+/// The access token granted to an application, as returned by
+/// [`exchange_code`] and [`refresh_token`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct AccessTokenResponse {
+    /// The token to perform requests on behalf of the user with, such as
+    /// through [`Http::new_with_bearer`].
+    pub access_token: String,
+    /// The type of the token, which is `Bearer`.
+    pub token_type: String,
+    /// The number of seconds until the access token expires.
+    pub expires_in: u64,
+    /// The token to get a new access token with through [`refresh_token`].
+    pub refresh_token: Option<String>,
+    /// The space-separated scopes the user authorized.
+    pub scope: String,
+}
+
+/// Exchanges the authorization `code` Discord passed to the `redirect_uri`
+/// for an access token.
+///
+/// The `redirect_uri` must be the one the user was redirected to.
+///
+/// # Errors
+///
+/// Returns [`Error::Http`] if the code is invalid or expired, or if the
+/// client credentials are wrong.
+pub async fn exchange_code(
+    http: impl AsRef<Http>,
+    client_id: u64,
+    client_secret: &str,
+    code: &str,
+    redirect_uri: &str,
+) -> Result<AccessTokenResponse> {
+    let http = http.as_ref();
+    let request = token_request(http, TOKEN_URL, client_id, client_secret, &[
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("redirect_uri", redirect_uri),
+    ])?;
+
+    deserialize(perform(http, request).await?).await
+}
+
+/// Gets a new access token with the `refresh_token` of an earlier one.
+///
+/// # Errors
+///
+/// Returns [`Error::Http`] if the refresh token is invalid or was revoked, or
+/// if the client credentials are wrong.
+pub async fn refresh_token(
+    http: impl AsRef<Http>,
+    client_id: u64,
+    client_secret: &str,
+    refresh_token: &str,
+) -> Result<AccessTokenResponse> {
+    let http = http.as_ref();
+    let request = token_request(http, TOKEN_URL, client_id, client_secret, &[
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token),
+    ])?;
+
+    deserialize(perform(http, request).await?).await
+}
+
+/// Revokes an access or refresh `token`, such as when the user logs out.
+///
+/// Revoking either token of a grant revokes both of them.
+///
+/// # Errors
+///
+/// Returns [`Error::Http`] if the client credentials are wrong.
+pub async fn revoke_token(
+    http: impl AsRef<Http>,
+    client_id: u64,
+    client_secret: &str,
+    token: &str,
+) -> Result<()> {
+    let http = http.as_ref();
+    let request = token_request(http, REVOKE_URL, client_id, client_secret, &[("token", token)])?;
+
+    perform(http, request).await.map(|_| ())
+}
+
+/// Builds a form-encoded request to one of the token endpoints, authenticated
+/// with the client credentials.
+fn token_request(
+    http: &Http,
+    url: &str,
+    client_id: u64,
+    client_secret: &str,
+    form: &[(&str, &str)],
+) -> Result<ReqwestRequest> {
+    let mut url = versioned_url(Cow::Borrowed(url), http.api_version);
+
+    if let Some(proxy) = &http.proxy {
+        url = Cow::Owned(url.replace("https://discord.com/", proxy.as_str()));
+    }
+
+    let mut builder = http
+        .client
+        .post(Url::parse(&url).map_err(HttpError::Url)?)
+        .header(USER_AGENT, HeaderValue::from_static(constants::USER_AGENT))
+        .basic_auth(client_id, Some(client_secret))
+        .form(form);
+
+    if let Some(timeout) = http.default_timeout {
+        builder = builder.timeout(timeout);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Performs a request to one of the token endpoints, retrying it once if it
+/// was ratelimited.
+async fn perform(http: &Http, request: ReqwestRequest) -> Result<ReqwestResponse> {
+    let retry = request.try_clone();
+    let mut response = http.client.execute(request).await?;
+
+    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        if let (Some(retry), Some(retry_after)) = (retry, retry_after(&response)) {
+            debug!("Ratelimited on the OAuth2 token endpoint for {:?}s", retry_after);
+            sleep(Duration::from_secs_f64(retry_after)).await;
+
+            response = http.client.execute(retry).await?;
+        }
+    }
+
+    if response.status().is_success() {
+        Ok(response)
+    } else {
+        Err(Error::Http(Box::new(HttpError::from_response(response).await)))
+    }
+}
+
+fn retry_after(response: &ReqwestResponse) -> Option<f64> {
+    response.headers().get("retry-after")?.to_str().ok()?.parse().ok()
+}
+
+async fn deserialize<T: DeserializeOwned>(response: ReqwestResponse) -> Result<T> {
+    let body = response.bytes().await?;
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+#[cfg(test)]
+mod test {
+    use reqwest::header::AUTHORIZATION;
+    use serde_json::json;
+
+    use super::{token_request, AccessTokenResponse, REVOKE_URL, TOKEN_URL};
+    use crate::http::Http;
+
+    fn body(request: &reqwest::Request) -> &str {
+        std::str::from_utf8(request.body().and_then(|body| body.as_bytes()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_exchange_code_request() {
+        let http = Http::new_with_token("token");
+
+        let request = token_request(&http, TOKEN_URL, 1234, "secret", &[
+            ("grant_type", "authorization_code"),
+            ("code", "a code"),
+            ("redirect_uri", "https://example.com/callback?state=1&x=2"),
+        ])
+        .unwrap();
+
+        assert_eq!(request.method(), reqwest::Method::POST);
+        assert_eq!(request.url().as_str(), "https://discord.com/api/v9/oauth2/token");
+        assert_eq!(request.headers()["content-type"], "application/x-www-form-urlencoded");
+        assert_eq!(
+            body(&request),
+            concat!(
+                "grant_type=authorization_code&code=a+code",
+                "&redirect_uri=https%3A%2F%2Fexample.com%2Fcallback%3Fstate%3D1%26x%3D2",
+            )
+        );
+
+        // The client credentials are used instead of the bot token.
+        // "1234:secret" in base64.
+        assert_eq!(request.headers()[AUTHORIZATION], "Basic MTIzNDpzZWNyZXQ=");
+    }
+
+    #[test]
+    fn test_revoke_request_through_proxy() {
+        let mut http = Http::new_with_token("token");
+        http.proxy = Some("http://127.0.0.1:3000/".parse().unwrap());
+
+        let request =
+            token_request(&http, REVOKE_URL, 1234, "secret", &[("token", "abc")]).unwrap();
+
+        assert_eq!(request.url().as_str(), "http://127.0.0.1:3000/api/v9/oauth2/token/revoke");
+        assert_eq!(body(&request), "token=abc");
+    }
+
+    #[test]
+    fn test_deserialize_access_token_response() {
+        let response: AccessTokenResponse = serde_json::from_value(json!({
+            "access_token": "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+            "token_type": "Bearer",
+            "expires_in": 604800,
+            "refresh_token": "D43f5y0ahjqew82jZ4NViEr2YafMKhue",
+            "scope": "identify role_connections.write",
+        }))
+        .unwrap();
+
+        assert_eq!(response.token_type, "Bearer");
+        assert_eq!(response.expires_in, 604800);
+        assert_eq!(response.refresh_token.as_deref(), Some("D43f5y0ahjqew82jZ4NViEr2YafMKhue"));
+        assert_eq!(response.scope.split(' ').collect::<Vec<_>>(), [
+            "identify",
+            "role_connections.write"
+        ]);
+    }
+}
@@ -45,7 +45,7 @@ use std::{
     fmt,
     i64,
     str::{self, FromStr},
-    sync::Arc,
+    sync::{Arc, PoisonError, RwLock as StdRwLock},
     time::SystemTime,
 };
 
@@ -61,6 +61,7 @@ use tracing::{debug, instrument};
 
 pub use super::routing::Route;
 use super::{HttpError, Request};
+use crate::constants::ApiVersion;
 use crate::internal::prelude::*;
 
 /// Ratelimiter for requests to the Discord API.
@@ -88,7 +89,7 @@ pub struct Ratelimiter {
     // When futures is implemented, make tasks clear out their respective entry
     // when the 'reset' passes.
     routes: Arc<RwLock<HashMap<Route, Arc<Mutex<Ratelimit>>>>>,
-    token: String,
+    token: StdRwLock<String>,
 }
 
 impl fmt::Debug for Ratelimiter {
@@ -116,10 +117,22 @@ impl Ratelimiter {
             client,
             global: Default::default(),
             routes: Default::default(),
-            token,
+            token: StdRwLock::new(token),
         }
     }
 
+    /// Replaces the bot token used for requests sent after this call.
+    ///
+    /// Like with [`Self::new`], the token must be prefixed with `"Bot "`.
+    pub fn set_token(&self, token: impl Into<String>) {
+        *self.token.write().unwrap_or_else(PoisonError::into_inner) = token.into();
+    }
+
+    /// Returns the bot token used for requests.
+    pub(crate) fn token(&self) -> String {
+        self.token.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
     /// The routes mutex is a HashMap of each [`Route`] and their respective
     /// ratelimit information.
     ///
@@ -160,6 +173,7 @@ impl Ratelimiter {
     pub async fn perform(&self, req: RatelimitedRequest<'_>) -> Result<Response> {
         let RatelimitedRequest {
             req,
+            api_version,
         } = req;
 
         loop {
@@ -189,7 +203,8 @@ impl Ratelimiter {
 
             bucket.lock().await.pre_hook(&route).await;
 
-            let request = req.build(&self.client, &self.token, None)?.build()?;
+            let request =
+                req.build_with_version(&self.client, &self.token(), None, api_version)?.build()?;
             let response = self.client.execute(request).await?;
 
             // Check if the request got ratelimited by checking for status 429,
@@ -380,12 +395,14 @@ impl Default for Ratelimit {
 #[derive(Debug)]
 pub struct RatelimitedRequest<'a> {
     req: Request<'a>,
+    pub(super) api_version: ApiVersion,
 }
 
 impl<'a> From<Request<'a>> for RatelimitedRequest<'a> {
     fn from(req: Request<'a>) -> Self {
         Self {
             req,
+            api_version: ApiVersion::default(),
         }
     }
 }
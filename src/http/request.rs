@@ -1,8 +1,11 @@
 use std::borrow::Cow;
+use std::time::Duration;
 
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use reqwest::{
     header::{
         HeaderMap as Headers,
+        HeaderName,
         HeaderValue,
         AUTHORIZATION,
         CONTENT_LENGTH,
@@ -14,13 +17,23 @@ use reqwest::{
 use reqwest::{Client, RequestBuilder as ReqwestRequestBuilder};
 use tracing::instrument;
 
+use super::utils::{truncate_snippet, versioned_url};
 use super::{routing::RouteInfo, HttpError};
-use crate::constants;
+use crate::constants::{self, ApiVersion};
+
+/// The maximum length of an audit log reason, in code points.
+const MAX_REASON_LENGTH: usize = 512;
+
+/// The characters to percent-encode in an audit log reason: everything except
+/// the unreserved characters of RFC 3986.
+const REASON_ENCODE_SET: &AsciiSet =
+    &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
 
 pub struct RequestBuilder<'a> {
     body: Option<&'a [u8]>,
     headers: Option<Headers>,
     route: RouteInfo<'a>,
+    timeout: Option<Duration>,
 }
 
 impl<'a> RequestBuilder<'a> {
@@ -29,6 +42,7 @@ impl<'a> RequestBuilder<'a> {
             body: None,
             headers: None,
             route: route_info,
+            timeout: None,
         }
     }
 
@@ -53,6 +67,16 @@ impl<'a> RequestBuilder<'a> {
 
         self
     }
+
+    /// Sets the timeout of the request, overriding the default timeout of the
+    /// [`Http`] client performing it.
+    ///
+    /// [`Http`]: super::Http
+    pub fn timeout(&mut self, timeout: Option<Duration>) -> &mut Self {
+        self.timeout = timeout;
+
+        self
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -60,6 +84,7 @@ pub struct Request<'a> {
     pub(super) body: Option<&'a [u8]>,
     pub(super) headers: Option<Headers>,
     pub(super) route: RouteInfo<'a>,
+    pub(super) timeout: Option<Duration>,
 }
 
 impl<'a> Request<'a> {
@@ -68,29 +93,83 @@ impl<'a> Request<'a> {
             body,
             headers,
             route,
+            timeout,
         } = builder;
 
         Self {
             body,
             headers,
             route,
+            timeout,
         }
     }
 
-    #[instrument(skip(token))]
+    /// Sets the reason for the action performed by the request, shown in the
+    /// audit log of the guild.
+    ///
+    /// The reason is sent in the `X-Audit-Log-Reason` header, percent-encoded
+    /// so that newlines and non-ASCII characters are kept intact. Reasons
+    /// longer than 512 characters are truncated, ending with an ellipsis.
+    ///
+    /// Passing [`None`] or an empty reason removes a previously set reason.
+    pub fn reason(mut self, reason: Option<&str>) -> Self {
+        let name = HeaderName::from_static("x-audit-log-reason");
+
+        match reason.filter(|reason| !reason.is_empty()) {
+            Some(reason) => {
+                let reason = if reason.chars().count() > MAX_REASON_LENGTH {
+                    truncate_snippet(reason, MAX_REASON_LENGTH - 1)
+                } else {
+                    reason.to_string()
+                };
+                let encoded = utf8_percent_encode(&reason, REASON_ENCODE_SET).to_string();
+
+                // The encoded reason only consists of visible ASCII characters,
+                // so it is always a valid header value.
+                if let Ok(value) = HeaderValue::from_str(&encoded) {
+                    self.headers.get_or_insert_with(Headers::new).insert(name, value);
+                }
+            },
+            None => {
+                if let Some(headers) = &mut self.headers {
+                    headers.remove(name);
+                }
+            },
+        }
+
+        self
+    }
+
     pub fn build(
         &'a self,
         client: &Client,
         token: &str,
         proxy: Option<&Url>,
+    ) -> Result<ReqwestRequestBuilder, HttpError> {
+        self.build_with_version(client, token, proxy, ApiVersion::default())
+    }
+
+    /// Builds the request against the given version of the API, which is
+    /// that of the [`Http`] client performing it.
+    ///
+    /// [`Http`]: super::Http
+    #[instrument(skip(token))]
+    pub(super) fn build_with_version(
+        &'a self,
+        client: &Client,
+        token: &str,
+        proxy: Option<&Url>,
+        api_version: ApiVersion,
     ) -> Result<ReqwestRequestBuilder, HttpError> {
         let Request {
             body,
             headers: ref request_headers,
             route: ref route_info,
+            timeout,
         } = *self;
 
-        let (method, _, mut path) = route_info.deconstruct();
+        let (method, _, path) = route_info.deconstruct();
+        let mut path = versioned_url(path, api_version);
 
         if let Some(proxy) = proxy {
             path = Cow::Owned(path.to_mut().replace("https://discord.com/", proxy.as_str()));
@@ -102,6 +181,10 @@ impl<'a> Request<'a> {
             builder = builder.body(Vec::from(bytes));
         }
 
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+
         let mut headers = Headers::with_capacity(4);
         headers.insert(USER_AGENT, HeaderValue::from_static(constants::USER_AGENT));
         headers
@@ -149,4 +232,100 @@ impl<'a> Request<'a> {
     pub fn route_mut(&mut self) -> &mut RouteInfo<'a> {
         &mut self.route
     }
+
+    pub fn timeout_ref(&self) -> &Option<Duration> {
+        &self.timeout
+    }
+
+    pub fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use reqwest::{Client, Url};
+
+    use super::RequestBuilder;
+    use crate::constants::ApiVersion;
+    use crate::http::routing::RouteInfo;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_timeout() {
+        let client = Client::new();
+
+        let request = RequestBuilder::new(RouteInfo::GetGateway).build();
+        let built = request.build(&client, "token", None).unwrap().build().unwrap();
+        assert_eq!(built.timeout(), None);
+
+        let mut builder = RequestBuilder::new(RouteInfo::GetGateway);
+        builder.timeout(Some(Duration::from_secs(3)));
+        let request = builder.build();
+        let built = request.build(&client, "token", None).unwrap().build().unwrap();
+        assert_eq!(built.timeout(), Some(&Duration::from_secs(3)));
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_api_version() {
+        let client = Client::new();
+
+        let request = RequestBuilder::new(RouteInfo::GetGateway).build();
+        let built = request.build(&client, "token", None).unwrap().build().unwrap();
+        assert_eq!(built.url().as_str(), "https://discord.com/api/v9/gateway");
+
+        let proxy = Url::parse("http://127.0.0.1:3000").unwrap();
+        let built = request
+            .build_with_version(&client, "token", Some(&proxy), ApiVersion::V10)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(built.url().as_str(), "http://127.0.0.1:3000/api/v10/gateway");
+    }
+
+    #[allow(clippy::unwrap_used)]
+    fn reason(reason: &str) -> Option<String> {
+        let request = RequestBuilder::new(RouteInfo::GetGateway).build().reason(Some(reason));
+
+        request.headers_ref().as_ref().and_then(|headers| {
+            headers.get("X-Audit-Log-Reason").map(|value| value.to_str().unwrap().to_string())
+        })
+    }
+
+    #[test]
+    fn test_reason_encoding() {
+        assert_eq!(reason("spam").as_deref(), Some("spam"));
+        assert_eq!(reason("spam, again").as_deref(), Some("spam%2C%20again"));
+        assert_eq!(reason("line 1\r\nline 2").as_deref(), Some("line%201%0D%0Aline%202"));
+        assert_eq!(reason("a-b_c.d~e").as_deref(), Some("a-b_c.d~e"));
+        assert_eq!(reason("ok 👍").as_deref(), Some("ok%20%F0%9F%91%8D"));
+        assert_eq!(reason(""), None);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_reason_truncation() {
+        let exact = "a".repeat(512);
+        assert_eq!(reason(&exact).unwrap(), exact);
+
+        let long = "👍".repeat(513);
+        let expected = format!("{}%E2%80%A6", "%F0%9F%91%8D".repeat(511));
+        assert_eq!(reason(&long).unwrap(), expected);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_reason_header() {
+        let client = Client::new();
+
+        let request = RequestBuilder::new(RouteInfo::GetGateway).build().reason(Some("ä"));
+        let built = request.build(&client, "token", None).unwrap().build().unwrap();
+        assert_eq!(built.headers()["X-Audit-Log-Reason"], "%C3%A4");
+
+        let request = request.reason(None);
+        assert!(request.headers_ref().as_ref().unwrap().get("X-Audit-Log-Reason").is_none());
+    }
 }
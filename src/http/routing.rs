@@ -3,7 +3,7 @@ use std::{
     fmt::{Display, Write},
 };
 
-use super::LightMethod;
+use super::{AuditLogFilter, BanFilter, LightMethod, MemberFilter};
 use crate::constants;
 
 /// A representation of all routes registered within the library. These are safe
@@ -58,6 +58,7 @@ pub enum Route {
     /// The data is the relevant [`ChannelId`].
     ///
     /// [`ChannelId`]: crate::model::id::ChannelId
+    #[deprecated(note = "Discord removed the message acknowledgement endpoint")]
     ChannelsIdMessagesIdAck(u64),
     /// Route for the `/channels/:channel_id/messages/:message_id/reactions`
     /// path.
@@ -104,6 +105,12 @@ pub enum Route {
     ///
     /// [`ChannelId`]: crate::model::id::ChannelId
     ChannelsIdTyping(u64),
+    /// Route for the `/channels/:channel_id/voice-status` path.
+    ///
+    /// The data is the relevant [`ChannelId`].
+    ///
+    /// [`ChannelId`]: crate::model::id::ChannelId
+    ChannelsIdVoiceStatus(u64),
     /// Route for the `/channels/:channel_id/webhooks` path.
     ///
     /// The data is the relevant [`ChannelId`].
@@ -241,6 +248,7 @@ pub enum Route {
     /// The data is the relevant [`GuildId`].
     ///
     /// [`GuildId`]: crate::model::id::GuildId
+    #[deprecated(note = "Discord removed the integration sync endpoint")]
     GuildsIdIntegrationsIdSync(u64),
     /// Route for the `/guilds/:guild_id/invites` path.
     ///
@@ -277,6 +285,7 @@ pub enum Route {
     /// The data is the relevant [`GuildId`].
     ///
     /// [`GuildId`]: crate::model::id::GuildId
+    #[deprecated(note = "use `GuildsIdMembersMe` instead")]
     GuildsIdMembersMeNick(u64),
     /// Route for the `/guilds/:guild_id/members/search` path.
     ///
@@ -308,6 +317,24 @@ pub enum Route {
     ///
     /// [`GuildId`]: crate::model::id::GuildId
     GuildsIdRolesId(u64),
+    /// Route for the `/guilds/:guild_id/scheduled-events` path.
+    ///
+    /// The data is the relevant [`GuildId`].
+    ///
+    /// [`GuildId`]: crate::model::id::GuildId
+    GuildsIdScheduledEvents(u64),
+    /// Route for the `/guilds/:guild_id/scheduled-events/:event_id` path.
+    ///
+    /// The data is the relevant [`GuildId`].
+    ///
+    /// [`GuildId`]: crate::model::id::GuildId
+    GuildsIdScheduledEventsId(u64),
+    /// Route for the `/guilds/:guild_id/stickers/:sticker_id` path.
+    ///
+    /// The data is the relevant [`GuildId`].
+    ///
+    /// [`GuildId`]: crate::model::id::GuildId
+    GuildsIdStickersId(u64),
     /// Route for the `/guilds/:guild_id/vanity-url` path.
     ///
     /// The data is the relevant [`GuildId`].
@@ -350,6 +377,13 @@ pub enum Route {
     UsersId,
     /// Route for the `/users/@me` path.
     UsersMe,
+    /// Route for the `/users/@me/applications/:application_id/role-connection`
+    /// path.
+    ///
+    /// The data is the relevant [`ApplicationId`].
+    ///
+    /// [`ApplicationId`]: crate::model::id::ApplicationId
+    UsersMeApplicationsIdRoleConnection(u64),
     /// Route for the `/users/@me/channels` path.
     UsersMeChannels,
     /// Route for the `/users/@me/connections` path.
@@ -424,6 +458,27 @@ pub enum Route {
     /// [`ApplicationId`]: crate::model::id::ApplicationId
     #[cfg(feature = "unstable_discord_api")]
     ApplicationsIdGuildsIdCommandsId(u64),
+    /// Route for the `/applications/:application_id/skus` path.
+    ///
+    /// The data is the relevant [`ApplicationId`].
+    ///
+    /// [`ApplicationId`]: crate::model::id::ApplicationId
+    #[cfg(feature = "unstable_discord_api")]
+    ApplicationsIdSkus(u64),
+    /// Route for the `/applications/:application_id/entitlements` path.
+    ///
+    /// The data is the relevant [`ApplicationId`].
+    ///
+    /// [`ApplicationId`]: crate::model::id::ApplicationId
+    #[cfg(feature = "unstable_discord_api")]
+    ApplicationsIdEntitlements(u64),
+    /// Route for the `/applications/:application_id/entitlements/:entitlement_id` path.
+    ///
+    /// The data is the relevant [`ApplicationId`].
+    ///
+    /// [`ApplicationId`]: crate::model::id::ApplicationId
+    #[cfg(feature = "unstable_discord_api")]
+    ApplicationsIdEntitlementsId(u64),
     /// Route for the `/stage-instances` path.
     ///
     /// The data is the relevant [`ChannelId`].
@@ -539,6 +594,10 @@ impl Route {
         format!(api!("/channels/{}/typing"), channel_id)
     }
 
+    pub fn channel_voice_status(channel_id: u64) -> String {
+        format!(api!("/channels/{}/voice-status"), channel_id)
+    }
+
     pub fn channel_webhooks(channel_id: u64) -> String {
         format!(api!("/channels/{}/webhooks"), channel_id)
     }
@@ -648,25 +707,25 @@ impl Route {
         before: Option<u64>,
         limit: Option<u8>,
     ) -> String {
-        let mut s = format!(api!("/guilds/{}/audit-logs?"), guild_id,);
+        let mut filter = AuditLogFilter::new();
 
         if let Some(action_type) = action_type {
-            let _ = write!(s, "&action_type={}", action_type);
+            filter = filter.action_type(action_type);
         }
 
         if let Some(before) = before {
-            let _ = write!(s, "&before={}", before);
+            filter = filter.before(before);
         }
 
         if let Some(limit) = limit {
-            let _ = write!(s, "&limit={}", limit);
+            filter = filter.limit(limit);
         }
 
         if let Some(user_id) = user_id {
-            let _ = write!(s, "&user_id={}", user_id);
+            filter = filter.user_id(user_id);
         }
 
-        s
+        format!(api!("/guilds/{}/audit-logs?{}"), guild_id, filter.to_query())
     }
 
     pub fn guild_ban(guild_id: u64, user_id: u64) -> String {
@@ -693,6 +752,29 @@ impl Route {
         format!(api!("/guilds/{}/bans"), guild_id)
     }
 
+    pub fn guild_bans_optioned(
+        guild_id: u64,
+        after: Option<u64>,
+        before: Option<u64>,
+        limit: Option<u16>,
+    ) -> String {
+        let mut filter = BanFilter::new();
+
+        if let Some(after) = after {
+            filter = filter.after(after);
+        }
+
+        if let Some(before) = before {
+            filter = filter.before(before);
+        }
+
+        if let Some(limit) = limit {
+            filter = filter.limit(limit);
+        }
+
+        format!(api!("/guilds/{}/bans?{}"), guild_id, filter.to_query())
+    }
+
     pub fn guild_channels(guild_id: u64) -> String {
         format!(api!("/guilds/{}/channels"), guild_id)
     }
@@ -717,6 +799,7 @@ impl Route {
         format!(api!("/guilds/{}/integrations/{}"), guild_id, integration_id)
     }
 
+    #[deprecated(note = "Discord removed the integration sync endpoint")]
     pub fn guild_integration_sync(guild_id: u64, integration_id: u64) -> String {
         format!(api!("/guilds/{}/integrations/{}/sync"), guild_id, integration_id,)
     }
@@ -754,23 +837,20 @@ impl Route {
 
     #[allow(clippy::let_underscore_must_use)]
     pub fn guild_members_optioned(guild_id: u64, after: Option<u64>, limit: Option<u64>) -> String {
-        let mut s = format!(api!("/guilds/{}/members?"), guild_id);
+        let mut filter = MemberFilter::new().limit(limit.unwrap_or(constants::MEMBER_FETCH_LIMIT));
 
         if let Some(after) = after {
-            let _ = write!(s, "&after={}", after);
-            // should not error, ignoring
+            filter = filter.after(after);
         }
 
-        let _ = write!(s, "&limit={}", limit.unwrap_or(constants::MEMBER_FETCH_LIMIT));
-        // should not error, ignoring
-
-        s
+        format!(api!("/guilds/{}/members?{}"), guild_id, filter.to_query())
     }
 
     pub fn guild_member_me(guild_id: u64) -> String {
         format!(api!("/guilds/{}/members/@me"), guild_id)
     }
 
+    #[deprecated(note = "use `guild_member_me` instead")]
     pub fn guild_nickname(guild_id: u64) -> String {
         format!(api!("/guilds/{}/members/@me/nick"), guild_id)
     }
@@ -791,6 +871,25 @@ impl Route {
         format!(api!("/guilds/{}/roles"), guild_id)
     }
 
+    #[allow(clippy::let_underscore_must_use)]
+    pub fn guild_scheduled_events(guild_id: u64, with_user_count: Option<bool>) -> String {
+        let mut s = format!(api!("/guilds/{}/scheduled-events"), guild_id);
+
+        if let Some(with_user_count) = with_user_count {
+            let _ = write!(s, "?with_user_count={}", with_user_count);
+        }
+
+        s
+    }
+
+    pub fn guild_scheduled_event(guild_id: u64, event_id: u64) -> String {
+        format!(api!("/guilds/{}/scheduled-events/{}"), guild_id, event_id)
+    }
+
+    pub fn guild_sticker(guild_id: u64, sticker_id: u64) -> String {
+        format!(api!("/guilds/{}/stickers/{}"), guild_id, sticker_id)
+    }
+
     pub fn guild_vanity_url(guild_id: u64) -> String {
         format!(api!("/guilds/{}/vanity-url"), guild_id)
     }
@@ -855,6 +954,10 @@ impl Route {
         api!("/users/@me/connections")
     }
 
+    pub fn user_me_application_role_connection(application_id: u64) -> String {
+        api!("/users/@me/applications/{}/role-connection", application_id)
+    }
+
     pub fn user_dm_channels<D: Display>(target: D) -> String {
         format!(api!("/users/{}/channels"), target)
     }
@@ -911,6 +1014,24 @@ impl Route {
         format!(api!("/webhooks/{}/{}?wait={}"), webhook_id, token, wait)
     }
 
+    pub fn webhook_with_token_optioned_components<D>(
+        webhook_id: u64,
+        token: D,
+        wait: bool,
+        with_components: bool,
+    ) -> String
+    where
+        D: Display,
+    {
+        let mut s = Route::webhook_with_token_optioned(webhook_id, token, wait);
+
+        if with_components {
+            s.push_str("&with_components=true");
+        }
+
+        s
+    }
+
     pub fn webhook_message<D>(webhook_id: u64, token: D, message_id: u64) -> String
     where
         D: Display,
@@ -918,6 +1039,24 @@ impl Route {
         format!(api!("/webhooks/{}/{}/messages/{}"), webhook_id, token, message_id)
     }
 
+    pub fn webhook_message_optioned<D>(
+        webhook_id: u64,
+        token: D,
+        message_id: u64,
+        with_components: bool,
+    ) -> String
+    where
+        D: Display,
+    {
+        let mut s = Route::webhook_message(webhook_id, token, message_id);
+
+        if with_components {
+            s.push_str("?with_components=true");
+        }
+
+        s
+    }
+
     #[cfg(feature = "unstable_discord_api")]
     pub fn webhook_original_interaction_response<D: Display>(
         application_id: u64,
@@ -955,6 +1094,67 @@ impl Route {
         format!(api!("/applications/{}/commands"), application_id)
     }
 
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn application_skus(application_id: u64) -> String {
+        format!(api!("/applications/{}/skus"), application_id)
+    }
+
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn application_entitlements(application_id: u64) -> String {
+        format!(api!("/applications/{}/entitlements"), application_id)
+    }
+
+    #[cfg(feature = "unstable_discord_api")]
+    #[allow(clippy::let_underscore_must_use, clippy::too_many_arguments)]
+    pub fn application_entitlements_optioned(
+        application_id: u64,
+        user_id: Option<u64>,
+        sku_ids: &[u64],
+        before: Option<u64>,
+        after: Option<u64>,
+        limit: Option<u8>,
+        guild_id: Option<u64>,
+        exclude_ended: bool,
+    ) -> String {
+        let mut s = format!(api!("/applications/{}/entitlements?"), application_id);
+
+        if let Some(user_id) = user_id {
+            let _ = write!(s, "&user_id={}", user_id);
+        }
+
+        if !sku_ids.is_empty() {
+            let sku_ids = sku_ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(",");
+            let _ = write!(s, "&sku_ids={}", sku_ids);
+        }
+
+        if let Some(before) = before {
+            let _ = write!(s, "&before={}", before);
+        }
+
+        if let Some(after) = after {
+            let _ = write!(s, "&after={}", after);
+        }
+
+        if let Some(limit) = limit {
+            let _ = write!(s, "&limit={}", limit);
+        }
+
+        if let Some(guild_id) = guild_id {
+            let _ = write!(s, "&guild_id={}", guild_id);
+        }
+
+        if exclude_ended {
+            s.push_str("&exclude_ended=true");
+        }
+
+        s
+    }
+
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn application_entitlement(application_id: u64, entitlement_id: u64) -> String {
+        format!(api!("/applications/{}/entitlements/{}"), application_id, entitlement_id)
+    }
+
     #[cfg(feature = "unstable_discord_api")]
     pub fn application_guild_command(
         application_id: u64,
@@ -1030,6 +1230,10 @@ pub enum RouteInfo<'a> {
     CreatePrivateThread {
         channel_id: u64,
     },
+    #[cfg(feature = "unstable_discord_api")]
+    CreateForumPost {
+        channel_id: u64,
+    },
     CreateEmoji {
         guild_id: u64,
     },
@@ -1085,6 +1289,13 @@ pub enum RouteInfo<'a> {
     CreateRole {
         guild_id: u64,
     },
+    CreateScheduledEvent {
+        guild_id: u64,
+    },
+    #[cfg(feature = "unstable_discord_api")]
+    CreateTestEntitlement {
+        application_id: u64,
+    },
     CreateWebhook {
         channel_id: u64,
     },
@@ -1094,6 +1305,11 @@ pub enum RouteInfo<'a> {
     DeleteStageInstance {
         channel_id: u64,
     },
+    #[cfg(feature = "unstable_discord_api")]
+    DeleteTestEntitlement {
+        application_id: u64,
+        entitlement_id: u64,
+    },
     DeleteEmoji {
         guild_id: u64,
         emoji_id: u64,
@@ -1160,6 +1376,14 @@ pub enum RouteInfo<'a> {
         guild_id: u64,
         role_id: u64,
     },
+    DeleteScheduledEvent {
+        guild_id: u64,
+        event_id: u64,
+    },
+    DeleteSticker {
+        guild_id: u64,
+        sticker_id: u64,
+    },
     DeleteWebhook {
         webhook_id: u64,
     },
@@ -1237,6 +1461,7 @@ pub enum RouteInfo<'a> {
     EditMemberMe {
         guild_id: u64,
     },
+    #[deprecated(note = "use `EditMemberMe` instead")]
     EditNickname {
         guild_id: u64,
     },
@@ -1250,6 +1475,9 @@ pub enum RouteInfo<'a> {
         application_id: u64,
         interaction_token: &'a str,
     },
+    EditCurrentUserApplicationRoleConnection {
+        application_id: u64,
+    },
     EditProfile,
     EditRole {
         guild_id: u64,
@@ -1268,6 +1496,9 @@ pub enum RouteInfo<'a> {
     EditVoiceStateMe {
         guild_id: u64,
     },
+    EditVoiceStatus {
+        channel_id: u64,
+    },
     EditWebhook {
         webhook_id: u64,
     },
@@ -1279,11 +1510,13 @@ pub enum RouteInfo<'a> {
         token: &'a str,
         webhook_id: u64,
         message_id: u64,
+        with_components: bool,
     },
     ExecuteWebhook {
         token: &'a str,
         wait: bool,
         webhook_id: u64,
+        with_components: bool,
     },
     JoinThread {
         channel_id: u64,
@@ -1310,6 +1543,12 @@ pub enum RouteInfo<'a> {
     GetBans {
         guild_id: u64,
     },
+    GetBansOptioned {
+        guild_id: u64,
+        after: Option<u64>,
+        before: Option<u64>,
+        limit: Option<u16>,
+    },
     GetBotGateway,
     GetChannel {
         channel_id: u64,
@@ -1357,6 +1596,17 @@ pub enum RouteInfo<'a> {
         emoji_id: u64,
     },
     #[cfg(feature = "unstable_discord_api")]
+    GetEntitlements {
+        application_id: u64,
+        user_id: Option<u64>,
+        sku_ids: &'a [u64],
+        before: Option<u64>,
+        after: Option<u64>,
+        limit: Option<u8>,
+        guild_id: Option<u64>,
+        exclude_ended: bool,
+    },
+    #[cfg(feature = "unstable_discord_api")]
     GetFollowupMessage {
         application_id: u64,
         interaction_token: &'a str,
@@ -1364,6 +1614,10 @@ pub enum RouteInfo<'a> {
     },
     GetGateway,
     #[cfg(feature = "unstable_discord_api")]
+    GetSkus {
+        application_id: u64,
+    },
+    #[cfg(feature = "unstable_discord_api")]
     GetGlobalApplicationCommands {
         application_id: u64,
     },
@@ -1430,9 +1684,17 @@ pub enum RouteInfo<'a> {
     GetGuildRegions {
         guild_id: u64,
     },
+    GetGuildRole {
+        guild_id: u64,
+        role_id: u64,
+    },
     GetGuildRoles {
         guild_id: u64,
     },
+    GetGuildScheduledEvents {
+        guild_id: u64,
+        with_user_count: bool,
+    },
     GetGuildVanityUrl {
         guild_id: u64,
     },
@@ -1493,7 +1755,6 @@ pub enum RouteInfo<'a> {
     KickMember {
         guild_id: u64,
         user_id: u64,
-        reason: &'a str,
     },
     LeaveGroup {
         group_id: u64,
@@ -1523,6 +1784,7 @@ pub enum RouteInfo<'a> {
         days: u64,
         guild_id: u64,
     },
+    #[deprecated(note = "Discord removed the integration sync endpoint")]
     StartIntegrationSync {
         guild_id: u64,
         integration_id: u64,
@@ -1537,6 +1799,7 @@ pub enum RouteInfo<'a> {
 }
 
 impl<'a> RouteInfo<'a> {
+    #[allow(deprecated)]
     pub fn deconstruct(&self) -> (LightMethod, Route, Cow<'_, str>) {
         match *self {
             RouteInfo::AddGuildMember {
@@ -1604,6 +1867,14 @@ impl<'a> RouteInfo<'a> {
                 Route::ChannelsIdThreads(channel_id),
                 Cow::from(Route::channel_private_threads(channel_id)),
             ),
+            #[cfg(feature = "unstable_discord_api")]
+            RouteInfo::CreateForumPost {
+                channel_id,
+            } => (
+                LightMethod::Post,
+                Route::ChannelsIdThreads(channel_id),
+                Cow::from(Route::channel_private_threads(channel_id)),
+            ),
             RouteInfo::CreateEmoji {
                 guild_id,
             } => (
@@ -1717,6 +1988,13 @@ impl<'a> RouteInfo<'a> {
                 Route::GuildsIdRoles(guild_id),
                 Cow::from(Route::guild_roles(guild_id)),
             ),
+            RouteInfo::CreateScheduledEvent {
+                guild_id,
+            } => (
+                LightMethod::Post,
+                Route::GuildsIdScheduledEvents(guild_id),
+                Cow::from(Route::guild_scheduled_events(guild_id, None)),
+            ),
             RouteInfo::CrosspostMessage {
                 channel_id,
                 message_id,
@@ -1725,6 +2003,14 @@ impl<'a> RouteInfo<'a> {
                 Route::ChannelsIdCrosspostsMessageId(channel_id),
                 Cow::from(Route::channel_message_crosspost(channel_id, message_id)),
             ),
+            #[cfg(feature = "unstable_discord_api")]
+            RouteInfo::CreateTestEntitlement {
+                application_id,
+            } => (
+                LightMethod::Post,
+                Route::ApplicationsIdEntitlements(application_id),
+                Cow::from(Route::application_entitlements(application_id)),
+            ),
             RouteInfo::CreateWebhook {
                 channel_id,
             } => (
@@ -1746,6 +2032,15 @@ impl<'a> RouteInfo<'a> {
                 Route::StageInstancesChannelId(channel_id),
                 Cow::from(Route::stage_instance(channel_id)),
             ),
+            #[cfg(feature = "unstable_discord_api")]
+            RouteInfo::DeleteTestEntitlement {
+                application_id,
+                entitlement_id,
+            } => (
+                LightMethod::Delete,
+                Route::ApplicationsIdEntitlementsId(application_id),
+                Cow::from(Route::application_entitlement(application_id, entitlement_id)),
+            ),
             RouteInfo::DeleteEmoji {
                 emoji_id,
                 guild_id,
@@ -1873,6 +2168,22 @@ impl<'a> RouteInfo<'a> {
                 Route::GuildsIdRolesId(guild_id),
                 Cow::from(Route::guild_role(guild_id, role_id)),
             ),
+            RouteInfo::DeleteScheduledEvent {
+                guild_id,
+                event_id,
+            } => (
+                LightMethod::Delete,
+                Route::GuildsIdScheduledEventsId(guild_id),
+                Cow::from(Route::guild_scheduled_event(guild_id, event_id)),
+            ),
+            RouteInfo::DeleteSticker {
+                guild_id,
+                sticker_id,
+            } => (
+                LightMethod::Delete,
+                Route::GuildsIdStickersId(guild_id),
+                Cow::from(Route::guild_sticker(guild_id, sticker_id)),
+            ),
             RouteInfo::DeleteWebhook {
                 webhook_id,
             } => (
@@ -2053,6 +2364,13 @@ impl<'a> RouteInfo<'a> {
                     interaction_token,
                 )),
             ),
+            RouteInfo::EditCurrentUserApplicationRoleConnection {
+                application_id,
+            } => (
+                LightMethod::Put,
+                Route::UsersMeApplicationsIdRoleConnection(application_id),
+                Cow::from(Route::user_me_application_role_connection(application_id)),
+            ),
             RouteInfo::EditProfile => {
                 (LightMethod::Patch, Route::UsersMe, Cow::from(Route::user("@me")))
             },
@@ -2093,6 +2411,13 @@ impl<'a> RouteInfo<'a> {
                 Route::GuildsIdVoiceStatesMe(guild_id),
                 Cow::from(Route::guild_voice_states_me(guild_id)),
             ),
+            RouteInfo::EditVoiceStatus {
+                channel_id,
+            } => (
+                LightMethod::Put,
+                Route::ChannelsIdVoiceStatus(channel_id),
+                Cow::from(Route::channel_voice_status(channel_id)),
+            ),
             RouteInfo::EditWebhook {
                 webhook_id,
             } => (
@@ -2121,19 +2446,31 @@ impl<'a> RouteInfo<'a> {
                 token,
                 webhook_id,
                 message_id,
+                with_components,
             } => (
                 LightMethod::Patch,
                 Route::WebhooksIdMessagesId(webhook_id),
-                Cow::from(Route::webhook_message(webhook_id, token, message_id)),
+                Cow::from(Route::webhook_message_optioned(
+                    webhook_id,
+                    token,
+                    message_id,
+                    with_components,
+                )),
             ),
             RouteInfo::ExecuteWebhook {
                 token,
                 wait,
                 webhook_id,
+                with_components,
             } => (
                 LightMethod::Post,
                 Route::WebhooksId(webhook_id),
-                Cow::from(Route::webhook_with_token_optioned(webhook_id, token, wait)),
+                Cow::from(Route::webhook_with_token_optioned_components(
+                    webhook_id,
+                    token,
+                    wait,
+                    with_components,
+                )),
             ),
             RouteInfo::GetActiveMaintenance => {
                 (LightMethod::Get, Route::None, Cow::from(Route::status_maintenances_active()))
@@ -2156,6 +2493,16 @@ impl<'a> RouteInfo<'a> {
                 Route::GuildsIdBans(guild_id),
                 Cow::from(Route::guild_bans(guild_id)),
             ),
+            RouteInfo::GetBansOptioned {
+                guild_id,
+                after,
+                before,
+                limit,
+            } => (
+                LightMethod::Get,
+                Route::GuildsIdBans(guild_id),
+                Cow::from(Route::guild_bans_optioned(guild_id, after, before, limit)),
+            ),
             RouteInfo::GetBotGateway => {
                 (LightMethod::Get, Route::GatewayBot, Cow::from(Route::gateway_bot()))
             },
@@ -2307,10 +2654,42 @@ impl<'a> RouteInfo<'a> {
                 Route::GuildsIdEmojisId(guild_id),
                 Cow::from(Route::guild_emoji(guild_id, emoji_id)),
             ),
+            #[cfg(feature = "unstable_discord_api")]
+            RouteInfo::GetEntitlements {
+                application_id,
+                user_id,
+                sku_ids,
+                before,
+                after,
+                limit,
+                guild_id,
+                exclude_ended,
+            } => (
+                LightMethod::Get,
+                Route::ApplicationsIdEntitlements(application_id),
+                Cow::from(Route::application_entitlements_optioned(
+                    application_id,
+                    user_id,
+                    sku_ids,
+                    before,
+                    after,
+                    limit,
+                    guild_id,
+                    exclude_ended,
+                )),
+            ),
             RouteInfo::GetGateway => {
                 (LightMethod::Get, Route::Gateway, Cow::from(Route::gateway()))
             },
             #[cfg(feature = "unstable_discord_api")]
+            RouteInfo::GetSkus {
+                application_id,
+            } => (
+                LightMethod::Get,
+                Route::ApplicationsIdSkus(application_id),
+                Cow::from(Route::application_skus(application_id)),
+            ),
+            #[cfg(feature = "unstable_discord_api")]
             RouteInfo::GetGlobalApplicationCommands {
                 application_id,
             } => (
@@ -2438,6 +2817,14 @@ impl<'a> RouteInfo<'a> {
                 Route::GuildsIdRegions(guild_id),
                 Cow::from(Route::guild_regions(guild_id)),
             ),
+            RouteInfo::GetGuildRole {
+                guild_id,
+                role_id,
+            } => (
+                LightMethod::Get,
+                Route::GuildsIdRolesId(guild_id),
+                Cow::from(Route::guild_role(guild_id, role_id)),
+            ),
             RouteInfo::GetGuildRoles {
                 guild_id,
             } => (
@@ -2445,6 +2832,14 @@ impl<'a> RouteInfo<'a> {
                 Route::GuildsIdRoles(guild_id),
                 Cow::from(Route::guild_roles(guild_id)),
             ),
+            RouteInfo::GetGuildScheduledEvents {
+                guild_id,
+                with_user_count,
+            } => (
+                LightMethod::Get,
+                Route::GuildsIdScheduledEvents(guild_id),
+                Cow::from(Route::guild_scheduled_events(guild_id, Some(with_user_count))),
+            ),
             RouteInfo::GetGuildVanityUrl {
                 guild_id,
             } => (
@@ -2560,11 +2955,10 @@ impl<'a> RouteInfo<'a> {
             RouteInfo::KickMember {
                 guild_id,
                 user_id,
-                reason,
             } => (
                 LightMethod::Delete,
                 Route::GuildsIdMembersId(guild_id),
-                Cow::from(Route::guild_kick_optioned(guild_id, user_id, reason)),
+                Cow::from(Route::guild_member(guild_id, user_id)),
             ),
             RouteInfo::LeaveGroup {
                 group_id,
@@ -2650,3 +3044,124 @@ impl<'a> RouteInfo<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{LightMethod, Route, RouteInfo};
+
+    #[test]
+    fn test_webhook_components_query() {
+        assert_eq!(
+            Route::webhook_with_token_optioned_components(1, "token", true, false),
+            "https://discord.com/api/v9/webhooks/1/token?wait=true",
+        );
+        assert_eq!(
+            Route::webhook_with_token_optioned_components(1, "token", false, true),
+            "https://discord.com/api/v9/webhooks/1/token?wait=false&with_components=true",
+        );
+        assert_eq!(
+            Route::webhook_message_optioned(1, "token", 2, false),
+            "https://discord.com/api/v9/webhooks/1/token/messages/2",
+        );
+        assert_eq!(
+            Route::webhook_message_optioned(1, "token", 2, true),
+            "https://discord.com/api/v9/webhooks/1/token/messages/2?with_components=true",
+        );
+    }
+
+    #[test]
+    fn test_application_role_connection_route() {
+        let route = RouteInfo::EditCurrentUserApplicationRoleConnection {
+            application_id: 7,
+        };
+        let (method, route, url) = route.deconstruct();
+        assert_eq!(method, LightMethod::Put);
+        assert_eq!(route, Route::UsersMeApplicationsIdRoleConnection(7));
+        assert_eq!(url, "https://discord.com/api/v9/users/@me/applications/7/role-connection");
+    }
+
+    #[test]
+    fn test_reasoned_delete_routes() {
+        let route = RouteInfo::KickMember {
+            guild_id: 1,
+            user_id: 2,
+        };
+        let (method, route, url) = route.deconstruct();
+        assert_eq!(method, LightMethod::Delete);
+        assert_eq!(route, Route::GuildsIdMembersId(1));
+        assert_eq!(url, "https://discord.com/api/v9/guilds/1/members/2");
+
+        let route = RouteInfo::DeleteSticker {
+            guild_id: 1,
+            sticker_id: 3,
+        };
+        let (method, route, url) = route.deconstruct();
+        assert_eq!(method, LightMethod::Delete);
+        assert_eq!(route, Route::GuildsIdStickersId(1));
+        assert_eq!(url, "https://discord.com/api/v9/guilds/1/stickers/3");
+
+        let route = RouteInfo::DeleteScheduledEvent {
+            guild_id: 1,
+            event_id: 4,
+        };
+        let (method, route, url) = route.deconstruct();
+        assert_eq!(method, LightMethod::Delete);
+        assert_eq!(route, Route::GuildsIdScheduledEventsId(1));
+        assert_eq!(url, "https://discord.com/api/v9/guilds/1/scheduled-events/4");
+    }
+
+    #[test]
+    #[cfg(feature = "unstable_discord_api")]
+    fn test_entitlements_query() {
+        assert_eq!(
+            Route::application_entitlements_optioned(1, None, &[], None, None, None, None, false),
+            "https://discord.com/api/v9/applications/1/entitlements?",
+        );
+        assert_eq!(
+            Route::application_entitlements_optioned(
+                1,
+                Some(2),
+                &[3, 4],
+                None,
+                Some(5),
+                Some(100),
+                Some(6),
+                true,
+            ),
+            "https://discord.com/api/v9/applications/1/entitlements?&user_id=2&sku_ids=3,4&after=5\
+             &limit=100&guild_id=6&exclude_ended=true",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "unstable_discord_api")]
+    fn test_interaction_routes_use_given_application_id() {
+        let original = RouteInfo::EditOriginalInteractionResponse {
+            application_id: 7,
+            interaction_token: "token",
+        };
+        let (method, route, url) = original.deconstruct();
+        assert_eq!(method, LightMethod::Patch);
+        assert_eq!(route, Route::WebhooksApplicationId(7));
+        assert_eq!(url, "https://discord.com/api/v9/webhooks/7/token/messages/@original");
+
+        let followup = RouteInfo::CreateFollowupMessage {
+            application_id: 8,
+            interaction_token: "token",
+        };
+        let (method, route, url) = followup.deconstruct();
+        assert_eq!(method, LightMethod::Post);
+        assert_eq!(route, Route::WebhooksId(8));
+        assert_eq!(url, "https://discord.com/api/v9/webhooks/8/token");
+
+        let followup_message = RouteInfo::DeleteFollowupMessage {
+            application_id: 9,
+            interaction_token: "token",
+            message_id: 10,
+        };
+        let (method, route, url) = followup_message.deconstruct();
+        assert_eq!(method, LightMethod::Delete);
+        assert_eq!(route, Route::WebhooksApplicationId(9));
+        assert_eq!(url, "https://discord.com/api/v9/webhooks/9/token/messages/10");
+    }
+}
@@ -1,5 +1,10 @@
+use std::borrow::Cow;
+#[cfg(feature = "client")]
+use std::io::{Error as IoError, ErrorKind, Result as IoResult, Write};
+
 use serde::de::{Deserialize, Deserializer};
 
+use crate::constants::ApiVersion;
 use crate::http::error::DiscordJsonSingleError;
 use crate::internal::prelude::*;
 
@@ -61,3 +66,94 @@ fn loop_errors(value: Value, errors: &mut Vec<DiscordJsonSingleError>, path: Vec
         loop_errors(looped.clone(), errors, new_path);
     }
 }
+
+/// Truncates a response body or gateway payload to at most `limit` characters,
+/// marking the cut with an ellipsis, for inclusion in an error.
+pub(crate) fn truncate_snippet(body: &str, limit: usize) -> String {
+    match body.char_indices().nth(limit) {
+        Some((index, _)) => format!("{}…", &body[..index]),
+        None => body.to_string(),
+    }
+}
+
+/// Serializes the start of a JSON value and truncates it like
+/// [`truncate_snippet`], without serializing more of the value than is kept.
+#[cfg(feature = "client")]
+pub(crate) fn json_snippet(value: &Value, limit: usize) -> String {
+    // A character takes at most 4 bytes, and the one past the limit is needed
+    // to tell whether the value was cut.
+    let mut writer = BoundedWriter {
+        bytes: Vec::new(),
+        capacity: limit.saturating_add(1).saturating_mul(4),
+    };
+
+    // Serialization fails once the writer is full, leaving the start of it.
+    #[allow(clippy::let_underscore_must_use)]
+    let _ = serde_json::to_writer(&mut writer, value);
+
+    truncate_snippet(&String::from_utf8_lossy(&writer.bytes), limit)
+}
+
+/// A writer which refuses to write past its capacity.
+#[cfg(feature = "client")]
+struct BoundedWriter {
+    bytes: Vec<u8>,
+    capacity: usize,
+}
+
+#[cfg(feature = "client")]
+impl Write for BoundedWriter {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        let len = buf.len().min(self.capacity - self.bytes.len());
+
+        if len == 0 && !buf.is_empty() {
+            return Err(IoError::new(ErrorKind::WriteZero, "snippet is complete"));
+        }
+
+        self.bytes.extend_from_slice(&buf[..len]);
+
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+/// Points a URL of the REST API, as built by the `api!` macro for the default
+/// [`ApiVersion`], to the given version instead.
+pub(crate) fn versioned_url(url: Cow<'_, str>, version: ApiVersion) -> Cow<'_, str> {
+    let default = ApiVersion::default();
+
+    if version == default {
+        return url;
+    }
+
+    let from = format!("/api/v{}/", default.num());
+    let to = format!("/api/v{}/", version.num());
+
+    Cow::Owned(url.replacen(&from, &to, 1))
+}
+
+#[cfg(all(test, feature = "client"))]
+mod test {
+    use serde_json::json;
+
+    use super::json_snippet;
+
+    #[test]
+    fn test_json_snippet() {
+        let value = json!({"t": "MESSAGE_CREATE", "d": {"content": "\u{e4}".repeat(1000)}});
+
+        let snippet = json_snippet(&value, 12);
+        assert_eq!(snippet, "{\"d\":{\"conte\u{2026}");
+
+        let snippet = json_snippet(&value, 40);
+        assert_eq!(snippet, format!("{{\"d\":{{\"content\":\"{}\u{2026}", "\u{e4}".repeat(23)));
+
+        let value = json!({"op": 11});
+        assert_eq!(json_snippet(&value, 10), "{\"op\":11}");
+        assert_eq!(json_snippet(&value, 9), "{\"op\":11}");
+        assert_eq!(json_snippet(&value, 8), "{\"op\":11\u{2026}");
+    }
+}
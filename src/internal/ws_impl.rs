@@ -1,3 +1,4 @@
+use std::io::Read;
 #[cfg(all(feature = "rustls_backend_marker", not(feature = "native_tls_backend_marker")))]
 use std::{
     error::Error as StdError,
@@ -6,10 +7,10 @@ use std::{
 };
 
 use async_trait::async_trait;
-use async_tungstenite::tungstenite::Message;
+use async_tungstenite::tungstenite::{Error as TungsteniteError, Message};
 use flate2::read::ZlibDecoder;
 use futures::stream::SplitSink;
-use futures::{SinkExt, StreamExt, TryStreamExt};
+use futures::{SinkExt, StreamExt};
 use tokio::time::timeout;
 use tracing::{instrument, warn};
 use url::Url;
@@ -19,8 +20,7 @@ use crate::internal::prelude::*;
 
 #[async_trait]
 pub trait ReceiverExt {
-    async fn recv_json(&mut self) -> Result<Option<Value>>;
-    async fn try_recv_json(&mut self) -> Result<Option<Value>>;
+    async fn recv_json(&mut self, max_payload_size: usize) -> Result<Option<Value>>;
 }
 
 #[async_trait]
@@ -30,20 +30,30 @@ pub trait SenderExt {
 
 #[async_trait]
 impl ReceiverExt for WsStream {
-    async fn recv_json(&mut self) -> Result<Option<Value>> {
+    async fn recv_json(&mut self, max_payload_size: usize) -> Result<Option<Value>> {
         const TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_millis(500);
 
         let ws_message = match timeout(TIMEOUT, self.next()).await {
             Ok(Some(Ok(v))) => Some(v),
-            Ok(Some(Err(e))) => return Err(e.into()),
+            Ok(Some(Err(e))) => return Err(convert_ws_error(e, max_payload_size)),
             Ok(None) | Err(_) => None,
         };
 
-        convert_ws_message(ws_message)
+        convert_ws_message(ws_message, max_payload_size)
     }
+}
 
-    async fn try_recv_json(&mut self) -> Result<Option<Value>> {
-        convert_ws_message(self.try_next().await.ok().flatten())
+/// Converts an error which occurred while receiving a message.
+///
+/// Tungstenite stops reassembling a fragmented message once it exceeds the
+/// maximum message size the connection was made with, which is reported as a
+/// payload exceeding `max_payload_size`.
+fn convert_ws_error(error: TungsteniteError, max_payload_size: usize) -> Error {
+    match error {
+        TungsteniteError::Capacity(_) => Error::Gateway(GatewayError::PayloadTooLarge {
+            limit: max_payload_size,
+        }),
+        error => error.into(),
     }
 }
 
@@ -69,17 +79,48 @@ impl SenderExt for WsStream {
     }
 }
 
-#[inline]
-pub(crate) fn convert_ws_message(message: Option<Message>) -> Result<Option<Value>> {
+/// Deserializes a message received from the gateway, decompressing it first
+/// if it is binary.
+///
+/// Messages larger than `max_payload_size` bytes, either as received or once
+/// decompressed, result in a [`GatewayError::PayloadTooLarge`]. Decompression
+/// stops as soon as the limit is exceeded.
+pub(crate) fn convert_ws_message(
+    message: Option<Message>,
+    max_payload_size: usize,
+) -> Result<Option<Value>> {
+    let too_large = || {
+        Error::Gateway(GatewayError::PayloadTooLarge {
+            limit: max_payload_size,
+        })
+    };
+
     Ok(match message {
         Some(Message::Binary(bytes)) => {
-            serde_json::from_reader(ZlibDecoder::new(&bytes[..])).map(Some).map_err(|why| {
+            if bytes.len() > max_payload_size {
+                return Err(too_large());
+            }
+
+            let mut payload = Vec::new();
+            ZlibDecoder::new(&bytes[..])
+                .take(max_payload_size as u64 + 1)
+                .read_to_end(&mut payload)?;
+
+            if payload.len() > max_payload_size {
+                return Err(too_large());
+            }
+
+            serde_json::from_slice(&payload).map(Some).map_err(|why| {
                 warn!("Err deserializing bytes: {:?}; bytes: {:?}", why, bytes);
 
                 why
             })?
         },
         Some(Message::Text(payload)) => {
+            if payload.len() > max_payload_size {
+                return Err(too_large());
+            }
+
             serde_json::from_str(&payload).map(Some).map_err(|why| {
                 warn!("Err deserializing text: {:?}; text: {}", why, payload,);
 
@@ -139,12 +180,12 @@ impl StdError for RustlsError {
 
 #[cfg(all(feature = "rustls_backend_marker", not(feature = "native_tls_backend_marker")))]
 #[instrument]
-pub(crate) async fn create_rustls_client(url: Url) -> Result<WsStream> {
+pub(crate) async fn create_rustls_client(url: Url, max_payload_size: usize) -> Result<WsStream> {
     let (stream, _) = async_tungstenite::tokio::connect_async_with_config::<Url>(
         url,
         Some(async_tungstenite::tungstenite::protocol::WebSocketConfig {
-            max_message_size: None,
-            max_frame_size: None,
+            max_message_size: Some(max_payload_size),
+            max_frame_size: Some(max_payload_size),
             max_send_queue: None,
         }),
     )
@@ -156,12 +197,15 @@ pub(crate) async fn create_rustls_client(url: Url) -> Result<WsStream> {
 
 #[cfg(feature = "native_tls_backend_marker")]
 #[instrument]
-pub(crate) async fn create_native_tls_client(url: Url) -> Result<WsStream> {
+pub(crate) async fn create_native_tls_client(
+    url: Url,
+    max_payload_size: usize,
+) -> Result<WsStream> {
     let (stream, _) = async_tungstenite::tokio::connect_async_with_config::<Url>(
         url.into(),
         Some(async_tungstenite::tungstenite::protocol::WebSocketConfig {
-            max_message_size: None,
-            max_frame_size: None,
+            max_message_size: Some(max_payload_size),
+            max_frame_size: Some(max_payload_size),
             max_send_queue: None,
         }),
     )
@@ -169,3 +213,109 @@ pub(crate) async fn create_native_tls_client(url: Url) -> Result<WsStream> {
 
     Ok(stream)
 }
+
+#[cfg(test)]
+mod test {
+    use std::io::{Cursor, Write};
+
+    use async_tungstenite::tungstenite::protocol::{Role, WebSocket, WebSocketConfig};
+    use async_tungstenite::tungstenite::Message;
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use serde_json::json;
+
+    use super::{convert_ws_error, convert_ws_message};
+    use crate::gateway::GatewayError;
+    use crate::internal::prelude::*;
+
+    #[allow(clippy::unwrap_used)]
+    fn compress(data: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn is_too_large(result: Result<Option<Value>>, expected: usize) -> bool {
+        matches!(
+            result,
+            Err(Error::Gateway(GatewayError::PayloadTooLarge {
+                limit,
+            })) if limit == expected
+        )
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_payload_within_limit() {
+        let expected = json!({"op": 0, "d": "a".repeat(1024)});
+        let payload = expected.to_string();
+
+        let message = Message::Binary(compress(payload.as_bytes()));
+        let value = convert_ws_message(Some(message), payload.len()).unwrap();
+        assert_eq!(value, Some(expected.clone()));
+
+        let value = convert_ws_message(Some(Message::Text(payload.clone())), payload.len());
+        assert_eq!(value.unwrap(), Some(expected));
+    }
+
+    #[test]
+    fn test_decompressed_payload_too_large() {
+        // Compresses to a few kilobytes, far below the limit.
+        let payload = format!(r#"{{"op":0,"d":"{}"}}"#, "a".repeat(1024 * 1024));
+        let bytes = compress(payload.as_bytes());
+        assert!(bytes.len() < 64 * 1024);
+
+        let result = convert_ws_message(Some(Message::Binary(bytes)), 64 * 1024);
+        assert!(is_too_large(result, 64 * 1024));
+    }
+
+    #[test]
+    fn test_received_payload_too_large() {
+        let payload = format!(r#"{{"op":0,"d":"{}"}}"#, "a".repeat(1024));
+
+        let result = convert_ws_message(Some(Message::Text(payload.clone())), 1024);
+        assert!(is_too_large(result, 1024));
+
+        let result = convert_ws_message(Some(Message::Binary(payload.into_bytes())), 1024);
+        assert!(is_too_large(result, 1024));
+    }
+
+    /// Encodes an unmasked frame, as sent by the server.
+    fn frame(fin: bool, opcode: u8, payload: &[u8]) -> Vec<u8> {
+        assert!(payload.len() < 126);
+
+        let mut frame = vec![if fin { 0x80 } else { 0 } | opcode, payload.len() as u8];
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    fn fragmented(max_message_size: usize) -> WebSocket<Cursor<Vec<u8>>> {
+        let mut bytes = frame(false, 0x1, br#"{"op":11,"#);
+        bytes.extend(frame(false, 0x0, br#""d":"#));
+        bytes.extend(frame(true, 0x0, b"null}"));
+
+        WebSocket::from_raw_socket(
+            Cursor::new(bytes),
+            Role::Client,
+            Some(WebSocketConfig {
+                max_message_size: Some(max_message_size),
+                max_frame_size: Some(max_message_size),
+                max_send_queue: None,
+            }),
+        )
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_fragmented_payload() {
+        let message = fragmented(64).read_message().unwrap();
+        assert_eq!(message, Message::Text(r#"{"op":11,"d":null}"#.to_string()));
+
+        let value = convert_ws_message(Some(message), 64).unwrap();
+        assert_eq!(value, Some(json!({"op": 11, "d": null})));
+
+        // Each frame is within the limit, but the reassembled payload is not.
+        let error = fragmented(16).read_message().unwrap_err();
+        assert!(is_too_large(Err(convert_ws_error(error, 16)), 16));
+    }
+}
@@ -80,6 +80,7 @@ extern crate bytes_compat as bytes;
 #[macro_use]
 mod internal;
 
+pub mod all;
 pub mod constants;
 pub mod model;
 pub mod prelude;
@@ -98,6 +99,8 @@ pub mod framework;
 pub mod gateway;
 #[cfg(feature = "http")]
 pub mod http;
+#[cfg(feature = "test_utils")]
+pub mod test;
 #[cfg(feature = "utils")]
 pub mod utils;
 
@@ -0,0 +1,105 @@
+use bitflags::__impl_bitflags;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(all(feature = "model", feature = "unstable_discord_api"))]
+use super::ChannelType;
+use crate::internal::prelude::StdResult;
+#[cfg(all(feature = "model", feature = "unstable_discord_api"))]
+use crate::internal::prelude::*;
+use crate::model::utils::U64Visitor;
+#[cfg(all(feature = "model", feature = "unstable_discord_api"))]
+use crate::model::ModelError;
+
+/// Describes extra features of a [`GuildChannel`].
+///
+/// [`GuildChannel`]: super::GuildChannel
+#[derive(Copy, PartialEq, Eq, Clone, PartialOrd, Ord, Hash, Default)]
+pub struct ChannelFlags {
+    pub bits: u64,
+}
+
+__impl_bitflags! {
+    ChannelFlags: u64 {
+        /// The thread is pinned to the top of its parent forum or media channel.
+        PINNED = 1 << 1;
+        /// A tag is required to be specified when creating a thread in a forum
+        /// or media channel.
+        REQUIRE_TAG = 1 << 4;
+        /// The embedded media download options are hidden for media channel
+        /// attachments.
+        HIDE_MEDIA_DOWNLOAD_OPTIONS = 1 << 15;
+    }
+}
+
+#[cfg(all(feature = "model", feature = "unstable_discord_api"))]
+impl ChannelFlags {
+    /// Returns the flags which cannot be set on a channel of the given type,
+    /// whose parent is of the given type if known.
+    ///
+    /// [`Self::PINNED`] can only be set on posts, which are public threads in
+    /// a forum or media channel, [`Self::REQUIRE_TAG`] only on forum and media
+    /// channels, and [`Self::HIDE_MEDIA_DOWNLOAD_OPTIONS`] only on media
+    /// channels.
+    pub(crate) fn unsettable_on(self, kind: ChannelType, parent: Option<ChannelType>) -> Self {
+        let thread_only = |kind| matches!(kind, ChannelType::Forum | ChannelType::Media);
+
+        let mut unsettable = ChannelFlags::empty();
+
+        if kind != ChannelType::PublicThread || !parent.map_or(true, thread_only) {
+            unsettable |= ChannelFlags::PINNED;
+        }
+
+        if !thread_only(kind) {
+            unsettable |= ChannelFlags::REQUIRE_TAG;
+        }
+
+        if kind != ChannelType::Media {
+            unsettable |= ChannelFlags::HIDE_MEDIA_DOWNLOAD_OPTIONS;
+        }
+
+        self & unsettable
+    }
+}
+
+/// Checks that the flags set by a [`CreateChannel`] or [`EditChannel`] builder
+/// can be set on a channel of the given type, whose parent is of the given
+/// type if known.
+///
+/// [`CreateChannel`]: crate::builder::CreateChannel
+/// [`EditChannel`]: crate::builder::EditChannel
+#[cfg(all(feature = "model", feature = "unstable_discord_api"))]
+pub(crate) fn check_channel_flags(
+    map: &JsonMap,
+    kind: ChannelType,
+    parent: Option<ChannelType>,
+) -> Result<()> {
+    let flags = match map.get("flags").and_then(Value::as_u64) {
+        Some(bits) => ChannelFlags::from_bits_truncate(bits),
+        None => return Ok(()),
+    };
+
+    let unsettable = flags.unsettable_on(kind, parent);
+    if !unsettable.is_empty() {
+        return Err(Error::Model(ModelError::InvalidChannelFlags(unsettable)));
+    }
+
+    Ok(())
+}
+
+impl<'de> Deserialize<'de> for ChannelFlags {
+    fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(ChannelFlags::from_bits_truncate(deserializer.deserialize_u64(U64Visitor)?))
+    }
+}
+
+impl Serialize for ChannelFlags {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(self.bits())
+    }
+}
@@ -1,12 +1,18 @@
 #[cfg(feature = "model")]
+use std::collections::HashSet;
+#[cfg(feature = "model")]
 use std::fmt::Write as FmtWrite;
 #[cfg(feature = "model")]
+use std::ops::Range;
+#[cfg(feature = "model")]
 use std::sync::Arc;
 
 #[cfg(feature = "model")]
 use bytes::buf::Buf;
+#[cfg(feature = "collector")]
+use futures::future::FutureExt;
 #[cfg(feature = "model")]
-use futures::stream::Stream;
+use futures::stream::{Stream, StreamExt};
 #[cfg(feature = "model")]
 use reqwest::Url;
 #[cfg(feature = "model")]
@@ -26,6 +32,8 @@ use crate::builder::{
     EditThread,
     GetMessages,
 };
+#[cfg(all(feature = "model", feature = "utils"))]
+use crate::builder::{split_message, SplitStrategy};
 #[cfg(all(feature = "cache", feature = "model"))]
 use crate::cache::Cache;
 #[cfg(feature = "collector")]
@@ -35,10 +43,16 @@ use crate::collector::{
     CollectReaction,
     CollectReply,
     MessageCollectorBuilder,
+    ReactionAction,
+    ReactionCollector,
     ReactionCollectorBuilder,
 };
 #[cfg(feature = "model")]
+use crate::constants::MESSAGE_FETCH_CONCURRENCY;
+#[cfg(feature = "model")]
 use crate::http::{AttachmentType, CacheHttp, Http, Typing};
+#[cfg(all(feature = "model", feature = "unstable_discord_api"))]
+use crate::model::interactions::check_components;
 use crate::model::prelude::*;
 #[cfg(all(feature = "model", feature = "utils"))]
 use crate::utils;
@@ -387,6 +401,9 @@ impl ChannelId {
 
         let map = utils::hashmap_to_json_map(msg.0);
 
+        #[cfg(feature = "unstable_discord_api")]
+        check_components(&map)?;
+
         http.as_ref().edit_message(self.0, message_id.into().0, &Value::Object(map)).await
     }
 
@@ -462,6 +479,90 @@ impl ChannelId {
         })
     }
 
+    /// Gets the messages with the given Ids from the channel, in the same
+    /// order as the Ids.
+    ///
+    /// Messages are fetched concurrently, up to
+    /// [`MESSAGE_FETCH_CONCURRENCY`] at a time. The first message is fetched
+    /// on its own, so that the ratelimits of the route are known before the
+    /// others are requested.
+    ///
+    /// Requires the [Read Message History] permission.
+    ///
+    /// # Examples
+    ///
+    /// Find which of the stored giveaway messages were deleted:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::http::Http;
+    /// # use serenity::model::id::{ChannelId, MessageId};
+    /// #
+    /// # async fn run() {
+    /// # let http = Http::default();
+    /// let giveaways = [MessageId(1), MessageId(2), MessageId(3)];
+    /// let results = ChannelId(7).messages_by_ids(&http, &giveaways).await;
+    ///
+    /// for (id, result) in giveaways.iter().zip(results) {
+    ///     if let Err(why) = result {
+    ///         if why.is_not_found() {
+    ///             println!("Giveaway {} was deleted", id);
+    ///         }
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`MESSAGE_FETCH_CONCURRENCY`]: crate::constants::MESSAGE_FETCH_CONCURRENCY
+    /// [Read Message History]: Permissions::READ_MESSAGE_HISTORY
+    pub async fn messages_by_ids(
+        self,
+        http: impl AsRef<Http>,
+        message_ids: &[MessageId],
+    ) -> Vec<StdResult<Message, MessageFetchError>> {
+        let http = http.as_ref();
+        let fetch = |message_id: MessageId| async move {
+            self.message(http, message_id).await.map_err(MessageFetchError::from)
+        };
+
+        let mut message_ids = message_ids.iter();
+        let mut messages = Vec::with_capacity(message_ids.len());
+
+        if let Some(&message_id) = message_ids.next() {
+            messages.push(fetch(message_id).await);
+        }
+
+        let rest = futures::stream::iter(message_ids)
+            .map(|&message_id| fetch(message_id))
+            .buffered(MESSAGE_FETCH_CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await;
+        messages.extend(rest);
+
+        messages
+    }
+
+    /// Checks whether a message exists in the channel.
+    ///
+    /// Requires the [Read Message History] permission.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission, or the
+    /// message could not be fetched for any other reason than not existing.
+    ///
+    /// [Read Message History]: Permissions::READ_MESSAGE_HISTORY
+    pub async fn message_exists(
+        self,
+        http: impl AsRef<Http>,
+        message_id: impl Into<MessageId>,
+    ) -> Result<bool> {
+        match self.message(http, message_id).await.map_err(MessageFetchError::from) {
+            Ok(_) => Ok(true),
+            Err(MessageFetchError::NotFound) => Ok(false),
+            Err(MessageFetchError::Other(why)) => Err(why),
+        }
+    }
+
     /// Gets messages from the channel.
     ///
     /// Refer to [`GetMessages`] for more information on how to use `builder`.
@@ -537,6 +638,85 @@ impl ChannelId {
         MessagesIter::<H>::stream(http, self)
     }
 
+    /// Searches the channel's messages for ones whose content contains
+    /// `needle`, returning at most `limit` matches, newest first.
+    ///
+    /// Discord's message search endpoints are not available to bots, so this
+    /// is done client-side by streaming over the channel's messages via
+    /// [`Self::messages_iter`]. The search stops as soon as `limit` matches were
+    /// found, or `max_scanned` messages were examined, whichever comes first.
+    /// As messages are fetched 100 at a time, a `max_scanned` of 1000 results
+    /// in at most 10 requests.
+    ///
+    /// The search is case-sensitive. If `needle` is empty, no messages match.
+    ///
+    /// **Note**: Requires the [Read Message History] permission.
+    ///
+    /// # Examples
+    ///
+    /// Find the last message in which someone mentioned "ferris":
+    ///
+    /// ```rust,no_run
+    /// # use serenity::model::id::ChannelId;
+    /// # use serenity::http::Http;
+    /// #
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let channel_id = ChannelId::default();
+    /// # let http = Http::default();
+    /// let found = channel_id.messages_containing(&http, "ferris", 1, 500).await?;
+    ///
+    /// if let Some(found) = found.first() {
+    ///     let text = &found.message.content[found.positions[0].clone()];
+    ///
+    ///     println!("{} said \"{}\"", found.message.author.name, text);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user does not have
+    /// permission to view the channel.
+    ///
+    /// [Read Message History]: Permissions::READ_MESSAGE_HISTORY
+    pub async fn messages_containing(
+        self,
+        http: impl AsRef<Http>,
+        needle: impl AsRef<str>,
+        limit: usize,
+        max_scanned: usize,
+    ) -> Result<Vec<MessageMatch>> {
+        let needle = needle.as_ref();
+        let mut found = Vec::new();
+
+        if needle.is_empty() || limit == 0 {
+            return Ok(found);
+        }
+
+        let mut messages = Box::pin(self.messages_iter(&http).take(max_scanned));
+
+        while let Some(message) = messages.next().await {
+            let message = message?;
+            let positions = match_positions(&message.content, needle);
+
+            if positions.is_empty() {
+                continue;
+            }
+
+            found.push(MessageMatch {
+                message,
+                positions,
+            });
+
+            if found.len() >= limit {
+                break;
+            }
+        }
+
+        Ok(found)
+    }
+
     /// Returns the name of whatever channel this id holds.
     #[cfg(feature = "cache")]
     pub async fn name(self, cache: impl AsRef<Cache>) -> Option<String> {
@@ -654,6 +834,47 @@ impl ChannelId {
         self.send_message(&http, |m| m.content(content)).await
     }
 
+    /// Sends a message with just the given message content in the channel,
+    /// splitting it over multiple messages if it is too long.
+    ///
+    /// The content is split at line breaks, keeping code blocks intact; refer
+    /// to [`SplitStrategy::CodeBlock`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::http::Http;
+    /// # use serenity::model::id::ChannelId;
+    /// #
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let http = Http::default();
+    /// let log = "a line of output\n".repeat(500);
+    ///
+    /// let messages = ChannelId(7).say_chunked(&http, log).await?;
+    /// assert_eq!(messages.len(), 5);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission to
+    /// send a message in this channel. If sending a part fails, the parts
+    /// sent before it are not deleted.
+    ///
+    /// [`SplitStrategy::CodeBlock`]: crate::builder::SplitStrategy::CodeBlock
+    #[cfg(feature = "utils")]
+    pub async fn say_chunked(
+        self,
+        http: impl AsRef<Http>,
+        content: impl std::fmt::Display,
+    ) -> Result<Vec<Message>> {
+        self.send_message_multi(&http, |m| {
+            m.content(content).split_on_overflow(SplitStrategy::CodeBlock)
+        })
+        .await
+    }
+
     /// Sends file(s) along with optional message contents. The filename _must_
     /// be specified.
     ///
@@ -743,6 +964,8 @@ impl ChannelId {
 
         Message::check_content_length(&map)?;
         Message::check_embed_length(&map)?;
+        #[cfg(feature = "unstable_discord_api")]
+        check_components(&map)?;
 
         http.as_ref().send_files(self.0, files, map).await
     }
@@ -779,6 +1002,8 @@ impl ChannelId {
 
         Message::check_content_length(&map)?;
         Message::check_embed_length(&map)?;
+        #[cfg(feature = "unstable_discord_api")]
+        check_components(&map)?;
 
         let message = if msg.2.is_empty() {
             http.as_ref().send_message(self.0, &Value::Object(map)).await?
@@ -795,6 +1020,97 @@ impl ChannelId {
         Ok(message)
     }
 
+    /// Sends a message to the channel like [`Self::send_message`], splitting
+    /// its content over multiple messages if it is too long and
+    /// [`CreateMessage::split_on_overflow`] was set.
+    ///
+    /// Returns the sent messages in order. The embeds, components, files and
+    /// reactions are only added to the first message.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::MessageTooLong`] if the content of the message
+    /// is too long and no [`SplitStrategy`] was set, and a
+    /// [`ModelError::EmbedTooLarge`] if the embeds are too large. Nothing is
+    /// sent in either case.
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission to
+    /// send a message in this channel. If sending a part fails, the parts
+    /// sent before it are not deleted.
+    ///
+    /// [`CreateMessage::split_on_overflow`]: crate::builder::CreateMessage::split_on_overflow
+    /// [`SplitStrategy`]: crate::builder::SplitStrategy
+    #[cfg(feature = "utils")]
+    pub async fn send_message_multi<'a, F>(
+        self,
+        http: impl AsRef<Http>,
+        f: F,
+    ) -> Result<Vec<Message>>
+    where
+        for<'b> F: FnOnce(&'b mut CreateMessage<'a>) -> &'b mut CreateMessage<'a>,
+    {
+        let mut create_message = CreateMessage::default();
+        let msg = f(&mut create_message);
+
+        let map = utils::hashmap_to_json_map(msg.0.clone());
+        let maps = match msg.3 {
+            Some(strategy) => split_message(map, strategy),
+            None => vec![map],
+        };
+
+        for map in &maps {
+            Message::check_content_length(map)?;
+            Message::check_embed_length(map)?;
+            #[cfg(feature = "unstable_discord_api")]
+            check_components(map)?;
+        }
+
+        let mut messages = Vec::with_capacity(maps.len());
+
+        for map in maps {
+            let message = if !messages.is_empty() || msg.2.is_empty() {
+                http.as_ref().send_message(self.0, &Value::Object(map)).await?
+            } else {
+                http.as_ref().send_files(self.0, msg.2.clone(), map).await?
+            };
+
+            if messages.is_empty() {
+                if let Some(reactions) = msg.1.clone() {
+                    for reaction in reactions {
+                        self.create_reaction(&http, message.id, reaction).await?;
+                    }
+                }
+            }
+
+            messages.push(message);
+        }
+
+        Ok(messages)
+    }
+
+    /// Sets the status of a voice channel, or clears it if `status` is [`None`].
+    ///
+    /// **Note**: Requires the [Manage Channels] permission, or the
+    /// [Set Voice Channel Status] permission while connected to the channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission, or if the
+    /// channel is not a voice channel.
+    ///
+    /// [Manage Channels]: Permissions::MANAGE_CHANNELS
+    /// [Set Voice Channel Status]: Permissions::SET_VOICE_CHANNEL_STATUS
+    pub async fn set_voice_status(
+        self,
+        http: impl AsRef<Http>,
+        status: Option<&str>,
+    ) -> Result<()> {
+        let mut map = JsonMap::new();
+        map.insert("status".to_string(), status.map_or(Value::Null, Value::from));
+
+        http.as_ref().edit_voice_status(self.0, &map).await
+    }
+
     /// Starts typing in the channel for an indefinite period of time.
     ///
     /// Returns [`Typing`] that is used to trigger the typing. [`Typing::stop`] must be called
@@ -943,6 +1259,42 @@ impl ChannelId {
     }
 
     /// Returns a future that will await one message sent in this channel.
+    ///
+    /// The returned [`CollectReply`] can be further scoped, e.g. with
+    /// [`CollectReply::author_id`] to only await a message by a certain user.
+    ///
+    /// # Examples
+    ///
+    /// Ask a question and wait up to 30 seconds for the author to answer it:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::client::Context;
+    /// # use serenity::model::channel::Message;
+    /// # use serenity::Result;
+    /// #
+    /// # async fn run(ctx: &Context, msg: &Message) -> Result<()> {
+    /// use std::time::Duration;
+    ///
+    /// msg.channel_id.say(&ctx, "What is your favourite colour?").await?;
+    ///
+    /// let answer = msg
+    ///     .channel_id
+    ///     .await_reply(&ctx)
+    ///     .author_id(msg.author.id)
+    ///     .timeout(Duration::from_secs(30))
+    ///     .await;
+    ///
+    /// match answer {
+    ///     Some(answer) => {
+    ///         msg.channel_id.say(&ctx, format!("{} it is!", answer.content)).await?;
+    ///     },
+    ///     None => {
+    ///         msg.channel_id.say(&ctx, "No answer within 30 seconds.").await?;
+    ///     },
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
     #[cfg(feature = "collector")]
     pub fn await_reply<'a>(
         &self,
@@ -960,7 +1312,37 @@ impl ChannelId {
         MessageCollectorBuilder::new(shard_messenger).channel_id(self.0)
     }
 
-    /// Await a single reaction in this guild.
+    /// Await a single reaction in this channel.
+    ///
+    /// # Examples
+    ///
+    /// Ask a yes/no question and wait for the author to react to it:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::client::Context;
+    /// # use serenity::model::channel::Message;
+    /// # use serenity::Result;
+    /// #
+    /// # async fn run(ctx: &Context, msg: &Message) -> Result<()> {
+    /// use std::time::Duration;
+    ///
+    /// let question = msg.channel_id.say(&ctx, "Continue? React with ✅ or ❌.").await?;
+    /// question.react(&ctx, '✅').await?;
+    /// question.react(&ctx, '❌').await?;
+    ///
+    /// let reaction = msg
+    ///     .channel_id
+    ///     .await_reaction(&ctx)
+    ///     .message_id(question.id)
+    ///     .author_id(msg.author.id)
+    ///     .timeout(Duration::from_secs(30))
+    ///     .await;
+    ///
+    /// let confirmed = reaction
+    ///     .map_or(false, |reaction| reaction.as_inner_ref().emoji.unicode_eq("✅"));
+    /// # Ok(())
+    /// # }
+    /// ```
     #[cfg(feature = "collector")]
     pub fn await_reaction<'a>(
         &self,
@@ -1103,6 +1485,26 @@ impl ChannelId {
         http.as_ref().create_private_thread(self.0, &map).await
     }
 
+    /// Creates a post in a forum or media channel, which is a thread starting
+    /// with the message set by [`CreateThread::message`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission, if the
+    /// channel is not a forum or media channel, or if no message was set.
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn create_forum_post<F>(&self, http: impl AsRef<Http>, f: F) -> Result<GuildChannel>
+    where
+        F: FnOnce(&mut CreateThread) -> &mut CreateThread,
+    {
+        let mut instance = CreateThread::default();
+        f(&mut instance);
+
+        let map = utils::hashmap_to_json_map(instance.0);
+
+        http.as_ref().create_forum_post(self.0, &map).await
+    }
+
     /// Gets the thread members, if this channel is a thread.
     ///
     /// # Errors
@@ -1254,6 +1656,25 @@ impl<'a> From<&'a GuildChannel> for ChannelId {
     }
 }
 
+/// A message found by [`ChannelId::messages_containing`].
+#[derive(Clone, Debug)]
+#[cfg(feature = "model")]
+#[non_exhaustive]
+pub struct MessageMatch {
+    /// The message whose content contains the searched text.
+    pub message: Message,
+    /// The byte ranges of each non-overlapping occurrence of the searched text
+    /// in the message's content.
+    pub positions: Vec<Range<usize>>,
+}
+
+/// Returns the byte ranges of each non-overlapping occurrence of `needle` in
+/// `content`.
+#[cfg(feature = "model")]
+fn match_positions(content: &str, needle: &str) -> Vec<Range<usize>> {
+    content.match_indices(needle).map(|(start, matched)| start..start + matched.len()).collect()
+}
+
 /// A helper class returned by [`ChannelId::messages_iter`]
 #[derive(Clone, Debug)]
 #[cfg(feature = "model")]
@@ -1366,3 +1787,568 @@ impl<H: AsRef<Http>> MessagesIter<H> {
         })
     }
 }
+
+/// A helper class returned by [`Message::reaction_users_all`] and
+/// [`Message::reaction_users_all_live`].
+#[cfg(feature = "model")]
+pub struct ReactionUsersIter<H: AsRef<Http>> {
+    http: H,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    reaction_type: ReactionType,
+    buffer: Vec<User>,
+    after: Option<UserId>,
+    tried_fetch: bool,
+    seen: HashSet<UserId>,
+    #[cfg(feature = "collector")]
+    live: Option<ReactionCollector>,
+    #[cfg(feature = "collector")]
+    live_buffer: Vec<(UserId, Option<User>)>,
+}
+
+#[cfg(feature = "model")]
+impl<H: AsRef<Http>> ReactionUsersIter<H> {
+    fn new(
+        http: H,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        reaction_type: ReactionType,
+    ) -> ReactionUsersIter<H> {
+        ReactionUsersIter {
+            http,
+            channel_id,
+            message_id,
+            reaction_type,
+            buffer: Vec::new(),
+            after: None,
+            tried_fetch: false,
+            seen: HashSet::new(),
+            #[cfg(feature = "collector")]
+            live: None,
+            #[cfg(feature = "collector")]
+            live_buffer: Vec::new(),
+        }
+    }
+
+    /// Fills the `self.buffer` cache with the next page of [`User`]s.
+    ///
+    /// This drops any users that were currently in the buffer, so it should
+    /// only be called when `self.buffer` is empty. Additionally, this updates
+    /// `self.after` so that the next call continues after the last user of the
+    /// page. If there are no more users to be fetched, then this marks
+    /// `self.after` as [`None`], indicating that no more calls ought to be made.
+    async fn refresh(&mut self) -> Result<()> {
+        // Number of users to fetch.
+        let grab_size = 100;
+
+        let page = self
+            .channel_id
+            .reaction_users(
+                &self.http,
+                self.message_id,
+                self.reaction_type.clone(),
+                Some(grab_size),
+                self.after,
+            )
+            .await?;
+
+        self.push_page(page, grab_size);
+
+        Ok(())
+    }
+
+    /// Replaces the buffer with the users of a fetched `page`, skipping users
+    /// which were already streamed.
+    fn push_page(&mut self, page: Vec<User>, grab_size: u8) {
+        // Get the last user. If shorter than the grab size, there are no more
+        // results anyway.
+        self.after = page.get(grab_size as usize - 1).map(|user| user.id);
+
+        let seen = &mut self.seen;
+        self.buffer = page.into_iter().filter(|user| seen.insert(user.id)).collect();
+
+        // Reverse to optimize pop()
+        self.buffer.reverse();
+
+        self.tried_fetch = true;
+    }
+
+    /// Queues the user of a `reaction` received while paginating, unless they
+    /// were already streamed.
+    #[cfg(feature = "collector")]
+    fn push_live(&mut self, reaction: &Reaction) {
+        let user_id = match reaction.user_id {
+            Some(user_id) => user_id,
+            None => return,
+        };
+
+        if self.seen.insert(user_id) {
+            let user = reaction.member.as_ref().and_then(|member| member.user.clone());
+
+            self.live_buffer.push((user_id, user));
+        }
+    }
+
+    /// Takes the reactions the live collector received so far, without
+    /// waiting for more.
+    #[cfg(feature = "collector")]
+    fn drain_live(&mut self) {
+        let mut added = Vec::new();
+
+        if let Some(collector) = self.live.as_mut() {
+            while let Some(Some(action)) = collector.next().now_or_never() {
+                if let ReactionAction::Added(reaction) = &*action {
+                    added.push(Arc::clone(reaction));
+                }
+            }
+        }
+
+        for reaction in added {
+            self.push_live(&reaction);
+        }
+    }
+
+    /// Streams over all the users who reacted to a message with the given
+    /// `reaction_type`.
+    ///
+    /// This is accomplished and equivalent to repeated calls to
+    /// [`ChannelId::reaction_users`], continuing after the last user of each
+    /// page. A buffer of at most 100 users is used to reduce the number of
+    /// calls necessary.
+    ///
+    /// Each user is streamed only once, even if reactions are removed and
+    /// added again while paginating.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::model::id::{ChannelId, MessageId};
+    /// # use serenity::http::Http;
+    /// #
+    /// # async fn run() {
+    /// # let channel_id = ChannelId::default();
+    /// # let message_id = MessageId::default();
+    /// # let ctx = Http::default();
+    /// use serenity::futures::StreamExt;
+    /// use serenity::model::channel::ReactionUsersIter;
+    ///
+    /// let mut users = ReactionUsersIter::stream(&ctx, channel_id, message_id, '🎉').boxed();
+    /// while let Some(user_result) = users.next().await {
+    ///     match user_result {
+    ///         Ok(user) => println!("{} entered the giveaway", user.name),
+    ///         Err(error) => eprintln!("Uh oh! Error: {}", error),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn stream(
+        http: H,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        reaction_type: impl Into<ReactionType>,
+    ) -> impl Stream<Item = Result<User>> {
+        ReactionUsersIter::new(http, channel_id, message_id, reaction_type.into()).into_stream()
+    }
+
+    /// Streams over all the users who reacted to a message with the given
+    /// `reaction_type`, like [`Self::stream`], including users who react while
+    /// the stream paginates.
+    ///
+    /// A reaction collector is started before the first page is fetched. The
+    /// users it received are merged into the stream after each page, and it is
+    /// stopped once the last page was fetched.
+    #[cfg(feature = "collector")]
+    pub async fn stream_live(
+        http: H,
+        shard_messenger: impl AsRef<ShardMessenger>,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        reaction_type: impl Into<ReactionType>,
+    ) -> impl Stream<Item = Result<User>> {
+        let reaction_type = reaction_type.into();
+
+        let collector = ReactionCollectorBuilder::new(shard_messenger)
+            .channel_id(channel_id.0)
+            .message_id(message_id.0)
+            .emoji(reaction_type.clone())
+            .await;
+
+        let mut init_state = ReactionUsersIter::new(http, channel_id, message_id, reaction_type);
+        init_state.live = Some(collector);
+
+        init_state.into_stream()
+    }
+
+    fn into_stream(self) -> impl Stream<Item = Result<User>> {
+        futures::stream::unfold(self, |mut state| async move {
+            loop {
+                if let Some(user) = state.buffer.pop() {
+                    return Some((Ok(user), state));
+                }
+
+                #[cfg(feature = "collector")]
+                {
+                    if let Some((user_id, user)) = state.live_buffer.pop() {
+                        let user = match user {
+                            Some(user) => Ok(user),
+                            None => state.http.as_ref().get_user(user_id.0).await,
+                        };
+
+                        return Some((user, state));
+                    }
+                }
+
+                if state.after.is_some() || !state.tried_fetch {
+                    if let Err(error) = state.refresh().await {
+                        return Some((Err(error), state));
+                    }
+
+                    #[cfg(feature = "collector")]
+                    state.drain_live();
+
+                    continue;
+                }
+
+                #[cfg(feature = "collector")]
+                {
+                    if state.live.is_some() {
+                        state.drain_live();
+                        // Dropping the collector removes its filter from the shard.
+                        state.live = None;
+
+                        continue;
+                    }
+                }
+
+                return None;
+            }
+        })
+    }
+}
+
+#[cfg(all(test, feature = "model"))]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use serde_json::{json, Value};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::{match_positions, ReactionUsersIter};
+    use crate::http::{Http, HttpBuilder};
+    use crate::model::prelude::*;
+
+    /// The messages of the channel served by [`mock_channel`].
+    struct MockChannel {
+        /// The Id of the newest message. Messages `1..=newest` exist.
+        newest: u64,
+        /// Messages which were deleted.
+        deleted: &'static [u64],
+        /// The path of every request, along with the number of requests which
+        /// were being handled once it was received, including itself.
+        requests: Mutex<Vec<(String, usize)>>,
+        in_flight: AtomicUsize,
+    }
+
+    impl MockChannel {
+        fn message(id: u64) -> Value {
+            json!({
+                "id": id.to_string(),
+                "attachments": [],
+                "author": {
+                    "id": "210",
+                    "username": "Meaning of Life",
+                    "discriminator": "0042",
+                    "avatar": null,
+                },
+                "channel_id": "1",
+                "content": if id == 1 { "ferris" } else { "" },
+                "edited_timestamp": null,
+                "embeds": [],
+                "type": 0,
+                "mention_everyone": false,
+                "mention_roles": [],
+                "mentions": [],
+                "pinned": false,
+                "timestamp": "2021-08-27T16:13:33.482000+00:00",
+                "tts": false,
+            })
+        }
+
+        fn exists(&self, id: u64) -> bool {
+            (1..=self.newest).contains(&id) && !self.deleted.contains(&id)
+        }
+
+        /// Responds to a request for `path` with a status and body.
+        fn respond(&self, path: &str) -> (&'static str, Value) {
+            let path = path.trim_start_matches("/api/v9/channels/1/messages");
+            let query = |name: &str| {
+                path.split(|c| c == '?' || c == '&')
+                    .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+                    .and_then(|value| value.parse::<u64>().ok())
+            };
+
+            if let Some(id) = path.strip_prefix('/') {
+                match id.parse() {
+                    Ok(id) if self.exists(id) => ("200 OK", Self::message(id)),
+                    _ => ("404 Not Found", json!({"code": 10008, "message": "Unknown Message"})),
+                }
+            } else {
+                let before = query("before").unwrap_or(self.newest + 1);
+                let limit = query("limit").unwrap_or(50);
+                let messages = (1..before)
+                    .rev()
+                    .filter(|&id| self.exists(id))
+                    .take(limit as usize)
+                    .map(Self::message)
+                    .collect();
+
+                ("200 OK", Value::Array(messages))
+            }
+        }
+
+        fn requests(&self) -> Vec<(String, usize)> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    /// Starts a server standing in for the Discord API, serving the messages
+    /// of channel 1. Every request takes a few milliseconds to respond to.
+    #[allow(clippy::unwrap_used)]
+    async fn mock_channel(newest: u64, deleted: &'static [u64]) -> (Http, Arc<MockChannel>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let channel = Arc::new(MockChannel {
+            newest,
+            deleted,
+            requests: Mutex::new(Vec::new()),
+            in_flight: AtomicUsize::new(0),
+        });
+
+        let mock = Arc::clone(&channel);
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let channel = Arc::clone(&mock);
+
+                tokio::spawn(async move {
+                    let mut head = Vec::new();
+                    let mut buf = [0; 1024];
+
+                    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+                        match socket.read(&mut buf).await.unwrap() {
+                            0 => break,
+                            n => head.extend_from_slice(&buf[..n]),
+                        }
+                    }
+
+                    let head = String::from_utf8_lossy(&head);
+                    let path = head.split(' ').nth(1).unwrap_or_default().to_string();
+
+                    let in_flight = channel.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    channel.requests.lock().unwrap().push((path.clone(), in_flight));
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    channel.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                    let (status, body) = channel.respond(&path);
+                    let body = body.to_string();
+                    let response = format!(
+                        "HTTP/1.1 {}\r\ncontent-type: application/json\r\n\
+                         content-length: {}\r\nconnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    socket.write_all(response.as_bytes()).await.unwrap();
+                });
+            }
+        });
+
+        let builder = HttpBuilder::new("token");
+        #[cfg(feature = "unstable_discord_api")]
+        let builder = builder.application_id(1);
+
+        let http = builder
+            .proxy(format!("http://{}", addr))
+            .unwrap()
+            .ratelimiter_disabled(true)
+            .await
+            .unwrap();
+
+        (http, channel)
+    }
+
+    fn users(ids: impl IntoIterator<Item = u64>) -> Vec<User> {
+        ids.into_iter()
+            .map(|id| User {
+                id: UserId(id),
+                ..User::default()
+            })
+            .collect()
+    }
+
+    fn reaction_users_iter() -> ReactionUsersIter<Http> {
+        ReactionUsersIter::new(
+            Http::default(),
+            ChannelId(1),
+            MessageId(2),
+            ReactionType::from('🎉'),
+        )
+    }
+
+    fn drain(iter: &mut ReactionUsersIter<Http>) -> Vec<u64> {
+        std::iter::from_fn(|| iter.buffer.pop()).map(|user| user.id.0).collect()
+    }
+
+    #[test]
+    fn test_match_positions() {
+        assert_eq!(match_positions("ferris the crab", "ferris"), vec![0..6]);
+        assert_eq!(match_positions("crab, crab, crab", "crab"), vec![0..4, 6..10, 12..16]);
+        assert_eq!(match_positions("aaaa", "aa"), vec![0..2, 2..4]);
+        assert_eq!(match_positions("Ferris", "ferris"), vec![]);
+        assert_eq!(match_positions("", "ferris"), vec![]);
+        // Positions are byte offsets, usable to slice the content.
+        let content = "🦀 ferris";
+        let position = match_positions(content, "ferris").remove(0);
+        assert_eq!(position, 5..11);
+        assert_eq!(&content[position], "ferris");
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_messages_containing_stops_at_scan_cap() {
+        // Only the oldest message contains the needle.
+        let (http, channel) = mock_channel(300, &[]).await;
+
+        let found = ChannelId(1).messages_containing(&http, "ferris", 1, 150).await.unwrap();
+        assert!(found.is_empty());
+        // 100 messages of the first page, and 50 of the second.
+        assert_eq!(channel.requests().len(), 2);
+
+        let found = ChannelId(1).messages_containing(&http, "ferris", 1, 300).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].message.id, MessageId(1));
+    }
+
+    #[tokio::test]
+    async fn test_messages_by_ids_order() {
+        let (http, _) = mock_channel(20, &[3, 12]).await;
+
+        let ids = [12, 1, 3, 20, 7, 21].iter().map(|&id| MessageId(id)).collect::<Vec<_>>();
+        let results = ChannelId(1).messages_by_ids(&http, &ids).await;
+
+        let found = results
+            .iter()
+            .map(|result| match result {
+                Ok(message) => Some(message.id),
+                Err(why) => {
+                    assert!(why.is_not_found(), "{:?}", why);
+                    None
+                },
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec![None, Some(MessageId(1)), None, Some(MessageId(20)), Some(
+            MessageId(7)
+        ), None]);
+    }
+
+    #[tokio::test]
+    async fn test_messages_by_ids_concurrency() {
+        let (http, channel) = mock_channel(20, &[]).await;
+
+        let ids = (1..=20).map(MessageId).collect::<Vec<_>>();
+        let results = ChannelId(1).messages_by_ids(&http, &ids).await;
+        assert!(results.iter().all(|result| result.is_ok()));
+
+        let requests = channel.requests();
+        assert_eq!(requests.len(), 20);
+        // The first message is fetched on its own.
+        assert_eq!(requests[0].1, 1);
+        assert_eq!(requests[1].1, 1);
+
+        let most = requests.iter().map(|(_, in_flight)| *in_flight).max();
+        assert!(most > Some(1));
+        assert!(most <= Some(crate::constants::MESSAGE_FETCH_CONCURRENCY));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_message_exists() {
+        let (http, _) = mock_channel(5, &[2]).await;
+
+        assert!(ChannelId(1).message_exists(&http, 1).await.unwrap());
+        assert!(!ChannelId(1).message_exists(&http, 2).await.unwrap());
+    }
+
+    #[test]
+    fn test_reaction_users_page_boundaries() {
+        let mut iter = reaction_users_iter();
+
+        iter.push_page(users(1..=100), 100);
+        assert_eq!(iter.after, Some(UserId(100)));
+        assert_eq!(drain(&mut iter), (1..=100).collect::<Vec<_>>());
+
+        // A short page is the last one.
+        iter.push_page(users(101..=150), 100);
+        assert_eq!(iter.after, None);
+        assert_eq!(drain(&mut iter), (101..=150).collect::<Vec<_>>());
+
+        // An exactly full last page is followed by an empty one.
+        let mut iter = reaction_users_iter();
+        iter.push_page(users(1..=100), 100);
+        drain(&mut iter);
+        iter.push_page(Vec::new(), 100);
+        assert_eq!(iter.after, None);
+        assert!(iter.buffer.is_empty());
+    }
+
+    #[test]
+    fn test_reaction_users_dedup() {
+        let mut iter = reaction_users_iter();
+
+        iter.push_page(users(1..=100), 100);
+        drain(&mut iter);
+
+        // Users who re-reacted show up again, but only new users are streamed.
+        iter.push_page(users((98..=100).chain(101..=102)), 100);
+        assert_eq!(drain(&mut iter), vec![101, 102]);
+
+        // A page of only duplicates still moves the cursor along.
+        let mut iter = reaction_users_iter();
+        iter.push_page(users(1..=100), 100);
+        drain(&mut iter);
+        iter.push_page(users(1..=100), 100);
+        assert_eq!(iter.after, Some(UserId(100)));
+        assert!(iter.buffer.is_empty());
+    }
+
+    #[cfg(feature = "collector")]
+    #[test]
+    fn test_reaction_users_live_dedup() {
+        let reaction = |user_id: u64| -> Reaction {
+            serde_json::from_value(json!({
+                "channel_id": "1",
+                "message_id": "2",
+                "user_id": user_id.to_string(),
+                "emoji": {"name": "🎉"},
+            }))
+            .unwrap()
+        };
+
+        let mut iter = reaction_users_iter();
+        iter.push_page(users(1..=100), 100);
+        drain(&mut iter);
+
+        // Reactions of already streamed users are dropped.
+        iter.push_live(&reaction(50));
+        iter.push_live(&reaction(150));
+        iter.push_live(&reaction(150));
+        assert_eq!(iter.live_buffer, vec![(UserId(150), None)]);
+
+        // Live users are not streamed again by later pages.
+        iter.push_page(users(101..=151), 100);
+        assert_eq!(iter.after, None);
+        assert!(!drain(&mut iter).contains(&150));
+    }
+}
@@ -4,8 +4,6 @@ use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
-#[cfg(feature = "cache")]
-use futures::stream::StreamExt;
 
 #[cfg(feature = "model")]
 use crate::builder::EditChannel;
@@ -138,6 +136,18 @@ pub struct GuildChannel {
     ///
     /// **Note**: It can currently only be set to 60, 1440, 4320, 10080.
     pub default_auto_archive_duration: Option<u64>,
+    /// Extra features of the channel.
+    #[serde(default)]
+    pub flags: ChannelFlags,
+    /// The default layout of the posts in the channel.
+    ///
+    /// **Note**: This is only available on forum channels.
+    pub default_forum_layout: Option<ForumLayout>,
+    /// The default order in which the posts in the channel are sorted.
+    ///
+    /// **Note**: This is only available on forum and media channels, and is
+    /// [`None`] if no order has been set.
+    pub default_sort_order: Option<SortOrder>,
 }
 
 #[cfg(feature = "model")]
@@ -148,6 +158,27 @@ impl GuildChannel {
         matches!(self.kind, ChannelType::Text | ChannelType::News)
     }
 
+    /// Whether or not this channel only contains threads, meaning that it is
+    /// a forum or media channel, in which messages can only be sent by
+    /// creating a post with [`Self::create_forum_post`].
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn is_thread_only(&self) -> bool {
+        matches!(self.kind, ChannelType::Forum | ChannelType::Media)
+    }
+
+    /// Whether posts in this forum or media channel must have a tag applied
+    /// with [`CreateThread::applied_tags`] when creating them.
+    #[inline]
+    pub fn requires_tag(&self) -> bool {
+        self.flags.contains(ChannelFlags::REQUIRE_TAG)
+    }
+
+    /// Returns a link referencing this channel. When clicked, users will jump
+    /// to the channel.
+    pub fn link(&self) -> String {
+        format!("https://discord.com/channels/{}/{}", self.guild_id.0, self.id.0)
+    }
+
     /// Broadcasts to the channel that the current user is typing.
     ///
     /// For bots, this is a good indicator for long-running commands.
@@ -415,6 +446,11 @@ impl GuildChannel {
     /// If the `cache` is enabled, returns [ModelError::InvalidPermissions]
     /// if the current user lacks permission to edit the channel.
     ///
+    /// Returns a [`ModelError::InvalidChannelFlags`] if [`EditChannel::flags`]
+    /// sets flags which the channel does not support. Whether the parent of a
+    /// thread is a forum or media channel is only checked if the `cache` is
+    /// enabled.
+    ///
     /// Otherwise returns [`Error::Http`] if the current user lacks permission.
     #[cfg(feature = "utils")]
     pub async fn edit<F>(&mut self, cache_http: impl CacheHttp, f: F) -> Result<()>
@@ -442,11 +478,55 @@ impl GuildChannel {
         f(&mut edit_channel);
         let edited = serenity_utils::hashmap_to_json_map(edit_channel.0);
 
+        #[cfg(feature = "unstable_discord_api")]
+        {
+            #[cfg(feature = "cache")]
+            let parent = match (cache_http.cache(), self.category_id) {
+                (Some(cache), Some(parent_id)) => {
+                    cache.guild_channel(parent_id).await.map(|parent| parent.kind)
+                },
+                _ => None,
+            };
+            #[cfg(not(feature = "cache"))]
+            let parent = None;
+
+            check_channel_flags(&edited, self.kind, parent)?;
+        }
+
         *self = cache_http.http().edit_channel(self.id.0, &edited).await?;
 
         Ok(())
     }
 
+    /// Replaces the channel's permission overwrites with the ones of its
+    /// parent category, so that its permissions are synced with it.
+    ///
+    /// **Note**: Requires the [Manage Channels] permission.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::ChannelNotFound`] if the channel is not in a
+    /// category, or a [`ModelError::InvalidChannelType`] if its parent is not
+    /// a category.
+    ///
+    /// Otherwise returns the same errors as [`Self::edit`].
+    ///
+    /// [Manage Channels]: Permissions::MANAGE_CHANNELS
+    #[cfg(feature = "utils")]
+    pub async fn sync_permissions_with_category(
+        &mut self,
+        cache_http: impl CacheHttp,
+    ) -> Result<()> {
+        let category_id = self.category_id.ok_or(ModelError::ChannelNotFound)?;
+
+        let overwrites = match category_id.to_channel(&cache_http).await? {
+            Channel::Category(category) => category.permission_overwrites,
+            _ => return Err(Error::from(ModelError::InvalidChannelType)),
+        };
+
+        self.edit(cache_http, |c| c.permissions(overwrites)).await
+    }
+
     /// Edits a [`Message`] in the channel given its Id.
     ///
     /// Message editing preserves all unchanged message data.
@@ -833,6 +913,22 @@ impl GuildChannel {
         guild.role_permissions_in(self, role)
     }
 
+    /// Checks whether the channel's permissions are synced with its parent
+    /// category, i.e. whether its permission overwrites are exactly the
+    /// category's, regardless of their order.
+    ///
+    /// Returns [`None`] if the channel is not in a category, or the category
+    /// could not be found in the [`Cache`].
+    #[cfg(feature = "cache")]
+    pub async fn permissions_synced(&self, cache: impl AsRef<Cache>) -> Option<bool> {
+        let category = cache.as_ref().category(self.category_id?).await?;
+
+        Some(super::permission_overwrites_match(
+            &self.permission_overwrites,
+            &category.permission_overwrites,
+        ))
+    }
+
     /// Pins a [`Message`] to the channel.
     ///
     /// **Note**: Requires the [Manage Messages] permission.
@@ -1075,47 +1171,217 @@ impl GuildChannel {
     /// Other [`ChannelType`]s lack the concept of [`Member`]s and
     /// will return: [`ModelError::InvalidChannelType`].
     #[cfg(feature = "cache")]
-    #[inline]
+    #[deprecated(note = "use `connected_members`, `members_with_access` or `thread_members`")]
     pub async fn members(&self, cache: impl AsRef<Cache>) -> Result<Vec<Member>> {
+        match self.kind {
+            ChannelType::Voice | ChannelType::Stage => self.connected_members(cache).await,
+            _ => self.members_with_access(cache, None).await,
+        }
+    }
+
+    /// Retrieves the [`Member`]s connected to a [`ChannelType::Voice`] or
+    /// [`ChannelType::Stage`] channel, from the cache.
+    ///
+    /// Members whose data has not been cached are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModelError::InvalidChannelType`] if the channel is not a voice
+    /// or stage channel, and [`ModelError::GuildNotFound`] if the channel's
+    /// guild is not cached.
+    #[cfg(feature = "cache")]
+    pub async fn connected_members(&self, cache: impl AsRef<Cache>) -> Result<Vec<Member>> {
+        if !matches!(self.kind, ChannelType::Voice | ChannelType::Stage) {
+            return Err(Error::from(ModelError::InvalidChannelType));
+        }
+
         let cache = cache.as_ref();
-        let guild = cache.guild(self.guild_id).await.ok_or(ModelError::GuildNotFound)?;
 
-        match self.kind {
-            ChannelType::Voice | ChannelType::Stage => Ok(guild
-                .voice_states
-                .values()
-                .filter_map(|v| {
-                    v.channel_id.and_then(|c| {
-                        if c == self.id {
-                            guild.members.get(&v.user_id).cloned()
-                        } else {
-                            None
-                        }
-                    })
-                })
-                .collect()),
-            ChannelType::News | ChannelType::Text => {
-                Ok(futures::stream::iter(guild.members.iter())
-                    .filter_map(|e| async move {
-                        if self
-                            .permissions_for_user(cache, e.0)
-                            .await
-                            .map(|p| p.contains(Permissions::READ_MESSAGES))
-                            .unwrap_or(false)
-                        {
-                            Some(e.1.clone())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect::<Vec<Member>>()
-                    .await)
-            },
-            _ => Err(Error::from(ModelError::InvalidChannelType)),
+        let user_ids = match cache.voice_channel_users.read().await.get(&self.guild_id) {
+            Some(channels) => channels.get(&self.id).cloned().unwrap_or_default(),
+            None => return Err(Error::from(ModelError::GuildNotFound)),
+        };
+
+        let mut members = Vec::with_capacity(user_ids.len());
+
+        for user_id in user_ids {
+            if let Some(member) = cache.member(self.guild_id, user_id).await {
+                members.push(member);
+            }
+        }
+
+        Ok(members)
+    }
+
+    /// Checks whether a user may join this [`ChannelType::Voice`] or
+    /// [`ChannelType::Stage`] channel, based on the cached guild.
+    ///
+    /// The user must be able to see the channel and have the [Connect]
+    /// permission. A channel with a user limit is full once that many users
+    /// are connected to it, unless the user is already one of them or has the
+    /// [Move Members] permission.
+    ///
+    /// In voice channels, the user must also have the [Speak] permission. In
+    /// stage channels, users join as listeners instead, so they must either be
+    /// able to unsuppress themselves via [Mute Members], or to request to
+    /// speak via [Request To Speak].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`VoiceJoinError`] describing why the user cannot join.
+    ///
+    /// [Connect]: Permissions::CONNECT
+    /// [Move Members]: Permissions::MOVE_MEMBERS
+    /// [Mute Members]: Permissions::MUTE_MEMBERS
+    /// [Request To Speak]: Permissions::REQUEST_TO_SPEAK
+    /// [Speak]: Permissions::SPEAK
+    #[cfg(feature = "cache")]
+    pub async fn user_can_connect(
+        &self,
+        cache: impl AsRef<Cache>,
+        user_id: impl Into<UserId>,
+    ) -> StdResult<(), VoiceJoinError> {
+        if !matches!(self.kind, ChannelType::Voice | ChannelType::Stage) {
+            return Err(VoiceJoinError::InvalidChannelType);
+        }
+
+        let cache = cache.as_ref();
+        let user_id = user_id.into();
+
+        let permissions = {
+            let guilds = cache.guilds.read().await;
+            let guild = guilds.get(&self.guild_id).ok_or(VoiceJoinError::NotCached)?;
+            let members = cache.members.read().await;
+            let member = members
+                .get(&self.guild_id)
+                .and_then(|members| members.get(&user_id))
+                .ok_or(VoiceJoinError::NotCached)?;
+
+            Guild::_user_permissions_in(
+                self,
+                user_id,
+                &member.roles,
+                &guild.roles,
+                guild.owner_id,
+                guild.id,
+            )
+            .map_err(|_| VoiceJoinError::NotCached)?
+        };
+
+        let (connected, is_connected) = match cache
+            .voice_channel_users
+            .read()
+            .await
+            .get(&self.guild_id)
+            .and_then(|channels| channels.get(&self.id))
+        {
+            Some(user_ids) => (user_ids.len(), user_ids.contains(&user_id)),
+            None => (0, false),
+        };
+
+        self._user_can_connect(permissions, connected, is_connected)
+    }
+
+    #[cfg(feature = "cache")]
+    fn _user_can_connect(
+        &self,
+        permissions: Permissions,
+        connected: usize,
+        is_connected: bool,
+    ) -> StdResult<(), VoiceJoinError> {
+        if !permissions.read_messages() {
+            return Err(VoiceJoinError::NotViewable);
+        }
+
+        if !permissions.connect() {
+            return Err(VoiceJoinError::MissingConnect);
+        }
+
+        if let Some(limit) = self.user_limit.filter(|&limit| limit > 0) {
+            if !is_connected && !permissions.move_members() && connected as u64 >= limit {
+                return Err(VoiceJoinError::Full);
+            }
+        }
+
+        let can_speak = match self.kind {
+            ChannelType::Stage => permissions.mute_members() || permissions.request_to_speak(),
+            _ => permissions.speak(),
+        };
+
+        if !can_speak {
+            return Err(VoiceJoinError::MissingSpeak);
+        }
+
+        Ok(())
+    }
+
+    /// Retrieves the [`Member`]s that can read a [`ChannelType::Text`] or
+    /// [`ChannelType::News`] channel, from the cache.
+    ///
+    /// This computes the permissions of every cached member of the guild in
+    /// the channel while holding the cache's read locks, which can take a
+    /// while for large guilds. Pass a `limit` to stop once that many members
+    /// were found.
+    ///
+    /// For threads, use [`Self::thread_members`] instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModelError::InvalidChannelType`] if the channel is not a text
+    /// or news channel, and [`ModelError::GuildNotFound`] if the channel's
+    /// guild is not cached.
+    #[cfg(feature = "cache")]
+    pub async fn members_with_access(
+        &self,
+        cache: impl AsRef<Cache>,
+        limit: Option<usize>,
+    ) -> Result<Vec<Member>> {
+        if !matches!(self.kind, ChannelType::Text | ChannelType::News) {
+            return Err(Error::from(ModelError::InvalidChannelType));
         }
+
+        let cache = cache.as_ref();
+        let guilds = cache.guilds.read().await;
+        let guild = guilds.get(&self.guild_id).ok_or(ModelError::GuildNotFound)?;
+        let members = cache.members.read().await;
+
+        let members = match members.get(&self.guild_id) {
+            Some(members) => members,
+            None => return Ok(Vec::new()),
+        };
+
+        Ok(members
+            .values()
+            .filter(|member| {
+                Guild::_user_permissions_in(
+                    self,
+                    member.user.id,
+                    &member.roles,
+                    &guild.roles,
+                    guild.owner_id,
+                    guild.id,
+                )
+                .map_or(false, |permissions| permissions.read_messages())
+            })
+            .take(limit.unwrap_or(usize::MAX))
+            .map(|member| member.to_member(self.guild_id))
+            .collect())
+    }
+
+    /// Retrieves the members of a thread.
+    ///
+    /// This is not served from the cache, and requires the privileged
+    /// `GUILD_MEMBERS` intent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the channel is not a thread.
+    #[inline]
+    pub async fn thread_members(&self, http: impl AsRef<Http>) -> Result<Vec<ThreadMember>> {
+        self.id.get_thread_members(http).await
     }
 
-    /// Returns a future that will await one message by this guild channel.
+    /// Returns a future that will await one message sent in this guild channel.
     #[cfg(feature = "collector")]
     pub fn await_reply<'a>(
         &self,
@@ -1124,7 +1390,7 @@ impl GuildChannel {
         CollectReply::new(shard_messenger).channel_id(self.id.0)
     }
 
-    /// Returns a stream builder which can be awaited to obtain a stream of messages sent by this guild channel.
+    /// Returns a stream builder which can be awaited to obtain a stream of messages sent in this guild channel.
     #[cfg(feature = "collector")]
     pub fn await_replies<'a>(
         &self,
@@ -1133,7 +1399,7 @@ impl GuildChannel {
         MessageCollectorBuilder::new(shard_messenger).channel_id(self.id.0)
     }
 
-    /// Await a single reaction by this guild channel.
+    /// Await a single reaction in this guild channel.
     #[cfg(feature = "collector")]
     pub fn await_reaction<'a>(
         &self,
@@ -1142,7 +1408,7 @@ impl GuildChannel {
         CollectReaction::new(shard_messenger).channel_id(self.id.0)
     }
 
-    /// Returns a stream builder which can be awaited to obtain a stream of reactions sent by this guild channel.
+    /// Returns a stream builder which can be awaited to obtain a stream of reactions sent in this guild channel.
     #[cfg(feature = "collector")]
     pub fn await_reactions<'a>(
         &self,
@@ -1291,6 +1557,42 @@ impl GuildChannel {
         self.id.create_public_thread(http, message_id, f).await
     }
 
+    /// Creates a post in this forum or media channel, which is a thread
+    /// starting with the message set by [`CreateThread::message`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::InvalidChannelType`] if the channel is not
+    /// [thread-only], or a [`ModelError::ForumTagRequired`] if the channel
+    /// [requires a tag] and none was applied with
+    /// [`CreateThread::applied_tags`].
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission, or if no
+    /// message was set.
+    ///
+    /// [thread-only]: Self::is_thread_only
+    /// [requires a tag]: Self::requires_tag
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn create_forum_post<F>(&self, http: impl AsRef<Http>, f: F) -> Result<GuildChannel>
+    where
+        F: FnOnce(&mut CreateThread) -> &mut CreateThread,
+    {
+        if !self.is_thread_only() {
+            return Err(Error::from(ModelError::InvalidChannelType));
+        }
+
+        let mut instance = CreateThread::default();
+        f(&mut instance);
+
+        if self.requires_tag() && !instance.has_applied_tags() {
+            return Err(Error::from(ModelError::ForumTagRequired));
+        }
+
+        let map = serenity_utils::hashmap_to_json_map(instance.0);
+
+        http.as_ref().create_forum_post(self.id.0, &map).await
+    }
+
     /// Creates a private thread.
     ///
     /// # Errors
@@ -1329,3 +1631,124 @@ pub struct PartialGuildChannel {
     #[serde(rename = "type")]
     pub kind: ChannelType,
 }
+
+#[cfg(all(test, feature = "cache"))]
+mod test {
+    use serde_json::{json, Value};
+
+    use crate::cache::Cache;
+    use crate::model::prelude::*;
+
+    fn member(user_id: u64) -> Value {
+        json!({
+            "user": {
+                "id": user_id.to_string(),
+                "username": format!("user {}", user_id),
+                "discriminator": "0001",
+                "avatar": null,
+            },
+            "roles": [],
+            "joined_at": "2021-01-01T00:00:00+00:00",
+            "deaf": false,
+            "mute": false,
+        })
+    }
+
+    fn voice_state(user_id: u64, channel_id: u64) -> Value {
+        json!({
+            "channel_id": channel_id.to_string(),
+            "user_id": user_id.to_string(),
+            "session_id": "",
+            "deaf": false,
+            "mute": false,
+            "self_deaf": false,
+            "self_mute": false,
+            "self_video": false,
+            "suppress": false,
+        })
+    }
+
+    #[allow(clippy::unwrap_used)]
+    fn channel(id: u64, kind: ChannelType) -> GuildChannel {
+        serde_json::from_value(json!({
+            "id": id.to_string(),
+            "guild_id": "1",
+            "type": kind.num(),
+            "name": "channel",
+            "position": 0,
+            "permission_overwrites": [],
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_channel_members() {
+        let cache = Cache::default();
+
+        let guild = serde_json::from_value(json!({
+            "id": "1",
+            "afk_channel_id": null,
+            "afk_timeout": 300,
+            "channels": [],
+            "default_message_notifications": 0,
+            "emojis": [],
+            "explicit_content_filter": 0,
+            "features": [],
+            "icon": null,
+            "joined_at": "2021-01-01T00:00:00+00:00",
+            "large": false,
+            "member_count": 3,
+            "members": [member(10), member(11), member(12)],
+            "mfa_level": 0,
+            "name": "guild",
+            "owner_id": "2",
+            "presences": [],
+            "region": "us-west",
+            "roles": [{
+                "id": "1",
+                "name": "@everyone",
+                "permissions": Permissions::READ_MESSAGES.bits().to_string(),
+                "color": 0,
+                "hoist": false,
+                "managed": false,
+                "mentionable": false,
+                "position": 0,
+            }],
+            "splash": null,
+            "verification_level": 0,
+            "voice_states": [voice_state(10, 100), voice_state(11, 100)],
+            "preferred_locale": "en-US",
+            "nsfw": false,
+            "nsfw_level": 0,
+            "system_channel_flags": 0,
+        }))
+        .unwrap();
+        let mut guild_create = GuildCreateEvent {
+            guild,
+        };
+        assert!(cache.update(&mut guild_create).await.is_none());
+
+        let voice = channel(100, ChannelType::Voice);
+        let mut connected = voice.connected_members(&cache).await.unwrap();
+        connected.sort_by_key(|member| member.user.id);
+        assert_eq!(connected.iter().map(|m| m.user.id).collect::<Vec<_>>(), [10, 11]);
+        let empty = channel(200, ChannelType::Voice);
+        assert!(empty.connected_members(&cache).await.unwrap().is_empty());
+
+        let mut text = channel(300, ChannelType::Text);
+        text.permission_overwrites.push(PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::READ_MESSAGES,
+            kind: PermissionOverwriteType::Member(UserId(12)),
+        });
+        let mut with_access = text.members_with_access(&cache, None).await.unwrap();
+        with_access.sort_by_key(|member| member.user.id);
+        assert_eq!(with_access.iter().map(|m| m.user.id).collect::<Vec<_>>(), [10, 11]);
+        assert_eq!(text.members_with_access(&cache, Some(1)).await.unwrap().len(), 1);
+
+        // Each method only applies to its own kind of channel.
+        assert!(voice.members_with_access(&cache, None).await.is_err());
+        assert!(text.connected_members(&cache).await.is_err());
+    }
+}
@@ -6,19 +6,33 @@ use std::fmt::Display;
 use std::fmt::Write;
 #[cfg(feature = "model")]
 use std::result::Result as StdResult;
+#[cfg(feature = "model")]
+use std::time::Duration;
 
 #[cfg(feature = "model")]
 use bitflags::__impl_bitflags;
 use chrono::{DateTime, Utc};
 #[cfg(feature = "model")]
+use futures::stream::Stream;
+#[cfg(feature = "model")]
 use serde::{
     de::{Deserialize, Deserializer},
     ser::{Serialize, Serializer},
 };
 use serde_json::Value;
+#[cfg(all(feature = "model", feature = "tokio_compat", not(feature = "tokio")))]
+use tokio::time::delay_for as sleep;
+#[cfg(all(feature = "model", feature = "tokio"))]
+use tokio::time::sleep;
 
+#[cfg(all(feature = "model", feature = "unstable_discord_api"))]
+use crate::builder::CreateInteractionResponseFollowup;
 #[cfg(all(feature = "model", feature = "utils"))]
 use crate::builder::{CreateEmbed, EditMessage};
+#[cfg(all(feature = "model", feature = "validate"))]
+use crate::builder::check_embed;
+#[cfg(feature = "model")]
+use crate::builder::embed_length;
 #[cfg(all(feature = "cache", feature = "model"))]
 use crate::cache::Cache;
 #[cfg(feature = "collector")]
@@ -34,14 +48,20 @@ use crate::collector::{
 use crate::collector::{CollectReaction, ReactionCollectorBuilder};
 #[cfg(feature = "model")]
 use crate::http::{CacheHttp, Http};
+#[cfg(all(feature = "model", feature = "unstable_discord_api"))]
+use crate::model::interactions::check_components;
 #[cfg(feature = "unstable_discord_api")]
-use crate::model::interactions::{message_component::ActionRow, MessageInteraction};
+use crate::model::interactions::{
+    message_component::ActionRow,
+    MessageInteraction,
+    MessageInteractionMetadata,
+};
 use crate::model::prelude::*;
 #[cfg(feature = "model")]
 use crate::model::utils::U64Visitor;
 #[cfg(feature = "model")]
 use crate::{
-    constants,
+    constants::{self, limits},
     model::id::{ApplicationId, ChannelId, GuildId, MessageId},
 };
 
@@ -119,6 +139,11 @@ pub struct Message {
     pub activity: Option<MessageActivity>,
     /// Sent with Rich Presence-related chat embeds.
     pub application: Option<MessageApplication>,
+    /// The Id of the application which sent the message, if it is a response
+    /// to an [`Interaction`] or was sent by an application-owned webhook.
+    ///
+    /// [`Interaction`]: crate::model::interactions::Interaction
+    pub application_id: Option<ApplicationId>,
     /// Reference data sent with crossposted messages.
     pub message_reference: Option<MessageReference>,
     /// Bit flags describing extra features of the message.
@@ -131,8 +156,15 @@ pub struct Message {
     /// Sent if the message is a response to an [`Interaction`].
     ///
     /// [`Interaction`]: crate::model::interactions::Interaction
+    ///
+    /// **Note**: Discord replaces this with [`Self::interaction_metadata`].
     #[cfg(feature = "unstable_discord_api")]
     pub interaction: Option<MessageInteraction>,
+    /// Sent if the message was created by an [`Interaction`].
+    ///
+    /// [`Interaction`]: crate::model::interactions::Interaction
+    #[cfg(feature = "unstable_discord_api")]
+    pub interaction_metadata: Option<MessageInteractionMetadata>,
     /// The components of this message
     #[cfg(feature = "unstable_discord_api")]
     #[serde(default)]
@@ -359,6 +391,9 @@ impl Message {
 
         let map = crate::utils::hashmap_to_json_map(builder.0);
 
+        #[cfg(feature = "unstable_discord_api")]
+        check_components(&map)?;
+
         *self = cache_http
             .http()
             .edit_message(self.channel_id.0, self.id.0, &Value::Object(map))
@@ -367,26 +402,133 @@ impl Message {
         Ok(())
     }
 
+    /// Edits this message through the token of the [`Interaction`] it was
+    /// sent for, such as a followup message, which can not be edited by
+    /// [`Self::edit`] as it was sent through the interaction's webhook.
+    ///
+    /// The message's [`Self::application_id`] is used for the request, falling
+    /// back to the application Id the [`Http`] client was created with.
+    ///
+    /// **Note**: Message contents must be under 2000 unicode code points.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::MessageTooLong`] if the content of the message
+    /// is over the limit.
+    ///
+    /// May also return an [`Error::Http`] if the API returns an error, such as
+    /// if the interaction token expired.
+    ///
+    /// [`Interaction`]: crate::model::interactions::Interaction
+    /// [`Error::Http`]: crate::error::Error::Http
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn edit_via_interaction_token<'a, F>(
+        &mut self,
+        http: impl AsRef<Http>,
+        token: &str,
+        f: F,
+    ) -> Result<()>
+    where
+        for<'b> F: FnOnce(
+            &'b mut CreateInteractionResponseFollowup<'a>,
+        ) -> &'b mut CreateInteractionResponseFollowup<'a>,
+    {
+        let http = http.as_ref();
+        let application_id = self.application_id.map_or(http.application_id, |id| id.0);
+
+        self.edit_followup(http, application_id, token, f).await
+    }
+
+    /// Edits this message as a followup message of the interaction with the
+    /// given `token`, replacing it with the edited message.
+    #[cfg(feature = "unstable_discord_api")]
+    pub(crate) async fn edit_followup<'a, F>(
+        &mut self,
+        http: &Http,
+        application_id: u64,
+        token: &str,
+        f: F,
+    ) -> Result<()>
+    where
+        for<'b> F: FnOnce(
+            &'b mut CreateInteractionResponseFollowup<'a>,
+        ) -> &'b mut CreateInteractionResponseFollowup<'a>,
+    {
+        let mut builder = CreateInteractionResponseFollowup::default();
+        f(&mut builder);
+
+        let map = crate::utils::hashmap_to_json_map(builder.0);
+
+        Self::check_content_length(&map)?;
+        Self::check_embed_length(&map)?;
+        check_components(&map)?;
+
+        *self = http
+            .edit_followup_message_with_files_for(application_id, token, self.id.0, builder.1, map)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Deletes this message through the token of the [`Interaction`] it was
+    /// sent for, such as a followup message.
+    ///
+    /// The Id of the application is chosen as in
+    /// [`Self::edit_via_interaction_token`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Http`] if the API returns an error, such as if the
+    /// interaction token expired.
+    ///
+    /// [`Interaction`]: crate::model::interactions::Interaction
+    /// [`Error::Http`]: crate::error::Error::Http
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn delete_via_interaction_token(
+        &self,
+        http: impl AsRef<Http>,
+        token: &str,
+    ) -> Result<()> {
+        let http = http.as_ref();
+        let application_id = self.application_id.map_or(http.application_id, |id| id.0);
+
+        http.delete_followup_message_for(application_id, token, self.id.0).await
+    }
+
     pub(crate) fn transform_content(&mut self) {
         match self.kind {
             MessageType::PinsAdd => {
                 self.content =
                     format!("{} pinned a message to this channel. See all the pins.", self.author);
             },
-            MessageType::MemberJoin => {
-                let sec = self.timestamp.timestamp() as usize;
-                let chosen = constants::JOIN_MESSAGES[sec % constants::JOIN_MESSAGES.len()];
-
-                self.content = if chosen.contains("$user") {
-                    chosen.replace("$user", &self.author.mention().to_string())
-                } else {
-                    chosen.to_string()
-                };
-            },
+            MessageType::MemberJoin => self.content = self.render_join_message(),
             _ => {},
         }
     }
 
+    /// Returns the content the official client shows for this message.
+    ///
+    /// Member join announcements are sent with empty content, and the client
+    /// picks one of [`JOIN_MESSAGES`] based on the message's timestamp. That
+    /// choice is reproduced here; for every other kind of message this is the
+    /// content as-is.
+    ///
+    /// [`JOIN_MESSAGES`]: crate::constants::JOIN_MESSAGES
+    pub fn content_rendered(&self) -> String {
+        if self.is_member_join() {
+            self.render_join_message()
+        } else {
+            self.content.clone()
+        }
+    }
+
+    fn render_join_message(&self) -> String {
+        let sec = self.timestamp.timestamp() as usize;
+        let chosen = constants::JOIN_MESSAGES[sec % constants::JOIN_MESSAGES.len()];
+
+        chosen.replace("$user", &self.author.mention().to_string())
+    }
+
     /// Returns message content, but with user and role mentions replaced with
     /// names and everyone/here mentions cancelled.
     #[cfg(feature = "cache")]
@@ -456,6 +598,48 @@ impl Message {
         self.channel_id.reaction_users(&http, self.id, reaction_type, limit, after).await
     }
 
+    /// Streams over all the [`User`]s who have reacted to the message with a
+    /// certain [`Emoji`], paginating through [`Self::reaction_users`].
+    ///
+    /// Each user is streamed only once, even if reactions are removed and
+    /// added again while paginating. Refer to [`ReactionUsersIter::stream`].
+    ///
+    /// **Note**: Requires the [Read Message History] permission.
+    ///
+    /// [Read Message History]: Permissions::READ_MESSAGE_HISTORY
+    pub fn reaction_users_all<H: AsRef<Http>>(
+        &self,
+        http: H,
+        reaction_type: impl Into<ReactionType>,
+    ) -> impl Stream<Item = Result<User>> {
+        ReactionUsersIter::<H>::stream(http, self.channel_id, self.id, reaction_type)
+    }
+
+    /// Same as [`Self::reaction_users_all`], but also streams the users who
+    /// react with the [`Emoji`] while the pages are fetched, so the streamed
+    /// users are complete as of the last page. Refer to
+    /// [`ReactionUsersIter::stream_live`].
+    ///
+    /// **Note**: Requires the [Read Message History] permission.
+    ///
+    /// [Read Message History]: Permissions::READ_MESSAGE_HISTORY
+    #[cfg(feature = "collector")]
+    pub async fn reaction_users_all_live<H: AsRef<Http>>(
+        &self,
+        http: H,
+        shard_messenger: impl AsRef<ShardMessenger>,
+        reaction_type: impl Into<ReactionType>,
+    ) -> impl Stream<Item = Result<User>> {
+        ReactionUsersIter::<H>::stream_live(
+            http,
+            shard_messenger,
+            self.channel_id,
+            self.id,
+            reaction_type,
+        )
+        .await
+    }
+
     /// Returns the associated [`Guild`] for the message if one is in the cache.
     ///
     /// Returns [`None`] if the guild's Id could not be found via [`Self::guild_id`] or
@@ -473,7 +657,12 @@ impl Message {
     /// Returns [`None`] if the guild's ID could not be found via [`Self::guild_id`] or
     /// if the Guild itself is not cached.
     ///
+    /// **Note**: The guild's [`members`] map is empty, refer to
+    /// [`Cache::guild_field`].
+    ///
     /// Requires the `cache` feature be enabled.
+    ///
+    /// [`members`]: Guild::members
     #[cfg(feature = "cache")]
     pub async fn guild_field<Ret, Fun>(
         &self,
@@ -493,6 +682,44 @@ impl Message {
         self.guild_id.is_none()
     }
 
+    /// True if the message is the announcement of a member joining the guild.
+    #[inline]
+    pub fn is_member_join(&self) -> bool {
+        self.kind == MessageType::MemberJoin
+    }
+
+    /// Returns the Id of the user who joined the guild, if the message is a
+    /// member join announcement.
+    #[inline]
+    pub fn joined_user_id(&self) -> Option<UserId> {
+        self.is_member_join().then(|| self.author.id)
+    }
+
+    /// True if the message was created by an [`Interaction`], either as its
+    /// response or as a followup message.
+    ///
+    /// [`Interaction`]: crate::model::interactions::Interaction
+    #[cfg(feature = "unstable_discord_api")]
+    #[inline]
+    pub fn is_interaction_response(&self) -> bool {
+        self.interaction_metadata.is_some() || self.interaction.is_some()
+    }
+
+    /// Returns the user who invoked the [`Interaction`] the message was
+    /// created by, if any.
+    ///
+    /// This is read from [`Self::interaction_metadata`], falling back to
+    /// [`Self::interaction`] for messages which only have the latter.
+    ///
+    /// [`Interaction`]: crate::model::interactions::Interaction
+    #[cfg(feature = "unstable_discord_api")]
+    pub fn invoking_user(&self) -> Option<&User> {
+        match &self.interaction_metadata {
+            Some(metadata) => Some(&metadata.user),
+            None => self.interaction.as_ref().map(|interaction| &interaction.user),
+        }
+    }
+
     /// Retrieves a clone of the author's Member instance, if this message was
     /// sent in a guild.
     ///
@@ -531,8 +758,8 @@ impl Message {
         // points.
         let count = content.chars().count();
 
-        if count > constants::MESSAGE_CODE_LIMIT {
-            Some(count - constants::MESSAGE_CODE_LIMIT)
+        if count > limits::MESSAGE_CONTENT_LENGTH {
+            Some(count - limits::MESSAGE_CONTENT_LENGTH)
         } else {
             None
         }
@@ -626,6 +853,78 @@ impl Message {
         })
     }
 
+    /// React to the message with multiple emojis, one after the other, in the
+    /// given order.
+    ///
+    /// Unlike sending the reactions concurrently, this guarantees that they
+    /// appear on the message in order. Every reaction is awaited before the
+    /// next one is sent, optionally waiting an additional `delay` in between
+    /// to spread the requests out.
+    ///
+    /// **Note**: Requires the [Add Reactions] permission.
+    ///
+    /// # Examples
+    ///
+    /// Add the options of a poll, resuming once if a reaction fails:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::http::Http;
+    /// # use serenity::model::channel::Message;
+    /// #
+    /// # async fn run(http: &Http, message: &Message) {
+    /// use std::time::Duration;
+    ///
+    /// let options = ['🍎', '🍌', '🍇', '🍑', '🍒'];
+    /// let delay = Some(Duration::from_millis(250));
+    ///
+    /// if let Err((index, why)) = message.react_in_order(http, options.to_vec(), delay).await {
+    ///     println!("Failed to add reaction {}: {:?}", index, why);
+    ///
+    ///     let _ = message.react_in_order(http, options[index..].to_vec(), delay).await;
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first reaction that could not be added, returning its
+    /// index in `reaction_types` together with the error. All reactions
+    /// before that index were added, so the caller can resume from it.
+    ///
+    /// If the `cache` is enabled, the error may be a
+    /// [`ModelError::InvalidPermissions`] if the current user does not have
+    /// the required [permissions].
+    ///
+    /// [Add Reactions]: Permissions::ADD_REACTIONS
+    /// [permissions]: super::permissions
+    pub async fn react_in_order<It>(
+        &self,
+        cache_http: impl CacheHttp,
+        reaction_types: It,
+        delay: Option<Duration>,
+    ) -> StdResult<Vec<Reaction>, (usize, Error)>
+    where
+        It: IntoIterator,
+        It::Item: Into<ReactionType>,
+    {
+        let mut reactions = Vec::new();
+
+        for (index, reaction_type) in reaction_types.into_iter().enumerate() {
+            if index > 0 {
+                if let Some(delay) = delay {
+                    sleep(delay).await;
+                }
+            }
+
+            match self._react(&cache_http, &reaction_type.into()).await {
+                Ok(reaction) => reactions.push(reaction),
+                Err(why) => return Err((index, why)),
+            }
+        }
+
+        Ok(reactions)
+    }
+
     /// Uses Discord's inline reply to a user without pinging them.
     ///
     /// User mentions are generally around 20 or 21 characters long.
@@ -807,7 +1106,60 @@ impl Message {
         self.mentions_user_id(user.id)
     }
 
-    /// Checks whether the message mentions the current user.
+    /// Checks whether the message mentions the passed [`Member`], either
+    /// explicitly, through one of their roles, or through `@everyone` or
+    /// `@here`.
+    ///
+    /// **Note**: This does not check whether the member can see the channel
+    /// the message was sent in.
+    pub fn mentions_member(&self, member: &Member) -> bool {
+        self.mentions_user_id(member.user.id)
+            || self.mention_everyone
+            || member.roles.iter().any(|role_id| self.mention_roles.contains(role_id))
+    }
+
+    /// Checks whether the message mentions the passed [`UserId`], like
+    /// [`Self::mentions_member`] does, looking up the member's roles in the
+    /// `cache`.
+    ///
+    /// The member is looked up in the guild with the given `guild_id`, or the
+    /// message's [`Self::guild_id`] if [`None`]. Outside of a guild, or if the
+    /// member is not cached, only the explicit mentions and `@everyone` are
+    /// checked.
+    #[cfg(feature = "cache")]
+    pub async fn mentions_user_comprehensive(
+        &self,
+        cache: impl AsRef<Cache>,
+        user_id: impl Into<UserId>,
+        guild_id: Option<GuildId>,
+    ) -> bool {
+        let user_id = user_id.into();
+
+        if self.mentions_user_id(user_id) || self.mention_everyone {
+            return true;
+        }
+
+        if self.mention_roles.is_empty() {
+            return false;
+        }
+
+        match guild_id.or(self.guild_id) {
+            Some(guild_id) => match cache.as_ref().member(guild_id, user_id).await {
+                Some(member) => self.mentions_member(&member),
+                None => false,
+            },
+            None => false,
+        }
+    }
+
+    /// Checks whether the message mentions the current user, either
+    /// explicitly, through one of their roles, or through `@everyone` or
+    /// `@here`.
+    ///
+    /// Refer to [`Self::mentions_user_comprehensive`] for how the current
+    /// user's roles are looked up when the cache is enabled. Otherwise, the
+    /// current user's member is requested over the REST API if the message
+    /// mentions any roles.
     ///
     /// # Errors
     ///
@@ -817,12 +1169,26 @@ impl Message {
         #[cfg(feature = "cache")]
         {
             if let Some(cache) = cache_http.cache() {
-                return Ok(self.mentions_user_id(cache.user.read().await.id));
+                let user_id = cache.current_user_id().await;
+
+                return Ok(self.mentions_user_comprehensive(cache, user_id, None).await);
             }
         }
 
         let current_user = cache_http.http().get_current_user().await?;
-        Ok(self.mentions_user_id(current_user.id))
+
+        if self.mentions_user_id(current_user.id) || self.mention_everyone {
+            return Ok(true);
+        }
+
+        match self.guild_id {
+            Some(guild_id) if !self.mention_roles.is_empty() => {
+                let member = cache_http.http().get_member(guild_id.0, current_user.id.0).await?;
+
+                Ok(self.mentions_member(&member))
+            },
+            _ => Ok(false),
+        }
     }
 
     /// Unpins the message from its channel.
@@ -949,57 +1315,34 @@ impl Message {
         Ok(())
     }
 
+    /// Checks the number of embeds of a message and their length.
+    ///
+    /// Like Discord, the length of the textual content of all embeds is
+    /// limited altogether, rather than that of each embed on its own.
     pub(crate) fn check_embed_length(map: &JsonMap) -> Result<()> {
         let embeds = match map.get("embeds") {
             Some(&Value::Array(ref value)) => value,
             _ => return Ok(()),
         };
 
-        if embeds.len() > 10 {
+        if embeds.len() > limits::EMBED_COUNT {
             return Err(Error::Model(ModelError::EmbedAmount));
         }
 
-        for embed in embeds {
-            let mut total: usize = 0;
-
-            if let Some(&Value::Object(ref author)) = embed.get("author") {
-                if let Some(&Value::Object(ref name)) = author.get("name") {
-                    total += name.len();
-                }
-            }
-
-            if let Some(&Value::String(ref description)) = embed.get("description") {
-                total += description.len();
-            }
-
-            if let Some(&Value::Array(ref fields)) = embed.get("fields") {
-                for field_as_value in fields {
-                    if let Value::Object(ref field) = *field_as_value {
-                        if let Some(&Value::String(ref field_name)) = field.get("name") {
-                            total += field_name.len();
-                        }
-
-                        if let Some(&Value::String(ref field_value)) = field.get("value") {
-                            total += field_value.len();
-                        }
-                    }
-                }
-            }
-
-            if let Some(&Value::Object(ref footer)) = embed.get("footer") {
-                if let Some(&Value::String(ref text)) = footer.get("text") {
-                    total += text.len();
-                }
-            }
+        #[cfg(feature = "validate")]
+        for embed in embeds.iter().filter_map(Value::as_object) {
+            check_embed(|key| embed.get(key))?;
+        }
 
-            if let Some(&Value::String(ref title)) = embed.get("title") {
-                total += title.len();
-            }
+        let total: usize = embeds
+            .iter()
+            .filter_map(Value::as_object)
+            .map(|embed| embed_length(|key| embed.get(key)))
+            .sum();
 
-            if total > constants::EMBED_MAX_LENGTH {
-                let overflow = total - constants::EMBED_MAX_LENGTH;
-                return Err(Error::Model(ModelError::EmbedTooLarge(overflow)));
-            }
+        if total > limits::EMBED_TOTAL_LENGTH {
+            let overflow = total - limits::EMBED_TOTAL_LENGTH;
+            return Err(Error::Model(ModelError::EmbedTooLarge(overflow)));
         }
 
         Ok(())
@@ -1156,6 +1499,7 @@ pub struct MessageApplication {
     /// ID of the embed's image asset.
     pub cover_image: Option<String>,
     /// Application's description.
+    #[serde(default)]
     pub description: String,
     /// ID of the application's icon.
     pub icon: Option<String>,
@@ -1163,6 +1507,26 @@ pub struct MessageApplication {
     pub name: String,
 }
 
+#[cfg(feature = "model")]
+impl MessageApplication {
+    /// Returns the formatted URL of the application's icon, if one exists.
+    ///
+    /// This will produce a PNG URL.
+    pub fn icon_url(&self) -> Option<String> {
+        self.icon.as_ref().map(|icon| format!(cdn!("/app-icons/{}/{}.png"), self.id, icon))
+    }
+
+    /// Returns the formatted URL of the application's cover image, if one
+    /// exists.
+    ///
+    /// This will produce a PNG URL.
+    pub fn cover_image_url(&self) -> Option<String> {
+        self.cover_image
+            .as_ref()
+            .map(|cover_image| format!(cdn!("/app-icons/{}/{}.png"), self.id, cover_image))
+    }
+}
+
 /// Rich Presence activity information.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[non_exhaustive]
@@ -1303,3 +1667,262 @@ impl MessageId {
         self.link(channel_id, guild_id)
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use serde_json::json;
+
+    use super::{Message, MessageActivityKind};
+    use crate::model::id::ApplicationId;
+
+    fn message_json(extra: serde_json::Value) -> serde_json::Value {
+        let mut message = json!({
+            "id": "880412432156278794",
+            "attachments": [],
+            "author": {
+                "id": "210",
+                "username": "Meaning of Life",
+                "discriminator": "0042",
+                "avatar": null,
+                "bot": true
+            },
+            "channel_id": "381880193700069377",
+            "content": "",
+            "edited_timestamp": null,
+            "embeds": [],
+            "type": 0,
+            "mention_everyone": false,
+            "mention_roles": [],
+            "mentions": [],
+            "pinned": false,
+            "timestamp": "2021-08-27T16:13:33.482000+00:00",
+            "tts": false
+        });
+
+        message.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+        message
+    }
+
+    #[test]
+    #[cfg(feature = "unstable_discord_api")]
+    fn slash_command_response() {
+        use super::MessageType;
+        use crate::model::id::{GuildId, InteractionId, UserId};
+        use crate::model::interactions::InteractionType;
+
+        let value = message_json(json!({
+            "type": 20,
+            "application_id": "210",
+            "webhook_id": "210",
+            "interaction": {
+                "id": "880412431401308200",
+                "type": 2,
+                "name": "ping",
+                "user": {
+                    "id": "114941315417899012",
+                    "username": "Caller",
+                    "discriminator": "0001",
+                    "avatar": null
+                }
+            },
+            "interaction_metadata": {
+                "id": "880412431401308200",
+                "type": 2,
+                "user": {
+                    "id": "114941315417899012",
+                    "username": "Caller",
+                    "discriminator": "0001",
+                    "avatar": null
+                },
+                "authorizing_integration_owners": {"0": "381880193251409931"}
+            }
+        }));
+        let message: Message = serde_json::from_value(value).unwrap();
+
+        assert_eq!(message.kind, MessageType::ApplicationCommand);
+        assert_eq!(message.application_id, Some(ApplicationId(210)));
+        assert!(message.is_interaction_response());
+        assert_eq!(message.invoking_user().map(|u| u.id), Some(UserId(114941315417899012)));
+
+        let metadata = message.interaction_metadata.as_ref().unwrap();
+        assert_eq!(metadata.id, InteractionId(880412431401308200));
+        assert_eq!(metadata.kind, InteractionType::ApplicationCommand);
+        assert_eq!(
+            metadata.authorizing_integration_owners.guild_id,
+            Some(GuildId(381880193251409931))
+        );
+        assert!(metadata.original_response_message_id.is_none());
+        assert!(metadata.triggering_interaction_metadata.is_none());
+
+        // Older messages only carry the deprecated field.
+        let mut message = message;
+        message.interaction_metadata = None;
+        assert!(message.is_interaction_response());
+        assert_eq!(message.invoking_user().map(|u| u.id), Some(UserId(114941315417899012)));
+    }
+
+    #[test]
+    #[cfg(feature = "model")]
+    fn link() {
+        use crate::model::id::GuildId;
+
+        let mut message: Message = serde_json::from_value(message_json(json!({}))).unwrap();
+        assert_eq!(
+            message.link(),
+            "https://discord.com/channels/@me/381880193700069377/880412432156278794"
+        );
+
+        message.guild_id = Some(GuildId(381880193251409931));
+        assert_eq!(
+            message.link(),
+            "https://discord.com/channels/381880193251409931/381880193700069377/880412432156278794"
+        );
+    }
+
+    fn member_json() -> serde_json::Value {
+        json!({
+            "guild_id": "381880193251409931",
+            "user": {
+                "id": "114941315417899012",
+                "username": "Member",
+                "discriminator": "0001",
+                "avatar": null
+            },
+            "roles": ["381880193251409932"],
+            "joined_at": "2021-08-27T16:13:33.482000+00:00",
+            "deaf": false,
+            "mute": false
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "model")]
+    fn mentions_member() {
+        use crate::model::guild::Member;
+        use crate::model::id::RoleId;
+
+        let member: Member = serde_json::from_value(member_json()).unwrap();
+        let mut message: Message = serde_json::from_value(message_json(json!({}))).unwrap();
+        assert!(!message.mentions_member(&member));
+
+        message.mentions = vec![member.user.clone()];
+        assert!(message.mentions_user_id(member.user.id));
+        assert!(message.mentions_member(&member));
+
+        message.mentions.clear();
+        message.mention_roles = vec![RoleId(1)];
+        assert!(!message.mentions_member(&member));
+
+        message.mention_roles.push(RoleId(381880193251409932));
+        assert!(!message.mentions_user_id(member.user.id));
+        assert!(message.mentions_member(&member));
+
+        message.mention_roles.clear();
+        message.mention_everyone = true;
+        assert!(message.mentions_member(&member));
+    }
+
+    #[tokio::test]
+    #[cfg(all(feature = "cache", feature = "model"))]
+    async fn mentions_user_comprehensive() {
+        use crate::cache::Cache;
+        use crate::model::guild::Member;
+        use crate::model::id::{GuildId, RoleId, UserId};
+
+        let cache = Cache::default();
+        let member: Member = serde_json::from_value(member_json()).unwrap();
+        cache.insert_member(member.guild_id, &member).await;
+
+        let user_id = UserId(114941315417899012);
+        let mut message: Message = serde_json::from_value(message_json(json!({
+            "guild_id": "381880193251409931",
+            "mention_roles": ["381880193251409932"]
+        })))
+        .unwrap();
+        assert!(message.mentions_user_comprehensive(&cache, user_id, None).await);
+
+        // The member's roles are unknown in other guilds and outside of one.
+        assert!(!message.mentions_user_comprehensive(&cache, user_id, Some(GuildId(1))).await);
+        message.guild_id = None;
+        assert!(!message.mentions_user_comprehensive(&cache, user_id, None).await);
+
+        message.mention_roles = vec![RoleId(1)];
+        message.mention_everyone = true;
+        assert!(message.mentions_user_comprehensive(&cache, user_id, None).await);
+    }
+
+    #[test]
+    #[cfg(feature = "model")]
+    fn member_join_rendered() {
+        use std::collections::HashSet;
+
+        use chrono::Duration;
+
+        use super::MessageType;
+        use crate::constants::JOIN_MESSAGES;
+        use crate::model::id::UserId;
+
+        let mut message: Message = serde_json::from_value(message_json(json!({}))).unwrap();
+        assert!(!message.is_member_join());
+        assert!(message.joined_user_id().is_none());
+        assert!(message.content_rendered().is_empty());
+
+        message.kind = MessageType::MemberJoin;
+        assert_eq!(message.joined_user_id(), Some(UserId(210)));
+
+        // Consecutive seconds cycle through every template.
+        let start = message.timestamp;
+        let mut rendered = HashSet::new();
+        for offset in 0..JOIN_MESSAGES.len() {
+            message.timestamp = start + Duration::seconds(offset as i64);
+
+            let content = message.content_rendered();
+            assert!(content.contains("<@210>"), "{}", content);
+            assert!(!content.contains("$user"), "{}", content);
+            rendered.insert(content);
+        }
+        assert_eq!(rendered.len(), JOIN_MESSAGES.len());
+    }
+
+    #[test]
+    fn activity_invite() {
+        let value = message_json(json!({
+            "activity": {
+                "type": 1,
+                "party_id": "spotify:114941315417899012"
+            },
+            "application": {
+                "id": "463097721130188830",
+                "name": "Spotify",
+                "icon": "f0d2b0aebbd3c3a53bf6e1d4d4e2b6e3",
+                "cover_image": "c3c9e3a5f1b2e2d2a9e8b7c6d5e4f3a2"
+            }
+        }));
+        let message: Message = serde_json::from_value(value).unwrap();
+
+        let activity = message.activity.as_ref().unwrap();
+        assert_eq!(activity.kind, MessageActivityKind::JOIN);
+        assert_eq!(activity.party_id.as_deref(), Some("spotify:114941315417899012"));
+
+        let application = message.application.as_ref().unwrap();
+        assert_eq!(application.id, ApplicationId(463097721130188830));
+        assert!(application.description.is_empty());
+        #[cfg(feature = "model")]
+        {
+            assert_eq!(
+                application.icon_url().as_deref(),
+                Some(
+                    "https://cdn.discordapp.com/app-icons/463097721130188830/f0d2b0aebbd3c3a53bf6e1d4d4e2b6e3.png"
+                )
+            );
+            assert_eq!(
+                application.cover_image_url().as_deref(),
+                Some(
+                    "https://cdn.discordapp.com/app-icons/463097721130188830/c3c9e3a5f1b2e2d2a9e8b7c6d5e4f3a2.png"
+                )
+            );
+        }
+        assert!(message.application_id.is_none());
+    }
+}
@@ -0,0 +1,99 @@
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::error::Error;
+use crate::http::HttpError;
+
+/// The JSON error code Discord responds with for a message which does not
+/// exist.
+const UNKNOWN_MESSAGE: isize = 10008;
+
+/// The reason a message could not be fetched, as returned by
+/// [`ChannelId::messages_by_ids`].
+///
+/// [`ChannelId::messages_by_ids`]: super::ChannelId::messages_by_ids
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MessageFetchError {
+    /// The message does not exist, such as when it was deleted.
+    NotFound,
+    /// Any other error, such as the current user lacking permission to read
+    /// the channel.
+    Other(Error),
+}
+
+impl MessageFetchError {
+    /// Returns true when the message does not exist.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Self::NotFound)
+    }
+}
+
+impl From<Error> for MessageFetchError {
+    fn from(error: Error) -> Self {
+        if let Error::Http(http_error) = &error {
+            if let HttpError::UnsuccessfulRequest(response) = &**http_error {
+                if response.error.code == UNKNOWN_MESSAGE {
+                    return Self::NotFound;
+                }
+            }
+        }
+
+        Self::Other(error)
+    }
+}
+
+impl Display for MessageFetchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::NotFound => f.write_str("The message does not exist."),
+            Self::Other(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl StdError for MessageFetchError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::NotFound => None,
+            Self::Other(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use reqwest::StatusCode;
+
+    use super::MessageFetchError;
+    use crate::error::Error;
+    use crate::http::error::{DiscordJsonError, ErrorResponse};
+    use crate::http::HttpError;
+
+    fn unsuccessful(status: u16, code: isize) -> Error {
+        let response = ErrorResponse {
+            status_code: StatusCode::from_u16(status).unwrap(),
+            url: "https://discord.com/api/v9/channels/1/messages/2".parse().unwrap(),
+            error: DiscordJsonError {
+                code,
+                message: String::new(),
+                errors: vec![],
+            },
+        };
+
+        Error::Http(Box::new(HttpError::UnsuccessfulRequest(response)))
+    }
+
+    #[test]
+    fn test_unknown_message_is_not_found() {
+        assert!(MessageFetchError::from(unsuccessful(404, 10008)).is_not_found());
+
+        // An unknown channel is not an unknown message.
+        let error = MessageFetchError::from(unsuccessful(404, 10003));
+        assert!(matches!(error, MessageFetchError::Other(Error::Http(_))));
+
+        let error = MessageFetchError::from(unsuccessful(403, 50001));
+        assert!(matches!(error, MessageFetchError::Other(Error::Http(_))));
+    }
+}
@@ -2,14 +2,18 @@
 
 mod attachment;
 mod channel_category;
+mod channel_flags;
 mod channel_id;
 mod embed;
 mod guild_channel;
 mod message;
+#[cfg(feature = "model")]
+mod message_fetch;
 mod partial_channel;
 mod private_channel;
 mod reaction;
 mod sticker;
+mod voice_join;
 
 #[cfg(feature = "model")]
 use std::fmt::{Display, Formatter, Result as FmtResult};
@@ -22,14 +26,18 @@ use serde::ser::{Serialize, SerializeStruct, Serializer};
 
 pub use self::attachment::*;
 pub use self::channel_category::*;
+pub use self::channel_flags::*;
 pub use self::channel_id::*;
 pub use self::embed::*;
 pub use self::guild_channel::*;
 pub use self::message::*;
+#[cfg(feature = "model")]
+pub use self::message_fetch::*;
 pub use self::partial_channel::*;
 pub use self::private_channel::*;
 pub use self::reaction::*;
 pub use self::sticker::*;
+pub use self::voice_join::*;
 use super::utils::deserialize_u64;
 #[cfg(all(feature = "cache", feature = "model"))]
 use crate::cache::Cache;
@@ -253,7 +261,7 @@ impl<'de> Deserialize<'de> for Channel {
         };
 
         match kind {
-            0 | 2 | 5 | 6 | 10 | 11 | 12 | 13 | 15 => {
+            0 | 2 | 5 | 6 | 10 | 11 | 12 | 13 | 15 | 16 => {
                 serde_json::from_value::<GuildChannel>(Value::Object(v))
                     .map(Channel::Guild)
                     .map_err(DeError::custom)
@@ -331,6 +339,10 @@ pub enum ChannelType {
     /// An indicator that the channel is a forum [`GuildChannel`].
     #[cfg(feature = "unstable_discord_api")]
     Forum = 15,
+    /// An indicator that the channel is a media [`GuildChannel`], which like a
+    /// forum channel only contains threads.
+    #[cfg(feature = "unstable_discord_api")]
+    Media = 16,
     /// An indicator that the channel is of unknown type.
     Unknown = !0,
 }
@@ -348,6 +360,8 @@ enum_number!(ChannelType {
     Stage,
     #[cfg(feature = "unstable_discord_api")]
     Forum,
+    #[cfg(feature = "unstable_discord_api")]
+    Media,
 });
 
 impl ChannelType {
@@ -366,6 +380,8 @@ impl ChannelType {
             ChannelType::Stage => "stage",
             #[cfg(feature = "unstable_discord_api")]
             ChannelType::Forum => "forum",
+            #[cfg(feature = "unstable_discord_api")]
+            ChannelType::Media => "media",
             ChannelType::Unknown => "unknown",
         }
     }
@@ -382,7 +398,7 @@ struct PermissionOverwriteData {
 }
 
 /// A channel-specific permission overwrite for a member or role.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PermissionOverwrite {
     pub allow: Permissions,
     pub deny: Permissions,
@@ -429,6 +445,26 @@ impl Serialize for PermissionOverwrite {
     }
 }
 
+/// Whether two lists of permission overwrites are the same, regardless of
+/// their order.
+///
+/// Like in the Discord client, an overwrite with both an empty allow and deny
+/// is not the same as no overwrite at all.
+#[cfg(all(feature = "cache", feature = "model"))]
+pub(crate) fn permission_overwrites_match(
+    left: &[PermissionOverwrite],
+    right: &[PermissionOverwrite],
+) -> bool {
+    left.len() == right.len()
+        && left.iter().all(|overwrite| {
+            right.iter().any(|other| {
+                other.kind == overwrite.kind
+                    && other.allow.bits() == overwrite.allow.bits()
+                    && other.deny.bits() == overwrite.deny.bits()
+            })
+        })
+}
+
 /// The type of edit being made to a Channel's permissions.
 ///
 /// This is for use with methods such as [`GuildChannel::create_permission`].
@@ -459,6 +495,44 @@ enum_number!(VideoQualityMode {
     Full
 });
 
+/// The default layout of the posts in a forum channel.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum ForumLayout {
+    /// An indicator that no default layout has been set.
+    NotSet = 0,
+    /// An indicator that posts are displayed as a list.
+    ListView = 1,
+    /// An indicator that posts are displayed as a collection of tiles.
+    GalleryView = 2,
+    /// An indicator that the layout is of unknown type.
+    Unknown = !0,
+}
+
+enum_number!(ForumLayout {
+    NotSet,
+    ListView,
+    GalleryView
+});
+
+/// The default order in which the posts of a forum or media channel are
+/// sorted.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum SortOrder {
+    /// An indicator that posts are sorted by their latest activity.
+    LatestActivity = 0,
+    /// An indicator that posts are sorted by when they were created.
+    CreationDate = 1,
+    /// An indicator that the sort order is of unknown type.
+    Unknown = !0,
+}
+
+enum_number!(SortOrder {
+    LatestActivity,
+    CreationDate
+});
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[non_exhaustive]
 pub struct StageInstance {
@@ -540,6 +614,9 @@ mod test {
                 thread_metadata: None,
                 member: None,
                 default_auto_archive_duration: None,
+                flags: ChannelFlags::default(),
+                default_forum_layout: None,
+                default_sort_order: None,
             }
         }
 
@@ -555,6 +632,7 @@ mod test {
                     bot: false,
                     discriminator: 1,
                     name: "ab".to_string(),
+                    global_name: None,
                     public_flags: None,
                     banner: None,
                     accent_colour: None,
@@ -592,6 +670,180 @@ mod test {
             assert!(!private_channel.is_nsfw());
         }
     }
+
+    #[cfg(all(feature = "cache", feature = "model"))]
+    mod overwrites {
+        use super::super::permission_overwrites_match;
+        use crate::model::prelude::*;
+
+        fn overwrite(role_id: u64, allow: Permissions, deny: Permissions) -> PermissionOverwrite {
+            PermissionOverwrite {
+                allow,
+                deny,
+                kind: PermissionOverwriteType::Role(RoleId(role_id)),
+            }
+        }
+
+        #[test]
+        fn order_insensitive() {
+            let left = vec![
+                overwrite(1, Permissions::SEND_MESSAGES, Permissions::empty()),
+                overwrite(2, Permissions::empty(), Permissions::READ_MESSAGES),
+            ];
+            let right = vec![left[1].clone(), left[0].clone()];
+
+            assert!(permission_overwrites_match(&left, &right));
+            assert!(permission_overwrites_match(&[], &[]));
+        }
+
+        #[test]
+        fn differing_permissions() {
+            let left = vec![overwrite(1, Permissions::SEND_MESSAGES, Permissions::empty())];
+            let right = vec![overwrite(1, Permissions::empty(), Permissions::SEND_MESSAGES)];
+
+            assert!(!permission_overwrites_match(&left, &right));
+        }
+
+        #[test]
+        fn empty_overwrite_is_unsynced() {
+            let left = vec![overwrite(1, Permissions::SEND_MESSAGES, Permissions::empty())];
+            let mut right = left.clone();
+            right.push(overwrite(2, Permissions::empty(), Permissions::empty()));
+
+            assert!(!permission_overwrites_match(&left, &right));
+            assert!(!permission_overwrites_match(&right, &left));
+        }
+
+        #[test]
+        fn member_and_role_with_same_id_differ() {
+            let left = vec![overwrite(1, Permissions::SEND_MESSAGES, Permissions::empty())];
+            let right = vec![PermissionOverwrite {
+                allow: Permissions::SEND_MESSAGES,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Member(UserId(1)),
+            }];
+
+            assert!(!permission_overwrites_match(&left, &right));
+        }
+    }
+
+    #[cfg(feature = "unstable_discord_api")]
+    mod thread_only {
+        use serde_json::json;
+
+        use crate::model::prelude::*;
+
+        fn media_channel() -> serde_json::Value {
+            json!({
+                "id": "3",
+                "guild_id": "1",
+                "type": 16,
+                "name": "media",
+                "position": 2,
+                "permission_overwrites": [],
+                "nsfw": false,
+                "flags": (1 << 4) | (1 << 15),
+            })
+        }
+
+        #[test]
+        fn media_channel_payload() {
+            let channel = match serde_json::from_value(media_channel()).unwrap() {
+                Channel::Guild(channel) => channel,
+                other => panic!("expected a guild channel, got {:?}", other),
+            };
+
+            assert_eq!(channel.kind, ChannelType::Media);
+            assert_eq!(channel.kind.name(), "media");
+            assert_eq!(
+                channel.flags,
+                ChannelFlags::REQUIRE_TAG | ChannelFlags::HIDE_MEDIA_DOWNLOAD_OPTIONS
+            );
+
+            let value = serde_json::to_value(&channel).unwrap();
+            assert_eq!(value["type"], 16);
+            assert_eq!(value["flags"], (1 << 4) | (1 << 15));
+        }
+
+        #[cfg(feature = "model")]
+        #[test]
+        fn thread_only_channels() {
+            let mut channel: GuildChannel = serde_json::from_value(media_channel()).unwrap();
+            assert!(channel.is_thread_only());
+
+            channel.kind = ChannelType::Forum;
+            assert!(channel.is_thread_only());
+
+            channel.kind = ChannelType::Text;
+            assert!(!channel.is_thread_only());
+        }
+
+        #[cfg(feature = "model")]
+        #[test]
+        fn forum_channel_settings() {
+            let mut payload = media_channel();
+            payload["type"] = json!(15);
+            payload["flags"] = json!(1 << 4);
+            payload["default_forum_layout"] = json!(2);
+            payload["default_sort_order"] = json!(1);
+
+            let mut channel: GuildChannel = serde_json::from_value(payload).unwrap();
+            assert_eq!(channel.default_forum_layout, Some(ForumLayout::GalleryView));
+            assert_eq!(channel.default_sort_order, Some(SortOrder::CreationDate));
+            assert!(channel.requires_tag());
+
+            channel.flags = ChannelFlags::empty();
+            assert!(!channel.requires_tag());
+
+            let channel: GuildChannel = serde_json::from_value(media_channel()).unwrap();
+            assert_eq!(channel.default_forum_layout, None);
+            assert_eq!(channel.default_sort_order, None);
+        }
+
+        #[cfg(feature = "model")]
+        #[test]
+        fn settable_channel_flags() {
+            let all = ChannelFlags::all();
+
+            assert_eq!(all.unsettable_on(ChannelType::Media, None), ChannelFlags::PINNED);
+            assert_eq!(
+                all.unsettable_on(ChannelType::Forum, None),
+                ChannelFlags::PINNED | ChannelFlags::HIDE_MEDIA_DOWNLOAD_OPTIONS
+            );
+            assert_eq!(all.unsettable_on(ChannelType::Text, None), all);
+
+            let thread = ChannelType::PublicThread;
+            assert_eq!(
+                ChannelFlags::PINNED.unsettable_on(thread, Some(ChannelType::Forum)),
+                ChannelFlags::empty()
+            );
+            assert_eq!(ChannelFlags::PINNED.unsettable_on(thread, None), ChannelFlags::empty());
+            assert_eq!(
+                ChannelFlags::PINNED.unsettable_on(thread, Some(ChannelType::Text)),
+                ChannelFlags::PINNED
+            );
+            assert_eq!(
+                ChannelFlags::PINNED.unsettable_on(ChannelType::PrivateThread, None),
+                ChannelFlags::PINNED
+            );
+        }
+
+        #[cfg(feature = "model")]
+        #[test]
+        fn check_channel_flags_in_builder() {
+            let mut builder = crate::builder::CreateChannel::default();
+            builder.kind(ChannelType::Text).flags(ChannelFlags::REQUIRE_TAG);
+            let map = crate::utils::hashmap_to_json_map(builder.0);
+
+            assert!(matches!(
+                check_channel_flags(&map, ChannelType::Text, None),
+                Err(Error::Model(ModelError::InvalidChannelFlags(ChannelFlags::REQUIRE_TAG)))
+            ));
+            assert!(check_channel_flags(&map, ChannelType::Forum, None).is_ok());
+
+            assert!(check_channel_flags(&serde_json::Map::new(), ChannelType::Text, None).is_ok());
+        }
+    }
 }
 
 #[cfg(all(feature = "cache", feature = "model", feature = "utils"))]
@@ -0,0 +1,65 @@
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::model::error::Error as ModelError;
+use crate::model::permissions::Permissions;
+
+/// The reason a user may not join a voice or stage channel, as returned by
+/// [`GuildChannel::user_can_connect`].
+///
+/// [`GuildChannel::user_can_connect`]: super::GuildChannel::user_can_connect
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum VoiceJoinError {
+    /// The user cannot see the channel.
+    NotViewable,
+    /// The user lacks the [Connect] permission.
+    ///
+    /// [Connect]: Permissions::CONNECT
+    MissingConnect,
+    /// The user could connect, but would not be able to speak in the channel.
+    MissingSpeak,
+    /// The channel's user limit has been reached.
+    Full,
+    /// The channel is not a voice or stage channel.
+    InvalidChannelType,
+    /// The channel's guild, the user's member or the guild's roles are not
+    /// present in the cache.
+    NotCached,
+}
+
+impl Display for VoiceJoinError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            VoiceJoinError::NotViewable => f.write_str("The channel is not visible to the user."),
+            VoiceJoinError::MissingConnect => {
+                f.write_str("The user may not connect to the channel.")
+            },
+            VoiceJoinError::MissingSpeak => f.write_str("The user may not speak in the channel."),
+            VoiceJoinError::Full => f.write_str("The channel's user limit has been reached."),
+            VoiceJoinError::InvalidChannelType => {
+                f.write_str("The channel is not a voice or stage channel.")
+            },
+            VoiceJoinError::NotCached => {
+                f.write_str("The required data is missing from the cache.")
+            },
+        }
+    }
+}
+
+impl StdError for VoiceJoinError {}
+
+impl From<VoiceJoinError> for ModelError {
+    fn from(error: VoiceJoinError) -> ModelError {
+        match error {
+            VoiceJoinError::NotViewable => {
+                ModelError::InvalidPermissions(Permissions::READ_MESSAGES)
+            },
+            VoiceJoinError::MissingConnect => ModelError::InvalidPermissions(Permissions::CONNECT),
+            VoiceJoinError::MissingSpeak => ModelError::InvalidPermissions(Permissions::SPEAK),
+            VoiceJoinError::Full => ModelError::VoiceChannelFull,
+            VoiceJoinError::InvalidChannelType => ModelError::InvalidChannelType,
+            VoiceJoinError::NotCached => ModelError::ItemMissing,
+        }
+    }
+}
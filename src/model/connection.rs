@@ -1,5 +1,7 @@
 //! Models for user connections.
 
+use std::collections::HashMap;
+
 use super::prelude::*;
 
 /// Information about a connection between the current user and a third party service.
@@ -29,6 +31,25 @@ pub struct Connection {
     pub visibility: ConnectionVisibility,
 }
 
+/// The connection between the current user and an application, shown on the
+/// user's profile and used by the guilds with linked roles of the application.
+///
+/// Updated with [`Http::edit_current_user_application_role_connection`].
+///
+/// [`Http::edit_current_user_application_role_connection`]: crate::http::Http::edit_current_user_application_role_connection
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct ApplicationRoleConnection {
+    /// The vanity name of the platform the application connected.
+    pub platform_name: Option<String>,
+    /// The username of the user on the platform.
+    pub platform_username: Option<String>,
+    /// The values of the role connection metadata keys of the application,
+    /// stringified.
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
 /// The visibility of a user connection on a user's profile.
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
 #[non_exhaustive]
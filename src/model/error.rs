@@ -5,6 +5,20 @@ use std::{
     fmt::{Display, Formatter, Result as FmtResult},
 };
 
+#[cfg(feature = "unstable_discord_api")]
+use super::channel::ChannelFlags;
+#[cfg(feature = "unstable_discord_api")]
+use super::interactions::application_command::ApplicationCommandViolation;
+#[cfg(feature = "unstable_discord_api")]
+use super::interactions::message_component::ComponentViolation;
+#[cfg(feature = "unstable_discord_api")]
+use super::interactions::{
+    InstallationContext,
+    InteractionContext,
+    InteractionResponseType,
+    InteractionType,
+};
+use super::guild::ScheduledEventViolation;
 use super::Permissions;
 
 /// An error returned from the [`model`] module.
@@ -74,6 +88,27 @@ pub enum Error {
     /// Indicates that the textual content of an embed exceeds the maximum
     /// length.
     EmbedTooLarge(usize),
+    /// When attempting to build an embed with over 25 fields.
+    EmbedFieldAmount,
+    /// Indicates that a part of an embed, such as its title or the value of
+    /// one of its fields, exceeds its maximum length.
+    ///
+    /// Returned by [`CreateEmbed::try_build`].
+    ///
+    /// [`CreateEmbed::try_build`]: crate::builder::CreateEmbed::try_build
+    EmbedPartTooLong {
+        /// The name of the part, e.g. `field value`.
+        part: &'static str,
+        /// The number of characters over the limit.
+        overflow: usize,
+    },
+    /// When attempting to send a message with over 5 action rows of
+    /// components.
+    ActionRowAmount,
+    /// When attempting to send a message with over 10 files.
+    FileAmount,
+    /// When attempting to send a message with over 3 stickers.
+    StickerAmount,
     /// An indication that a [guild][`Guild`] could not be found by
     /// [Id][`GuildId`] in the [`Cache`].
     ///
@@ -164,8 +199,92 @@ pub enum Error {
     NotAuthor,
     /// Indicates that the webhook token is missing.
     NoTokenSet,
+    /// Indicates that a webhook was executed with a username which Discord
+    /// does not accept.
+    ///
+    /// Refer to [`ExecuteWebhook::username`] for which usernames are valid.
+    ///
+    /// [`ExecuteWebhook::username`]: crate::builder::ExecuteWebhook::username
+    InvalidWebhookUsername,
     /// Indicates that the component type cannot be used in this context.
     InvalidComponentType,
+    /// Indicates that forum tags were applied to a webhook message which does
+    /// not create a forum post, as no thread name was given.
+    AppliedTagsWithoutThreadName,
+    /// Indicates that a voice or stage channel has reached its user limit.
+    VoiceChannelFull,
+    /// Indicates that channel flags were set on a type of channel which does
+    /// not support them. Contains the flags which cannot be set.
+    ///
+    /// Refer to [`EditChannel::flags`] for which flags are valid.
+    ///
+    /// [`EditChannel::flags`]: crate::builder::EditChannel::flags
+    #[cfg(feature = "unstable_discord_api")]
+    InvalidChannelFlags(ChannelFlags),
+    /// Indicates that a post was created in a forum or media channel which
+    /// requires a tag, without applying any tag.
+    ///
+    /// Refer to [`GuildChannel::requires_tag`] for whether a tag is required.
+    ///
+    /// [`GuildChannel::requires_tag`]: crate::model::channel::GuildChannel::requires_tag
+    #[cfg(feature = "unstable_discord_api")]
+    ForumTagRequired,
+    /// Indicates that an interaction was responded to with a response type
+    /// Discord does not accept for its type of interaction, such as responding
+    /// to a modal submission with another modal.
+    ///
+    /// Refer to [`InteractionResponseType::is_valid_for`] for which response
+    /// types are valid.
+    #[cfg(feature = "unstable_discord_api")]
+    InvalidInteractionResponseType {
+        interaction_kind: InteractionType,
+        response_kind: InteractionResponseType,
+    },
+    /// Indicates that an application command was made available in an
+    /// interaction context which none of its installation contexts support,
+    /// such as a [`InteractionContext::PrivateChannel`] without
+    /// [`InstallationContext::User`].
+    ///
+    /// Refer to [`InteractionContext::required_installation`] for which
+    /// combinations are valid.
+    #[cfg(feature = "unstable_discord_api")]
+    UnsupportedCommandContext {
+        context: InteractionContext,
+        required: InstallationContext,
+    },
+    /// Indicates that an edit of the initial response to an interaction would
+    /// change whether it is ephemeral, which Discord ignores. Contains whether
+    /// the initial response is ephemeral.
+    ///
+    /// Only returned by interactions with `strict_ephemerality` set, such as
+    /// [`ApplicationCommandInteraction::strict_ephemerality`].
+    ///
+    /// [`ApplicationCommandInteraction::strict_ephemerality`]: crate::model::interactions::application_command::ApplicationCommandInteraction::strict_ephemerality
+    #[cfg(feature = "unstable_discord_api")]
+    EphemeralResponseChange(bool),
+    /// Indicates that an application command would be rejected by Discord when
+    /// registering it. Contains every violation which was found.
+    ///
+    /// Refer to [`CreateApplicationCommand::build_check`] for what is checked.
+    ///
+    /// [`CreateApplicationCommand::build_check`]: crate::builder::CreateApplicationCommand::build_check
+    #[cfg(feature = "unstable_discord_api")]
+    InvalidApplicationCommand(Vec<ApplicationCommandViolation>),
+    /// Indicates that the components of a message or interaction response
+    /// would be rejected by Discord. Contains every violation which was found.
+    ///
+    /// Refer to [`CreateComponents::build_check`] for what is checked.
+    ///
+    /// [`CreateComponents::build_check`]: crate::builder::CreateComponents::build_check
+    #[cfg(feature = "unstable_discord_api")]
+    InvalidComponents(Vec<ComponentViolation>),
+    /// Indicates that a scheduled event would be rejected by Discord when
+    /// creating it. Contains every violation which was found.
+    ///
+    /// Refer to [`CreateScheduledEvent::build_check`] for what is checked.
+    ///
+    /// [`CreateScheduledEvent::build_check`]: crate::builder::CreateScheduledEvent::build_check
+    InvalidScheduledEvent(Vec<ScheduledEventViolation>),
 }
 
 impl Error {
@@ -190,6 +309,13 @@ impl Display for Error {
             Error::DeleteMessageDaysAmount(_) => f.write_str("Invalid delete message days."),
             Error::EmbedAmount => f.write_str("Too many embeds in a message."),
             Error::EmbedTooLarge(_) => f.write_str("Embed too large."),
+            Error::EmbedFieldAmount => f.write_str("Too many fields in an embed."),
+            Error::EmbedPartTooLong {
+                ..
+            } => f.write_str("Part of an embed is too long."),
+            Error::ActionRowAmount => f.write_str("Too many action rows in a message."),
+            Error::FileAmount => f.write_str("Too many files in a message."),
+            Error::StickerAmount => f.write_str("Too many stickers in a message."),
             Error::GuildNotFound => f.write_str("Guild not found in the cache."),
             Error::RoleNotFound => f.write_str("Role not found in the cache."),
             Error::MemberNotFound => f.write_str("Member not found in the cache."),
@@ -209,6 +335,34 @@ impl Display for Error {
             Error::NameTooLong => f.write_str("Name is over the character limit."),
             Error::NotAuthor => f.write_str("The bot is not author of this message."),
             Error::NoTokenSet => f.write_str("Token is not set."),
+            Error::InvalidWebhookUsername => f.write_str("Webhook username is not accepted."),
+            Error::AppliedTagsWithoutThreadName => {
+                f.write_str("Forum tags can only be applied when creating a thread.")
+            },
+            Error::VoiceChannelFull => f.write_str("The voice channel is full."),
+            #[cfg(feature = "unstable_discord_api")]
+            Error::InvalidChannelFlags(_) => {
+                f.write_str("Channel flags cannot be set on this type of channel.")
+            },
+            #[cfg(feature = "unstable_discord_api")]
+            Error::ForumTagRequired => f.write_str("The forum requires a tag for new posts."),
+            #[cfg(feature = "unstable_discord_api")]
+            Error::InvalidInteractionResponseType {
+                ..
+            } => f.write_str("Invalid response type for this interaction."),
+            #[cfg(feature = "unstable_discord_api")]
+            Error::UnsupportedCommandContext {
+                ..
+            } => f.write_str("Command context requires an installation context which is not set."),
+            #[cfg(feature = "unstable_discord_api")]
+            Error::EphemeralResponseChange(_) => {
+                f.write_str("Cannot change whether an interaction response is ephemeral.")
+            },
+            #[cfg(feature = "unstable_discord_api")]
+            Error::InvalidApplicationCommand(_) => f.write_str("Application command is invalid."),
+            #[cfg(feature = "unstable_discord_api")]
+            Error::InvalidComponents(_) => f.write_str("Components are invalid."),
+            Error::InvalidScheduledEvent(_) => f.write_str("Scheduled event is invalid."),
         }
     }
 }
@@ -19,7 +19,7 @@ use serde::ser::{Serialize, SerializeSeq, Serializer};
 use super::prelude::*;
 use super::utils::deserialize_emojis;
 #[cfg(feature = "cache")]
-use crate::cache::{Cache, CacheUpdate};
+use crate::cache::{Cache, CacheUpdate, CachedMember};
 use crate::constants::OpCode;
 use crate::internal::prelude::*;
 #[cfg(feature = "unstable_discord_api")]
@@ -85,15 +85,8 @@ impl CacheUpdate for ChannelCreateEvent {
                 }
 
                 let id = {
-                    let user_id = {
-                        cache.update_user_entry(&channel.recipient).await;
-
-                        channel.recipient.id
-                    };
-
-                    if let Some(u) = cache.users.read().await.get(&user_id) {
-                        channel.recipient = u.clone();
-                    }
+                    let user = cache.update_user_entry(&channel.recipient).await;
+                    channel.recipient = User::clone(&user);
 
                     channel.id
                 };
@@ -139,6 +132,10 @@ impl CacheUpdate for ChannelDeleteEvent {
                     .await
                     .get_mut(&guild_id)
                     .map(|g| g.channels.remove(&channel_id));
+
+                if let Some(channels) = cache.voice_channel_users.write().await.get_mut(&guild_id) {
+                    channels.remove(&channel_id);
+                }
             },
             Channel::Category(ref category) => {
                 let channel_id = category.id;
@@ -291,16 +288,20 @@ impl CacheUpdate for GuildCreateEvent {
 
     async fn update(&mut self, cache: &Cache) -> Option<()> {
         cache.unavailable_guilds.write().await.remove(&self.guild.id);
+        cache.notify_ready_changed();
         let mut guild = self.guild.clone();
 
-        for (user_id, member) in &mut guild.members {
-            cache.update_user_entry(&member.user).await;
-            if let Some(u) = cache.user(user_id).await {
-                member.user = u;
+        let mut voice_channel_users = HashMap::<ChannelId, HashSet<UserId>>::new();
+        for voice_state in guild.voice_states.values() {
+            if let Some(channel_id) = voice_state.channel_id {
+                voice_channel_users.entry(channel_id).or_default().insert(voice_state.user_id);
             }
         }
 
+        cache.voice_channel_users.write().await.insert(self.guild.id, voice_channel_users);
         cache.channels.write().await.extend(guild.channels.clone().into_iter());
+        cache.guild_inserted(&guild).await;
+        cache.insert_guild_members(&mut guild).await;
         cache.guilds.write().await.insert(self.guild.id, guild);
 
         None
@@ -336,8 +337,14 @@ impl CacheUpdate for GuildDeleteEvent {
     type Output = Guild;
 
     async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        match cache.guilds.write().await.remove(&self.guild.id) {
-            Some(guild) => {
+        cache.voice_channel_users.write().await.remove(&self.guild.id);
+
+        let removed = cache.guilds.write().await.remove(&self.guild.id);
+
+        match removed {
+            Some(mut guild) => {
+                guild.members = cache.remove_guild_members(guild.id).await;
+
                 for channel_id in guild.channels.keys() {
                     // Remove the channel from the cache.
                     cache.channels.write().await.remove(channel_id);
@@ -346,6 +353,8 @@ impl CacheUpdate for GuildDeleteEvent {
                     cache.messages.write().await.remove(channel_id);
                 }
 
+                cache.guild_removed(guild.id).await;
+
                 Some(guild)
             },
             None => None,
@@ -411,15 +420,19 @@ impl CacheUpdate for GuildMemberAddEvent {
     type Output = ();
 
     async fn update(&mut self, cache: &Cache) -> Option<()> {
-        let user_id = self.member.user.id;
-        cache.update_user_entry(&self.member.user).await;
-        if let Some(u) = cache.user(user_id).await {
-            self.member.user = u;
-        }
+        let in_guild = match cache.guilds.write().await.get_mut(&self.guild_id) {
+            Some(guild) => {
+                guild.member_count += 1;
+                true
+            },
+            None => false,
+        };
 
-        if let Some(guild) = cache.guilds.write().await.get_mut(&self.guild_id) {
-            guild.member_count += 1;
-            guild.members.insert(user_id, self.member.clone());
+        if in_guild {
+            cache.insert_member(self.guild_id, &self.member).await;
+            cache.member_inserted(self.guild_id, &self.member).await;
+        } else {
+            cache.update_user_entry(&self.member.user).await;
         }
 
         None
@@ -458,10 +471,12 @@ impl CacheUpdate for GuildMemberRemoveEvent {
     async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
         if let Some(guild) = cache.guilds.write().await.get_mut(&self.guild_id) {
             guild.member_count -= 1;
-            return guild.members.remove(&self.user.id);
         }
 
-        None
+        let mut members = cache.members.write().await;
+        let member = members.get_mut(&self.guild_id)?.remove(&self.user.id)?;
+
+        Some(member.to_member(self.guild_id))
     }
 }
 
@@ -490,16 +505,23 @@ impl CacheUpdate for GuildMemberUpdateEvent {
     type Output = Member;
 
     async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        cache.update_user_entry(&self.user).await;
+        let user = cache.update_user_entry(&self.user).await;
 
-        if let Some(guild) = cache.guilds.write().await.get_mut(&self.guild_id) {
-            let item = if let Some(member) = guild.members.get_mut(&self.user.id) {
-                let item = Some(member.clone());
+        if !cache.guilds.read().await.contains_key(&self.guild_id) {
+            return None;
+        }
+
+        let (item, member) = {
+            let mut members = cache.members.write().await;
+            let members = members.entry(self.guild_id).or_default();
+
+            let item = if let Some(member) = members.get_mut(&self.user.id) {
+                let item = Some(member.to_member(self.guild_id));
 
                 member.joined_at.clone_from(&Some(self.joined_at));
                 member.nick.clone_from(&self.nick);
                 member.roles.clone_from(&self.roles);
-                member.user.clone_from(&self.user);
+                member.user = user;
                 member.pending.clone_from(&self.pending);
                 member.premium_since.clone_from(&self.premium_since);
                 member.deaf.clone_from(&self.deaf);
@@ -509,31 +531,33 @@ impl CacheUpdate for GuildMemberUpdateEvent {
 
                 item
             } else {
-                None
-            };
-
-            if item.is_none() {
-                guild.members.insert(self.user.id, Member {
+                members.insert(self.user.id, CachedMember {
                     deaf: false,
-                    guild_id: self.guild_id,
                     joined_at: Some(self.joined_at),
                     mute: false,
                     nick: self.nick.clone(),
                     roles: self.roles.clone(),
-                    user: self.user.clone(),
+                    user,
                     pending: self.pending,
                     premium_since: self.premium_since,
                     #[cfg(feature = "unstable_discord_api")]
                     permissions: None,
                     avatar: self.avatar.clone(),
-                    communication_disabled_until: self.communication_disabled_until.clone(),
+                    communication_disabled_until: self.communication_disabled_until,
+                    last_access: Default::default(),
                 });
-            }
 
-            item
-        } else {
-            None
+                None
+            };
+
+            (item, members.get(&self.user.id).map(|member| member.to_member(self.guild_id)))
+        };
+
+        if let Some(member) = member {
+            cache.member_inserted(self.guild_id, &member).await;
         }
+
+        item
     }
 }
 
@@ -553,12 +577,26 @@ impl CacheUpdate for GuildMembersChunkEvent {
     type Output = ();
 
     async fn update(&mut self, cache: &Cache) -> Option<()> {
-        for member in self.members.values() {
-            cache.update_user_entry(&member.user).await;
+        {
+            // The guild is kept from being removed, along with its members,
+            // until the members of the chunk were inserted.
+            let guilds = cache.guilds.read().await;
+
+            if !guilds.contains_key(&self.guild_id) {
+                drop(guilds);
+
+                for member in self.members.values() {
+                    cache.update_user_entry(&member.user).await;
+                }
+
+                return None;
+            }
+
+            cache.insert_members(self.guild_id, self.members.values()).await;
         }
 
-        if let Some(g) = cache.guilds.write().await.get_mut(&self.guild_id) {
-            g.members.extend(self.members.clone());
+        for member in self.members.values() {
+            cache.member_inserted(self.guild_id, member).await;
         }
 
         None
@@ -790,7 +828,11 @@ impl CacheUpdate for GuildUnavailableEvent {
 
     async fn update(&mut self, cache: &Cache) -> Option<()> {
         cache.unavailable_guilds.write().await.insert(self.guild_id);
-        cache.guilds.write().await.remove(&self.guild_id);
+
+        if cache.guilds.write().await.remove(&self.guild_id).is_some() {
+            cache.remove_guild_members(self.guild_id).await;
+            cache.guild_removed(self.guild_id).await;
+        }
 
         None
     }
@@ -805,46 +847,54 @@ pub struct GuildUpdateEvent {
 #[cfg(feature = "cache")]
 #[async_trait]
 impl CacheUpdate for GuildUpdateEvent {
-    type Output = ();
-
-    async fn update(&mut self, cache: &Cache) -> Option<()> {
-        if let Some(guild) = cache.guilds.write().await.get_mut(&self.guild.id) {
-            guild.afk_channel_id.clone_from(&self.guild.afk_channel_id);
-            guild.afk_timeout = self.guild.afk_timeout;
-            guild.banner.clone_from(&self.guild.banner);
-            guild.discovery_splash.clone_from(&self.guild.discovery_splash);
-            guild.features.clone_from(&self.guild.features);
-            guild.icon.clone_from(&self.guild.icon);
-            guild.name.clone_from(&self.guild.name);
-            guild.owner_id.clone_from(&self.guild.owner_id);
-            guild.roles.clone_from(&self.guild.roles);
-            guild.splash.clone_from(&self.guild.splash);
-            guild.vanity_url_code.clone_from(&self.guild.vanity_url_code);
-            guild.welcome_screen.clone_from(&self.guild.welcome_screen);
+    /// The guild as it was cached before the update.
+    type Output = Guild;
 
-            #[allow(deprecated)]
-            {
-                guild.region.clone_from(&self.guild.region);
-            }
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        let mut guilds = cache.guilds.write().await;
+        let guild = guilds.get_mut(&self.guild.id)?;
+        let mut old = guild.clone();
+        old.members = cache.guild_members(old.id).await;
+
+        guild.afk_channel_id.clone_from(&self.guild.afk_channel_id);
+        guild.afk_timeout = self.guild.afk_timeout;
+        guild.banner.clone_from(&self.guild.banner);
+        guild.description.clone_from(&self.guild.description);
+        guild.discovery_splash.clone_from(&self.guild.discovery_splash);
+        guild.features.clone_from(&self.guild.features);
+        guild.icon.clone_from(&self.guild.icon);
+        guild.name.clone_from(&self.guild.name);
+        guild.owner_id.clone_from(&self.guild.owner_id);
+        guild.roles.clone_from(&self.guild.roles);
+        guild.splash.clone_from(&self.guild.splash);
+        guild.vanity_url_code.clone_from(&self.guild.vanity_url_code);
+        guild.welcome_screen.clone_from(&self.guild.welcome_screen);
 
-            guild.default_message_notifications = self.guild.default_message_notifications;
-            guild.max_members = self.guild.max_members;
-            guild.max_presences = self.guild.max_presences;
-            guild.max_video_channel_users = self.guild.max_video_channel_users;
-            guild.mfa_level = self.guild.mfa_level;
-            guild.nsfw_level = self.guild.nsfw_level;
-            guild.premium_subscription_count = self.guild.premium_subscription_count;
-            guild.premium_tier = self.guild.premium_tier;
-            guild.public_updates_channel_id = self.guild.public_updates_channel_id;
-            guild.rules_channel_id = self.guild.rules_channel_id;
-            guild.system_channel_flags = self.guild.system_channel_flags;
-            guild.system_channel_id = self.guild.system_channel_id;
-            guild.verification_level = self.guild.verification_level;
-            guild.widget_channel_id = self.guild.widget_channel_id;
-            guild.widget_enabled = self.guild.widget_enabled;
+        #[allow(deprecated)]
+        {
+            guild.region.clone_from(&self.guild.region);
         }
 
-        None
+        guild.default_message_notifications = self.guild.default_message_notifications;
+        guild.max_members = self.guild.max_members;
+        guild.max_presences = self.guild.max_presences;
+        guild.max_video_channel_users = self.guild.max_video_channel_users;
+        guild.mfa_level = self.guild.mfa_level;
+        guild.nsfw = self.guild.nsfw;
+        guild.nsfw_level = self.guild.nsfw_level;
+        guild.premium_subscription_count = self.guild.premium_subscription_count;
+        guild.premium_tier = self.guild.premium_tier;
+        guild.public_updates_channel_id = self.guild.public_updates_channel_id;
+        guild.rules_channel_id = self.guild.rules_channel_id;
+        guild.system_channel_flags = self.guild.system_channel_flags;
+        guild.system_channel_id = self.guild.system_channel_id;
+        guild.verification_level = self.guild.verification_level;
+        guild.widget_channel_id = self.guild.widget_channel_id;
+        guild.widget_enabled = self.guild.widget_enabled;
+
+        cache.guild_inserted(guild).await;
+
+        Some(old)
     }
 }
 
@@ -865,6 +915,118 @@ impl Serialize for GuildUpdateEvent {
     }
 }
 
+impl GuildUpdateEvent {
+    /// Compares the updated guild with its previous state, e.g. the cached
+    /// [`Guild`] returned by the cache update, and lists the fields which
+    /// changed.
+    ///
+    /// This is equivalent to [`GuildUpdateDiff::new`].
+    #[must_use]
+    pub fn diff(&self, old: &Guild) -> GuildUpdateDiff {
+        GuildUpdateDiff::new(old, &self.guild)
+    }
+}
+
+/// The scalar fields of a guild which changed in a [`GuildUpdateEvent`], as
+/// computed by [`GuildUpdateDiff::new`].
+///
+/// Every field is [`None`] if it is unchanged, or holds the old and the new
+/// value otherwise.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct GuildUpdateDiff {
+    pub afk_channel_id: Option<(Option<ChannelId>, Option<ChannelId>)>,
+    pub afk_timeout: Option<(u64, u64)>,
+    pub banner: Option<(Option<String>, Option<String>)>,
+    pub default_message_notifications:
+        Option<(DefaultMessageNotificationLevel, DefaultMessageNotificationLevel)>,
+    pub description: Option<(Option<String>, Option<String>)>,
+    pub discovery_splash: Option<(Option<String>, Option<String>)>,
+    pub features: Option<(Vec<String>, Vec<String>)>,
+    pub icon: Option<(Option<String>, Option<String>)>,
+    pub max_members: Option<(Option<u64>, Option<u64>)>,
+    pub max_presences: Option<(Option<u64>, Option<u64>)>,
+    pub max_video_channel_users: Option<(Option<u64>, Option<u64>)>,
+    pub mfa_level: Option<(MfaLevel, MfaLevel)>,
+    pub name: Option<(String, String)>,
+    pub nsfw: Option<(bool, bool)>,
+    pub nsfw_level: Option<(NsfwLevel, NsfwLevel)>,
+    pub owner_id: Option<(UserId, UserId)>,
+    pub premium_subscription_count: Option<(u64, u64)>,
+    pub premium_tier: Option<(PremiumTier, PremiumTier)>,
+    pub public_updates_channel_id: Option<(Option<ChannelId>, Option<ChannelId>)>,
+    pub rules_channel_id: Option<(Option<ChannelId>, Option<ChannelId>)>,
+    pub splash: Option<(Option<String>, Option<String>)>,
+    pub system_channel_flags: Option<(SystemChannelFlags, SystemChannelFlags)>,
+    pub system_channel_id: Option<(Option<ChannelId>, Option<ChannelId>)>,
+    pub vanity_url_code: Option<(Option<String>, Option<String>)>,
+    pub verification_level: Option<(VerificationLevel, VerificationLevel)>,
+    pub widget_channel_id: Option<(Option<ChannelId>, Option<ChannelId>)>,
+    pub widget_enabled: Option<(Option<bool>, Option<bool>)>,
+}
+
+impl GuildUpdateDiff {
+    /// Compares the previous state of a guild, e.g. the cached [`Guild`]
+    /// passed to [`EventHandler::guild_update`], with its updated partial
+    /// data.
+    ///
+    /// [`EventHandler::guild_update`]: crate::client::EventHandler::guild_update
+    #[must_use]
+    pub fn new(old: &Guild, new: &PartialGuild) -> Self {
+        fn changed<T: Clone + PartialEq>(old: &T, new: &T) -> Option<(T, T)> {
+            (old != new).then(|| (old.clone(), new.clone()))
+        }
+
+        Self {
+            afk_channel_id: changed(&old.afk_channel_id, &new.afk_channel_id),
+            afk_timeout: changed(&old.afk_timeout, &new.afk_timeout),
+            banner: changed(&old.banner, &new.banner),
+            default_message_notifications: changed(
+                &old.default_message_notifications,
+                &new.default_message_notifications,
+            ),
+            description: changed(&old.description, &new.description),
+            discovery_splash: changed(&old.discovery_splash, &new.discovery_splash),
+            features: changed(&old.features, &new.features),
+            icon: changed(&old.icon, &new.icon),
+            max_members: changed(&old.max_members, &new.max_members),
+            max_presences: changed(&old.max_presences, &new.max_presences),
+            max_video_channel_users: changed(
+                &old.max_video_channel_users,
+                &new.max_video_channel_users,
+            ),
+            mfa_level: changed(&old.mfa_level, &new.mfa_level),
+            name: changed(&old.name, &new.name),
+            nsfw: changed(&old.nsfw, &new.nsfw),
+            nsfw_level: changed(&old.nsfw_level, &new.nsfw_level),
+            owner_id: changed(&old.owner_id, &new.owner_id),
+            premium_subscription_count: changed(
+                &old.premium_subscription_count,
+                &new.premium_subscription_count,
+            ),
+            premium_tier: changed(&old.premium_tier, &new.premium_tier),
+            public_updates_channel_id: changed(
+                &old.public_updates_channel_id,
+                &new.public_updates_channel_id,
+            ),
+            rules_channel_id: changed(&old.rules_channel_id, &new.rules_channel_id),
+            splash: changed(&old.splash, &new.splash),
+            system_channel_flags: changed(&old.system_channel_flags, &new.system_channel_flags),
+            system_channel_id: changed(&old.system_channel_id, &new.system_channel_id),
+            vanity_url_code: changed(&old.vanity_url_code, &new.vanity_url_code),
+            verification_level: changed(&old.verification_level, &new.verification_level),
+            widget_channel_id: changed(&old.widget_channel_id, &new.widget_channel_id),
+            widget_enabled: changed(&old.widget_enabled, &new.widget_enabled),
+        }
+    }
+
+    /// Whether none of the compared fields changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct MessageCreateEvent {
@@ -893,12 +1055,12 @@ impl CacheUpdate for MessageCreateEvent {
         let mut removed_msg = None;
 
         if messages.len() == max {
-            if let Some(id) = queue.pop_front() {
+            if let Some((id, _)) = queue.pop_front() {
                 removed_msg = messages.remove(&id);
             }
         }
 
-        queue.push_back(self.message.id);
+        queue.push_back((self.message.id, cache.minute()));
         messages.insert(self.message.id, self.message.clone());
 
         removed_msg
@@ -1024,14 +1186,10 @@ impl CacheUpdate for PresenceUpdateEvent {
     type Output = ();
 
     async fn update(&mut self, cache: &Cache) -> Option<()> {
-        let user_id = self.presence.user_id;
-
-        if let Some(user) = self.presence.user.as_mut() {
-            cache.update_user_entry(user).await;
-            if let Some(u) = cache.user(user_id).await {
-                *user = u;
-            }
-        }
+        let user = match self.presence.user.as_ref() {
+            Some(user) => Some(cache.update_user_entry(user).await),
+            None => None,
+        };
 
         if let Some(guild_id) = self.guild_id {
             if let Some(guild) = cache.guilds.write().await.get_mut(&guild_id) {
@@ -1044,21 +1202,24 @@ impl CacheUpdate for PresenceUpdateEvent {
 
                 // Create a partial member instance out of the presence update
                 // data.
-                if let Some(user) = self.presence.user.as_ref() {
-                    guild.members.entry(self.presence.user_id).or_insert_with(|| Member {
+                if let Some(user) = user {
+                    let mut members = cache.members.write().await;
+                    let members = members.entry(guild_id).or_default();
+
+                    members.entry(self.presence.user_id).or_insert_with(|| CachedMember {
                         deaf: false,
-                        guild_id,
                         joined_at: None,
                         mute: false,
                         nick: None,
-                        user: user.clone(),
                         roles: vec![],
+                        user,
                         pending: false,
                         premium_since: None,
                         #[cfg(feature = "unstable_discord_api")]
                         permissions: None,
                         avatar: None,
                         communication_disabled_until: None,
+                        last_access: Default::default(),
                     });
                 }
             }
@@ -1214,11 +1375,16 @@ impl CacheUpdate for ReadyEvent {
         for guild in ready.guilds {
             match guild {
                 GuildStatus::Offline(unavailable) => {
-                    cache.guilds.write().await.remove(&unavailable.id);
+                    if cache.guilds.write().await.remove(&unavailable.id).is_some() {
+                        cache.remove_guild_members(unavailable.id).await;
+                        cache.guild_removed(unavailable.id).await;
+                    }
                     cache.unavailable_guilds.write().await.insert(unavailable.id);
                 },
-                GuildStatus::OnlineGuild(guild) => {
+                GuildStatus::OnlineGuild(mut guild) => {
                     cache.unavailable_guilds.write().await.remove(&guild.id);
+                    cache.guild_inserted(&guild).await;
+                    cache.insert_guild_members(&mut guild).await;
                     cache.guilds.write().await.insert(guild.id, guild);
                 },
                 GuildStatus::OnlinePartialGuild(_) => {},
@@ -1238,10 +1404,10 @@ impl CacheUpdate for ReadyEvent {
                 guilds_to_remove.push(*guild);
             }
         }
-        if !guilds_to_remove.is_empty() {
-            let mut handle = cache.guilds.write().await;
-            for guild in guilds_to_remove {
-                handle.remove(&guild);
+        for guild in guilds_to_remove {
+            if cache.guilds.write().await.remove(&guild).is_some() {
+                cache.remove_guild_members(guild).await;
+                cache.guild_removed(guild).await;
             }
         }
 
@@ -1260,6 +1426,9 @@ impl CacheUpdate for ReadyEvent {
         *cache.shard_count.write().await = ready.shard.map_or(1, |s| s[1]);
         *cache.user.write().await = ready.user;
 
+        cache.ready_shards.write().await.insert(self.ready.shard.map_or(0, |s| s[0]));
+        cache.notify_ready_changed();
+
         None
     }
 }
@@ -1316,6 +1485,8 @@ impl CacheUpdate for UserUpdateEvent {
     type Output = CurrentUser;
 
     async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        cache.update_user_entry(&User::from(&self.current_user)).await;
+
         let mut user = cache.user.write().await;
         Some(mem::replace(&mut user, self.current_user.clone()))
     }
@@ -1357,6 +1528,18 @@ impl fmt::Debug for VoiceServerUpdateEvent {
     }
 }
 
+/// Sent when the status of a voice channel changes.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct VoiceChannelStatusUpdateEvent {
+    /// The Id of the voice channel.
+    pub id: ChannelId,
+    /// The Id of the guild the channel is in.
+    pub guild_id: GuildId,
+    /// The new status of the channel, or [`None`] if it was cleared.
+    pub status: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize)]
 #[non_exhaustive]
 pub struct VoiceStateUpdateEvent {
@@ -1373,16 +1556,37 @@ impl CacheUpdate for VoiceStateUpdateEvent {
         if let Some(guild_id) = self.guild_id {
             if let Some(guild) = cache.guilds.write().await.get_mut(&guild_id) {
                 if let Some(member) = &self.voice_state.member {
-                    guild.members.insert(member.user.id, member.clone());
+                    cache.insert_member(guild_id, member).await;
+                    cache.member_inserted(guild_id, member).await;
                 }
 
-                if self.voice_state.channel_id.is_some() {
+                let user_id = self.voice_state.user_id;
+                let old = if self.voice_state.channel_id.is_some() {
                     // Update or add to the voice state list
-                    guild.voice_states.insert(self.voice_state.user_id, self.voice_state.clone())
+                    guild.voice_states.insert(user_id, self.voice_state.clone())
                 } else {
                     // Remove the user from the voice state list
-                    guild.voice_states.remove(&self.voice_state.user_id)
+                    guild.voice_states.remove(&user_id)
+                };
+
+                let mut voice_channel_users = cache.voice_channel_users.write().await;
+                let channels = voice_channel_users.entry(guild_id).or_default();
+
+                if let Some(old_channel_id) = old.as_ref().and_then(|old| old.channel_id) {
+                    if let Some(users) = channels.get_mut(&old_channel_id) {
+                        users.remove(&user_id);
+
+                        if users.is_empty() {
+                            channels.remove(&old_channel_id);
+                        }
+                    }
                 }
+
+                if let Some(channel_id) = self.voice_state.channel_id {
+                    channels.entry(channel_id).or_default().insert(user_id);
+                }
+
+                old
             } else {
                 None
             }
@@ -1485,6 +1689,60 @@ pub struct IntegrationDeleteEvent {
     pub application_id: Option<ApplicationId>,
 }
 
+#[cfg(feature = "unstable_discord_api")]
+#[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
+pub struct EntitlementCreateEvent {
+    pub entitlement: Entitlement,
+}
+
+#[cfg(feature = "unstable_discord_api")]
+impl<'de> Deserialize<'de> for EntitlementCreateEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        let entitlement = Entitlement::deserialize(deserializer)?;
+
+        Ok(Self {
+            entitlement,
+        })
+    }
+}
+
+#[cfg(feature = "unstable_discord_api")]
+#[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
+pub struct EntitlementUpdateEvent {
+    pub entitlement: Entitlement,
+}
+
+#[cfg(feature = "unstable_discord_api")]
+impl<'de> Deserialize<'de> for EntitlementUpdateEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        let entitlement = Entitlement::deserialize(deserializer)?;
+
+        Ok(Self {
+            entitlement,
+        })
+    }
+}
+
+#[cfg(feature = "unstable_discord_api")]
+#[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
+pub struct EntitlementDeleteEvent {
+    pub entitlement: Entitlement,
+}
+
+#[cfg(feature = "unstable_discord_api")]
+impl<'de> Deserialize<'de> for EntitlementDeleteEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        let entitlement = Entitlement::deserialize(deserializer)?;
+
+        Ok(Self {
+            entitlement,
+        })
+    }
+}
+
 #[cfg(feature = "unstable_discord_api")]
 #[derive(Clone, Debug, Serialize)]
 #[non_exhaustive]
@@ -1882,6 +2140,8 @@ pub enum Event {
     VoiceStateUpdate(VoiceStateUpdateEvent),
     /// Voice server information is available
     VoiceServerUpdate(VoiceServerUpdateEvent),
+    /// The status of a voice channel has changed
+    VoiceChannelStatusUpdate(VoiceChannelStatusUpdateEvent),
     /// A webhook for a [channel][`GuildChannel`] was updated in a [`Guild`].
     WebhookUpdate(WebhookUpdateEvent),
     /// An interaction was created.
@@ -1896,6 +2156,15 @@ pub enum Event {
     /// A guild integration was deleted
     #[cfg(feature = "unstable_discord_api")]
     IntegrationDelete(IntegrationDeleteEvent),
+    /// A user subscribed to a SKU or an entitlement was otherwise granted.
+    #[cfg(feature = "unstable_discord_api")]
+    EntitlementCreate(EntitlementCreateEvent),
+    /// An entitlement was updated, e.g. when a subscription renews.
+    #[cfg(feature = "unstable_discord_api")]
+    EntitlementUpdate(EntitlementUpdateEvent),
+    /// An entitlement was deleted.
+    #[cfg(feature = "unstable_discord_api")]
+    EntitlementDelete(EntitlementDeleteEvent),
     /// An application command was created
     #[cfg(feature = "unstable_discord_api")]
     #[deprecated(since = "0.10.10", note = "bots do no receive this event")]
@@ -2226,6 +2495,12 @@ macro_rules! with_related_ids_for_event_types {
                 channel_id: Never,
                 message_id: Never,
             },
+            Self::VoiceChannelStatusUpdate, Self::VoiceChannelStatusUpdate(e) => {
+                user_id: Never,
+                guild_id: Some(e.guild_id),
+                channel_id: Some(e.id),
+                message_id: Never,
+            },
             Self::WebhookUpdate, Self::WebhookUpdate(e) => {
                 user_id: Never,
                 guild_id: Some(e.guild_id),
@@ -2285,6 +2560,27 @@ macro_rules! with_related_ids_for_event_types {
                 message_id: Never,
             },
             #[cfg(feature = "unstable_discord_api")]
+            Self::EntitlementCreate, Self::EntitlementCreate(e) => {
+                user_id: e.entitlement.user_id.into(),
+                guild_id: e.entitlement.guild_id.into(),
+                channel_id: Never,
+                message_id: Never,
+            },
+            #[cfg(feature = "unstable_discord_api")]
+            Self::EntitlementUpdate, Self::EntitlementUpdate(e) => {
+                user_id: e.entitlement.user_id.into(),
+                guild_id: e.entitlement.guild_id.into(),
+                channel_id: Never,
+                message_id: Never,
+            },
+            #[cfg(feature = "unstable_discord_api")]
+            Self::EntitlementDelete, Self::EntitlementDelete(e) => {
+                user_id: e.entitlement.user_id.into(),
+                guild_id: e.entitlement.guild_id.into(),
+                channel_id: Never,
+                message_id: Never,
+            },
+            #[cfg(feature = "unstable_discord_api")]
             Self::ApplicationCommandCreate, Self::ApplicationCommandCreate(e) => {
                 user_id: Never,
                 guild_id: e.application_command.guild_id.into(),
@@ -2414,6 +2710,7 @@ impl Event {
             Self::UserUpdate(_) => EventType::UserUpdate,
             Self::VoiceStateUpdate(_) => EventType::VoiceStateUpdate,
             Self::VoiceServerUpdate(_) => EventType::VoiceServerUpdate,
+            Self::VoiceChannelStatusUpdate(_) => EventType::VoiceChannelStatusUpdate,
             Self::WebhookUpdate(_) => EventType::WebhookUpdate,
             #[cfg(feature = "unstable_discord_api")]
             Self::InteractionCreate(_) => EventType::InteractionCreate,
@@ -2424,6 +2721,12 @@ impl Event {
             #[cfg(feature = "unstable_discord_api")]
             Self::IntegrationDelete(_) => EventType::IntegrationDelete,
             #[cfg(feature = "unstable_discord_api")]
+            Self::EntitlementCreate(_) => EventType::EntitlementCreate,
+            #[cfg(feature = "unstable_discord_api")]
+            Self::EntitlementUpdate(_) => EventType::EntitlementUpdate,
+            #[cfg(feature = "unstable_discord_api")]
+            Self::EntitlementDelete(_) => EventType::EntitlementDelete,
+            #[cfg(feature = "unstable_discord_api")]
             #[allow(deprecated)]
             Self::ApplicationCommandCreate(_) => EventType::ApplicationCommandCreate,
             #[cfg(feature = "unstable_discord_api")]
@@ -2573,6 +2876,9 @@ pub fn deserialize_event_with_type(kind: EventType, v: Value) -> Result<Event> {
         EventType::UserUpdate => Event::UserUpdate(serde_json::from_value(v)?),
         EventType::VoiceServerUpdate => Event::VoiceServerUpdate(serde_json::from_value(v)?),
         EventType::VoiceStateUpdate => Event::VoiceStateUpdate(serde_json::from_value(v)?),
+        EventType::VoiceChannelStatusUpdate => {
+            Event::VoiceChannelStatusUpdate(serde_json::from_value(v)?)
+        },
         EventType::WebhookUpdate => Event::WebhookUpdate(serde_json::from_value(v)?),
         #[cfg(feature = "unstable_discord_api")]
         EventType::InteractionCreate => Event::InteractionCreate(serde_json::from_value(v)?),
@@ -2583,6 +2889,12 @@ pub fn deserialize_event_with_type(kind: EventType, v: Value) -> Result<Event> {
         #[cfg(feature = "unstable_discord_api")]
         EventType::IntegrationDelete => Event::IntegrationDelete(serde_json::from_value(v)?),
         #[cfg(feature = "unstable_discord_api")]
+        EventType::EntitlementCreate => Event::EntitlementCreate(serde_json::from_value(v)?),
+        #[cfg(feature = "unstable_discord_api")]
+        EventType::EntitlementUpdate => Event::EntitlementUpdate(serde_json::from_value(v)?),
+        #[cfg(feature = "unstable_discord_api")]
+        EventType::EntitlementDelete => Event::EntitlementDelete(serde_json::from_value(v)?),
+        #[cfg(feature = "unstable_discord_api")]
         #[allow(deprecated)]
         EventType::ApplicationCommandCreate => {
             Event::ApplicationCommandCreate(serde_json::from_value(v)?)
@@ -2620,7 +2932,7 @@ pub fn deserialize_event_with_type(kind: EventType, v: Value) -> Result<Event> {
 /// A Deserialization implementation is provided for deserializing raw event
 /// dispatch type strings to this enum, e.g. deserializing `"CHANNEL_CREATE"` to
 /// [`EventType::ChannelCreate`].
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 #[non_exhaustive]
 pub enum EventType {
     /// Indicator that a channel create payload was received.
@@ -2767,6 +3079,10 @@ pub enum EventType {
     ///
     /// This maps to [`VoiceServerUpdateEvent`].
     VoiceServerUpdate,
+    /// Indicator that a voice channel status update payload was received.
+    ///
+    /// This maps to [`VoiceChannelStatusUpdateEvent`].
+    VoiceChannelStatusUpdate,
     /// Indicator that a webhook update payload was received.
     ///
     /// This maps to [`WebhookUpdateEvent`].
@@ -2791,6 +3107,21 @@ pub enum EventType {
     /// This maps to [`IntegrationDeleteEvent`].
     #[cfg(feature = "unstable_discord_api")]
     IntegrationDelete,
+    /// Indicator that an entitlement was created.
+    ///
+    /// This maps to [`EntitlementCreateEvent`].
+    #[cfg(feature = "unstable_discord_api")]
+    EntitlementCreate,
+    /// Indicator that an entitlement was updated.
+    ///
+    /// This maps to [`EntitlementUpdateEvent`].
+    #[cfg(feature = "unstable_discord_api")]
+    EntitlementUpdate,
+    /// Indicator that an entitlement was deleted.
+    ///
+    /// This maps to [`EntitlementDeleteEvent`].
+    #[cfg(feature = "unstable_discord_api")]
+    EntitlementDelete,
     /// Indicator that an application command was created.
     ///
     /// This maps to [`ApplicationCommandCreateEvent`].
@@ -2952,6 +3283,7 @@ impl EventType {
     const USER_UPDATE: &'static str = "USER_UPDATE";
     const VOICE_SERVER_UPDATE: &'static str = "VOICE_SERVER_UPDATE";
     const VOICE_STATE_UPDATE: &'static str = "VOICE_STATE_UPDATE";
+    const VOICE_CHANNEL_STATUS_UPDATE: &'static str = "VOICE_CHANNEL_STATUS_UPDATE";
     const WEBHOOKS_UPDATE: &'static str = "WEBHOOKS_UPDATE";
     #[cfg(feature = "unstable_discord_api")]
     const INTERACTION_CREATE: &'static str = "INTERACTION_CREATE";
@@ -2962,6 +3294,12 @@ impl EventType {
     #[cfg(feature = "unstable_discord_api")]
     const INTEGRATION_DELETE: &'static str = "INTEGRATION_DELETE";
     #[cfg(feature = "unstable_discord_api")]
+    const ENTITLEMENT_CREATE: &'static str = "ENTITLEMENT_CREATE";
+    #[cfg(feature = "unstable_discord_api")]
+    const ENTITLEMENT_UPDATE: &'static str = "ENTITLEMENT_UPDATE";
+    #[cfg(feature = "unstable_discord_api")]
+    const ENTITLEMENT_DELETE: &'static str = "ENTITLEMENT_DELETE";
+    #[cfg(feature = "unstable_discord_api")]
     const APPLICATION_COMMAND_CREATE: &'static str = "APPLICATION_COMMAND_CREATE";
     #[cfg(feature = "unstable_discord_api")]
     const APPLICATION_COMMAND_UPDATE: &'static str = "APPLICATION_COMMAND_UPDATE";
@@ -3017,6 +3355,7 @@ impl EventType {
             Self::UserUpdate => Some(Self::USER_UPDATE),
             Self::VoiceServerUpdate => Some(Self::VOICE_SERVER_UPDATE),
             Self::VoiceStateUpdate => Some(Self::VOICE_STATE_UPDATE),
+            Self::VoiceChannelStatusUpdate => Some(Self::VOICE_CHANNEL_STATUS_UPDATE),
             Self::WebhookUpdate => Some(Self::WEBHOOKS_UPDATE),
             #[cfg(feature = "unstable_discord_api")]
             Self::InteractionCreate => Some(Self::INTERACTION_CREATE),
@@ -3027,6 +3366,12 @@ impl EventType {
             #[cfg(feature = "unstable_discord_api")]
             Self::IntegrationDelete => Some(Self::INTEGRATION_DELETE),
             #[cfg(feature = "unstable_discord_api")]
+            Self::EntitlementCreate => Some(Self::ENTITLEMENT_CREATE),
+            #[cfg(feature = "unstable_discord_api")]
+            Self::EntitlementUpdate => Some(Self::ENTITLEMENT_UPDATE),
+            #[cfg(feature = "unstable_discord_api")]
+            Self::EntitlementDelete => Some(Self::ENTITLEMENT_DELETE),
+            #[cfg(feature = "unstable_discord_api")]
             #[allow(deprecated)]
             Self::ApplicationCommandCreate => Some(Self::APPLICATION_COMMAND_CREATE),
             #[cfg(feature = "unstable_discord_api")]
@@ -3109,6 +3454,7 @@ impl<'de> Deserialize<'de> for EventType {
                     EventType::USER_UPDATE => EventType::UserUpdate,
                     EventType::VOICE_SERVER_UPDATE => EventType::VoiceServerUpdate,
                     EventType::VOICE_STATE_UPDATE => EventType::VoiceStateUpdate,
+                    EventType::VOICE_CHANNEL_STATUS_UPDATE => EventType::VoiceChannelStatusUpdate,
                     EventType::WEBHOOKS_UPDATE => EventType::WebhookUpdate,
                     #[cfg(feature = "unstable_discord_api")]
                     EventType::INTERACTION_CREATE => EventType::InteractionCreate,
@@ -3119,6 +3465,12 @@ impl<'de> Deserialize<'de> for EventType {
                     #[cfg(feature = "unstable_discord_api")]
                     EventType::INTEGRATION_DELETE => EventType::IntegrationDelete,
                     #[cfg(feature = "unstable_discord_api")]
+                    EventType::ENTITLEMENT_CREATE => EventType::EntitlementCreate,
+                    #[cfg(feature = "unstable_discord_api")]
+                    EventType::ENTITLEMENT_UPDATE => EventType::EntitlementUpdate,
+                    #[cfg(feature = "unstable_discord_api")]
+                    EventType::ENTITLEMENT_DELETE => EventType::EntitlementDelete,
+                    #[cfg(feature = "unstable_discord_api")]
                     #[allow(deprecated)]
                     EventType::APPLICATION_COMMAND_CREATE => EventType::ApplicationCommandCreate,
                     #[cfg(feature = "unstable_discord_api")]
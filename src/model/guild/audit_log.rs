@@ -1,5 +1,10 @@
+#[cfg(feature = "model")]
+use std::time::Duration;
 use std::{collections::HashMap, fmt, mem::transmute};
 
+#[cfg(feature = "model")]
+use chrono::{DateTime, Utc};
+
 use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
 use serde::ser::Serializer;
 
@@ -323,6 +328,34 @@ pub struct AuditLogs {
     pub users: Vec<User>,
 }
 
+#[cfg(feature = "model")]
+impl AuditLogs {
+    /// Removes and returns the newest entry of the given action type on the
+    /// given target, if it is no older than `max_age` at `now`.
+    pub(crate) fn take_newest_entry(
+        &mut self,
+        action: u8,
+        target_id: u64,
+        now: DateTime<Utc>,
+        max_age: Duration,
+    ) -> Option<AuditLogEntry> {
+        // Entries created after `now`, due to clock skew, count as new.
+        let is_recent = |entry: &AuditLogEntry| {
+            (now - entry.id.created_at()).to_std().map_or(true, |age| age <= max_age)
+        };
+
+        let id = self
+            .entries
+            .values()
+            .filter(|entry| entry.action.num() == action && entry.target_id == Some(target_id))
+            .filter(|entry| is_recent(entry))
+            .map(|entry| entry.id)
+            .max()?;
+
+        self.entries.remove(&id)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[non_exhaustive]
 pub struct AuditLogEntry {
@@ -547,3 +580,61 @@ impl<'de> Deserialize<'de> for AuditLogs {
         de.deserialize_struct("AuditLogs", FIELD, EntriesVisitor)
     }
 }
+
+#[cfg(all(test, feature = "model"))]
+mod test {
+    use std::time::Duration;
+
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    use super::AuditLogs;
+
+    fn entry(id: u64, action_type: u8, target_id: u64) -> serde_json::Value {
+        json!({
+            "id": id.to_string(),
+            "action_type": action_type,
+            "target_id": target_id.to_string(),
+            "user_id": "1",
+            "reason": format!("reason {}", id),
+        })
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_take_newest_entry() {
+        // Snowflakes created at 2021-01-01 00:00, 00:01 and 00:02 UTC.
+        let (old, newer, newest) = (
+            (1_609_459_200_000 - 1_420_070_400_000) << 22,
+            (1_609_459_260_000 - 1_420_070_400_000) << 22,
+            (1_609_459_320_000 - 1_420_070_400_000) << 22,
+        );
+
+        let mut logs: AuditLogs = serde_json::from_value(json!({
+            "audit_log_entries": [
+                entry(old, 22, 10),
+                entry(newer, 22, 10),
+                entry(newest, 22, 11),
+                entry(newest + 1, 20, 10),
+            ],
+            "users": [],
+            "webhooks": [],
+        }))
+        .unwrap();
+
+        let now = Utc.with_ymd_and_hms(2021, 1, 1, 0, 2, 0).unwrap();
+        let max_age = Duration::from_secs(90);
+
+        let found = logs.take_newest_entry(22, 10, now, max_age).unwrap();
+        assert_eq!(found.id.0, newer);
+        assert_eq!(found.reason.as_deref(), Some(&*format!("reason {}", newer)));
+
+        // The old entry is outside of the window.
+        assert!(logs.take_newest_entry(22, 10, now, max_age).is_none());
+        assert!(logs.take_newest_entry(22, 12, now, max_age).is_none());
+
+        // Entries from the future are considered recent.
+        let past = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        assert!(logs.take_newest_entry(22, 11, past, max_age).is_some());
+    }
+}
@@ -1,11 +1,22 @@
 #[cfg(feature = "model")]
+use std::time::Duration;
+
+#[cfg(feature = "model")]
+use chrono::Utc;
+#[cfg(feature = "model")]
 use futures::stream::Stream;
 #[cfg(feature = "model")]
 use serde_json::json;
+#[cfg(all(feature = "model", feature = "tokio_compat", not(feature = "tokio")))]
+use tokio::time::delay_for as sleep;
+#[cfg(all(feature = "model", feature = "tokio"))]
+use tokio::time::sleep;
 
 #[cfg(feature = "model")]
 use crate::builder::CreateChannel;
 #[cfg(feature = "model")]
+use crate::builder::CreateScheduledEvent;
+#[cfg(feature = "model")]
 use crate::builder::{
     AddMember,
     EditGuild,
@@ -20,13 +31,15 @@ use crate::cache::Cache;
 use crate::client::bridge::gateway::ShardMessenger;
 #[cfg(feature = "collector")]
 use crate::collector::{
+    CollectGuildMember,
     CollectReaction,
     CollectReply,
+    GuildMemberCollectorBuilder,
     MessageCollectorBuilder,
     ReactionCollectorBuilder,
 };
 #[cfg(feature = "model")]
-use crate::http::{CacheHttp, Http};
+use crate::http::{AuditLogFilter, CacheHttp, Http, MemberFilter};
 #[cfg(feature = "model")]
 use crate::internal::prelude::*;
 use crate::model::prelude::*;
@@ -41,8 +54,16 @@ use crate::{
         CreateApplicationCommandsPermissions,
     },
     model::interactions::application_command::{ApplicationCommand, ApplicationCommandPermission},
+    model::channel::check_channel_flags,
+    model::interactions::command_sync::{self, CommandSyncOptions, CommandSyncReport},
+    model::interactions::{check_command_contexts, check_command_options},
 };
 
+/// The longest delay between two retries of
+/// [`GuildId::find_ban_audit_entry_with_retry`].
+#[cfg(feature = "model")]
+const MAX_BAN_AUDIT_RETRY_DELAY: Duration = Duration::from_secs(60);
+
 #[cfg(feature = "model")]
 impl GuildId {
     /// Adds a [`User`] to this guild with a valid OAuth2 access token.
@@ -134,7 +155,7 @@ impl GuildId {
             return Err(Error::Model(ModelError::DeleteMessageDaysAmount(dmd)));
         }
 
-        if reason.len() > 512 {
+        if reason.chars().count() > 512 {
             return Err(Error::ExceededLimit(reason.to_string(), 512));
         }
 
@@ -174,9 +195,127 @@ impl GuildId {
         before: Option<AuditLogEntryId>,
         limit: Option<u8>,
     ) -> Result<AuditLogs> {
-        http.as_ref()
-            .get_audit_logs(self.0, action_type, user_id.map(|u| u.0), before.map(|a| a.0), limit)
-            .await
+        let mut filter = AuditLogFilter::new();
+
+        if let Some(action_type) = action_type {
+            filter = filter.action_type(action_type);
+        }
+
+        if let Some(user_id) = user_id {
+            filter = filter.user_id(user_id);
+        }
+
+        if let Some(before) = before {
+            filter = filter.before(before);
+        }
+
+        if let Some(limit) = limit {
+            filter = filter.limit(limit);
+        }
+
+        http.as_ref().get_audit_logs_filtered(self.0, filter).await
+    }
+
+    /// Finds the audit log entry of the ban of the given user, if it was
+    /// created within `max_age`.
+    ///
+    /// This is useful to get the reason and the moderator of a ban when
+    /// handling [`EventHandler::guild_ban_addition`]. If the user was banned
+    /// several times within `max_age`, the newest entry is returned.
+    ///
+    /// **Note**: Requires the [View Audit Log] permission.
+    ///
+    /// **Note**: Discord may dispatch the ban event before the audit log entry
+    /// is written, in which case this returns `None`. Use
+    /// [`Self::find_ban_audit_entry_with_retry`] to wait for the entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission.
+    ///
+    /// [`EventHandler::guild_ban_addition`]: crate::client::EventHandler::guild_ban_addition
+    /// [View Audit Log]: Permissions::VIEW_AUDIT_LOG
+    pub async fn find_ban_audit_entry(
+        self,
+        http: impl AsRef<Http>,
+        user_id: impl Into<UserId>,
+        max_age: Duration,
+    ) -> Result<Option<AuditLogEntry>> {
+        let action = Action::Member(ActionMember::BanAdd).num();
+        let mut logs = self.audit_logs(http, Some(action), None, None, Some(50)).await?;
+
+        Ok(logs.take_newest_entry(action, user_id.into().0, Utc::now(), max_age))
+    }
+
+    /// Finds the audit log entry of the ban of the given user like
+    /// [`Self::find_ban_audit_entry`], retrying up to `retries` times if it
+    /// does not exist yet.
+    ///
+    /// The first retry happens after `delay`, which is doubled for every
+    /// following retry, up to a minute.
+    ///
+    /// # Examples
+    ///
+    /// Announce the reason of a ban:
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "client")]
+    /// # async fn run() {
+    /// use std::time::Duration;
+    ///
+    /// use serenity::model::prelude::*;
+    /// use serenity::prelude::*;
+    ///
+    /// struct Handler;
+    ///
+    /// #[serenity::async_trait]
+    /// impl EventHandler for Handler {
+    ///     async fn guild_ban_addition(&self, ctx: Context, guild_id: GuildId, user: User) {
+    ///         let entry = guild_id
+    ///             .find_ban_audit_entry_with_retry(
+    ///                 &ctx,
+    ///                 user.id,
+    ///                 Duration::from_secs(30),
+    ///                 3,
+    ///                 Duration::from_millis(500),
+    ///             )
+    ///             .await;
+    ///
+    ///         if let Ok(Some(entry)) = entry {
+    ///             let reason = entry.reason.as_deref().unwrap_or("no reason given");
+    ///             println!("{} was banned by {}: {}", user.tag(), entry.user_id, reason);
+    ///         }
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission.
+    pub async fn find_ban_audit_entry_with_retry(
+        self,
+        http: impl AsRef<Http>,
+        user_id: impl Into<UserId>,
+        max_age: Duration,
+        retries: u32,
+        mut delay: Duration,
+    ) -> Result<Option<AuditLogEntry>> {
+        let http = http.as_ref();
+        let user_id = user_id.into();
+
+        for _ in 0..retries {
+            if let Some(entry) = self.find_ban_audit_entry(http, user_id, max_age).await? {
+                return Ok(Some(entry));
+            }
+
+            sleep(delay).await;
+            delay = delay.checked_mul(2).map_or(MAX_BAN_AUDIT_RETRY_DELAY, |delay| {
+                delay.min(MAX_BAN_AUDIT_RETRY_DELAY)
+            });
+        }
+
+        self.find_ban_audit_entry(http, user_id, max_age).await
     }
 
     /// Gets all of the guild's channels over the REST API.
@@ -230,6 +369,9 @@ impl GuildId {
     /// Returns [`Error::Http`] if the current user lacks permission,
     /// or if invalid values are set.
     ///
+    /// Returns a [`ModelError::InvalidChannelFlags`] if [`CreateChannel::flags`]
+    /// sets flags which the type of channel does not support.
+    ///
     /// [Manage Channels]: Permissions::MANAGE_CHANNELS
     #[inline]
     pub async fn create_channel(
@@ -242,6 +384,11 @@ impl GuildId {
 
         let map = utils::hashmap_to_json_map(builder.0);
 
+        #[cfg(feature = "unstable_discord_api")]
+        if let Some(kind) = map.get("type").and_then(|kind| ChannelType::deserialize(kind).ok()) {
+            check_channel_flags(&map, kind, None)?;
+        }
+
         http.as_ref().create_channel(self.0, &map).await
     }
 
@@ -329,12 +476,57 @@ impl GuildId {
         let role = http.as_ref().create_role(self.0, &map).await?;
 
         if let Some(position) = map.get("position").and_then(Value::as_u64) {
-            self.edit_role_position(&http, role.id, position).await?;
+            self.edit_role_position(http.as_ref(), role.id, position).await?;
         }
 
         Ok(role)
     }
 
+    /// Creates a scheduled event in the guild with the data set.
+    ///
+    /// **Note**: Requires the Manage Events permission.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::InvalidScheduledEvent`] if the event would be
+    /// rejected by Discord, as checked by [`CreateScheduledEvent::build_check`].
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission,
+    /// or if invalid data is given.
+    pub async fn create_scheduled_event<F>(
+        self,
+        http: impl AsRef<Http>,
+        f: F,
+    ) -> Result<ScheduledEvent>
+    where
+        F: FnOnce(&mut CreateScheduledEvent) -> &mut CreateScheduledEvent,
+    {
+        let mut builder = CreateScheduledEvent::default();
+        f(&mut builder);
+
+        if let Err(violations) = builder.build_check() {
+            return Err(Error::Model(ModelError::InvalidScheduledEvent(violations)));
+        }
+
+        let map = utils::hashmap_to_json_map(builder.0);
+
+        http.as_ref().create_scheduled_event(self.0, &map).await
+    }
+
+    /// Gets the scheduled events of the guild, optionally with the number of
+    /// users subscribed to each of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the guild is unavailable.
+    pub async fn scheduled_events(
+        self,
+        http: impl AsRef<Http>,
+        with_user_count: bool,
+    ) -> Result<Vec<ScheduledEvent>> {
+        http.as_ref().get_scheduled_events(self.0, with_user_count).await
+    }
+
     /// Deletes the current guild if the current account is the owner of the
     /// guild.
     ///
@@ -559,6 +751,10 @@ impl GuildId {
     /// Edits the order of [`Role`]s
     /// Requires the [Manage Roles] permission.
     ///
+    /// Returns all roles of the guild with their updated positions. If the
+    /// `cache` feature is enabled, the cached guild's roles are replaced with
+    /// them.
+    ///
     /// # Examples
     ///
     /// Change the order of a role:
@@ -574,14 +770,25 @@ impl GuildId {
     ///
     /// [Manage Roles]: Permissions::MANAGE_ROLES
     /// [`Error::Http`]: crate::error::Error::Http
-    #[inline]
     pub async fn edit_role_position(
         self,
-        http: impl AsRef<Http>,
+        cache_http: impl CacheHttp,
         role_id: impl Into<RoleId>,
         position: u64,
     ) -> Result<Vec<Role>> {
-        http.as_ref().edit_role_position(self.0, role_id.into().0, position).await
+        let roles =
+            cache_http.http().edit_role_position(self.0, role_id.into().0, position).await?;
+
+        #[cfg(feature = "cache")]
+        {
+            if let Some(cache) = cache_http.cache() {
+                if let Some(guild) = cache.guilds.write().await.get_mut(&self) {
+                    guild.roles = roles.iter().map(|role| (role.id, role.clone())).collect();
+                }
+            }
+        }
+
+        Ok(roles)
     }
 
     /// Edits the [`GuildWelcomeScreen`].
@@ -842,7 +1049,17 @@ impl GuildId {
         limit: Option<u64>,
         after: impl Into<Option<UserId>>,
     ) -> Result<Vec<Member>> {
-        http.as_ref().get_guild_members(self.0, limit, after.into().map(|x| x.0)).await
+        let mut filter = MemberFilter::new();
+
+        if let Some(limit) = limit {
+            filter = filter.limit(limit);
+        }
+
+        if let Some(after) = after.into() {
+            filter = filter.after(after);
+        }
+
+        http.as_ref().get_guild_members_filtered(self.0, filter).await
     }
 
     /// Streams over all the members in a guild.
@@ -1051,10 +1268,13 @@ impl GuildId {
     ///
     /// # Errors
     ///
-    /// Returns [`Error::Http`] if the current user lacks permission,
-    /// or if an [`Integration`] with that Id does not exist.
+    /// Always returns [`Error::Http`] with [`HttpError::EndpointRemoved`], as
+    /// Discord removed the endpoint.
     ///
     /// [Manage Guild]: Permissions::MANAGE_GUILD
+    /// [`HttpError::EndpointRemoved`]: crate::http::HttpError::EndpointRemoved
+    #[deprecated(note = "Discord removed the integration sync endpoint")]
+    #[allow(deprecated)]
     #[inline]
     pub async fn start_integration_sync(
         self,
@@ -1116,6 +1336,11 @@ impl GuildId {
 
     /// Retrieves the guild's webhooks.
     ///
+    /// This includes the channel follower webhooks, whose
+    /// [`Webhook::source_guild`] and [`Webhook::source_channel`] tell which
+    /// announcement channels the guild's channels follow. Refer to
+    /// [`Webhook::is_follower`].
+    ///
     /// **Note**: Requires the [Manage Webhooks] permission.
     ///
     /// [Manage Webhooks]: Permissions::MANAGE_WEBHOOKS
@@ -1143,6 +1368,38 @@ impl GuildId {
     }
 
     /// Returns a stream builder which can be awaited to obtain a stream of messages in this guild.
+    ///
+    /// # Examples
+    ///
+    /// Collect the next five answers to a question, sent in any channel of
+    /// the guild:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::client::Context;
+    /// # use serenity::model::channel::Message;
+    /// # use serenity::Result;
+    /// #
+    /// # async fn run(ctx: &Context, msg: &Message) -> Result<()> {
+    /// use std::time::Duration;
+    ///
+    /// use serenity::futures::StreamExt;
+    ///
+    /// # let guild_id = msg.guild_id.unwrap();
+    /// msg.channel_id.say(&ctx, "Everyone, what should we play tonight?").await?;
+    ///
+    /// let answers = guild_id
+    ///     .await_replies(&ctx)
+    ///     .filter(|m| !m.author.bot)
+    ///     .collect_limit(5)
+    ///     .timeout(Duration::from_secs(120))
+    ///     .await
+    ///     .collect::<Vec<_>>()
+    ///     .await;
+    ///
+    /// msg.channel_id.say(&ctx, format!("Got {} suggestions.", answers.len())).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     #[cfg(feature = "collector")]
     pub fn await_replies<'a>(
         &self,
@@ -1169,6 +1426,59 @@ impl GuildId {
         ReactionCollectorBuilder::new(shard_messenger).guild_id(self.0)
     }
 
+    /// Await a single member joining this guild.
+    ///
+    /// Call [`CollectGuildMember::left`] to await a member leaving instead.
+    ///
+    /// **Note**: This requires the [`GatewayIntents::GUILD_MEMBERS`] intent.
+    ///
+    /// # Examples
+    ///
+    /// Wait for up to ten minutes for a given user to leave the guild:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::client::Context;
+    /// # use serenity::model::id::{GuildId, UserId};
+    /// #
+    /// # async fn run(ctx: &Context, guild_id: GuildId, user_id: UserId) {
+    /// use std::time::Duration;
+    ///
+    /// let left = guild_id
+    ///     .await_member(&ctx)
+    ///     .user_id(user_id)
+    ///     .joined(false)
+    ///     .left(true)
+    ///     .timeout(Duration::from_secs(600))
+    ///     .await;
+    ///
+    /// if left.is_some() {
+    ///     println!("{} left the guild", user_id);
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`GatewayIntents::GUILD_MEMBERS`]: crate::client::bridge::gateway::GatewayIntents::GUILD_MEMBERS
+    #[cfg(feature = "collector")]
+    pub fn await_member<'a>(
+        &self,
+        shard_messenger: &'a impl AsRef<ShardMessenger>,
+    ) -> CollectGuildMember<'a> {
+        CollectGuildMember::new(shard_messenger).guild_id(self.0)
+    }
+
+    /// Returns a stream builder which can be awaited to obtain a stream of members joining this guild.
+    ///
+    /// **Note**: This requires the [`GatewayIntents::GUILD_MEMBERS`] intent.
+    ///
+    /// [`GatewayIntents::GUILD_MEMBERS`]: crate::client::bridge::gateway::GatewayIntents::GUILD_MEMBERS
+    #[cfg(feature = "collector")]
+    pub fn await_members<'a>(
+        &self,
+        shard_messenger: &'a impl AsRef<ShardMessenger>,
+    ) -> GuildMemberCollectorBuilder<'a> {
+        GuildMemberCollectorBuilder::new(shard_messenger).guild_id(self.0)
+    }
+
     /// Creates a guild specific [`ApplicationCommand`]
     ///
     /// **Note**: Unlike global `ApplicationCommand`s, guild commands will update instantly.
@@ -1188,7 +1498,7 @@ impl GuildId {
     where
         F: FnOnce(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand,
     {
-        let map = ApplicationCommand::build_application_command(f);
+        let map = ApplicationCommand::build_application_command(f)?;
         http.as_ref().create_guild_application_command(self.0, &Value::Object(map)).await
     }
 
@@ -1212,6 +1522,11 @@ impl GuildId {
 
         f(&mut array);
 
+        for command in array.0.iter().filter_map(Value::as_object) {
+            check_command_options(command)?;
+            check_command_contexts(command)?;
+        }
+
         http.as_ref().create_guild_application_commands(self.0, &Value::Array(array.0)).await
     }
 
@@ -1329,7 +1644,7 @@ impl GuildId {
     where
         F: FnOnce(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand,
     {
-        let map = ApplicationCommand::build_application_command(f);
+        let map = ApplicationCommand::build_application_command_edit(f)?;
         http.as_ref()
             .edit_guild_application_command(self.0, command_id.into(), &Value::Object(map))
             .await
@@ -1352,6 +1667,89 @@ impl GuildId {
         http.as_ref().delete_guild_application_command(self.0, command_id.into()).await
     }
 
+    /// Syncs the guild's application commands with the `desired` ones, using
+    /// the default [`CommandSyncOptions`].
+    ///
+    /// Refer to [`Self::sync_commands_with`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::sync_commands_with`].
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn sync_commands(
+        &self,
+        http: impl AsRef<Http>,
+        desired: Vec<CreateApplicationCommand>,
+    ) -> Result<CommandSyncReport> {
+        self.sync_commands_with(http, desired, CommandSyncOptions::default()).await
+    }
+
+    /// Syncs the guild's application commands with the `desired` ones, such as
+    /// to remove the commands of features which were disabled in the guild.
+    ///
+    /// The existing commands are fetched and matched with the desired ones by
+    /// their type and name. Missing commands are created, differing ones are
+    /// edited, and the ones which are not desired anymore are deleted. Commands
+    /// which are already as desired are left alone.
+    ///
+    /// If more commands changed than the [bulk threshold], all of them are
+    /// overwritten with a single request instead, which keeps the Ids of the
+    /// commands which remain. In a [dry run], the changes are only planned.
+    /// Either way, the returned [`CommandSyncReport`] lists every change.
+    ///
+    /// **Note**: Commands are compared by the fields which can be set when
+    /// creating them. Fields Discord adds on its own, such as their version,
+    /// are ignored.
+    ///
+    /// # Examples
+    ///
+    /// Check which commands would change before syncing them:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::http::Http;
+    /// # use std::sync::Arc;
+    /// #
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let http = Arc::new(Http::default());
+    /// use serenity::builder::CreateApplicationCommand;
+    /// use serenity::model::id::GuildId;
+    /// use serenity::model::interactions::command_sync::CommandSyncOptions;
+    ///
+    /// let mut ping = CreateApplicationCommand::default();
+    /// ping.name("ping").description("Replies with pong");
+    ///
+    /// let options = CommandSyncOptions::new().dry_run(true);
+    /// let report = GuildId(7).sync_commands_with(&http, vec![ping], options).await?;
+    ///
+    /// for operation in &report.operations {
+    ///     println!("Planned: {:?}", operation);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::InvalidApplicationCommand`] if a desired command
+    /// would be rejected by Discord, before anything is changed.
+    ///
+    /// Otherwise, if there is an error, it will be either [`Error::Http`] or
+    /// [`Error::Json`]. Changes made before the error are kept.
+    ///
+    /// [bulk threshold]: CommandSyncOptions::bulk_threshold
+    /// [dry run]: CommandSyncOptions::dry_run
+    /// [`Error::Http`]: crate::error::Error::Http
+    /// [`Error::Json`]: crate::error::Error::Json
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn sync_commands_with(
+        &self,
+        http: impl AsRef<Http>,
+        desired: Vec<CreateApplicationCommand>,
+        options: CommandSyncOptions,
+    ) -> Result<CommandSyncReport> {
+        command_sync::sync_guild_commands(http.as_ref(), *self, desired, &options).await
+    }
+
     /// Get all guild application commands permissions only.
     ///
     /// # Errors
@@ -1522,7 +1920,13 @@ impl<H: AsRef<Http>> MembersIter<H> {
         // Number of profiles to fetch
         let grab_size: u64 = 1000;
 
-        self.buffer = self.guild_id.members(&self.http, Some(grab_size), self.after).await?;
+        let mut filter = MemberFilter::new().limit(grab_size);
+
+        if let Some(after) = self.after {
+            filter = filter.after(after);
+        }
+
+        self.buffer = self.http.as_ref().get_guild_members_filtered(self.guild_id.0, filter).await?;
 
         // Get the last member.  If shorter than 1000, there are no more results anyway
         self.after = self.buffer.get(grab_size as usize - 1).map(|member| member.user.id);
@@ -0,0 +1,60 @@
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::model::error::Error as ModelError;
+use crate::model::permissions::Permissions;
+
+/// The reason a member may not moderate another member, as returned by
+/// [`Guild::can_moderate`].
+///
+/// [`Guild::can_moderate`]: super::Guild::can_moderate
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum HierarchyError {
+    /// The acting member lacks some of the required permissions. Contains the
+    /// permissions which are missing.
+    MissingPermission(Permissions),
+    /// The target is the owner of the guild, who cannot be moderated.
+    TargetIsOwner,
+    /// The target's highest role is not below the acting member's highest
+    /// role.
+    TargetHigherRole,
+    /// The acting member and the target are the same member.
+    SelfAction,
+    /// The target's highest role is not below the current user's highest
+    /// role, so the bot itself cannot perform the action, even though the
+    /// acting member could.
+    TargetHigherThanCurrentUser,
+}
+
+impl Display for HierarchyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            HierarchyError::MissingPermission(_) => f.write_str("Missing required permissions."),
+            HierarchyError::TargetIsOwner => f.write_str("The guild owner cannot be moderated."),
+            HierarchyError::TargetHigherRole => {
+                f.write_str("The target's highest role is not below the member's.")
+            },
+            HierarchyError::SelfAction => f.write_str("A member cannot moderate themselves."),
+            HierarchyError::TargetHigherThanCurrentUser => {
+                f.write_str("The target's highest role is not below the current user's.")
+            },
+        }
+    }
+}
+
+impl StdError for HierarchyError {}
+
+impl From<HierarchyError> for ModelError {
+    fn from(error: HierarchyError) -> ModelError {
+        match error {
+            HierarchyError::MissingPermission(permissions) => {
+                ModelError::InvalidPermissions(permissions)
+            },
+            HierarchyError::SelfAction => ModelError::InvalidUser,
+            HierarchyError::TargetIsOwner
+            | HierarchyError::TargetHigherRole
+            | HierarchyError::TargetHigherThanCurrentUser => ModelError::Hierarchy,
+        }
+    }
+}
@@ -1,7 +1,5 @@
 #[cfg(feature = "model")]
 use std::borrow::Cow;
-#[cfg(all(feature = "cache", feature = "model"))]
-use std::cmp::Reverse;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 
 use bitflags::__impl_bitflags;
@@ -64,6 +62,34 @@ pub struct Member {
     pub communication_disabled_until: Option<DateTime<Utc>>,
 }
 
+impl Member {
+    /// Creates a member of the guild with the given ID for the `user`, with all
+    /// other fields defaulted, such as to build on where only the user is
+    /// known.
+    ///
+    /// **Note**: The defaulted fields do not reflect the member's actual state.
+    /// The member has no nickname, roles or guild avatar, is neither deafened
+    /// nor muted, not pending, not boosting, and not timed out, and its
+    /// [`Self::joined_at`] is [`None`].
+    pub fn from_parts(guild_id: GuildId, user: User) -> Self {
+        Self {
+            deaf: false,
+            guild_id,
+            joined_at: None,
+            mute: false,
+            nick: None,
+            roles: Vec::new(),
+            user,
+            pending: false,
+            premium_since: None,
+            #[cfg(feature = "unstable_discord_api")]
+            permissions: None,
+            avatar: None,
+            communication_disabled_until: None,
+        }
+    }
+}
+
 #[cfg(feature = "model")]
 impl Member {
     /// Adds a [`Role`] to the member, editing its roles in-place if the request
@@ -169,19 +195,16 @@ impl Member {
     /// Determines the member's colour.
     #[cfg(feature = "cache")]
     pub async fn colour(&self, cache: impl AsRef<Cache>) -> Option<Colour> {
-        let guild_roles = cache.as_ref().guild_field(self.guild_id, |g| g.roles.clone()).await?;
-
-        let mut roles = self
-            .roles
-            .iter()
-            .filter_map(|role_id| guild_roles.get(role_id))
-            .collect::<Vec<&Role>>();
-
-        roles.sort_by_key(|&b| Reverse(b));
-
-        let default = Colour::default();
-
-        roles.iter().find(|r| r.colour.0 != default.0).map(|r| r.colour)
+        cache
+            .as_ref()
+            .guild_field(self.guild_id, |guild| {
+                guild
+                    .member_roles_sorted(self)
+                    .into_iter()
+                    .find(|role| role.colour.0 != Colour::default().0)
+                    .map(|role| role.colour)
+            })
+            .await?
     }
 
     /// Returns the "default channel" of the guild for the member.
@@ -315,25 +338,11 @@ impl Member {
     pub async fn highest_role_info(&self, cache: impl AsRef<Cache>) -> Option<(RoleId, i64)> {
         let guild_roles = cache.as_ref().guild_field(self.guild_id, |g| g.roles.clone()).await?;
 
-        let mut highest = None;
-
-        for role_id in &self.roles {
-            if let Some(role) = guild_roles.get(role_id) {
-                // Skip this role if this role in iteration has:
-                //
-                // - a position less than the recorded highest
-                // - a position equal to the recorded, but a higher ID
-                if let Some((id, pos)) = highest {
-                    if role.position < pos || (role.position == pos && role.id > id) {
-                        continue;
-                    }
-                }
-
-                highest = Some((role.id, role.position));
-            }
-        }
-
-        highest
+        self.roles
+            .iter()
+            .filter_map(|role_id| guild_roles.get(role_id))
+            .max_by(|a, b| a.position_cmp_fixed(b))
+            .map(|role| (role.id, role.position))
     }
 
     /// Kick the member from the guild.
@@ -405,14 +414,43 @@ impl Member {
         #[cfg(feature = "cache")]
         {
             if let Some(cache) = cache_http.cache() {
-                if let Some(guild) = cache.guilds.read().await.get(&self.guild_id) {
-                    let req = Permissions::KICK_MEMBERS;
-
-                    if !guild.has_perms(&cache_http, req).await {
-                        return Err(Error::Model(ModelError::InvalidPermissions(req)));
+                let current_user_id = cache.current_user_id().await;
+
+                if let Some(owner_id) = cache.guild_field(self.guild_id, |g| g.owner_id).await {
+                    // The owner may kick anyone, and is not required to be a
+                    // cached member.
+                    if owner_id != current_user_id {
+                        let req = Permissions::KICK_MEMBERS;
+                        let current_member = self
+                            .guild_id
+                            .member(&cache_http, current_user_id)
+                            .await
+                            .map_err(|_| Error::Model(ModelError::InvalidPermissions(req)))?;
+
+                        // Only the roles of the guild are read, instead of
+                        // cloning the guild along with its members.
+                        let check = cache
+                            .guild_field(self.guild_id, |guild| {
+                                let permissions =
+                                    guild._member_permission_from_member(&current_member);
+
+                                if !permissions.contains(req) {
+                                    return Err(ModelError::InvalidPermissions(req));
+                                }
+
+                                match guild.greater_hierarchy(self, &current_member) {
+                                    Some(higher) if higher != current_user_id => {
+                                        Err(ModelError::Hierarchy)
+                                    },
+                                    _ => Ok(()),
+                                }
+                            })
+                            .await;
+
+                        if let Some(Err(why)) = check {
+                            return Err(Error::Model(why));
+                        }
                     }
-
-                    guild.check_hierarchy(cache, self.user.id).await?;
                 }
             }
         }
@@ -656,6 +694,36 @@ pub struct PartialMember {
     pub permissions: Option<Permissions>,
 }
 
+impl PartialMember {
+    /// Merges the partial member with the `user` it belongs to into a
+    /// [`Member`] of the guild with the given ID, such as for the author of a
+    /// [`Message`], which is sent separately from its partial member.
+    ///
+    /// The `user` and `guild_id` are used even if [`Self::user`] or
+    /// [`Self::guild_id`] are set.
+    ///
+    /// **Note**: The fields a partial member lacks are defaulted as by
+    /// [`Member::from_parts`], so they must not be trusted:
+    /// [`Member::avatar`] and [`Member::communication_disabled_until`] are
+    /// always [`None`]. Likewise, [`Member::deaf`] and [`Member::mute`] are
+    /// `false` if the payload omitted them, which is the case for messages and
+    /// interactions.
+    pub fn into_member(self, user: User, guild_id: GuildId) -> Member {
+        Member {
+            deaf: self.deaf,
+            joined_at: self.joined_at,
+            mute: self.mute,
+            nick: self.nick,
+            roles: self.roles,
+            pending: self.pending,
+            premium_since: self.premium_since,
+            #[cfg(feature = "unstable_discord_api")]
+            permissions: self.permissions,
+            ..Member::from_parts(guild_id, user)
+        }
+    }
+}
+
 #[cfg(feature = "model")]
 fn avatar_url(guild_id: GuildId, user_id: UserId, hash: Option<&String>) -> Option<String> {
     hash.map(|hash| {
@@ -708,3 +776,44 @@ impl Serialize for ThreadMemberFlags {
         serializer.serialize_u64(self.bits())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{Member, PartialMember};
+    use crate::model::id::{GuildId, RoleId, UserId};
+    use crate::model::user::User;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_partial_member_into_member() {
+        let user: User = serde_json::from_value(json!({
+            "id": "2",
+            "username": "user",
+            "discriminator": "0001",
+            "avatar": null,
+        }))
+        .unwrap();
+        // As sent with a message, without the user and guild ID.
+        let partial: PartialMember = serde_json::from_value(json!({
+            "roles": ["3"],
+            "joined_at": "2021-01-01T00:00:00+00:00",
+            "nick": "nick",
+        }))
+        .unwrap();
+
+        let member = partial.into_member(user.clone(), GuildId(1));
+        assert_eq!(member.guild_id, GuildId(1));
+        assert_eq!(member.user.id, UserId(2));
+        assert_eq!(member.nick.as_deref(), Some("nick"));
+        assert_eq!(member.roles, [RoleId(3)]);
+        assert!(member.joined_at.is_some());
+        assert!(!member.deaf && !member.mute);
+        assert!(member.avatar.is_none());
+
+        let member = Member::from_parts(GuildId(1), user);
+        assert!(member.roles.is_empty());
+        assert!(member.joined_at.is_none());
+    }
+}
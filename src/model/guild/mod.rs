@@ -7,11 +7,13 @@ mod audit_log;
 mod emoji;
 mod guild_id;
 mod guild_preview;
+mod hierarchy;
 mod integration;
 mod member;
 mod partial_guild;
 mod premium_tier;
 mod role;
+mod scheduled_event;
 mod system_channel;
 
 use chrono::{DateTime, Utc};
@@ -30,11 +32,13 @@ pub use self::audit_log::*;
 pub use self::emoji::*;
 pub use self::guild_id::*;
 pub use self::guild_preview::*;
+pub use self::hierarchy::*;
 pub use self::integration::*;
 pub use self::member::*;
 pub use self::partial_guild::*;
 pub use self::premium_tier::*;
 pub use self::role::*;
+pub use self::scheduled_event::*;
 pub use self::system_channel::*;
 use super::utils::*;
 #[cfg(feature = "model")]
@@ -72,6 +76,7 @@ use crate::{
         CreateApplicationCommandsPermissions,
     },
     model::interactions::application_command::{ApplicationCommand, ApplicationCommandPermission},
+    model::interactions::command_sync::{CommandSyncOptions, CommandSyncReport},
 };
 
 /// A representation of a banning of a user.
@@ -811,6 +816,40 @@ impl Guild {
         self.id.delete_application_command(http, command_id).await
     }
 
+    /// Syncs the guild's application commands with the `desired` ones, using
+    /// the default [`CommandSyncOptions`].
+    ///
+    /// Refer to [`GuildId::sync_commands_with`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`GuildId::sync_commands_with`].
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn sync_commands(
+        &self,
+        http: impl AsRef<Http>,
+        desired: Vec<CreateApplicationCommand>,
+    ) -> Result<CommandSyncReport> {
+        self.id.sync_commands(http, desired).await
+    }
+
+    /// Syncs the guild's application commands with the `desired` ones.
+    ///
+    /// Refer to [`GuildId::sync_commands_with`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`GuildId::sync_commands_with`].
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn sync_commands_with(
+        &self,
+        http: impl AsRef<Http>,
+        desired: Vec<CreateApplicationCommand>,
+        options: CommandSyncOptions,
+    ) -> Result<CommandSyncReport> {
+        self.id.sync_commands_with(http, desired, options).await
+    }
+
     /// Get all guild application commands permissions only.
     ///
     /// # Errors
@@ -1185,11 +1224,11 @@ impl Guild {
     #[inline]
     pub async fn edit_role_position(
         &self,
-        http: impl AsRef<Http>,
+        cache_http: impl CacheHttp,
         role_id: impl Into<RoleId>,
         position: u64,
     ) -> Result<Vec<Role>> {
-        self.id.edit_role_position(&http, role_id, position).await
+        self.id.edit_role_position(cache_http, role_id, position).await
     }
 
     /// Edits the [`GuildWelcomeScreen`].
@@ -1290,30 +1329,128 @@ impl Guild {
         let rhs =
             self.members.get(&rhs_id)?.highest_role_info(&cache).await.unwrap_or((RoleId(0), 0));
 
-        // If LHS and RHS both have no top position or have the same role ID,
-        // then no one wins.
-        if (lhs.1 == 0 && rhs.1 == 0) || (lhs.0 == rhs.0) {
+        greater_role_info((lhs_id, lhs), (rhs_id, rhs))
+    }
+
+    /// Returns which of two [`Member`]s has a higher hierarchy, like
+    /// [`Self::greater_member_hierarchy`], but comparing the given members
+    /// with the roles of this guild instead of looking them up in the cache.
+    #[cfg(feature = "cache")]
+    pub(crate) fn greater_hierarchy(&self, lhs: &Member, rhs: &Member) -> Option<UserId> {
+        let (lhs_id, rhs_id) = (lhs.user.id, rhs.user.id);
+
+        if lhs_id == rhs_id {
             return None;
         }
 
-        // If LHS's top position is higher than RHS, then LHS wins.
-        if lhs.1 > rhs.1 {
+        if lhs_id == self.owner_id {
             return Some(lhs_id);
+        } else if rhs_id == self.owner_id {
+            return Some(rhs_id);
         }
 
-        // If RHS's top position is higher than LHS, then RHS wins.
-        if rhs.1 > lhs.1 {
-            return Some(rhs_id);
+        let highest_role_info = |member: &Member| {
+            member
+                .roles
+                .iter()
+                .filter_map(|role_id| self.roles.get(role_id))
+                .max_by(|a, b| a.position_cmp_fixed(b))
+                .map_or((RoleId(0), 0), |role| (role.id, role.position))
+        };
+
+        greater_role_info((lhs_id, highest_role_info(lhs)), (rhs_id, highest_role_info(rhs)))
+    }
+
+    /// Checks whether the `actor` may moderate the `target`, e.g. kick, ban or
+    /// time them out, given the `required` permissions for the action.
+    ///
+    /// The actor must have the `required` permissions and their highest role
+    /// must be above the target's highest role. The guild owner can moderate
+    /// every other member, while nobody can moderate the owner. Roles sharing
+    /// a position are resolved with [`Role::position_cmp_fixed`].
+    ///
+    /// As the action is performed by the current user, its highest role must
+    /// be above the target's as well. This check is skipped if the current
+    /// user's [`Member`] is not present in the guild.
+    ///
+    /// **Note**: The [Administrator] permission grants all permissions, but
+    /// does not allow bypassing the role hierarchy.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`HierarchyError`] describing why the action is not allowed.
+    ///
+    /// [Administrator]: Permissions::ADMINISTRATOR
+    #[cfg(feature = "cache")]
+    pub async fn can_moderate(
+        &self,
+        cache: impl AsRef<Cache>,
+        actor: &Member,
+        target: &Member,
+        required: Permissions,
+    ) -> StdResult<(), HierarchyError> {
+        let current_user_id = cache.as_ref().current_user_id().await;
+
+        self._can_moderate(current_user_id, actor, target, required)
+    }
+
+    #[cfg(feature = "cache")]
+    fn _can_moderate(
+        &self,
+        current_user_id: UserId,
+        actor: &Member,
+        target: &Member,
+        required: Permissions,
+    ) -> StdResult<(), HierarchyError> {
+        if actor.user.id == target.user.id {
+            return Err(HierarchyError::SelfAction);
         }
 
-        // If LHS and RHS both have the same position, but LHS has the lower
-        // role ID, then LHS wins.
-        //
-        // If RHS has the higher role ID, then RHS wins.
-        if lhs.1 == rhs.1 && lhs.0 < rhs.0 {
-            Some(lhs_id)
-        } else {
-            Some(rhs_id)
+        if target.user.id == self.owner_id {
+            return Err(HierarchyError::TargetIsOwner);
+        }
+
+        let missing = required - self._member_permission_from_member(actor);
+
+        if !missing.is_empty() {
+            return Err(HierarchyError::MissingPermission(missing));
+        }
+
+        if !self.outranks(actor, target) {
+            return Err(HierarchyError::TargetHigherRole);
+        }
+
+        if actor.user.id != current_user_id {
+            if let Some(current_member) = self.members.get(&current_user_id) {
+                if !self.outranks(current_member, target) {
+                    return Err(HierarchyError::TargetHigherThanCurrentUser);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether the `lhs` member is the owner of the guild, or has a highest
+    /// role above the `rhs` member's.
+    #[cfg(feature = "cache")]
+    fn outranks(&self, lhs: &Member, rhs: &Member) -> bool {
+        if lhs.user.id == self.owner_id {
+            return true;
+        }
+
+        let highest_role = |member: &Member| {
+            member
+                .roles
+                .iter()
+                .filter_map(|role_id| self.roles.get(role_id))
+                .max_by(|a, b| a.position_cmp_fixed(b))
+        };
+
+        match (highest_role(lhs), highest_role(rhs)) {
+            (Some(lhs), Some(rhs)) => lhs.position_cmp_fixed(rhs) == std::cmp::Ordering::Greater,
+            (Some(_), None) => true,
+            (None, _) => false,
         }
     }
 
@@ -1404,6 +1541,17 @@ impl Guild {
         self.id.invites(cache_http.http()).await
     }
 
+    /// Checks whether system messages of the given kind are suppressed in the
+    /// guild's system channel, such as [`MessageType::MemberJoin`]
+    /// announcements.
+    ///
+    /// Kinds of messages which can not be suppressed always return `false`.
+    pub fn system_messages_suppressed(&self, kind: MessageType) -> bool {
+        let flag = SystemChannelFlags::suppressing(kind);
+
+        !flag.is_empty() && self.system_channel_flags.contains(flag)
+    }
+
     /// Checks if the guild is 'large'. A guild is considered large if it has
     /// more than 250 members.
     #[inline]
@@ -1512,6 +1660,33 @@ impl Guild {
         members
     }
 
+    /// Gets the Ids of all users connected to the given voice channel, based on
+    /// the guild's voice states.
+    ///
+    /// The Ids are sorted, so the order is stable.
+    pub fn voice_channel_members(&self, channel_id: impl Into<ChannelId>) -> Vec<UserId> {
+        let channel_id = channel_id.into();
+
+        let mut members = self
+            .voice_states
+            .values()
+            .filter(|voice_state| voice_state.channel_id == Some(channel_id))
+            .map(|voice_state| voice_state.user_id)
+            .collect::<Vec<_>>();
+        members.sort_unstable();
+
+        members
+    }
+
+    /// Gets the Id of the voice or stage channel the current user is connected
+    /// to in this guild, based on the guild's voice states.
+    #[cfg(feature = "cache")]
+    pub async fn bot_voice_channel(&self, cache: impl AsRef<Cache>) -> Option<ChannelId> {
+        let current_user_id = cache.as_ref().current_user_id().await;
+
+        self.voice_states.get(&current_user_id).and_then(|voice_state| voice_state.channel_id)
+    }
+
     /// Retrieves the first [`Member`] found that matches the name - with an
     /// optional discriminator - provided.
     ///
@@ -1808,6 +1983,74 @@ impl Guild {
         members
     }
 
+    /// Searches the cached [`Member`]s for those whose nickname, global name
+    /// or username matches the `query`, returning up to `limit` of them with
+    /// the best matches first.
+    ///
+    /// Names are compared case-insensitively, folding unicode case, so that
+    /// e.g. "STRASSE" matches "Straße". A name equal to the `query` is the
+    /// best match, followed by a name starting with it, followed by a name
+    /// containing it. For the same kind of match, the nickname is preferred
+    /// over the global name, which is preferred over the username. Members
+    /// matching equally well are ordered by their user ID.
+    ///
+    /// Refer to [`MemberSearchResult::score`] for how the matches are scored.
+    ///
+    /// **Note**: This will only search members that are cached. If you want to
+    /// search all members in the guild via the Http API, use
+    /// [`Self::search_members`].
+    pub fn search_members_by_name(&self, query: &str, limit: usize) -> Vec<MemberSearchResult<'_>> {
+        let query = fold_case(query);
+
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut results = self
+            .members
+            .values()
+            .filter_map(|member| {
+                let names = [
+                    member.nick.as_deref(),
+                    member.user.global_name.as_deref(),
+                    Some(&member.user.name[..]),
+                ];
+
+                names
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(priority, name)| {
+                        let name = (*name)?;
+                        let folded = fold_case(name);
+
+                        let kind = if folded == query {
+                            3
+                        } else if folded.starts_with(&query) {
+                            2
+                        } else if folded.contains(&query) {
+                            1
+                        } else {
+                            return None;
+                        };
+
+                        Some(MemberSearchResult {
+                            member,
+                            name,
+                            score: kind * 3 - priority as u8,
+                        })
+                    })
+                    .max_by_key(|result| result.score)
+            })
+            .collect::<Vec<_>>();
+
+        results.sort_by(|a, b| {
+            b.score.cmp(&a.score).then_with(|| a.member.user.id.cmp(&b.member.user.id))
+        });
+        results.truncate(limit);
+
+        results
+    }
+
     /// Calculate a [`Member`]'s permissions in the guild.
     ///
     /// If member caching is enabled the cache will be checked
@@ -1912,19 +2155,28 @@ impl Guild {
         channel: &GuildChannel,
         member: &Member,
     ) -> Result<Permissions> {
-        Self::_user_permissions_in(channel, member, &self.roles, self.owner_id, self.id)
-    }
-
-    /// Helper function that can also be used from [`PartialGuild`].
+        Self::_user_permissions_in(
+            channel,
+            member.user.id,
+            &member.roles,
+            &self.roles,
+            self.owner_id,
+            self.id,
+        )
+    }
+
+    /// Helper function that can also be used from [`PartialGuild`], and from
+    /// the cache's members, which are not stored as [`Member`]s.
     pub(crate) fn _user_permissions_in(
         channel: &GuildChannel,
-        member: &Member,
+        user_id: UserId,
+        member_roles: &[RoleId],
         roles: &HashMap<RoleId, Role>,
         owner_id: UserId,
         guild_id: GuildId,
     ) -> Result<Permissions> {
         // The owner has all permissions in all cases.
-        if member.user.id == owner_id {
+        if user_id == owner_id {
             return Ok(Self::remove_unnecessary_voice_permissions(channel, Permissions::all()));
         }
 
@@ -1940,11 +2192,11 @@ impl Guild {
         // Create a base set of permissions, starting with `@everyone`s.
         let mut permissions = everyone.permissions;
 
-        for &role in &member.roles {
+        for &role in member_roles {
             if let Some(role) = roles.get(&role) {
                 permissions |= role.permissions;
             } else {
-                error!("{} on {} has non-existent role {:?}", member.user.id, guild_id, role);
+                error!("{} on {} has non-existent role {:?}", user_id, guild_id, role);
                 return Err(Error::Model(ModelError::RoleNotFound));
             }
         }
@@ -1961,12 +2213,12 @@ impl Guild {
         // First apply the denied permission overwrites for each, then apply
         // the allowed.
 
-        let mut data = Vec::with_capacity(member.roles.len());
+        let mut data = Vec::with_capacity(member_roles.len());
 
         // Roles
         for overwrite in &channel.permission_overwrites {
             if let PermissionOverwriteType::Role(role) = overwrite.kind {
-                if role.0 != guild_id.0 && !member.roles.contains(&role) {
+                if role.0 != guild_id.0 && !member_roles.contains(&role) {
                     continue;
                 }
 
@@ -1984,7 +2236,7 @@ impl Guild {
 
         // Member
         for overwrite in &channel.permission_overwrites {
-            if PermissionOverwriteType::Member(member.user.id) != overwrite.kind {
+            if PermissionOverwriteType::Member(user_id) != overwrite.kind {
                 continue;
             }
 
@@ -2219,11 +2471,14 @@ impl Guild {
     ///
     /// # Errors
     ///
-    /// Returns an [`Error::Http`] if the current user does not have permission,
-    /// or if an [`Integration`] with that Id does not exist.
+    /// Always returns an [`Error::Http`] with [`HttpError::EndpointRemoved`],
+    /// as Discord removed the endpoint.
     ///
     /// [Manage Guild]: Permissions::MANAGE_GUILD
     /// [`Error::Http`]: crate::error::Error::Http
+    /// [`HttpError::EndpointRemoved`]: crate::http::HttpError::EndpointRemoved
+    #[deprecated(note = "Discord removed the integration sync endpoint")]
+    #[allow(deprecated)]
     #[inline]
     pub async fn start_integration_sync(
         &self,
@@ -2379,6 +2634,51 @@ impl Guild {
         self.roles.values().find(|role| role_name == role.name)
     }
 
+    /// Returns the managed roles belonging to the bot with the given Id.
+    ///
+    /// Discord creates one such role when a bot joins a guild with
+    /// permissions requested, so this will usually contain at most one role.
+    pub fn roles_for_bot(&self, bot_user_id: impl Into<UserId>) -> Vec<&Role> {
+        let bot_user_id = bot_user_id.into();
+
+        self.roles.values().filter(|role| role.tags.bot_id == Some(bot_user_id)).collect()
+    }
+
+    /// Returns the roles of the given member, ordered from highest to lowest
+    /// in the role hierarchy.
+    ///
+    /// Roles sharing a position are ordered the way Discord resolves them, with
+    /// the lower Id ranking higher. The first role with a colour or with
+    /// [`Role::hoist`] set is therefore the one the client displays.
+    pub fn member_roles_sorted(&self, member: &Member) -> Vec<&Role> {
+        let mut roles =
+            member.roles.iter().filter_map(|role_id| self.roles.get(role_id)).collect::<Vec<_>>();
+
+        roles.sort_by(|a, b| b.position_cmp_fixed(a));
+
+        roles
+    }
+
+    /// Returns the channels in a category whose permissions are not synced
+    /// with it, ordered by their position.
+    ///
+    /// Channels whose category could not be found in the [`Cache`] are not
+    /// included. Refer to [`GuildChannel::permissions_synced`] for more info.
+    #[cfg(feature = "cache")]
+    pub async fn unsynced_channels(&self, cache: impl AsRef<Cache>) -> Vec<&GuildChannel> {
+        let mut channels = Vec::new();
+
+        for channel in self.channels.values() {
+            if channel.permissions_synced(&cache).await == Some(false) {
+                channels.push(channel);
+            }
+        }
+
+        channels.sort_by_key(|channel| (channel.position, channel.id));
+
+        channels
+    }
+
     /// Returns a future that will await one message sent in this guild.
     #[cfg(feature = "collector")]
     pub fn await_reply<'a>(
@@ -2776,6 +3076,15 @@ fn starts_with_case_insensitive(to_look_at: &str, to_find: &str) -> bool {
     to_look_at.to_lowercase().starts_with(&to_find.to_lowercase())
 }
 
+/// Folds the case of a `&str` for case-insensitive comparisons.
+///
+/// Upper-casing before lower-casing maps characters with several lower-case
+/// forms to the same one, such as "ß" and "ss", or "ς" and "σ".
+#[cfg(feature = "model")]
+fn fold_case(s: &str) -> String {
+    s.chars().flat_map(char::to_uppercase).flat_map(char::to_lowercase).collect()
+}
+
 /// Takes a `&str` as `origin` and tests if either
 /// `word_a` or `word_b` is closer.
 ///
@@ -2798,6 +3107,24 @@ fn closest_to_origin(origin: &str, word_a: &str, word_b: &str) -> std::cmp::Orde
     value_a.cmp(&value_b)
 }
 
+/// A [`Member`] found by [`Guild::search_members_by_name`].
+#[cfg(feature = "model")]
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct MemberSearchResult<'a> {
+    /// The member which was found.
+    pub member: &'a Member,
+    /// The name of the member which matched the query best.
+    pub name: &'a str,
+    /// How well the name matched the query, from `1` to `9`, higher being
+    /// better.
+    ///
+    /// A name equal to the query scores `7` to `9`, a name starting with it
+    /// scores `4` to `6`, and a name containing it scores `1` to `3`. Within
+    /// each of these, the nickname scores highest and the username lowest.
+    pub score: u8,
+}
+
 /// A container for guilds.
 ///
 /// This is used to differentiate whether a guild itself can be used or whether
@@ -3215,6 +3542,40 @@ enum_number!(NsfwLevel {
     AgeRestricted
 });
 
+/// Returns which of two users has the higher of the given highest roles, as
+/// described by [`Guild::greater_member_hierarchy`].
+#[cfg(all(feature = "cache", feature = "model"))]
+fn greater_role_info(
+    (lhs_id, lhs): (UserId, (RoleId, i64)),
+    (rhs_id, rhs): (UserId, (RoleId, i64)),
+) -> Option<UserId> {
+    // If LHS and RHS both have no top position or have the same role ID,
+    // then no one wins.
+    if (lhs.1 == 0 && rhs.1 == 0) || (lhs.0 == rhs.0) {
+        return None;
+    }
+
+    // If LHS's top position is higher than RHS, then LHS wins.
+    if lhs.1 > rhs.1 {
+        return Some(lhs_id);
+    }
+
+    // If RHS's top position is higher than LHS, then RHS wins.
+    if rhs.1 > lhs.1 {
+        return Some(rhs_id);
+    }
+
+    // If LHS and RHS both have the same position, but LHS has the lower
+    // role ID, then LHS wins.
+    //
+    // If RHS has the higher role ID, then RHS wins.
+    if lhs.1 == rhs.1 && lhs.0 < rhs.0 {
+        Some(lhs_id)
+    } else {
+        Some(rhs_id)
+    }
+}
+
 #[cfg(test)]
 mod test {
     #[cfg(feature = "model")]
@@ -3342,5 +3703,319 @@ mod test {
 
             assert_eq!(lhs, gen_member().display_name());
         }
+
+        #[test]
+        fn system_messages_suppressed() {
+            let mut guild = gen();
+            assert!(!guild.system_messages_suppressed(MessageType::MemberJoin));
+
+            guild.system_channel_flags = SystemChannelFlags::SUPPRESS_JOIN_NOTIFICATIONS
+                | SystemChannelFlags::SUPPRESS_PREMIUM_SUBSCRIPTIONS;
+            assert!(guild.system_messages_suppressed(MessageType::MemberJoin));
+            assert!(guild.system_messages_suppressed(MessageType::NitroTier2));
+            assert!(!guild.system_messages_suppressed(MessageType::GuildInviteReminder));
+            assert!(!guild.system_messages_suppressed(MessageType::Regular));
+        }
+
+        fn gen_named_member(
+            id: u64,
+            name: &str,
+            global_name: Option<&str>,
+            nick: Option<&str>,
+        ) -> Member {
+            let mut member = gen_member();
+            member.user.id = UserId(id);
+            member.user.name = name.to_string();
+            member.user.global_name = global_name.map(ToString::to_string);
+            member.nick = nick.map(ToString::to_string);
+
+            member
+        }
+
+        fn search(guild: &Guild, query: &str, limit: usize) -> Vec<(u64, u8)> {
+            guild
+                .search_members_by_name(query, limit)
+                .into_iter()
+                .map(|result| (result.member.user.id.0, result.score))
+                .collect()
+        }
+
+        #[test]
+        fn search_members_by_name() {
+            let mut guild = gen();
+            guild.members.clear();
+            for member in vec![
+                gen_named_member(1, "joseph", None, None),
+                gen_named_member(2, "mojo", None, None),
+                gen_named_member(3, "someone", Some("Jo"), None),
+                gen_named_member(4, "other", None, Some("JOHN")),
+                gen_named_member(5, "jo", None, Some("nobody")),
+                gen_named_member(6, "unrelated", Some("Nobody"), None),
+            ] {
+                guild.members.insert(member.user.id, member);
+            }
+
+            assert_eq!(search(&guild, "jo", 10), vec![(3, 8), (5, 7), (4, 6), (1, 4), (2, 1)]);
+            assert_eq!(search(&guild, "jo", 2), vec![(3, 8), (5, 7)]);
+            assert_eq!(search(&guild, "body", 10), vec![(5, 3), (6, 2)]);
+            assert!(search(&guild, "", 10).is_empty());
+            assert!(search(&guild, "xyz", 10).is_empty());
+
+            let result = guild.search_members_by_name("JOH", 1).remove(0);
+            assert_eq!(result.name, "JOHN");
+        }
+
+        #[test]
+        fn search_members_by_name_unicode() {
+            let mut guild = gen();
+            guild.members.clear();
+            for member in vec![
+                gen_named_member(1, "straße", None, None),
+                gen_named_member(2, "user", Some("ΟΔΥΣΣΕΥΣ"), None),
+                gen_named_member(3, "émile", None, None),
+            ] {
+                guild.members.insert(member.user.id, member);
+            }
+
+            assert_eq!(search(&guild, "STRASSE", 10), vec![(1, 7)]);
+            assert_eq!(search(&guild, "Straß", 10), vec![(1, 4)]);
+            assert_eq!(search(&guild, "οδυσσευς", 10), vec![(2, 8)]);
+            assert_eq!(search(&guild, "ÉMI", 10), vec![(3, 4)]);
+        }
+
+        #[test]
+        fn search_members_by_name_ties() {
+            let mut guild = gen();
+            guild.members.clear();
+            for &id in &[30, 10, 20] {
+                let member = gen_named_member(id, "twin", None, None);
+                guild.members.insert(member.user.id, member);
+            }
+
+            assert_eq!(search(&guild, "TWIN", 10), vec![(10, 7), (20, 7), (30, 7)]);
+            assert_eq!(search(&guild, "tw", 1), vec![(10, 4)]);
+        }
+
+        #[cfg(feature = "cache")]
+        #[allow(clippy::unwrap_used)]
+        fn gen_role(id: u64, position: i64, permissions: Permissions) -> Role {
+            serde_json::from_value(serde_json::json!({
+                "id": id.to_string(),
+                "guild_id": "1",
+                "color": 0,
+                "hoist": false,
+                "managed": false,
+                "name": "role",
+                "permissions": permissions.bits().to_string(),
+                "position": position,
+            }))
+            .unwrap()
+        }
+
+        #[cfg(feature = "cache")]
+        fn gen_member_with(id: u64, roles: &[u64]) -> Member {
+            let mut member = gen_member();
+            member.user.id = UserId(id);
+            member.roles = roles.iter().copied().map(RoleId).collect();
+
+            member
+        }
+
+        #[test]
+        #[cfg(feature = "cache")]
+        fn can_moderate() {
+            const OWNER: u64 = 210;
+            const BOT: u64 = 999;
+            const EVERYONE: u64 = 1;
+            const MODERATOR: u64 = 10;
+            const TWIN: u64 = 11;
+            const ADMIN: u64 = 20;
+            const HIGH: u64 = 30;
+            const BOT_ROLE: u64 = 40;
+            const TOP: u64 = 50;
+
+            let kick = Permissions::KICK_MEMBERS;
+
+            let mut guild = gen();
+            for role in vec![
+                gen_role(EVERYONE, 0, Permissions::empty()),
+                gen_role(MODERATOR, 5, kick),
+                gen_role(TWIN, 5, kick),
+                gen_role(ADMIN, 2, Permissions::ADMINISTRATOR),
+                gen_role(HIGH, 8, Permissions::empty()),
+                gen_role(BOT_ROLE, 6, Permissions::ADMINISTRATOR),
+                gen_role(TOP, 9, kick),
+            ] {
+                guild.roles.insert(role.id, role);
+            }
+            guild.members.insert(UserId(BOT), gen_member_with(BOT, &[BOT_ROLE]));
+
+            let cases = [
+                // The owner bypasses permissions and hierarchy.
+                (OWNER, &[][..], 2, &[MODERATOR][..], Ok(())),
+                (1, &[TOP], OWNER, &[], Err(HierarchyError::TargetIsOwner)),
+                (1, &[TOP], 1, &[TOP], Err(HierarchyError::SelfAction)),
+                (1, &[], 2, &[], Err(HierarchyError::MissingPermission(kick))),
+                (1, &[HIGH], 2, &[], Err(HierarchyError::MissingPermission(kick))),
+                (1, &[MODERATOR], 2, &[], Ok(())),
+                // Equal top roles.
+                (1, &[MODERATOR], 2, &[MODERATOR], Err(HierarchyError::TargetHigherRole)),
+                // Equal positions resolve to the lower Id.
+                (1, &[MODERATOR], 2, &[TWIN], Ok(())),
+                (1, &[TWIN], 2, &[MODERATOR], Err(HierarchyError::TargetHigherRole)),
+                // Administrators still need a higher role.
+                (1, &[ADMIN], 2, &[], Ok(())),
+                (1, &[ADMIN], 2, &[MODERATOR], Err(HierarchyError::TargetHigherRole)),
+                // The current user's role is too low, but the actor's is not.
+                (1, &[TOP], 2, &[HIGH], Err(HierarchyError::TargetHigherThanCurrentUser)),
+                (BOT, &[BOT_ROLE], 2, &[HIGH], Err(HierarchyError::TargetHigherRole)),
+                (1, &[TOP], BOT, &[BOT_ROLE], Err(HierarchyError::TargetHigherThanCurrentUser)),
+            ];
+
+            for &(actor_id, actor_roles, target_id, target_roles, expected) in cases.iter() {
+                let actor = gen_member_with(actor_id, actor_roles);
+                let target = gen_member_with(target_id, target_roles);
+
+                assert_eq!(
+                    guild._can_moderate(UserId(BOT), &actor, &target, kick),
+                    expected,
+                    "actor {:?} with roles {:?}, target {:?} with roles {:?}",
+                    actor_id,
+                    actor_roles,
+                    target_id,
+                    target_roles,
+                );
+            }
+        }
+
+        #[cfg(feature = "cache")]
+        #[allow(clippy::unwrap_used)]
+        fn gen_channel(id: u64, kind: u8, user_limit: u64) -> GuildChannel {
+            serde_json::from_value(serde_json::json!({
+                "id": id.to_string(),
+                "guild_id": "1",
+                "type": kind,
+                "name": "channel",
+                "position": 0,
+                "permission_overwrites": [],
+                "user_limit": user_limit,
+            }))
+            .unwrap()
+        }
+
+        #[cfg(feature = "cache")]
+        fn gen_voice_state(user_id: u64, channel_id: u64) -> VoiceState {
+            VoiceState {
+                channel_id: Some(ChannelId(channel_id)),
+                deaf: false,
+                guild_id: Some(GuildId(1)),
+                member: None,
+                mute: false,
+                self_deaf: false,
+                self_mute: false,
+                self_stream: None,
+                self_video: false,
+                session_id: String::new(),
+                suppress: false,
+                token: None,
+                user_id: UserId(user_id),
+                request_to_speak_timestamp: None,
+            }
+        }
+
+        #[tokio::test]
+        #[cfg(feature = "cache")]
+        #[allow(clippy::unwrap_used)]
+        async fn user_can_connect() {
+            use crate::cache::{Cache, CacheUpdate};
+
+            const EVERYONE: u64 = 1;
+            const VIEW: u64 = 2;
+            const CONNECT: u64 = 3;
+            const SPEAK: u64 = 4;
+            const MOVE: u64 = 5;
+            const REQUEST: u64 = 6;
+
+            const FULL: u64 = 100;
+            const STAGE: u64 = 200;
+            const TEXT: u64 = 300;
+            const OPEN: u64 = 400;
+
+            let members: &[(u64, &[u64])] = &[
+                (1, &[]),
+                (2, &[VIEW]),
+                (3, &[VIEW, CONNECT]),
+                (4, &[VIEW, CONNECT, SPEAK]),
+                (5, &[VIEW, CONNECT, SPEAK, MOVE]),
+                (6, &[VIEW, CONNECT, REQUEST]),
+                (50, &[VIEW, CONNECT, SPEAK]),
+                (51, &[VIEW, CONNECT, SPEAK]),
+            ];
+
+            let mut guild = gen();
+            for role in vec![
+                gen_role(EVERYONE, 0, Permissions::empty()),
+                gen_role(VIEW, 1, Permissions::READ_MESSAGES),
+                gen_role(CONNECT, 2, Permissions::CONNECT),
+                gen_role(SPEAK, 3, Permissions::SPEAK),
+                gen_role(MOVE, 4, Permissions::MOVE_MEMBERS),
+                gen_role(REQUEST, 5, Permissions::REQUEST_TO_SPEAK),
+            ] {
+                guild.roles.insert(role.id, role);
+            }
+            for &(id, roles) in members {
+                guild.members.insert(UserId(id), gen_member_with(id, roles));
+            }
+            for channel in vec![
+                gen_channel(FULL, 2, 2),
+                gen_channel(STAGE, 13, 0),
+                gen_channel(TEXT, 0, 0),
+                gen_channel(OPEN, 2, 0),
+            ] {
+                guild.channels.insert(channel.id, channel);
+            }
+            guild.voice_states.insert(UserId(50), gen_voice_state(50, FULL));
+            guild.voice_states.insert(UserId(51), gen_voice_state(51, FULL));
+
+            let cache = Cache::default();
+            let mut guild_create = GuildCreateEvent {
+                guild,
+            };
+            assert!(cache.update(&mut guild_create).await.is_none());
+
+            let cases = [
+                (1, OPEN, Err(VoiceJoinError::NotViewable)),
+                (2, OPEN, Err(VoiceJoinError::MissingConnect)),
+                (3, OPEN, Err(VoiceJoinError::MissingSpeak)),
+                (4, OPEN, Ok(())),
+                (4, TEXT, Err(VoiceJoinError::InvalidChannelType)),
+                (99, OPEN, Err(VoiceJoinError::NotCached)),
+                // The user limit is bypassed by moving members, or being connected already.
+                (4, FULL, Err(VoiceJoinError::Full)),
+                (5, FULL, Ok(())),
+                (50, FULL, Ok(())),
+                // Stage channels need a way to become a speaker.
+                (3, STAGE, Err(VoiceJoinError::MissingSpeak)),
+                (4, STAGE, Err(VoiceJoinError::MissingSpeak)),
+                (6, STAGE, Ok(())),
+            ];
+
+            for &(user_id, channel_id, expected) in cases.iter() {
+                let channel = cache.guild_channel(channel_id).await.unwrap();
+
+                assert_eq!(
+                    channel.user_can_connect(&cache, user_id).await,
+                    expected,
+                    "user {:?} in channel {:?}",
+                    user_id,
+                    channel_id,
+                );
+            }
+
+            let guild = cache.guild(1).await.unwrap();
+            assert_eq!(guild.bot_voice_channel(&cache).await, None);
+            cache.user.write().await.id = UserId(50);
+            assert_eq!(guild.bot_voice_channel(&cache).await, Some(ChannelId(FULL)));
+        }
     }
 }
@@ -38,6 +38,7 @@ use crate::{
         CreateApplicationCommandsPermissions,
     },
     model::interactions::application_command::{ApplicationCommand, ApplicationCommandPermission},
+    model::interactions::command_sync::{CommandSyncOptions, CommandSyncReport},
 };
 
 /// Partial information about a [`Guild`]. This does not include information
@@ -525,6 +526,40 @@ impl PartialGuild {
         self.id.delete_application_command(http, command_id).await
     }
 
+    /// Syncs the guild's application commands with the `desired` ones, using
+    /// the default [`CommandSyncOptions`].
+    ///
+    /// Refer to [`GuildId::sync_commands_with`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`GuildId::sync_commands_with`].
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn sync_commands(
+        &self,
+        http: impl AsRef<Http>,
+        desired: Vec<CreateApplicationCommand>,
+    ) -> Result<CommandSyncReport> {
+        self.id.sync_commands(http, desired).await
+    }
+
+    /// Syncs the guild's application commands with the `desired` ones.
+    ///
+    /// Refer to [`GuildId::sync_commands_with`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`GuildId::sync_commands_with`].
+    #[cfg(feature = "unstable_discord_api")]
+    pub async fn sync_commands_with(
+        &self,
+        http: impl AsRef<Http>,
+        desired: Vec<CreateApplicationCommand>,
+        options: CommandSyncOptions,
+    ) -> Result<CommandSyncReport> {
+        self.id.sync_commands_with(http, desired, options).await
+    }
+
     /// Get all guild application commands permissions only.
     ///
     /// # Errors
@@ -821,11 +856,11 @@ impl PartialGuild {
     #[inline]
     pub async fn edit_role_position(
         &self,
-        http: impl AsRef<Http>,
+        cache_http: impl CacheHttp,
         role_id: impl Into<RoleId>,
         position: u64,
     ) -> Result<Vec<Role>> {
-        self.id.edit_role_position(&http, role_id, position).await
+        self.id.edit_role_position(cache_http, role_id, position).await
     }
 
     /// Edits the [`GuildWelcomeScreen`].
@@ -1288,7 +1323,14 @@ impl PartialGuild {
         channel: &GuildChannel,
         member: &Member,
     ) -> Result<Permissions> {
-        Guild::_user_permissions_in(channel, member, &self.roles, self.owner_id, self.id)
+        Guild::_user_permissions_in(
+            channel,
+            member.user.id,
+            &member.roles,
+            &self.roles,
+            self.owner_id,
+            self.id,
+        )
     }
 
     /// Calculate a [`Role`]'s permissions in a given channel in the guild.
@@ -1379,6 +1421,8 @@ impl PartialGuild {
     ///
     /// [Manage Guild]: Permissions::MANAGE_GUILD
     /// [`Guild::start_integration_sync`]: crate::model::guild::Guild::start_integration_sync
+    #[deprecated(note = "Discord removed the integration sync endpoint")]
+    #[allow(deprecated)]
     #[inline]
     pub async fn start_integration_sync(
         &self,
@@ -2,7 +2,6 @@ use std::cmp::Ordering;
 
 #[cfg(all(feature = "cache", feature = "model", feature = "utils"))]
 use async_trait::async_trait;
-use serde::de::{Deserialize, Deserializer, Error as DeError};
 
 #[cfg(feature = "model")]
 use crate::builder::EditRole;
@@ -12,11 +11,13 @@ use crate::cache::Cache;
 use crate::cache::FromStrAndCache;
 #[cfg(feature = "model")]
 use crate::http::Http;
+use crate::internal::is_false;
 #[cfg(all(feature = "cache", feature = "model"))]
 use crate::internal::prelude::*;
 #[cfg(all(feature = "cache", feature = "model", feature = "utils"))]
 use crate::model::misc::RoleParseError;
 use crate::model::prelude::*;
+use crate::model::utils::{deserialize_presence_as_bool, serialize_presence_as_bool};
 #[cfg(all(feature = "cache", feature = "model", feature = "utils"))]
 use crate::utils::parse_role;
 
@@ -155,6 +156,48 @@ impl Role {
             self.permissions.contains(permissions)
         }
     }
+
+    /// Whether this is the managed role of a bot, created when the bot joined
+    /// the guild.
+    #[inline]
+    pub fn is_bot_role(&self) -> bool {
+        self.tags.bot_id.is_some()
+    }
+
+    /// Whether this role is managed by an [`Integration`], such as Twitch or
+    /// YouTube subscriptions.
+    ///
+    /// [`Integration`]: super::Integration
+    #[inline]
+    pub fn is_integration_role(&self) -> bool {
+        self.tags.integration_id.is_some()
+    }
+
+    /// Whether this is the guild's "Server Booster" role, given to members who
+    /// boost the guild.
+    #[inline]
+    pub fn is_booster_role(&self) -> bool {
+        self.tags.premium_subscriber
+    }
+
+    /// Whether this role is granted through a paid role subscription.
+    #[inline]
+    pub fn is_subscription_role(&self) -> bool {
+        self.tags.subscription_listing_id.is_some()
+    }
+
+    /// Compares the roles' places in the guild's role hierarchy, the way
+    /// Discord resolves them.
+    ///
+    /// The role with the higher position is greater. If both roles share a
+    /// position, the one with the lower Id ranks higher.
+    ///
+    /// This differs from the [`Ord`] implementation of [`Role`], which ranks
+    /// the role with the higher Id higher, and is kept as is for compatibility.
+    #[must_use]
+    pub fn position_cmp_fixed(&self, other: &Role) -> Ordering {
+        self.position.cmp(&other.position).then_with(|| other.id.cmp(&self.id))
+    }
 }
 
 impl Display for Role {
@@ -191,6 +234,21 @@ impl PartialOrd for Role {
 
 #[cfg(feature = "model")]
 impl RoleId {
+    /// Requests the [`Role`] from the REST API.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the role does not exist in the guild, or if
+    /// the current user is not in the guild.
+    #[inline]
+    pub async fn to_role(
+        self,
+        http: impl AsRef<Http>,
+        guild_id: impl Into<GuildId>,
+    ) -> Result<Role> {
+        http.as_ref().get_guild_role(guild_id.into().0, self.0).await
+    }
+
     /// Tries to find the [`Role`] by its Id in the cache.
     #[cfg(feature = "cache")]
     pub async fn to_role_cached(self, cache: impl AsRef<Cache>) -> Option<Role> {
@@ -242,48 +300,127 @@ impl FromStrAndCache for Role {
 }
 
 /// The tags of a [`Role`].
-#[derive(Clone, Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[non_exhaustive]
 pub struct RoleTags {
     /// The Id of the bot the [`Role`] belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub bot_id: Option<UserId>,
     /// The Id of the integration the [`Role`] belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub integration_id: Option<IntegrationId>,
     /// Whether this is the guild's premium subscriber role.
-    #[serde(default)]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_presence_as_bool",
+        serialize_with = "serialize_presence_as_bool",
+        skip_serializing_if = "is_false"
+    )]
     pub premium_subscriber: bool,
+    /// The Id of the role subscription SKU and listing the [`Role`] belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscription_listing_id: Option<SkuId>,
+    /// Whether the [`Role`] is available for purchase as a role subscription.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_presence_as_bool",
+        serialize_with = "serialize_presence_as_bool",
+        skip_serializing_if = "is_false"
+    )]
+    pub available_for_purchase: bool,
+    /// Whether the [`Role`] is a guild's linked role.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_presence_as_bool",
+        serialize_with = "serialize_presence_as_bool",
+        skip_serializing_if = "is_false"
+    )]
+    pub guild_connections: bool,
 }
 
-impl<'de> Deserialize<'de> for RoleTags {
-    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
-        let mut map = JsonMap::deserialize(deserializer)?;
-
-        let bot_id = match map.contains_key("bot_id") {
-            true => Some(
-                map.remove("bot_id")
-                    .ok_or_else(|| DeError::custom("expected bot_id"))
-                    .and_then(UserId::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
-            false => None,
-        };
+#[cfg(test)]
+mod test {
+    use serde_json::json;
 
-        let integration_id = match map.contains_key("integration_id") {
-            true => Some(
-                map.remove("integration_id")
-                    .ok_or_else(|| DeError::custom("expected integration_id"))
-                    .and_then(IntegrationId::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
-            false => None,
-        };
+    use super::RoleTags;
+    use crate::model::id::{SkuId, UserId};
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn role_tags_null_means_true() {
+        let tags: RoleTags = serde_json::from_value(json!({
+            "premium_subscriber": null,
+        }))
+        .unwrap();
+
+        assert!(tags.premium_subscriber);
+        assert!(!tags.available_for_purchase);
+        assert!(!tags.guild_connections);
+        assert!(tags.bot_id.is_none());
 
-        let premium_subscriber = map.contains_key("premium_subscriber");
+        let tags: RoleTags = serde_json::from_value(json!({
+            "bot_id": "1",
+            "subscription_listing_id": "2",
+            "available_for_purchase": null,
+            "guild_connections": null,
+        }))
+        .unwrap();
+
+        assert!(!tags.premium_subscriber);
+        assert!(tags.available_for_purchase);
+        assert!(tags.guild_connections);
+        assert_eq!(tags.bot_id, Some(UserId(1)));
+        assert_eq!(tags.subscription_listing_id, Some(SkuId(2)));
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn role_tags_round_trip() {
+        let tags: RoleTags = serde_json::from_value(json!({
+            "premium_subscriber": null,
+        }))
+        .unwrap();
+
+        let value = serde_json::to_value(&tags).unwrap();
+        assert_eq!(value, json!({ "premium_subscriber": null }));
+
+        let tags: RoleTags = serde_json::from_value(value).unwrap();
+        assert!(tags.premium_subscriber);
+
+        let value = serde_json::to_value(&RoleTags::default()).unwrap();
+        assert_eq!(value, json!({}));
+
+        let tags: RoleTags = serde_json::from_value(value).unwrap();
+        assert!(!tags.premium_subscriber);
+    }
+
+    #[test]
+    #[cfg(feature = "model")]
+    #[allow(clippy::unwrap_used)]
+    fn position_cmp_fixed_prefers_lower_id() {
+        use std::cmp::Ordering;
+
+        use super::Role;
+
+        let role = |id: u64, position: i64| -> Role {
+            serde_json::from_value(json!({
+                "id": id.to_string(),
+                "guild_id": "1",
+                "color": 0,
+                "hoist": false,
+                "managed": false,
+                "mentionable": false,
+                "name": "role",
+                "permissions": "0",
+                "position": position,
+            }))
+            .unwrap()
+        };
 
-        Ok(Self {
-            bot_id,
-            integration_id,
-            premium_subscriber,
-        })
+        assert_eq!(role(2, 3).position_cmp_fixed(&role(1, 2)), Ordering::Greater);
+        assert_eq!(role(1, 2).position_cmp_fixed(&role(2, 3)), Ordering::Less);
+        assert_eq!(role(1, 2).position_cmp_fixed(&role(2, 2)), Ordering::Greater);
+        assert_eq!(role(2, 2).position_cmp_fixed(&role(1, 2)), Ordering::Less);
+        assert_eq!(role(1, 2).position_cmp_fixed(&role(1, 2)), Ordering::Equal);
     }
 }
@@ -0,0 +1,344 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::model::prelude::*;
+
+/// An event of a guild which is scheduled to take place in a stage or voice
+/// channel, or at an external location.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct ScheduledEvent {
+    /// The Id of the scheduled event.
+    pub id: ScheduledEventId,
+    /// The Id of the guild the event belongs to.
+    pub guild_id: GuildId,
+    /// The Id of the channel the event takes place in.
+    ///
+    /// **Note**: This is [`None`] for [`ScheduledEventType::External`] events.
+    pub channel_id: Option<ChannelId>,
+    /// The Id of the user who created the event.
+    pub creator_id: Option<UserId>,
+    /// The name of the event.
+    pub name: String,
+    /// The description of the event.
+    pub description: Option<String>,
+    /// The time the event is scheduled to start at.
+    #[serde(rename = "scheduled_start_time")]
+    pub start_time: DateTime<Utc>,
+    /// The time the event is scheduled to end at.
+    ///
+    /// **Note**: This is always set for [`ScheduledEventType::External`] events.
+    #[serde(rename = "scheduled_end_time")]
+    pub end_time: Option<DateTime<Utc>>,
+    /// The status of the event.
+    pub status: ScheduledEventStatus,
+    /// The kind of the event, telling where it takes place.
+    #[serde(rename = "entity_type")]
+    pub kind: ScheduledEventType,
+    /// Additional data about the event's location.
+    ///
+    /// **Note**: This is only set for [`ScheduledEventType::External`] events.
+    #[serde(rename = "entity_metadata")]
+    pub metadata: Option<ScheduledEventMetadata>,
+    /// The user who created the event.
+    ///
+    /// **Note**: This is not set for events created before October 25th, 2021.
+    pub creator: Option<User>,
+    /// The number of users who subscribed to the event.
+    ///
+    /// **Note**: This is only set when requested.
+    pub user_count: Option<u64>,
+    /// The hash of the event's cover image.
+    pub image: Option<String>,
+    /// The rule by which the event repeats, if it does.
+    pub recurrence_rule: Option<RecurrenceRule>,
+}
+
+/// The kind of a [`ScheduledEvent`], telling where it takes place.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum ScheduledEventType {
+    /// The event takes place in a stage channel.
+    StageInstance = 1,
+    /// The event takes place in a voice channel.
+    Voice = 2,
+    /// The event takes place at a location outside of Discord.
+    External = 3,
+    Unknown = !0,
+}
+
+enum_number!(ScheduledEventType {
+    StageInstance,
+    Voice,
+    External
+});
+
+/// The status of a [`ScheduledEvent`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum ScheduledEventStatus {
+    Scheduled = 1,
+    Active = 2,
+    Completed = 3,
+    Canceled = 4,
+    Unknown = !0,
+}
+
+enum_number!(ScheduledEventStatus {
+    Scheduled,
+    Active,
+    Completed,
+    Canceled
+});
+
+/// Additional data about the location of a [`ScheduledEvent`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct ScheduledEventMetadata {
+    /// The location of an external event.
+    pub location: Option<String>,
+}
+
+/// The rule by which a [`ScheduledEvent`] repeats.
+///
+/// Refer to [`CreateRecurrenceRule`] for which rules Discord accepts.
+///
+/// [`CreateRecurrenceRule`]: crate::builder::CreateRecurrenceRule
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct RecurrenceRule {
+    /// The time the first occurrence starts at.
+    pub start: DateTime<Utc>,
+    /// The time after which the event no longer repeats.
+    pub end: Option<DateTime<Utc>>,
+    /// How often the event repeats.
+    pub frequency: RecurrenceRuleFrequency,
+    /// The number of [`Self::frequency`] units between occurrences, such as
+    /// `2` for every other week.
+    pub interval: u64,
+    /// The days of the week the event repeats on.
+    pub by_weekday: Option<Vec<RecurrenceRuleWeekday>>,
+    /// The days of specific weeks of a month the event repeats on.
+    pub by_n_weekday: Option<Vec<RecurrenceRuleNWeekday>>,
+    /// The months the event repeats in.
+    pub by_month: Option<Vec<RecurrenceRuleMonth>>,
+    /// The days of the month the event repeats on.
+    pub by_month_day: Option<Vec<u8>>,
+    /// The days of the year the event repeats on.
+    pub by_year_day: Option<Vec<u16>>,
+    /// The number of times the event repeats.
+    pub count: Option<u64>,
+}
+
+/// How often a [`ScheduledEvent`] repeats.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum RecurrenceRuleFrequency {
+    Yearly = 0,
+    Monthly = 1,
+    Weekly = 2,
+    Daily = 3,
+    Unknown = !0,
+}
+
+enum_number!(RecurrenceRuleFrequency {
+    Yearly,
+    Monthly,
+    Weekly,
+    Daily
+});
+
+/// A day of the week a [`ScheduledEvent`] repeats on.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum RecurrenceRuleWeekday {
+    Monday = 0,
+    Tuesday = 1,
+    Wednesday = 2,
+    Thursday = 3,
+    Friday = 4,
+    Saturday = 5,
+    Sunday = 6,
+    Unknown = !0,
+}
+
+enum_number!(RecurrenceRuleWeekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday
+});
+
+/// A day of a specific week of the month, such as the second Tuesday.
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct RecurrenceRuleNWeekday {
+    /// The week of the month, from `1` to `5`.
+    pub n: u8,
+    /// The day of the week.
+    pub day: RecurrenceRuleWeekday,
+}
+
+impl RecurrenceRuleNWeekday {
+    pub fn new(n: u8, day: RecurrenceRuleWeekday) -> Self {
+        Self {
+            n,
+            day,
+        }
+    }
+}
+
+/// A month a [`ScheduledEvent`] repeats in.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum RecurrenceRuleMonth {
+    January = 1,
+    February = 2,
+    March = 3,
+    April = 4,
+    May = 5,
+    June = 6,
+    July = 7,
+    August = 8,
+    September = 9,
+    October = 10,
+    November = 11,
+    December = 12,
+    Unknown = !0,
+}
+
+enum_number!(RecurrenceRuleMonth {
+    January,
+    February,
+    March,
+    April,
+    May,
+    June,
+    July,
+    August,
+    September,
+    October,
+    November,
+    December
+});
+
+/// A reason for Discord to reject a scheduled event when creating it, as found
+/// by [`CreateScheduledEvent::build_check`].
+///
+/// [`CreateScheduledEvent::build_check`]: crate::builder::CreateScheduledEvent::build_check
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ScheduledEventViolation {
+    /// A field which is required for the kind of the event is not set, such as
+    /// the location of an external event. Contains the path of the field.
+    MissingField(&'static str),
+    /// A field is set which is not allowed for the kind of the event, such as
+    /// the channel of an external event. Contains the path of the field.
+    UnexpectedField(&'static str),
+    /// A field of the recurrence rule is set which Discord does not allow with
+    /// its frequency, or at all. Contains the path of the field.
+    UnsupportedRecurrenceField {
+        field: &'static str,
+        frequency: RecurrenceRuleFrequency,
+    },
+    /// The interval of the recurrence rule is not allowed with its frequency.
+    /// Only weekly events may repeat every other week; all other events must
+    /// repeat with an interval of `1`.
+    InvalidRecurrenceInterval {
+        interval: u64,
+        frequency: RecurrenceRuleFrequency,
+    },
+    /// A field of the recurrence rule lists a number of values Discord does not
+    /// accept, such as several months for a yearly event. Contains the path of
+    /// the field.
+    InvalidRecurrenceLength(&'static str),
+    /// The weekdays of a daily recurrence rule are not one of the sets Discord
+    /// accepts, such as Monday to Friday.
+    InvalidRecurrenceWeekdays,
+}
+
+impl fmt::Display for ScheduledEventViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "`{}` is required for this event", field),
+            Self::UnexpectedField(field) => write!(f, "`{}` is not allowed for this event", field),
+            Self::UnsupportedRecurrenceField {
+                field,
+                frequency,
+            } => write!(f, "`{}` cannot be set for a {:?} recurrence", field, frequency),
+            Self::InvalidRecurrenceInterval {
+                interval,
+                frequency,
+            } => {
+                write!(f, "an interval of {} is invalid for a {:?} recurrence", interval, frequency)
+            },
+            Self::InvalidRecurrenceLength(field) => {
+                write!(f, "`{}` has an invalid number of values", field)
+            },
+            Self::InvalidRecurrenceWeekdays => {
+                f.write_str("the weekdays of a daily recurrence are not an accepted set")
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_recurring_event() {
+        let event: ScheduledEvent = serde_json::from_value(json!({
+            "id": "1",
+            "guild_id": "2",
+            "channel_id": null,
+            "creator_id": "3",
+            "name": "Meetup",
+            "description": "Every other week",
+            "scheduled_start_time": "2024-05-06T18:00:00+00:00",
+            "scheduled_end_time": "2024-05-06T20:00:00+00:00",
+            "privacy_level": 2,
+            "status": 1,
+            "entity_type": 3,
+            "entity_id": null,
+            "entity_metadata": {"location": "The park"},
+            "image": null,
+            "recurrence_rule": {
+                "start": "2024-05-06T18:00:00+00:00",
+                "end": null,
+                "frequency": 2,
+                "interval": 2,
+                "by_weekday": [0],
+                "by_n_weekday": null,
+                "by_month": null,
+                "by_month_day": null,
+                "by_year_day": null,
+                "count": null,
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(event.id, ScheduledEventId(1));
+        assert_eq!(event.kind, ScheduledEventType::External);
+        assert_eq!(event.status, ScheduledEventStatus::Scheduled);
+        assert_eq!(event.metadata.unwrap().location.as_deref(), Some("The park"));
+        assert!(event.end_time.is_some());
+
+        let rule = event.recurrence_rule.unwrap();
+        assert_eq!(rule.frequency, RecurrenceRuleFrequency::Weekly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.by_weekday, Some(vec![RecurrenceRuleWeekday::Monday]));
+        assert!(rule.by_n_weekday.is_none());
+    }
+}
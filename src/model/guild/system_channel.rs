@@ -2,6 +2,7 @@ use bitflags::__impl_bitflags;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::internal::prelude::StdResult;
+use crate::model::channel::MessageType;
 use crate::model::utils::U64Visitor;
 
 /// Describes a system channel flags.
@@ -23,6 +24,23 @@ __impl_bitflags! {
     }
 }
 
+impl SystemChannelFlags {
+    /// Returns the flag suppressing system messages of the given kind.
+    ///
+    /// This is empty for kinds of messages which can not be suppressed.
+    pub fn suppressing(kind: MessageType) -> Self {
+        match kind {
+            MessageType::MemberJoin => Self::SUPPRESS_JOIN_NOTIFICATIONS,
+            MessageType::NitroBoost
+            | MessageType::NitroTier1
+            | MessageType::NitroTier2
+            | MessageType::NitroTier3 => Self::SUPPRESS_PREMIUM_SUBSCRIPTIONS,
+            MessageType::GuildInviteReminder => Self::SUPPRESS_GUILD_REMINDER_NOTIFICATIONS,
+            _ => Self::empty(),
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for SystemChannelFlags {
     fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
     where
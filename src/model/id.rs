@@ -5,6 +5,7 @@ use std::fmt::{Display, Formatter, Result as FmtResult};
 use chrono::{DateTime, TimeZone, Utc};
 use serde::de::{Deserialize, Deserializer};
 
+use super::misc::Mention;
 use super::utils::U64Visitor;
 use crate::internal::prelude::*;
 
@@ -163,6 +164,22 @@ pub struct TargetId(pub u64);
 #[derive(Copy, Clone, Default, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct StageInstanceId(pub u64);
 
+/// An identifier for a scheduled event of a guild.
+#[derive(Copy, Clone, Default, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
+pub struct ScheduledEventId(pub u64);
+
+/// An identifier for a tag of a forum channel.
+#[derive(Copy, Clone, Default, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
+pub struct ForumTagId(pub u64);
+
+/// An identifier for a SKU (stock-keeping unit) of a monetized application.
+#[derive(Copy, Clone, Default, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
+pub struct SkuId(pub u64);
+
+/// An identifier for an entitlement of a user or guild to a SKU.
+#[derive(Copy, Clone, Default, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
+pub struct EntitlementId(pub u64);
+
 id_u64! {
     AttachmentId;
     ApplicationId;
@@ -183,11 +200,41 @@ id_u64! {
     CommandVersionId;
     TargetId;
     StageInstanceId;
+    ScheduledEventId;
+    ForumTagId;
+    SkuId;
+    EntitlementId;
+}
+
+macro_rules! id_mention {
+    ($($name:ident, $doc:literal;)*) => {
+        $(
+            impl $name {
+                #[doc = $doc]
+                ///
+                /// Unlike [`Mentionable::mention`], this does not require the trait to be
+                /// imported, and can be used directly inside a [`format_args!`] type of macro.
+                ///
+                /// [`Mentionable::mention`]: super::misc::Mentionable::mention
+                #[inline]
+                #[must_use]
+                pub fn mention(self) -> Mention {
+                    Mention::from(self)
+                }
+            }
+        )*
+    }
+}
+
+id_mention! {
+    ChannelId, "Creates a [`Mention`] of the channel, displayed as `<#CHANNEL_ID>`.";
+    RoleId, "Creates a [`Mention`] of the role, displayed as `<@&ROLE_ID>`.";
+    UserId, "Creates a [`Mention`] of the user, displayed as `<@USER_ID>`.";
 }
 
 #[cfg(test)]
 mod tests {
-    use super::GuildId;
+    use super::{ChannelId, GuildId, RoleId, UserId};
 
     #[test]
     fn test_created_at() {
@@ -197,4 +244,12 @@ mod tests {
             "2016-04-30T11:18:25.796+00:00"
         );
     }
+
+    #[test]
+    fn test_mention() {
+        assert_eq!(
+            format!("{} {} {}", UserId(1).mention(), ChannelId(2).mention(), RoleId(3).mention()),
+            "<@1> <#2> <@&3>"
+        );
+    }
 }
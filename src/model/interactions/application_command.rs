@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::fmt;
 
 use serde::de::Error as DeError;
 use serde::{Deserialize, Deserializer};
@@ -12,6 +13,10 @@ use crate::builder::{
     CreateInteractionResponseFollowup,
     EditInteractionResponse,
 };
+#[cfg(feature = "collector")]
+use crate::client::bridge::gateway::ShardMessenger;
+#[cfg(feature = "collector")]
+use crate::collector::{CollectModalInteraction, ModalInteractionCollectorBuilder};
 #[cfg(feature = "model")]
 use crate::http::Http;
 use crate::internal::prelude::{JsonMap, StdResult, Value};
@@ -26,7 +31,21 @@ use crate::model::id::{
     RoleId,
     UserId,
 };
-use crate::model::interactions::InteractionType;
+#[cfg(feature = "model")]
+use crate::model::interactions::followup::FollowupMessage;
+#[cfg(feature = "model")]
+use crate::model::interactions::{
+    check_command_contexts,
+    check_command_edit,
+    check_command_options,
+    check_components,
+    check_response_kind,
+};
+use crate::model::interactions::{
+    remove_invocation_fields,
+    InteractionType,
+    ResponseEphemerality,
+};
 use crate::model::prelude::User;
 use crate::model::utils::{
     deserialize_channels_map,
@@ -70,9 +89,53 @@ pub struct ApplicationCommandInteraction {
     /// Always `1`.
     pub version: u8,
     /// The guild's preferred locale.
-    pub guild_locale: Option<String>,
+    pub guild_locale: Option<Locale>,
     /// The selected language of the invoking user.
-    pub locale: String,
+    pub locale: Locale,
+    /// The entitlements of the invoking user and guild, for monetized applications.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entitlements: Vec<Entitlement>,
+    /// The permissions of the bot in the channel the interaction was invoked
+    /// from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_permissions: Option<Permissions>,
+    /// The context the interaction was invoked from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<InteractionContext>,
+    /// The installations of the application which authorized the interaction.
+    #[serde(skip_serializing_if = "AuthorizingIntegrationOwners::is_empty")]
+    pub authorizing_integration_owners: AuthorizingIntegrationOwners,
+    /// Whether [`Self::edit_original_interaction_response`] returns a
+    /// [`ModelError::EphemeralResponseChange`] instead of logging a warning
+    /// when an edit would change whether the initial response is ephemeral,
+    /// which Discord ignores.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`ModelError::EphemeralResponseChange`]: crate::model::ModelError::EphemeralResponseChange
+    #[serde(skip)]
+    pub strict_ephemerality: bool,
+    #[serde(skip)]
+    response_ephemerality: ResponseEphemerality,
+}
+
+impl ApplicationCommandInteraction {
+    /// Gets the locale to respond in: the [`locale`] of the invoking user, or
+    /// the [`guild_locale`] if the user's locale is not known to serenity.
+    ///
+    /// Falls back to the user's locale if neither is known.
+    ///
+    /// [`locale`]: Self::locale
+    /// [`guild_locale`]: Self::guild_locale
+    #[must_use]
+    pub fn preferred_locale(&self) -> &Locale {
+        match &self.guild_locale {
+            Some(guild_locale) if !self.locale.is_known() && guild_locale.is_known() => {
+                guild_locale
+            },
+            _ => &self.locale,
+        }
+    }
 }
 
 #[cfg(feature = "model")]
@@ -85,7 +148,9 @@ impl ApplicationCommandInteraction {
     ///
     /// [`Error::Http`]: crate::error::Error::Http
     pub async fn get_interaction_response(&self, http: impl AsRef<Http>) -> Result<Message> {
-        http.as_ref().get_original_interaction_response(&self.token).await
+        http.as_ref()
+            .get_original_interaction_response_for(self.application_id.0, &self.token)
+            .await
     }
 
     /// Creates a response to the interaction received.
@@ -94,13 +159,17 @@ impl ApplicationCommandInteraction {
     ///
     /// # Errors
     ///
-    /// Returns an [`Error::Model`] if the message content is too long.
+    /// Returns an [`Error::Model`] if the message content is too long, or if
+    /// the response type is not valid for this interaction; refer to
+    /// [`InteractionResponseType::is_valid_for`]. The check can be skipped by
+    /// using [`Http::create_interaction_response`] directly.
     /// May also return an [`Error::Http`] if the API returns an error,
     /// or an [`Error::Json`] if there is an error in deserializing the
     /// API response.
     ///
     /// [`Error::Model`]: crate::error::Error::Model
     /// [`Error::Http`]: crate::error::Error::Http
+    /// [`InteractionResponseType::is_valid_for`]: crate::model::interactions::InteractionResponseType::is_valid_for
     /// [`Error::Json`]: crate::error::Error::Json
     pub async fn create_interaction_response<F>(&self, http: impl AsRef<Http>, f: F) -> Result<()>
     where
@@ -111,10 +180,37 @@ impl ApplicationCommandInteraction {
 
         let map = utils::hashmap_to_json_map(interaction_response.0);
 
+        check_response_kind(&map, InteractionType::ApplicationCommand)?;
         Message::check_content_length(&map)?;
         Message::check_embed_length(&map)?;
+        check_components(&map)?;
+
+        let ephemeral = ResponseEphemerality::of_response(&map);
+
+        http.as_ref()
+            .create_interaction_response_with_files(
+                self.id.0,
+                &self.token,
+                interaction_response.1,
+                map,
+            )
+            .await?;
+
+        if let Some(ephemeral) = ephemeral {
+            self.response_ephemerality.set(ephemeral);
+        }
+
+        Ok(())
+    }
 
-        http.as_ref().create_interaction_response(self.id.0, &self.token, &Value::Object(map)).await
+    /// Whether the initial response to the interaction is ephemeral.
+    ///
+    /// This is only known once the interaction was responded to with a
+    /// message, or deferred, by [`Self::create_interaction_response`] or
+    /// [`Self::defer`] of this interaction or one of its clones.
+    #[must_use]
+    pub fn is_response_ephemeral(&self) -> Option<bool> {
+        self.response_ephemerality.get()
     }
 
     /// Edits the initial interaction response.
@@ -129,20 +225,23 @@ impl ApplicationCommandInteraction {
     ///
     /// # Errors
     ///
-    /// Returns [`Error::Model`] if the edited content is too long.
+    /// Returns [`Error::Model`] if the edited content is too long, or if the
+    /// edit would change whether the response is ephemeral while
+    /// [`Self::strict_ephemerality`] is set.
     /// May also return [`Error::Http`] if the API returns an error,
     /// or an [`Error::Json`] if there is an error deserializing the response.
     ///
     /// [`Error::Model`]: crate::error::Error::Model
     /// [`Error::Http`]: crate::error::Error::Http
     /// [`Error::Json`]: crate::error::Error::Json
-    pub async fn edit_original_interaction_response<F>(
+    pub async fn edit_original_interaction_response<'a, F>(
         &self,
         http: impl AsRef<Http>,
         f: F,
     ) -> Result<Message>
     where
-        F: FnOnce(&mut EditInteractionResponse) -> &mut EditInteractionResponse,
+        for<'b> F:
+            FnOnce(&'b mut EditInteractionResponse<'a>) -> &'b mut EditInteractionResponse<'a>,
     {
         let mut interaction_response = EditInteractionResponse::default();
         f(&mut interaction_response);
@@ -151,8 +250,17 @@ impl ApplicationCommandInteraction {
 
         Message::check_content_length(&map)?;
         Message::check_embed_length(&map)?;
+        check_components(&map)?;
+        self.response_ephemerality.check_edit(&map, self.strict_ephemerality)?;
 
-        http.as_ref().edit_original_interaction_response(&self.token, &Value::Object(map)).await
+        http.as_ref()
+            .edit_original_interaction_response_with_files_for(
+                self.application_id.0,
+                &self.token,
+                interaction_response.1,
+                map,
+            )
+            .await
     }
 
     /// Deletes the initial interaction response.
@@ -162,11 +270,16 @@ impl ApplicationCommandInteraction {
     /// May return [`Error::Http`] if the API returns an error.
     /// Such as if the response was already deleted.
     pub async fn delete_original_interaction_response(&self, http: impl AsRef<Http>) -> Result<()> {
-        http.as_ref().delete_original_interaction_response(&self.token).await
+        http.as_ref()
+            .delete_original_interaction_response_for(self.application_id.0, &self.token)
+            .await
     }
 
     /// Creates a followup response to the response sent.
     ///
+    /// The message is returned along with the interaction's token, so that it
+    /// can still be edited or deleted through the returned [`FollowupMessage`].
+    ///
     /// **Note**: Message contents must be under 2000 unicode code points.
     ///
     /// # Errors
@@ -182,7 +295,7 @@ impl ApplicationCommandInteraction {
         &self,
         http: impl AsRef<Http>,
         f: F,
-    ) -> Result<Message>
+    ) -> Result<FollowupMessage>
     where
         for<'b> F: FnOnce(
             &'b mut CreateInteractionResponseFollowup<'a>,
@@ -195,8 +308,31 @@ impl ApplicationCommandInteraction {
 
         Message::check_content_length(&map)?;
         Message::check_embed_length(&map)?;
+        check_components(&map)?;
 
-        http.as_ref().create_followup_message(&self.token, &Value::Object(map)).await
+        let message = http
+            .as_ref()
+            .create_followup_message_for(self.application_id.0, &self.token, &Value::Object(map))
+            .await?;
+
+        Ok(FollowupMessage::new(message, self.application_id, self.token.clone()))
+    }
+
+    /// Creates an ephemeral followup message with the given content, which
+    /// is only visible to the user who invoked the command.
+    ///
+    /// Followup messages can be ephemeral even if the initial response is
+    /// not, such as after [`Self::defer`].
+    ///
+    /// # Errors
+    ///
+    /// Refer to [`Self::create_followup_message`].
+    pub async fn create_ephemeral_followup(
+        &self,
+        http: impl AsRef<Http>,
+        content: impl ToString,
+    ) -> Result<FollowupMessage> {
+        self.create_followup_message(http, |f| f.content(content).ephemeral(true)).await
     }
 
     /// Edits a followup response to the response sent.
@@ -230,9 +366,16 @@ impl ApplicationCommandInteraction {
 
         Message::check_content_length(&map)?;
         Message::check_embed_length(&map)?;
+        check_components(&map)?;
 
         http.as_ref()
-            .edit_followup_message(&self.token, message_id.into().into(), &Value::Object(map))
+            .edit_followup_message_with_files_for(
+                self.application_id.0,
+                &self.token,
+                message_id.into().into(),
+                interaction_response.1,
+                map,
+            )
             .await
     }
 
@@ -247,7 +390,13 @@ impl ApplicationCommandInteraction {
         http: impl AsRef<Http>,
         message_id: M,
     ) -> Result<()> {
-        http.as_ref().delete_followup_message(&self.token, message_id.into().into()).await
+        http.as_ref()
+            .delete_followup_message_for(
+                self.application_id.0,
+                &self.token,
+                message_id.into().into(),
+            )
+            .await
     }
 
     /// Gets a followup message.
@@ -261,7 +410,9 @@ impl ApplicationCommandInteraction {
         http: impl AsRef<Http>,
         message_id: M,
     ) -> Result<Message> {
-        http.as_ref().get_followup_message(&self.token, message_id.into().into()).await
+        http.as_ref()
+            .get_followup_message_for(self.application_id.0, &self.token, message_id.into().into())
+            .await
     }
 
     /// Helper function to defer an interaction
@@ -282,6 +433,26 @@ impl ApplicationCommandInteraction {
         })
         .await
     }
+
+    /// Await a single modal submit interaction of the user who invoked the
+    /// command, such as after responding with a modal.
+    #[cfg(feature = "collector")]
+    pub fn await_modal_interaction<'a>(
+        &self,
+        shard_messenger: &'a impl AsRef<ShardMessenger>,
+    ) -> CollectModalInteraction<'a> {
+        CollectModalInteraction::new(shard_messenger).author_id(self.user.id.0)
+    }
+
+    /// Returns a stream builder which can be awaited to obtain a stream of
+    /// modal submit interactions of the user who invoked the command.
+    #[cfg(feature = "collector")]
+    pub fn await_modal_interactions<'a>(
+        &self,
+        shard_messenger: &'a impl AsRef<ShardMessenger>,
+    ) -> ModalInteractionCollectorBuilder<'a> {
+        ModalInteractionCollectorBuilder::new(shard_messenger).author_id(self.user.id.0)
+    }
 }
 
 impl<'de> Deserialize<'de> for ApplicationCommandInteraction {
@@ -350,12 +521,11 @@ impl<'de> Deserialize<'de> for ApplicationCommandInteraction {
             .map_err(DeError::custom)?;
 
         let guild_id = match map.contains_key("guild_id") {
-            true => Some(
-                map.remove("guild_id")
-                    .ok_or_else(|| DeError::custom("expected guild_id"))
-                    .and_then(GuildId::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
+            true => map
+                .remove("guild_id")
+                .ok_or_else(|| DeError::custom("expected guild_id"))
+                .and_then(Option::<GuildId>::deserialize)
+                .map_err(DeError::custom)?,
             false => None,
         };
 
@@ -366,12 +536,11 @@ impl<'de> Deserialize<'de> for ApplicationCommandInteraction {
             .map_err(DeError::custom)?;
 
         let member = match map.contains_key("member") {
-            true => Some(
-                map.remove("member")
-                    .ok_or_else(|| DeError::custom("expected member"))
-                    .and_then(Member::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
+            true => map
+                .remove("member")
+                .ok_or_else(|| DeError::custom("expected member"))
+                .and_then(Option::<Member>::deserialize)
+                .map_err(DeError::custom)?,
             false => None,
         };
 
@@ -397,21 +566,32 @@ impl<'de> Deserialize<'de> for ApplicationCommandInteraction {
             .map_err(DeError::custom)?;
 
         let guild_locale = match map.contains_key("guild_locale") {
-            true => Some(
-                map.remove("guild_locale")
-                    .ok_or_else(|| DeError::custom("expected guild_locale"))
-                    .and_then(String::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
+            true => map
+                .remove("guild_locale")
+                .ok_or_else(|| DeError::custom("expected guild_locale"))
+                .and_then(Option::<Locale>::deserialize)
+                .map_err(DeError::custom)?,
             false => None,
         };
 
         let locale = map
             .remove("locale")
             .ok_or_else(|| DeError::custom("expected locale"))
-            .and_then(String::deserialize)
+            .and_then(Locale::deserialize)
             .map_err(DeError::custom)?;
 
+        let entitlements = match map.contains_key("entitlements") {
+            true => map
+                .remove("entitlements")
+                .ok_or_else(|| DeError::custom("expected entitlements"))
+                .and_then(Vec::<Entitlement>::deserialize)
+                .map_err(DeError::custom)?,
+            false => Vec::new(),
+        };
+
+        let (app_permissions, context, authorizing_integration_owners) =
+            remove_invocation_fields(&mut map)?;
+
         Ok(Self {
             id,
             application_id,
@@ -425,6 +605,12 @@ impl<'de> Deserialize<'de> for ApplicationCommandInteraction {
             version,
             guild_locale,
             locale,
+            entitlements,
+            app_permissions,
+            context,
+            authorizing_integration_owners,
+            strict_ephemerality: false,
+            response_ephemerality: ResponseEphemerality::default(),
         })
     }
 }
@@ -521,14 +707,14 @@ impl<'de> Deserialize<'de> for ApplicationCommandInteractionData {
                         .expect("expected message object")
                         .to_owned();
 
-                    Some(ResolvedTarget::Message(resolved))
+                    Some(ResolvedTarget::Message(Box::new(resolved)))
                 } else {
                     let user_id = id.to_user_id();
 
                     let user = resolved.users.get(&user_id).expect("expected user").to_owned();
-                    let member = resolved.members.get(&user_id).map(|m| m.to_owned());
+                    let member = resolved.members.get(&user_id).map(|m| Box::new(m.to_owned()));
 
-                    Some(ResolvedTarget::User(user, member))
+                    Some(ResolvedTarget::User(Box::new(user), member))
                 }
             },
             None => None,
@@ -551,8 +737,8 @@ impl<'de> Deserialize<'de> for ApplicationCommandInteractionData {
 #[non_exhaustive]
 #[repr(u8)]
 pub enum ResolvedTarget {
-    User(User, Option<PartialMember>),
-    Message(Message),
+    User(Box<User>, Option<Box<PartialMember>>),
+    Message(Box<Message>),
 }
 
 /// The resolved data of a command data interaction payload.
@@ -673,12 +859,11 @@ impl<'de> Deserialize<'de> for ApplicationCommandInteractionDataOption {
             .map_err(DeError::custom)?;
 
         let value = match map.contains_key("value") {
-            true => Some(
-                map.remove("value")
-                    .ok_or_else(|| DeError::custom("expected value"))
-                    .and_then(Value::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
+            true => map
+                .remove("value")
+                .ok_or_else(|| DeError::custom("expected value"))
+                .and_then(Option::<Value>::deserialize)
+                .map_err(DeError::custom)?,
             false => None,
         };
 
@@ -757,6 +942,16 @@ pub struct ApplicationCommand {
     /// the application is added to a guild.
     #[serde(default = "self::default_permission_value")]
     pub default_permission: bool,
+    /// The installation contexts the command is available in.
+    ///
+    /// **Note**: Only present for global commands.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integration_types: Option<Vec<InstallationContext>>,
+    /// The contexts the command can be invoked from.
+    ///
+    /// **Note**: Only present for global commands.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contexts: Option<Vec<InteractionContext>>,
     /// An autoincremented version identifier updated during substantial record changes.
     pub version: CommandVersionId,
 }
@@ -830,12 +1025,24 @@ impl ApplicationCommand {
     /// Can also return an [`Error::Json`] if there is an error in deserializing
     /// the response.
     ///
+    /// Returns a [`ModelError::UnsupportedCommandContext`] if one of the
+    /// [`contexts`] requires an installation context which is missing from the
+    /// command's [`integration_types`].
+    ///
+    /// Returns a [`ModelError::InvalidApplicationCommand`] if the command would
+    /// be rejected by Discord, as checked by [`build_check`].
+    ///
     /// [`ApplicationCommand`]: crate::model::interactions::application_command::ApplicationCommand
     /// [`InteractionCreate`]: crate::client::EventHandler::interaction_create
     /// [API Docs]: https://discord.com/developers/docs/interactions/slash-commands
     /// [`Error::Http`]: crate::error::Error::Http
     /// [`Error::Json`]: crate::error::Error::Json
     /// [`choices`]: crate::model::interactions::application_command::ApplicationCommandOption::choices
+    /// [`ModelError::UnsupportedCommandContext`]: crate::model::error::Error::UnsupportedCommandContext
+    /// [`contexts`]: crate::builder::CreateApplicationCommand::contexts
+    /// [`integration_types`]: crate::builder::CreateApplicationCommand::integration_types
+    /// [`ModelError::InvalidApplicationCommand`]: crate::model::error::Error::InvalidApplicationCommand
+    /// [`build_check`]: crate::builder::CreateApplicationCommand::build_check
     pub async fn create_global_application_command<F>(
         http: impl AsRef<Http>,
         f: F,
@@ -843,7 +1050,7 @@ impl ApplicationCommand {
     where
         F: FnOnce(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand,
     {
-        let map = ApplicationCommand::build_application_command(f);
+        let map = ApplicationCommand::build_application_command(f)?;
         http.as_ref().create_global_application_command(&Value::Object(map)).await
     }
 
@@ -868,6 +1075,11 @@ impl ApplicationCommand {
 
         f(&mut array);
 
+        for command in array.0.iter().filter_map(Value::as_object) {
+            check_command_options(command)?;
+            check_command_contexts(command)?;
+        }
+
         http.as_ref().create_global_application_commands(&Value::Array(array.0)).await
     }
 
@@ -887,7 +1099,7 @@ impl ApplicationCommand {
     where
         F: FnOnce(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand,
     {
-        let map = ApplicationCommand::build_application_command(f);
+        let map = ApplicationCommand::build_application_command_edit(f)?;
         http.as_ref().edit_global_application_command(command_id.into(), &Value::Object(map)).await
     }
 
@@ -936,13 +1148,33 @@ impl ApplicationCommand {
     }
 
     #[inline]
-    pub(crate) fn build_application_command<F>(f: F) -> Map<String, Value>
+    pub(crate) fn build_application_command<F>(f: F) -> Result<Map<String, Value>>
     where
         F: FnOnce(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand,
     {
         let mut create_application_command = CreateApplicationCommand::default();
         f(&mut create_application_command);
-        utils::hashmap_to_json_map(create_application_command.0)
+        let map = utils::hashmap_to_json_map(create_application_command.0);
+        check_command_options(&map)?;
+        check_command_contexts(&map)?;
+
+        Ok(map)
+    }
+
+    /// Builds an edit of an application command, which only holds the fields
+    /// to change.
+    #[inline]
+    pub(crate) fn build_application_command_edit<F>(f: F) -> Result<Map<String, Value>>
+    where
+        F: FnOnce(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand,
+    {
+        let mut create_application_command = CreateApplicationCommand::default();
+        f(&mut create_application_command);
+        let map = utils::hashmap_to_json_map(create_application_command.0);
+        check_command_edit(&map)?;
+        check_command_contexts(&map)?;
+
+        Ok(map)
     }
 }
 
@@ -1163,6 +1395,50 @@ enum_number!(ApplicationCommandOptionType {
     Number
 });
 
+/// A reason for Discord to reject an application command when registering it,
+/// as found by [`CreateApplicationCommand::build_check`].
+///
+/// Each variant contains the path of the offending command or option, which is
+/// its name preceded by the names of its parents, separated by spaces, such as
+/// `config set value`.
+///
+/// [`CreateApplicationCommand::build_check`]: crate::builder::CreateApplicationCommand::build_check
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ApplicationCommandViolation {
+    /// An option has both choices and autocomplete set.
+    ChoicesWithAutocomplete(String),
+    /// A required option is placed after an optional one.
+    RequiredAfterOptional(String),
+    /// A name is not made of 1 to 32 lowercase letters, numbers, `-` or `_`.
+    InvalidName(String),
+    /// An option has the same name as another option of its parent.
+    DuplicateOptionName(String),
+    /// An option is nested where Discord does not allow it, such as a
+    /// sub-command group within a sub-command.
+    InvalidNesting(String),
+    /// A command or option has more than 25 options. Contains the path of the
+    /// parent.
+    TooManyOptions(String),
+}
+
+impl fmt::Display for ApplicationCommandViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ChoicesWithAutocomplete(path) => {
+                write!(f, "`{}` has both choices and autocomplete set", path)
+            },
+            Self::RequiredAfterOptional(path) => {
+                write!(f, "`{}` is required but follows an optional option", path)
+            },
+            Self::InvalidName(path) => write!(f, "`{}` has an invalid name", path),
+            Self::DuplicateOptionName(path) => write!(f, "`{}` is a duplicate option name", path),
+            Self::InvalidNesting(path) => write!(f, "`{}` cannot be nested in its parent", path),
+            Self::TooManyOptions(path) => write!(f, "`{}` has more than 25 options", path),
+        }
+    }
+}
+
 /// The type of an [`ApplicationCommandPermissionData`].
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
 #[non_exhaustive]
@@ -12,6 +12,7 @@ use crate::internal::prelude::{JsonMap, StdResult, Value};
 use crate::model::id::{ApplicationId, ChannelId, GuildId, InteractionId};
 use crate::model::interactions::{
     application_command::ApplicationCommandInteractionData,
+    remove_invocation_fields,
     InteractionType,
 };
 use crate::model::prelude::User;
@@ -48,9 +49,22 @@ pub struct AutocompleteInteraction {
     /// Always `1`.
     pub version: u8,
     /// The guild's preferred locale.
-    pub guild_locale: Option<String>,
+    pub guild_locale: Option<Locale>,
     /// The selected language of the invoking user.
-    pub locale: String,
+    pub locale: Locale,
+    /// The entitlements of the invoking user and guild, for monetized applications.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entitlements: Vec<Entitlement>,
+    /// The permissions of the bot in the channel the interaction was invoked
+    /// from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_permissions: Option<Permissions>,
+    /// The context the interaction was invoked from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<InteractionContext>,
+    /// The installations of the application which authorized the interaction.
+    #[serde(skip_serializing_if = "AuthorizingIntegrationOwners::is_empty")]
+    pub authorizing_integration_owners: AuthorizingIntegrationOwners,
 }
 
 #[cfg(feature = "model")]
@@ -146,12 +160,11 @@ impl<'de> Deserialize<'de> for AutocompleteInteraction {
             .map_err(DeError::custom)?;
 
         let guild_id = match map.contains_key("guild_id") {
-            true => Some(
-                map.remove("guild_id")
-                    .ok_or_else(|| DeError::custom("expected guild_id"))
-                    .and_then(GuildId::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
+            true => map
+                .remove("guild_id")
+                .ok_or_else(|| DeError::custom("expected guild_id"))
+                .and_then(Option::<GuildId>::deserialize)
+                .map_err(DeError::custom)?,
             false => None,
         };
 
@@ -162,12 +175,11 @@ impl<'de> Deserialize<'de> for AutocompleteInteraction {
             .map_err(DeError::custom)?;
 
         let member = match map.contains_key("member") {
-            true => Some(
-                map.remove("member")
-                    .ok_or_else(|| DeError::custom("expected member"))
-                    .and_then(Member::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
+            true => map
+                .remove("member")
+                .ok_or_else(|| DeError::custom("expected member"))
+                .and_then(Option::<Member>::deserialize)
+                .map_err(DeError::custom)?,
             false => None,
         };
 
@@ -193,21 +205,32 @@ impl<'de> Deserialize<'de> for AutocompleteInteraction {
             .map_err(DeError::custom)?;
 
         let guild_locale = match map.contains_key("guild_locale") {
-            true => Some(
-                map.remove("guild_locale")
-                    .ok_or_else(|| DeError::custom("expected guild_locale"))
-                    .and_then(String::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
+            true => map
+                .remove("guild_locale")
+                .ok_or_else(|| DeError::custom("expected guild_locale"))
+                .and_then(Option::<Locale>::deserialize)
+                .map_err(DeError::custom)?,
             false => None,
         };
 
         let locale = map
             .remove("locale")
             .ok_or_else(|| DeError::custom("expected locale"))
-            .and_then(String::deserialize)
+            .and_then(Locale::deserialize)
             .map_err(DeError::custom)?;
 
+        let entitlements = match map.contains_key("entitlements") {
+            true => map
+                .remove("entitlements")
+                .ok_or_else(|| DeError::custom("expected entitlements"))
+                .and_then(Vec::<Entitlement>::deserialize)
+                .map_err(DeError::custom)?,
+            false => Vec::new(),
+        };
+
+        let (app_permissions, context, authorizing_integration_owners) =
+            remove_invocation_fields(&mut map)?;
+
         Ok(Self {
             id,
             application_id,
@@ -221,6 +244,10 @@ impl<'de> Deserialize<'de> for AutocompleteInteraction {
             version,
             guild_locale,
             locale,
+            entitlements,
+            app_permissions,
+            context,
+            authorizing_integration_owners,
         })
     }
 }
@@ -0,0 +1,492 @@
+//! Syncing the application commands of a guild with a declarative set of
+//! commands, such as with [`GuildId::sync_commands`].
+//!
+//! [`GuildId::sync_commands`]: crate::model::id::GuildId::sync_commands
+
+use serde::de::Deserialize;
+use serde_json::Value;
+
+use super::application_command::ApplicationCommandType;
+#[cfg(feature = "model")]
+use super::{check_command_contexts, check_command_options};
+#[cfg(feature = "model")]
+use crate::builder::CreateApplicationCommand;
+#[cfg(feature = "model")]
+use crate::http::{request::RequestBuilder, routing::RouteInfo, Http};
+use crate::internal::prelude::*;
+use crate::model::id::{CommandId, GuildId};
+#[cfg(feature = "model")]
+use crate::utils;
+
+/// The number of changes above which the commands are overwritten in bulk by
+/// default.
+pub const DEFAULT_BULK_THRESHOLD: usize = 5;
+
+/// Fields compared even if a desired command or option does not set them, as
+/// Discord resets them when editing it.
+const RESET_FIELDS: &[&str] = &[
+    "type",
+    "description",
+    "name_localizations",
+    "description_localizations",
+    "default_permission",
+    "options",
+    "required",
+    "choices",
+    "autocomplete",
+    "channel_types",
+    "min_value",
+    "max_value",
+];
+
+/// How the commands are synced, refer to [`GuildId::sync_commands_with`].
+///
+/// [`GuildId::sync_commands_with`]: crate::model::id::GuildId::sync_commands_with
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct CommandSyncOptions {
+    bulk_threshold: usize,
+    dry_run: bool,
+}
+
+impl CommandSyncOptions {
+    /// Creates the default options, which apply the changes and overwrite the
+    /// commands in bulk once there are more than [`DEFAULT_BULK_THRESHOLD`]
+    /// changes.
+    pub fn new() -> Self {
+        Self {
+            bulk_threshold: DEFAULT_BULK_THRESHOLD,
+            dry_run: false,
+        }
+    }
+
+    /// Sets the number of changes above which all commands are overwritten
+    /// with a single request, instead of creating, editing and deleting them
+    /// one by one.
+    ///
+    /// A threshold of `0` always overwrites the commands if any changed.
+    pub fn bulk_threshold(mut self, threshold: usize) -> Self {
+        self.bulk_threshold = threshold;
+        self
+    }
+
+    /// Sets whether to only plan the changes, without applying them.
+    ///
+    /// The existing commands are still fetched in order to plan the changes.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+impl Default for CommandSyncOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A change to the commands of a guild, as planned by a sync.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CommandSyncOperation {
+    /// A desired command which does not exist yet.
+    Create {
+        kind: ApplicationCommandType,
+        name: String,
+    },
+    /// An existing command which differs from the desired one.
+    Edit {
+        id: CommandId,
+        kind: ApplicationCommandType,
+        name: String,
+    },
+    /// An existing command which is not desired anymore.
+    Delete {
+        id: CommandId,
+        kind: ApplicationCommandType,
+        name: String,
+    },
+}
+
+/// The outcome of syncing the commands of a guild.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct CommandSyncReport {
+    /// The changes which were made, or would be made in a dry run.
+    ///
+    /// These are also listed if the commands were overwritten in bulk.
+    pub operations: Vec<CommandSyncOperation>,
+    /// The number of commands which were created.
+    pub created: usize,
+    /// The number of commands which were edited.
+    pub updated: usize,
+    /// The number of commands which were deleted.
+    pub deleted: usize,
+    /// The number of commands which were already as desired.
+    pub unchanged: usize,
+    /// Whether all commands were overwritten with a single request.
+    pub bulk_overwrite: bool,
+    /// Whether the changes were only planned, without applying them.
+    pub dry_run: bool,
+}
+
+/// The changes needed to sync the existing commands with the desired ones.
+#[derive(Debug)]
+struct CommandSyncPlan {
+    /// The operations, along with the index of the desired command they
+    /// create or edit the command with.
+    steps: Vec<(CommandSyncOperation, Option<usize>)>,
+    unchanged: usize,
+}
+
+impl CommandSyncPlan {
+    fn new(desired: &[JsonMap], existing: &[JsonMap]) -> Result<Self> {
+        let mut existing = existing
+            .iter()
+            .map(|map| {
+                let id = CommandId::deserialize(map.get("id").unwrap_or(&Value::Null))?;
+
+                Ok((id, command_key(map)?, map))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut steps = Vec::new();
+        let mut unchanged = 0;
+
+        for (index, map) in desired.iter().enumerate() {
+            let (kind, name) = command_key(map)?;
+            let position =
+                existing.iter().position(|(_, key, _)| key.0 == kind && key.1 == name);
+
+            match position.map(|position| existing.remove(position)) {
+                Some((_, _, existing)) if is_unchanged(map, existing, true) => unchanged += 1,
+                Some((id, _, _)) => steps.push((
+                    CommandSyncOperation::Edit {
+                        id,
+                        kind,
+                        name,
+                    },
+                    Some(index),
+                )),
+                None => steps.push((
+                    CommandSyncOperation::Create {
+                        kind,
+                        name,
+                    },
+                    Some(index),
+                )),
+            }
+        }
+
+        for (id, (kind, name), _) in existing {
+            steps.push((
+                CommandSyncOperation::Delete {
+                    id,
+                    kind,
+                    name,
+                },
+                None,
+            ));
+        }
+
+        Ok(Self {
+            steps,
+            unchanged,
+        })
+    }
+
+    fn into_report(self, bulk_overwrite: bool, dry_run: bool) -> CommandSyncReport {
+        let mut report = CommandSyncReport {
+            unchanged: self.unchanged,
+            bulk_overwrite,
+            dry_run,
+            ..CommandSyncReport::default()
+        };
+
+        for (operation, _) in self.steps {
+            match operation {
+                CommandSyncOperation::Create {
+                    ..
+                } => report.created += 1,
+                CommandSyncOperation::Edit {
+                    ..
+                } => report.updated += 1,
+                CommandSyncOperation::Delete {
+                    ..
+                } => report.deleted += 1,
+            }
+
+            report.operations.push(operation);
+        }
+
+        report
+    }
+}
+
+/// The type and name of a command, which identify it among the commands of a
+/// guild.
+fn command_key(map: &JsonMap) -> Result<(ApplicationCommandType, String)> {
+    let kind = match map.get("type") {
+        Some(kind) => ApplicationCommandType::deserialize(kind)?,
+        None => ApplicationCommandType::ChatInput,
+    };
+    let name = map.get("name").and_then(Value::as_str).unwrap_or_default();
+
+    Ok((kind, name.to_string()))
+}
+
+/// Whether an existing command or option is the same as the desired one.
+///
+/// Fields Discord returns without being set, such as the Id, are ignored.
+fn is_unchanged(desired: &JsonMap, existing: &JsonMap, is_command: bool) -> bool {
+    let is_reset = |key: &str| match existing.get(key) {
+        Some(value) if !desired.contains_key(key) => is_unset(key, value, is_command),
+        _ => true,
+    };
+
+    desired.iter().all(|(key, value)| is_value_unchanged(key, value, existing.get(key)))
+        && RESET_FIELDS.iter().all(|key| is_reset(key))
+}
+
+fn is_value_unchanged(key: &str, desired: &Value, existing: Option<&Value>) -> bool {
+    let existing = match existing {
+        Some(existing) => existing,
+        None => return is_unset(key, desired, false),
+    };
+
+    match (desired, existing) {
+        (Value::Number(desired), Value::Number(existing)) => desired.as_f64() == existing.as_f64(),
+        (Value::Array(desired), Value::Array(existing)) => {
+            desired.len() == existing.len()
+                && desired.iter().zip(existing).all(|(desired, existing)| {
+                    match (desired.as_object(), existing.as_object()) {
+                        (Some(desired), Some(existing)) => is_unchanged(desired, existing, false),
+                        _ => is_value_unchanged(key, desired, Some(existing)),
+                    }
+                })
+        },
+        // Localizations are only compared as a whole, so removed ones count.
+        (Value::Object(desired), Value::Object(existing)) => desired == existing,
+        (Value::Null, existing) | (existing, Value::Null) => is_unset(key, existing, false),
+        (desired, existing) => desired == existing,
+    }
+}
+
+/// Whether the value of a field is the one it has when not set.
+fn is_unset(key: &str, value: &Value, is_command: bool) -> bool {
+    match (key, value) {
+        ("type", value) if is_command => value.as_u64() == Some(1),
+        ("default_permission", value) => value == &Value::Bool(true),
+        (_, Value::Null) | (_, Value::Bool(false)) => true,
+        (_, Value::String(value)) => value.is_empty(),
+        (_, Value::Array(values)) => values.is_empty(),
+        (_, Value::Object(map)) => map.is_empty(),
+        _ => false,
+    }
+}
+
+/// Syncs the commands of a guild, refer to [`GuildId::sync_commands_with`].
+#[cfg(feature = "model")]
+pub(crate) async fn sync_guild_commands(
+    http: &Http,
+    guild_id: GuildId,
+    desired: Vec<CreateApplicationCommand>,
+    options: &CommandSyncOptions,
+) -> Result<CommandSyncReport> {
+    let desired = desired
+        .into_iter()
+        .map(|command| {
+            let map = utils::hashmap_to_json_map(command.0);
+            check_command_options(&map)?;
+            check_command_contexts(&map)?;
+
+            Ok(map)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Fetched as JSON, as the model omits fields which are compared, such as
+    // the localizations.
+    let route = RouteInfo::GetGuildApplicationCommands {
+        application_id: http.application_id,
+        guild_id: guild_id.0,
+    };
+    let existing: Vec<JsonMap> = http.fire(RequestBuilder::new(route).build()).await?;
+
+    let plan = CommandSyncPlan::new(&desired, &existing)?;
+    let bulk_overwrite = plan.steps.len() > options.bulk_threshold;
+
+    if options.dry_run || plan.steps.is_empty() {
+        return Ok(plan.into_report(bulk_overwrite, options.dry_run));
+    }
+
+    if bulk_overwrite {
+        let commands = desired.into_iter().map(Value::Object).collect();
+        http.create_guild_application_commands(guild_id.0, &Value::Array(commands)).await?;
+
+        return Ok(plan.into_report(true, false));
+    }
+
+    for (operation, index) in &plan.steps {
+        let command = index.and_then(|index| desired.get(index)).cloned().map(Value::Object);
+
+        match (operation, command) {
+            (CommandSyncOperation::Create {
+                ..
+            }, Some(command)) => {
+                http.create_guild_application_command(guild_id.0, &command).await?;
+            },
+            (CommandSyncOperation::Edit {
+                id, ..
+            }, Some(command)) => {
+                http.edit_guild_application_command(guild_id.0, id.0, &command).await?;
+            },
+            (CommandSyncOperation::Delete {
+                id, ..
+            }, _) => {
+                http.delete_guild_application_command(guild_id.0, id.0).await?;
+            },
+            // Creating and editing always use a desired command.
+            _ => {},
+        }
+    }
+
+    Ok(plan.into_report(false, false))
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Value};
+
+    use super::{CommandSyncOperation, CommandSyncPlan};
+    use crate::internal::prelude::*;
+    use crate::model::id::CommandId;
+    use crate::model::interactions::application_command::ApplicationCommandType;
+
+    fn map(value: Value) -> JsonMap {
+        match value {
+            Value::Object(map) => map,
+            _ => unreachable!("fixtures are objects"),
+        }
+    }
+
+    /// A command as returned by Discord, with the fields it fills in.
+    fn existing(id: u64, name: &str, description: &str) -> JsonMap {
+        map(json!({
+            "id": id.to_string(),
+            "application_id": "1",
+            "version": "1",
+            "type": 1,
+            "name": name,
+            "description": description,
+            "default_permission": true,
+            "dm_permission": true,
+            "default_member_permissions": null,
+            "nsfw": false,
+        }))
+    }
+
+    fn desired(name: &str, description: &str) -> JsonMap {
+        map(json!({"name": name, "description": description}))
+    }
+
+    fn plan(desired: &[JsonMap], existing: &[JsonMap]) -> CommandSyncPlan {
+        match CommandSyncPlan::new(desired, existing) {
+            Ok(plan) => plan,
+            Err(why) => panic!("planning failed: {:?}", why),
+        }
+    }
+
+    fn operations(plan: CommandSyncPlan) -> Vec<CommandSyncOperation> {
+        plan.steps.into_iter().map(|(operation, _)| operation).collect()
+    }
+
+    #[test]
+    fn test_plan_operations() {
+        let plan = plan(
+            &[desired("ping", "Pong!"), desired("echo", "Repeats"), desired("new", "New")],
+            &[existing(1, "ping", "Pong!"), existing(2, "echo", "Echoes"), existing(3, "old", "Old")],
+        );
+
+        assert_eq!(plan.unchanged, 1);
+        assert_eq!(plan.steps[0].1, Some(1));
+        assert_eq!(plan.steps[1].1, Some(2));
+
+        let report = plan.into_report(false, true);
+        assert_eq!(report.operations, vec![
+            CommandSyncOperation::Edit {
+                id: CommandId(2),
+                kind: ApplicationCommandType::ChatInput,
+                name: "echo".to_string(),
+            },
+            CommandSyncOperation::Create {
+                kind: ApplicationCommandType::ChatInput,
+                name: "new".to_string(),
+            },
+            CommandSyncOperation::Delete {
+                id: CommandId(3),
+                kind: ApplicationCommandType::ChatInput,
+                name: "old".to_string(),
+            },
+        ]);
+        assert_eq!((report.created, report.updated, report.deleted), (1, 1, 1));
+        assert!(report.dry_run);
+    }
+
+    #[test]
+    fn test_plan_matches_kind() {
+        let mut user_command = desired("Info", "");
+        user_command.insert("type".to_string(), json!(2));
+
+        let plan = plan(&[user_command], &[existing(1, "Info", "")]);
+
+        assert!(matches!(operations(plan)[..], [
+            CommandSyncOperation::Create {
+                kind: ApplicationCommandType::User,
+                ..
+            },
+            CommandSyncOperation::Delete {
+                kind: ApplicationCommandType::ChatInput,
+                ..
+            },
+        ]));
+    }
+
+    #[test]
+    fn test_plan_compares_options() {
+        let option = |min_value: Value| {
+            json!({"type": 4, "name": "count", "description": "How many", "min_value": min_value})
+        };
+
+        let mut current = existing(1, "roll", "Rolls dice");
+        current.insert("options".to_string(), json!([option(json!(1.0))]));
+
+        let mut same = desired("roll", "Rolls dice");
+        same.insert("options".to_string(), json!([option(json!(1))]));
+        assert_eq!(plan(&[same], &[current.clone()]).unchanged, 1);
+
+        let mut changed = desired("roll", "Rolls dice");
+        changed.insert("options".to_string(), json!([option(json!(2))]));
+        assert_eq!(plan(&[changed], &[current.clone()]).unchanged, 0);
+
+        // The options are removed by editing the command.
+        assert_eq!(plan(&[desired("roll", "Rolls dice")], &[current]).unchanged, 0);
+    }
+
+    #[test]
+    fn test_plan_compares_localizations() {
+        let mut current = existing(1, "ping", "Pong!");
+        current.insert("name_localizations".to_string(), json!({"de": "ping", "fr": "ping"}));
+
+        let mut fewer = desired("ping", "Pong!");
+        fewer.insert("name_localizations".to_string(), json!({"de": "ping"}));
+        assert_eq!(plan(&[fewer], &[current.clone()]).unchanged, 0);
+
+        let mut same = desired("ping", "Pong!");
+        same.insert("name_localizations".to_string(), json!({"de": "ping", "fr": "ping"}));
+        assert_eq!(plan(&[same], &[current]).unchanged, 1);
+
+        let mut unset = existing(1, "ping", "Pong!");
+        unset.insert("name_localizations".to_string(), Value::Null);
+        assert_eq!(plan(&[desired("ping", "Pong!")], &[unset]).unchanged, 1);
+    }
+}
@@ -0,0 +1,184 @@
+use std::ops::Deref;
+
+#[cfg(feature = "model")]
+use crate::builder::CreateInteractionResponseFollowup;
+#[cfg(feature = "model")]
+use crate::http::Http;
+#[cfg(feature = "model")]
+use crate::internal::prelude::*;
+use crate::model::channel::Message;
+use crate::model::id::ApplicationId;
+
+/// A followup message of an interaction, along with the interaction's token,
+/// which is needed to edit or delete the message.
+///
+/// Followup messages are sent through the interaction's webhook, so unlike
+/// other messages they can not be edited by [`Message::edit`]. This derefs to
+/// the [`Message`] for read access.
+#[derive(Clone, Debug)]
+pub struct FollowupMessage {
+    message: Message,
+    application_id: ApplicationId,
+    token: String,
+}
+
+impl FollowupMessage {
+    /// Wraps a followup `message` of the interaction with the given `token`,
+    /// which belongs to the application with the given Id.
+    pub fn new(
+        message: Message,
+        application_id: impl Into<ApplicationId>,
+        token: impl Into<String>,
+    ) -> Self {
+        Self {
+            message,
+            application_id: application_id.into(),
+            token: token.into(),
+        }
+    }
+
+    /// The Id of the application the interaction belongs to.
+    pub fn application_id(&self) -> ApplicationId {
+        self.application_id
+    }
+
+    /// The token of the interaction the message was sent for.
+    ///
+    /// **Note**: Interaction tokens are only valid for 15 minutes.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Unwraps the message, discarding the interaction's token.
+    pub fn into_message(self) -> Message {
+        self.message
+    }
+
+    /// Edits the message, replacing it with the edited message.
+    ///
+    /// **Note**: Message contents must be under 2000 unicode code points.
+    ///
+    /// # Errors
+    ///
+    /// Refer to [`Message::edit_via_interaction_token`].
+    #[cfg(feature = "model")]
+    pub async fn edit<'a, F>(&mut self, http: impl AsRef<Http>, f: F) -> Result<()>
+    where
+        for<'b> F: FnOnce(
+            &'b mut CreateInteractionResponseFollowup<'a>,
+        ) -> &'b mut CreateInteractionResponseFollowup<'a>,
+    {
+        self.message.edit_followup(http.as_ref(), self.application_id.0, &self.token, f).await
+    }
+
+    /// Deletes the message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Http`] if the API returns an error, such as if the
+    /// interaction token expired.
+    ///
+    /// [`Error::Http`]: crate::error::Error::Http
+    #[cfg(feature = "model")]
+    pub async fn delete(&self, http: impl AsRef<Http>) -> Result<()> {
+        http.as_ref()
+            .delete_followup_message_for(self.application_id.0, &self.token, self.message.id.0)
+            .await
+    }
+}
+
+impl Deref for FollowupMessage {
+    type Target = Message;
+
+    fn deref(&self) -> &Self::Target {
+        &self.message
+    }
+}
+
+impl AsRef<Message> for FollowupMessage {
+    fn as_ref(&self) -> &Message {
+        &self.message
+    }
+}
+
+impl From<FollowupMessage> for Message {
+    fn from(followup: FollowupMessage) -> Self {
+        followup.message
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::FollowupMessage;
+    use crate::model::channel::Message;
+    use crate::model::id::{ApplicationId, MessageId};
+
+    fn message() -> Message {
+        serde_json::from_value(json!({
+            "id": "846962698468081685",
+            "channel_id": "381880193700069377",
+            "application_id": "846961981552525312",
+            "author": {
+                "id": "846961981552525312",
+                "username": "bot",
+                "discriminator": "0001",
+                "avatar": null,
+            },
+            "content": "followup",
+            "timestamp": "2021-05-25T00:00:00+00:00",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "embeds": [],
+            "pinned": false,
+            "type": 0,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_followup_message() {
+        let followup = FollowupMessage::new(message(), 846961981552525312, "token");
+
+        // The message can be read through the wrapper.
+        assert_eq!(followup.id, MessageId(846962698468081685));
+        assert_eq!(followup.content, "followup");
+        assert_eq!(followup.application_id(), ApplicationId(846961981552525312));
+        assert_eq!(followup.token(), "token");
+
+        let message: Message = followup.into();
+        assert_eq!(message.content, "followup");
+    }
+
+    #[cfg(feature = "model")]
+    #[tokio::test]
+    async fn test_edit_checks_content_length() {
+        use crate::http::Http;
+        use crate::internal::prelude::*;
+        use crate::model::ModelError;
+
+        let http = Http::default();
+        let mut followup = FollowupMessage::new(message(), 846961981552525312, "token");
+        let content = "a".repeat(2001);
+
+        // Too long messages are rejected before sending any request.
+        match followup.edit(&http, |m| m.content(&content)).await {
+            Err(Error::Model(ModelError::MessageTooLong(1))) => {},
+            other => panic!("unexpected result: {:?}", other),
+        }
+        match followup
+            .message
+            .edit_via_interaction_token(&http, "token", |m| m.content(&content))
+            .await
+        {
+            Err(Error::Model(ModelError::MessageTooLong(1))) => {},
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(followup.content, "followup");
+    }
+}
@@ -1,18 +1,33 @@
 use std::convert::TryFrom;
+use std::fmt;
 
 use serde::de::Error as DeError;
 use serde::{Serialize, Serializer};
 
+use super::application_command::ApplicationCommandInteractionDataResolved;
 use super::prelude::*;
 #[cfg(feature = "model")]
 use crate::builder::{
     CreateInteractionResponse,
+    CreateInteractionResponseData,
     CreateInteractionResponseFollowup,
     EditInteractionResponse,
 };
+#[cfg(feature = "collector")]
+use crate::client::bridge::gateway::ShardMessenger;
+#[cfg(feature = "collector")]
+use crate::collector::{CollectModalInteraction, ModalInteractionCollectorBuilder};
 #[cfg(feature = "model")]
 use crate::http::Http;
-use crate::model::interactions::InteractionType;
+#[cfg(feature = "model")]
+use crate::model::interactions::{check_components, check_response_kind};
+#[cfg(feature = "model")]
+use crate::model::interactions::followup::FollowupMessage;
+use crate::model::interactions::{
+    remove_invocation_fields,
+    InteractionType,
+    ResponseEphemerality,
+};
 #[cfg(feature = "model")]
 use crate::utils;
 
@@ -49,9 +64,34 @@ pub struct MessageComponentInteraction {
     /// Always `1`.
     pub version: u8,
     /// The guild's preferred locale.
-    pub guild_locale: Option<String>,
+    pub guild_locale: Option<Locale>,
     /// The selected language of the invoking user.
-    pub locale: String,
+    pub locale: Locale,
+    /// The entitlements of the invoking user and guild, for monetized applications.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entitlements: Vec<Entitlement>,
+    /// The permissions of the bot in the channel the interaction was invoked
+    /// from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_permissions: Option<Permissions>,
+    /// The context the interaction was invoked from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<InteractionContext>,
+    /// The installations of the application which authorized the interaction.
+    #[serde(skip_serializing_if = "AuthorizingIntegrationOwners::is_empty")]
+    pub authorizing_integration_owners: AuthorizingIntegrationOwners,
+    /// Whether [`Self::edit_original_interaction_response`] returns a
+    /// [`ModelError::EphemeralResponseChange`] instead of logging a warning
+    /// when an edit would change whether the initial response is ephemeral,
+    /// which Discord ignores.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`ModelError::EphemeralResponseChange`]: crate::model::ModelError::EphemeralResponseChange
+    #[serde(skip)]
+    pub strict_ephemerality: bool,
+    #[serde(skip)]
+    response_ephemerality: ResponseEphemerality,
 }
 
 #[cfg(feature = "model")]
@@ -64,7 +104,9 @@ impl MessageComponentInteraction {
     ///
     /// [`Error::Http`]: crate::error::Error::Http
     pub async fn get_interaction_response(&self, http: impl AsRef<Http>) -> Result<Message> {
-        http.as_ref().get_original_interaction_response(&self.token).await
+        http.as_ref()
+            .get_original_interaction_response_for(self.application_id.0, &self.token)
+            .await
     }
 
     /// Creates a response to the interaction received.
@@ -73,7 +115,10 @@ impl MessageComponentInteraction {
     ///
     /// # Errors
     ///
-    /// Returns an [`Error::Model`] if the message content is too long.
+    /// Returns an [`Error::Model`] if the message content is too long, or if
+    /// the response type is not valid for this interaction; refer to
+    /// [`InteractionResponseType::is_valid_for`]. The check can be skipped by
+    /// using [`Http::create_interaction_response`] directly.
     /// May also return an [`Error::Http`] if the API returns an error,
     /// or an [`Error::Json`] if there is an error in deserializing the
     /// API response.
@@ -82,6 +127,7 @@ impl MessageComponentInteraction {
     ///
     /// [`Error::Model`]: crate::error::Error::Model
     /// [`Error::Http`]: crate::error::Error::Http
+    /// [`InteractionResponseType::is_valid_for`]: crate::model::interactions::InteractionResponseType::is_valid_for
     /// [`Error::Json`]: crate::error::Error::Json
     pub async fn create_interaction_response<F>(&self, http: impl AsRef<Http>, f: F) -> Result<()>
     where
@@ -92,10 +138,80 @@ impl MessageComponentInteraction {
 
         let map = utils::hashmap_to_json_map(interaction_response.0);
 
+        check_response_kind(&map, InteractionType::MessageComponent)?;
         Message::check_content_length(&map)?;
         Message::check_embed_length(&map)?;
+        check_components(&map)?;
+
+        let ephemeral = ResponseEphemerality::of_response(&map);
+
+        http.as_ref()
+            .create_interaction_response_with_files(
+                self.id.0,
+                &self.token,
+                interaction_response.1,
+                map,
+            )
+            .await?;
+
+        if let Some(ephemeral) = ephemeral {
+            self.response_ephemerality.set(ephemeral);
+        }
+
+        Ok(())
+    }
+
+    /// Updates the message the component is attached to, as an
+    /// [`InteractionResponseType::UpdateMessage`] response.
+    ///
+    /// Files added with [`CreateInteractionResponseData::add_file`] are
+    /// uploaded along with the response. As when editing a message, they are
+    /// added to the attachments the message already has, unless attachments
+    /// are kept with [`CreateInteractionResponseData::keep_attachment`] or
+    /// removed with [`CreateInteractionResponseData::clear_attachments`].
+    ///
+    /// # Examples
+    ///
+    /// Replace the image of a message when one of its buttons is clicked:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::http::Http;
+    /// # use serenity::model::interactions::message_component::MessageComponentInteraction;
+    /// # use serenity::Result;
+    /// #
+    /// # async fn run(http: &Http, interaction: MessageComponentInteraction) -> Result<()> {
+    /// let next = std::path::Path::new("./next.png");
+    ///
+    /// interaction
+    ///     .update_message(http, |d| {
+    ///         d.clear_attachments().add_file(next).embed(|e| e.image("attachment://next.png"))
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::create_interaction_response`].
+    pub async fn update_message<F>(&self, http: impl AsRef<Http>, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut CreateInteractionResponseData) -> &mut CreateInteractionResponseData,
+    {
+        self.create_interaction_response(http, |r| {
+            r.kind(InteractionResponseType::UpdateMessage).interaction_response_data(f)
+        })
+        .await
+    }
 
-        http.as_ref().create_interaction_response(self.id.0, &self.token, &Value::Object(map)).await
+    /// Whether the initial response to the interaction is ephemeral.
+    ///
+    /// This is only known once the interaction was responded to with a
+    /// message, or a deferred one, by [`Self::create_interaction_response`] of
+    /// this interaction or one of its clones.
+    #[must_use]
+    pub fn is_response_ephemeral(&self) -> Option<bool> {
+        self.response_ephemerality.get()
     }
 
     /// Edits the initial interaction response.
@@ -110,20 +226,23 @@ impl MessageComponentInteraction {
     ///
     /// # Errors
     ///
-    /// Returns [`Error::Model`] if the edited content is too long.
+    /// Returns [`Error::Model`] if the edited content is too long, or if the
+    /// edit would change whether the response is ephemeral while
+    /// [`Self::strict_ephemerality`] is set.
     /// May also return [`Error::Http`] if the API returns an error,
     /// or an [`Error::Json`] if there is an error deserializing the response.
     ///
     /// [`Error::Model`]: crate::error::Error::Model
     /// [`Error::Http`]: crate::error::Error::Http
     /// [`Error::Json`]: crate::error::Error::Json
-    pub async fn edit_original_interaction_response<F>(
+    pub async fn edit_original_interaction_response<'a, F>(
         &self,
         http: impl AsRef<Http>,
         f: F,
     ) -> Result<Message>
     where
-        F: FnOnce(&mut EditInteractionResponse) -> &mut EditInteractionResponse,
+        for<'b> F:
+            FnOnce(&'b mut EditInteractionResponse<'a>) -> &'b mut EditInteractionResponse<'a>,
     {
         let mut interaction_response = EditInteractionResponse::default();
         f(&mut interaction_response);
@@ -132,8 +251,17 @@ impl MessageComponentInteraction {
 
         Message::check_content_length(&map)?;
         Message::check_embed_length(&map)?;
+        check_components(&map)?;
+        self.response_ephemerality.check_edit(&map, self.strict_ephemerality)?;
 
-        http.as_ref().edit_original_interaction_response(&self.token, &Value::Object(map)).await
+        http.as_ref()
+            .edit_original_interaction_response_with_files_for(
+                self.application_id.0,
+                &self.token,
+                interaction_response.1,
+                map,
+            )
+            .await
     }
 
     /// Deletes the initial interaction response.
@@ -143,11 +271,16 @@ impl MessageComponentInteraction {
     /// May return [`Error::Http`] if the API returns an error.
     /// Such as if the response was already deleted.
     pub async fn delete_original_interaction_response(&self, http: impl AsRef<Http>) -> Result<()> {
-        http.as_ref().delete_original_interaction_response(&self.token).await
+        http.as_ref()
+            .delete_original_interaction_response_for(self.application_id.0, &self.token)
+            .await
     }
 
     /// Creates a followup response to the response sent.
     ///
+    /// The message is returned along with the interaction's token, so that it
+    /// can still be edited or deleted through the returned [`FollowupMessage`].
+    ///
     /// **Note**: Message contents must be under 2000 unicode code points.
     ///
     /// # Errors
@@ -163,7 +296,7 @@ impl MessageComponentInteraction {
         &self,
         http: impl AsRef<Http>,
         f: F,
-    ) -> Result<Message>
+    ) -> Result<FollowupMessage>
     where
         for<'b> F: FnOnce(
             &'b mut CreateInteractionResponseFollowup<'a>,
@@ -176,8 +309,14 @@ impl MessageComponentInteraction {
 
         Message::check_content_length(&map)?;
         Message::check_embed_length(&map)?;
+        check_components(&map)?;
+
+        let message = http
+            .as_ref()
+            .create_followup_message_for(self.application_id.0, &self.token, &Value::Object(map))
+            .await?;
 
-        http.as_ref().create_followup_message(&self.token, &Value::Object(map)).await
+        Ok(FollowupMessage::new(message, self.application_id, self.token.clone()))
     }
 
     /// Edits a followup response to the response sent.
@@ -211,9 +350,16 @@ impl MessageComponentInteraction {
 
         Message::check_content_length(&map)?;
         Message::check_embed_length(&map)?;
+        check_components(&map)?;
 
         http.as_ref()
-            .edit_followup_message(&self.token, message_id.into().into(), &Value::Object(map))
+            .edit_followup_message_with_files_for(
+                self.application_id.0,
+                &self.token,
+                message_id.into().into(),
+                interaction_response.1,
+                map,
+            )
             .await
     }
 
@@ -228,7 +374,13 @@ impl MessageComponentInteraction {
         http: impl AsRef<Http>,
         message_id: M,
     ) -> Result<()> {
-        http.as_ref().delete_followup_message(&self.token, message_id.into().into()).await
+        http.as_ref()
+            .delete_followup_message_for(
+                self.application_id.0,
+                &self.token,
+                message_id.into().into(),
+            )
+            .await
     }
 
     /// Gets a followup message.
@@ -242,7 +394,9 @@ impl MessageComponentInteraction {
         http: impl AsRef<Http>,
         message_id: M,
     ) -> Result<Message> {
-        http.as_ref().get_followup_message(&self.token, message_id.into().into()).await
+        http.as_ref()
+            .get_followup_message_for(self.application_id.0, &self.token, message_id.into().into())
+            .await
     }
 
     /// Helper function to defer an interaction
@@ -263,6 +417,30 @@ impl MessageComponentInteraction {
         })
         .await
     }
+
+    /// Await a single modal submit interaction of the user who triggered this
+    /// interaction, such as after responding with a modal.
+    #[cfg(feature = "collector")]
+    pub fn await_modal_interaction<'a>(
+        &self,
+        shard_messenger: &'a impl AsRef<ShardMessenger>,
+    ) -> CollectModalInteraction<'a> {
+        CollectModalInteraction::new(shard_messenger)
+            .author_id(self.user.id.0)
+            .message_id(self.message.id.0)
+    }
+
+    /// Returns a stream builder which can be awaited to obtain a stream of
+    /// modal submit interactions of the user who triggered this interaction.
+    #[cfg(feature = "collector")]
+    pub fn await_modal_interactions<'a>(
+        &self,
+        shard_messenger: &'a impl AsRef<ShardMessenger>,
+    ) -> ModalInteractionCollectorBuilder<'a> {
+        ModalInteractionCollectorBuilder::new(shard_messenger)
+            .author_id(self.user.id.0)
+            .message_id(self.message.id.0)
+    }
 }
 
 impl<'de> Deserialize<'de> for MessageComponentInteraction {
@@ -336,12 +514,11 @@ impl<'de> Deserialize<'de> for MessageComponentInteraction {
             .map_err(DeError::custom)?;
 
         let guild_id = match map.contains_key("guild_id") {
-            true => Some(
-                map.remove("guild_id")
-                    .ok_or_else(|| DeError::custom("expected guild_id"))
-                    .and_then(GuildId::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
+            true => map
+                .remove("guild_id")
+                .ok_or_else(|| DeError::custom("expected guild_id"))
+                .and_then(Option::<GuildId>::deserialize)
+                .map_err(DeError::custom)?,
             false => None,
         };
 
@@ -352,12 +529,11 @@ impl<'de> Deserialize<'de> for MessageComponentInteraction {
             .map_err(DeError::custom)?;
 
         let member = match map.contains_key("member") {
-            true => Some(
-                map.remove("member")
-                    .ok_or_else(|| DeError::custom("expected member"))
-                    .and_then(Member::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
+            true => map
+                .remove("member")
+                .ok_or_else(|| DeError::custom("expected member"))
+                .and_then(Option::<Member>::deserialize)
+                .map_err(DeError::custom)?,
             false => None,
         };
 
@@ -389,21 +565,32 @@ impl<'de> Deserialize<'de> for MessageComponentInteraction {
             .map_err(DeError::custom)?;
 
         let guild_locale = match map.contains_key("guild_locale") {
-            true => Some(
-                map.remove("guild_locale")
-                    .ok_or_else(|| DeError::custom("expected guild_locale"))
-                    .and_then(String::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
+            true => map
+                .remove("guild_locale")
+                .ok_or_else(|| DeError::custom("expected guild_locale"))
+                .and_then(Option::<Locale>::deserialize)
+                .map_err(DeError::custom)?,
             false => None,
         };
 
         let locale = map
             .remove("locale")
             .ok_or_else(|| DeError::custom("expected locale"))
-            .and_then(String::deserialize)
+            .and_then(Locale::deserialize)
             .map_err(DeError::custom)?;
 
+        let entitlements = match map.contains_key("entitlements") {
+            true => map
+                .remove("entitlements")
+                .ok_or_else(|| DeError::custom("expected entitlements"))
+                .and_then(Vec::<Entitlement>::deserialize)
+                .map_err(DeError::custom)?,
+            false => Vec::new(),
+        };
+
+        let (app_permissions, context, authorizing_integration_owners) =
+            remove_invocation_fields(&mut map)?;
+
         Ok(Self {
             id,
             application_id,
@@ -418,6 +605,12 @@ impl<'de> Deserialize<'de> for MessageComponentInteraction {
             version,
             guild_locale,
             locale,
+            entitlements,
+            app_permissions,
+            context,
+            authorizing_integration_owners,
+            strict_ephemerality: false,
+            response_ephemerality: ResponseEphemerality::default(),
         })
     }
 }
@@ -431,8 +624,52 @@ pub struct MessageComponentInteractionData {
     /// The type of the component.
     pub component_type: ComponentType,
     /// The given values of the [`SelectMenu`]s
+    ///
+    /// For select menus populated by Discord, these are the Ids of the
+    /// selected users, roles or channels, which are also available with
+    /// [`Self::user_ids`], [`Self::role_ids`] and [`Self::channel_ids`].
     #[serde(default)]
     pub values: Vec<String>,
+    /// The users, members, roles and channels selected in a select menu
+    /// populated by Discord.
+    #[serde(default)]
+    pub resolved: ApplicationCommandInteractionDataResolved,
+}
+
+impl MessageComponentInteractionData {
+    /// The Ids of the users selected in a [`ComponentType::UserSelect`] or a
+    /// [`ComponentType::MentionableSelect`].
+    pub fn user_ids(&self) -> Vec<UserId> {
+        self.selected_ids(ComponentType::UserSelect, |id| self.resolved.users.contains_key(&id))
+    }
+
+    /// The Ids of the roles selected in a [`ComponentType::RoleSelect`] or a
+    /// [`ComponentType::MentionableSelect`].
+    pub fn role_ids(&self) -> Vec<RoleId> {
+        self.selected_ids(ComponentType::RoleSelect, |id| self.resolved.roles.contains_key(&id))
+    }
+
+    /// The Ids of the channels selected in a [`ComponentType::ChannelSelect`].
+    pub fn channel_ids(&self) -> Vec<ChannelId> {
+        self.selected_ids(ComponentType::ChannelSelect, |_| false)
+    }
+
+    /// Parses the values as Ids if the component is of the given type, or
+    /// only those which are resolved as the type of the Id if the component is
+    /// a [`ComponentType::MentionableSelect`].
+    fn selected_ids<Id: From<u64> + Copy>(
+        &self,
+        kind: ComponentType,
+        is_resolved: impl Fn(Id) -> bool,
+    ) -> Vec<Id> {
+        let ids = self.values.iter().filter_map(|value| value.parse::<u64>().ok()).map(Id::from);
+
+        match self.component_type {
+            component_type if component_type == kind => ids.collect(),
+            ComponentType::MentionableSelect => ids.filter(|id| is_resolved(*id)).collect(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 // A component.
@@ -462,9 +699,15 @@ impl<'de> Deserialize<'de> for Component {
             ComponentType::Button => serde_json::from_value::<Button>(Value::Object(map))
                 .map(Component::Button)
                 .map_err(DeError::custom),
-            ComponentType::SelectMenu => serde_json::from_value::<SelectMenu>(Value::Object(map))
-                .map(Component::SelectMenu)
-                .map_err(DeError::custom),
+            ComponentType::SelectMenu
+            | ComponentType::UserSelect
+            | ComponentType::RoleSelect
+            | ComponentType::MentionableSelect
+            | ComponentType::ChannelSelect => {
+                serde_json::from_value::<SelectMenu>(Value::Object(map))
+                    .map(Component::SelectMenu)
+                    .map_err(DeError::custom)
+            },
             ComponentType::InputText => serde_json::from_value::<InputText>(Value::Object(map))
                 .map(Component::InputText)
                 .map_err(DeError::custom),
@@ -518,8 +761,18 @@ impl From<InputText> for Component {
 pub enum ComponentType {
     ActionRow = 1,
     Button = 2,
+    /// A select menu of options defined by the developer.
     SelectMenu = 3,
     InputText = 4,
+    /// A select menu of the users, which is populated by Discord.
+    UserSelect = 5,
+    /// A select menu of the roles, which is populated by Discord.
+    RoleSelect = 6,
+    /// A select menu of both the users and the roles, which is populated by
+    /// Discord.
+    MentionableSelect = 7,
+    /// A select menu of the channels, which is populated by Discord.
+    ChannelSelect = 8,
     Unknown = !0,
 }
 
@@ -527,9 +780,27 @@ enum_number!(ComponentType {
     ActionRow,
     Button,
     SelectMenu,
-    InputText
+    InputText,
+    UserSelect,
+    RoleSelect,
+    MentionableSelect,
+    ChannelSelect
 });
 
+impl ComponentType {
+    /// Whether the component is a [`SelectMenu`] of any kind.
+    pub fn is_select_menu(self) -> bool {
+        matches!(
+            self,
+            ComponentType::SelectMenu
+                | ComponentType::UserSelect
+                | ComponentType::RoleSelect
+                | ComponentType::MentionableSelect
+                | ComponentType::ChannelSelect
+        )
+    }
+}
+
 /// An action row.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ActionRow {
@@ -564,9 +835,15 @@ impl<'de> Deserialize<'de> for ActionRowComponent {
             ComponentType::Button => serde_json::from_value::<Button>(Value::Object(map))
                 .map(ActionRowComponent::Button)
                 .map_err(DeError::custom),
-            ComponentType::SelectMenu => serde_json::from_value::<SelectMenu>(Value::Object(map))
-                .map(ActionRowComponent::SelectMenu)
-                .map_err(DeError::custom),
+            ComponentType::SelectMenu
+            | ComponentType::UserSelect
+            | ComponentType::RoleSelect
+            | ComponentType::MentionableSelect
+            | ComponentType::ChannelSelect => {
+                serde_json::from_value::<SelectMenu>(Value::Object(map))
+                    .map(ActionRowComponent::SelectMenu)
+                    .map_err(DeError::custom)
+            },
             ComponentType::InputText => serde_json::from_value::<InputText>(Value::Object(map))
                 .map(ActionRowComponent::InputText)
                 .map_err(DeError::custom),
@@ -668,7 +945,9 @@ enum_number!(ButtonStyle {
 /// A select menu component.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SelectMenu {
-    /// The component type, it will always be [`ComponentType::SelectMenu`].
+    /// The component type, which is [`ComponentType::SelectMenu`] for a select
+    /// menu of options, or another type of select menu populated by Discord,
+    /// such as [`ComponentType::RoleSelect`].
     #[serde(rename = "type")]
     pub kind: ComponentType,
     /// The placeholder shown when nothing is selected.
@@ -680,8 +959,65 @@ pub struct SelectMenu {
     /// The maximum number of selections allowed.
     pub max_values: Option<u64>,
     /// The options of this select menu.
+    ///
+    /// **Note**: This is only available for [`ComponentType::SelectMenu`].
     #[serde(default)]
     pub options: Vec<SelectMenuOption>,
+    /// The types of channels which can be selected.
+    ///
+    /// **Note**: This is only available for [`ComponentType::ChannelSelect`],
+    /// and is empty if channels of any type can be selected.
+    #[serde(default)]
+    pub channel_types: Vec<ChannelType>,
+    /// The users, roles or channels which are selected by default.
+    ///
+    /// **Note**: This is only available for select menus populated by
+    /// Discord, such as [`ComponentType::RoleSelect`].
+    #[serde(default)]
+    pub default_values: Vec<SelectMenuDefaultValue>,
+}
+
+/// The type of a [`SelectMenu`], as set by [`CreateSelectMenu::kind`].
+///
+/// [`CreateSelectMenu::kind`]: crate::builder::CreateSelectMenu::kind
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum SelectMenuType {
+    /// A select menu of options defined by the developer.
+    String = 3,
+    /// A select menu of the users.
+    User = 5,
+    /// A select menu of the roles.
+    Role = 6,
+    /// A select menu of both the users and the roles.
+    Mentionable = 7,
+    /// A select menu of the channels, which can be limited to some types of
+    /// channels.
+    Channel = 8,
+}
+
+impl From<SelectMenuType> for ComponentType {
+    fn from(kind: SelectMenuType) -> Self {
+        match kind {
+            SelectMenuType::String => ComponentType::SelectMenu,
+            SelectMenuType::User => ComponentType::UserSelect,
+            SelectMenuType::Role => ComponentType::RoleSelect,
+            SelectMenuType::Mentionable => ComponentType::MentionableSelect,
+            SelectMenuType::Channel => ComponentType::ChannelSelect,
+        }
+    }
+}
+
+/// A user, role or channel which is selected by default in a [`SelectMenu`]
+/// populated by Discord.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", content = "id", rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum SelectMenuDefaultValue {
+    User(UserId),
+    Role(RoleId),
+    Channel(ChannelId),
 }
 
 /// A select menu component options.
@@ -727,3 +1063,177 @@ enum_number!(InputTextStyle {
     Paragraph,
     Unknown
 });
+
+/// A reason for Discord to reject the components of a message or interaction
+/// response, as found by [`CreateComponents::build_check`].
+///
+/// Each variant contains the index of the offending action row, and of the
+/// offending component within it where there is one.
+///
+/// [`CreateComponents::build_check`]: crate::builder::CreateComponents::build_check
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ComponentViolation {
+    /// There are more than 5 action rows. Contains the number of rows.
+    TooManyRows(usize),
+    /// An action row has no components.
+    EmptyRow {
+        row: usize,
+    },
+    /// An action row has more than 5 buttons. Contains the number of buttons.
+    TooManyButtons {
+        row: usize,
+        count: usize,
+    },
+    /// A select menu or input text shares its action row with other
+    /// components, such as buttons.
+    SharedRow {
+        row: usize,
+        component: usize,
+    },
+    /// A select menu has more than 25 options. Contains the number of options.
+    TooManyOptions {
+        row: usize,
+        component: usize,
+        count: usize,
+    },
+    /// The label of an input text is longer than 45 characters. Contains the
+    /// length of the label.
+    InputTextLabelTooLong {
+        row: usize,
+        component: usize,
+        length: usize,
+    },
+    /// The minimum length of an input text is greater than its maximum
+    /// length, or either is greater than 4000. Contains both lengths, as set.
+    InvalidInputTextLength {
+        row: usize,
+        component: usize,
+        min: Option<u64>,
+        max: Option<u64>,
+    },
+}
+
+impl fmt::Display for ComponentViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyRows(count) => write!(f, "there are {} action rows, over 5", count),
+            Self::EmptyRow {
+                row,
+            } => write!(f, "action row {} is empty", row),
+            Self::TooManyButtons {
+                row,
+                count,
+            } => write!(f, "action row {} has {} buttons, over 5", row, count),
+            Self::SharedRow {
+                row,
+                component,
+            } => {
+                write!(f, "component {} of action row {} must be alone in its row", component, row)
+            },
+            Self::TooManyOptions {
+                row,
+                component,
+                count,
+            } => write!(
+                f,
+                "select menu {} of action row {} has {} options, over 25",
+                component, row, count
+            ),
+            Self::InputTextLabelTooLong {
+                row,
+                component,
+                length,
+            } => write!(
+                f,
+                "the label of input text {} of action row {} is {} characters long, over 45",
+                component, row, length
+            ),
+            Self::InvalidInputTextLength {
+                row,
+                component,
+                ..
+            } => write!(
+                f,
+                "input text {} of action row {} has an invalid minimum or maximum length",
+                component, row
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_select_menu_kinds() {
+        let row: ActionRow = serde_json::from_value(json!({
+            "type": 1,
+            "components": [{
+                "type": 8,
+                "custom_id": "channels",
+                "channel_types": [0, 2],
+                "default_values": [{"id": "10", "type": "channel"}],
+            }],
+        }))
+        .unwrap();
+
+        let menu = match &row.components[0] {
+            ActionRowComponent::SelectMenu(menu) => menu,
+            other => panic!("expected a select menu, got {:?}", other),
+        };
+        assert_eq!(menu.kind, ComponentType::ChannelSelect);
+        assert!(menu.kind.is_select_menu());
+        assert_eq!(menu.channel_types, vec![ChannelType::Text, ChannelType::Voice]);
+        assert_eq!(menu.default_values, vec![SelectMenuDefaultValue::Channel(ChannelId(10))]);
+        assert!(menu.options.is_empty());
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_selected_ids() {
+        let data: MessageComponentInteractionData = serde_json::from_value(json!({
+            "custom_id": "roles",
+            "component_type": 6,
+            "values": ["1", "2"],
+        }))
+        .unwrap();
+
+        assert_eq!(data.role_ids(), vec![RoleId(1), RoleId(2)]);
+        assert!(data.user_ids().is_empty());
+        assert!(data.channel_ids().is_empty());
+
+        let data: MessageComponentInteractionData = serde_json::from_value(json!({
+            "custom_id": "mentionables",
+            "component_type": 7,
+            "values": ["3", "4"],
+            "resolved": {
+                "users": {
+                    "3": {"id": "3", "username": "user", "discriminator": "0001", "avatar": null},
+                },
+                "roles": {
+                    "4": {
+                        "id": "4",
+                        "guild_id": "1",
+                        "color": 0,
+                        "hoist": false,
+                        "managed": false,
+                        "mentionable": true,
+                        "name": "role",
+                        "permissions": "0",
+                        "position": 1,
+                    },
+                },
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(data.user_ids(), vec![UserId(3)]);
+        assert_eq!(data.role_ids(), vec![RoleId(4)]);
+        assert_eq!(data.resolved.roles[&RoleId(4)].name, "role");
+    }
+}
@@ -1,9 +1,14 @@
 pub mod application_command;
 pub mod autocomplete;
+pub mod command_sync;
+pub mod followup;
 pub mod message_component;
 pub mod modal;
 pub mod ping;
 
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
 use application_command::ApplicationCommandInteraction;
 use autocomplete::AutocompleteInteraction;
 use bitflags::__impl_bitflags;
@@ -15,6 +20,10 @@ use serde::ser::{Serialize, Serializer};
 use serde_json::Value;
 
 use super::prelude::*;
+#[cfg(feature = "model")]
+use crate::builder::{command_violations, component_violations};
+#[cfg(feature = "model")]
+use crate::http::CacheHttp;
 use crate::internal::prelude::*;
 
 #[derive(Clone, Debug)]
@@ -72,55 +81,252 @@ impl Interaction {
     }
 
     /// Gets the invoked guild locale.
-    pub fn guild_locale(&self) -> Option<&str> {
+    pub fn guild_locale(&self) -> Option<&Locale> {
+        match self {
+            Interaction::Ping(i) => i.guild_locale.as_ref(),
+            Interaction::ApplicationCommand(i) => i.guild_locale.as_ref(),
+            Interaction::MessageComponent(i) => i.guild_locale.as_ref(),
+            Interaction::Autocomplete(i) => i.guild_locale.as_ref(),
+            Interaction::ModalSubmit(i) => i.guild_locale.as_ref(),
+        }
+    }
+
+    /// Gets the selected locale of the invoking user.
+    ///
+    /// **Note**: This is always [`None`] for [`Interaction::Ping`].
+    pub fn locale(&self) -> Option<&Locale> {
+        match self {
+            Interaction::Ping(_) => None,
+            Interaction::ApplicationCommand(i) => Some(&i.locale),
+            Interaction::MessageComponent(i) => Some(&i.locale),
+            Interaction::Autocomplete(i) => Some(&i.locale),
+            Interaction::ModalSubmit(i) => Some(&i.locale),
+        }
+    }
+
+    /// Gets the entitlements of the invoking user and guild.
+    ///
+    /// **Note**: This is always empty for [`Interaction::Ping`].
+    pub fn entitlements(&self) -> &[Entitlement] {
+        match self {
+            Interaction::Ping(_) => &[],
+            Interaction::ApplicationCommand(i) => &i.entitlements,
+            Interaction::MessageComponent(i) => &i.entitlements,
+            Interaction::Autocomplete(i) => &i.entitlements,
+            Interaction::ModalSubmit(i) => &i.entitlements,
+        }
+    }
+
+    /// Gets the permissions of the bot in the channel the interaction was
+    /// invoked from.
+    ///
+    /// **Note**: This is always [`None`] for [`Interaction::Ping`].
+    pub fn app_permissions(&self) -> Option<Permissions> {
+        match self {
+            Interaction::Ping(_) => None,
+            Interaction::ApplicationCommand(i) => i.app_permissions,
+            Interaction::MessageComponent(i) => i.app_permissions,
+            Interaction::Autocomplete(i) => i.app_permissions,
+            Interaction::ModalSubmit(i) => i.app_permissions,
+        }
+    }
+
+    /// Whether the bot has all of the given permissions in the channel the
+    /// interaction was invoked from, according to [`Self::app_permissions`].
+    ///
+    /// Unlike computing the permissions from the cache, this is also correct
+    /// where the bot is not a member, such as for user-installed applications.
+    ///
+    /// **Note**: This is always `false` if the permissions are not known.
+    pub fn bot_can(&self, permissions: Permissions) -> bool {
+        self.app_permissions()
+            .map_or(false, |app_permissions| app_permissions.contains(permissions))
+    }
+
+    /// Gets the context the interaction was invoked from.
+    ///
+    /// **Note**: This is always [`None`] for [`Interaction::Ping`].
+    pub fn context(&self) -> Option<InteractionContext> {
+        match self {
+            Interaction::Ping(_) => None,
+            Interaction::ApplicationCommand(i) => i.context,
+            Interaction::MessageComponent(i) => i.context,
+            Interaction::Autocomplete(i) => i.context,
+            Interaction::ModalSubmit(i) => i.context,
+        }
+    }
+
+    /// Gets the installations of the application which authorized the
+    /// interaction.
+    ///
+    /// **Note**: This is always [`None`] for [`Interaction::Ping`].
+    pub fn authorizing_integration_owners(&self) -> Option<&AuthorizingIntegrationOwners> {
+        match self {
+            Interaction::Ping(_) => None,
+            Interaction::ApplicationCommand(i) => Some(&i.authorizing_integration_owners),
+            Interaction::MessageComponent(i) => Some(&i.authorizing_integration_owners),
+            Interaction::Autocomplete(i) => Some(&i.authorizing_integration_owners),
+            Interaction::ModalSubmit(i) => Some(&i.authorizing_integration_owners),
+        }
+    }
+
+    /// Gets the [`Member`] who invoked the interaction, if it was invoked in a
+    /// guild.
+    ///
+    /// The member sent with the interaction is returned if there is one.
+    /// Otherwise, the member is retrieved from the cache, or if it cannot be
+    /// found there or the `cache` feature is disabled, from Discord's API.
+    ///
+    /// **Note**: Only the member sent with the interaction has
+    /// [`Member::permissions`] set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModelError::ItemMissing`] if the interaction was not invoked
+    /// in a guild, or is an [`Interaction::Ping`].
+    ///
+    /// Returns [`Error::Http`] if the member had to be retrieved, and is not a
+    /// member of the guild anymore.
+    #[cfg(feature = "model")]
+    pub async fn author_member(&self, cache_http: impl CacheHttp) -> Result<Member> {
+        let (member, guild_id, user) = match self {
+            Interaction::Ping(_) => return Err(Error::Model(ModelError::ItemMissing)),
+            Interaction::ApplicationCommand(i) => (&i.member, i.guild_id, &i.user),
+            Interaction::MessageComponent(i) => (&i.member, i.guild_id, &i.user),
+            Interaction::Autocomplete(i) => (&i.member, i.guild_id, &i.user),
+            Interaction::ModalSubmit(i) => (&i.member, i.guild_id, &i.user),
+        };
+
+        if let Some(member) = member {
+            return Ok(member.clone());
+        }
+
+        let guild_id = match guild_id {
+            Some(guild_id) => guild_id,
+            None => return Err(Error::Model(ModelError::ItemMissing)),
+        };
+
+        #[cfg(feature = "cache")]
+        {
+            if let Some(cache) = cache_http.cache() {
+                if let Some(member) = cache.member(guild_id, user.id).await {
+                    return Ok(member);
+                }
+            }
+        }
+
+        cache_http.http().get_member(guild_id.0, user.id.0).await
+    }
+
+    /// Gets a reference to the inner [`PingInteraction`], if this is one.
+    pub fn as_ping(&self) -> Option<&PingInteraction> {
         match self {
-            Interaction::Ping(i) => i.guild_locale.as_ref().map(String::as_str),
-            Interaction::ApplicationCommand(i) => i.guild_locale.as_ref().map(String::as_str),
-            Interaction::MessageComponent(i) => i.guild_locale.as_ref().map(String::as_str),
-            Interaction::Autocomplete(i) => i.guild_locale.as_ref().map(String::as_str),
-            Interaction::ModalSubmit(i) => i.guild_locale.as_ref().map(String::as_str),
+            Interaction::Ping(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Converts this into a [`PingInteraction`], taking ownership of it.
+    pub fn into_ping(self) -> Option<PingInteraction> {
+        match self {
+            Interaction::Ping(i) => Some(i),
+            _ => None,
         }
     }
 
     /// Converts this to a [`PingInteraction`]
+    #[deprecated(note = "use `into_ping` instead")]
     pub fn ping(self) -> Option<PingInteraction> {
+        self.into_ping()
+    }
+
+    /// Gets a reference to the inner [`ApplicationCommandInteraction`], if this is one.
+    pub fn as_application_command(&self) -> Option<&ApplicationCommandInteraction> {
         match self {
-            Interaction::Ping(i) => Some(i),
+            Interaction::ApplicationCommand(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Converts this into an [`ApplicationCommandInteraction`], taking ownership of it.
+    pub fn into_application_command(self) -> Option<ApplicationCommandInteraction> {
+        match self {
+            Interaction::ApplicationCommand(i) => Some(i),
             _ => None,
         }
     }
 
     /// Converts this to an [`ApplicationCommandInteraction`]
+    #[deprecated(note = "use `into_application_command` instead")]
     pub fn application_command(self) -> Option<ApplicationCommandInteraction> {
+        self.into_application_command()
+    }
+
+    /// Gets a reference to the inner [`MessageComponentInteraction`], if this is one.
+    pub fn as_message_component(&self) -> Option<&MessageComponentInteraction> {
         match self {
-            Interaction::ApplicationCommand(i) => Some(i),
+            Interaction::MessageComponent(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Converts this into a [`MessageComponentInteraction`], taking ownership of it.
+    pub fn into_message_component(self) -> Option<MessageComponentInteraction> {
+        match self {
+            Interaction::MessageComponent(i) => Some(i),
             _ => None,
         }
     }
 
     /// Converts this to a [`MessageComponentInteraction`]
+    #[deprecated(note = "use `into_message_component` instead")]
     pub fn message_component(self) -> Option<MessageComponentInteraction> {
+        self.into_message_component()
+    }
+
+    /// Gets a reference to the inner [`AutocompleteInteraction`], if this is one.
+    pub fn as_autocomplete(&self) -> Option<&AutocompleteInteraction> {
         match self {
-            Interaction::MessageComponent(i) => Some(i),
+            Interaction::Autocomplete(i) => Some(i),
             _ => None,
         }
     }
 
-    /// Converts this to a [`AutocompleteInteraction`]
-    pub fn autocomplete(self) -> Option<AutocompleteInteraction> {
+    /// Converts this into an [`AutocompleteInteraction`], taking ownership of it.
+    pub fn into_autocomplete(self) -> Option<AutocompleteInteraction> {
         match self {
             Interaction::Autocomplete(i) => Some(i),
             _ => None,
         }
     }
 
-    /// Converts this to a [`ModalSubmitInteraction`]
-    pub fn modal_submit(self) -> Option<ModalSubmitInteraction> {
+    /// Converts this to an [`AutocompleteInteraction`]
+    #[deprecated(note = "use `into_autocomplete` instead")]
+    pub fn autocomplete(self) -> Option<AutocompleteInteraction> {
+        self.into_autocomplete()
+    }
+
+    /// Gets a reference to the inner [`ModalSubmitInteraction`], if this is one.
+    pub fn as_modal_submit(&self) -> Option<&ModalSubmitInteraction> {
         match self {
             Interaction::ModalSubmit(i) => Some(i),
             _ => None,
         }
     }
+
+    /// Converts this into a [`ModalSubmitInteraction`], taking ownership of it.
+    pub fn into_modal_submit(self) -> Option<ModalSubmitInteraction> {
+        match self {
+            Interaction::ModalSubmit(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Converts this to a [`ModalSubmitInteraction`]
+    #[deprecated(note = "use `into_modal_submit` instead")]
+    pub fn modal_submit(self) -> Option<ModalSubmitInteraction> {
+        self.into_modal_submit()
+    }
 }
 
 impl<'de> Deserialize<'de> for Interaction {
@@ -202,6 +408,178 @@ enum_number!(InteractionType {
     ModalSubmit
 });
 
+/// Where an interaction was invoked from.
+///
+/// [Discord docs](https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-object-interaction-context-types).
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum InteractionContext {
+    /// Invoked from within a guild.
+    Guild = 0,
+    /// Invoked from the direct messages with the bot.
+    BotDm = 1,
+    /// Invoked from a direct message or group direct message not including the
+    /// bot, only possible for user-installed applications.
+    PrivateChannel = 2,
+    Unknown = !0,
+}
+
+enum_number!(InteractionContext {
+    Guild,
+    BotDm,
+    PrivateChannel
+});
+
+impl InteractionContext {
+    /// The installation context an application command needs in order to be
+    /// available in this context, if any.
+    ///
+    /// Only user-installed applications can be invoked from
+    /// [`Self::PrivateChannel`]s, as guild-installed ones require the bot to
+    /// be present.
+    #[must_use]
+    pub fn required_installation(self) -> Option<InstallationContext> {
+        match self {
+            Self::PrivateChannel => Some(InstallationContext::User),
+            _ => None,
+        }
+    }
+}
+
+/// How an application is installed.
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/application#application-object-application-integration-types).
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum InstallationContext {
+    /// Installed to a guild.
+    Guild = 0,
+    /// Installed to a user.
+    User = 1,
+    Unknown = !0,
+}
+
+enum_number!(InstallationContext {
+    Guild,
+    User
+});
+
+/// The installations of an application which authorized an interaction.
+///
+/// [Discord docs](https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-object-authorizing-integration-owners-object).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct AuthorizingIntegrationOwners {
+    /// The guild the application is installed to, if it was authorized by a
+    /// [`InstallationContext::Guild`].
+    ///
+    /// **Note**: This is `GuildId(0)` if the interaction was invoked from the
+    /// direct messages with the bot.
+    pub guild_id: Option<GuildId>,
+    /// The user the application is installed to, if it was authorized by a
+    /// [`InstallationContext::User`].
+    pub user_id: Option<UserId>,
+}
+
+impl AuthorizingIntegrationOwners {
+    /// Whether the interaction was authorized by the given kind of
+    /// installation.
+    #[must_use]
+    pub fn contains(&self, kind: InstallationContext) -> bool {
+        match kind {
+            InstallationContext::Guild => self.guild_id.is_some(),
+            InstallationContext::User => self.user_id.is_some(),
+            InstallationContext::Unknown => false,
+        }
+    }
+
+    /// Whether neither installation is known, e.g. for interactions from before
+    /// user-installable applications.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.guild_id.is_none() && self.user_id.is_none()
+    }
+}
+
+impl<'de> Deserialize<'de> for AuthorizingIntegrationOwners {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        let mut map = JsonMap::deserialize(deserializer)?;
+
+        let guild_id = match map.contains_key("0") {
+            true => Some(
+                map.remove("0")
+                    .ok_or_else(|| DeError::custom("expected guild install owner"))
+                    .and_then(GuildId::deserialize)
+                    .map_err(DeError::custom)?,
+            ),
+            false => None,
+        };
+
+        let user_id = match map.contains_key("1") {
+            true => Some(
+                map.remove("1")
+                    .ok_or_else(|| DeError::custom("expected user install owner"))
+                    .and_then(UserId::deserialize)
+                    .map_err(DeError::custom)?,
+            ),
+            false => None,
+        };
+
+        Ok(Self {
+            guild_id,
+            user_id,
+        })
+    }
+}
+
+impl Serialize for AuthorizingIntegrationOwners {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = JsonMap::new();
+
+        if let Some(guild_id) = self.guild_id {
+            map.insert("0".to_string(), Value::String(guild_id.0.to_string()));
+        }
+
+        if let Some(user_id) = self.user_id {
+            map.insert("1".to_string(), Value::String(user_id.0.to_string()));
+        }
+
+        map.serialize(serializer)
+    }
+}
+
+/// Removes the fields describing where and by whom an interaction was
+/// invoked, which every interaction but a ping has, from its JSON `map`:
+/// `app_permissions`, `context` and `authorizing_integration_owners`.
+///
+/// Missing fields are defaulted, as interactions from before these fields
+/// existed do not have them.
+pub(crate) fn remove_invocation_fields<E: DeError>(
+    map: &mut JsonMap,
+) -> StdResult<(Option<Permissions>, Option<InteractionContext>, AuthorizingIntegrationOwners), E>
+{
+    fn remove<T, E>(map: &mut JsonMap, key: &str) -> StdResult<T, E>
+    where
+        T: for<'de> Deserialize<'de> + Default,
+        E: DeError,
+    {
+        map.remove(key).map_or_else(|| Ok(T::default()), |value| {
+            T::deserialize(value).map_err(DeError::custom)
+        })
+    }
+
+    Ok((
+        remove(map, "app_permissions")?,
+        remove(map, "context")?,
+        remove(map, "authorizing_integration_owners")?,
+    ))
+}
+
 /// The flags for an interaction response.
 #[derive(Clone)]
 #[non_exhaustive]
@@ -211,6 +589,8 @@ pub struct InteractionApplicationCommandCallbackDataFlags {
 
 __impl_bitflags! {
     InteractionApplicationCommandCallbackDataFlags: u64 {
+        /// Do not include any embeds when serializing this message.
+        SUPPRESS_EMBEDS = 1 << 2;
         /// Interaction message will only be visible to sender and will
         /// be quickly deleted.
         EPHEMERAL = 1 << 6;
@@ -247,6 +627,39 @@ pub struct MessageInteraction {
     pub user: User,
 }
 
+/// Sent when a [`Message`] was created by an [`Interaction`], either as its
+/// response or as a followup message.
+///
+/// This replaces [`MessageInteraction`], and additionally tells which
+/// installations of the application authorized the interaction.
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/message#message-interaction-metadata-object).
+///
+/// [`Message`]: crate::model::channel::Message
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct MessageInteractionMetadata {
+    /// The id of the interaction.
+    pub id: InteractionId,
+    /// The type of the interaction.
+    #[serde(rename = "type")]
+    pub kind: InteractionType,
+    /// The user who invoked the interaction.
+    pub user: User,
+    /// The installations of the application which authorized the interaction.
+    #[serde(default, skip_serializing_if = "AuthorizingIntegrationOwners::is_empty")]
+    pub authorizing_integration_owners: AuthorizingIntegrationOwners,
+    /// The Id of the original response message, if this is a followup
+    /// message.
+    pub original_response_message_id: Option<MessageId>,
+    /// The Id of the message containing the component which was interacted
+    /// with, if the interaction was a [`InteractionType::MessageComponent`].
+    pub interacted_message_id: Option<MessageId>,
+    /// The metadata of the interaction which opened the modal, if the
+    /// interaction was a [`InteractionType::ModalSubmit`].
+    pub triggering_interaction_metadata: Option<Box<MessageInteractionMetadata>>,
+}
+
 /// The available responses types for an interaction response.
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
 #[non_exhaustive]
@@ -259,4 +672,709 @@ pub enum InteractionResponseType {
     UpdateMessage = 7,
     Autocomplete = 8,
     Modal = 9,
+    PremiumRequired = 10,
+}
+
+impl InteractionResponseType {
+    fn from_num(num: u64) -> Option<Self> {
+        Some(match num {
+            1 => Self::Pong,
+            4 => Self::ChannelMessageWithSource,
+            5 => Self::DeferredChannelMessageWithSource,
+            6 => Self::DeferredUpdateMessage,
+            7 => Self::UpdateMessage,
+            8 => Self::Autocomplete,
+            9 => Self::Modal,
+            10 => Self::PremiumRequired,
+            _ => return None,
+        })
+    }
+
+    /// Whether Discord accepts this response type for an interaction of the
+    /// given type.
+    ///
+    /// For example, a [`ModalSubmitInteraction`] cannot be responded to with
+    /// another [`Self::Modal`], and only a [`MessageComponentInteraction`] or
+    /// a [`ModalSubmitInteraction`] can be responded to with
+    /// [`Self::UpdateMessage`].
+    #[must_use]
+    pub fn is_valid_for(self, interaction_kind: InteractionType) -> bool {
+        use InteractionResponseType::*;
+
+        match interaction_kind {
+            InteractionType::Ping => self == Pong,
+            InteractionType::ApplicationCommand => matches!(
+                self,
+                ChannelMessageWithSource
+                    | DeferredChannelMessageWithSource
+                    | Modal
+                    | PremiumRequired
+            ),
+            InteractionType::MessageComponent => matches!(
+                self,
+                ChannelMessageWithSource
+                    | DeferredChannelMessageWithSource
+                    | DeferredUpdateMessage
+                    | UpdateMessage
+                    | Modal
+                    | PremiumRequired
+            ),
+            InteractionType::Autocomplete => self == Autocomplete,
+            InteractionType::ModalSubmit => matches!(
+                self,
+                ChannelMessageWithSource
+                    | DeferredChannelMessageWithSource
+                    | DeferredUpdateMessage
+                    | UpdateMessage
+                    | PremiumRequired
+            ),
+            InteractionType::Unknown => true,
+        }
+    }
+}
+
+/// Checks that the type of an interaction response is valid for the type of
+/// the interaction it responds to.
+///
+/// Response types unknown to the library are not checked.
+#[cfg(feature = "model")]
+pub(crate) fn check_response_kind(map: &JsonMap, interaction_kind: InteractionType) -> Result<()> {
+    let response_kind =
+        map.get("type").and_then(Value::as_u64).and_then(InteractionResponseType::from_num);
+
+    match response_kind {
+        Some(response_kind) if !response_kind.is_valid_for(interaction_kind) => {
+            Err(Error::Model(ModelError::InvalidInteractionResponseType {
+                interaction_kind,
+                response_kind,
+            }))
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Whether the initial response to an interaction is ephemeral, shared by the
+/// clones of the interaction.
+///
+/// It is only known once the interaction was responded to with a message or
+/// a deferred one through the interaction itself.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ResponseEphemerality(Arc<AtomicU8>);
+
+impl ResponseEphemerality {
+    const PUBLIC: u8 = 1;
+    const EPHEMERAL: u8 = 2;
+
+    pub(crate) fn get(&self) -> Option<bool> {
+        match self.0.load(Ordering::Relaxed) {
+            Self::PUBLIC => Some(false),
+            Self::EPHEMERAL => Some(true),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn set(&self, ephemeral: bool) {
+        let state = if ephemeral { Self::EPHEMERAL } else { Self::PUBLIC };
+        self.0.store(state, Ordering::Relaxed);
+    }
+
+    /// Whether a response is ephemeral, if it is a message or a deferred
+    /// one, which are the responses that become the initial response.
+    #[cfg(feature = "model")]
+    pub(crate) fn of_response(map: &JsonMap) -> Option<bool> {
+        let kind =
+            map.get("type").and_then(Value::as_u64).and_then(InteractionResponseType::from_num);
+
+        match kind {
+            Some(InteractionResponseType::ChannelMessageWithSource)
+            | Some(InteractionResponseType::DeferredChannelMessageWithSource) => {
+                Some(is_ephemeral(map.get("data").and_then(|data| data.get("flags"))))
+            },
+            _ => None,
+        }
+    }
+
+    /// Checks that an edit of the initial response does not change whether it
+    /// is ephemeral, which Discord ignores.
+    ///
+    /// Unless `strict`, a warning is logged instead of returning an error.
+    #[cfg(feature = "model")]
+    pub(crate) fn check_edit(&self, map: &JsonMap, strict: bool) -> Result<()> {
+        let ephemeral = match (self.get(), map.get("flags")) {
+            (Some(ephemeral), Some(flags)) if is_ephemeral(Some(flags)) != ephemeral => ephemeral,
+            _ => return Ok(()),
+        };
+
+        if strict {
+            return Err(Error::Model(ModelError::EphemeralResponseChange(ephemeral)));
+        }
+
+        tracing::warn!(
+            "Editing the interaction response cannot change whether it is ephemeral, it stays {}",
+            if ephemeral { "ephemeral" } else { "public" },
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "model")]
+fn is_ephemeral(flags: Option<&Value>) -> bool {
+    let ephemeral = InteractionApplicationCommandCallbackDataFlags::EPHEMERAL.bits();
+
+    flags.and_then(Value::as_u64).map_or(false, |flags| flags & ephemeral != 0)
+}
+
+/// Checks that every interaction context of an application command is
+/// supported by one of its installation contexts.
+///
+/// Commands which do not set their installation contexts are not checked, as
+/// they default to the ones of the application.
+#[cfg(feature = "model")]
+pub(crate) fn check_command_contexts(map: &JsonMap) -> Result<()> {
+    let integration_types = match map.get("integration_types") {
+        Some(integration_types) => Vec::<InstallationContext>::deserialize(integration_types)?,
+        None => return Ok(()),
+    };
+
+    let contexts = match map.get("contexts") {
+        Some(contexts) => Vec::<InteractionContext>::deserialize(contexts)?,
+        None => Vec::new(),
+    };
+
+    for context in contexts {
+        if let Some(required) = context.required_installation() {
+            if !integration_types.contains(&required) {
+                return Err(Error::Model(ModelError::UnsupportedCommandContext {
+                    context,
+                    required,
+                }));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks an application command for mistakes which Discord would reject when
+/// registering it.
+///
+/// Refer to [`CreateApplicationCommand::build_check`] for what is checked.
+///
+/// [`CreateApplicationCommand::build_check`]: crate::builder::CreateApplicationCommand::build_check
+#[cfg(feature = "model")]
+pub(crate) fn check_command_options(map: &JsonMap) -> Result<()> {
+    violations_to_result(command_violations(|key| map.get(key), false))
+}
+
+/// Checks an edit of an application command like [`check_command_options`].
+///
+/// As an edit only holds the fields to change, a missing name is allowed.
+#[cfg(feature = "model")]
+pub(crate) fn check_command_edit(map: &JsonMap) -> Result<()> {
+    violations_to_result(command_violations(|key| map.get(key), true))
+}
+
+#[cfg(feature = "model")]
+fn violations_to_result(
+    violations: Vec<application_command::ApplicationCommandViolation>,
+) -> Result<()> {
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Model(ModelError::InvalidApplicationCommand(violations)))
+    }
+}
+
+/// Checks the components of a message, or of the data of an interaction
+/// response, for mistakes which Discord would reject when sending it.
+///
+/// Refer to [`CreateComponents::build_check`] for what is checked.
+///
+/// [`CreateComponents::build_check`]: crate::builder::CreateComponents::build_check
+#[cfg(feature = "model")]
+pub(crate) fn check_components(map: &JsonMap) -> Result<()> {
+    let message = map.get("data").and_then(Value::as_object).unwrap_or(map);
+    let rows = match message.get("components") {
+        Some(Value::Array(rows)) => rows,
+        _ => return Ok(()),
+    };
+
+    let violations = component_violations(rows);
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Model(ModelError::InvalidComponents(violations)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{InteractionResponseType, InteractionType};
+
+    #[test]
+    fn test_response_kind_validity() {
+        use InteractionResponseType::*;
+
+        assert!(Modal.is_valid_for(InteractionType::ApplicationCommand));
+        assert!(!UpdateMessage.is_valid_for(InteractionType::ApplicationCommand));
+        assert!(!DeferredUpdateMessage.is_valid_for(InteractionType::ApplicationCommand));
+
+        assert!(UpdateMessage.is_valid_for(InteractionType::MessageComponent));
+        assert!(Modal.is_valid_for(InteractionType::MessageComponent));
+
+        assert!(UpdateMessage.is_valid_for(InteractionType::ModalSubmit));
+        assert!(!Modal.is_valid_for(InteractionType::ModalSubmit));
+
+        assert!(Autocomplete.is_valid_for(InteractionType::Autocomplete));
+        assert!(!ChannelMessageWithSource.is_valid_for(InteractionType::Autocomplete));
+        assert!(!PremiumRequired.is_valid_for(InteractionType::Ping));
+    }
+
+    #[test]
+    #[cfg(feature = "model")]
+    fn test_check_command_edit() {
+        use serde_json::json;
+
+        use super::application_command::ApplicationCommandViolation;
+        use super::{check_command_edit, check_command_options};
+        use crate::internal::prelude::*;
+        use crate::model::ModelError;
+
+        let map = |value: Value| match value {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        };
+
+        // An edit of the description alone leaves the name as it is.
+        let description = map(json!({"description": "Pings the bot"}));
+        assert!(check_command_edit(&description).is_ok());
+        assert!(check_command_options(&description).is_err());
+
+        // Names and options which are sent are still checked.
+        let invalid_name = map(json!({"name": "Ping"}));
+        match check_command_edit(&invalid_name) {
+            Err(Error::Model(ModelError::InvalidApplicationCommand(violations))) => {
+                assert_eq!(violations, vec![ApplicationCommandViolation::InvalidName(
+                    "Ping".to_string()
+                )]);
+            },
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let invalid_option = map(json!({"options": [{"type": 3, "name": "Text"}]}));
+        assert!(check_command_edit(&invalid_option).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "model")]
+    fn test_check_response_kind() {
+        use serde_json::json;
+
+        use super::check_response_kind;
+        use crate::internal::prelude::*;
+        use crate::model::ModelError;
+
+        let map = |value: Value| match value {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        };
+
+        assert!(check_response_kind(&map(json!({"type": 4})), InteractionType::ModalSubmit).is_ok());
+
+        match check_response_kind(&map(json!({"type": 9})), InteractionType::ModalSubmit) {
+            Err(Error::Model(ModelError::InvalidInteractionResponseType {
+                interaction_kind: InteractionType::ModalSubmit,
+                response_kind: InteractionResponseType::Modal,
+            })) => {},
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // Response types unknown to the library are not checked.
+        assert!(
+            check_response_kind(&map(json!({"type": 42})), InteractionType::ModalSubmit).is_ok()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "model")]
+    fn test_response_ephemerality() {
+        use super::{InteractionApplicationCommandCallbackDataFlags, ResponseEphemerality};
+        use crate::builder::{CreateInteractionResponse, EditInteractionResponse};
+        use crate::internal::prelude::*;
+        use crate::model::ModelError;
+        use crate::utils::hashmap_to_json_map;
+
+        let mut defer = CreateInteractionResponse::default();
+        defer.kind(InteractionResponseType::DeferredChannelMessageWithSource);
+        let mut ephemeral_message = CreateInteractionResponse::default();
+        ephemeral_message.interaction_response_data(|d| d.content("Hi").ephemeral(true));
+        let mut update = CreateInteractionResponse::default();
+        update.kind(InteractionResponseType::UpdateMessage);
+
+        let of_response = |response: CreateInteractionResponse| {
+            ResponseEphemerality::of_response(&hashmap_to_json_map(response.0))
+        };
+        assert_eq!(of_response(defer), Some(false));
+        assert_eq!(of_response(ephemeral_message), Some(true));
+        assert_eq!(of_response(update), None);
+
+        let mut content_edit = EditInteractionResponse::default();
+        content_edit.content("Edited");
+        let content_edit = hashmap_to_json_map(content_edit.0);
+        let mut ephemeral_edit = EditInteractionResponse::default();
+        ephemeral_edit.ephemeral(true);
+        let ephemeral_edit = hashmap_to_json_map(ephemeral_edit.0);
+        let mut flags_edit = EditInteractionResponse::default();
+        flags_edit.flags(InteractionApplicationCommandCallbackDataFlags::SUPPRESS_EMBEDS);
+        let flags_edit = hashmap_to_json_map(flags_edit.0);
+
+        // Nothing is checked before the interaction was responded to.
+        let state = ResponseEphemerality::default();
+        assert_eq!(state.get(), None);
+        assert!(state.check_edit(&ephemeral_edit, true).is_ok());
+
+        // The state is shared by the clones of the interaction, such as after
+        // deferring through one of them.
+        let clone = state.clone();
+        state.set(false);
+        assert_eq!(clone.get(), Some(false));
+
+        assert!(clone.check_edit(&content_edit, true).is_ok());
+        assert!(clone.check_edit(&flags_edit, true).is_ok());
+        assert!(clone.check_edit(&ephemeral_edit, false).is_ok());
+        match clone.check_edit(&ephemeral_edit, true) {
+            Err(Error::Model(ModelError::EphemeralResponseChange(false))) => {},
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // Flags replace the ephemeral flag of an ephemeral response.
+        state.set(true);
+        assert!(state.check_edit(&content_edit, true).is_ok());
+        assert!(state.check_edit(&ephemeral_edit, true).is_ok());
+        match state.check_edit(&flags_edit, true) {
+            Err(Error::Model(ModelError::EphemeralResponseChange(true))) => {},
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_locales() {
+        use serde_json::json;
+
+        use super::Interaction;
+        use crate::model::misc::Locale;
+
+        let interaction = |locale: &str, guild_locale: &str| {
+            let value = json!({
+                "id": "846962698468081684",
+                "application_id": "846961981552525312",
+                "type": 2,
+                "data": {
+                    "id": "846962548127842314",
+                    "name": "ping",
+                    "type": 1,
+                },
+                "guild_id": "381880193251409931",
+                "channel_id": "381880193700069377",
+                "user": {
+                    "id": "210104891282489345",
+                    "username": "fake",
+                    "discriminator": "0001",
+                    "avatar": null,
+                },
+                "token": "token",
+                "version": 1,
+                "locale": locale,
+                "guild_locale": guild_locale,
+            });
+
+            serde_json::from_value::<Interaction>(value).unwrap()
+        };
+
+        let known = interaction("pt-BR", "de");
+        assert_eq!(known.locale(), Some(&Locale::PortugueseBr));
+        assert_eq!(known.guild_locale(), Some(&Locale::German));
+        let command = known.as_application_command().unwrap();
+        assert_eq!(command.preferred_locale(), &Locale::PortugueseBr);
+
+        let unknown = interaction("xx-YY", "de");
+        assert_eq!(unknown.locale(), Some(&Locale::Other("xx-YY".to_string())));
+        assert_eq!(unknown.as_application_command().unwrap().preferred_locale(), &Locale::German);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_app_permissions_and_context() {
+        use serde_json::json;
+
+        use super::{
+            AuthorizingIntegrationOwners,
+            InstallationContext,
+            Interaction,
+            InteractionContext,
+        };
+        use crate::model::id::{GuildId, UserId};
+        use crate::model::permissions::Permissions;
+
+        let mut value = json!({
+            "id": "846962698468081684",
+            "application_id": "846961981552525312",
+            "type": 3,
+            "data": {
+                "custom_id": "button",
+                "component_type": 2,
+            },
+            "channel_id": "381880193700069377",
+            "user": {
+                "id": "210104891282489345",
+                "username": "fake",
+                "discriminator": "0001",
+                "avatar": null,
+            },
+            "message": {
+                "id": "846962698468081685",
+                "channel_id": "381880193700069377",
+                "author": {
+                    "id": "846961981552525312",
+                    "username": "bot",
+                    "discriminator": "0002",
+                    "avatar": null,
+                },
+                "content": "",
+                "timestamp": "2021-05-25T00:00:00+00:00",
+                "edited_timestamp": null,
+                "tts": false,
+                "mention_everyone": false,
+                "mentions": [],
+                "mention_roles": [],
+                "attachments": [],
+                "embeds": [],
+                "pinned": false,
+                "type": 0,
+            },
+            "token": "token",
+            "version": 1,
+            "locale": "en-US",
+        });
+
+        let interaction = serde_json::from_value::<Interaction>(value.clone()).unwrap();
+        assert_eq!(interaction.app_permissions(), None);
+        assert!(!interaction.bot_can(Permissions::SEND_MESSAGES));
+        assert_eq!(interaction.context(), None);
+        assert!(interaction.authorizing_integration_owners().unwrap().is_empty());
+
+        value["app_permissions"] =
+            json!((Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS).bits().to_string());
+        value["context"] = json!(2);
+        value["authorizing_integration_owners"] = json!({"1": "210104891282489345"});
+
+        let interaction = serde_json::from_value::<Interaction>(value).unwrap();
+        assert!(interaction.bot_can(Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS));
+        assert!(!interaction.bot_can(Permissions::ATTACH_FILES));
+        assert_eq!(interaction.context(), Some(InteractionContext::PrivateChannel));
+
+        let value = serde_json::to_value(&interaction).unwrap();
+        let round_trip = serde_json::from_value::<Interaction>(value).unwrap();
+        assert!(round_trip.as_message_component().is_some());
+        assert_eq!(round_trip.context(), interaction.context());
+
+        let owners = interaction.authorizing_integration_owners().unwrap();
+        assert_eq!(owners.user_id, Some(UserId(210104891282489345)));
+        assert!(owners.contains(InstallationContext::User));
+        assert!(!owners.contains(InstallationContext::Guild));
+
+        let owners = AuthorizingIntegrationOwners {
+            guild_id: Some(GuildId(0)),
+            user_id: Some(UserId(210104891282489345)),
+        };
+        let value = serde_json::to_value(&owners).unwrap();
+        assert_eq!(value, json!({"0": "0", "1": "210104891282489345"}));
+        assert_eq!(serde_json::from_value::<AuthorizingIntegrationOwners>(value).unwrap(), owners);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_converters_round_trip() {
+        use serde_json::json;
+
+        use super::Interaction;
+        use crate::model::id::{ApplicationId, InteractionId};
+
+        let ping = serde_json::from_value::<Interaction>(json!({
+            "id": "846962698468081684",
+            "application_id": "846961981552525312",
+            "type": 1,
+            "token": "token",
+            "version": 1,
+        }))
+        .unwrap();
+        assert_eq!(ping.kind(), InteractionType::Ping);
+        assert_eq!(ping.id(), InteractionId(846962698468081684));
+        assert_eq!(ping.application_id(), ApplicationId(846961981552525312));
+        assert_eq!(ping.token(), "token");
+        assert!(ping.as_ping().is_some());
+        assert!(ping.as_application_command().is_none());
+
+        let command = serde_json::from_value::<Interaction>(json!({
+            "id": "846962698468081684",
+            "application_id": "846961981552525312",
+            "type": 2,
+            "data": {
+                "id": "846962548127842314",
+                "name": "ping",
+                "type": 1,
+            },
+            "channel_id": "381880193700069377",
+            "user": {
+                "id": "210104891282489345",
+                "username": "fake",
+                "discriminator": "0001",
+                "avatar": null,
+            },
+            "token": "token",
+            "version": 1,
+            "locale": "en-US",
+        }))
+        .unwrap();
+        assert_eq!(command.kind(), InteractionType::ApplicationCommand);
+        assert!(command.clone().into_message_component().is_none());
+        assert!(command.clone().into_modal_submit().is_none());
+        assert!(command.clone().into_autocomplete().is_none());
+        assert_eq!(command.clone().into_application_command().unwrap().data.name, "ping");
+
+        for interaction in vec![ping, command] {
+            let value = serde_json::to_value(&interaction).unwrap();
+            assert_eq!(value["type"], json!(interaction.kind() as u8));
+
+            let parsed = serde_json::from_value::<Interaction>(value).unwrap();
+            assert_eq!(parsed.kind(), interaction.kind());
+            assert_eq!(parsed.id(), interaction.id());
+            assert_eq!(parsed.token(), interaction.token());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "model")]
+    fn test_check_command_contexts() {
+        use serde_json::json;
+
+        use super::{check_command_contexts, InstallationContext, InteractionContext};
+        use crate::internal::prelude::*;
+        use crate::model::ModelError;
+
+        let map = |value: Value| match value {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        };
+
+        assert!(check_command_contexts(&map(json!({"contexts": [2]}))).is_ok());
+        assert!(check_command_contexts(&map(json!({"integration_types": [0]}))).is_ok());
+        assert!(check_command_contexts(&map(json!({
+            "integration_types": [0, 1],
+            "contexts": [0, 1, 2],
+        })))
+        .is_ok());
+
+        match check_command_contexts(&map(json!({
+            "integration_types": [0],
+            "contexts": [0, 2],
+        }))) {
+            Err(Error::Model(ModelError::UnsupportedCommandContext {
+                context: InteractionContext::PrivateChannel,
+                required: InstallationContext::User,
+            })) => {},
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "model")]
+    #[allow(clippy::unwrap_used)]
+    async fn test_author_member() {
+        use serde_json::json;
+
+        use super::Interaction;
+        use crate::http::Http;
+        use crate::internal::prelude::*;
+        use crate::model::id::{GuildId, RoleId, UserId};
+        use crate::model::ModelError;
+
+        let user = json!({
+            "id": "210104891282489345",
+            "username": "fake",
+            "discriminator": "0001",
+            "avatar": null,
+        });
+        let mut value = json!({
+            "id": "846962698468081684",
+            "application_id": "846961981552525312",
+            "type": 2,
+            "data": {
+                "id": "846962548127842314",
+                "name": "ping",
+                "type": 1,
+            },
+            "channel_id": "381880193700069377",
+            "user": user,
+            "token": "token",
+            "version": 1,
+            "locale": "en-US",
+        });
+
+        let http = Http::default();
+
+        // Interactions in DMs have no member.
+        let interaction = serde_json::from_value::<Interaction>(value.clone()).unwrap();
+        match interaction.author_member(&http).await {
+            Err(Error::Model(ModelError::ItemMissing)) => {},
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        value["guild_id"] = json!("381880193251409931");
+        value["member"] = json!({
+            "user": user,
+            "roles": ["381880193251409932"],
+            "joined_at": "2021-01-01T00:00:00+00:00",
+            "deaf": false,
+            "mute": false,
+            "permissions": "2048",
+        });
+
+        // The member sent with the interaction is used without a request.
+        let interaction = serde_json::from_value::<Interaction>(value).unwrap();
+        let member = interaction.author_member(&http).await.unwrap();
+        assert_eq!(member.guild_id, GuildId(381880193251409931));
+        assert_eq!(member.user.id, UserId(210104891282489345));
+        assert_eq!(member.roles, [RoleId(381880193251409932)]);
+    }
+
+    #[cfg(feature = "model")]
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_check_components() {
+        use serde_json::json;
+
+        use super::check_components;
+        use crate::error::Error;
+        use crate::model::interactions::message_component::ComponentViolation;
+        use crate::model::ModelError;
+
+        let row = json!({"type": 1, "components": []});
+        let message = json!({"components": [row]});
+        let response = json!({"type": 4, "data": message});
+
+        for map in [message, response].iter().filter_map(|v| v.as_object()) {
+            assert!(matches!(
+                check_components(map),
+                Err(Error::Model(ModelError::InvalidComponents(violations)))
+                    if violations == vec![ComponentViolation::EmptyRow { row: 0 }]
+            ));
+        }
+
+        let deferred = json!({"type": 5});
+        assert!(check_components(deferred.as_object().unwrap()).is_ok());
+    }
 }
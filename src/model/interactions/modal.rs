@@ -11,7 +11,15 @@ use crate::builder::{
 };
 #[cfg(feature = "model")]
 use crate::http::Http;
-use crate::model::interactions::InteractionType;
+#[cfg(feature = "model")]
+use crate::model::interactions::{check_components, check_response_kind};
+#[cfg(feature = "model")]
+use crate::model::interactions::followup::FollowupMessage;
+use crate::model::interactions::{
+    remove_invocation_fields,
+    InteractionType,
+    ResponseEphemerality,
+};
 #[cfg(feature = "model")]
 use crate::utils;
 
@@ -50,9 +58,34 @@ pub struct ModalSubmitInteraction {
     /// Always `1`.
     pub version: u8,
     /// The guild's preferred locale.
-    pub guild_locale: Option<String>,
+    pub guild_locale: Option<Locale>,
     /// The selected language of the invoking user.
-    pub locale: String,
+    pub locale: Locale,
+    /// The entitlements of the invoking user and guild, for monetized applications.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entitlements: Vec<Entitlement>,
+    /// The permissions of the bot in the channel the interaction was invoked
+    /// from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_permissions: Option<Permissions>,
+    /// The context the interaction was invoked from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<InteractionContext>,
+    /// The installations of the application which authorized the interaction.
+    #[serde(skip_serializing_if = "AuthorizingIntegrationOwners::is_empty")]
+    pub authorizing_integration_owners: AuthorizingIntegrationOwners,
+    /// Whether [`Self::edit_original_interaction_response`] returns a
+    /// [`ModelError::EphemeralResponseChange`] instead of logging a warning
+    /// when an edit would change whether the initial response is ephemeral,
+    /// which Discord ignores.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`ModelError::EphemeralResponseChange`]: crate::model::ModelError::EphemeralResponseChange
+    #[serde(skip)]
+    pub strict_ephemerality: bool,
+    #[serde(skip)]
+    response_ephemerality: ResponseEphemerality,
 }
 
 #[cfg(feature = "model")]
@@ -65,7 +98,9 @@ impl ModalSubmitInteraction {
     ///
     /// [`Error::Http`]: crate::error::Error::Http
     pub async fn get_interaction_response(&self, http: impl AsRef<Http>) -> Result<Message> {
-        http.as_ref().get_original_interaction_response(&self.token).await
+        http.as_ref()
+            .get_original_interaction_response_for(self.application_id.0, &self.token)
+            .await
     }
 
     /// Creates a response to the interaction received.
@@ -74,7 +109,10 @@ impl ModalSubmitInteraction {
     ///
     /// # Errors
     ///
-    /// Returns an [`Error::Model`] if the message content is too long.
+    /// Returns an [`Error::Model`] if the message content is too long, or if
+    /// the response type is not valid for this interaction; refer to
+    /// [`InteractionResponseType::is_valid_for`]. The check can be skipped by
+    /// using [`Http::create_interaction_response`] directly.
     /// May also return an [`Error::Http`] if the API returns an error,
     /// or an [`Error::Json`] if there is an error in deserializing the
     /// API response.
@@ -83,6 +121,7 @@ impl ModalSubmitInteraction {
     ///
     /// [`Error::Model`]: crate::error::Error::Model
     /// [`Error::Http`]: crate::error::Error::Http
+    /// [`InteractionResponseType::is_valid_for`]: crate::model::interactions::InteractionResponseType::is_valid_for
     /// [`Error::Json`]: crate::error::Error::Json
     pub async fn create_interaction_response<F>(&self, http: impl AsRef<Http>, f: F) -> Result<()>
     where
@@ -93,10 +132,37 @@ impl ModalSubmitInteraction {
 
         let map = utils::hashmap_to_json_map(interaction_response.0);
 
+        check_response_kind(&map, InteractionType::ModalSubmit)?;
         Message::check_content_length(&map)?;
         Message::check_embed_length(&map)?;
+        check_components(&map)?;
+
+        let ephemeral = ResponseEphemerality::of_response(&map);
 
-        http.as_ref().create_interaction_response(self.id.0, &self.token, &Value::Object(map)).await
+        http.as_ref()
+            .create_interaction_response_with_files(
+                self.id.0,
+                &self.token,
+                interaction_response.1,
+                map,
+            )
+            .await?;
+
+        if let Some(ephemeral) = ephemeral {
+            self.response_ephemerality.set(ephemeral);
+        }
+
+        Ok(())
+    }
+
+    /// Whether the initial response to the interaction is ephemeral.
+    ///
+    /// This is only known once the interaction was responded to with a
+    /// message, or a deferred one, by [`Self::create_interaction_response`] of
+    /// this interaction or one of its clones.
+    #[must_use]
+    pub fn is_response_ephemeral(&self) -> Option<bool> {
+        self.response_ephemerality.get()
     }
 
     /// Edits the initial interaction response.
@@ -111,20 +177,23 @@ impl ModalSubmitInteraction {
     ///
     /// # Errors
     ///
-    /// Returns [`Error::Model`] if the edited content is too long.
+    /// Returns [`Error::Model`] if the edited content is too long, or if the
+    /// edit would change whether the response is ephemeral while
+    /// [`Self::strict_ephemerality`] is set.
     /// May also return [`Error::Http`] if the API returns an error,
     /// or an [`Error::Json`] if there is an error deserializing the response.
     ///
     /// [`Error::Model`]: crate::error::Error::Model
     /// [`Error::Http`]: crate::error::Error::Http
     /// [`Error::Json`]: crate::error::Error::Json
-    pub async fn edit_original_interaction_response<F>(
+    pub async fn edit_original_interaction_response<'a, F>(
         &self,
         http: impl AsRef<Http>,
         f: F,
     ) -> Result<Message>
     where
-        F: FnOnce(&mut EditInteractionResponse) -> &mut EditInteractionResponse,
+        for<'b> F:
+            FnOnce(&'b mut EditInteractionResponse<'a>) -> &'b mut EditInteractionResponse<'a>,
     {
         let mut interaction_response = EditInteractionResponse::default();
         f(&mut interaction_response);
@@ -133,8 +202,17 @@ impl ModalSubmitInteraction {
 
         Message::check_content_length(&map)?;
         Message::check_embed_length(&map)?;
+        check_components(&map)?;
+        self.response_ephemerality.check_edit(&map, self.strict_ephemerality)?;
 
-        http.as_ref().edit_original_interaction_response(&self.token, &Value::Object(map)).await
+        http.as_ref()
+            .edit_original_interaction_response_with_files_for(
+                self.application_id.0,
+                &self.token,
+                interaction_response.1,
+                map,
+            )
+            .await
     }
 
     /// Deletes the initial interaction response.
@@ -144,11 +222,16 @@ impl ModalSubmitInteraction {
     /// May return [`Error::Http`] if the API returns an error.
     /// Such as if the response was already deleted.
     pub async fn delete_original_interaction_response(&self, http: impl AsRef<Http>) -> Result<()> {
-        http.as_ref().delete_original_interaction_response(&self.token).await
+        http.as_ref()
+            .delete_original_interaction_response_for(self.application_id.0, &self.token)
+            .await
     }
 
     /// Creates a followup response to the response sent.
     ///
+    /// The message is returned along with the interaction's token, so that it
+    /// can still be edited or deleted through the returned [`FollowupMessage`].
+    ///
     /// **Note**: Message contents must be under 2000 unicode code points.
     ///
     /// # Errors
@@ -164,7 +247,7 @@ impl ModalSubmitInteraction {
         &self,
         http: impl AsRef<Http>,
         f: F,
-    ) -> Result<Message>
+    ) -> Result<FollowupMessage>
     where
         for<'b> F: FnOnce(
             &'b mut CreateInteractionResponseFollowup<'a>,
@@ -177,8 +260,14 @@ impl ModalSubmitInteraction {
 
         Message::check_content_length(&map)?;
         Message::check_embed_length(&map)?;
+        check_components(&map)?;
+
+        let message = http
+            .as_ref()
+            .create_followup_message_for(self.application_id.0, &self.token, &Value::Object(map))
+            .await?;
 
-        http.as_ref().create_followup_message(&self.token, &Value::Object(map)).await
+        Ok(FollowupMessage::new(message, self.application_id, self.token.clone()))
     }
 
     /// Edits a followup response to the response sent.
@@ -212,9 +301,16 @@ impl ModalSubmitInteraction {
 
         Message::check_content_length(&map)?;
         Message::check_embed_length(&map)?;
+        check_components(&map)?;
 
         http.as_ref()
-            .edit_followup_message(&self.token, message_id.into().into(), &Value::Object(map))
+            .edit_followup_message_with_files_for(
+                self.application_id.0,
+                &self.token,
+                message_id.into().into(),
+                interaction_response.1,
+                map,
+            )
             .await
     }
 
@@ -229,7 +325,13 @@ impl ModalSubmitInteraction {
         http: impl AsRef<Http>,
         message_id: M,
     ) -> Result<()> {
-        http.as_ref().delete_followup_message(&self.token, message_id.into().into()).await
+        http.as_ref()
+            .delete_followup_message_for(
+                self.application_id.0,
+                &self.token,
+                message_id.into().into(),
+            )
+            .await
     }
     /// Helper function to defer an interaction
     ///
@@ -322,12 +424,11 @@ impl<'de> Deserialize<'de> for ModalSubmitInteraction {
             .map_err(DeError::custom)?;
 
         let guild_id = match map.contains_key("guild_id") {
-            true => Some(
-                map.remove("guild_id")
-                    .ok_or_else(|| DeError::custom("expected guild_id"))
-                    .and_then(GuildId::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
+            true => map
+                .remove("guild_id")
+                .ok_or_else(|| DeError::custom("expected guild_id"))
+                .and_then(Option::<GuildId>::deserialize)
+                .map_err(DeError::custom)?,
             false => None,
         };
 
@@ -338,12 +439,11 @@ impl<'de> Deserialize<'de> for ModalSubmitInteraction {
             .map_err(DeError::custom)?;
 
         let member = match map.contains_key("member") {
-            true => Some(
-                map.remove("member")
-                    .ok_or_else(|| DeError::custom("expected member"))
-                    .and_then(Member::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
+            true => map
+                .remove("member")
+                .ok_or_else(|| DeError::custom("expected member"))
+                .and_then(Option::<Member>::deserialize)
+                .map_err(DeError::custom)?,
             false => None,
         };
 
@@ -357,12 +457,11 @@ impl<'de> Deserialize<'de> for ModalSubmitInteraction {
         };
 
         let message = match map.contains_key("message") {
-            true => Some(
-                map.remove("message")
-                    .ok_or_else(|| DeError::custom("expected message"))
-                    .and_then(Message::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
+            true => map
+                .remove("message")
+                .ok_or_else(|| DeError::custom("expected message"))
+                .and_then(Option::<Message>::deserialize)
+                .map_err(DeError::custom)?,
             false => None,
         };
 
@@ -379,21 +478,32 @@ impl<'de> Deserialize<'de> for ModalSubmitInteraction {
             .map_err(DeError::custom)?;
 
         let guild_locale = match map.contains_key("guild_locale") {
-            true => Some(
-                map.remove("guild_locale")
-                    .ok_or_else(|| DeError::custom("expected guild_locale"))
-                    .and_then(String::deserialize)
-                    .map_err(DeError::custom)?,
-            ),
+            true => map
+                .remove("guild_locale")
+                .ok_or_else(|| DeError::custom("expected guild_locale"))
+                .and_then(Option::<Locale>::deserialize)
+                .map_err(DeError::custom)?,
             false => None,
         };
 
         let locale = map
             .remove("locale")
             .ok_or_else(|| DeError::custom("expected locale"))
-            .and_then(String::deserialize)
+            .and_then(Locale::deserialize)
             .map_err(DeError::custom)?;
 
+        let entitlements = match map.contains_key("entitlements") {
+            true => map
+                .remove("entitlements")
+                .ok_or_else(|| DeError::custom("expected entitlements"))
+                .and_then(Vec::<Entitlement>::deserialize)
+                .map_err(DeError::custom)?,
+            false => Vec::new(),
+        };
+
+        let (app_permissions, context, authorizing_integration_owners) =
+            remove_invocation_fields(&mut map)?;
+
         Ok(Self {
             id,
             application_id,
@@ -408,6 +518,12 @@ impl<'de> Deserialize<'de> for ModalSubmitInteraction {
             version,
             guild_locale,
             locale,
+            entitlements,
+            app_permissions,
+            context,
+            authorizing_integration_owners,
+            strict_ephemerality: false,
+            response_ephemerality: ResponseEphemerality::default(),
         })
     }
 }
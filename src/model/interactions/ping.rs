@@ -1,5 +1,6 @@
 use crate::model::id::{ApplicationId, InteractionId};
 use crate::model::interactions::InteractionType;
+use crate::model::misc::Locale;
 
 /// A ping interaction, which can only be received through an endpoint url.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -17,5 +18,5 @@ pub struct PingInteraction {
     /// Always `1`.
     pub version: u8,
     /// The guild's preferred locale.
-    pub guild_locale: Option<String>,
+    pub guild_locale: Option<Locale>,
 }
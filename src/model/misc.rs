@@ -3,11 +3,13 @@
 #[cfg(all(feature = "model", feature = "utils"))]
 use std::error::Error as StdError;
 use std::fmt;
-#[cfg(all(feature = "model", feature = "utils"))]
 use std::result::Result as StdResult;
 #[cfg(all(feature = "model", feature = "utils"))]
 use std::str::FromStr;
 
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
 use super::prelude::*;
 #[cfg(all(feature = "model", any(feature = "cache", feature = "utils")))]
 use crate::utils;
@@ -371,10 +373,197 @@ pub struct Maintenance {
     pub stop: String,
 }
 
+macro_rules! locales {
+    ($($(#[$attr:meta])* $variant:ident = $code:literal,)*) => {
+        /// A language or regional variant supported by the Discord client, such
+        /// as the [`locale`] of the user invoking an interaction.
+        ///
+        /// Locale codes that are not known to serenity yet are kept as
+        /// [`Locale::Other`].
+        ///
+        /// [Discord docs](https://discord.com/developers/docs/reference#locales).
+        ///
+        /// [`locale`]: crate::model::interactions::application_command::ApplicationCommandInteraction::locale
+        #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+        #[non_exhaustive]
+        pub enum Locale {
+            $($(#[$attr])* $variant,)*
+            /// A locale code not known to serenity.
+            Other(String),
+        }
+
+        impl Locale {
+            /// All locales known to serenity.
+            pub const ALL: &'static [Locale] = &[$(Locale::$variant),*];
+
+            /// Returns the locale code, such as `"en-US"`.
+            ///
+            /// This is also the key used for the locale in localization maps,
+            /// such as when registering a command with
+            /// [`CreateApplicationCommand::name_localized`].
+            ///
+            /// [`CreateApplicationCommand::name_localized`]: crate::builder::CreateApplicationCommand::name_localized
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Locale::$variant => $code,)*
+                    Locale::Other(code) => code,
+                }
+            }
+        }
+
+        impl<'a> From<&'a str> for Locale {
+            fn from(code: &'a str) -> Self {
+                match code {
+                    $($code => Locale::$variant,)*
+                    _ => Locale::Other(code.to_string()),
+                }
+            }
+        }
+    };
+}
+
+locales! {
+    /// Indonesian.
+    Indonesian = "id",
+    /// Danish.
+    Danish = "da",
+    /// German.
+    German = "de",
+    /// English, as used in the United Kingdom.
+    EnglishGb = "en-GB",
+    /// English, as used in the United States.
+    EnglishUs = "en-US",
+    /// Spanish, as used in Spain.
+    SpanishEs = "es-ES",
+    /// Spanish, as used in Latin America.
+    SpanishLatam = "es-419",
+    /// French.
+    French = "fr",
+    /// Croatian.
+    Croatian = "hr",
+    /// Italian.
+    Italian = "it",
+    /// Lithuanian.
+    Lithuanian = "lt",
+    /// Hungarian.
+    Hungarian = "hu",
+    /// Dutch.
+    Dutch = "nl",
+    /// Norwegian.
+    Norwegian = "no",
+    /// Polish.
+    Polish = "pl",
+    /// Portuguese, as used in Brazil.
+    PortugueseBr = "pt-BR",
+    /// Romanian.
+    Romanian = "ro",
+    /// Finnish.
+    Finnish = "fi",
+    /// Swedish.
+    Swedish = "sv-SE",
+    /// Vietnamese.
+    Vietnamese = "vi",
+    /// Turkish.
+    Turkish = "tr",
+    /// Czech.
+    Czech = "cs",
+    /// Greek.
+    Greek = "el",
+    /// Bulgarian.
+    Bulgarian = "bg",
+    /// Russian.
+    Russian = "ru",
+    /// Ukrainian.
+    Ukrainian = "uk",
+    /// Hindi.
+    Hindi = "hi",
+    /// Thai.
+    Thai = "th",
+    /// Chinese, as used in China.
+    ChineseCn = "zh-CN",
+    /// Japanese.
+    Japanese = "ja",
+    /// Chinese, as used in Taiwan.
+    ChineseTw = "zh-TW",
+    /// Korean.
+    Korean = "ko",
+}
+
+impl Locale {
+    /// Returns the language part of the locale code, without the region,
+    /// such as `"en"` for [`Locale::EnglishUs`].
+    #[must_use]
+    pub fn language_code(&self) -> &str {
+        let code = self.as_str();
+        code.split('-').next().unwrap_or(code)
+    }
+
+    /// Whether the locale is known to serenity, i.e. is not a
+    /// [`Locale::Other`].
+    #[must_use]
+    pub fn is_known(&self) -> bool {
+        !matches!(self, Locale::Other(_))
+    }
+}
+
+impl From<String> for Locale {
+    fn from(code: String) -> Self {
+        match Locale::from(code.as_str()) {
+            Locale::Other(_) => Locale::Other(code),
+            locale => locale,
+        }
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Locale {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        String::deserialize(deserializer).map(Locale::from)
+    }
+}
+
+impl Serialize for Locale {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::model::prelude::*;
 
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_locale() {
+        for locale in Locale::ALL {
+            assert_eq!(&Locale::from(locale.as_str()), locale);
+            assert_eq!(&Locale::from(locale.to_string()), locale);
+            assert!(locale.is_known());
+        }
+
+        assert_eq!(Locale::EnglishUs.as_str(), "en-US");
+        assert_eq!(Locale::EnglishUs.language_code(), "en");
+        assert_eq!(Locale::SpanishLatam.language_code(), "es");
+        assert_eq!(Locale::French.language_code(), "fr");
+
+        let locale: Locale = serde_json::from_str("\"zh-TW\"").unwrap();
+        assert_eq!(locale, Locale::ChineseTw);
+        assert_eq!(serde_json::to_string(&locale).unwrap(), "\"zh-TW\"");
+
+        // Codes added by Discord later on must not fail to deserialize.
+        let locale: Locale = serde_json::from_str("\"tlh-KL\"").unwrap();
+        assert_eq!(locale, Locale::Other("tlh-KL".to_string()));
+        assert!(!locale.is_known());
+        assert_eq!(locale.language_code(), "tlh");
+        assert_eq!(serde_json::to_string(&locale).unwrap(), "\"tlh-KL\"");
+    }
+
     #[test]
     fn test_formatters() {
         assert_eq!(ChannelId(1).to_string(), "1");
@@ -414,6 +603,9 @@ mod test {
                 thread_metadata: None,
                 member: None,
                 default_auto_archive_duration: None,
+                flags: ChannelFlags::default(),
+                default_forum_layout: None,
+                default_sort_order: None,
             });
             let emoji = Emoji {
                 animated: false,
@@ -445,6 +637,7 @@ mod test {
                 bot: false,
                 discriminator: 4132,
                 name: "fake".to_string(),
+                global_name: None,
                 public_flags: None,
                 banner: None,
                 accent_colour: None,
@@ -34,6 +34,8 @@ pub mod id;
 pub mod interactions;
 pub mod invite;
 pub mod misc;
+#[cfg(feature = "unstable_discord_api")]
+pub mod monetization;
 pub mod oauth2;
 pub mod permissions;
 pub mod prelude;
@@ -0,0 +1,277 @@
+//! Models for monetized applications: SKUs and entitlements.
+
+use bitflags::__impl_bitflags;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::prelude::*;
+use crate::model::utils::U64Visitor;
+
+/// A premium offering that can be made available to users or guilds of an application.
+///
+/// [Discord docs](https://discord.com/developers/docs/monetization/skus#sku-object).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct Sku {
+    /// The Id of the SKU.
+    pub id: SkuId,
+    /// The type of the SKU.
+    #[serde(rename = "type")]
+    pub kind: SkuType,
+    /// The Id of the parent application.
+    pub application_id: ApplicationId,
+    /// The customer-facing name of the premium offering.
+    pub name: String,
+    /// A system-generated URL slug based on the SKU name.
+    pub slug: String,
+    /// Flags describing the SKU's availability.
+    pub flags: SkuFlags,
+}
+
+/// The type of a [`Sku`].
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum SkuType {
+    /// A durable one-time purchase.
+    Durable = 2,
+    /// A consumable one-time purchase.
+    Consumable = 3,
+    /// A recurring subscription.
+    Subscription = 5,
+    /// A system-generated group for each [`SkuType::Subscription`] SKU.
+    SubscriptionGroup = 6,
+    Unknown = !0,
+}
+
+enum_number!(SkuType {
+    Durable,
+    Consumable,
+    Subscription,
+    SubscriptionGroup
+});
+
+/// Describes the availability of a [`Sku`].
+#[derive(Copy, PartialEq, Eq, Clone, PartialOrd, Ord, Hash, Default)]
+pub struct SkuFlags {
+    pub bits: u64,
+}
+
+__impl_bitflags! {
+    SkuFlags: u64 {
+        /// The SKU is available for purchase.
+        AVAILABLE = 1 << 2;
+        /// A recurring SKU that can be purchased by a user and applied to a single guild.
+        GUILD_SUBSCRIPTION = 1 << 7;
+        /// A recurring SKU purchased by a user for themselves.
+        USER_SUBSCRIPTION = 1 << 8;
+    }
+}
+
+impl<'de> Deserialize<'de> for SkuFlags {
+    fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(SkuFlags::from_bits_truncate(deserializer.deserialize_u64(U64Visitor)?))
+    }
+}
+
+impl Serialize for SkuFlags {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(self.bits())
+    }
+}
+
+/// Represents that a user or guild has access to a premium offering of an application.
+///
+/// [Discord docs](https://discord.com/developers/docs/monetization/entitlements#entitlement-object).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct Entitlement {
+    /// The Id of the entitlement.
+    pub id: EntitlementId,
+    /// The Id of the SKU the entitlement grants access to.
+    pub sku_id: SkuId,
+    /// The Id of the parent application.
+    pub application_id: ApplicationId,
+    /// The Id of the user that is granted access to the SKU, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<UserId>,
+    /// The Id of the guild that is granted access to the SKU, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    /// The type of the entitlement.
+    #[serde(rename = "type")]
+    pub kind: EntitlementType,
+    /// Whether the entitlement was deleted.
+    #[serde(default)]
+    pub deleted: bool,
+    /// Start date at which the entitlement is valid.
+    ///
+    /// **Note**: Not present on test entitlements.
+    #[serde(default)]
+    pub starts_at: Option<DateTime<Utc>>,
+    /// Date at which the entitlement is no longer valid.
+    ///
+    /// **Note**: Not present on test entitlements.
+    #[serde(default)]
+    pub ends_at: Option<DateTime<Utc>>,
+    /// For consumable SKUs, whether or not the entitlement has been consumed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consumed: Option<bool>,
+}
+
+impl Entitlement {
+    /// Whether the entitlement is currently active, i.e. it has not been deleted and has not
+    /// ended yet.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        !self.deleted && self.ends_at.map_or(true, |ends_at| ends_at > Utc::now())
+    }
+}
+
+/// The type of an [`Entitlement`].
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum EntitlementType {
+    /// Entitlement was purchased by a user.
+    Purchase = 1,
+    /// Entitlement for a Discord Nitro subscription.
+    PremiumSubscription = 2,
+    /// Entitlement was gifted by a developer.
+    DeveloperGift = 3,
+    /// Entitlement was purchased by a developer in application test mode.
+    TestModePurchase = 4,
+    /// Entitlement was granted when the SKU was free.
+    FreePurchase = 5,
+    /// Entitlement was gifted by another user.
+    UserGift = 6,
+    /// Entitlement was claimed by a user for free as a Nitro subscriber.
+    PremiumPurchase = 7,
+    /// Entitlement was purchased as an application subscription.
+    ApplicationSubscription = 8,
+    Unknown = !0,
+}
+
+enum_number!(EntitlementType {
+    Purchase,
+    PremiumSubscription,
+    DeveloperGift,
+    TestModePurchase,
+    FreePurchase,
+    UserGift,
+    PremiumPurchase,
+    ApplicationSubscription
+});
+
+/// The owner of a test entitlement, used when creating one via
+/// [`Http::create_test_entitlement`].
+///
+/// [`Http::create_test_entitlement`]: crate::http::Http::create_test_entitlement
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum EntitlementOwner {
+    /// The entitlement is granted to a guild.
+    Guild(GuildId),
+    /// The entitlement is granted to a user.
+    User(UserId),
+}
+
+impl EntitlementOwner {
+    /// Returns the Id of the owner together with the `owner_type` value Discord expects.
+    #[must_use]
+    pub fn id_and_type(self) -> (u64, u8) {
+        match self {
+            Self::Guild(id) => (id.0, 1),
+            Self::User(id) => (id.0, 2),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn entitlement_fixture() {
+        let value = json!({
+            "id": "1019653849998299136",
+            "sku_id": "1019475255913222144",
+            "application_id": "1019370614521200640",
+            "user_id": "771129655544643584",
+            "promotion_id": null,
+            "type": 8,
+            "deleted": false,
+            "gift_code_flags": 0,
+            "consumed": false,
+            "starts_at": "2022-09-14T17:00:18.704163+00:00",
+            "ends_at": "2022-10-14T17:00:18.704163+00:00",
+            "guild_id": "1015034326372454400",
+            "subscription_id": "1019653835926409216"
+        });
+
+        let entitlement: Entitlement = serde_json::from_value(value).unwrap();
+        assert_eq!(entitlement.id, EntitlementId(1019653849998299136));
+        assert_eq!(entitlement.sku_id, SkuId(1019475255913222144));
+        assert_eq!(entitlement.user_id, Some(UserId(771129655544643584)));
+        assert_eq!(entitlement.guild_id, Some(GuildId(1015034326372454400)));
+        assert_eq!(entitlement.kind, EntitlementType::ApplicationSubscription);
+        assert_eq!(entitlement.consumed, Some(false));
+        assert!(!entitlement.deleted);
+        assert!(entitlement.ends_at.is_some());
+        assert!(!entitlement.is_active());
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_entitlement_fixture() {
+        let value = json!({
+            "id": "1019653849998299136",
+            "sku_id": "1019475255913222144",
+            "application_id": "1019370614521200640",
+            "user_id": "771129655544643584",
+            "type": 4,
+            "deleted": false
+        });
+
+        let entitlement: Entitlement = serde_json::from_value(value).unwrap();
+        assert_eq!(entitlement.kind, EntitlementType::TestModePurchase);
+        assert_eq!(entitlement.guild_id, None);
+        assert_eq!(entitlement.starts_at, None);
+        assert_eq!(entitlement.ends_at, None);
+        assert!(entitlement.is_active());
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn sku_fixture() {
+        let value = json!({
+            "id": "1088510058284990888",
+            "type": 5,
+            "dependent_sku_id": null,
+            "application_id": "788708323867885999",
+            "manifest_labels": null,
+            "access_type": 1,
+            "name": "Test Premium",
+            "features": [],
+            "release_date": null,
+            "premium": false,
+            "slug": "test-premium",
+            "flags": 128,
+            "show_age_gate": false
+        });
+
+        let sku: Sku = serde_json::from_value(value).unwrap();
+        assert_eq!(sku.kind, SkuType::Subscription);
+        assert_eq!(sku.flags, SkuFlags::GUILD_SUBSCRIPTION);
+        assert_eq!(sku.name, "Test Premium");
+    }
+}
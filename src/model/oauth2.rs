@@ -17,6 +17,8 @@ pub enum OAuth2Scope {
     Email,
     /// Allows `/users/@me/connections` to return linked third-party accounts.
     Connections,
+    /// Allows your app to update a user's connection and metadata for the app.
+    RoleConnectionsWrite,
     /// Allows `/users/@me/guilds` to return basic information about all of a user's guilds.
     Guilds,
     /// Allows `/guilds/{guild.id}/members/{user.id}` to be used for joining users to a guild.
@@ -61,6 +63,7 @@ impl fmt::Display for OAuth2Scope {
             Self::Identify => "identify",
             Self::Email => "email",
             Self::Connections => "connections",
+            Self::RoleConnectionsWrite => "role_connections.write",
             Self::Guilds => "guilds",
             Self::GuildsJoin => "guilds.join",
             Self::GdmJoin => "gdm.join",
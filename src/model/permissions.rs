@@ -361,6 +361,8 @@ __impl_bitflags! {
         /// Allows for timing out users to prevent them from sending or reacting to messages in
         /// chat and threads, and from speaking in voice and stage channels.
         MODERATE_MEMBERS = 1 << 40;
+        /// Allows for setting the status of a voice channel.
+        SET_VOICE_CHANNEL_STATUS = 1 << 48;
         /// Allows for creating and participating in public threads.
         #[deprecated(note = "This permission no longer exists")]
         USE_PUBLIC_THREADS = 0b0010_0000_0000_0000_0000_0000_0000_0000_0000;
@@ -21,6 +21,8 @@ pub use super::id::*;
 pub use super::interactions::*;
 pub use super::invite::*;
 pub use super::misc::*;
+#[cfg(feature = "unstable_discord_api")]
+pub use super::monetization::*;
 pub use super::oauth2::*;
 pub use super::permissions::*;
 pub use super::user::*;
@@ -32,7 +32,7 @@ use crate::http::GuildPagination;
 use crate::http::{CacheHttp, Http};
 #[cfg(feature = "model")]
 use crate::utils;
-use crate::{internal::prelude::*, model::misc::Mentionable};
+use crate::internal::prelude::*;
 
 /// Information about the current user.
 #[derive(Clone, Default, Debug, Deserialize, Serialize)]
@@ -506,6 +506,10 @@ pub struct User {
     /// change if the username+discriminator pair becomes non-unique.
     #[serde(rename = "username")]
     pub name: String,
+    /// The user's display name, if it is set. Unlike [`Self::name`], this is
+    /// not unique.
+    #[serde(default)]
+    pub global_name: Option<String>,
     /// The public flags on a user's account
     pub public_flags: Option<UserPublicFlags>,
     /// Optional banner hash.
@@ -600,6 +604,7 @@ impl Default for User {
             bot: true,
             discriminator: 1432,
             name: "test".to_string(),
+            global_name: None,
             public_flags: None,
             banner: None,
             accent_colour: None,
@@ -941,10 +946,10 @@ impl User {
         #[cfg(feature = "cache")]
         {
             if let Some(cache) = cache_http.cache() {
-                if let Some(guild) = guild_id.to_guild_cached(cache).await {
-                    if let Some(member) = guild.members.get(&self.id) {
-                        return member.nick.clone();
-                    }
+                let nick = cache.member_field(guild_id, self.id, |m| m.nick.clone()).await;
+
+                if let Some(nick) = nick {
+                    return nick;
                 }
             }
         }
@@ -1086,6 +1091,7 @@ impl From<CurrentUser> for User {
             discriminator: user.discriminator,
             id: user.id,
             name: user.name,
+            global_name: None,
             public_flags: user.public_flags,
             banner: user.banner,
             accent_colour: user.accent_colour,
@@ -1101,6 +1107,7 @@ impl<'a> From<&'a CurrentUser> for User {
             discriminator: user.discriminator,
             id: user.id,
             name: user.name.clone(),
+            global_name: None,
             public_flags: user.public_flags,
             banner: user.banner.clone(),
             accent_colour: user.accent_colour,
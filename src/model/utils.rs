@@ -1,7 +1,7 @@
 use std::{collections::HashMap, hash::Hash};
 
 use serde::de::Error as DeError;
-use serde::de::MapAccess;
+use serde::de::{IgnoredAny, MapAccess};
 use serde::ser::{Serialize, SerializeSeq, Serializer};
 
 #[cfg(all(feature = "cache", feature = "model"))]
@@ -18,6 +18,29 @@ pub fn default_true() -> bool {
     true
 }
 
+/// Deserializes a field whose mere presence signifies `true`, regardless of
+/// its value. Discord sends some flags this way, as a key with a `null` value.
+///
+/// This must be paired with `#[serde(default)]`, so that an absent field
+/// becomes `false`.
+pub fn deserialize_presence_as_bool<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> StdResult<bool, D::Error> {
+    IgnoredAny::deserialize(deserializer).map(|_| true)
+}
+
+/// Serializes a flag in the form expected by [`deserialize_presence_as_bool`].
+///
+/// This must be paired with `skip_serializing_if = "is_false"`, so that the
+/// field is only present when set.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+pub fn serialize_presence_as_bool<S: Serializer>(
+    _: &bool,
+    serializer: S,
+) -> StdResult<S::Ok, S::Error> {
+    serializer.serialize_none()
+}
+
 pub fn deserialize_emojis<'de, D: Deserializer<'de>>(
     deserializer: D,
 ) -> StdResult<HashMap<EmojiId, Emoji>, D::Error> {
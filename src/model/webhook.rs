@@ -7,15 +7,17 @@ use std::mem;
 #[cfg(feature = "model")]
 use super::channel::Message;
 use super::{
-    id::{ChannelId, GuildId, WebhookId},
+    id::{ApplicationId, ChannelId, GuildId, WebhookId},
     user::User,
 };
 #[cfg(feature = "model")]
-use crate::builder::{EditWebhookMessage, ExecuteWebhook};
+use crate::builder::{check_username, EditWebhookMessage, ExecuteWebhook};
 #[cfg(feature = "model")]
 use crate::http::Http;
 #[cfg(feature = "model")]
 use crate::internal::prelude::*;
+#[cfg(all(feature = "model", feature = "unstable_discord_api"))]
+use crate::model::interactions::check_components;
 #[cfg(feature = "model")]
 use crate::model::prelude::*;
 #[cfg(feature = "model")]
@@ -33,13 +35,17 @@ pub enum WebhookType {
     /// An indicator that the webhook is managed by Discord for posting new
     /// messages to channels without a token.
     ChannelFollower = 2,
+    /// An indicator that the webhook is used by an application, such as for
+    /// responding to interactions.
+    Application = 3,
     /// An indicator that the webhook is of unknown type.
     Unknown = !0,
 }
 
 enum_number!(WebhookType {
     Incoming,
-    ChannelFollower
+    ChannelFollower,
+    Application
 });
 
 impl WebhookType {
@@ -48,6 +54,7 @@ impl WebhookType {
         match self {
             WebhookType::Incoming => "incoming",
             WebhookType::ChannelFollower => "channel follower",
+            WebhookType::Application => "application",
             WebhookType::Unknown => "unknown",
         }
     }
@@ -84,6 +91,37 @@ pub struct Webhook {
     ///
     /// **Note**: This is not received when getting a webhook by its token.
     pub user: Option<User>,
+    /// The Id of the application that created the webhook, if it is a
+    /// [`WebhookType::Application`] webhook.
+    pub application_id: Option<ApplicationId>,
+    /// The guild of the channel that a [`WebhookType::ChannelFollower`]
+    /// webhook follows.
+    pub source_guild: Option<WebhookGuild>,
+    /// The channel that a [`WebhookType::ChannelFollower`] webhook follows.
+    pub source_channel: Option<WebhookChannel>,
+}
+
+/// The guild of the channel that a [`WebhookType::ChannelFollower`] webhook
+/// follows.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct WebhookGuild {
+    /// The Id of the guild.
+    pub id: GuildId,
+    /// The name of the guild.
+    pub name: String,
+    /// The hash of the guild's icon.
+    pub icon: Option<String>,
+}
+
+/// The channel that a [`WebhookType::ChannelFollower`] webhook follows.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct WebhookChannel {
+    /// The Id of the channel.
+    pub id: ChannelId,
+    /// The name of the channel.
+    pub name: String,
 }
 
 impl fmt::Debug for Webhook {
@@ -96,10 +134,26 @@ impl fmt::Debug for Webhook {
             .field("guild_id", &self.guild_id)
             .field("name", &self.name)
             .field("user", &self.user)
+            .field("application_id", &self.application_id)
+            .field("source_guild", &self.source_guild)
+            .field("source_channel", &self.source_channel)
             .finish()
     }
 }
 
+impl Webhook {
+    /// Whether the webhook posts the messages of a followed channel, in which
+    /// case [`Self::source_guild`] and [`Self::source_channel`] describe that
+    /// channel.
+    ///
+    /// **Note**: The source is not received when getting a webhook by its
+    /// token.
+    #[inline]
+    pub fn is_follower(&self) -> bool {
+        self.kind == WebhookType::ChannelFollower
+    }
+}
+
 #[cfg(feature = "model")]
 impl Webhook {
     /// Deletes the webhook.
@@ -282,7 +336,9 @@ impl Webhook {
     ///
     /// # Errors
     ///
-    /// Returns an [`Error::Model`] if the [`Self::token`] is [`None`].
+    /// Returns an [`Error::Model`] if the [`Self::token`] is [`None`], if
+    /// forum tags were applied without a thread name, or if the username is
+    /// rejected by the [`ExecuteWebhook::username_strategy`].
     ///
     /// May also return an [`Error::Http`] if the content is malformed, or if the webhook's token is invalid.
     ///
@@ -305,7 +361,18 @@ impl Webhook {
         let mut execute_webhook = ExecuteWebhook::default();
         f(&mut execute_webhook);
 
-        let map = utils::hashmap_to_json_map(execute_webhook.0);
+        let mut map = utils::hashmap_to_json_map(execute_webhook.0);
+
+        if !check_username(&mut map, execute_webhook.2) {
+            return Err(Error::Model(ModelError::InvalidWebhookUsername));
+        }
+
+        if map.contains_key("applied_tags") && !map.contains_key("thread_name") {
+            return Err(Error::Model(ModelError::AppliedTagsWithoutThreadName));
+        }
+
+        #[cfg(feature = "unstable_discord_api")]
+        check_components(&map)?;
 
         if !execute_webhook.1.is_empty() {
             http.as_ref()
@@ -369,6 +436,9 @@ impl Webhook {
 
         let map = utils::hashmap_to_json_map(edit_webhook_message.0);
 
+        #[cfg(feature = "unstable_discord_api")]
+        check_components(&map)?;
+
         http.as_ref().edit_webhook_message(self.id.0, token, message_id.0, &map).await
     }
 
@@ -461,3 +531,118 @@ impl WebhookId {
         http.as_ref().get_webhook(self.0).await
     }
 }
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_incoming_webhook() {
+        let webhook: Webhook = serde_json::from_value(json!({
+            "id": "1",
+            "type": 1,
+            "guild_id": "2",
+            "channel_id": "3",
+            "user": {
+                "id": "4",
+                "username": "creator",
+                "discriminator": "0001",
+                "avatar": null,
+            },
+            "name": "Incoming",
+            "avatar": null,
+            "token": "token",
+            "application_id": null,
+        }))
+        .unwrap();
+
+        assert_eq!(webhook.kind, WebhookType::Incoming);
+        assert!(!webhook.is_follower());
+        assert_eq!(webhook.token.as_deref(), Some("token"));
+        assert!(webhook.source_guild.is_none());
+        assert!(webhook.source_channel.is_none());
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_channel_follower_webhook() {
+        let webhook: Webhook = serde_json::from_value(json!({
+            "id": "1",
+            "type": 2,
+            "guild_id": "2",
+            "channel_id": "3",
+            "name": "Announcements",
+            "avatar": null,
+            "application_id": null,
+            "source_guild": {
+                "id": "5",
+                "name": "Source",
+                "icon": "a_icon",
+            },
+            "source_channel": {
+                "id": "6",
+                "name": "news",
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(webhook.kind, WebhookType::ChannelFollower);
+        assert!(webhook.is_follower());
+        assert!(webhook.token.is_none());
+
+        let source_guild = webhook.source_guild.unwrap();
+        assert_eq!(source_guild.id, GuildId(5));
+        assert_eq!(source_guild.name, "Source");
+        assert_eq!(source_guild.icon.as_deref(), Some("a_icon"));
+
+        let source_channel = webhook.source_channel.unwrap();
+        assert_eq!(source_channel.id, ChannelId(6));
+        assert_eq!(source_channel.name, "news");
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_application_webhook() {
+        let webhook: Webhook = serde_json::from_value(json!({
+            "id": "1",
+            "type": 3,
+            "guild_id": null,
+            "channel_id": "3",
+            "name": "App",
+            "avatar": null,
+            "application_id": "7",
+        }))
+        .unwrap();
+
+        assert_eq!(webhook.kind, WebhookType::Application);
+        assert!(!webhook.is_follower());
+        assert_eq!(webhook.application_id, Some(ApplicationId(7)));
+        assert_eq!(webhook.kind.name(), "application");
+    }
+
+    #[cfg(feature = "model")]
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_applied_tags_without_thread_name() {
+        let webhook: Webhook = serde_json::from_value(json!({
+            "id": "1",
+            "type": 1,
+            "channel_id": "3",
+            "name": "Forum",
+            "avatar": null,
+            "token": "token",
+            "application_id": null,
+        }))
+        .unwrap();
+        let http = Http::new_with_token("token");
+
+        let result = webhook
+            .execute(&http, false, |w| w.content("post").applied_tags(vec![ForumTagId(1)]))
+            .await;
+
+        assert!(matches!(result, Err(Error::Model(ModelError::AppliedTagsWithoutThreadName))));
+    }
+}
@@ -0,0 +1,31 @@
+//! Utilities for testing bots without connecting to Discord.
+//!
+//! Gateway events received by a bot can be recorded with a
+//! [`RecordingRawEventHandler`], and then be replayed with an
+//! [`EventReplayer`], which dispatches them the same way a shard does: the
+//! cache is updated, and the events are passed on to the event handlers, the
+//! framework, and collectors.
+//!
+//! # Recordings
+//!
+//! A recording consists of gateway dispatch payloads, one JSON object per
+//! line, as they are sent by Discord:
+//!
+//! ```json
+//! {"op":0,"s":1,"t":"MESSAGE_CREATE","d":{"id":"1", ...},"recorded_at":1500}
+//! ```
+//!
+//! The optional `recorded_at` field holds the milliseconds since the recording
+//! started, which allows replaying the events with their recorded delays.
+//! Payloads other than dispatches, such as heartbeat acknowledgements, are
+//! skipped when replaying.
+
+mod recorder;
+mod replayer;
+
+pub use self::recorder::RecordingRawEventHandler;
+pub use self::replayer::{EventReplayer, ReplayTiming};
+
+/// The field of a recorded payload holding the milliseconds since the
+/// recording started.
+const RECORDED_AT: &str = "recorded_at";
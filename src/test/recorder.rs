@@ -0,0 +1,225 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+use tokio::sync::oneshot;
+use tracing::warn;
+
+use super::RECORDED_AT;
+use crate::client::RawEventHandler;
+use crate::internal::prelude::*;
+
+/// A [`RawEventHandler`] which records the received dispatches, to be replayed
+/// by an [`EventReplayer`].
+///
+/// Each dispatch is written as received from the gateway on its own line,
+/// together with the time it was received at, so fields which serenity does
+/// not know of are recorded as well. Refer to the [module-level documentation]
+/// for the format.
+///
+/// The payloads are written by a dedicated thread, so recording does not block
+/// the shards. Call [`Self::flush`] to wait for the queued payloads to be
+/// written.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use serenity::prelude::*;
+/// use serenity::test::RecordingRawEventHandler;
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let recorder = RecordingRawEventHandler::create("events.jsonl")?;
+/// let mut client = Client::builder("token").raw_event_handler(recorder).await?;
+///
+/// client.start().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`EventReplayer`]: super::EventReplayer
+/// [module-level documentation]: super
+#[derive(Clone)]
+pub struct RecordingRawEventHandler {
+    records: Arc<Mutex<Sender<Record>>>,
+    started: Instant,
+}
+
+enum Record {
+    Payload(String),
+    Flush(oneshot::Sender<()>),
+}
+
+impl RecordingRawEventHandler {
+    /// Creates a recorder writing the dispatches to `writer`, on a thread
+    /// spawned for it.
+    ///
+    /// Each dispatch is written with a single call to [`Write::write_all`],
+    /// followed by a call to [`Write::flush`].
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        let (records, received) = mpsc::channel();
+
+        thread::spawn(move || write_records(writer, received.iter()));
+
+        Self {
+            records: Arc::new(Mutex::new(records)),
+            started: Instant::now(),
+        }
+    }
+
+    /// Creates a recorder writing the dispatches to the file at `path`, which
+    /// is truncated if it exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Io`] if the file cannot be created.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(LineWriter::new(File::create(path)?)))
+    }
+
+    /// Waits until the dispatches recorded so far are written.
+    pub async fn flush(&self) {
+        let (flushed, flushing) = oneshot::channel();
+
+        if self.send(Record::Flush(flushed)) {
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = flushing.await;
+        }
+    }
+
+    /// Queues the `record` for the writer thread, returning whether it is
+    /// still running.
+    fn send(&self, record: Record) -> bool {
+        match self.records.lock() {
+            Ok(records) => records.send(record).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+fn write_records(mut writer: impl Write, records: impl Iterator<Item = Record>) {
+    for record in records {
+        match record {
+            Record::Payload(payload) => {
+                if let Err(why) = writer.write_all(payload.as_bytes()).and_then(|()| writer.flush())
+                {
+                    warn!("Failed to record a dispatch: {:?}", why);
+                }
+            },
+            Record::Flush(flushed) => {
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = flushed.send(());
+            },
+        }
+    }
+}
+
+impl fmt::Debug for RecordingRawEventHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordingRawEventHandler").field("started", &self.started).finish()
+    }
+}
+
+impl RawEventHandler for RecordingRawEventHandler {
+    fn raw_payload(&self, payload: &Value) {
+        if payload.get("op").and_then(Value::as_u64) != Some(0) {
+            return;
+        }
+
+        let recorded_at = self.started.elapsed().as_millis() as u64;
+
+        let mut payload = match serde_json::to_string(payload) {
+            Ok(payload) => payload,
+            Err(why) => {
+                warn!("Failed to serialize a dispatch for recording: {:?}", why);
+
+                return;
+            },
+        };
+
+        // Splice the time in before the closing brace of the payload object.
+        payload.pop();
+        payload.push_str(&format!(",\"{}\":{}}}\n", RECORDED_AT, recorded_at));
+
+        if !self.send(Record::Payload(payload)) {
+            warn!("Failed to record a dispatch: the writer thread has stopped");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::{json, Value};
+
+    use super::RecordingRawEventHandler;
+    use crate::test::EventReplayer;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    async fn record(payloads: Vec<Value>) -> String {
+        let buffer = Buffer::default();
+        let recorder = RecordingRawEventHandler::new(buffer.clone());
+        let mut replayer = EventReplayer::new().raw_event_handler(recorder.clone());
+
+        replayer.replay(payloads).await.unwrap();
+        recorder.flush().await;
+
+        let recording = buffer.0.lock().unwrap().clone();
+        String::from_utf8(recording).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let payloads = vec![
+            json!({"op": 0, "s": 7, "t": "CUSTOM_EVENT", "d": {"a": 1}, "x": true}),
+            json!({"op": 11}),
+            json!({"op": 0, "s": 8, "t": "TYPING_START", "d": {
+                "channel_id": "2",
+                "timestamp": 1,
+                "user_id": "3",
+            }}),
+        ];
+
+        let recording = record(payloads).await;
+        let lines = recording
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["s"], 7);
+        assert_eq!(lines[0]["t"], "CUSTOM_EVENT");
+        assert_eq!(lines[0]["d"], json!({"a": 1}));
+        assert_eq!(lines[0]["x"], true);
+        assert!(lines[0]["recorded_at"].is_u64());
+        assert_eq!(lines[1]["s"], 8);
+        assert_eq!(lines[1]["t"], "TYPING_START");
+        assert_eq!(lines[1]["d"]["timestamp"], 1);
+
+        // Recording a replayed recording yields the same events.
+        let rerecording = record(lines.clone()).await;
+        for (line, reline) in lines.iter().zip(rerecording.lines()) {
+            let reline = serde_json::from_str::<Value>(reline).unwrap();
+            assert_eq!(line["t"], reline["t"]);
+            assert_eq!(line["d"], reline["d"]);
+        }
+    }
+}
@@ -0,0 +1,631 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_tungstenite::tungstenite::Message;
+use futures::channel::mpsc::{self, UnboundedReceiver as Receiver, UnboundedSender as Sender};
+use futures::{FutureExt, StreamExt};
+use serde::Deserialize;
+use tokio::sync::RwLock;
+use tokio::task::yield_now;
+#[cfg(all(feature = "tokio_compat", not(feature = "tokio")))]
+use tokio::time::{delay_for as sleep, timeout, Instant};
+#[cfg(feature = "tokio")]
+use tokio::time::{sleep, timeout, Instant};
+use typemap_rev::{TypeMap, TypeMapKey};
+
+use super::RECORDED_AT;
+#[cfg(feature = "cache")]
+use crate::cache::{Cache, Settings as CacheSettings};
+#[cfg(feature = "collector")]
+use crate::client::bridge::gateway::CollectorFilters;
+use crate::client::bridge::gateway::{dispatching, ShardClientMessage, ShardRunnerMessage};
+use crate::client::dispatch::{dispatch, DispatchEvent};
+use crate::client::{Context, EventHandler, RawEventHandler, TaskTracker};
+#[cfg(feature = "framework")]
+use crate::framework::Framework;
+use crate::gateway::InterMessage;
+use crate::http::Http;
+use crate::internal::prelude::*;
+#[cfg(feature = "framework")]
+use crate::model::channel::Message as ChannelMessage;
+use crate::model::event::GatewayEvent;
+use crate::CacheAndHttp;
+
+/// How an [`EventReplayer`] paces the replayed events.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ReplayTiming {
+    /// Replays the events one after another, only waiting for the tasks
+    /// handling the previous event to make progress.
+    Instant,
+    /// Waits between the events for as long as was recorded between them,
+    /// as given by their `recorded_at` fields.
+    ///
+    /// Events without a `recorded_at` field are replayed instantly.
+    Recorded,
+}
+
+impl Default for ReplayTiming {
+    fn default() -> Self {
+        Self::Instant
+    }
+}
+
+/// Replays recorded gateway events through the same dispatch path as a
+/// shard of a [`Client`], without connecting to Discord.
+///
+/// The cache is updated with the events, which are then passed to the
+/// [`EventHandler`], the [`RawEventHandler`], the [`Framework`], and
+/// collectors, in the same way as the shard runner does. The [`Context`]s
+/// passed to them are backed by a stub [`ShardMessenger`], whose messages are
+/// captured instead of being sent to Discord, and can be inspected with
+/// [`Self::sent_messages`].
+///
+/// Refer to the [module-level documentation] for the format of recordings.
+///
+/// **Note**: HTTP requests are still sent to Discord, unless a different
+/// [`Http`] client is provided with [`Self::http`].
+///
+/// Between two events, the replayer yields to the tasks handling the previous
+/// event until they no longer message the shard, such as to build a
+/// collector. On a runtime with a single thread, such as the default one of
+/// `#[tokio::test]`, replaying is therefore deterministic.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use serenity::model::channel::Message;
+/// use serenity::prelude::*;
+/// use serenity::test::EventReplayer;
+///
+/// struct Handler;
+///
+/// #[serenity::async_trait]
+/// impl EventHandler for Handler {
+///     async fn message(&self, ctx: Context, msg: Message) {
+///         if msg.content == "!online" {
+///             ctx.online().await;
+///         }
+///     }
+/// }
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut replayer = EventReplayer::new().event_handler(Handler);
+/// replayer.replay_file("events.jsonl").await?;
+///
+/// for message in replayer.sent_messages() {
+///     println!("{:?}", message);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Client`]: crate::Client
+/// [`Framework`]: crate::framework::Framework
+/// [`ShardMessenger`]: crate::client::bridge::gateway::ShardMessenger
+/// [module-level documentation]: super
+pub struct EventReplayer {
+    data: Arc<RwLock<TypeMap>>,
+    shard_data: Arc<RwLock<TypeMap>>,
+    event_handler: Option<Arc<dyn EventHandler>>,
+    raw_event_handler: Option<Arc<dyn RawEventHandler>>,
+    #[cfg(feature = "framework")]
+    framework: Arc<Box<dyn Framework + Send + Sync>>,
+    cache_and_http: Arc<CacheAndHttp>,
+    task_tracker: TaskTracker,
+    shard_id: u64,
+    timing: ReplayTiming,
+    runner_tx: Sender<InterMessage>,
+    runner_rx: Receiver<InterMessage>,
+    sent: Vec<ShardRunnerMessage>,
+    #[cfg(feature = "collector")]
+    collector_filters: CollectorFilters,
+}
+
+impl EventReplayer {
+    /// Creates a replayer without any handlers, an empty cache, and an
+    /// [`Http`] client without a token.
+    pub fn new() -> Self {
+        let (runner_tx, runner_rx) = mpsc::unbounded();
+
+        Self {
+            data: Arc::new(RwLock::new(TypeMap::new())),
+            shard_data: Arc::new(RwLock::new(TypeMap::new())),
+            event_handler: None,
+            raw_event_handler: None,
+            #[cfg(feature = "framework")]
+            framework: Arc::new(Box::new(NoFramework)),
+            cache_and_http: Arc::new(CacheAndHttp::default()),
+            task_tracker: TaskTracker::default(),
+            shard_id: 0,
+            timing: ReplayTiming::default(),
+            runner_tx,
+            runner_rx,
+            sent: Vec::new(),
+            #[cfg(feature = "collector")]
+            collector_filters: CollectorFilters::new(),
+        }
+    }
+
+    /// Sets the event handler to dispatch the events to.
+    pub fn event_handler<H: EventHandler + 'static>(mut self, event_handler: H) -> Self {
+        self.event_handler = Some(Arc::new(event_handler));
+
+        self
+    }
+
+    /// Sets the raw event handler to dispatch the events to.
+    pub fn raw_event_handler<H: RawEventHandler + 'static>(mut self, raw_event_handler: H) -> Self {
+        self.raw_event_handler = Some(Arc::new(raw_event_handler));
+
+        self
+    }
+
+    /// Sets the framework to dispatch messages to.
+    #[cfg(feature = "framework")]
+    pub fn framework<F>(mut self, framework: F) -> Self
+    where
+        F: Framework + Send + Sync + 'static,
+    {
+        self.framework = Arc::new(Box::new(framework));
+
+        self
+    }
+
+    /// Inserts a value into the data available through [`Context::data`].
+    pub fn type_map_insert<T: TypeMapKey>(self, value: T::Value) -> Self {
+        if let Ok(mut data) = self.data.try_write() {
+            data.insert::<T>(value);
+        }
+
+        self
+    }
+
+    /// Sets the [`Http`] client available through the [`Context`], such as
+    /// one sending requests to a mock server.
+    pub fn http(mut self, http: Http) -> Self {
+        self.cache_and_http = Arc::new(CacheAndHttp {
+            #[cfg(feature = "cache")]
+            cache: Arc::clone(&self.cache_and_http.cache),
+            #[cfg(feature = "cache")]
+            update_cache_timeout: None,
+            http: Arc::new(http),
+        });
+
+        self
+    }
+
+    /// Sets the settings of the cache, replacing it with an empty one.
+    /// Refer to [`CacheSettings`] for more information.
+    #[cfg(feature = "cache")]
+    pub fn cache_settings<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut CacheSettings) -> &mut CacheSettings,
+    {
+        let mut settings = CacheSettings::new();
+        f(&mut settings);
+
+        self.cache_and_http = Arc::new(CacheAndHttp {
+            cache: Arc::new(Cache::new_with_settings(settings)),
+            update_cache_timeout: None,
+            http: Arc::clone(&self.cache_and_http.http),
+        });
+
+        self
+    }
+
+    /// Sets the ID of the shard the events are replayed on, `0` by default.
+    pub fn shard_id(mut self, shard_id: u64) -> Self {
+        self.shard_id = shard_id;
+
+        self
+    }
+
+    /// Sets how the events are paced, [`ReplayTiming::Instant`] by default.
+    pub fn timing(mut self, timing: ReplayTiming) -> Self {
+        self.timing = timing;
+
+        self
+    }
+
+    /// The cache and the [`Http`] client used by the [`Context`]s.
+    pub fn cache_and_http(&self) -> &Arc<CacheAndHttp> {
+        &self.cache_and_http
+    }
+
+    /// Creates a [`Context`] like the ones passed to the handlers.
+    pub fn context(&self) -> Context {
+        Context::new(
+            Arc::clone(&self.data),
+            Arc::clone(&self.shard_data),
+            self.runner_tx.clone(),
+            self.shard_id,
+            Arc::clone(&self.cache_and_http.http),
+            #[cfg(feature = "cache")]
+            Arc::clone(&self.cache_and_http.cache),
+        )
+    }
+
+    /// Takes the messages sent to the shard since the last call, such as
+    /// presence updates or member chunk requests.
+    ///
+    /// Messages adding or removing the filters of collectors are handled by
+    /// the replayer, and not included.
+    pub fn sent_messages(&mut self) -> Vec<ShardRunnerMessage> {
+        self.handle_messages();
+
+        std::mem::take(&mut self.sent)
+    }
+
+    /// Replays the newline-delimited payloads of the file at `path`.
+    ///
+    /// Refer to [`Self::replay`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Io`] if the file cannot be read, and the errors of
+    /// [`Self::replay_str`].
+    pub async fn replay_file(&mut self, path: impl AsRef<Path>) -> Result<usize> {
+        let payloads = tokio::fs::read_to_string(path).await?;
+
+        self.replay_str(&payloads).await
+    }
+
+    /// Replays newline-delimited payloads, skipping empty lines.
+    ///
+    /// Refer to [`Self::replay`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Json`] if a line is not valid JSON, and the errors
+    /// of [`Self::replay`].
+    pub async fn replay_str(&mut self, payloads: &str) -> Result<usize> {
+        let payloads = payloads
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<StdResult<Vec<Value>, _>>()?;
+
+        self.replay(payloads).await
+    }
+
+    /// Replays gateway payloads in order, returning how many events were
+    /// dispatched.
+    ///
+    /// Payloads other than dispatches are skipped. Once all events were
+    /// dispatched, the tasks handling them are given a chance to progress,
+    /// but are not awaited; refer to [`Self::wait`] for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Json`] if a payload cannot be deserialized, after
+    /// the events before it were replayed.
+    pub async fn replay(&mut self, payloads: impl IntoIterator<Item = Value>) -> Result<usize> {
+        let mut dispatched = 0;
+        let mut previous_recorded_at = None;
+
+        for mut payload in payloads {
+            let recorded_at = payload
+                .as_object_mut()
+                .and_then(|map| map.remove(RECORDED_AT))
+                .and_then(|recorded_at| recorded_at.as_u64());
+
+            if let (ReplayTiming::Recorded, Some(recorded_at)) = (self.timing, recorded_at) {
+                if let Some(previous) = previous_recorded_at {
+                    sleep(Duration::from_millis(recorded_at.saturating_sub(previous))).await;
+                }
+
+                previous_recorded_at = Some(recorded_at);
+            }
+
+            if let Some(raw_event_handler) = &self.raw_event_handler {
+                raw_event_handler.raw_payload(&payload);
+            }
+
+            let event = match GatewayEvent::deserialize(payload)? {
+                GatewayEvent::Dispatch(_, event) => event,
+                _ => continue,
+            };
+
+            // Like a shard runner, handle the messages sent meanwhile, such
+            // as the filters of new collectors, before the event.
+            self.handle_messages();
+
+            #[cfg(feature = "collector")]
+            {
+                self.collector_filters.purge_filters();
+                self.collector_filters.handle_filters(&event, &self.cache_and_http).await;
+            }
+
+            let dispatch = dispatch(
+                DispatchEvent::Model(event),
+                #[cfg(feature = "framework")]
+                &self.framework,
+                &self.data,
+                &self.shard_data,
+                &self.event_handler,
+                &self.raw_event_handler,
+                &self.runner_tx,
+                self.shard_id,
+                Arc::clone(&self.cache_and_http),
+                &self.task_tracker,
+            );
+            dispatching(dispatch).await;
+            dispatched += 1;
+
+            self.settle().await;
+        }
+
+        Ok(dispatched)
+    }
+
+    /// Waits for up to `duration` for the tasks handling the replayed events
+    /// to complete, while handling the messages they send to the shard.
+    ///
+    /// Returns whether all tasks completed.
+    pub async fn wait(&mut self, duration: Duration) -> bool {
+        let deadline = Instant::now() + duration;
+
+        loop {
+            self.handle_messages();
+
+            if self.task_tracker.is_empty() {
+                return true;
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+
+            // Wakes up once a task sends a message, or checks again shortly.
+            let poll = (deadline - now).min(Duration::from_millis(10));
+            if let Ok(Some(message)) = timeout(poll, self.runner_rx.next()).await {
+                self.handle_message(message);
+            }
+        }
+    }
+
+    /// Yields to the tasks handling the replayed events until they no longer
+    /// send messages to the shard.
+    async fn settle(&mut self) {
+        loop {
+            yield_now().await;
+
+            if !self.handle_messages() {
+                break;
+            }
+        }
+    }
+
+    /// Handles the messages sent to the shard, returning whether there were
+    /// any.
+    fn handle_messages(&mut self) -> bool {
+        let mut handled = false;
+
+        while let Some(Some(message)) = self.runner_rx.next().now_or_never() {
+            self.handle_message(message);
+            handled = true;
+        }
+
+        handled
+    }
+
+    fn handle_message(&mut self, message: InterMessage) {
+        let message = match message {
+            InterMessage::Client(message) => match *message {
+                ShardClientMessage::Runner(message) => message,
+                // Only the shard manager sends these.
+                ShardClientMessage::Manager(_) => return,
+            },
+            InterMessage::Json(value) => {
+                ShardRunnerMessage::Message(Message::Text(value.to_string()))
+            },
+        };
+
+        #[cfg(feature = "collector")]
+        let message = match self.collector_filters.handle_message(message) {
+            Some(message) => message,
+            None => return,
+        };
+
+        match message {
+            ShardRunnerMessage::Acknowledge(ack) => ack.send(),
+            message => self.sent.push(message),
+        }
+    }
+}
+
+impl Default for EventReplayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for EventReplayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventReplayer")
+            .field("shard_id", &self.shard_id)
+            .field("timing", &self.timing)
+            .field("sent", &self.sent)
+            .finish()
+    }
+}
+
+/// Used while no framework is set, as one is needed with the `framework`
+/// feature.
+#[cfg(feature = "framework")]
+struct NoFramework;
+
+#[cfg(feature = "framework")]
+#[async_trait::async_trait]
+impl Framework for NoFramework {
+    async fn dispatch(&self, _: Context, _: ChannelMessage) {}
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    #[cfg(feature = "collector")]
+    use futures::StreamExt;
+    use serde_json::{json, Value};
+    use tokio::sync::Mutex;
+    use tokio::time::Instant;
+
+    use super::{EventReplayer, ReplayTiming};
+    use crate::client::bridge::gateway::ShardRunnerMessage;
+    use crate::client::{Context, EventHandler};
+    #[cfg(feature = "collector")]
+    use crate::collector::MessageCollectorBuilder;
+    use crate::model::prelude::*;
+    use crate::Error;
+
+    fn message_payload(id: u64, content: &str) -> Value {
+        json!({
+            "op": 0,
+            "s": id,
+            "t": "MESSAGE_CREATE",
+            "d": {
+                "id": id.to_string(),
+                "channel_id": "2",
+                "author": {
+                    "id": "3",
+                    "username": "user",
+                    "discriminator": "0001",
+                    "avatar": null,
+                },
+                "content": content,
+                "timestamp": "2021-01-01T00:00:00+00:00",
+                "edited_timestamp": null,
+                "tts": false,
+                "mention_everyone": false,
+                "mentions": [],
+                "mention_roles": [],
+                "attachments": [],
+                "embeds": [],
+                "pinned": false,
+                "type": 0,
+            },
+        })
+    }
+
+    #[derive(Default)]
+    struct Handler {
+        received: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl EventHandler for Handler {
+        async fn message(&self, ctx: Context, msg: Message) {
+            self.received.lock().await.push(msg.content.clone());
+
+            match &msg.content[..] {
+                "!online" => ctx.online().await,
+                #[cfg(feature = "collector")]
+                "!collect" => {
+                    let reply = MessageCollectorBuilder::new(&ctx)
+                        .channel_id(msg.channel_id)
+                        .collect_limit(1)
+                        .await
+                        .next()
+                        .await;
+
+                    if let Some(reply) = reply {
+                        self.received.lock().await.push(format!("collected {}", reply.content));
+                    }
+                },
+                _ => {},
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_to_event_handler() {
+        let handler = Handler::default();
+        let received = Arc::clone(&handler.received);
+        let mut replayer = EventReplayer::new().event_handler(handler);
+        #[cfg(feature = "cache")]
+        {
+            replayer = replayer.cache_settings(|settings| settings.max_messages(10));
+        }
+
+        let payloads = [
+            message_payload(1, "hello").to_string(),
+            String::new(),
+            json!({"op": 11}).to_string(),
+            message_payload(2, "!online").to_string(),
+        ]
+        .join("\n");
+
+        assert_eq!(replayer.replay_str(&payloads).await.unwrap(), 2);
+        assert!(replayer.wait(Duration::from_secs(1)).await);
+
+        assert_eq!(*received.lock().await, ["hello", "!online"]);
+        assert!(matches!(
+            &replayer.sent_messages()[..],
+            [ShardRunnerMessage::SetStatus(OnlineStatus::Online)]
+        ));
+        assert!(replayer.sent_messages().is_empty());
+
+        #[cfg(feature = "cache")]
+        {
+            let cache = &replayer.cache_and_http().cache;
+            assert!(cache.message(ChannelId(2), MessageId(2)).await.is_some());
+        }
+    }
+
+    #[cfg(feature = "collector")]
+    #[tokio::test]
+    async fn test_replay_to_collector() {
+        let handler = Handler::default();
+        let received = Arc::clone(&handler.received);
+        let mut replayer = EventReplayer::new().event_handler(handler);
+
+        let payloads = vec![
+            message_payload(1, "!collect"),
+            message_payload(2, "first"),
+            message_payload(3, "second"),
+        ];
+
+        assert_eq!(replayer.replay(payloads).await.unwrap(), 3);
+        assert!(replayer.wait(Duration::from_secs(1)).await);
+
+        // The collector receives the message before the event handler.
+        assert_eq!(*received.lock().await, ["!collect", "collected first", "first", "second"]);
+        assert!(replayer.sent_messages().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_replay_timing() {
+        let mut first = message_payload(1, "first");
+        first["recorded_at"] = 1000.into();
+        let mut second = message_payload(2, "second");
+        second["recorded_at"] = 4000.into();
+        let payloads = vec![first, second];
+
+        let mut replayer = EventReplayer::new();
+        let start = Instant::now();
+        assert_eq!(replayer.replay(payloads.clone()).await.unwrap(), 2);
+        assert_eq!(start.elapsed(), Duration::from_secs(0));
+
+        let mut replayer = EventReplayer::new().timing(ReplayTiming::Recorded);
+        let start = Instant::now();
+        assert_eq!(replayer.replay(payloads).await.unwrap(), 2);
+        assert_eq!(start.elapsed(), Duration::from_secs(3));
+    }
+
+    #[tokio::test]
+    async fn test_replay_errors() {
+        let mut replayer = EventReplayer::new();
+
+        assert!(matches!(replayer.replay_str("{").await, Err(Error::Json(_))));
+
+        let payloads = vec![message_payload(1, "valid"), json!({"op": 0, "s": 2, "t": "READY"})];
+        assert!(matches!(replayer.replay(payloads).await, Err(Error::Json(_))));
+    }
+}
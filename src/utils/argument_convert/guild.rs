@@ -34,12 +34,21 @@ impl ArgumentConvert for Guild {
         _channel_id: Option<ChannelId>,
         s: &str,
     ) -> Result<Self, Self::Err> {
-        let guilds = ctx.cache.guilds.read().await;
+        let guild_id = {
+            let guilds = ctx.cache.guilds.read().await;
 
-        let lookup_by_id = || guilds.get(&GuildId(s.parse().ok()?));
+            let lookup_by_id = || guilds.get(&GuildId(s.parse().ok()?));
 
-        let lookup_by_name = || guilds.values().find(|guild| guild.name.eq_ignore_ascii_case(s));
+            let lookup_by_name =
+                || guilds.values().find(|guild| guild.name.eq_ignore_ascii_case(s));
 
-        lookup_by_id().or_else(lookup_by_name).cloned().ok_or(GuildParseError::NotFoundOrMalformed)
+            lookup_by_id().or_else(lookup_by_name).map(|guild| guild.id)
+        };
+
+        match guild_id {
+            Some(guild_id) => ctx.cache.guild(guild_id).await,
+            None => None,
+        }
+        .ok_or(GuildParseError::NotFoundOrMalformed)
     }
 }
@@ -34,8 +34,10 @@ impl std::fmt::Display for MemberParseError {
 /// 1. Lookup by ID.
 /// 2. [Lookup by mention](`crate::utils::parse_username`).
 /// 3. [Lookup by name#discrim](`crate::utils::parse_user_tag`).
-/// 4. Lookup by name
-/// 5. Lookup by nickname
+/// 4. [Lookup by nickname, global name or name in the cache](`Guild::search_members_by_name`),
+///    preferring exact matches over prefixes over substrings.
+/// 5. Lookup by name
+/// 6. Lookup by nickname
 #[cfg(feature = "cache")]
 #[async_trait::async_trait]
 impl ArgumentConvert for Member {
@@ -53,7 +55,8 @@ impl ArgumentConvert for Member {
             .await
             .ok_or(MemberParseError::GuildNotInCache)?;
 
-        // DON'T use guild.members: it's only populated when guild presences intent is enabled!
+        // Don't rely on guild.members alone: it's only fully populated when the guild presences
+        // intent is enabled!
 
         // If string is a raw user ID or a mention
         if let Some(user_id) = s.parse().ok().or_else(|| crate::utils::parse_username(s)) {
@@ -75,6 +78,11 @@ impl ArgumentConvert for Member {
             }
         }
 
+        // If string is (part of) a nickname, global name or username of a cached member
+        if let Some(result) = guild.search_members_by_name(s, 1).into_iter().next() {
+            return Ok(result.member.clone());
+        }
+
         // If string is username or nickname
         if let Ok(member_results) = guild.search_members(ctx, s, Some(100)).await {
             if let Some(member) = member_results.into_iter().find(|m| {
@@ -246,6 +246,7 @@ fn dummy_message() -> Message {
             bot: false,
             discriminator: 0x0000,
             name: String::new(),
+            global_name: None,
             public_flags: None,
             banner: None,
             accent_colour: None,
@@ -269,6 +270,7 @@ fn dummy_message() -> Message {
         timestamp: Utc::now(),
         activity: None,
         application: None,
+        application_id: None,
         message_reference: None,
         flags: None,
         stickers: Vec::new(),
@@ -276,6 +278,8 @@ fn dummy_message() -> Message {
         #[cfg(feature = "unstable_discord_api")]
         interaction: None,
         #[cfg(feature = "unstable_discord_api")]
+        interaction_metadata: None,
+        #[cfg(feature = "unstable_discord_api")]
         components: vec![],
     }
 }
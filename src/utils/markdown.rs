@@ -0,0 +1,729 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Struct that allows to alter [`escape_markdown`]'s behaviour.
+///
+/// By default, only markdown formatting is escaped.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EscapeOptions {
+    escape_mentions: bool,
+    escape_links: bool,
+}
+
+impl EscapeOptions {
+    pub fn new() -> Self {
+        EscapeOptions::default()
+    }
+
+    /// If set to true, [`escape_markdown`] will also escape user, role and
+    /// channel mentions, as well as `@everyone` and `@here`, so that they are
+    /// shown as written instead of mentioning anyone.
+    pub fn escape_mentions(mut self, b: bool) -> Self {
+        self.escape_mentions = b;
+
+        self
+    }
+
+    /// If set to true, [`escape_markdown`] will wrap links in angle brackets
+    /// so that they are not embedded, and escape masked links (`[text](url)`)
+    /// so that they are shown as written.
+    pub fn escape_links(mut self, b: bool) -> Self {
+        self.escape_links = b;
+
+        self
+    }
+}
+
+/// A code block extracted from a message by [`extract_codeblocks`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CodeBlock {
+    /// The language given after the opening fence, if any.
+    pub language: Option<String>,
+    /// The code within the block, without the newlines around it.
+    pub code: String,
+}
+
+/// Escapes the markdown of a text, so that it is shown as written when sent
+/// in a message, such as when echoing user input.
+///
+/// Links, custom emojis and timestamps are kept as they are, as escaping
+/// their contents would break them. Mentions are kept as well, unless
+/// [`EscapeOptions::escape_mentions`] is set.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::utils::{escape_markdown, EscapeOptions};
+///
+/// let escaped = escape_markdown("**hi** <@123>", EscapeOptions::default());
+/// assert_eq!(escaped, r"\*\*hi\*\* <@123>");
+///
+/// let escaped = escape_markdown("**hi** <@123>", EscapeOptions::new().escape_mentions(true));
+/// assert_eq!(escaped, r"\*\*hi\*\* \<@123>");
+/// ```
+pub fn escape_markdown(text: &str, options: EscapeOptions) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut line_start = true;
+    let mut index = 0;
+
+    while let Some(c) = text[index..].chars().next() {
+        let rest = &text[index..];
+
+        if let Some(len) = link_len(rest) {
+            if options.escape_links {
+                escaped.push('<');
+                escaped.push_str(&rest[..len]);
+                escaped.push('>');
+            } else {
+                escaped.push_str(&rest[..len]);
+            }
+
+            index += len;
+            line_start = false;
+            continue;
+        }
+
+        if c == '<' {
+            if let Some(len) = angle_token_len(rest) {
+                if options.escape_mentions && is_mention(&rest[1..len - 1]) {
+                    escaped.push('\\');
+                }
+
+                escaped.push_str(&rest[..len]);
+                index += len;
+                line_start = false;
+                continue;
+            }
+        }
+
+        if c == '@' && options.escape_mentions {
+            if let Some(name) =
+                ["everyone", "here"].iter().find(|name| rest[1..].starts_with(*name))
+            {
+                escaped.push('@');
+                escaped.push('\u{200B}');
+                escaped.push_str(name);
+                index += 1 + name.len();
+                line_start = false;
+                continue;
+            }
+        }
+
+        match c {
+            '\\' | '*' | '_' | '`' | '~' | '|' => escaped.push('\\'),
+            '>' if line_start => escaped.push('\\'),
+            '[' if options.escape_links => escaped.push('\\'),
+            _ => {},
+        }
+
+        escaped.push(c);
+        index += c.len_utf8();
+        line_start = c == '\n' || (line_start && (c == ' ' || c == '\t'));
+    }
+
+    escaped
+}
+
+/// Removes the markdown of a text, keeping the text it formats, such as for
+/// writing messages to plain-text logs.
+///
+/// Bold, italics, underlines, strikethroughs, spoilers and block quotes are
+/// removed, as are the backslashes of escaped characters. Code is kept as
+/// written without its backticks, and without the language of code blocks.
+/// Formatting characters which do not format anything, such as an unclosed
+/// `**`, are kept.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::utils::strip_markdown;
+///
+/// assert_eq!(strip_markdown("> **Note**: `a * b` is ||secret||"), "Note: a * b is secret");
+/// assert_eq!(strip_markdown("snake_case and 2 * 3"), "snake_case and 2 * 3");
+/// ```
+pub fn strip_markdown(text: &str) -> String {
+    let mut stripper = Stripper::new();
+
+    for span in spans(text) {
+        match span {
+            Span::Text(range) => stripper.text(text, range),
+            Span::InlineCode(code)
+            | Span::CodeBlock {
+                code, ..
+            } => stripper.literal(code),
+        }
+    }
+
+    stripper.finish()
+}
+
+/// Extracts the code blocks of a text, along with their languages.
+///
+/// Inline code is skipped, and a code block is only found where Discord would
+/// show one, so an unterminated code block is not extracted.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::utils::extract_codeblocks;
+///
+/// let content = "Run `this`:\n```rust\nfn main() {}\n```";
+/// let blocks = extract_codeblocks(content);
+///
+/// assert_eq!(blocks.len(), 1);
+/// assert_eq!(blocks[0].language.as_deref(), Some("rust"));
+/// assert_eq!(blocks[0].code, "fn main() {}");
+/// ```
+pub fn extract_codeblocks(text: &str) -> Vec<CodeBlock> {
+    spans(text)
+        .into_iter()
+        .filter_map(|span| match span {
+            Span::CodeBlock {
+                language,
+                code,
+            } => Some(CodeBlock {
+                language: language.map(ToString::to_string),
+                code: code.to_string(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The length of the link at the start of a text, as Discord finds links.
+fn link_len(text: &str) -> Option<usize> {
+    let scheme_len = if text.starts_with("https://") {
+        8
+    } else if text.starts_with("http://") {
+        7
+    } else {
+        return None;
+    };
+
+    let end = text.find(|c: char| c.is_whitespace() || c == '<').unwrap_or(text.len());
+    let link = text[..end]
+        .trim_end_matches(|c| matches!(c, '.' | ',' | ':' | ';' | '"' | '\'' | ')' | ']'));
+
+    if link.len() > scheme_len {
+        Some(link.len())
+    } else {
+        None
+    }
+}
+
+/// The length of the mention, custom emoji, timestamp or link in angle
+/// brackets at the start of a text.
+fn angle_token_len(text: &str) -> Option<usize> {
+    let end = text[1..].find(|c: char| c == '<' || c == '>' || c.is_whitespace())? + 1;
+
+    if !text[end..].starts_with('>') {
+        return None;
+    }
+
+    let inner = &text[1..end];
+    let is_token = is_mention(inner)
+        || inner.starts_with(':')
+        || inner.starts_with("a:")
+        || inner.starts_with("t:")
+        || link_len(inner).is_some();
+
+    if is_token {
+        Some(end + 1)
+    } else {
+        None
+    }
+}
+
+/// Whether the text within angle brackets is a user, role or channel mention.
+fn is_mention(inner: &str) -> bool {
+    let id = if let Some(id) = inner.strip_prefix('@') {
+        id.strip_prefix(|c| c == '!' || c == '&').unwrap_or(id)
+    } else if let Some(id) = inner.strip_prefix('#') {
+        id
+    } else {
+        return false;
+    };
+
+    !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// A part of a text, split by where Discord shows code.
+enum Span<'a> {
+    /// Text in which markdown applies.
+    Text(Range<usize>),
+    /// Inline code, without its backticks.
+    InlineCode(&'a str),
+    /// A code block, without its fences.
+    CodeBlock {
+        language: Option<&'a str>,
+        code: &'a str,
+    },
+}
+
+/// Splits a text by where Discord shows code.
+///
+/// As with Discord, a code block starts at three backticks and ends at the
+/// next three, and inline code starts at any other run of backticks and ends
+/// at the next run of the same length. Backticks which start neither are kept
+/// as text, as are backticks escaped by a backslash.
+fn spans(text: &str) -> Vec<Span<'_>> {
+    let bytes = text.as_bytes();
+    let mut runs = BacktickRuns::new(bytes);
+    let mut no_fence_from = usize::MAX;
+    let mut run_end = 0;
+    let mut spans = Vec::new();
+    let mut text_start = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' if bytes.get(index + 1).map_or(false, u8::is_ascii_punctuation) => index += 2,
+            b'`' => {
+                // Code blocks may end within a run of backticks, so the rest
+                // of the run is not counted again.
+                if index >= run_end {
+                    run_end = index + run_len(bytes, index);
+                }
+
+                let len = run_end - index;
+                let found = code_block(text, index, &mut no_fence_from).or_else(|| {
+                    runs.next(len, index + len)
+                        .map(|close| (Span::InlineCode(&text[index + len..close]), close + len))
+                });
+
+                match found {
+                    Some((span, end)) => {
+                        if text_start < index {
+                            spans.push(Span::Text(text_start..index));
+                        }
+
+                        spans.push(span);
+                        index = end;
+                        text_start = end;
+                    },
+                    None => index += len,
+                }
+            },
+            _ => index += 1,
+        }
+    }
+
+    if text_start < bytes.len() {
+        spans.push(Span::Text(text_start..bytes.len()));
+    }
+
+    spans
+}
+
+/// The code block starting at `start`, along with where it ends.
+///
+/// `no_fence_from` is where no closing fence was found by an earlier search,
+/// so that searches are not repeated.
+fn code_block<'a>(
+    text: &'a str,
+    start: usize,
+    no_fence_from: &mut usize,
+) -> Option<(Span<'a>, usize)> {
+    if !text[start..].starts_with("```") {
+        return None;
+    }
+
+    // The code must be at least a character long.
+    let search_from = start + 4;
+    if search_from >= *no_fence_from || search_from > text.len() {
+        return None;
+    }
+
+    let close = match text.as_bytes()[search_from..].windows(3).position(|w| w == b"```") {
+        Some(position) => search_from + position,
+        None => {
+            *no_fence_from = search_from;
+            return None;
+        },
+    };
+
+    let body = &text[start + 3..close];
+    let (language, code) = match body.find('\n') {
+        Some(newline)
+            if is_language(&body[..newline]) && !body[newline..].trim_matches('\n').is_empty() =>
+        {
+            (Some(&body[..newline]), &body[newline..])
+        },
+        _ => (None, body),
+    };
+
+    let span = Span::CodeBlock {
+        language,
+        code: code.trim_matches('\n'),
+    };
+
+    Some((span, close + 3))
+}
+
+fn is_language(language: &str) -> bool {
+    !language.is_empty()
+        && language.bytes().all(|b| b.is_ascii_alphanumeric() || b"_+-.#".contains(&b))
+}
+
+fn run_len(bytes: &[u8], start: usize) -> usize {
+    bytes[start..].iter().take_while(|&&b| b == b'`').count()
+}
+
+/// The starts of every run of backticks in a text, by the length of the run.
+///
+/// Runs are looked up in the order of the text, so each length keeps a cursor
+/// to the first run which was not passed yet.
+struct BacktickRuns {
+    by_len: HashMap<usize, (Vec<usize>, usize)>,
+}
+
+impl BacktickRuns {
+    fn new(bytes: &[u8]) -> Self {
+        let mut by_len: HashMap<usize, (Vec<usize>, usize)> = HashMap::new();
+        let mut index = 0;
+
+        while index < bytes.len() {
+            if bytes[index] == b'`' {
+                let len = run_len(bytes, index);
+                by_len.entry(len).or_default().0.push(index);
+                index += len;
+            } else {
+                index += 1;
+            }
+        }
+
+        Self {
+            by_len,
+        }
+    }
+
+    /// The start of the first run of exactly `len` backticks at or after
+    /// `from`, which must not be before that of an earlier call.
+    fn next(&mut self, len: usize, from: usize) -> Option<usize> {
+        let (starts, cursor) = self.by_len.get_mut(&len)?;
+
+        while starts.get(*cursor).map_or(false, |&start| start < from) {
+            *cursor += 1;
+        }
+
+        starts.get(*cursor).copied()
+    }
+}
+
+/// The formatting characters which may be paired, indexed as in
+/// [`Stripper::openers_by_marker`].
+const MARKERS: [u8; 4] = [b'*', b'_', b'~', b'|'];
+
+/// A run of formatting characters which may be closed by a later run.
+struct Opener {
+    piece: usize,
+    marker: usize,
+    len: usize,
+}
+
+/// Removes the markdown of a text, one span at a time.
+///
+/// Formatting characters are paired as they are found: a run which may close
+/// formatting closes the latest opener of the same character, and every
+/// opener after it is kept as text, as it can no longer be closed.
+struct Stripper<'a> {
+    /// The pieces of the text, and whether each was removed as formatting.
+    pieces: Vec<(&'a str, bool)>,
+    openers: Vec<Opener>,
+    /// The indices of the openers of each character of [`MARKERS`].
+    openers_by_marker: [Vec<usize>; 4],
+    line_start: bool,
+}
+
+impl<'a> Stripper<'a> {
+    fn new() -> Self {
+        Self {
+            pieces: Vec::new(),
+            openers: Vec::new(),
+            openers_by_marker: Default::default(),
+            line_start: true,
+        }
+    }
+
+    fn literal(&mut self, text: &'a str) {
+        self.pieces.push((text, false));
+        self.line_start = false;
+    }
+
+    fn text(&mut self, text: &'a str, range: Range<usize>) {
+        let bytes = text.as_bytes();
+        let mut plain_start = range.start;
+        let mut index = range.start;
+
+        while index < range.end {
+            let rest = &text[index..range.end];
+            let byte = bytes[index];
+
+            if self.line_start && (rest.starts_with("> ") || rest.starts_with(">>> ")) {
+                self.pieces.push((&text[plain_start..index], false));
+                index += if rest.starts_with(">>> ") { 4 } else { 2 };
+                plain_start = index;
+                self.line_start = false;
+                continue;
+            }
+
+            self.line_start = byte == b'\n';
+
+            if byte == b'\\' && index + 1 < range.end && bytes[index + 1].is_ascii_punctuation() {
+                self.pieces.push((&text[plain_start..index], false));
+                self.pieces.push((&text[index + 1..index + 2], false));
+                index += 2;
+                plain_start = index;
+            } else if let Some(marker) = MARKERS.iter().position(|&m| m == byte) {
+                let len = bytes[index..range.end].iter().take_while(|&&b| b == byte).count();
+                let prev = text[..index].chars().next_back();
+                let next = text[index + len..].chars().next();
+
+                self.pieces.push((&text[plain_start..index], false));
+                self.run(&text[index..index + len], marker, prev, next);
+                index += len;
+                plain_start = index;
+            } else {
+                index += 1;
+            }
+        }
+
+        self.pieces.push((&text[plain_start..range.end], false));
+    }
+
+    fn run(&mut self, mut run: &'a str, marker: usize, prev: Option<char>, next: Option<char>) {
+        let space_before = prev.map_or(true, char::is_whitespace);
+        let space_after = next.map_or(true, char::is_whitespace);
+
+        // Underscores do not format within words.
+        let (can_open, can_close) = match MARKERS[marker] {
+            b'*' => (!space_after, !space_before),
+            b'_' => (
+                !space_after && !prev.map_or(false, char::is_alphanumeric),
+                !space_before && !next.map_or(false, char::is_alphanumeric),
+            ),
+            _ => (true, true),
+        };
+
+        if can_close {
+            while let Some(&top) = self.openers_by_marker[marker].last() {
+                let len = self.openers[top].len;
+                if len > run.len() {
+                    break;
+                }
+
+                self.pieces[self.openers[top].piece].1 = true;
+                for opener in self.openers.drain(top..) {
+                    self.openers_by_marker[opener.marker].pop();
+                }
+
+                run = &run[len..];
+            }
+        }
+
+        // Strikethroughs and spoilers take two characters, while a single
+        // asterisk or underscore is italics.
+        let single_opens = MARKERS[marker] == b'*' || MARKERS[marker] == b'_';
+
+        if run.len() % 2 == 1 {
+            if can_open && single_opens {
+                self.open(&run[..1], marker);
+            } else {
+                self.pieces.push((&run[..1], false));
+            }
+
+            run = &run[1..];
+        }
+
+        while !run.is_empty() {
+            if can_open {
+                self.open(&run[..2], marker);
+            } else {
+                self.pieces.push((&run[..2], false));
+            }
+
+            run = &run[2..];
+        }
+    }
+
+    fn open(&mut self, run: &'a str, marker: usize) {
+        self.openers.push(Opener {
+            piece: self.pieces.len(),
+            marker,
+            len: run.len(),
+        });
+        self.openers_by_marker[marker].push(self.openers.len() - 1);
+        self.pieces.push((run, false));
+    }
+
+    fn finish(self) -> String {
+        self.pieces.into_iter().filter(|(_, removed)| !removed).map(|(piece, _)| piece).collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::non_ascii_literal)]
+mod test {
+    use super::*;
+
+    fn block(language: Option<&str>, code: &str) -> CodeBlock {
+        CodeBlock {
+            language: language.map(ToString::to_string),
+            code: code.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_escape_markdown() {
+        let options = EscapeOptions::default();
+
+        assert_eq!(escape_markdown("plain text", options), "plain text");
+        assert_eq!(
+            escape_markdown(r"**b** _i_ `c` ~~s~~ ||sp|| \", options),
+            r"\*\*b\*\* \_i\_ \`c\` \~\~s\~\~ \|\|sp\|\| \\",
+        );
+        assert_eq!(escape_markdown("> quote\na > b", options), "\\> quote\na > b");
+        assert_eq!(escape_markdown("  >>> quote", options), "  \\>>> quote");
+        assert_eq!(escape_markdown("ünï_cödé", options), "ünï\\_cödé");
+    }
+
+    #[test]
+    fn test_escape_markdown_keeps_tokens() {
+        let options = EscapeOptions::default();
+
+        assert_eq!(
+            escape_markdown("https://example.com/a_b*c*. x_y", options),
+            "https://example.com/a_b*c*. x\\_y",
+        );
+        assert_eq!(
+            escape_markdown("<https://example.com/a_b>", options),
+            "<https://example.com/a_b>",
+        );
+        assert_eq!(
+            escape_markdown("<:my_emoji:1> <a:my_gif:2>", options),
+            "<:my_emoji:1> <a:my_gif:2>",
+        );
+        assert_eq!(escape_markdown("<t:1618953630:R>", options), "<t:1618953630:R>");
+        assert_eq!(escape_markdown("<not_a_token>", options), "<not\\_a\\_token>");
+        assert_eq!(escape_markdown("http:// and https://.", options), "http:// and https://.");
+    }
+
+    #[test]
+    fn test_escape_markdown_mentions() {
+        let text = "<@1> <@!2> <@&3> <#4> @everyone @here <@x>";
+
+        assert_eq!(escape_markdown(text, EscapeOptions::default()), text);
+        assert_eq!(
+            escape_markdown(text, EscapeOptions::new().escape_mentions(true)),
+            "\\<@1> \\<@!2> \\<@&3> \\<#4> @\u{200B}everyone @\u{200B}here <@x>",
+        );
+    }
+
+    #[test]
+    fn test_escape_markdown_links() {
+        let options = EscapeOptions::new().escape_links(true);
+
+        assert_eq!(
+            escape_markdown("see https://example.com.", options),
+            "see <https://example.com>.",
+        );
+        assert_eq!(
+            escape_markdown("[text](https://example.com)", options),
+            "\\[text](<https://example.com>)",
+        );
+        assert_eq!(escape_markdown("<https://example.com>", options), "<https://example.com>");
+    }
+
+    #[test]
+    fn test_strip_markdown() {
+        assert_eq!(
+            strip_markdown("**b** and *i* and __u__ and ~~s~~ and ||sp||"),
+            "b and i and u and s and sp",
+        );
+        assert_eq!(strip_markdown("***both*** and _i_"), "both and i");
+        assert_eq!(strip_markdown("**bold _and italic_**"), "bold and italic");
+        assert_eq!(strip_markdown("> quote\n>>> block\nnot > quote"), "quote\nblock\nnot > quote");
+        assert_eq!(strip_markdown(r"\*not italic\* \\"), r"*not italic* \");
+    }
+
+    #[test]
+    fn test_strip_markdown_keeps_unpaired() {
+        assert_eq!(strip_markdown("**unclosed"), "**unclosed");
+        assert_eq!(strip_markdown("2 * 3 * 4"), "2 * 3 * 4");
+        assert_eq!(strip_markdown("snake_case_name"), "snake_case_name");
+        assert_eq!(strip_markdown("a ~ b | c"), "a ~ b | c");
+        assert_eq!(strip_markdown("~~~a~~"), "~a");
+    }
+
+    #[test]
+    fn test_strip_markdown_code() {
+        assert_eq!(strip_markdown("`**code**` and **`bold code`**"), "**code** and bold code");
+        assert_eq!(strip_markdown("``a ` b``"), "a ` b");
+        assert_eq!(strip_markdown("```rust\nlet _x = 1;\n```"), "let _x = 1;");
+        assert_eq!(strip_markdown("```rust\n*unterminated*"), "```rust\nunterminated");
+        assert_eq!(strip_markdown(r"\`*a*\`"), "`a`");
+    }
+
+    #[test]
+    fn test_extract_codeblocks() {
+        assert_eq!(
+            extract_codeblocks("a\n```rust\nfn main() {}\n```\nb\n```\nplain\n\n```"),
+            vec![block(Some("rust"), "fn main() {}"), block(None, "plain")],
+        );
+        assert_eq!(extract_codeblocks("```c++\nint x;```"), vec![block(Some("c++"), "int x;")]);
+        assert_eq!(extract_codeblocks("```rust```"), vec![block(None, "rust")]);
+        assert_eq!(extract_codeblocks("```rust\n```"), vec![block(None, "rust")]);
+        assert_eq!(extract_codeblocks("```two words\nx```"), vec![block(None, "two words\nx")]);
+    }
+
+    #[test]
+    fn test_extract_codeblocks_backticks() {
+        assert_eq!(
+            extract_codeblocks("```\nlet s = \"`\" + \"``\";\n```"),
+            vec![block(None, "let s = \"`\" + \"``\";")],
+        );
+        assert_eq!(extract_codeblocks("`inline` and ``nested `inline` ``"), vec![]);
+        assert_eq!(extract_codeblocks("`` ``` ``"), vec![]);
+        assert_eq!(extract_codeblocks("`a ```b```"), vec![block(None, "b")]);
+        assert_eq!(extract_codeblocks("\\```rust\ncode\n```"), vec![]);
+    }
+
+    #[test]
+    fn test_extract_codeblocks_unterminated() {
+        assert_eq!(extract_codeblocks("```rust\nfn main() {}"), vec![]);
+        assert_eq!(extract_codeblocks("```a```\n```b"), vec![block(None, "a")]);
+        assert_eq!(extract_codeblocks("``````"), vec![]);
+        assert_eq!(extract_codeblocks(""), vec![]);
+    }
+
+    #[test]
+    fn test_pathological_inputs() {
+        let backticks = "`".repeat(1_000_000);
+        assert!(extract_codeblocks(&backticks).iter().all(|block| block.code == "`"));
+        assert_eq!(escape_markdown(&backticks, EscapeOptions::default()).len(), 2_000_000);
+        strip_markdown(&backticks);
+
+        let unterminated = format!("```{}", "a`".repeat(500_000));
+        assert!(extract_codeblocks(&unterminated).is_empty());
+
+        let runs = (1..1_400).map(|len| "`".repeat(len)).collect::<Vec<_>>().join(" ");
+        assert!(!extract_codeblocks(&runs).is_empty());
+        assert!(strip_markdown(&runs).len() < runs.len());
+
+        let openers = "*a".repeat(500_000);
+        assert_eq!(strip_markdown(&openers).len(), 500_000);
+        let unclosed = "a **".repeat(250_000);
+        assert_eq!(strip_markdown(&unclosed), unclosed);
+        let mixed = "*_~~||".repeat(200_000);
+        assert!(strip_markdown(&mixed).len() < mixed.len());
+
+        let brackets = "<".repeat(1_000_000);
+        assert_eq!(escape_markdown(&brackets, EscapeOptions::default()), brackets);
+        let mention_starts = "<@".repeat(500_000);
+        let options = EscapeOptions::new().escape_mentions(true).escape_links(true);
+        assert_eq!(escape_markdown(&mention_starts, options), mention_starts);
+        let links = "https://".repeat(100_000);
+        assert_eq!(escape_markdown(&links, options).len(), links.len() + 2);
+    }
+}
@@ -0,0 +1,291 @@
+use serde_json::Value;
+
+use crate::builder::{check_embed, embed_length, CreateEmbed, CreateMessage};
+use crate::constants::limits;
+#[cfg(feature = "http")]
+use crate::http::AttachmentType;
+use crate::internal::prelude::*;
+use crate::model::ModelError;
+
+/// The sizes of the parts of a message that Discord limits, as reported by
+/// [`check_message`].
+///
+/// Lengths are counted in unicode code points. The limits themselves are
+/// listed in [`constants::limits`].
+///
+/// [`constants::limits`]: crate::constants::limits
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MessageStats {
+    /// The length of the content.
+    pub content_length: usize,
+    /// The number of embeds.
+    pub embed_count: usize,
+    /// The length of the textual content of each embed, in order.
+    pub embed_lengths: Vec<usize>,
+    /// The combined length of the textual content of all embeds.
+    pub total_embed_length: usize,
+    /// The number of action rows of components.
+    pub component_rows: usize,
+    /// The number of attached files.
+    pub file_count: usize,
+    /// The size of each attached file in bytes, in order, if it is known
+    /// without reading the file.
+    ///
+    /// This is known for files given as bytes, and for paths that can be
+    /// inspected on disk.
+    pub file_sizes: Vec<Option<u64>>,
+    /// The number of stickers.
+    pub sticker_count: usize,
+}
+
+/// Counts the characters of the textual content of an embed, which Discord
+/// limits to [`limits::EMBED_TOTAL_LENGTH`] across all embeds of a message.
+///
+/// The title, description, author name, footer text, and field names and
+/// values are counted.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::builder::CreateEmbed;
+/// use serenity::utils::embed_len;
+///
+/// let mut embed = CreateEmbed::default();
+/// embed.title("Hello").field("Name", "Value", false);
+///
+/// assert_eq!(embed_len(&embed), 14);
+/// ```
+///
+/// [`limits::EMBED_TOTAL_LENGTH`]: crate::constants::limits::EMBED_TOTAL_LENGTH
+pub fn embed_len(embed: &CreateEmbed) -> usize {
+    embed_length(|key| embed.0.get(key))
+}
+
+/// Checks whether a message will be accepted by Discord, without sending it,
+/// and reports the sizes of its parts.
+///
+/// The embeds are checked the same way as by [`CreateEmbed::try_build`]. A
+/// [`SplitStrategy`] set on the message is not taken into account.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::builder::CreateMessage;
+/// use serenity::utils::check_message;
+///
+/// let mut message = CreateMessage::default();
+/// message.content("Hello!").embed(|e| e.title("Greetings"));
+///
+/// let stats = check_message(&message)?;
+/// assert_eq!(stats.content_length, 6);
+/// assert_eq!(stats.embed_lengths, [9]);
+/// # Ok::<(), serenity::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns a [`ModelError::MessageTooLong`] if the content is too long, a
+/// [`ModelError::EmbedAmount`] if there are too many embeds, and a
+/// [`ModelError::EmbedTooLarge`] if the embeds are too large altogether.
+///
+/// Returns a [`ModelError::ActionRowAmount`], [`ModelError::FileAmount`] or
+/// [`ModelError::StickerAmount`] if there are too many action rows, files or
+/// stickers.
+///
+/// Returns the errors of [`CreateEmbed::try_build`] if an embed exceeds one
+/// of its limits.
+///
+/// [`SplitStrategy`]: crate::builder::SplitStrategy
+/// [`ModelError::MessageTooLong`]: crate::model::error::Error::MessageTooLong
+/// [`ModelError::EmbedAmount`]: crate::model::error::Error::EmbedAmount
+/// [`ModelError::EmbedTooLarge`]: crate::model::error::Error::EmbedTooLarge
+/// [`ModelError::ActionRowAmount`]: crate::model::error::Error::ActionRowAmount
+/// [`ModelError::FileAmount`]: crate::model::error::Error::FileAmount
+/// [`ModelError::StickerAmount`]: crate::model::error::Error::StickerAmount
+pub fn check_message(message: &CreateMessage<'_>) -> Result<MessageStats> {
+    let map = &message.0;
+    let array_len = |key| map.get(key).and_then(Value::as_array).map_or(0, Vec::len);
+
+    let content_length =
+        map.get("content").and_then(Value::as_str).map_or(0, |content| content.chars().count());
+
+    if content_length > limits::MESSAGE_CONTENT_LENGTH {
+        let overflow = content_length - limits::MESSAGE_CONTENT_LENGTH;
+
+        return Err(Error::Model(ModelError::MessageTooLong(overflow)));
+    }
+
+    let embeds = map.get("embeds").and_then(Value::as_array).map_or(&[][..], Vec::as_slice);
+
+    if embeds.len() > limits::EMBED_COUNT {
+        return Err(Error::Model(ModelError::EmbedAmount));
+    }
+
+    let embed_lengths = embeds
+        .iter()
+        .filter_map(Value::as_object)
+        .map(|embed| check_embed(|key| embed.get(key)))
+        .collect::<Result<Vec<_>>>()?;
+    let total_embed_length = embed_lengths.iter().sum();
+
+    if total_embed_length > limits::EMBED_TOTAL_LENGTH {
+        let overflow = total_embed_length - limits::EMBED_TOTAL_LENGTH;
+
+        return Err(Error::Model(ModelError::EmbedTooLarge(overflow)));
+    }
+
+    let component_rows = array_len("components");
+
+    if component_rows > limits::ACTION_ROW_COUNT {
+        return Err(Error::Model(ModelError::ActionRowAmount));
+    }
+
+    #[cfg(feature = "http")]
+    let file_count = message.2.len();
+    #[cfg(not(feature = "http"))]
+    let file_count = 0;
+
+    if file_count > limits::FILE_COUNT {
+        return Err(Error::Model(ModelError::FileAmount));
+    }
+
+    let sticker_count = array_len("sticker_ids");
+
+    if sticker_count > limits::STICKER_COUNT {
+        return Err(Error::Model(ModelError::StickerAmount));
+    }
+
+    #[cfg(feature = "http")]
+    let file_sizes = message.2.iter().map(file_size).collect::<Vec<_>>();
+    #[cfg(not(feature = "http"))]
+    let file_sizes = Vec::new();
+
+    Ok(MessageStats {
+        content_length,
+        embed_count: embed_lengths.len(),
+        embed_lengths,
+        total_embed_length,
+        component_rows,
+        file_count,
+        file_sizes,
+        sticker_count,
+    })
+}
+
+/// Returns the size of a file in bytes, if it is known without reading it.
+#[cfg(feature = "http")]
+fn file_size(file: &AttachmentType<'_>) -> Option<u64> {
+    match file {
+        AttachmentType::Bytes {
+            data, ..
+        } => Some(data.len() as u64),
+        AttachmentType::Path(path) => std::fs::metadata(path).ok().map(|metadata| metadata.len()),
+        AttachmentType::File {
+            ..
+        }
+        | AttachmentType::Image(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{check_message, embed_len, MessageStats};
+    use crate::builder::{CreateEmbed, CreateMessage};
+    use crate::constants::limits;
+    use crate::model::ModelError;
+    use crate::Error;
+
+    #[test]
+    fn test_embed_len() {
+        let mut embed = CreateEmbed::default();
+        assert_eq!(embed_len(&embed), 0);
+
+        embed
+            .title("héllo")
+            .description("abc")
+            .author(|a| a.name("me").url("https://example.com"))
+            .footer(|f| f.text("foot"))
+            .field("a", "bc", true);
+        assert_eq!(embed_len(&embed), 5 + 3 + 2 + 4 + 3);
+    }
+
+    #[test]
+    fn test_check_message() {
+        let mut message = CreateMessage::default();
+        message.content("hi").add_embed(|e| e.title("one")).add_embed(|e| e.description("two!"));
+        #[cfg(feature = "http")]
+        message.add_file((&b"data"[..], "data.txt"));
+
+        let stats = check_message(&message).unwrap();
+        assert_eq!(
+            stats,
+            MessageStats {
+                content_length: 2,
+                embed_count: 2,
+                embed_lengths: vec![3, 4],
+                total_embed_length: 7,
+                component_rows: 0,
+                file_count: usize::from(cfg!(feature = "http")),
+                file_sizes: if cfg!(feature = "http") { vec![Some(4)] } else { vec![] },
+                sticker_count: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_message_limits() {
+        let mut message = CreateMessage::default();
+        message.content("a".repeat(limits::MESSAGE_CONTENT_LENGTH + 1));
+        assert!(matches!(
+            check_message(&message),
+            Err(Error::Model(ModelError::MessageTooLong(1)))
+        ));
+
+        let mut message = CreateMessage::default();
+        message.add_embeds(vec![CreateEmbed::default(); limits::EMBED_COUNT + 1]);
+        assert!(matches!(check_message(&message), Err(Error::Model(ModelError::EmbedAmount))));
+
+        let mut message = CreateMessage::default();
+        message.add_embed(|e| e.title("a".repeat(limits::EMBED_TITLE_LENGTH + 2)));
+        assert!(matches!(
+            check_message(&message),
+            Err(Error::Model(ModelError::EmbedPartTooLong {
+                part: "title",
+                overflow: 2,
+            }))
+        ));
+
+        // Each embed is within its limits, but not all of them together.
+        let description = "a".repeat(limits::EMBED_DESCRIPTION_LENGTH);
+        let mut message = CreateMessage::default();
+        message
+            .add_embed(|e| e.description(&description))
+            .add_embed(|e| e.description(&description));
+        let overflow = 2 * limits::EMBED_DESCRIPTION_LENGTH - limits::EMBED_TOTAL_LENGTH;
+        assert!(matches!(
+            check_message(&message),
+            Err(Error::Model(ModelError::EmbedTooLarge(o))) if o == overflow
+        ));
+
+        let mut message = CreateMessage::default();
+        let row = json!({"type": 1, "components": []});
+        message.0.insert("components", json!(vec![row; limits::ACTION_ROW_COUNT + 1]));
+        assert!(matches!(check_message(&message), Err(Error::Model(ModelError::ActionRowAmount))));
+
+        let mut message = CreateMessage::default();
+        message.0.insert("sticker_ids", json!(vec!["1"; limits::STICKER_COUNT + 1]));
+        assert!(matches!(check_message(&message), Err(Error::Model(ModelError::StickerAmount))));
+
+        #[cfg(feature = "http")]
+        {
+            let mut message = CreateMessage::default();
+            for _ in 0..=limits::FILE_COUNT {
+                message.add_file((&b"data"[..], "data.txt"));
+            }
+            assert!(matches!(check_message(&message), Err(Error::Model(ModelError::FileAmount))));
+        }
+    }
+}
@@ -5,16 +5,31 @@
 mod argument_convert;
 mod colour;
 mod custom_message;
+mod markdown;
 mod message_builder;
+#[cfg(feature = "builder")]
+mod message_check;
+#[cfg(all(feature = "collector", feature = "client", feature = "unstable_discord_api"))]
+mod quick_modal;
+#[cfg(all(feature = "collector", feature = "cache", feature = "http"))]
+mod reaction_roles;
 
 #[cfg(all(feature = "client", feature = "cache"))]
 pub use argument_convert::*;
+#[cfg(all(feature = "collector", feature = "client", feature = "unstable_discord_api"))]
+pub use quick_modal::{CreateQuickModal, QuickModalResponse};
+#[cfg(all(feature = "collector", feature = "cache", feature = "http"))]
+pub use reaction_roles::{ReactionRoleBinding, ReactionRoles};
 #[cfg(feature = "url")]
 use url::Url;
 
+#[cfg(feature = "builder")]
+pub use self::message_check::{check_message, embed_len, MessageStats};
+
 pub use self::{
     colour::{colours, Colour},
     custom_message::CustomMessage,
+    markdown::{escape_markdown, extract_codeblocks, strip_markdown, CodeBlock, EscapeOptions},
     message_builder::{Content, ContentModifier, EmbedMessageBuilding, MessageBuilder},
 };
 pub type Color = Colour;
@@ -683,15 +698,11 @@ async fn clean_users(
 
             if let Ok(id) = UserId::from_str(&s[mention_start..mention_end]) {
                 let replacement = if let Some(guild_id) = guild {
-                    if let Some(guild) = cache.guild(&guild_id).await {
-                        if let Some(member) = guild.members.get(&id) {
-                            if show_discriminator {
-                                format!("@{}", member.distinct())
-                            } else {
-                                format!("@{}", member.display_name())
-                            }
+                    if let Some(member) = cache.member(guild_id, id).await {
+                        if show_discriminator {
+                            format!("@{}", member.distinct())
                         } else {
-                            "@invalid-user".to_string()
+                            format!("@{}", member.display_name())
                         }
                     } else {
                         "@invalid-user".to_string()
@@ -858,6 +869,7 @@ mod test {
             bot: false,
             discriminator: 0000,
             name: "Crab".to_string(),
+            global_name: None,
             public_flags: None,
             banner: None,
             accent_colour: None,
@@ -971,13 +983,16 @@ mod test {
             thread_metadata: None,
             member: None,
             default_auto_archive_duration: None,
+            flags: ChannelFlags::default(),
+            default_forum_layout: None,
+            default_sort_order: None,
         };
 
         let cache = Arc::new(Cache::default());
 
         guild.members.insert(user.id, member.clone());
         guild.roles.insert(role.id, role.clone());
-        cache.users.write().await.insert(user.id, user.clone());
+        cache.insert_guild_members(&mut guild).await;
         cache.guilds.write().await.insert(guild.id, guild.clone());
         cache.channels.write().await.insert(channel.id, channel.clone());
 
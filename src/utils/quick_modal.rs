@@ -0,0 +1,290 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::builder::{CreateInputText, CreateInteractionResponse};
+use crate::client::Context;
+use crate::collector::CollectModalInteraction;
+use crate::internal::prelude::*;
+use crate::model::interactions::{check_components, check_response_kind};
+use crate::model::interactions::message_component::{ActionRowComponent, InputTextStyle};
+use crate::model::interactions::modal::{ModalSubmitInteraction, ModalSubmitInteractionData};
+use crate::model::prelude::*;
+use crate::utils;
+
+/// Sends a modal in response to an interaction and awaits its submission in
+/// one call.
+///
+/// Every field is a text input on its own row. The custom Ids of the modal and
+/// its fields are generated from the Id of the interaction being responded
+/// to, so they do not clash with those of other modals.
+///
+/// # Examples
+///
+/// Ask for a name and a description when a command is used:
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use serenity::async_trait;
+/// use serenity::model::prelude::*;
+/// use serenity::prelude::*;
+/// use serenity::utils::CreateQuickModal;
+///
+/// struct Handler;
+///
+/// #[async_trait]
+/// impl EventHandler for Handler {
+///     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+///         let modal = CreateQuickModal::new("About you")
+///             .short_field("Name")
+///             .paragraph_field("Description")
+///             .timeout(Duration::from_secs(600));
+///
+///         let response = match modal.execute(&ctx, &interaction).await {
+///             Ok(Some(response)) => response,
+///             Ok(None) => return,
+///             Err(why) => {
+///                 println!("Could not send the modal: {:?}", why);
+///                 return;
+///             },
+///         };
+///
+///         let (name, description) = (&response.inputs[0], &response.inputs[1]);
+///         let _ = response
+///             .interaction
+///             .create_interaction_response(&ctx, |r| {
+///                 r.interaction_response_data(|d| {
+///                     d.content(format!("**{}**: {}", name, description))
+///                 })
+///             })
+///             .await;
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct CreateQuickModal {
+    title: String,
+    fields: Vec<CreateInputText>,
+    timeout: Option<Duration>,
+}
+
+/// The values submitted to a [`CreateQuickModal`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct QuickModalResponse {
+    /// The modal submission, which still needs to be responded to.
+    pub interaction: Arc<ModalSubmitInteraction>,
+    /// The submitted value of every field, in the order they were added.
+    ///
+    /// Optional fields left empty are empty strings.
+    pub inputs: Vec<String>,
+}
+
+impl CreateQuickModal {
+    /// Creates a modal with the given title and no fields.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            fields: Vec::new(),
+            timeout: None,
+        }
+    }
+
+    /// Sets the title of the modal.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Adds a text input field.
+    ///
+    /// Its custom Id is replaced with a generated one.
+    pub fn field(mut self, input_text: CreateInputText) -> Self {
+        self.fields.push(input_text);
+        self
+    }
+
+    /// Adds a single-line text input field with the given label.
+    pub fn short_field(self, label: impl ToString) -> Self {
+        self.field_with_style(label, InputTextStyle::Short)
+    }
+
+    /// Adds a multi-line text input field with the given label.
+    pub fn paragraph_field(self, label: impl ToString) -> Self {
+        self.field_with_style(label, InputTextStyle::Paragraph)
+    }
+
+    fn field_with_style(self, label: impl ToString, style: InputTextStyle) -> Self {
+        let mut input_text = CreateInputText::default();
+        input_text.label(label).style(style);
+
+        self.field(input_text)
+    }
+
+    /// Sets how long to wait for the modal to be submitted.
+    ///
+    /// By default, the submission is awaited until the shard shuts down.
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.timeout = Some(duration);
+        self
+    }
+
+    /// Responds to the interaction with the modal and awaits its submission by
+    /// the user who triggered the interaction.
+    ///
+    /// Returns [`None`] if the modal was not submitted before the timeout.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::InvalidInteractionResponseType`] if the
+    /// interaction cannot be responded to with a modal, such as a modal
+    /// submission, or a [`ModelError::InvalidComponents`] if a field is
+    /// invalid, such as with a label longer than 45 characters.
+    ///
+    /// May also return an [`Error::Http`] if the response could not be sent,
+    /// such as when the interaction was already responded to.
+    ///
+    /// [`Error::Http`]: crate::error::Error::Http
+    pub async fn execute(
+        self,
+        ctx: &Context,
+        interaction: &Interaction,
+    ) -> Result<Option<QuickModalResponse>> {
+        let custom_id = format!("quick_modal-{}", interaction.id());
+        let field_count = self.fields.len();
+        let timeout = self.timeout;
+        let map = utils::hashmap_to_json_map(self.build(&custom_id).0);
+
+        check_response_kind(&map, interaction.kind())?;
+        check_components(&map)?;
+
+        ctx.http
+            .create_interaction_response(
+                interaction.id().0,
+                interaction.token(),
+                &Value::Object(map),
+            )
+            .await?;
+
+        let mut collector = CollectModalInteraction::new(ctx).custom_id(custom_id);
+
+        if let Some(user_id) = user_id(interaction) {
+            collector = collector.author_id(user_id);
+        }
+
+        if let Some(timeout) = timeout {
+            collector = collector.timeout(timeout);
+        }
+
+        Ok(collector.await.map(|interaction| {
+            let inputs = inputs(&interaction.data, field_count);
+
+            QuickModalResponse {
+                interaction,
+                inputs,
+            }
+        }))
+    }
+
+    fn build(self, custom_id: &str) -> CreateInteractionResponse {
+        let Self {
+            title,
+            fields,
+            ..
+        } = self;
+
+        let mut response = CreateInteractionResponse::default();
+        response.kind(InteractionResponseType::Modal).interaction_response_data(|d| {
+            d.custom_id(custom_id).title(title).components(|c| {
+                for (index, mut input_text) in fields.into_iter().enumerate() {
+                    input_text.custom_id(index);
+                    c.create_action_row(|row| row.add_input_text(input_text));
+                }
+
+                c
+            })
+        });
+
+        response
+    }
+}
+
+/// The user who triggered an interaction which can be responded to with a
+/// modal.
+fn user_id(interaction: &Interaction) -> Option<UserId> {
+    match interaction {
+        Interaction::ApplicationCommand(i) => Some(i.user.id),
+        Interaction::MessageComponent(i) => Some(i.user.id),
+        _ => None,
+    }
+}
+
+/// Orders the submitted values by the index in their custom Id, as given by
+/// [`CreateQuickModal::build`].
+fn inputs(data: &ModalSubmitInteractionData, field_count: usize) -> Vec<String> {
+    let mut inputs = vec![String::new(); field_count];
+
+    let input_texts = data.components.iter().flat_map(|row| &row.components);
+    for component in input_texts {
+        if let ActionRowComponent::InputText(input_text) = component {
+            if let Some(input) =
+                input_text.custom_id.parse::<usize>().ok().and_then(|index| inputs.get_mut(index))
+            {
+                input.clone_from(&input_text.value);
+            }
+        }
+    }
+
+    inputs
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::{inputs, CreateQuickModal};
+    use crate::model::interactions::modal::ModalSubmitInteractionData;
+    use crate::utils;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_build() {
+        let response = CreateQuickModal::new("Title")
+            .short_field("Name")
+            .paragraph_field("Description")
+            .build("quick_modal-1");
+        let value = serde_json::Value::Object(utils::hashmap_to_json_map(response.0));
+
+        assert_eq!(value["type"], 9);
+        assert_eq!(value["data"]["custom_id"], "quick_modal-1");
+        assert_eq!(value["data"]["title"], "Title");
+
+        let rows = value["data"]["components"].as_array().unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["components"][0]["custom_id"], "0");
+        assert_eq!(rows[0]["components"][0]["label"], "Name");
+        assert_eq!(rows[0]["components"][0]["style"], 1);
+        assert_eq!(rows[1]["components"][0]["custom_id"], "1");
+        assert_eq!(rows[1]["components"][0]["label"], "Description");
+        assert_eq!(rows[1]["components"][0]["style"], 2);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_inputs_in_field_order() {
+        let input_text = |custom_id: &str, value: &str| {
+            json!({
+                "type": 1,
+                "components": [{"type": 4, "custom_id": custom_id, "value": value}],
+            })
+        };
+        let data: ModalSubmitInteractionData = serde_json::from_value(json!({
+            "custom_id": "quick_modal-1",
+            "components": [input_text("1", "second"), input_text("0", "first")],
+        }))
+        .unwrap();
+
+        assert_eq!(inputs(&data, 3), vec!["first", "second", ""]);
+    }
+}
@@ -0,0 +1,599 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use futures::StreamExt;
+use tracing::warn;
+
+use crate::client::bridge::gateway::ShardMessenger;
+use crate::collector::{ReactionAction, ReactionCollectorBuilder};
+use crate::http::CacheHttp;
+use crate::internal::prelude::*;
+use crate::model::prelude::*;
+
+type ErrorCallback = Arc<dyn Fn(&Reaction, Error) + Send + Sync>;
+
+/// A binding granting a role to users reacting with an emoji on a message.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ReactionRoleBinding {
+    /// The Id of the message the reaction must be added to.
+    pub message_id: MessageId,
+    /// The emoji the reaction must use.
+    pub emoji: ReactionType,
+    /// The role given to users who react.
+    pub role_id: RoleId,
+}
+
+impl ReactionRoleBinding {
+    /// Whether the reaction was added to the bound message using the bound
+    /// emoji.
+    ///
+    /// Custom emojis are compared by their Id only, as the name of an emoji
+    /// may change after the binding was created.
+    fn matches(&self, reaction: &Reaction) -> bool {
+        if self.message_id != reaction.message_id {
+            return false;
+        }
+
+        match (&self.emoji, &reaction.emoji) {
+            (
+                ReactionType::Custom {
+                    id, ..
+                },
+                ReactionType::Custom {
+                    id: other_id, ..
+                },
+            ) => id == other_id,
+            (emoji, other) => emoji == other,
+        }
+    }
+}
+
+/// Gives and takes roles from guild members reacting to messages.
+///
+/// The manager is configured with a set of [`ReactionRoleBinding`]s. Whenever
+/// a user adds a bound reaction, they are given its role. By default, the role
+/// is taken away again when the reaction is removed.
+///
+/// Reactions need to be passed to [`Self::handle_reaction_add`] and
+/// [`Self::handle_reaction_remove`] from the [`EventHandler`]. Alternatively,
+/// [`Self::run`] collects the reactions on the bound messages itself.
+///
+/// Errors while updating roles, e.g. because the current user lacks the
+/// [Manage Roles] permission or a role was deleted, are passed to the
+/// callback set via [`Self::on_error`], or logged otherwise.
+///
+/// **Note**: Requires the [`GatewayIntents::GUILD_MESSAGE_REACTIONS`] intent.
+///
+/// # Examples
+///
+/// Give roles for reactions to a message from within an [`EventHandler`]:
+///
+/// ```rust,no_run
+/// use serenity::async_trait;
+/// use serenity::model::prelude::*;
+/// use serenity::prelude::*;
+/// use serenity::utils::ReactionRoles;
+///
+/// struct Handler {
+///     reaction_roles: ReactionRoles,
+/// }
+///
+/// #[async_trait]
+/// impl EventHandler for Handler {
+///     async fn reaction_add(&self, ctx: Context, reaction: Reaction) {
+///         self.reaction_roles.handle_reaction_add(&ctx, &reaction).await;
+///     }
+///
+///     async fn reaction_remove(&self, ctx: Context, reaction: Reaction) {
+///         self.reaction_roles.handle_reaction_remove(&ctx, &reaction).await;
+///     }
+/// }
+///
+/// let reaction_roles = ReactionRoles::new()
+///     .bind(MessageId(7), '🔴', RoleId(8))
+///     .bind(MessageId(7), '🔵', RoleId(9))
+///     .exclusive(vec![RoleId(8), RoleId(9)])
+///     .on_error(|reaction, why| {
+///         println!("Could not update roles for {:?}: {:?}", reaction.user_id, why);
+///     });
+///
+/// let handler = Handler {
+///     reaction_roles,
+/// };
+/// ```
+///
+/// [`EventHandler`]: crate::client::EventHandler
+/// [`GatewayIntents::GUILD_MESSAGE_REACTIONS`]: crate::client::bridge::gateway::GatewayIntents::GUILD_MESSAGE_REACTIONS
+/// [Manage Roles]: Permissions::MANAGE_ROLES
+#[derive(Clone)]
+pub struct ReactionRoles {
+    bindings: Vec<ReactionRoleBinding>,
+    exclusive_groups: Vec<HashSet<RoleId>>,
+    remove_on_unreact: bool,
+    ignore_bots: bool,
+    on_error: Option<ErrorCallback>,
+}
+
+impl ReactionRoles {
+    /// Creates a manager without any bindings, which removes roles once the
+    /// reaction is removed and ignores reactions of bots.
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+            exclusive_groups: Vec::new(),
+            remove_on_unreact: true,
+            ignore_bots: true,
+            on_error: None,
+        }
+    }
+
+    /// Gives the role to users reacting with the emoji on the message.
+    pub fn bind(
+        mut self,
+        message_id: impl Into<MessageId>,
+        emoji: impl Into<ReactionType>,
+        role_id: impl Into<RoleId>,
+    ) -> Self {
+        self.bindings.push(ReactionRoleBinding {
+            message_id: message_id.into(),
+            emoji: emoji.into(),
+            role_id: role_id.into(),
+        });
+
+        self
+    }
+
+    /// Makes the roles mutually exclusive: when a user is given one of them
+    /// through a reaction, the others are taken away.
+    ///
+    /// **Note**: The user's reactions for the roles taken away are not
+    /// removed.
+    pub fn exclusive<It>(mut self, role_ids: It) -> Self
+    where
+        It: IntoIterator,
+        It::Item: Into<RoleId>,
+    {
+        self.exclusive_groups.push(role_ids.into_iter().map(Into::into).collect());
+
+        self
+    }
+
+    /// Whether to take the role away when a user removes their reaction.
+    ///
+    /// Defaults to `true`.
+    pub fn remove_on_unreact(mut self, remove_on_unreact: bool) -> Self {
+        self.remove_on_unreact = remove_on_unreact;
+
+        self
+    }
+
+    /// Whether to ignore reactions of bot users.
+    ///
+    /// Defaults to `true`. The current user's own reactions are always
+    /// ignored.
+    pub fn ignore_bots(mut self, ignore_bots: bool) -> Self {
+        self.ignore_bots = ignore_bots;
+
+        self
+    }
+
+    /// Sets the callback receiving errors which occurred while updating a
+    /// member's roles, along with the reaction which caused the update.
+    ///
+    /// Without a callback, errors are logged.
+    pub fn on_error<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Reaction, Error) + Send + Sync + 'static,
+    {
+        self.on_error = Some(Arc::new(callback));
+
+        self
+    }
+
+    /// The configured bindings.
+    pub fn bindings(&self) -> &[ReactionRoleBinding] {
+        &self.bindings
+    }
+
+    /// Gives the bound role to the user who added the reaction, taking away
+    /// the other roles of its exclusive groups.
+    ///
+    /// Returns whether the reaction matched a binding and was not ignored.
+    pub async fn handle_reaction_add(
+        &self,
+        cache_http: impl CacheHttp,
+        reaction: &Reaction,
+    ) -> bool {
+        let (guild_id, user_id, role_id) = match self.target(&cache_http, reaction).await {
+            Some(target) => target,
+            None => return false,
+        };
+
+        if let Err(why) = self.add_role(&cache_http, guild_id, user_id, role_id).await {
+            self.error(reaction, why);
+
+            return true;
+        }
+
+        let held_roles = match cache_http.cache() {
+            Some(cache) => {
+                cache.member_field(guild_id, user_id, |member| member.roles.clone()).await
+            },
+            None => None,
+        };
+
+        let exclusive_roles = self
+            .exclusive_groups
+            .iter()
+            .filter(|group| group.contains(&role_id))
+            .flatten()
+            .filter(|&&other| other != role_id)
+            .collect::<HashSet<_>>();
+
+        for &other in exclusive_roles {
+            if held_roles.as_ref().map_or(true, |roles| roles.contains(&other)) {
+                if let Err(why) =
+                    cache_http.http().remove_member_role(guild_id.0, user_id.0, other.0).await
+                {
+                    self.error(reaction, why);
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Takes the bound role away from the user who removed the reaction, if
+    /// [`Self::remove_on_unreact`] is enabled.
+    ///
+    /// Returns whether the reaction matched a binding and was not ignored.
+    pub async fn handle_reaction_remove(
+        &self,
+        cache_http: impl CacheHttp,
+        reaction: &Reaction,
+    ) -> bool {
+        if !self.remove_on_unreact {
+            return false;
+        }
+
+        let (guild_id, user_id, role_id) = match self.target(&cache_http, reaction).await {
+            Some(target) => target,
+            None => return false,
+        };
+
+        if let Err(why) =
+            cache_http.http().remove_member_role(guild_id.0, user_id.0, role_id.0).await
+        {
+            self.error(reaction, why);
+        }
+
+        true
+    }
+
+    /// Collects the reactions added to and removed from the bound messages,
+    /// handling them until the shard shuts down.
+    ///
+    /// This can be used instead of calling [`Self::handle_reaction_add`] and
+    /// [`Self::handle_reaction_remove`] from the [`EventHandler`]. Bindings
+    /// are read once, when the collector is created.
+    ///
+    /// [`EventHandler`]: crate::client::EventHandler
+    pub async fn run(
+        &self,
+        shard_messenger: impl AsRef<ShardMessenger>,
+        cache_http: impl CacheHttp,
+    ) {
+        let message_ids =
+            self.bindings.iter().map(|binding| binding.message_id).collect::<HashSet<_>>();
+
+        let mut collector = ReactionCollectorBuilder::new(shard_messenger)
+            .added(true)
+            .removed(true)
+            .filter(move |reaction| message_ids.contains(&reaction.message_id))
+            .await;
+
+        while let Some(action) = collector.next().await {
+            match action.as_ref() {
+                ReactionAction::Added(reaction) => {
+                    self.handle_reaction_add(&cache_http, reaction).await;
+                },
+                ReactionAction::Removed(reaction) => {
+                    self.handle_reaction_remove(&cache_http, reaction).await;
+                },
+            }
+        }
+    }
+
+    /// Returns the guild, user and role to update for the reaction, or [`None`]
+    /// if it matches no binding or is ignored.
+    async fn target(
+        &self,
+        cache_http: impl CacheHttp,
+        reaction: &Reaction,
+    ) -> Option<(GuildId, UserId, RoleId)> {
+        let guild_id = reaction.guild_id?;
+        let user_id = reaction.user_id?;
+        let binding = self.bindings.iter().find(|binding| binding.matches(reaction))?;
+
+        if let Some(cache) = cache_http.cache() {
+            if cache.current_user_id().await == user_id {
+                return None;
+            }
+        }
+
+        if self.ignore_bots && self.is_bot(&cache_http, reaction, user_id).await {
+            return None;
+        }
+
+        Some((guild_id, user_id, binding.role_id))
+    }
+
+    async fn is_bot(
+        &self,
+        cache_http: impl CacheHttp,
+        reaction: &Reaction,
+        user_id: UserId,
+    ) -> bool {
+        if let Some(user) = reaction.member.as_ref().and_then(|member| member.user.as_ref()) {
+            return user.bot;
+        }
+
+        match cache_http.cache() {
+            Some(cache) => cache.user(user_id).await.map_or(false, |user| user.bot),
+            None => false,
+        }
+    }
+
+    /// Gives the role, checking that it exists and that the current user may
+    /// manage roles first, if the guild is cached.
+    async fn add_role(
+        &self,
+        cache_http: impl CacheHttp,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+    ) -> Result<()> {
+        if let Some(cache) = cache_http.cache() {
+            let current_user_id = cache.current_user_id().await;
+            let current_member = cache.member(guild_id, current_user_id).await;
+
+            let checked = cache
+                .guild_field(guild_id, |guild| {
+                    let permissions = current_member
+                        .as_ref()
+                        .map(|member| guild._member_permission_from_member(member));
+
+                    (guild.roles.contains_key(&role_id), permissions)
+                })
+                .await;
+
+            if let Some((role_exists, permissions)) = checked {
+                if !role_exists {
+                    return Err(Error::Model(ModelError::RoleNotFound));
+                }
+
+                if let Some(permissions) = permissions {
+                    if !permissions.manage_roles() {
+                        return Err(Error::Model(ModelError::InvalidPermissions(
+                            Permissions::MANAGE_ROLES,
+                        )));
+                    }
+                }
+            }
+        }
+
+        cache_http.http().add_member_role(guild_id.0, user_id.0, role_id.0).await
+    }
+
+    fn error(&self, reaction: &Reaction, why: Error) {
+        match &self.on_error {
+            Some(callback) => callback(reaction, why),
+            None => warn!("Failed to update roles for reaction {:?}: {:?}", reaction, why),
+        }
+    }
+}
+
+impl Default for ReactionRoles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ReactionRoles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReactionRoles")
+            .field("bindings", &self.bindings)
+            .field("exclusive_groups", &self.exclusive_groups)
+            .field("remove_on_unreact", &self.remove_on_unreact)
+            .field("ignore_bots", &self.ignore_bots)
+            .field("on_error", &self.on_error.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::json;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::ReactionRoles;
+    use crate::cache::Cache;
+    use crate::http::{Http, HttpBuilder};
+    use crate::model::prelude::*;
+
+    /// A role for which the mocked API responds with "Missing Permissions".
+    const FORBIDDEN_ROLE: u64 = 66;
+
+    /// Starts a server standing in for the Discord API, recording the method
+    /// and path of every request.
+    #[allow(clippy::unwrap_used)]
+    async fn mock_http() -> (Http, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = Arc::clone(&requests);
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut head = Vec::new();
+                let mut buf = [0; 1024];
+
+                while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+                    match socket.read(&mut buf).await.unwrap() {
+                        0 => break,
+                        n => head.extend_from_slice(&buf[..n]),
+                    }
+                }
+
+                let head = String::from_utf8_lossy(&head);
+                let line = head.lines().next().unwrap_or_default();
+                let request = line.rsplitn(2, ' ').nth(1).unwrap_or_default().to_string();
+
+                let response = if request.ends_with(&format!("/roles/{}", FORBIDDEN_ROLE)) {
+                    let body = r#"{"code":50013,"message":"Missing Permissions"}"#;
+                    format!(
+                        "HTTP/1.1 403 Forbidden\r\ncontent-type: application/json\r\n\
+                         content-length: {}\r\nconnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                } else {
+                    "HTTP/1.1 204 No Content\r\nconnection: close\r\n\r\n".to_string()
+                };
+
+                recorded.lock().unwrap().push(request);
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let builder = HttpBuilder::new("token");
+        #[cfg(feature = "unstable_discord_api")]
+        let builder = builder.application_id(1);
+
+        let http = builder
+            .proxy(format!("http://{}", addr))
+            .unwrap()
+            .ratelimiter_disabled(true)
+            .await
+            .unwrap();
+
+        (http, requests)
+    }
+
+    #[allow(clippy::unwrap_used)]
+    fn reaction(user_id: u64, emoji: &str, bot: bool) -> Reaction {
+        serde_json::from_value(json!({
+            "channel_id": "2",
+            "message_id": "3",
+            "guild_id": "1",
+            "user_id": user_id.to_string(),
+            "emoji": {"id": null, "name": emoji},
+            "member": {
+                "deaf": false,
+                "mute": false,
+                "roles": [],
+                "user": {
+                    "id": user_id.to_string(),
+                    "username": "user",
+                    "discriminator": "0001",
+                    "avatar": null,
+                    "bot": bot,
+                },
+            },
+        }))
+        .unwrap()
+    }
+
+    fn reaction_roles() -> ReactionRoles {
+        ReactionRoles::new()
+            .bind(MessageId(3), '🔴', RoleId(10))
+            .bind(MessageId(3), '🔵', RoleId(11))
+            .bind(MessageId(3), '🟢', RoleId(12))
+            .bind(MessageId(3), '⛔', RoleId(FORBIDDEN_ROLE))
+            .exclusive(vec![RoleId(10), RoleId(11)])
+    }
+
+    #[allow(clippy::unwrap_used)]
+    fn take(requests: &Mutex<Vec<String>>) -> Vec<String> {
+        let mut requests = std::mem::take(&mut *requests.lock().unwrap());
+        requests.sort();
+
+        requests
+    }
+
+    #[tokio::test]
+    async fn test_bound_reactions() {
+        let (http, requests) = mock_http().await;
+        let reaction_roles = reaction_roles();
+
+        assert!(reaction_roles.handle_reaction_add(&http, &reaction(5, "🟢", false)).await);
+        assert_eq!(take(&requests), ["PUT /api/v9/guilds/1/members/5/roles/12"]);
+
+        assert!(reaction_roles.handle_reaction_remove(&http, &reaction(5, "🟢", false)).await);
+        assert_eq!(take(&requests), ["DELETE /api/v9/guilds/1/members/5/roles/12"]);
+
+        assert!(!reaction_roles.handle_reaction_add(&http, &reaction(5, "🟡", false)).await);
+        assert!(take(&requests).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_exclusive_roles() {
+        let (http, requests) = mock_http().await;
+        let reaction_roles = reaction_roles();
+
+        assert!(reaction_roles.handle_reaction_add(&http, &reaction(5, "🔴", false)).await);
+        assert_eq!(
+            take(&requests),
+            [
+                "DELETE /api/v9/guilds/1/members/5/roles/11",
+                "PUT /api/v9/guilds/1/members/5/roles/10",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ignored_reactions() {
+        let (http, requests) = mock_http().await;
+
+        let cache = Arc::new(Cache::new());
+        cache.user.write().await.id = UserId(9);
+
+        let reaction_roles = reaction_roles();
+        assert!(!reaction_roles.handle_reaction_add(&http, &reaction(5, "🟢", true)).await);
+        assert!(
+            !reaction_roles.handle_reaction_add((&cache, &http), &reaction(9, "🟢", false)).await
+        );
+
+        let reaction_roles = reaction_roles.ignore_bots(false).remove_on_unreact(false);
+        assert!(!reaction_roles.handle_reaction_remove(&http, &reaction(5, "🟢", false)).await);
+        assert!(take(&requests).is_empty());
+
+        assert!(reaction_roles.handle_reaction_add(&http, &reaction(5, "🟢", true)).await);
+        assert_eq!(take(&requests), ["PUT /api/v9/guilds/1/members/5/roles/12"]);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_errors_reach_callback() {
+        let (http, requests) = mock_http().await;
+        let errors = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = Arc::clone(&errors);
+        let reaction_roles = reaction_roles().on_error(move |reaction, why| {
+            recorded.lock().unwrap().push((reaction.user_id, why.to_string()));
+        });
+
+        assert!(reaction_roles.handle_reaction_add(&http, &reaction(5, "⛔", false)).await);
+        assert_eq!(take(&requests), ["PUT /api/v9/guilds/1/members/5/roles/66"]);
+
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, Some(UserId(5)));
+        assert!(errors[0].1.contains("Missing Permissions"), "{}", errors[0].1);
+    }
+}
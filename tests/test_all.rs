@@ -0,0 +1,33 @@
+//! Checks that glob-importing `serenity::all` compiles and that the names most
+//! likely to collide resolve, under whichever features the tests are run with.
+
+#![allow(dead_code)]
+
+use serenity::all::*;
+
+fn error_types(_: SerenityError, _: Result<()>) {}
+
+fn model_types(_: Message, _: GuildChannel, _: UserId, _: Permissions) {}
+
+#[cfg(feature = "model")]
+fn model_error(_: ModelError) {}
+
+#[cfg(feature = "builder")]
+fn builders(_: CreateMessage<'_>, _: CreateEmbed, _: EmbedTimestamp) {}
+
+#[cfg(all(feature = "builder", feature = "unstable_discord_api"))]
+fn interaction_builders(_: CreateComponents, _: CreateInteractionResponse) {}
+
+#[cfg(feature = "http")]
+fn http(_: &Http, _: HttpError) {}
+
+#[cfg(feature = "gateway")]
+fn gateway(_: GatewayIntents, _: &dyn EventHandler, _: GatewayError) {}
+
+#[cfg(feature = "collector")]
+fn collectors(_: MessageCollectorBuilder<'_>, _: ReactionAction, _: CollectorError) {}
+
+#[test]
+fn mentionable_is_in_scope() {
+    assert_eq!(UserId(7).mention().to_string(), "<@7>");
+}